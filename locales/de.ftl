@@ -0,0 +1,201 @@
+# German
+
+language-name = Deutsch
+
+## Overlay
+
+# Links / rechts
+click-letter-left = L
+click-letter-right = R
+
+## Settings window
+
+settings = Einstellungen
+language = Sprache
+system-default = Systemstandard
+radius-px = Radius (px)
+border-px = Rand (px)
+color = Farbe
+choose = Auswählen...
+hex = Hex
+hex-color = Hex-Farbe
+hue = Farbton (°)
+saturation = Sättigung (%)
+brightness = Helligkeit (%)
+palette = Palette
+save-color = Farbe speichern
+remove = Entfernen
+rainbow-colors = Regenbogenfarben
+rainbow-speed-cycles-min = Regenbogen-Tempo (Zyklen/min)
+fill-transparency = Füllungstransparenz (%)
+style = Stil
+circle = Kreis
+laser-pointer = Laserpointer
+recording-frame = Aufnahmerahmen
+frame-color = Rahmenfarbe
+speed-fade = Bei Tempo ausblenden
+speed-size = Bei Tempo vergrößern
+left-click-letter = Buchstabe für Linksklick
+right-click-letter = Buchstabe für Rechtsklick
+letter-font = Schrift der Buchstaben
+letter-weight = Schriftstärke der Buchstaben
+light = Dünn
+regular = Normal
+medium = Mittel
+bold = Fett
+black = Extrafett
+click-icon = Klicksymbol
+letters = Buchstaben
+tap = Tippen
+mouse = Maus
+star = Stern
+click-hold-ms = Klick halten (ms)
+hold-ring = Halte-Ring
+hold-ring-ms = Halte-Ring (ms)
+pulse = Pulsieren
+pulse-amplitude = Pulsstärke (%)
+pulse-period-ms = Pulsdauer (ms)
+click-counter = Klickzähler
+click-interval-ms = Klickintervall (ms)
+idle-auto-hide = Im Leerlauf ausblenden
+idle-delay-s = Leerlaufverzögerung (s)
+motion = Bewegung
+follow-system = Wie System
+reduced = Reduziert
+full = Vollständig
+show-hide-animation-ms = Ein-/Ausblendanimation (ms)
+outline-halo = Umrisshalo
+high-contrast = Hoher Kontrast
+glow = Leuchten
+none = Keins
+soft-glow = Weiches Leuchten
+drop-shadow = Schlagschatten
+grid-color = Rasterfarbe
+grid-spacing-px = Rasterabstand (px)
+grid-subdivisions = Rasterunterteilungen
+confirm-before-quitting = Vor dem Beenden nachfragen
+notify-on-toggle = Beim Umschalten benachrichtigen
+hide-from-screen-capture = Bei Bildschirmaufnahmen ausblenden
+run-at-windows-startup = Beim Windows-Start ausführen
+sync-folder = Sync-Ordner
+off = Aus
+stop-syncing = Synchronisierung beenden
+choose-sync-folder = Sync-Ordner wählen
+press-keys = Tasten drücken...
+reset = Zurücksetzen
+hotkey-taken = Bereits belegt von { $action }
+close = Schließen
+some-settings-are-managed-by-your-administrator = Einige Einstellungen werden von Ihrem Administrator verwaltet.
+
+## Quit dialog
+
+quit = Beenden
+cancel = Abbrechen
+quit-the-app = App beenden?
+the-app-will-close = Die App wird geschlossen
+
+## Help overlay and menus
+
+toggle = Ein/Aus
+help = Hilfe
+keyboard-shortcuts = Tastenkürzel
+toggle-overlay = Hervorhebung ein/aus
+highlight-shown = Hervorhebung sichtbar
+highlight-hidden = Hervorhebung ausgeblendet
+open-settings = Einstellungen öffnen
+show-help = Hilfe anzeigen
+zoom-in = Vergrößern
+zoom-out = Verkleinern
+cursor-coordinates = Cursor-Koordinaten
+color-sampler = Farbpipette
+ruler = Lineal
+grid = Raster
+lock-settings = Einstellungen sperren
+settings-locked = Einstellungen gesperrt
+radius = Radius
+profile = Profil
+unavailable = nicht verfügbar
+hotkeys = Tastenkürzel
+standard = Standard
+presentation-f13-f20 = Präsentation (F13–F20)
+record-clicks = Klicks aufzeichnen
+export-clicks-csv = Klicks exportieren (CSV)...
+export-clicks-json = Klicks exportieren (JSON)...
+export-click-log = Klickprotokoll exportieren
+quit-app = App beenden
+quit-application = Anwendung beenden
+about = Über...
+restart = Neu starten
+press-ok-to-close = Zum Schließen OK drücken
+press-any-key-to-close = Zum Schließen eine Taste drücken
+
+## Settings file problems
+
+settings-file-problems = Probleme in der Einstellungsdatei
+settings-file-problems-detail = Einige Einstellungen in { $path } konnten nicht verwendet werden:
+
+## Crash report
+
+lumbus-crashed = Lumbus ist abgestürzt
+crash-report-detail = Ein Absturzbericht wurde unter { $path } gespeichert. Bitte beim Melden des Problems anhängen.
+show-report = Bericht anzeigen
+show-the-report-now = Bericht jetzt anzeigen?
+
+## Update check
+
+check-for-updates = Nach Updates suchen
+check-for-updates-automatically = Automatisch nach Updates suchen
+update-available = Update verfügbar: { $version }
+update-available-detail = Lumbus { $version } ist verfügbar (installiert ist { $current }).
+download = Herunterladen
+download-it-now = Jetzt herunterladen?
+lumbus-is-up-to-date = Lumbus ist auf dem neuesten Stand
+up-to-date-detail = Du hast die neueste Version ({ $version }).
+could-not-check-for-updates = Suche nach Updates fehlgeschlagen
+
+## About window
+
+about-lumbus = Über Lumbus
+mouse-pointer-highlighter-for-presentations-and-screen-recordings = Mauszeiger-Hervorhebung für Präsentationen und Bildschirmaufnahmen.
+version-label = Version { $version }
+website = Website
+report-an-issue = Problem melden
+license = Lizenz
+
+## First-run walkthrough
+
+welcome-to-lumbus = Willkommen bei Lumbus
+onboarding-welcome = Lumbus hebt den Mauszeiger hervor, damit Ihr Publikum ihm in Präsentationen und Bildschirmaufnahmen folgen kann. Diese kurze Tour zeigt die Tastenkürzel und lässt Sie die Hervorhebung einrichten.
+onboarding-hotkeys = Diese Tastenkürzel funktionieren in jeder App:
+onboarding-hotkey-profiles = Weitere Tastenkürzel-Sätze finden Sie im Menü „Tastenkürzel“ des Lumbus-Symbols.
+accessibility-permission = Bedienungshilfen-Berechtigung
+onboarding-permission = Damit Lumbus Ihre Klicks auch über anderen Apps anzeigen kann, braucht es die Berechtigung für Bedienungshilfen. macOS bittet Sie, Lumbus in den Systemeinstellungen unter Datenschutz & Sicherheit, Bedienungshilfen einzuschalten.
+grant-access = Zugriff erlauben...
+permission-granted = Berechtigung erteilt
+onboarding-permission-later = Sie können diesen Schritt überspringen. Lumbus fragt beim nächsten Start erneut.
+size-and-color = Größe und Farbe
+onboarding-appearance = Wählen Sie, wie die Hervorhebung aussieht. Sie können das jederzeit in den Einstellungen ändern.
+onboarding-step = Schritt { $step } von { $total }
+next = Weiter
+back = Zurück
+skip = Überspringen
+done = Fertig
+
+## Permissions
+
+permissions = Berechtigungen
+accessibility = Bedienungshilfen
+screen-recording = Bildschirmaufnahme
+click-letters = Klickbuchstaben
+zoom-and-color-sampler = Zoom und Farbpipette
+granted = Erteilt
+permission-missing = Nicht erteilt: { $feature } aus
+open-system-settings = Systemeinstellungen öffnen
+re-check = Erneut prüfen
+
+## Settings history
+
+undo = Rückgängig
+redo = Wiederholen
+revert-to-previous = Vorherige wiederherstellen
+restore-defaults = Standardwerte wiederherstellen
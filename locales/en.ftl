@@ -0,0 +1,201 @@
+# English (default and fallback language)
+
+language-name = English
+
+## Overlay
+
+# Letter drawn for a left / right click
+click-letter-left = L
+click-letter-right = R
+
+## Settings window
+
+settings = Settings
+language = Language
+system-default = System default
+radius-px = Radius (px)
+border-px = Border (px)
+color = Color
+choose = Choose...
+hex = Hex
+hex-color = Hex color
+hue = Hue (°)
+saturation = Saturation (%)
+brightness = Brightness (%)
+palette = Palette
+save-color = Save color
+remove = Remove
+rainbow-colors = Rainbow colors
+rainbow-speed-cycles-min = Rainbow speed (cycles/min)
+fill-transparency = Fill Transparency (%)
+style = Style
+circle = Circle
+laser-pointer = Laser pointer
+recording-frame = Recording frame
+frame-color = Frame color
+speed-fade = Speed fade
+speed-size = Speed size
+left-click-letter = Left click letter
+right-click-letter = Right click letter
+letter-font = Letter font
+letter-weight = Letter weight
+light = Light
+regular = Regular
+medium = Medium
+bold = Bold
+black = Black
+click-icon = Click icon
+letters = Letters
+tap = Tap
+mouse = Mouse
+star = Star
+click-hold-ms = Click hold (ms)
+hold-ring = Hold ring
+hold-ring-ms = Hold ring (ms)
+pulse = Pulse
+pulse-amplitude = Pulse amplitude (%)
+pulse-period-ms = Pulse period (ms)
+click-counter = Click counter
+click-interval-ms = Click interval (ms)
+idle-auto-hide = Idle auto-hide
+idle-delay-s = Idle delay (s)
+motion = Motion
+follow-system = Follow system
+reduced = Reduced
+full = Full
+show-hide-animation-ms = Show/hide animation (ms)
+outline-halo = Outline halo
+high-contrast = High contrast
+glow = Glow
+none = None
+soft-glow = Soft glow
+drop-shadow = Drop shadow
+grid-color = Grid color
+grid-spacing-px = Grid spacing (px)
+grid-subdivisions = Grid subdivisions
+confirm-before-quitting = Confirm before quitting
+notify-on-toggle = Notify on toggle
+hide-from-screen-capture = Hide from screen capture
+run-at-windows-startup = Run at Windows startup
+sync-folder = Sync folder
+off = Off
+stop-syncing = Stop syncing
+choose-sync-folder = Choose sync folder
+press-keys = Press keys...
+reset = Reset
+hotkey-taken = Already used by { $action }
+close = Close
+some-settings-are-managed-by-your-administrator = Some settings are managed by your administrator.
+
+## Quit dialog
+
+quit = Quit
+cancel = Cancel
+quit-the-app = Quit the app?
+the-app-will-close = The app will close
+
+## Help overlay and menus
+
+toggle = Toggle
+help = Help
+keyboard-shortcuts = Keyboard Shortcuts
+toggle-overlay = Toggle overlay
+highlight-shown = Highlight shown
+highlight-hidden = Highlight hidden
+open-settings = Open settings
+show-help = Show help
+zoom-in = Zoom in
+zoom-out = Zoom out
+cursor-coordinates = Cursor coordinates
+color-sampler = Color sampler
+ruler = Ruler
+grid = Grid
+lock-settings = Lock settings
+settings-locked = Settings locked
+radius = Radius
+profile = Profile
+unavailable = unavailable
+hotkeys = Hotkeys
+standard = Standard
+presentation-f13-f20 = Presentation (F13–F20)
+record-clicks = Record clicks
+export-clicks-csv = Export clicks (CSV)...
+export-clicks-json = Export clicks (JSON)...
+export-click-log = Export click log
+quit-app = Quit app
+quit-application = Quit application
+about = About...
+restart = Restart
+press-ok-to-close = Press OK to close
+press-any-key-to-close = Press any key to close
+
+## Settings file problems
+
+settings-file-problems = Settings file problems
+settings-file-problems-detail = Some settings in { $path } could not be used:
+
+## Crash report
+
+lumbus-crashed = Lumbus crashed
+crash-report-detail = A crash report was saved to { $path }. Please attach it when you report the problem.
+show-report = Show report
+show-the-report-now = Show the report now?
+
+## Update check
+
+check-for-updates = Check for updates
+check-for-updates-automatically = Check for updates automatically
+update-available = Update available: { $version }
+update-available-detail = Lumbus { $version } is available (you have { $current }).
+download = Download
+download-it-now = Download it now?
+lumbus-is-up-to-date = Lumbus is up to date
+up-to-date-detail = You have the latest version ({ $version }).
+could-not-check-for-updates = Could not check for updates
+
+## About window
+
+about-lumbus = About Lumbus
+mouse-pointer-highlighter-for-presentations-and-screen-recordings = Mouse pointer highlighter for presentations and screen recordings.
+version-label = Version { $version }
+website = Website
+report-an-issue = Report an issue
+license = License
+
+## First-run walkthrough
+
+welcome-to-lumbus = Welcome to Lumbus
+onboarding-welcome = Lumbus highlights the mouse pointer so your audience can follow it in presentations and screen recordings. This short tour shows the hotkeys and lets you set up the highlight.
+onboarding-hotkeys = These hotkeys work from any app:
+onboarding-hotkey-profiles = Other hotkey sets are in the Hotkeys menu of the Lumbus icon.
+accessibility-permission = Accessibility permission
+onboarding-permission = To show your clicks while other apps are in front, Lumbus needs Accessibility permission. macOS asks you to turn Lumbus on in System Settings, under Privacy & Security, Accessibility.
+grant-access = Grant access...
+permission-granted = Permission granted
+onboarding-permission-later = You can skip this for now. Lumbus asks again the next time it starts.
+size-and-color = Size and color
+onboarding-appearance = Choose how the highlight looks. You can change this any time in Settings.
+onboarding-step = Step { $step } of { $total }
+next = Next
+back = Back
+skip = Skip
+done = Done
+
+## Permissions
+
+permissions = Permissions
+accessibility = Accessibility
+screen-recording = Screen Recording
+click-letters = Click letters
+zoom-and-color-sampler = Zoom and color sampler
+granted = Granted
+permission-missing = Not granted: { $feature } off
+open-system-settings = Open System Settings
+re-check = Re-check
+
+## Settings history
+
+undo = Undo
+redo = Redo
+revert-to-previous = Revert to previous
+restore-defaults = Restore Defaults
@@ -0,0 +1,201 @@
+# Spanish
+
+language-name = Español
+
+## Overlay
+
+# Izquierdo / derecho
+click-letter-left = I
+click-letter-right = D
+
+## Settings window
+
+settings = Configuración
+language = Idioma
+system-default = Predeterminado del sistema
+radius-px = Radio (px)
+border-px = Grosor (px)
+color = Color
+choose = Elegir...
+hex = Hex
+hex-color = Color hexadecimal
+hue = Tono (°)
+saturation = Saturación (%)
+brightness = Brillo (%)
+palette = Paleta
+save-color = Guardar color
+remove = Quitar
+rainbow-colors = Colores arcoíris
+rainbow-speed-cycles-min = Velocidad del arcoíris (ciclos/min)
+fill-transparency = Transparencia (%)
+style = Estilo
+circle = Círculo
+laser-pointer = Puntero láser
+recording-frame = Marco de grabación
+frame-color = Color del marco
+speed-fade = Atenuar con velocidad
+speed-size = Agrandar con velocidad
+left-click-letter = Letra del clic izquierdo
+right-click-letter = Letra del clic derecho
+letter-font = Fuente de las letras
+letter-weight = Grosor de las letras
+light = Fina
+regular = Normal
+medium = Media
+bold = Negrita
+black = Gruesa
+click-icon = Icono de clic
+letters = Letras
+tap = Toque
+mouse = Ratón
+star = Estrella
+click-hold-ms = Mantener clic (ms)
+hold-ring = Anillo de pulsación larga
+hold-ring-ms = Anillo de pulsación larga (ms)
+pulse = Pulso
+pulse-amplitude = Amplitud del pulso (%)
+pulse-period-ms = Periodo del pulso (ms)
+click-counter = Contador de clics
+click-interval-ms = Intervalo de clics (ms)
+idle-auto-hide = Ocultar en reposo
+idle-delay-s = Espera en reposo (s)
+motion = Movimiento
+follow-system = Según el sistema
+reduced = Reducido
+full = Completo
+show-hide-animation-ms = Animación al mostrar/ocultar (ms)
+outline-halo = Halo de contorno
+high-contrast = Alto contraste
+glow = Resplandor
+none = Ninguno
+soft-glow = Resplandor suave
+drop-shadow = Sombra
+grid-color = Color de la cuadrícula
+grid-spacing-px = Espaciado de la cuadrícula (px)
+grid-subdivisions = Subdivisiones de la cuadrícula
+confirm-before-quitting = Confirmar antes de salir
+notify-on-toggle = Notificar al alternar
+hide-from-screen-capture = Ocultar en las capturas de pantalla
+run-at-windows-startup = Ejecutar al iniciar Windows
+sync-folder = Carpeta de sincronización
+off = Desactivada
+stop-syncing = Dejar de sincronizar
+choose-sync-folder = Elegir carpeta de sincronización
+press-keys = Pulsa las teclas...
+reset = Restablecer
+hotkey-taken = Ya lo usa { $action }
+close = Cerrar
+some-settings-are-managed-by-your-administrator = Algunos ajustes los gestiona tu administrador.
+
+## Quit dialog
+
+quit = Salir
+cancel = Cancelar
+quit-the-app = ¿Salir de la aplicación?
+the-app-will-close = Se cerrará la app
+
+## Help overlay and menus
+
+toggle = Alternar
+help = Ayuda
+keyboard-shortcuts = Atajos de teclado
+toggle-overlay = Mostrar/ocultar resaltado
+highlight-shown = Resaltado visible
+highlight-hidden = Resaltado oculto
+open-settings = Abrir configuración
+show-help = Mostrar ayuda
+zoom-in = Ampliar
+zoom-out = Reducir
+cursor-coordinates = Coordenadas del cursor
+color-sampler = Cuentagotas de color
+ruler = Regla
+grid = Cuadrícula
+lock-settings = Bloquear ajustes
+settings-locked = Ajustes bloqueados
+radius = Radio
+profile = Perfil
+unavailable = no disponible
+hotkeys = Atajos de teclado
+standard = Estándar
+presentation-f13-f20 = Presentación (F13–F20)
+record-clicks = Grabar clics
+export-clicks-csv = Exportar clics (CSV)...
+export-clicks-json = Exportar clics (JSON)...
+export-click-log = Exportar registro de clics
+quit-app = Salir de la app
+quit-application = Salir de la aplicación
+about = Acerca de...
+restart = Reiniciar
+press-ok-to-close = Pulsa Aceptar para cerrar
+press-any-key-to-close = Pulsa cualquier tecla para cerrar
+
+## Settings file problems
+
+settings-file-problems = Problemas en el archivo de ajustes
+settings-file-problems-detail = Algunos ajustes de { $path } no se han podido usar:
+
+## Crash report
+
+lumbus-crashed = Lumbus se ha cerrado inesperadamente
+crash-report-detail = Se ha guardado un informe del fallo en { $path }. Adjúntalo al informar del problema.
+show-report = Mostrar informe
+show-the-report-now = ¿Mostrar el informe ahora?
+
+## Update check
+
+check-for-updates = Buscar actualizaciones
+check-for-updates-automatically = Buscar actualizaciones automáticamente
+update-available = Actualización disponible: { $version }
+update-available-detail = Lumbus { $version } está disponible (tienes la { $current }).
+download = Descargar
+download-it-now = ¿Descargarla ahora?
+lumbus-is-up-to-date = Lumbus está actualizado
+up-to-date-detail = Tienes la última versión ({ $version }).
+could-not-check-for-updates = No se pudo buscar actualizaciones
+
+## About window
+
+about-lumbus = Acerca de Lumbus
+mouse-pointer-highlighter-for-presentations-and-screen-recordings = Resaltador del puntero del ratón para presentaciones y grabaciones de pantalla.
+version-label = Versión { $version }
+website = Sitio web
+report-an-issue = Informar de un problema
+license = Licencia
+
+## First-run walkthrough
+
+welcome-to-lumbus = Bienvenido a Lumbus
+onboarding-welcome = Lumbus resalta el puntero del ratón para que tu público pueda seguirlo en presentaciones y grabaciones de pantalla. Este breve recorrido muestra los atajos de teclado y te permite configurar el resaltado.
+onboarding-hotkeys = Estos atajos funcionan desde cualquier aplicación:
+onboarding-hotkey-profiles = Hay otros juegos de atajos en el menú Atajos de teclado del icono de Lumbus.
+accessibility-permission = Permiso de accesibilidad
+onboarding-permission = Para mostrar tus clics mientras otras aplicaciones están delante, Lumbus necesita permiso de accesibilidad. macOS te pedirá que actives Lumbus en Ajustes del Sistema, en Privacidad y seguridad, Accesibilidad.
+grant-access = Conceder acceso...
+permission-granted = Permiso concedido
+onboarding-permission-later = Puedes omitir este paso. Lumbus volverá a pedirlo la próxima vez que se inicie.
+size-and-color = Tamaño y color
+onboarding-appearance = Elige cómo se ve el resaltado. Puedes cambiarlo cuando quieras en Configuración.
+onboarding-step = Paso { $step } de { $total }
+next = Siguiente
+back = Atrás
+skip = Omitir
+done = Hecho
+
+## Permissions
+
+permissions = Permisos
+accessibility = Accesibilidad
+screen-recording = Grabación de pantalla
+click-letters = Letras de clic
+zoom-and-color-sampler = Zoom y cuentagotas
+granted = Concedido
+permission-missing = No concedido: { $feature } desactivado
+open-system-settings = Abrir Ajustes del Sistema
+re-check = Volver a comprobar
+
+## Settings history
+
+undo = Deshacer
+redo = Rehacer
+revert-to-previous = Restaurar anterior
+restore-defaults = Restaurar valores predeterminados
@@ -0,0 +1,201 @@
+# French
+
+language-name = Français
+
+## Overlay
+
+# Gauche / droite
+click-letter-left = G
+click-letter-right = D
+
+## Settings window
+
+settings = Réglages
+language = Langue
+system-default = Langue du système
+radius-px = Rayon (px)
+border-px = Bordure (px)
+color = Couleur
+choose = Choisir...
+hex = Hex
+hex-color = Couleur hexadécimale
+hue = Teinte (°)
+saturation = Saturation (%)
+brightness = Luminosité (%)
+palette = Palette
+save-color = Enregistrer la couleur
+remove = Retirer
+rainbow-colors = Couleurs arc-en-ciel
+rainbow-speed-cycles-min = Vitesse de l’arc-en-ciel (cycles/min)
+fill-transparency = Transparence (%)
+style = Style
+circle = Cercle
+laser-pointer = Pointeur laser
+recording-frame = Cadre d'enregistrement
+frame-color = Couleur du cadre
+speed-fade = Estomper selon la vitesse
+speed-size = Agrandir selon la vitesse
+left-click-letter = Lettre du clic gauche
+right-click-letter = Lettre du clic droit
+letter-font = Police des lettres
+letter-weight = Graisse des lettres
+light = Maigre
+regular = Normal
+medium = Moyen
+bold = Gras
+black = Extra-gras
+click-icon = Icône de clic
+letters = Lettres
+tap = Appui
+mouse = Souris
+star = Étoile
+click-hold-ms = Maintien du clic (ms)
+hold-ring = Anneau d’appui long
+hold-ring-ms = Anneau d’appui long (ms)
+pulse = Pulsation
+pulse-amplitude = Amplitude de pulsation (%)
+pulse-period-ms = Période de pulsation (ms)
+click-counter = Compteur de clics
+click-interval-ms = Intervalle de clics (ms)
+idle-auto-hide = Masquer au repos
+idle-delay-s = Délai de repos (s)
+motion = Animations
+follow-system = Selon le système
+reduced = Réduites
+full = Complètes
+show-hide-animation-ms = Animation afficher/masquer (ms)
+outline-halo = Halo de contour
+high-contrast = Contraste élevé
+glow = Lueur
+none = Aucune
+soft-glow = Lueur douce
+drop-shadow = Ombre portée
+grid-color = Couleur de la grille
+grid-spacing-px = Espacement de la grille (px)
+grid-subdivisions = Subdivisions de la grille
+confirm-before-quitting = Confirmer avant de quitter
+notify-on-toggle = Notifier lors du basculement
+hide-from-screen-capture = Masquer dans les captures d'écran
+run-at-windows-startup = Lancer au démarrage de Windows
+sync-folder = Dossier de synchronisation
+off = Désactivé
+stop-syncing = Arrêter la synchronisation
+choose-sync-folder = Choisir le dossier de synchronisation
+press-keys = Appuyez sur les touches...
+reset = Réinitialiser
+hotkey-taken = Déjà utilisé par { $action }
+close = Fermer
+some-settings-are-managed-by-your-administrator = Certains réglages sont gérés par votre administrateur.
+
+## Quit dialog
+
+quit = Quitter
+cancel = Annuler
+quit-the-app = Quitter l'application ?
+the-app-will-close = L'application va se fermer
+
+## Help overlay and menus
+
+toggle = Activer/désactiver
+help = Aide
+keyboard-shortcuts = Raccourcis clavier
+toggle-overlay = Afficher/masquer le surlignage
+highlight-shown = Surlignage affiché
+highlight-hidden = Surlignage masqué
+open-settings = Ouvrir les réglages
+show-help = Afficher l'aide
+zoom-in = Zoom avant
+zoom-out = Zoom arrière
+cursor-coordinates = Coordonnées du curseur
+color-sampler = Pipette de couleur
+ruler = Règle
+grid = Grille
+lock-settings = Verrouiller les réglages
+settings-locked = Réglages verrouillés
+radius = Rayon
+profile = Profil
+unavailable = indisponible
+hotkeys = Raccourcis clavier
+standard = Standard
+presentation-f13-f20 = Présentation (F13–F20)
+record-clicks = Enregistrer les clics
+export-clicks-csv = Exporter les clics (CSV)...
+export-clicks-json = Exporter les clics (JSON)...
+export-click-log = Exporter le journal des clics
+quit-app = Quitter l'app
+quit-application = Quitter l'application
+about = À propos...
+restart = Redémarrer
+press-ok-to-close = Appuyez sur OK pour fermer
+press-any-key-to-close = Appuyez sur une touche pour fermer
+
+## Settings file problems
+
+settings-file-problems = Problèmes dans le fichier de réglages
+settings-file-problems-detail = Certains réglages de { $path } n’ont pas pu être utilisés :
+
+## Crash report
+
+lumbus-crashed = Lumbus s’est arrêté de manière inattendue
+crash-report-detail = Un rapport de plantage a été enregistré dans { $path }. Joignez-le lorsque vous signalez le problème.
+show-report = Afficher le rapport
+show-the-report-now = Afficher le rapport maintenant ?
+
+## Update check
+
+check-for-updates = Rechercher des mises à jour
+check-for-updates-automatically = Rechercher automatiquement les mises à jour
+update-available = Mise à jour disponible : { $version }
+update-available-detail = Lumbus { $version } est disponible (vous avez la { $current }).
+download = Télécharger
+download-it-now = La télécharger maintenant ?
+lumbus-is-up-to-date = Lumbus est à jour
+up-to-date-detail = Vous avez la dernière version ({ $version }).
+could-not-check-for-updates = Impossible de rechercher des mises à jour
+
+## About window
+
+about-lumbus = À propos de Lumbus
+mouse-pointer-highlighter-for-presentations-and-screen-recordings = Mise en évidence du pointeur de la souris pour les présentations et les enregistrements d’écran.
+version-label = Version { $version }
+website = Site web
+report-an-issue = Signaler un problème
+license = Licence
+
+## First-run walkthrough
+
+welcome-to-lumbus = Bienvenue dans Lumbus
+onboarding-welcome = Lumbus met en évidence le pointeur de la souris pour que votre public puisse le suivre pendant les présentations et les enregistrements d’écran. Cette courte visite présente les raccourcis et vous permet de régler la mise en évidence.
+onboarding-hotkeys = Ces raccourcis fonctionnent depuis n’importe quelle app :
+onboarding-hotkey-profiles = D’autres jeux de raccourcis se trouvent dans le menu Raccourcis clavier de l’icône de Lumbus.
+accessibility-permission = Autorisation d’accessibilité
+onboarding-permission = Pour afficher vos clics lorsque d’autres apps sont au premier plan, Lumbus a besoin de l’autorisation d’accessibilité. macOS vous demande d’activer Lumbus dans Réglages Système, sous Confidentialité et sécurité, Accessibilité.
+grant-access = Autoriser l’accès...
+permission-granted = Autorisation accordée
+onboarding-permission-later = Vous pouvez passer cette étape. Lumbus la redemandera au prochain démarrage.
+size-and-color = Taille et couleur
+onboarding-appearance = Choisissez l’apparence de la mise en évidence. Vous pourrez la modifier à tout moment dans les Réglages.
+onboarding-step = Étape { $step } sur { $total }
+next = Suivant
+back = Retour
+skip = Passer
+done = Terminé
+
+## Permissions
+
+permissions = Autorisations
+accessibility = Accessibilité
+screen-recording = Enregistrement de l’écran
+click-letters = Lettres de clic
+zoom-and-color-sampler = Zoom et pipette
+granted = Accordée
+permission-missing = Non accordée : { $feature } désactivé
+open-system-settings = Ouvrir Réglages Système
+re-check = Vérifier à nouveau
+
+## Settings history
+
+undo = Annuler
+redo = Rétablir
+revert-to-previous = Revenir aux réglages précédents
+restore-defaults = Rétablir les valeurs par défaut
@@ -0,0 +1,201 @@
+# Japanese
+
+language-name = 日本語
+
+## Overlay
+
+# Latin letters: the overlay font may lack CJK glyphs
+click-letter-left = L
+click-letter-right = R
+
+## Settings window
+
+settings = 設定
+language = 言語
+system-default = システムの既定
+radius-px = 半径 (px)
+border-px = 枠線 (px)
+color = 色
+choose = 選択...
+hex = 16進
+hex-color = 16進カラー
+hue = 色相 (°)
+saturation = 彩度 (%)
+brightness = 明度 (%)
+palette = パレット
+save-color = 色を保存
+remove = 削除
+rainbow-colors = レインボーカラー
+rainbow-speed-cycles-min = レインボーの速さ（周/分）
+fill-transparency = 塗りの透明度 (%)
+style = スタイル
+circle = 円
+laser-pointer = レーザーポインター
+recording-frame = 録画フレーム
+frame-color = フレームの色
+speed-fade = 高速移動時に薄くする
+speed-size = 高速移動時に大きくする
+left-click-letter = 左クリックの文字
+right-click-letter = 右クリックの文字
+letter-font = 文字のフォント
+letter-weight = 文字の太さ
+light = 細字
+regular = 標準
+medium = 中太
+bold = 太字
+black = 極太
+click-icon = クリックのアイコン
+letters = 文字
+tap = タップ
+mouse = マウス
+star = 星
+click-hold-ms = クリック表示の保持 (ms)
+hold-ring = 長押しリング
+hold-ring-ms = 長押しリング (ms)
+pulse = パルス
+pulse-amplitude = パルスの振幅 (%)
+pulse-period-ms = パルスの周期 (ms)
+click-counter = クリック回数
+click-interval-ms = クリック間隔 (ms)
+idle-auto-hide = 操作がないときに隠す
+idle-delay-s = 待機時間 (s)
+motion = 動き
+follow-system = システムに従う
+reduced = 減らす
+full = すべて
+show-hide-animation-ms = 表示/非表示アニメーション (ms)
+outline-halo = 輪郭ハロー
+high-contrast = ハイコントラスト
+glow = グロー
+none = なし
+soft-glow = ソフトグロー
+drop-shadow = ドロップシャドウ
+grid-color = グリッドの色
+grid-spacing-px = グリッド間隔 (px)
+grid-subdivisions = グリッドの分割数
+confirm-before-quitting = 終了前に確認
+notify-on-toggle = 切り替え時に通知
+hide-from-screen-capture = 画面キャプチャに表示しない
+run-at-windows-startup = Windows の起動時に実行
+sync-folder = 同期フォルダ
+off = オフ
+stop-syncing = 同期を停止
+choose-sync-folder = 同期フォルダを選択
+press-keys = キーを押してください...
+reset = リセット
+hotkey-taken = { $action } で使用中です
+close = 閉じる
+some-settings-are-managed-by-your-administrator = 一部の設定は管理者によって管理されています。
+
+## Quit dialog
+
+quit = 終了
+cancel = キャンセル
+quit-the-app = アプリを終了しますか?
+the-app-will-close = アプリが閉じます
+
+## Help overlay and menus
+
+toggle = 切り替え
+help = ヘルプ
+keyboard-shortcuts = キーボードショートカット
+toggle-overlay = ハイライトの表示/非表示
+highlight-shown = ハイライト表示中
+highlight-hidden = ハイライト非表示
+open-settings = 設定を開く
+show-help = ヘルプを表示
+zoom-in = 拡大
+zoom-out = 縮小
+cursor-coordinates = カーソル座標
+color-sampler = カラーピッカー
+ruler = ルーラー
+grid = グリッド
+lock-settings = 設定をロック
+settings-locked = 設定ロック中
+radius = 半径
+profile = プロファイル
+unavailable = 使用不可
+hotkeys = ホットキー
+standard = 標準
+presentation-f13-f20 = プレゼンテーション (F13–F20)
+record-clicks = クリックを記録
+export-clicks-csv = クリックを書き出す (CSV)...
+export-clicks-json = クリックを書き出す (JSON)...
+export-click-log = クリックログを書き出す
+quit-app = アプリを終了
+quit-application = アプリケーションを終了
+about = このアプリについて...
+restart = 再起動
+press-ok-to-close = OK を押すと閉じます
+press-any-key-to-close = いずれかのキーを押すと閉じます
+
+## Settings file problems
+
+settings-file-problems = 設定ファイルの問題
+settings-file-problems-detail = { $path } の一部の設定を使用できませんでした:
+
+## Crash report
+
+lumbus-crashed = Lumbus がクラッシュしました
+crash-report-detail = クラッシュレポートを { $path } に保存しました。問題を報告する際に添付してください。
+show-report = レポートを表示
+show-the-report-now = 今すぐレポートを表示しますか?
+
+## Update check
+
+check-for-updates = アップデートを確認
+check-for-updates-automatically = アップデートを自動的に確認
+update-available = アップデートがあります: { $version }
+update-available-detail = Lumbus { $version } が利用可能です(現在のバージョン: { $current })。
+download = ダウンロード
+download-it-now = 今すぐダウンロードしますか?
+lumbus-is-up-to-date = Lumbus は最新です
+up-to-date-detail = 最新バージョン ({ $version }) を使用しています。
+could-not-check-for-updates = アップデートを確認できませんでした
+
+## About window
+
+about-lumbus = Lumbus について
+mouse-pointer-highlighter-for-presentations-and-screen-recordings = プレゼンテーションや画面収録のためのマウスポインター強調表示ツール。
+version-label = バージョン { $version }
+website = Web サイト
+report-an-issue = 問題を報告
+license = ライセンス
+
+## First-run walkthrough
+
+welcome-to-lumbus = Lumbus へようこそ
+onboarding-welcome = Lumbus はマウスポインターを強調表示し、プレゼンテーションや画面収録で見ている人がポインターを追えるようにします。この短いガイドでは、ホットキーを紹介し、強調表示を設定します。
+onboarding-hotkeys = 次のホットキーはどのアプリからでも使えます:
+onboarding-hotkey-profiles = ほかのホットキーセットは、Lumbus アイコンの「ホットキー」メニューにあります。
+accessibility-permission = アクセシビリティの許可
+onboarding-permission = ほかのアプリが前面にあるときもクリックを表示するには、アクセシビリティの許可が必要です。macOS から、システム設定の「プライバシーとセキュリティ」>「アクセシビリティ」で Lumbus をオンにするよう求められます。
+grant-access = アクセスを許可...
+permission-granted = 許可されています
+onboarding-permission-later = この手順は後回しにできます。次回の起動時にもう一度確認します。
+size-and-color = サイズと色
+onboarding-appearance = 強調表示の見た目を選んでください。設定からいつでも変更できます。
+onboarding-step = ステップ { $step } / { $total }
+next = 次へ
+back = 戻る
+skip = スキップ
+done = 完了
+
+## Permissions
+
+permissions = アクセス許可
+accessibility = アクセシビリティ
+screen-recording = 画面収録
+click-letters = クリック文字
+zoom-and-color-sampler = ズームとカラーピッカー
+granted = 許可済み
+permission-missing = 未許可: { $feature } はオフ
+open-system-settings = システム設定を開く
+re-check = 再確認
+
+## Settings history
+
+undo = 元に戻す
+redo = やり直す
+revert-to-previous = 以前の設定に戻す
+restore-defaults = デフォルトに戻す
@@ -0,0 +1,201 @@
+# Portuguese
+
+language-name = Português
+
+## Overlay
+
+# Esquerdo / direito
+click-letter-left = E
+click-letter-right = D
+
+## Settings window
+
+settings = Configurações
+language = Idioma
+system-default = Padrão do sistema
+radius-px = Raio (px)
+border-px = Borda (px)
+color = Cor
+choose = Escolher...
+hex = Hex
+hex-color = Cor hexadecimal
+hue = Matiz (°)
+saturation = Saturação (%)
+brightness = Brilho (%)
+palette = Paleta
+save-color = Salvar cor
+remove = Remover
+rainbow-colors = Cores de arco-íris
+rainbow-speed-cycles-min = Velocidade do arco-íris (ciclos/min)
+fill-transparency = Transparência (%)
+style = Estilo
+circle = Círculo
+laser-pointer = Ponteiro laser
+recording-frame = Moldura de gravação
+frame-color = Cor da moldura
+speed-fade = Esmaecer com a velocidade
+speed-size = Aumentar com a velocidade
+left-click-letter = Letra do clique esquerdo
+right-click-letter = Letra do clique direito
+letter-font = Fonte das letras
+letter-weight = Peso das letras
+light = Fina
+regular = Normal
+medium = Média
+bold = Negrito
+black = Preta
+click-icon = Ícone de clique
+letters = Letras
+tap = Toque
+mouse = Mouse
+star = Estrela
+click-hold-ms = Manter clique (ms)
+hold-ring = Anel de pressão longa
+hold-ring-ms = Anel de pressão longa (ms)
+pulse = Pulsação
+pulse-amplitude = Amplitude da pulsação (%)
+pulse-period-ms = Período da pulsação (ms)
+click-counter = Contador de cliques
+click-interval-ms = Intervalo de cliques (ms)
+idle-auto-hide = Ocultar em repouso
+idle-delay-s = Espera em repouso (s)
+motion = Movimento
+follow-system = Seguir o sistema
+reduced = Reduzido
+full = Completo
+show-hide-animation-ms = Animação ao mostrar/ocultar (ms)
+outline-halo = Halo de contorno
+high-contrast = Alto contraste
+glow = Brilho
+none = Nenhum
+soft-glow = Brilho suave
+drop-shadow = Sombra projetada
+grid-color = Cor da grade
+grid-spacing-px = Espaçamento da grade (px)
+grid-subdivisions = Subdivisões da grade
+confirm-before-quitting = Confirmar antes de sair
+notify-on-toggle = Notificar ao alternar
+hide-from-screen-capture = Ocultar nas capturas de tela
+run-at-windows-startup = Executar ao iniciar o Windows
+sync-folder = Pasta de sincronização
+off = Desativada
+stop-syncing = Parar de sincronizar
+choose-sync-folder = Escolher pasta de sincronização
+press-keys = Pressione as teclas...
+reset = Redefinir
+hotkey-taken = Já usado por { $action }
+close = Fechar
+some-settings-are-managed-by-your-administrator = Algumas configurações são gerenciadas pelo seu administrador.
+
+## Quit dialog
+
+quit = Sair
+cancel = Cancelar
+quit-the-app = Sair do aplicativo?
+the-app-will-close = O aplicativo será fechado
+
+## Help overlay and menus
+
+toggle = Alternar
+help = Ajuda
+keyboard-shortcuts = Atalhos de teclado
+toggle-overlay = Mostrar/ocultar destaque
+highlight-shown = Destaque visível
+highlight-hidden = Destaque oculto
+open-settings = Abrir configurações
+show-help = Mostrar ajuda
+zoom-in = Ampliar
+zoom-out = Reduzir
+cursor-coordinates = Coordenadas do cursor
+color-sampler = Conta-gotas de cor
+ruler = Régua
+grid = Grade
+lock-settings = Bloquear configurações
+settings-locked = Configurações bloqueadas
+radius = Raio
+profile = Perfil
+unavailable = indisponível
+hotkeys = Atalhos de teclado
+standard = Padrão
+presentation-f13-f20 = Apresentação (F13–F20)
+record-clicks = Gravar cliques
+export-clicks-csv = Exportar cliques (CSV)...
+export-clicks-json = Exportar cliques (JSON)...
+export-click-log = Exportar registro de cliques
+quit-app = Sair do app
+quit-application = Sair do aplicativo
+about = Sobre...
+restart = Reiniciar
+press-ok-to-close = Pressione OK para fechar
+press-any-key-to-close = Pressione qualquer tecla para fechar
+
+## Settings file problems
+
+settings-file-problems = Problemas no arquivo de configurações
+settings-file-problems-detail = Algumas configurações em { $path } não puderam ser usadas:
+
+## Crash report
+
+lumbus-crashed = O Lumbus falhou
+crash-report-detail = Um relatório de falha foi salvo em { $path }. Anexe-o ao relatar o problema.
+show-report = Mostrar relatório
+show-the-report-now = Mostrar o relatório agora?
+
+## Update check
+
+check-for-updates = Verificar atualizações
+check-for-updates-automatically = Verificar atualizações automaticamente
+update-available = Atualização disponível: { $version }
+update-available-detail = O Lumbus { $version } está disponível (você tem a { $current }).
+download = Baixar
+download-it-now = Baixar agora?
+lumbus-is-up-to-date = O Lumbus está atualizado
+up-to-date-detail = Você tem a versão mais recente ({ $version }).
+could-not-check-for-updates = Não foi possível verificar atualizações
+
+## About window
+
+about-lumbus = Sobre o Lumbus
+mouse-pointer-highlighter-for-presentations-and-screen-recordings = Realce do ponteiro do mouse para apresentações e gravações de tela.
+version-label = Versão { $version }
+website = Site
+report-an-issue = Relatar um problema
+license = Licença
+
+## First-run walkthrough
+
+welcome-to-lumbus = Bem-vindo ao Lumbus
+onboarding-welcome = O Lumbus realça o ponteiro do mouse para que seu público possa acompanhá-lo em apresentações e gravações de tela. Este breve tour mostra os atalhos e permite configurar o realce.
+onboarding-hotkeys = Estes atalhos funcionam em qualquer app:
+onboarding-hotkey-profiles = Outros conjuntos de atalhos estão no menu Atalhos de teclado do ícone do Lumbus.
+accessibility-permission = Permissão de acessibilidade
+onboarding-permission = Para mostrar seus cliques enquanto outros apps estão na frente, o Lumbus precisa da permissão de acessibilidade. O macOS pede que você ative o Lumbus em Ajustes do Sistema, em Privacidade e Segurança, Acessibilidade.
+grant-access = Permitir acesso...
+permission-granted = Permissão concedida
+onboarding-permission-later = Você pode pular esta etapa. O Lumbus perguntará de novo na próxima vez que iniciar.
+size-and-color = Tamanho e cor
+onboarding-appearance = Escolha a aparência do realce. Você pode mudar isso a qualquer momento em Configurações.
+onboarding-step = Etapa { $step } de { $total }
+next = Próximo
+back = Voltar
+skip = Pular
+done = Concluído
+
+## Permissions
+
+permissions = Permissões
+accessibility = Acessibilidade
+screen-recording = Gravação de tela
+click-letters = Letras de clique
+zoom-and-color-sampler = Zoom e conta-gotas
+granted = Concedida
+permission-missing = Não concedida: { $feature } desativado
+open-system-settings = Abrir Ajustes do Sistema
+re-check = Verificar de novo
+
+## Settings history
+
+undo = Desfazer
+redo = Refazer
+revert-to-previous = Reverter para o anterior
+restore-defaults = Restaurar padrões
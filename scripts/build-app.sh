@@ -110,6 +110,10 @@ if [ -f "$PROJECT_ROOT/resources/Credits.rtf" ]; then
     cp "$PROJECT_ROOT/resources/Credits.rtf" "$RESOURCES_DIR/"
 fi
 
+# Step 6d: Copy the AppleScript dictionary
+echo ">>> Copying Lumbus.sdef..."
+cp "$PROJECT_ROOT/resources/Lumbus.sdef" "$RESOURCES_DIR/"
+
 # Step 7: Create PkgInfo
 echo ">>> Creating PkgInfo..."
 echo -n "APPLmhlt" > "$CONTENTS_DIR/PkgInfo"
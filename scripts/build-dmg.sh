@@ -97,6 +97,9 @@ if [ -f "$PROJECT_ROOT/resources/Credits.rtf" ]; then
     cp "$PROJECT_ROOT/resources/Credits.rtf" "$APP_BUNDLE/Contents/Resources/"
 fi
 
+# Copy the AppleScript dictionary
+cp "$PROJECT_ROOT/resources/Lumbus.sdef" "$APP_BUNDLE/Contents/Resources/"
+
 # Set executable permissions
 chmod +x "$APP_BUNDLE/Contents/MacOS/$APP_NAME"
 
@@ -0,0 +1,218 @@
+//! Settings changes, carried out the same way from every frontend.
+//!
+//! The Settings window, hotkeys, the command line, `lumbus://` URLs and the
+//! control endpoint all change settings through [`run_command`]. An
+//! [`AppCommand`] is validated (unknown keys refused, numbers clamped to
+//! their limits), applied to the stored preferences, persisted, then
+//! broadcast to the overlay and the menus. Each frontend hands over a
+//! [`CommandTarget`]: the dispatcher its [`OverlayBackend`], the Settings
+//! window its store.
+
+use std::fmt;
+
+use super::{load_settings, OverlayBackend};
+use crate::model::constants::{DEFAULT_SCREEN_FRAME_COLOR, SETTINGS_STRING_KEYS};
+use crate::model::preferences::clamp_number;
+use crate::model::{Preferences, MANAGED_KEYS};
+
+/// A change to the settings.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppCommand {
+    /// Set a numeric preference.
+    SetNumber(String, f64),
+    /// Set an integer (or boolean, 0/1) preference.
+    SetInt(String, i32),
+    /// Set a text preference.
+    SetText(String, String),
+    /// Set the recording-frame color (RGBA) of the target's display.
+    SetFrameColor((f64, f64, f64, f64)),
+    /// Put every setting back to its default.
+    RestoreDefaults,
+    /// Several changes, persisted and broadcast once (a color, a preset).
+    Batch(Vec<AppCommand>),
+}
+
+/// Why a command was refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    /// No numeric preference with this key.
+    UnknownNumber(String),
+    /// No text preference with this key.
+    UnknownText(String),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::UnknownNumber(key) => write!(f, "no numeric setting `{key}`"),
+            CommandError::UnknownText(key) => write!(f, "no text setting `{key}`"),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+impl AppCommand {
+    /// Set the numeric preference `key`.
+    pub fn number(key: &str, value: f64) -> Self {
+        AppCommand::SetNumber(key.to_string(), value)
+    }
+
+    /// Set the integer preference `key`.
+    pub fn int(key: &str, value: i32) -> Self {
+        AppCommand::SetInt(key.to_string(), value)
+    }
+
+    /// Set the text preference `key`.
+    pub fn text(key: &str, text: &str) -> Self {
+        AppCommand::SetText(key.to_string(), text.to_string())
+    }
+
+    /// Set every numeric preference of `values` as one change.
+    pub fn numbers<'a>(values: impl IntoIterator<Item = (&'a str, f64)>) -> Self {
+        AppCommand::Batch(
+            values
+                .into_iter()
+                .map(|(key, value)| Self::number(key, value))
+                .collect(),
+        )
+    }
+
+    /// The command as it will be applied: numbers clamped to their limits
+    /// and color components to [0.0, 1.0] (non-finite ones replaced by the
+    /// default). Fails on the first key
+    /// that is not a preference; a batch is then refused as a whole.
+    pub fn validate(self) -> Result<Self, CommandError> {
+        let defaults = Preferences::default();
+        self.validated(&defaults)
+    }
+
+    fn validated(self, defaults: &Preferences) -> Result<Self, CommandError> {
+        Ok(match self {
+            AppCommand::SetNumber(key, value) => {
+                if defaults.number(&key).is_none() {
+                    return Err(CommandError::UnknownNumber(key));
+                }
+                let value = clamp_number(&key, value);
+                AppCommand::SetNumber(key, value)
+            }
+            AppCommand::SetInt(key, value) => {
+                if defaults.number(&key).is_none() {
+                    return Err(CommandError::UnknownNumber(key));
+                }
+                let value = clamp_number(&key, value as f64).round() as i32;
+                AppCommand::SetInt(key, value)
+            }
+            AppCommand::SetText(key, text) => {
+                if defaults.string(&key).is_none() {
+                    return Err(CommandError::UnknownText(key));
+                }
+                AppCommand::SetText(key, text)
+            }
+            AppCommand::SetFrameColor((r, g, b, a)) => {
+                let (dr, dg, db, da) = DEFAULT_SCREEN_FRAME_COLOR;
+                let unit = |v: f64, default: f64| {
+                    if v.is_finite() {
+                        v.clamp(0.0, 1.0)
+                    } else {
+                        default
+                    }
+                };
+                AppCommand::SetFrameColor((unit(r, dr), unit(g, dg), unit(b, db), unit(a, da)))
+            }
+            AppCommand::RestoreDefaults => AppCommand::RestoreDefaults,
+            AppCommand::Batch(commands) => AppCommand::Batch(
+                commands
+                    .into_iter()
+                    .map(|c| c.validated(defaults))
+                    .collect::<Result<_, _>>()?,
+            ),
+        })
+    }
+
+    /// Write the values to `target`, without persisting them.
+    fn apply<T: CommandTarget + ?Sized>(&self, target: &mut T) {
+        match self {
+            AppCommand::SetNumber(key, value) => target.write_number(key, *value),
+            AppCommand::SetInt(key, value) => target.write_int(key, *value),
+            AppCommand::SetText(key, text) => target.write_text(key, text),
+            AppCommand::SetFrameColor(color) => target.write_frame_color(*color),
+            AppCommand::RestoreDefaults => target.write_defaults(),
+            AppCommand::Batch(commands) => commands.iter().for_each(|c| c.apply(target)),
+        }
+    }
+}
+
+/// Where a frontend's commands land.
+///
+/// Writes follow the storage rules: keys locked by the administrator keep
+/// their value.
+pub trait CommandTarget {
+    /// Store a numeric preference.
+    fn write_number(&mut self, key: &str, value: f64);
+
+    /// Store an integer (or boolean) preference.
+    fn write_int(&mut self, key: &str, value: i32) {
+        self.write_number(key, value as f64);
+    }
+
+    /// Store a text preference.
+    fn write_text(&mut self, key: &str, text: &str);
+
+    /// Store the recording-frame color of the target's display. Targets
+    /// without a display of their own ignore it.
+    fn write_frame_color(&mut self, _color: (f64, f64, f64, f64)) {}
+
+    /// Put every setting back to its default. The default writes the
+    /// built-in defaults key by key.
+    fn write_defaults(&mut self) {
+        let defaults = Preferences::default();
+        for key in MANAGED_KEYS {
+            if let Some(value) = defaults.number(key) {
+                self.write_number(key, value);
+            }
+        }
+        for key in SETTINGS_STRING_KEYS {
+            self.write_text(key, defaults.string(key).unwrap_or_default());
+        }
+    }
+
+    /// Save the stored preferences.
+    fn persist(&mut self);
+
+    /// Apply the stored preferences to the overlay and the menus.
+    fn broadcast(&mut self);
+}
+
+/// Validate `command`, apply it to `target`, persist it and broadcast
+/// the result. Nothing is written when validation fails.
+pub fn run_command<T: CommandTarget + ?Sized>(
+    target: &mut T,
+    command: AppCommand,
+) -> Result<(), CommandError> {
+    let command = command.validate()?;
+    command.apply(target);
+    target.persist();
+    target.broadcast();
+    Ok(())
+}
+
+impl CommandTarget for dyn OverlayBackend + '_ {
+    fn write_number(&mut self, key: &str, value: f64) {
+        self.set_double(key, value);
+    }
+
+    fn write_text(&mut self, key: &str, text: &str) {
+        self.set_string(key, text);
+    }
+
+    fn persist(&mut self) {
+        self.flush();
+    }
+
+    fn broadcast(&mut self) {
+        load_settings(self);
+        self.refresh_menus();
+        self.redraw();
+    }
+}
@@ -0,0 +1,168 @@
+//! The event dispatcher, over the actions of a platform.
+//!
+//! [`dispatch_pending`] takes the events published on the bus one at a
+//! time. Those handled the same way everywhere go to [`dispatch`] over the
+//! context's [`OverlayBackend`]; windows, dialogs, zoom and updates go to
+//! the [`DispatchContext`]'s handlers. macOS and Windows implement the
+//! context over their windows; tests use a fake that records the calls.
+
+use tracing::{debug, info};
+
+use super::{dispatch, OverlayBackend};
+use crate::events::AppEvent;
+use crate::model::{ClickLogFormat, UpdateStatus};
+
+/// What the dispatcher needs from a platform, beyond its backend.
+///
+/// Handlers that can block until a window closes (a modal dialog, a save
+/// panel) return whether they did.
+pub trait DispatchContext {
+    /// Backend for the events handled the same way on every platform.
+    fn backend(&mut self) -> &mut dyn OverlayBackend;
+
+    /// Does the presentation lock let `event` through?
+    fn allows(&self, event: &AppEvent) -> bool;
+
+    /// Turn the presentation lock on or off; returns whether it is on.
+    fn toggle_presentation_lock(&mut self) -> bool;
+
+    /// Open the Settings window.
+    fn open_settings(&mut self) -> bool;
+
+    /// Ask before quitting, and quit if confirmed.
+    fn confirm_quit(&mut self) -> bool;
+
+    /// Quit and start again, without asking.
+    fn restart(&mut self);
+
+    /// Show the About window.
+    fn show_about(&mut self);
+
+    /// Show the help overlay.
+    fn show_help(&mut self) -> bool;
+
+    /// Zoom in around the cursor.
+    fn zoom_in(&mut self);
+
+    /// Zoom out.
+    fn zoom_out(&mut self);
+
+    /// Start a new click recording or stop the current one; returns
+    /// whether it is recording.
+    fn toggle_click_recording(&mut self) -> bool;
+
+    /// Save the click log as `format`.
+    fn export_click_log(&mut self, format: ClickLogFormat) -> bool;
+
+    /// Start an update check; the answer comes back as
+    /// [`AppEvent::UpdateChecked`].
+    fn check_for_updates(&mut self);
+
+    /// Carry out the answer of an update check.
+    fn update_checked(&mut self, status: &UpdateStatus, manual: bool) -> bool;
+}
+
+/// Handle `event`. Returns whether it blocked until a window closed.
+///
+/// The presentation lock is checked by the caller.
+pub fn dispatch_event(context: &mut dyn DispatchContext, event: &AppEvent) -> bool {
+    if dispatch(context.backend(), event) {
+        return false;
+    }
+    match event {
+        AppEvent::OpenSettings => context.open_settings(),
+        AppEvent::RequestQuit => context.confirm_quit(),
+        AppEvent::Restart => {
+            context.restart();
+            false
+        }
+        AppEvent::ShowAbout => {
+            context.show_about();
+            false
+        }
+        AppEvent::ShowHelp => context.show_help(),
+        AppEvent::ZoomIn => {
+            context.zoom_in();
+            false
+        }
+        AppEvent::ZoomOut => {
+            context.zoom_out();
+            false
+        }
+        AppEvent::TogglePresentationLock => {
+            let locked = context.toggle_presentation_lock();
+            info!("Presentation lock {}", if locked { "on" } else { "off" });
+            // Show or hide the Settings entry of the menu
+            context.backend().refresh_menus();
+            false
+        }
+        AppEvent::ToggleClickRecording => {
+            let recording = context.toggle_click_recording();
+            info!(
+                "Click recording {}",
+                if recording { "started" } else { "stopped" }
+            );
+            // Update the checkmark
+            context.backend().refresh_menus();
+            false
+        }
+        AppEvent::ExportClickLogCsv => context.export_click_log(ClickLogFormat::Csv),
+        AppEvent::ExportClickLogJson => context.export_click_log(ClickLogFormat::Json),
+        AppEvent::CheckForUpdates => {
+            context.check_for_updates();
+            false
+        }
+        AppEvent::UpdateChecked { status, manual } => context.update_checked(status, *manual),
+        AppEvent::SettingsClosed
+        | AppEvent::QuitCancelled
+        | AppEvent::HelpClosed
+        | AppEvent::ReinstallHotkeys => {
+            context.backend().reinstall_hotkeys();
+            false
+        }
+        // Handled by `dispatch`
+        _ => false,
+    }
+}
+
+/// Handle the events `next` hands out until there are none left.
+///
+/// Events the presentation lock holds back are dropped. Once an event has
+/// blocked until a window closed, the requests to open a window that piled
+/// up meanwhile (the user pressing the hotkey again) are dropped too, so a
+/// second window does not open after the first one closes. Every other
+/// event still queued (`SettingsClosed`, a toggle published before the
+/// request, a quit or restart) is handled in order.
+pub fn dispatch_pending(
+    context: &mut dyn DispatchContext,
+    mut next: impl FnMut() -> Option<AppEvent>,
+) {
+    let mut after_modal = false;
+    while let Some(event) = next() {
+        if !context.allows(&event) {
+            continue;
+        }
+        if after_modal && opens_window(&event) {
+            debug!("Dropped request piled up behind a modal: {:?}", event);
+            continue;
+        }
+        if dispatch_event(context, &event) {
+            after_modal = true;
+        }
+    }
+}
+
+/// Does `event` open a window that blocks until closed?
+///
+/// `RequestQuit` may too (the quit confirmation), but a quit the user asked
+/// for while a modal was up must still happen.
+fn opens_window(event: &AppEvent) -> bool {
+    matches!(
+        event,
+        AppEvent::OpenSettings
+            | AppEvent::ShowHelp
+            | AppEvent::ShowAbout
+            | AppEvent::ExportClickLogCsv
+            | AppEvent::ExportClickLogJson
+    )
+}
@@ -0,0 +1,117 @@
+//! Events handled the same way on every platform.
+
+use tracing::{info, warn};
+
+use super::command::{run_command, AppCommand};
+use super::OverlayBackend;
+use crate::events::AppEvent;
+use crate::model::constants::{
+    PREF_HOTKEY_PROFILE, PREF_RADIUS, PREF_STROKE_A, PREF_STROKE_B, PREF_STROKE_G, PREF_STROKE_R,
+};
+use crate::model::{look_profile_values, resolve_look_profile, resolve_profile, HOTKEY_PROFILES};
+
+/// Handle `event` if it only needs the backend. Returns `false` for the
+/// events the platform handles itself (windows, dialogs, updates).
+///
+/// The presentation lock is checked by the caller.
+pub fn dispatch(backend: &mut dyn OverlayBackend, event: &AppEvent) -> bool {
+    match event {
+        AppEvent::ToggleOverlay => {
+            backend.toggle_overlay();
+            backend.redraw();
+        }
+        AppEvent::ToggleCoordinates => {
+            backend.toggle_coordinates();
+            backend.redraw();
+        }
+        AppEvent::ToggleColorSampler => {
+            backend.toggle_color_sampler();
+            backend.redraw();
+        }
+        AppEvent::ToggleRuler => {
+            backend.toggle_ruler();
+            backend.redraw();
+        }
+        AppEvent::ClearRuler => {
+            backend.clear_ruler();
+            backend.redraw();
+        }
+        AppEvent::ToggleGrid => {
+            backend.toggle_grid();
+            backend.redraw();
+        }
+        AppEvent::SelectHotkeyProfile(profile) => select_hotkey_profile(backend, profile),
+        AppEvent::SetPreference(key, value) => set_preference(backend, key, *value),
+        AppEvent::SelectLookProfile(name) => select_look_profile(backend, name),
+        AppEvent::SetRadius(radius) => set_preference(backend, PREF_RADIUS, *radius),
+        AppEvent::SetColor { r, g, b, a } => set_color(backend, (*r, *g, *b, *a)),
+        AppEvent::LoadProfile(name) => load_profile(backend, name),
+        AppEvent::SetMode(mode) => {
+            backend.show_display_mode(*mode);
+            backend.redraw();
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// Switch to another hotkey profile: save it, register its hotkeys and
+/// refresh the menus to match. Unknown names select the default profile.
+pub fn select_hotkey_profile(backend: &mut dyn OverlayBackend, profile: &str) {
+    let profile = resolve_profile(profile);
+    backend.set_string(PREF_HOTKEY_PROFILE, profile);
+    backend.flush();
+    info!("Hotkey profile: {}", profile);
+    backend.reinstall_hotkeys();
+    backend.refresh_menus();
+}
+
+/// Switch to the hotkey profile called `name`, ignoring case. Unknown
+/// names are ignored, unlike [`select_hotkey_profile`] which falls back to
+/// the default profile: a typo from a script should not rebind the keys.
+pub fn load_profile(backend: &mut dyn OverlayBackend, name: &str) {
+    match HOTKEY_PROFILES
+        .iter()
+        .find(|p| p.eq_ignore_ascii_case(name.trim()))
+    {
+        Some(profile) => select_hotkey_profile(backend, profile),
+        None => warn!("Unknown hotkey profile `{}`", name),
+    }
+}
+
+/// Store the highlight color (each component clamped to [0, 1]) and apply
+/// it like a preference.
+pub fn set_color(backend: &mut dyn OverlayBackend, (r, g, b, a): (f64, f64, f64, f64)) {
+    run(
+        backend,
+        AppCommand::numbers([
+            (PREF_STROKE_R, r),
+            (PREF_STROKE_G, g),
+            (PREF_STROKE_B, b),
+            (PREF_STROKE_A, a),
+        ]),
+    );
+}
+
+/// Store a numeric preference (clamped to its range) and apply the
+/// settings to the overlay and the menus. Unknown keys are ignored.
+pub fn set_preference(backend: &mut dyn OverlayBackend, key: &str, value: f64) {
+    run(backend, AppCommand::number(key, value));
+}
+
+/// Switch to the look called `name` and apply it like a preference.
+/// Unknown names are ignored.
+pub fn select_look_profile(backend: &mut dyn OverlayBackend, name: &str) {
+    let Some(name) = resolve_look_profile(name) else {
+        return;
+    };
+    info!("Look profile: {}", name);
+    run(backend, AppCommand::numbers(look_profile_values(name)));
+}
+
+/// Run `command` on the backend, logging a refused one.
+fn run(backend: &mut dyn OverlayBackend, command: AppCommand) {
+    if let Err(e) = run_command(backend, command) {
+        warn!("Ignoring setting change: {}", e);
+    }
+}
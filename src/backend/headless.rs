@@ -0,0 +1,284 @@
+//! Backend with no windows, for tests and CI.
+//!
+//! Preferences live in memory, [`HeadlessHotkeys`] keeps the bindings of
+//! the selected profile, and every redraw records the frame's
+//! [`DrawCommand`]s instead of drawing them.
+
+use super::hotkeys::{register_bindings, HotkeyError, HotkeyManager, TriggerHandler};
+use super::scene::{compose_frame, DrawCommand, FrameInput};
+use super::{dispatch, OverlayBackend};
+use crate::events::{publish, AppEvent};
+use crate::model::{
+    coordinates_label, profile_bindings, Binding, DisplayMode, Hotkey, HotkeyAction,
+    HotkeyPlatform, OverlayState, Preferences, Rgb8, Ruler,
+};
+
+/// Hotkeys registered in memory.
+#[derive(Debug, Default)]
+pub struct HeadlessHotkeys {
+    registered: Vec<Binding>,
+    /// Hotkeys "another app" has, refused on registration.
+    taken: Vec<Hotkey>,
+    handler: Option<TriggerHandler>,
+}
+
+impl HeadlessHotkeys {
+    /// No hotkey registered, none taken.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Have another app take `hotkey`, so registering it fails.
+    pub fn take(&mut self, hotkey: Hotkey) {
+        self.taken.push(hotkey);
+    }
+
+    /// Press `hotkey`: the trigger handler gets its action, or the action's
+    /// event is published. Returns whether a registered hotkey matched.
+    pub fn press(&self, hotkey: Hotkey) -> bool {
+        let Some(binding) = self.registered.iter().find(|b| b.hotkey == hotkey) else {
+            return false;
+        };
+        match self.handler {
+            Some(handler) => handler(binding.action),
+            None => publish(binding.action.event()),
+        }
+        true
+    }
+}
+
+impl HotkeyManager for HeadlessHotkeys {
+    fn register(&mut self, binding: Binding) -> Result<(), HotkeyError> {
+        if self.taken.contains(&binding.hotkey) {
+            return Err(HotkeyError::Refused {
+                hotkey: binding.hotkey,
+                reason: "taken".into(),
+            });
+        }
+        self.unregister(binding.action);
+        self.registered.push(binding);
+        Ok(())
+    }
+
+    fn unregister(&mut self, action: HotkeyAction) {
+        self.registered.retain(|b| b.action != action);
+    }
+
+    fn list(&self) -> Vec<Binding> {
+        self.registered.clone()
+    }
+
+    fn on_trigger(&mut self, handler: TriggerHandler) {
+        self.handler = Some(handler);
+    }
+}
+
+/// In-memory overlay that records what it would draw.
+pub struct HeadlessBackend {
+    /// Contents of `config.toml`.
+    pub prefs: Preferences,
+    /// Pointer, click and zoom state used by the next redraw.
+    pub input: FrameInput,
+    /// Locale tag reported as the OS one.
+    pub locale: Option<String>,
+    /// Screen color under the pointer, as the color sampler reads it.
+    pub screen_color: Rgb8,
+    /// Global hotkeys, registered by profile.
+    pub keys: HeadlessHotkeys,
+    /// Screens connected, each getting an overlay window.
+    pub screens: usize,
+    platform: HotkeyPlatform,
+    state: OverlayState,
+    windows: usize,
+    capture_excluded: bool,
+    frames: Vec<Vec<DrawCommand>>,
+    flushes: usize,
+    menu_refreshes: usize,
+}
+
+impl HeadlessBackend {
+    /// Backend with the default preferences, using `platform`'s shortcuts.
+    pub fn new(platform: HotkeyPlatform) -> Self {
+        Self::with_prefs(Preferences::default(), platform)
+    }
+
+    /// Backend started from `prefs`, as the app starts from `config.toml`.
+    pub fn with_prefs(prefs: Preferences, platform: HotkeyPlatform) -> Self {
+        let mut backend = Self {
+            state: prefs.to_state(),
+            prefs,
+            input: FrameInput::default(),
+            locale: None,
+            screen_color: (0, 0, 0),
+            keys: HeadlessHotkeys::new(),
+            screens: 1,
+            platform,
+            windows: 0,
+            capture_excluded: false,
+            frames: Vec::new(),
+            flushes: 0,
+            menu_refreshes: 0,
+        };
+        backend.reinstall_hotkeys();
+        backend
+    }
+
+    /// Dispatch `events` in order; returns those left to the platform.
+    pub fn dispatch_all(&mut self, events: impl IntoIterator<Item = AppEvent>) -> Vec<AppEvent> {
+        events
+            .into_iter()
+            .filter(|event| !dispatch(self, event))
+            .collect()
+    }
+
+    /// Overlay state last applied.
+    pub fn state(&self) -> &OverlayState {
+        &self.state
+    }
+
+    /// Is the overlay shown?
+    pub fn visible(&self) -> bool {
+        self.input.visible
+    }
+
+    /// Hotkeys currently registered.
+    pub fn hotkeys(&self) -> Vec<Binding> {
+        self.keys.list()
+    }
+
+    /// Every frame drawn so far, oldest first.
+    pub fn frames(&self) -> &[Vec<DrawCommand>] {
+        &self.frames
+    }
+
+    /// Left click at `point`, as the platforms route it: taken by the
+    /// ruler while its mode is on (returns `true` and redraws), else
+    /// passed on.
+    pub fn click(&mut self, point: (f64, f64)) -> bool {
+        let Some(ruler) = self.input.ruler.as_mut() else {
+            return false;
+        };
+        ruler.click(point);
+        self.redraw();
+        true
+    }
+
+    /// Last frame drawn, if any.
+    pub fn last_frame(&self) -> Option<&[DrawCommand]> {
+        self.frames.last().map(Vec::as_slice)
+    }
+
+    /// Overlay windows, as of the last sync.
+    pub fn windows(&self) -> usize {
+        self.windows
+    }
+
+    /// Are the overlay windows kept out of screen captures?
+    pub fn excluded_from_capture(&self) -> bool {
+        self.capture_excluded
+    }
+
+    /// Times the preferences were written to disk.
+    pub fn flushes(&self) -> usize {
+        self.flushes
+    }
+
+    /// Times the menus were relabeled.
+    pub fn menu_refreshes(&self) -> usize {
+        self.menu_refreshes
+    }
+}
+
+impl OverlayBackend for HeadlessBackend {
+    fn load_state(&self) -> OverlayState {
+        self.prefs.to_state()
+    }
+
+    fn set_double(&mut self, key: &str, value: f64) {
+        self.prefs.set_number(key, value);
+    }
+
+    fn set_string(&mut self, key: &str, value: &str) {
+        self.prefs.set_string(key, value);
+    }
+
+    fn flush(&mut self) {
+        self.flushes += 1;
+    }
+
+    fn apply_state(&mut self, state: &OverlayState) {
+        self.state = state.clone();
+    }
+
+    fn sync_windows(&mut self) -> usize {
+        self.windows = self.screens;
+        self.windows
+    }
+
+    fn apply_capture_exclusion(&mut self) {
+        self.capture_excluded = self.prefs.exclude_from_capture;
+    }
+
+    fn cursor_position(&self) -> (f64, f64) {
+        self.input.pointer
+    }
+
+    fn toggle_overlay(&mut self) {
+        self.input.visible = !self.input.visible;
+    }
+
+    /// The readout shows the pointer of `input` on a single display.
+    fn toggle_coordinates(&mut self) {
+        self.input.coordinates = match self.input.coordinates {
+            Some(_) => None,
+            None => Some(coordinates_label(self.cursor_position(), &[], false)),
+        };
+    }
+
+    fn toggle_color_sampler(&mut self) {
+        self.input.color_sample = match self.input.color_sample {
+            Some(_) => None,
+            None => Some(self.screen_color),
+        };
+    }
+
+    /// Like the platforms, the ruler only starts while the overlay is
+    /// shown, as its clicks would otherwise vanish without a trace.
+    fn toggle_ruler(&mut self) {
+        self.input.ruler = match self.input.ruler {
+            Some(_) => None,
+            None if self.input.visible => Some(Ruler::new()),
+            None => None,
+        };
+    }
+
+    fn clear_ruler(&mut self) {
+        self.input.ruler = None;
+    }
+
+    fn toggle_grid(&mut self) {
+        self.input.grid = !self.input.grid;
+    }
+
+    /// There is no clock: a letter stays up until the next mode.
+    fn show_display_mode(&mut self, mode: DisplayMode) {
+        self.input.display_mode = mode.code();
+    }
+
+    fn reinstall_hotkeys(&mut self) {
+        let bindings = profile_bindings(&self.prefs.hotkey_profile, self.platform);
+        register_bindings(&mut self.keys, bindings);
+    }
+
+    fn refresh_menus(&mut self) {
+        self.menu_refreshes += 1;
+    }
+
+    fn redraw(&mut self) {
+        self.frames.push(compose_frame(&self.state, &self.input));
+    }
+
+    fn system_locale(&self) -> Option<String> {
+        self.locale.clone()
+    }
+}
@@ -0,0 +1,124 @@
+//! Global hotkeys, over the registration API of a platform.
+//!
+//! A [`HotkeyManager`] registers single [`Binding`]s with the OS (Carbon
+//! hotkeys on macOS, `RegisterHotKey` on Windows) and reports which are
+//! registered. Installing a profile, rebinding one command and finding
+//! the command a hotkey clashes with are written once here against the
+//! trait; [`HeadlessHotkeys`](super::HeadlessHotkeys) keeps the
+//! registrations in memory for tests.
+
+use std::fmt;
+
+use tracing::{debug, info, warn};
+
+use crate::model::{hotkey_conflict, Binding, Hotkey, HotkeyAction};
+
+/// Called with the action of a registered hotkey when it is pressed.
+pub type TriggerHandler = fn(HotkeyAction);
+
+/// Why a hotkey could not be registered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HotkeyError {
+    /// The platform has no key code for the key (F1-F12 on macOS).
+    Unsupported(Hotkey),
+    /// The OS refused it, most likely because another app has it.
+    Refused { hotkey: Hotkey, reason: String },
+    /// Another command of the app is bound to it.
+    Taken { hotkey: Hotkey, by: HotkeyAction },
+}
+
+impl fmt::Display for HotkeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HotkeyError::Unsupported(hotkey) => {
+                write!(f, "{} cannot be registered here", hotkey.label())
+            }
+            HotkeyError::Refused { hotkey, reason } => write!(
+                f,
+                "{} refused (taken by another app?): {}",
+                hotkey.label(),
+                reason
+            ),
+            HotkeyError::Taken { hotkey, by } => {
+                write!(f, "{} is already bound to {}", hotkey.label(), by.id())
+            }
+        }
+    }
+}
+
+impl std::error::Error for HotkeyError {}
+
+/// Global hotkeys of one platform, one per command.
+pub trait HotkeyManager {
+    /// Register `binding`, in place of the hotkey its action had.
+    fn register(&mut self, binding: Binding) -> Result<(), HotkeyError>;
+
+    /// Unregister the hotkey of `action`; does nothing if it has none.
+    fn unregister(&mut self, action: HotkeyAction);
+
+    /// Hotkeys registered right now.
+    fn list(&self) -> Vec<Binding>;
+
+    /// Call `handler` when a registered hotkey is pressed, instead of
+    /// publishing the event of its action.
+    fn on_trigger(&mut self, handler: TriggerHandler);
+}
+
+/// Unregister every hotkey of the app.
+pub fn unregister_all(manager: &mut dyn HotkeyManager) {
+    for action in HotkeyAction::ALL {
+        manager.unregister(action);
+    }
+}
+
+/// Replace the registered hotkeys with `bindings` (a profile, with the
+/// rebinds in place). Hotkeys the OS refuses are logged and skipped;
+/// returns how many were registered.
+pub fn register_bindings(manager: &mut dyn HotkeyManager, bindings: &[Binding]) -> usize {
+    unregister_all(manager);
+    let mut registered = 0;
+    for binding in bindings {
+        match manager.register(*binding) {
+            Ok(()) => {
+                registered += 1;
+                debug!(action = ?binding.action, "Registered {}", binding.hotkey.label());
+            }
+            Err(e) => warn!(action = ?binding.action, "{}", e),
+        }
+    }
+    info!("{} hotkeys registered", registered);
+    registered
+}
+
+/// Is a hotkey registered for `action`?
+pub fn is_registered(manager: &dyn HotkeyManager, action: HotkeyAction) -> bool {
+    manager.list().iter().any(|b| b.action == action)
+}
+
+/// Command other than `action` whose registered hotkey is `hotkey`.
+pub fn conflict(
+    manager: &dyn HotkeyManager,
+    action: HotkeyAction,
+    hotkey: Hotkey,
+) -> Option<HotkeyAction> {
+    hotkey_conflict(&manager.list(), action, hotkey)
+}
+
+/// Move `action` to `hotkey`, unless another command has it. If the OS
+/// refuses the new hotkey, the old one is registered again.
+pub fn rebind(
+    manager: &mut dyn HotkeyManager,
+    action: HotkeyAction,
+    hotkey: Hotkey,
+) -> Result<(), HotkeyError> {
+    if let Some(by) = conflict(manager, action, hotkey) {
+        return Err(HotkeyError::Taken { hotkey, by });
+    }
+    let old = manager.list().into_iter().find(|b| b.action == action);
+    manager.unregister(action);
+    let result = manager.register(Binding { action, hotkey });
+    if let (Err(_), Some(old)) = (&result, old) {
+        let _ = manager.register(old);
+    }
+    result
+}
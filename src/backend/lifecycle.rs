@@ -0,0 +1,37 @@
+//! Bringing the overlay windows up and back in line with the settings.
+//!
+//! The persisted settings reach the overlay windows at startup, when the
+//! settings file is edited and after every settings change.
+//! [`start_overlay`] and [`load_settings`] do it for both platforms,
+//! against their [`OverlayBackend`].
+
+use super::OverlayBackend;
+use crate::i18n;
+use crate::model::OverlayState;
+
+/// Apply the persisted settings: the language, the overlay state and the
+/// capture exclusion. Returns the state applied.
+///
+/// Menus are not relabeled and nothing is redrawn; callers do so when the
+/// menus already exist and the windows are shown.
+pub fn load_settings(backend: &mut dyn OverlayBackend) -> OverlayState {
+    let state = backend.load_state();
+    // LANG_SYSTEM (the default) follows the OS language; other values
+    // index i18n::languages() and unknown ones fall back to English
+    i18n::set_language(&i18n::resolve_language(
+        state.lang,
+        backend.system_locale().as_deref(),
+    ));
+    backend.apply_state(&state);
+    backend.apply_capture_exclusion();
+    state
+}
+
+/// Fit the overlay windows to the screens and load the settings into
+/// them, before the menus and hotkeys are installed. Returns how many
+/// windows there are.
+pub fn start_overlay(backend: &mut dyn OverlayBackend) -> usize {
+    let windows = backend.sync_windows();
+    load_settings(backend);
+    windows
+}
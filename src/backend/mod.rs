@@ -0,0 +1,110 @@
+//! Overlay backends: what the event dispatcher needs from a platform.
+//!
+//! The handling of events that only touch the preferences, the hotkeys and
+//! the overlay state is written once in [`dispatch`] against the
+//! [`OverlayBackend`] trait. macOS and Windows implement it over their
+//! native windows and storage; [`HeadlessBackend`] keeps everything in
+//! memory and records what would be drawn, so tests can run the dispatcher,
+//! the hotkey profiles and the state sync without a GUI.
+//!
+//! The other events (windows, dialogs, zoom, updates) are handled by
+//! [`dispatch_pending`] through a platform's [`DispatchContext`].
+//!
+//! Every settings change, from an event or from the Settings window, goes
+//! through [`run_command`] (see [`command`]). Global hotkeys are
+//! registered through a platform's [`HotkeyManager`] (see [`hotkeys`]).
+//! Startup and settings reloads bring the windows up to date through
+//! [`start_overlay`] and [`load_settings`] (see [`lifecycle`]).
+
+pub mod command;
+pub mod context;
+pub mod dispatch;
+pub mod headless;
+pub mod hotkeys;
+pub mod lifecycle;
+pub mod scene;
+
+pub use command::{run_command, AppCommand, CommandError, CommandTarget};
+pub use context::{dispatch_event, dispatch_pending, DispatchContext};
+pub use dispatch::{
+    dispatch, load_profile, select_hotkey_profile, select_look_profile, set_color, set_preference,
+};
+pub use headless::{HeadlessBackend, HeadlessHotkeys};
+pub use hotkeys::{
+    conflict, is_registered, rebind, register_bindings, unregister_all, HotkeyError, HotkeyManager,
+    TriggerHandler,
+};
+pub use lifecycle::{load_settings, start_overlay};
+pub use scene::{compose_frame, DrawCommand, FrameInput};
+
+use crate::model::{DisplayMode, OverlayState};
+
+/// Preferences, hotkeys, menus and overlay windows of one platform.
+///
+/// Setters follow the storage rules: writes to keys locked by the
+/// administrator are ignored.
+pub trait OverlayBackend {
+    /// Persisted settings, validated.
+    fn load_state(&self) -> OverlayState;
+
+    /// Store a numeric preference.
+    fn set_double(&mut self, key: &str, value: f64);
+
+    /// Store a string preference.
+    fn set_string(&mut self, key: &str, value: &str);
+
+    /// Write pending preference changes to disk.
+    fn flush(&mut self);
+
+    /// Push `state` to the overlay windows.
+    fn apply_state(&mut self, state: &OverlayState);
+
+    /// Create, move, resize or close overlay windows so they cover every
+    /// screen (one window per screen on macOS, one spanning them all on
+    /// Windows). Returns how many there are.
+    fn sync_windows(&mut self) -> usize;
+
+    /// Keep the overlay windows out of screen captures and recordings, or
+    /// let them be captured again, as the settings say.
+    fn apply_capture_exclusion(&mut self);
+
+    /// Pointer position on the desktop, in the coordinates of the
+    /// platform (points from the bottom left of the main screen on macOS,
+    /// pixels from the top left of the primary monitor on Windows).
+    fn cursor_position(&self) -> (f64, f64);
+
+    /// Show the overlay if hidden, hide it if shown.
+    fn toggle_overlay(&mut self);
+
+    /// Show the cursor coordinates readout if hidden, hide it if shown.
+    fn toggle_coordinates(&mut self);
+
+    /// Start sampling the screen color under the pointer, or stop.
+    fn toggle_color_sampler(&mut self);
+
+    /// Start the ruler mode, taking clicks to place the measured line, or
+    /// end it.
+    fn toggle_ruler(&mut self);
+
+    /// Clear the measured line and end the ruler mode.
+    fn clear_ruler(&mut self);
+
+    /// Show the alignment grid if hidden, hide it if shown.
+    fn toggle_grid(&mut self);
+
+    /// Draw `mode`: a button's letter stays up for the click hold time, as
+    /// after a click; the circle takes down a letter still shown.
+    fn show_display_mode(&mut self, mode: DisplayMode);
+
+    /// Register the hotkeys of the selected profile, replacing the old ones.
+    fn reinstall_hotkeys(&mut self);
+
+    /// Relabel the status bar or tray menu (language, checkmarks).
+    fn refresh_menus(&mut self);
+
+    /// Mark the overlay windows for drawing again.
+    fn redraw(&mut self);
+
+    /// Locale tag of the OS (e.g. `es-ES`), for the "System default" language.
+    fn system_locale(&self) -> Option<String>;
+}
@@ -0,0 +1,340 @@
+//! What one overlay frame draws, in order, without drawing it.
+//!
+//! Mirrors the layering of the native renderers (`drawRect:` on macOS,
+//! the Direct2D pass on Windows): magnified screen content first, then the
+//! alignment grid, the recording frame, the ruler, the touch rings, the
+//! highlight (over its glow), the hold ring, the click counter, the pen tip, the coordinates
+//! readout, the color sample and the debug label.
+//! Coordinates are the pointer's, before any magnification.
+
+use crate::i18n;
+use crate::model::animation::{transition_alpha, transition_scale};
+use crate::model::click_burst::badge_label;
+use crate::model::click_icon::{click_icon, ClickIcon};
+use crate::model::color_sampler::{sample_hex, Rgb8};
+use crate::model::constants::{DISPLAY_MODE_CIRCLE, DISPLAY_MODE_LEFT};
+use crate::model::pen::{pen_scale, pen_tip_color};
+use crate::model::pulse::pulse_radius;
+use crate::model::rainbow::rainbow_color;
+use crate::model::ruler::{ruler_label, Ruler, RulerLine};
+use crate::model::screen_frame::Rgba;
+use crate::model::touch::TouchRing;
+use crate::model::trail::TrailSegment;
+use crate::model::OverlayState;
+use crate::render::geometry::{fill_alpha, hold_ring_radius};
+
+/// One drawing operation of a frame.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawCommand {
+    /// Screen content magnified `level` times around `focus`.
+    Zoom { level: f64, focus: (f64, f64) },
+    /// Grid lines across the screen, from its top-left corner.
+    Grid {
+        spacing: f64,
+        subdivisions: i32,
+        color: Rgba,
+    },
+    /// Border along the edges of the screen.
+    ScreenFrame { color: Rgba },
+    /// Line measured by the ruler, labeled with its length.
+    Ruler { line: RulerLine, label: String },
+    /// Ring around a finger on the touchscreen.
+    TouchRing {
+        center: (f64, f64),
+        radius: f64,
+        border_width: f64,
+        stroke: Rgba,
+    },
+    /// Circle highlight around the pointer.
+    Circle {
+        center: (f64, f64),
+        radius: f64,
+        border_width: f64,
+        stroke: Rgba,
+        /// Opacity of the fill (stroke alpha less the fill transparency).
+        fill_alpha: f64,
+        halo: bool,
+        /// `GLOW_*` drawn behind the circle.
+        glow: i32,
+    },
+    /// Laser dot with its fading trail.
+    Laser {
+        center: (f64, f64),
+        radius: f64,
+        stroke: Rgba,
+        trail: Vec<TrailSegment>,
+        halo: bool,
+        glow: i32,
+    },
+    /// Click letter (L/R in the selected language, or the custom text).
+    Letter {
+        center: (f64, f64),
+        letter: String,
+        radius: f64,
+        stroke: Rgba,
+        halo: bool,
+        glow: i32,
+    },
+    /// Click icon drawn instead of the letter.
+    Icon {
+        center: (f64, f64),
+        icon: ClickIcon,
+        radius: f64,
+        stroke: Rgba,
+        halo: bool,
+        glow: i32,
+    },
+    /// Ring around the letter filling up while the button is held,
+    /// clockwise from 12 o'clock.
+    HoldRing {
+        center: (f64, f64),
+        radius: f64,
+        width: f64,
+        /// Filled fraction [0.0, 1.0].
+        progress: f64,
+        stroke: Rgba,
+        halo: bool,
+    },
+    /// Multi-click counter next to the letter.
+    Badge {
+        center: (f64, f64),
+        radius: f64,
+        label: String,
+        stroke: Rgba,
+        halo: bool,
+    },
+    /// Crosshair on the tip of the pen driving the pointer.
+    PenTip { center: (f64, f64), color: Rgba },
+    /// Cursor coordinates readout beside the highlight.
+    Coordinates {
+        center: (f64, f64),
+        radius: f64,
+        label: String,
+    },
+    /// Swatch and hex of the pixel under the pointer, while sampling.
+    ColorSample {
+        center: (f64, f64),
+        radius: f64,
+        color: Rgb8,
+        label: String,
+    },
+    /// Frame timing label of the debug overlay.
+    DebugLabel(String),
+}
+
+/// Per-frame input that is not part of the persisted state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameInput {
+    /// Pointer position.
+    pub pointer: (f64, f64),
+    /// Is the overlay shown (toggled by the hotkey)?
+    pub visible: bool,
+    /// `DISPLAY_MODE_*`: circle, or the letter of the button held.
+    pub display_mode: i32,
+    /// Clicks in the current burst.
+    pub click_count: u32,
+    /// Fill of the hold ring while a button is down [0.0, 1.0].
+    pub hold_progress: Option<f64>,
+    /// Opacity multiplier of the idle fade [0.0, 1.0].
+    pub fade_alpha: f64,
+    /// Radius multiplier of the speed size scaling (1.0 when off).
+    pub speed_scale: f64,
+    /// Progress of the show/hide transition [0.0, 1.0] (1.0 when shown).
+    pub transition: f64,
+    /// Pressure [0.0, 1.0] of the pen driving the pointer (0.0 while it
+    /// hovers), when it is a pen.
+    pub pen_pressure: Option<f64>,
+    /// Zoom level and focus, while zoomed in.
+    pub zoom: Option<(f64, (f64, f64))>,
+    /// Is the alignment grid on (toggled by the hotkey)?
+    pub grid: bool,
+    /// Recording-frame color of this screen.
+    pub frame_color: Rgba,
+    /// Laser trail segments, newest last.
+    pub trail: Vec<TrailSegment>,
+    /// Rings of the fingers on the touchscreen.
+    pub touches: Vec<TouchRing>,
+    /// Hue phase of the rainbow mode, while it animates.
+    pub rainbow_phase: Option<f64>,
+    /// Phase of the pulse, while it animates.
+    pub pulse_phase: Option<f64>,
+    /// Cursor coordinates readout, when it is on.
+    pub coordinates: Option<String>,
+    /// Screen color under the pointer, while the color sampler is on.
+    pub color_sample: Option<Rgb8>,
+    /// Points placed so far, while the ruler mode is on.
+    pub ruler: Option<Ruler>,
+    /// Debug overlay label, when it is on.
+    pub debug: Option<String>,
+}
+
+impl Default for FrameInput {
+    fn default() -> Self {
+        Self {
+            pointer: (0.0, 0.0),
+            visible: true,
+            display_mode: DISPLAY_MODE_CIRCLE,
+            click_count: 0,
+            hold_progress: None,
+            fade_alpha: 1.0,
+            speed_scale: 1.0,
+            transition: 1.0,
+            pen_pressure: None,
+            zoom: None,
+            grid: false,
+            frame_color: (0.0, 0.0, 0.0, 0.0),
+            trail: Vec::new(),
+            touches: Vec::new(),
+            rainbow_phase: None,
+            pulse_phase: None,
+            coordinates: None,
+            color_sample: None,
+            ruler: None,
+            debug: None,
+        }
+    }
+}
+
+/// Drawing operations of one frame, bottom to top.
+pub fn compose_frame(state: &OverlayState, input: &FrameInput) -> Vec<DrawCommand> {
+    let mut commands = Vec::new();
+    if let Some((level, focus)) = input.zoom {
+        commands.push(DrawCommand::Zoom { level, focus });
+    }
+    if !input.visible {
+        return commands;
+    }
+    if input.grid {
+        commands.push(DrawCommand::Grid {
+            spacing: state.grid_spacing,
+            subdivisions: state.grid_subdivisions,
+            color: state.grid_color(),
+        });
+    }
+    if state.screen_frame {
+        commands.push(DrawCommand::ScreenFrame {
+            color: input.frame_color,
+        });
+    }
+    if let Some(line) = input.ruler.and_then(|r| r.line(input.pointer)) {
+        commands.push(DrawCommand::Ruler {
+            line,
+            label: ruler_label(line, 1.0),
+        });
+    }
+
+    let center = input.pointer;
+    let (r, g, b, a) = match input.rainbow_phase {
+        Some(phase) => rainbow_color(state.stroke_color(), phase),
+        None => state.stroke_color(),
+    };
+    let stroke = (
+        r,
+        g,
+        b,
+        a * input.fade_alpha * transition_alpha(input.transition),
+    );
+    let radius = match input.pulse_phase {
+        Some(phase) => pulse_radius(state.radius, state.pulse_amplitude, phase),
+        None => state.radius,
+    } * input.speed_scale
+        * input.pen_pressure.map_or(1.0, pen_scale)
+        * transition_scale(input.transition);
+    for ring in &input.touches {
+        commands.push(DrawCommand::TouchRing {
+            center: (ring.x, ring.y),
+            radius: state.radius,
+            border_width: state.border_width,
+            stroke: (r, g, b, a * ring.alpha),
+        });
+    }
+    commands.push(match input.display_mode {
+        DISPLAY_MODE_CIRCLE if state.is_laser() => DrawCommand::Laser {
+            center,
+            radius,
+            stroke,
+            trail: input.trail.clone(),
+            halo: state.halo,
+            glow: state.glow,
+        },
+        DISPLAY_MODE_CIRCLE => DrawCommand::Circle {
+            center,
+            radius,
+            border_width: state.border_width,
+            stroke,
+            fill_alpha: fill_alpha(stroke.3, state.fill_transparency_pct),
+            halo: state.halo,
+            glow: state.glow,
+        },
+        mode => match click_icon(state.click_icon) {
+            Some(icon) => DrawCommand::Icon {
+                center,
+                icon: *icon,
+                radius,
+                stroke,
+                halo: state.halo,
+                glow: state.glow,
+            },
+            None => {
+                let left = mode == DISPLAY_MODE_LEFT;
+                DrawCommand::Letter {
+                    center,
+                    letter: i18n::click_label(state.custom_click_letter(left), left),
+                    radius,
+                    stroke,
+                    halo: state.halo,
+                    glow: state.glow,
+                }
+            }
+        },
+    });
+
+    if input.display_mode != DISPLAY_MODE_CIRCLE && state.hold_ring {
+        if let Some(progress) = input.hold_progress {
+            commands.push(DrawCommand::HoldRing {
+                center,
+                radius: hold_ring_radius(radius),
+                width: state.border_width,
+                progress,
+                stroke,
+                halo: state.halo,
+            });
+        }
+    }
+    if input.display_mode != DISPLAY_MODE_CIRCLE && state.click_burst {
+        if let Some(label) = badge_label(input.click_count) {
+            commands.push(DrawCommand::Badge {
+                center,
+                radius,
+                label,
+                stroke,
+                halo: state.halo,
+            });
+        }
+    }
+    if input.pen_pressure.is_some() {
+        commands.push(DrawCommand::PenTip {
+            center,
+            color: pen_tip_color(stroke),
+        });
+    }
+    if let Some(label) = &input.coordinates {
+        commands.push(DrawCommand::Coordinates {
+            center,
+            radius,
+            label: label.clone(),
+        });
+    }
+    if let Some(color) = input.color_sample {
+        commands.push(DrawCommand::ColorSample {
+            center,
+            radius,
+            color,
+            label: sample_hex(color),
+        });
+    }
+    if let Some(label) = &input.debug {
+        commands.push(DrawCommand::DebugLabel(label.clone()));
+    }
+    commands
+}
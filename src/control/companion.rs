@@ -0,0 +1,75 @@
+//! Companion sessions on the control endpoint's socket (std only, no FFI).
+
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::TcpStream;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::events::EventPublisher;
+use crate::model::constants::MAX_CONTROL_REQUEST;
+use crate::model::{state_changes, CompanionSession, ControlState};
+
+/// How often a session checks the app's state for changes to push.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Serve a companion until it leaves, fails to authenticate or the
+/// server stops. `pending` holds the bytes already read from `stream`.
+pub fn run_session(
+    mut stream: TcpStream,
+    mut pending: Vec<u8>,
+    token: &str,
+    state: &Mutex<ControlState>,
+    stop: &Arc<AtomicBool>,
+    publisher: &EventPublisher,
+) {
+    let _ = stream.set_read_timeout(Some(POLL_INTERVAL));
+    let snapshot = || state.lock().map(|s| s.clone()).unwrap_or_default();
+    let mut session = CompanionSession::new();
+    let mut sent = snapshot();
+    let mut chunk = [0u8; 1024];
+
+    loop {
+        while let Some(end) = pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = pending.drain(..=end).collect();
+            let current = snapshot();
+            let reply = session.handle(String::from_utf8_lossy(&line).trim(), token, &current);
+            // Pushes continue from the state the reply described
+            sent = current;
+            for event in reply.events {
+                publisher.publish(event);
+            }
+            if write_lines(&mut stream, &reply.lines).is_err() || reply.close {
+                return;
+            }
+        }
+        if pending.len() > MAX_CONTROL_REQUEST || stop.load(Ordering::Relaxed) {
+            return;
+        }
+
+        if session.is_watching() {
+            let current = snapshot();
+            let changes = state_changes(&sent, &current);
+            sent = current;
+            if write_lines(&mut stream, &changes).is_err() {
+                return;
+            }
+        }
+
+        match stream.read(&mut chunk) {
+            Ok(0) => return,
+            Ok(read) => pending.extend_from_slice(&chunk[..read]),
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(_) => return,
+        }
+    }
+}
+
+fn write_lines(stream: &mut TcpStream, lines: &[String]) -> io::Result<()> {
+    if lines.is_empty() {
+        return Ok(());
+    }
+    let text: String = lines.iter().map(|line| format!("{line}\n")).collect();
+    stream.write_all(text.as_bytes())?;
+    stream.flush()
+}
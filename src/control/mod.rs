@@ -0,0 +1,12 @@
+//! Local control endpoint server.
+//!
+//! Serves the API of [`crate::model::control`] and the companion protocol
+//! of [`crate::model::companion`] on `127.0.0.1` from background threads.
+//! The platform code starts it with [`configure`] when `control_server` is
+//! on, keeps its snapshot current with [`update_state`], and handles the
+//! events it publishes on the event bus like any other.
+
+pub mod companion;
+pub mod server;
+
+pub use server::{configure, is_running, new_token, update_state, ControlServer};
@@ -0,0 +1,299 @@
+//! HTTP server of the local control endpoint (std only, no FFI).
+
+use std::io::{self, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::events::{self, EventPublisher};
+use crate::model::constants::{
+    MAX_COMPANION_SESSIONS, MAX_CONTROL_CONNECTIONS, MAX_CONTROL_REQUEST,
+};
+use crate::model::{
+    body_length, handle_request, is_http_request, parse_request, ControlResponse, ControlState,
+};
+
+use super::companion::run_session;
+
+/// How long a client may take to send its whole request (or, for a
+/// companion, its first line).
+const REQUEST_DEADLINE: Duration = Duration::from_secs(2);
+
+/// One of a limited number of places, given back when dropped.
+struct Slot(Arc<AtomicUsize>);
+
+impl Slot {
+    /// Take a place if fewer than `max` of `taken` are in use.
+    fn take(taken: &Arc<AtomicUsize>, max: usize) -> Option<Self> {
+        taken
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n < max).then_some(n + 1)
+            })
+            .ok()?;
+        Some(Self(Arc::clone(taken)))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// What the connections of a server share.
+struct Shared {
+    token: String,
+    state: Arc<Mutex<ControlState>>,
+    stop: Arc<AtomicBool>,
+    publisher: EventPublisher,
+    connections: Arc<AtomicUsize>,
+    sessions: Arc<AtomicUsize>,
+}
+
+/// A running endpoint, listening on the loopback interface for HTTP
+/// requests and companion sessions.
+///
+/// Dropping it stops the server.
+pub struct ControlServer {
+    addr: SocketAddr,
+    token: String,
+    state: Arc<Mutex<ControlState>>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ControlServer {
+    /// Listen on `127.0.0.1:port` (0 picks a free port) and publish the
+    /// events of accepted requests with `publisher`.
+    pub fn start(port: u16, token: &str, publisher: EventPublisher) -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(ControlState::default()));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let shared = Arc::new(Shared {
+                token: token.to_string(),
+                state: Arc::clone(&state),
+                stop: Arc::clone(&stop),
+                publisher,
+                connections: Arc::new(AtomicUsize::new(0)),
+                sessions: Arc::new(AtomicUsize::new(0)),
+            });
+            thread::Builder::new()
+                .name("lumbus-control".into())
+                .spawn(move || {
+                    for stream in listener.incoming() {
+                        if shared.stop.load(Ordering::Relaxed) {
+                            break;
+                        }
+                        let Ok(stream) = stream else {
+                            continue;
+                        };
+                        // Past the limit the connection is just closed
+                        let Some(slot) = Slot::take(&shared.connections, MAX_CONTROL_CONNECTIONS)
+                        else {
+                            continue;
+                        };
+                        let shared = Arc::clone(&shared);
+                        let _ = thread::Builder::new()
+                            .name("lumbus-control-client".into())
+                            .spawn(move || serve(stream, slot, &shared));
+                    }
+                })?
+        };
+
+        Ok(Self {
+            addr,
+            token: token.to_string(),
+            state,
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    /// Address the server listens on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Replace the snapshot answered by `GET /state` and `GET /settings`.
+    pub fn set_state(&self, state: ControlState) {
+        if let Ok(mut current) = self.state.lock() {
+            *current = state;
+        }
+    }
+}
+
+impl Drop for ControlServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // Wake the blocking accept so the thread sees the flag
+        let _ = TcpStream::connect(self.addr);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Answer one connection, on a thread of its own holding `slot`: an HTTP
+/// request, or a companion session while fewer than
+/// `MAX_COMPANION_SESSIONS` are open. Errors only drop the connection.
+fn serve(mut stream: TcpStream, slot: Slot, shared: &Shared) {
+    let deadline = Instant::now() + REQUEST_DEADLINE;
+    let Some(raw) = read_first_line(&mut stream, deadline) else {
+        return;
+    };
+    let first_line = String::from_utf8_lossy(&raw);
+    if !is_http_request(first_line.lines().next().unwrap_or_default()) {
+        // A session counts against its own limit, not the connections'
+        drop(slot);
+        let Some(_session) = Slot::take(&shared.sessions, MAX_COMPANION_SESSIONS) else {
+            let _ = stream.write_all(b"ERR too many sessions\n");
+            return;
+        };
+        run_session(
+            stream,
+            raw,
+            &shared.token,
+            &shared.state,
+            &shared.stop,
+            &shared.publisher,
+        );
+        return;
+    }
+
+    let response = match read_request(&mut stream, raw, deadline) {
+        Some(raw) => match parse_request(&raw) {
+            Some(request) => {
+                let state = shared.state.lock().map(|s| s.clone()).unwrap_or_default();
+                handle_request(&request, &shared.token, &state)
+            }
+            None => bad_request(),
+        },
+        None => bad_request(),
+    };
+    for event in &response.events {
+        shared.publisher.publish(event.clone());
+    }
+    let _ = write_response(&mut stream, &response);
+}
+
+fn bad_request() -> ControlResponse {
+    ControlResponse {
+        status: 400,
+        body: r#"{"error":"malformed request"}"#.to_string(),
+        events: Vec::new(),
+    }
+}
+
+/// Read some bytes into `chunk` before `deadline`; `None` once it has
+/// passed, on an error, or at the end of the stream.
+fn read_before(stream: &mut TcpStream, chunk: &mut [u8], deadline: Instant) -> Option<usize> {
+    let left = deadline.checked_duration_since(Instant::now())?;
+    if left.is_zero() {
+        return None;
+    }
+    stream.set_read_timeout(Some(left)).ok()?;
+    stream.read(chunk).ok().filter(|&read| read > 0)
+}
+
+/// Read until the end of the first line, up to `MAX_CONTROL_REQUEST`
+/// bytes, before `deadline`.
+fn read_first_line(stream: &mut TcpStream, deadline: Instant) -> Option<Vec<u8>> {
+    let mut raw = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !raw.contains(&b'\n') {
+        let read = read_before(stream, &mut chunk, deadline)?;
+        if raw.len() + read > MAX_CONTROL_REQUEST {
+            return None;
+        }
+        raw.extend_from_slice(&chunk[..read]);
+    }
+    Some(raw)
+}
+
+/// Read the rest of the headers and the body they announce, after the
+/// bytes already in `raw`, up to `MAX_CONTROL_REQUEST` bytes, before
+/// `deadline`.
+fn read_request(stream: &mut TcpStream, mut raw: Vec<u8>, deadline: Instant) -> Option<String> {
+    let mut chunk = [0u8; 4096];
+    loop {
+        let text = String::from_utf8_lossy(&raw);
+        if let Some(length) = body_length(&text) {
+            let head = text.find("\r\n\r\n")? + 4;
+            if raw.len() >= head + length {
+                raw.truncate(head + length);
+                return String::from_utf8(raw).ok();
+            }
+        }
+        let read = read_before(stream, &mut chunk, deadline)?;
+        if raw.len() + read > MAX_CONTROL_REQUEST {
+            return None;
+        }
+        raw.extend_from_slice(&chunk[..read]);
+    }
+}
+
+fn write_response(stream: &mut TcpStream, response: &ControlResponse) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.reason(),
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+/// The endpoint started by [`configure`].
+fn running() -> &'static Mutex<Option<ControlServer>> {
+    static RUNNING: OnceLock<Mutex<Option<ControlServer>>> = OnceLock::new();
+    RUNNING.get_or_init(|| Mutex::new(None))
+}
+
+/// Start, restart or stop the app's endpoint to match the settings.
+///
+/// Requires the global event bus. A server already running with the same
+/// port and token is left alone.
+pub fn configure(enabled: bool, port: u16, token: &str) -> io::Result<()> {
+    let Ok(mut running) = running().lock() else {
+        return Ok(());
+    };
+    if let Some(server) = running.as_ref() {
+        if enabled && server.addr.port() == port && server.token == token {
+            return Ok(());
+        }
+    }
+    // Stop first so a restart on the same port can bind it again
+    *running = None;
+    if enabled && !token.is_empty() {
+        *running = Some(ControlServer::start(port, token, events::publisher())?);
+    }
+    Ok(())
+}
+
+/// Is the app's endpoint running?
+pub fn is_running() -> bool {
+    running().lock().map(|r| r.is_some()).unwrap_or(false)
+}
+
+/// Update the snapshot of the app's endpoint, if it is running.
+pub fn update_state(state: ControlState) {
+    if let Ok(running) = running().lock() {
+        if let Some(server) = running.as_ref() {
+            server.set_state(state);
+        }
+    }
+}
+
+/// Random 32-digit hex token (128 bits) for a new endpoint, from the OS
+/// random source.
+pub fn new_token() -> io::Result<String> {
+    let mut bytes = [0u8; 16];
+    getrandom::fill(&mut bytes)?;
+    Ok(bytes.iter().map(|b| format!("{b:02x}")).collect())
+}
@@ -1,20 +1,250 @@
-//! Thread-safe event bus using mpsc channels.
+//! Thread-safe, bounded event bus.
 //!
 //! The bus provides a simple publish/subscribe mechanism where:
 //! - Any thread can publish events via `EventPublisher::publish()`
 //! - The main thread polls for events via `EventBus::drain()`
 //!
+//! Events wait in one of two lanes ([`EventPriority`]): quitting,
+//! restarting and opening the settings are taken before bulk events. An
+//! event published while an earlier one of the same kind is still queued
+//! combines with it ([`Coalesce`]): two toggles undo each other, a repeated
+//! reinstall is done once, the latest preference value wins.
+//!
+//! The queue holds at most a fixed number of events. When the main loop
+//! stalls while hotkeys, scripts or the control endpoint keep publishing,
+//! the [`OverflowPolicy`] decides which event goes, and the drops are
+//! counted (see [`QueueMetrics`]), instead of memory growing without end.
+//!
+//! Each event is timestamped when published, and the time it waits until
+//! the dispatcher takes it is kept per event name ([`EventLatency`]), so a
+//! slow dispatch ("settings opens seconds after Cmd+,") shows in the log
+//! and the diagnostics report.
+//!
 //! This is pure Rust with no external dependencies beyond std.
 
-use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use tracing::{debug, trace, warn};
+
+use super::types::{AppEvent, Coalesce, EventPriority};
+
+/// Events the bus holds by default before dropping any.
+pub const DEFAULT_CAPACITY: usize = 1024;
+
+/// Wait in the queue past which a taken event is logged as slow.
+pub const SLOW_EVENT: Duration = Duration::from_millis(250);
+
+/// What a full queue does with one more event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Drop the oldest queued event to make room: the latest state wins.
+    /// A normal event is dropped itself rather than evict a high one.
+    #[default]
+    DropOldest,
+    /// Drop the new event: the queue keeps what came first.
+    DropNewest,
+}
+
+/// Point-in-time numbers of a bus's queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct QueueMetrics {
+    /// Events published and not yet taken.
+    pub depth: usize,
+    /// Deepest the queue has been.
+    pub high_water: usize,
+    /// Events dropped because the queue was full.
+    pub dropped: u64,
+    /// Events that combined with a queued one (see [`Coalesce`]).
+    pub coalesced: u64,
+    /// Most events the queue holds.
+    pub capacity: usize,
+}
+
+/// Time events of one name waited in the queue, from publishing to being
+/// taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventLatency {
+    /// Event name (see [`AppEvent::name`]).
+    pub name: &'static str,
+    /// Events taken.
+    pub count: u64,
+    /// Time they waited in all.
+    pub total: Duration,
+    /// Longest wait.
+    pub max: Duration,
+    /// Wait of the latest one.
+    pub last: Duration,
+}
+
+impl EventLatency {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            count: 0,
+            total: Duration::ZERO,
+            max: Duration::ZERO,
+            last: Duration::ZERO,
+        }
+    }
+
+    fn record(&mut self, wait: Duration) {
+        self.count += 1;
+        self.total += wait;
+        self.max = self.max.max(wait);
+        self.last = wait;
+    }
+
+    /// Average wait.
+    pub fn mean(&self) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        self.total.div_f64(self.count as f64)
+    }
+}
+
+/// An event and when it was published.
+#[derive(Debug)]
+struct Queued {
+    event: AppEvent,
+    published: Instant,
+}
+
+/// Queued events, by lane.
+#[derive(Debug, Default)]
+struct Lanes {
+    high: VecDeque<Queued>,
+    normal: VecDeque<Queued>,
+}
+
+impl Lanes {
+    fn len(&self) -> usize {
+        self.high.len() + self.normal.len()
+    }
+
+    fn lane(&mut self, priority: EventPriority) -> &mut VecDeque<Queued> {
+        match priority {
+            EventPriority::High => &mut self.high,
+            EventPriority::Normal => &mut self.normal,
+        }
+    }
+
+    /// Next event: the oldest high-priority one, else the oldest.
+    fn pop(&mut self) -> Option<Queued> {
+        self.high.pop_front().or_else(|| self.normal.pop_front())
+    }
+
+    /// Make room for an event of `priority` by dropping the oldest event,
+    /// normal ones first. A normal event never evicts a high one: with only
+    /// high ones queued, nothing is dropped and `false` is returned.
+    fn drop_oldest(&mut self, priority: EventPriority) -> bool {
+        if self.normal.pop_front().is_some() {
+            return true;
+        }
+        priority == EventPriority::High && self.high.pop_front().is_some()
+    }
+}
+
+/// Queue shared by a bus and its publishers.
+#[derive(Debug)]
+struct Shared {
+    lanes: Mutex<Lanes>,
+    capacity: usize,
+    policy: OverflowPolicy,
+    high_water: AtomicUsize,
+    dropped: AtomicU64,
+    coalesced: AtomicU64,
+    latency: Mutex<Vec<EventLatency>>,
+}
+
+impl Shared {
+    fn lanes(&self) -> MutexGuard<'_, Lanes> {
+        // A publisher panicking mid-push leaves consistent lanes
+        self.lanes.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn push(&self, event: AppEvent) {
+        trace!("Published {}", event.name());
+        let event = Queued {
+            event,
+            published: Instant::now(),
+        };
+        let mut lanes = self.lanes();
+        let lane = lanes.lane(event.event.priority());
+        if let Some(i) = lane
+            .iter()
+            .position(|queued| event.event.coalesces_with(&queued.event))
+        {
+            match event.event.coalescing() {
+                Coalesce::Cancel => {
+                    lane.remove(i);
+                    self.coalesced.fetch_add(2, Ordering::Relaxed);
+                    return;
+                }
+                Coalesce::Dedupe => {
+                    self.coalesced.fetch_add(1, Ordering::Relaxed);
+                    return;
+                }
+                Coalesce::Replace => {
+                    lane.remove(i);
+                    self.coalesced.fetch_add(1, Ordering::Relaxed);
+                }
+                Coalesce::Keep => {}
+            }
+        }
+        if lanes.len() >= self.capacity {
+            match self.policy {
+                OverflowPolicy::DropOldest => {
+                    let priority = event.event.priority();
+                    if lanes.drop_oldest(priority) {
+                        lanes.lane(priority).push_back(event);
+                    }
+                }
+                OverflowPolicy::DropNewest => {}
+            }
+            if self.dropped.fetch_add(1, Ordering::Relaxed) == 0 {
+                warn!(
+                    "Event queue full ({} events), dropping events",
+                    self.capacity
+                );
+            }
+        } else {
+            lanes.lane(event.event.priority()).push_back(event);
+        }
+        self.high_water.fetch_max(lanes.len(), Ordering::Relaxed);
+    }
 
-use super::types::AppEvent;
+    /// Record how long `queued` waited, and hand its event over.
+    fn take(&self, queued: Queued) -> AppEvent {
+        let wait = queued.published.elapsed();
+        let name = queued.event.name();
+        let ms = wait.as_secs_f64() * 1000.0;
+        if wait >= SLOW_EVENT {
+            warn!("Event {} waited {:.0} ms in the queue", name, ms);
+        } else {
+            debug!("Took {} after {:.1} ms", name, ms);
+        }
+        let mut latency = self.latency.lock().unwrap_or_else(|e| e.into_inner());
+        match latency.iter_mut().find(|l| l.name == name) {
+            Some(l) => l.record(wait),
+            None => {
+                let mut l = EventLatency::new(name);
+                l.record(wait);
+                latency.push(l);
+            }
+        }
+        queued.event
+    }
+}
 
 /// Thread-safe event bus for application-wide event distribution.
 ///
-/// Uses a multi-producer, single-consumer (mpsc) channel internally.
 /// Multiple publishers can send events concurrently, and a single
-/// consumer (the main thread) receives and processes them.
+/// consumer (the main thread) receives and processes them, high-priority
+/// ones first and otherwise oldest first.
 ///
 /// # Example
 ///
@@ -29,16 +259,32 @@ use super::types::AppEvent;
 /// let events = bus.drain();
 /// assert_eq!(events.len(), 1);
 /// ```
+#[derive(Debug)]
 pub struct EventBus {
-    sender: Sender<AppEvent>,
-    receiver: Receiver<AppEvent>,
+    shared: Arc<Shared>,
 }
 
 impl EventBus {
-    /// Create a new event bus.
+    /// Create a new event bus holding up to [`DEFAULT_CAPACITY`] events,
+    /// dropping the oldest when full.
     pub fn new() -> Self {
-        let (sender, receiver) = mpsc::channel();
-        Self { sender, receiver }
+        Self::bounded(DEFAULT_CAPACITY, OverflowPolicy::default())
+    }
+
+    /// Create a new event bus holding up to `capacity` events (at least
+    /// one), with `policy` deciding what goes when it is full.
+    pub fn bounded(capacity: usize, policy: OverflowPolicy) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                lanes: Mutex::new(Lanes::default()),
+                capacity: capacity.max(1),
+                policy,
+                high_water: AtomicUsize::new(0),
+                dropped: AtomicU64::new(0),
+                coalesced: AtomicU64::new(0),
+                latency: Mutex::new(Vec::new()),
+            }),
+        }
     }
 
     /// Get a publisher handle that can be cloned and sent to other threads.
@@ -47,7 +293,7 @@ impl EventBus {
     /// to emit events should hold its own publisher.
     pub fn publisher(&self) -> EventPublisher {
         EventPublisher {
-            sender: self.sender.clone(),
+            shared: Arc::clone(&self.shared),
         }
     }
 
@@ -56,15 +302,8 @@ impl EventBus {
     /// Returns `Some(event)` if an event is available, `None` otherwise.
     /// This should be called from the main thread's event loop.
     pub fn try_recv(&self) -> Option<AppEvent> {
-        match self.receiver.try_recv() {
-            Ok(event) => Some(event),
-            Err(TryRecvError::Empty) => None,
-            Err(TryRecvError::Disconnected) => {
-                // All senders dropped - this shouldn't happen in normal operation
-                // but we handle it gracefully
-                None
-            }
-        }
+        let queued = self.shared.lanes().pop()?;
+        Some(self.shared.take(queued))
     }
 
     /// Drain all pending events into a Vec.
@@ -72,21 +311,43 @@ impl EventBus {
     /// This is the preferred method for processing events in the main loop.
     /// It collects all available events at once, allowing batch processing.
     pub fn drain(&self) -> Vec<AppEvent> {
-        let mut events = Vec::new();
-        while let Some(event) = self.try_recv() {
-            events.push(event);
-        }
-        events
+        let queued: Vec<Queued> = {
+            let mut lanes = self.shared.lanes();
+            let lanes = &mut *lanes;
+            lanes.high.drain(..).chain(lanes.normal.drain(..)).collect()
+        };
+        queued.into_iter().map(|q| self.shared.take(q)).collect()
     }
 
     /// Check if there are any pending events without consuming them.
     ///
-    /// Note: Due to the nature of mpsc channels, this is a point-in-time check.
-    /// Events may arrive between checking and draining.
+    /// This is a point-in-time check: events may arrive between checking
+    /// and draining.
     pub fn has_pending(&self) -> bool {
-        // We can't peek without consuming, so we try_recv and re-send
-        // This is a limitation of mpsc - for this app it's fine to just drain
-        false // Conservative: always return false, let drain handle it
+        self.shared.lanes().len() > 0
+    }
+
+    /// Current depth, high-water mark and drops of the queue.
+    pub fn metrics(&self) -> QueueMetrics {
+        QueueMetrics {
+            depth: self.shared.lanes().len(),
+            high_water: self.shared.high_water.load(Ordering::Relaxed),
+            dropped: self.shared.dropped.load(Ordering::Relaxed),
+            coalesced: self.shared.coalesced.load(Ordering::Relaxed),
+            capacity: self.shared.capacity,
+        }
+    }
+
+    /// Queue latency of the events taken so far, by name.
+    pub fn latency(&self) -> Vec<EventLatency> {
+        let mut latency = self
+            .shared
+            .latency
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        latency.sort_by_key(|l| l.name);
+        latency
     }
 }
 
@@ -99,30 +360,21 @@ impl Default for EventBus {
 /// A cloneable, thread-safe event publisher.
 ///
 /// Multiple modules can hold publishers and send events concurrently.
-/// Cloning a publisher is cheap (just clones the internal Sender).
-#[derive(Clone)]
+/// Cloning a publisher is cheap (just clones a reference to the queue).
+#[derive(Debug, Clone)]
 pub struct EventPublisher {
-    sender: Sender<AppEvent>,
+    shared: Arc<Shared>,
 }
 
 impl EventPublisher {
-    /// Create a publisher from an existing sender.
-    ///
-    /// Used by the global access module to create publishers from the static sender.
-    pub fn from_sender(sender: Sender<AppEvent>) -> Self {
-        Self { sender }
-    }
-
     /// Publish an event to the bus.
     ///
     /// This is non-blocking and thread-safe. The event will be queued
-    /// and processed on the next drain cycle in the main thread.
-    ///
-    /// If the receiver has been dropped (app shutting down), the send
-    /// silently fails - this is intentional.
+    /// (or combined with a queued one) and processed on the next drain
+    /// cycle in the main thread. When the queue is full, the bus's
+    /// [`OverflowPolicy`] drops an event.
     pub fn publish(&self, event: AppEvent) {
-        // Ignore send errors - receiver dropped means app is shutting down
-        let _ = self.sender.send(event);
+        self.shared.push(event);
     }
 }
 
@@ -157,11 +409,12 @@ mod tests {
         publisher.publish(AppEvent::OpenSettings);
         publisher.publish(AppEvent::RequestQuit);
 
+        // Settings and quit jump ahead of the toggle
         let events = bus.drain();
         assert_eq!(events.len(), 3);
-        assert_eq!(events[0], AppEvent::ToggleOverlay);
-        assert_eq!(events[1], AppEvent::OpenSettings);
-        assert_eq!(events[2], AppEvent::RequestQuit);
+        assert_eq!(events[0], AppEvent::OpenSettings);
+        assert_eq!(events[1], AppEvent::RequestQuit);
+        assert_eq!(events[2], AppEvent::ToggleOverlay);
     }
 
     #[test]
@@ -219,10 +472,10 @@ mod tests {
         let publisher = bus.publisher();
 
         publisher.publish(AppEvent::ToggleOverlay);
-        publisher.publish(AppEvent::OpenSettings);
+        publisher.publish(AppEvent::ZoomIn);
 
         assert_eq!(bus.try_recv(), Some(AppEvent::ToggleOverlay));
-        assert_eq!(bus.try_recv(), Some(AppEvent::OpenSettings));
+        assert_eq!(bus.try_recv(), Some(AppEvent::ZoomIn));
         assert_eq!(bus.try_recv(), None);
     }
 
@@ -240,11 +493,11 @@ mod tests {
         let bus = EventBus::new();
         let publisher = bus.publisher();
 
-        // Test all event types round-trip correctly
+        // Test all event types round-trip correctly (high priority first)
         let test_events = vec![
-            AppEvent::ToggleOverlay,
             AppEvent::OpenSettings,
             AppEvent::RequestQuit,
+            AppEvent::ToggleOverlay,
             AppEvent::SettingsClosed,
             AppEvent::QuitCancelled,
             AppEvent::ReinstallHotkeys,
@@ -257,4 +510,179 @@ mod tests {
         let received = bus.drain();
         assert_eq!(received, test_events);
     }
+
+    #[test]
+    fn test_metrics_track_queue_depth() {
+        let bus = EventBus::new();
+        let publisher = bus.publisher();
+
+        publisher.publish(AppEvent::ToggleOverlay);
+        publisher.publish(AppEvent::ZoomIn);
+        let metrics = bus.metrics();
+        assert_eq!(metrics.depth, 2);
+        assert_eq!(metrics.capacity, DEFAULT_CAPACITY);
+        assert!(bus.has_pending());
+
+        assert_eq!(bus.drain().len(), 2);
+        let metrics = bus.metrics();
+        assert_eq!(metrics.depth, 0);
+        assert_eq!(metrics.high_water, 2);
+        assert_eq!(metrics.dropped, 0);
+        assert!(!bus.has_pending());
+    }
+
+    #[test]
+    fn test_high_priority_events_go_first() {
+        let bus = EventBus::new();
+        let publisher = bus.publisher();
+
+        publisher.publish(AppEvent::ZoomIn);
+        publisher.publish(AppEvent::ToggleGrid);
+        publisher.publish(AppEvent::RequestQuit);
+
+        assert_eq!(bus.try_recv(), Some(AppEvent::RequestQuit));
+        assert_eq!(bus.try_recv(), Some(AppEvent::ZoomIn));
+        assert_eq!(bus.try_recv(), Some(AppEvent::ToggleGrid));
+    }
+
+    #[test]
+    fn test_repeated_toggles_cancel_out() {
+        let bus = EventBus::new();
+        let publisher = bus.publisher();
+
+        publisher.publish(AppEvent::ToggleOverlay);
+        publisher.publish(AppEvent::ZoomIn);
+        publisher.publish(AppEvent::ToggleOverlay);
+        publisher.publish(AppEvent::ToggleOverlay);
+
+        assert_eq!(bus.drain(), vec![AppEvent::ZoomIn, AppEvent::ToggleOverlay]);
+        assert_eq!(bus.metrics().coalesced, 2);
+    }
+
+    #[test]
+    fn test_repeated_reinstall_is_queued_once() {
+        let bus = EventBus::new();
+        let publisher = bus.publisher();
+
+        publisher.publish(AppEvent::ReinstallHotkeys);
+        publisher.publish(AppEvent::ReinstallHotkeys);
+
+        assert_eq!(bus.drain(), vec![AppEvent::ReinstallHotkeys]);
+        // Taken events no longer coalesce
+        publisher.publish(AppEvent::ReinstallHotkeys);
+        assert_eq!(bus.drain(), vec![AppEvent::ReinstallHotkeys]);
+    }
+
+    #[test]
+    fn test_latest_preference_value_wins() {
+        let bus = EventBus::new();
+        let publisher = bus.publisher();
+
+        publisher.publish(AppEvent::SetPreference("radius".into(), 40.0));
+        publisher.publish(AppEvent::SetPreference("borderWidth".into(), 3.0));
+        publisher.publish(AppEvent::SetPreference("radius".into(), 60.0));
+
+        assert_eq!(
+            bus.drain(),
+            vec![
+                AppEvent::SetPreference("borderWidth".into(), 3.0),
+                AppEvent::SetPreference("radius".into(), 60.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_full_queue_drops_oldest() {
+        let bus = EventBus::bounded(2, OverflowPolicy::DropOldest);
+        let publisher = bus.publisher();
+
+        publisher.publish(AppEvent::ToggleOverlay);
+        publisher.publish(AppEvent::ZoomIn);
+        publisher.publish(AppEvent::ZoomOut);
+
+        assert_eq!(bus.metrics().dropped, 1);
+        assert_eq!(bus.drain(), vec![AppEvent::ZoomIn, AppEvent::ZoomOut]);
+    }
+
+    #[test]
+    fn test_normal_event_does_not_evict_high_ones() {
+        let bus = EventBus::bounded(2, OverflowPolicy::DropOldest);
+        let publisher = bus.publisher();
+
+        publisher.publish(AppEvent::RequestQuit);
+        publisher.publish(AppEvent::Restart);
+        publisher.publish(AppEvent::ToggleOverlay);
+
+        assert_eq!(bus.metrics().dropped, 1);
+        assert_eq!(bus.drain(), vec![AppEvent::RequestQuit, AppEvent::Restart]);
+    }
+
+    #[test]
+    fn test_full_queue_drops_newest() {
+        let bus = EventBus::bounded(2, OverflowPolicy::DropNewest);
+        let publisher = bus.publisher();
+
+        publisher.publish(AppEvent::ToggleOverlay);
+        publisher.publish(AppEvent::ZoomIn);
+        publisher.publish(AppEvent::ZoomOut);
+
+        let metrics = bus.metrics();
+        assert_eq!(metrics.dropped, 1);
+        assert_eq!(metrics.high_water, 2);
+        assert_eq!(bus.drain(), vec![AppEvent::ToggleOverlay, AppEvent::ZoomIn]);
+    }
+
+    #[test]
+    fn test_zero_capacity_holds_one_event() {
+        let bus = EventBus::bounded(0, OverflowPolicy::DropOldest);
+        bus.publisher().publish(AppEvent::ZoomIn);
+        assert_eq!(bus.metrics().capacity, 1);
+        assert_eq!(bus.drain(), vec![AppEvent::ZoomIn]);
+    }
+
+    #[test]
+    fn test_latency_is_kept_per_event_name() {
+        let bus = EventBus::new();
+        let publisher = bus.publisher();
+
+        publisher.publish(AppEvent::ZoomIn);
+        publisher.publish(AppEvent::ZoomIn);
+        publisher.publish(AppEvent::OpenSettings);
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(bus.try_recv().is_some());
+        bus.drain();
+
+        let latency = bus.latency();
+        let names: Vec<_> = latency.iter().map(|l| (l.name, l.count)).collect();
+        assert_eq!(names, vec![("open_settings", 1), ("zoom_in", 2)]);
+        for l in &latency {
+            assert!(l.max >= Duration::from_millis(5));
+            assert!(l.mean() <= l.max);
+            assert!(l.last <= l.max);
+        }
+    }
+
+    #[test]
+    fn test_coalesced_events_are_not_timed() {
+        let bus = EventBus::new();
+        let publisher = bus.publisher();
+
+        publisher.publish(AppEvent::ToggleOverlay);
+        publisher.publish(AppEvent::ToggleOverlay);
+        bus.drain();
+
+        assert!(bus.latency().is_empty());
+    }
+
+    #[test]
+    fn test_mean_latency() {
+        let mut latency = EventLatency::new("zoom_in");
+        assert_eq!(latency.mean(), Duration::ZERO);
+        latency.record(Duration::from_millis(10));
+        latency.record(Duration::from_millis(30));
+        assert_eq!(latency.count, 2);
+        assert_eq!(latency.mean(), Duration::from_millis(20));
+        assert_eq!(latency.max, Duration::from_millis(30));
+        assert_eq!(latency.last, Duration::from_millis(30));
+    }
 }
@@ -6,8 +6,10 @@
 //!
 //! # Design
 //!
-//! - `Sender` is stored in `OnceLock` - it's `Send + Sync`, perfect for static
-//! - `Receiver` is stored in `Mutex` - only accessed from main thread, minimal overhead
+//! The [`EventBus`] is stored in a `OnceLock`: its queue is behind a
+//! `Mutex`, only locked for a push or a take, so contention is effectively
+//! zero. It holds [`DEFAULT_CAPACITY`](super::bus::DEFAULT_CAPACITY)
+//! events and drops the oldest when full.
 //!
 //! # Example
 //!
@@ -25,22 +27,23 @@
 //! publisher.publish(AppEvent::OpenSettings);
 //! ```
 
-use std::sync::mpsc::{self, Receiver, Sender};
-use std::sync::{Mutex, OnceLock};
+use std::sync::OnceLock;
 
-use super::bus::EventPublisher;
+use super::bus::{EventBus, EventLatency, EventPublisher, QueueMetrics};
 use super::types::AppEvent;
 
-/// Global sender for publishing events.
-///
-/// `Sender` is `Send + Sync`, so it can be safely stored in a static.
-static SENDER: OnceLock<Sender<AppEvent>> = OnceLock::new();
+/// The application's event bus.
+static BUS: OnceLock<EventBus> = OnceLock::new();
 
-/// Global receiver for draining events.
+/// The global bus.
+///
+/// # Panics
 ///
-/// Wrapped in `Mutex` for `Sync` requirement. Only accessed from main thread,
-/// so contention is effectively zero.
-static RECEIVER: OnceLock<Mutex<Receiver<AppEvent>>> = OnceLock::new();
+/// Panics if `init_event_bus()` has not been called.
+fn bus() -> &'static EventBus {
+    BUS.get()
+        .expect("Event bus not initialized - call init_event_bus() first")
+}
 
 /// Initialize the global event bus.
 ///
@@ -51,15 +54,8 @@ static RECEIVER: OnceLock<Mutex<Receiver<AppEvent>>> = OnceLock::new();
 ///
 /// Panics if the event bus has already been initialized.
 pub fn init_event_bus() {
-    let (sender, receiver) = mpsc::channel();
-
-    SENDER
-        .set(sender)
-        .expect("Event bus already initialized (sender)");
-
-    RECEIVER
-        .set(Mutex::new(receiver))
-        .expect("Event bus already initialized (receiver)");
+    BUS.set(EventBus::new())
+        .expect("Event bus already initialized");
 }
 
 /// Get a publisher handle for the global event bus.
@@ -71,11 +67,7 @@ pub fn init_event_bus() {
 ///
 /// Panics if `init_event_bus()` has not been called.
 pub fn publisher() -> EventPublisher {
-    let sender = SENDER
-        .get()
-        .expect("Event bus not initialized - call init_event_bus() first");
-
-    EventPublisher::from_sender(sender.clone())
+    bus().publisher()
 }
 
 /// Publish an event to the global event bus.
@@ -88,12 +80,7 @@ pub fn publisher() -> EventPublisher {
 ///
 /// Panics if `init_event_bus()` has not been called.
 pub fn publish(event: AppEvent) {
-    let sender = SENDER
-        .get()
-        .expect("Event bus not initialized - call init_event_bus() first");
-
-    // Ignore send errors - receiver dropped means app is shutting down
-    let _ = sender.send(event);
+    bus().publisher().publish(event);
 }
 
 /// Take a single event from the global event bus.
@@ -105,14 +92,8 @@ pub fn publish(event: AppEvent) {
 /// # Panics
 ///
 /// Panics if `init_event_bus()` has not been called.
-/// May also panic if the receiver mutex is poisoned.
 pub fn take_event() -> Option<AppEvent> {
-    let receiver = RECEIVER
-        .get()
-        .expect("Event bus not initialized - call init_event_bus() first");
-
-    let receiver = receiver.lock().expect("Event bus receiver mutex poisoned");
-    receiver.try_recv().ok()
+    bus().try_recv()
 }
 
 /// Drain all pending events from the global event bus.
@@ -123,19 +104,28 @@ pub fn take_event() -> Option<AppEvent> {
 /// # Panics
 ///
 /// Panics if `init_event_bus()` has not been called.
-/// May also panic if the receiver mutex is poisoned.
 pub fn drain_events() -> Vec<AppEvent> {
-    let receiver = RECEIVER
-        .get()
-        .expect("Event bus not initialized - call init_event_bus() first");
+    bus().drain()
+}
+
+/// Number of events published to the global bus and not yet taken.
+///
+/// Used by the soak test to watch the queue depth; returns 0 before
+/// `init_event_bus()`.
+pub fn pending_events() -> usize {
+    event_metrics().depth
+}
 
-    let receiver = receiver.lock().expect("Event bus receiver mutex poisoned");
+/// Depth, high-water mark and drops of the global bus's queue; all zero
+/// before `init_event_bus()`.
+pub fn event_metrics() -> QueueMetrics {
+    BUS.get().map(EventBus::metrics).unwrap_or_default()
+}
 
-    let mut events = Vec::new();
-    while let Ok(event) = receiver.try_recv() {
-        events.push(event);
-    }
-    events
+/// Queue latency of the events taken from the global bus so far, by name;
+/// empty before `init_event_bus()`.
+pub fn event_latency() -> Vec<EventLatency> {
+    BUS.get().map(EventBus::latency).unwrap_or_default()
 }
 
 #[cfg(test)]
@@ -144,7 +134,7 @@ mod tests {
     // OnceLock can only be set once per process. Instead, we test the
     // EventBus functionality directly in bus.rs tests.
     //
-    // The global functions are thin wrappers that delegate to mpsc,
+    // The global functions are thin wrappers that delegate to EventBus,
     // so testing EventBus provides sufficient coverage.
     //
     // Integration testing of the global access would require either:
@@ -1,11 +1,13 @@
 //! Event system for decoupled inter-module communication.
 //!
-//! This module provides a simple publish/subscribe mechanism using Rust's
-//! standard library `mpsc` channels. It enables:
+//! This module provides a simple publish/subscribe mechanism over a bounded
+//! queue (standard library only). It enables:
 //!
 //! - **Decoupled architecture**: Modules publish events without knowing who handles them
 //! - **Thread safety**: Multiple publishers can send events concurrently
 //! - **Testability**: Event types are pure Rust enums, easily testable without FFI
+//! - **Bounded memory**: A stalled main loop drops events (counted) rather than
+//!   queueing them without end
 //!
 //! # Architecture
 //!
@@ -19,7 +21,7 @@
 //!        ▼                   ▼                   ▼
 //! ┌─────────────────────────────────────────────────────┐
 //! │                     EventBus                        │
-//! │                   (mpsc channel)                    │
+//! │                  (bounded queue)                    │
 //! └─────────────────────────┬───────────────────────────┘
 //!                           │ drain()
 //!                           ▼
@@ -53,7 +55,7 @@
 //! # Module Structure
 //!
 //! - [`types`]: Event definitions (`AppEvent` enum)
-//! - [`bus`]: `EventBus` and `EventPublisher` types
+//! - [`bus`]: `EventBus`, `EventPublisher`, overflow policy and queue metrics
 //! - [`global`]: Static access functions
 
 pub mod bus;
@@ -61,6 +63,9 @@ pub mod global;
 pub mod types;
 
 // Re-export main types for convenient access
-pub use bus::{EventBus, EventPublisher};
-pub use global::{drain_events, init_event_bus, publish, publisher, take_event};
-pub use types::AppEvent;
+pub use bus::{EventBus, EventLatency, EventPublisher, OverflowPolicy, QueueMetrics};
+pub use global::{
+    drain_events, event_latency, event_metrics, init_event_bus, pending_events, publish, publisher,
+    take_event,
+};
+pub use types::{AppEvent, Coalesce, EventPriority};
@@ -3,12 +3,44 @@
 //! These events represent high-level application actions that can be
 //! published by any module and handled by the event dispatcher.
 //! This module is pure Rust with no FFI dependencies, making it fully testable.
+//!
+//! Events serialize to JSON as `{"event": "set_preference", "args": ["radius", 60.0]}`
+//! (`args` is left out for events without arguments), so they can be sent
+//! between processes, saved and compared in tests.
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::{DisplayMode, UpdateStatus};
+
+/// Lane of the event bus an event is queued in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventPriority {
+    /// Taken before any normal event (quit, restart, settings).
+    High,
+    /// Everything else, in order.
+    Normal,
+}
+
+/// How an event combines with an earlier one of the same kind that is
+/// still queued (see [`AppEvent::coalesces_with`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Coalesce {
+    /// Queued as is.
+    Keep,
+    /// A toggle: it undoes the queued one, and both go.
+    Cancel,
+    /// Doing it twice is doing it once: the queued one stays.
+    Dedupe,
+    /// The latest value wins: the queued one goes.
+    Replace,
+}
 
 /// Application-level events for decoupled communication between modules.
 ///
 /// Events flow from producers (hotkeys, UI, observers) through the EventBus
 /// to the dispatcher, which executes the appropriate actions.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", content = "args", rename_all = "snake_case")]
 pub enum AppEvent {
     // === Input Events ===
     /// Toggle overlay visibility (Ctrl+A)
@@ -20,12 +52,75 @@ pub enum AppEvent {
     /// Request application quit with confirmation dialog (Ctrl+Shift+X)
     RequestQuit,
 
+    /// Quit and start again, for options that only apply at startup and
+    /// for recovery (no confirmation)
+    Restart,
+
     /// Show About dialog
     ShowAbout,
 
     /// Show help overlay with keyboard shortcuts (Cmd+?)
     ShowHelp,
 
+    /// Magnify the screen around the cursor one step further (Ctrl+Shift+=)
+    ZoomIn,
+
+    /// Step the magnification back towards 1x (Ctrl+Shift+-)
+    ZoomOut,
+
+    /// Show or hide the cursor coordinates next to the highlight (Ctrl+Shift+M)
+    ToggleCoordinates,
+
+    /// Start or stop sampling the pixel color under the cursor (Ctrl+Shift+E)
+    ToggleColorSampler,
+
+    /// Start or stop the ruler mode, which measures between two clicks (Ctrl+Shift+U)
+    ToggleRuler,
+
+    /// Clear the measured line and end the ruler mode (Esc, while it is on)
+    ClearRuler,
+
+    /// Show or hide the alignment grid on the display under the cursor (Ctrl+Shift+G)
+    ToggleGrid,
+
+    /// Lock or unlock the settings for a presentation (Ctrl+Shift+L)
+    TogglePresentationLock,
+
+    /// Start or stop recording clicks for usability testing
+    ToggleClickRecording,
+
+    /// Export the recorded clicks as CSV (save dialog)
+    ExportClickLogCsv,
+
+    /// Export the recorded clicks as JSON (save dialog)
+    ExportClickLogJson,
+
+    /// Switch to the named hotkey profile (see `model::hotkeys`)
+    SelectHotkeyProfile(String),
+
+    /// Set a numeric preference (by key, e.g. `radius`) and apply it
+    SetPreference(String, f64),
+
+    /// Switch to a look of the Profile submenu (see `model::quick_adjust`).
+    SelectLookProfile(String),
+
+    /// Check the release feed for a new version now ("Check for updates")
+    CheckForUpdates,
+
+    // === Remote Control Events ===
+    /// Set the highlight radius and apply it
+    SetRadius(f64),
+
+    /// Set the highlight color (components in [0, 1]) and apply it
+    SetColor { r: f64, g: f64, b: f64, a: f64 },
+
+    /// Switch to the named hotkey profile, matched ignoring case; unknown
+    /// names are ignored
+    LoadProfile(String),
+
+    /// Draw the circle, or a button's letter as if it was just clicked
+    SetMode(DisplayMode),
+
     // === UI Lifecycle Events ===
     /// Settings window was closed by user
     SettingsClosed,
@@ -39,6 +134,9 @@ pub enum AppEvent {
     // === System Events ===
     /// Hotkeys need to be reinstalled (after sleep/wake, space change, etc.)
     ReinstallHotkeys,
+
+    /// An update check finished (`manual` when the user asked for it)
+    UpdateChecked { status: UpdateStatus, manual: bool },
 }
 
 impl AppEvent {
@@ -53,21 +151,138 @@ impl AppEvent {
                 | AppEvent::QuitCancelled
                 | AppEvent::HelpClosed
                 | AppEvent::ReinstallHotkeys
+                | AppEvent::SelectHotkeyProfile(_)
+                | AppEvent::LoadProfile(_)
         )
     }
 
+    /// Returns true if this event can change the highlight settings.
+    ///
+    /// These events are ignored while the presentation lock is on.
+    pub fn mutates_settings(&self) -> bool {
+        matches!(
+            self,
+            AppEvent::OpenSettings
+                | AppEvent::SetPreference(..)
+                | AppEvent::SelectLookProfile(_)
+                | AppEvent::SetRadius(_)
+                | AppEvent::SetColor { .. }
+        )
+    }
+
+    /// Lane of the event bus: quitting, restarting and opening the
+    /// settings go before bulk events.
+    pub fn priority(&self) -> EventPriority {
+        match self {
+            AppEvent::RequestQuit | AppEvent::Restart | AppEvent::OpenSettings => {
+                EventPriority::High
+            }
+            _ => EventPriority::Normal,
+        }
+    }
+
+    /// How this event combines with a queued one it
+    /// [coalesces with](Self::coalesces_with).
+    pub fn coalescing(&self) -> Coalesce {
+        match self {
+            AppEvent::ToggleOverlay
+            | AppEvent::ToggleCoordinates
+            | AppEvent::ToggleColorSampler
+            | AppEvent::ToggleGrid => Coalesce::Cancel,
+            AppEvent::ReinstallHotkeys | AppEvent::CheckForUpdates => Coalesce::Dedupe,
+            AppEvent::SetPreference(..)
+            | AppEvent::SelectLookProfile(_)
+            | AppEvent::SelectHotkeyProfile(_)
+            | AppEvent::SetRadius(_)
+            | AppEvent::SetColor { .. }
+            | AppEvent::LoadProfile(_) => Coalesce::Replace,
+            _ => Coalesce::Keep,
+        }
+    }
+
+    /// Does this event combine with the queued `other`? Only events of the
+    /// same kind do (for preferences, of the same key), and never those
+    /// that are kept as is.
+    pub fn coalesces_with(&self, other: &AppEvent) -> bool {
+        match (self, other) {
+            _ if self.coalescing() == Coalesce::Keep => false,
+            (AppEvent::SetPreference(key, _), AppEvent::SetPreference(other_key, _)) => {
+                key == other_key
+            }
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+
+    /// Name of the event, as in its JSON form (e.g. `open_settings`); the
+    /// event latency is reported per name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AppEvent::ToggleOverlay => "toggle_overlay",
+            AppEvent::OpenSettings => "open_settings",
+            AppEvent::RequestQuit => "request_quit",
+            AppEvent::Restart => "restart",
+            AppEvent::ShowAbout => "show_about",
+            AppEvent::ShowHelp => "show_help",
+            AppEvent::ZoomIn => "zoom_in",
+            AppEvent::ZoomOut => "zoom_out",
+            AppEvent::ToggleCoordinates => "toggle_coordinates",
+            AppEvent::ToggleColorSampler => "toggle_color_sampler",
+            AppEvent::ToggleRuler => "toggle_ruler",
+            AppEvent::ClearRuler => "clear_ruler",
+            AppEvent::ToggleGrid => "toggle_grid",
+            AppEvent::TogglePresentationLock => "toggle_presentation_lock",
+            AppEvent::ToggleClickRecording => "toggle_click_recording",
+            AppEvent::ExportClickLogCsv => "export_click_log_csv",
+            AppEvent::ExportClickLogJson => "export_click_log_json",
+            AppEvent::SelectHotkeyProfile(_) => "select_hotkey_profile",
+            AppEvent::SetPreference(..) => "set_preference",
+            AppEvent::SelectLookProfile(_) => "select_look_profile",
+            AppEvent::CheckForUpdates => "check_for_updates",
+            AppEvent::SetRadius(_) => "set_radius",
+            AppEvent::SetColor { .. } => "set_color",
+            AppEvent::LoadProfile(_) => "load_profile",
+            AppEvent::SetMode(_) => "set_mode",
+            AppEvent::SettingsClosed => "settings_closed",
+            AppEvent::QuitCancelled => "quit_cancelled",
+            AppEvent::HelpClosed => "help_closed",
+            AppEvent::ReinstallHotkeys => "reinstall_hotkeys",
+            AppEvent::UpdateChecked { .. } => "update_checked",
+        }
+    }
+
     /// Returns a human-readable description of the event for debugging.
     pub fn description(&self) -> &'static str {
         match self {
             AppEvent::ToggleOverlay => "Toggle overlay visibility",
             AppEvent::OpenSettings => "Open settings window",
             AppEvent::RequestQuit => "Request quit with confirmation",
+            AppEvent::Restart => "Restart the app",
             AppEvent::ShowAbout => "Show about dialog",
             AppEvent::ShowHelp => "Show help overlay",
+            AppEvent::ZoomIn => "Zoom in around the cursor",
+            AppEvent::ZoomOut => "Zoom out",
+            AppEvent::ToggleCoordinates => "Toggle cursor coordinates",
+            AppEvent::ToggleColorSampler => "Toggle color sampler",
+            AppEvent::ToggleRuler => "Toggle ruler",
+            AppEvent::ClearRuler => "Clear ruler",
+            AppEvent::ToggleGrid => "Toggle alignment grid",
+            AppEvent::TogglePresentationLock => "Toggle presentation lock",
+            AppEvent::ToggleClickRecording => "Toggle click recording",
+            AppEvent::ExportClickLogCsv => "Export click log as CSV",
+            AppEvent::ExportClickLogJson => "Export click log as JSON",
+            AppEvent::SelectHotkeyProfile(_) => "Select hotkey profile",
+            AppEvent::SetPreference(..) => "Set a preference",
+            AppEvent::SelectLookProfile(_) => "Select look profile",
+            AppEvent::CheckForUpdates => "Check for updates",
+            AppEvent::SetRadius(_) => "Set the highlight radius",
+            AppEvent::SetColor { .. } => "Set the highlight color",
+            AppEvent::LoadProfile(_) => "Load hotkey profile",
+            AppEvent::SetMode(_) => "Set the display mode",
             AppEvent::SettingsClosed => "Settings window closed",
             AppEvent::QuitCancelled => "Quit cancelled by user",
             AppEvent::HelpClosed => "Help overlay closed",
             AppEvent::ReinstallHotkeys => "Reinstall hotkeys",
+            AppEvent::UpdateChecked { .. } => "Update check finished",
         }
     }
 }
@@ -82,6 +297,7 @@ mod tests {
         assert!(AppEvent::QuitCancelled.requires_hotkey_reinstall());
         assert!(AppEvent::HelpClosed.requires_hotkey_reinstall());
         assert!(AppEvent::ReinstallHotkeys.requires_hotkey_reinstall());
+        assert!(AppEvent::SelectHotkeyProfile("presentation".into()).requires_hotkey_reinstall());
     }
 
     #[test]
@@ -89,8 +305,107 @@ mod tests {
         assert!(!AppEvent::ToggleOverlay.requires_hotkey_reinstall());
         assert!(!AppEvent::OpenSettings.requires_hotkey_reinstall());
         assert!(!AppEvent::RequestQuit.requires_hotkey_reinstall());
+        assert!(!AppEvent::Restart.requires_hotkey_reinstall());
         assert!(!AppEvent::ShowAbout.requires_hotkey_reinstall());
         assert!(!AppEvent::ShowHelp.requires_hotkey_reinstall());
+        assert!(!AppEvent::ZoomIn.requires_hotkey_reinstall());
+        assert!(!AppEvent::ZoomOut.requires_hotkey_reinstall());
+        assert!(!AppEvent::ToggleCoordinates.requires_hotkey_reinstall());
+        assert!(!AppEvent::ToggleColorSampler.requires_hotkey_reinstall());
+        assert!(!AppEvent::ToggleRuler.requires_hotkey_reinstall());
+        assert!(!AppEvent::ClearRuler.requires_hotkey_reinstall());
+        assert!(!AppEvent::ToggleGrid.requires_hotkey_reinstall());
+        assert!(!AppEvent::TogglePresentationLock.requires_hotkey_reinstall());
+        assert!(!AppEvent::ToggleClickRecording.requires_hotkey_reinstall());
+        assert!(!AppEvent::ExportClickLogCsv.requires_hotkey_reinstall());
+    }
+
+    #[test]
+    fn test_only_settings_events_mutate_settings() {
+        assert!(AppEvent::OpenSettings.mutates_settings());
+        assert!(AppEvent::SetPreference("radius".into(), 60.0).mutates_settings());
+        assert!(AppEvent::SelectLookProfile("high_contrast".into()).mutates_settings());
+        assert!(!AppEvent::TogglePresentationLock.mutates_settings());
+        assert!(!AppEvent::ToggleOverlay.mutates_settings());
+        assert!(!AppEvent::ShowHelp.mutates_settings());
+        assert!(!AppEvent::ZoomIn.mutates_settings());
+        assert!(!AppEvent::SettingsClosed.mutates_settings());
+        assert!(!AppEvent::SelectHotkeyProfile("presentation".into()).mutates_settings());
+        assert!(AppEvent::SetRadius(60.0).mutates_settings());
+        assert!(AppEvent::SetColor {
+            r: 0.0,
+            g: 0.0,
+            b: 1.0,
+            a: 1.0
+        }
+        .mutates_settings());
+        assert!(!AppEvent::LoadProfile("presentation".into()).mutates_settings());
+        assert!(!AppEvent::SetMode(DisplayMode::Right).mutates_settings());
+    }
+
+    #[test]
+    fn test_parameterized_events_serialize_with_their_data() {
+        let json = |event: AppEvent| serde_json::to_value(event).unwrap();
+        assert_eq!(
+            json(AppEvent::SetRadius(60.0)),
+            serde_json::json!({"event": "set_radius", "args": 60.0})
+        );
+        assert_eq!(
+            json(AppEvent::SetColor {
+                r: 1.0,
+                g: 0.5,
+                b: 0.0,
+                a: 1.0
+            }),
+            serde_json::json!({"event": "set_color", "args": {"r": 1.0, "g": 0.5, "b": 0.0, "a": 1.0}})
+        );
+        assert_eq!(
+            json(AppEvent::SetMode(DisplayMode::Left)),
+            serde_json::json!({"event": "set_mode", "args": "left"})
+        );
+        let event: AppEvent =
+            serde_json::from_str(r#"{"event": "load_profile", "args": "presentation"}"#).unwrap();
+        assert_eq!(event, AppEvent::LoadProfile("presentation".into()));
+    }
+
+    #[test]
+    fn test_quit_restart_and_settings_have_high_priority() {
+        assert_eq!(AppEvent::RequestQuit.priority(), EventPriority::High);
+        assert_eq!(AppEvent::Restart.priority(), EventPriority::High);
+        assert_eq!(AppEvent::OpenSettings.priority(), EventPriority::High);
+        assert_eq!(AppEvent::ToggleOverlay.priority(), EventPriority::Normal);
+        assert_eq!(AppEvent::QuitCancelled.priority(), EventPriority::Normal);
+        assert_eq!(
+            AppEvent::SetPreference("radius".into(), 60.0).priority(),
+            EventPriority::Normal
+        );
+    }
+
+    #[test]
+    fn test_coalescing_of_events() {
+        assert_eq!(AppEvent::ToggleOverlay.coalescing(), Coalesce::Cancel);
+        assert_eq!(AppEvent::ToggleColorSampler.coalescing(), Coalesce::Cancel);
+        assert_eq!(AppEvent::ReinstallHotkeys.coalescing(), Coalesce::Dedupe);
+        assert_eq!(
+            AppEvent::SelectLookProfile("standard".into()).coalescing(),
+            Coalesce::Replace
+        );
+        // Each step or dialog counts
+        assert_eq!(AppEvent::ZoomIn.coalescing(), Coalesce::Keep);
+        assert_eq!(AppEvent::ToggleRuler.coalescing(), Coalesce::Keep);
+        assert_eq!(AppEvent::ShowHelp.coalescing(), Coalesce::Keep);
+    }
+
+    #[test]
+    fn test_events_coalesce_with_same_kind_only() {
+        let radius = AppEvent::SetPreference("radius".into(), 60.0);
+        assert!(AppEvent::SetPreference("radius".into(), 80.0).coalesces_with(&radius));
+        assert!(!AppEvent::SetPreference("borderWidth".into(), 3.0).coalesces_with(&radius));
+        assert!(AppEvent::ToggleOverlay.coalesces_with(&AppEvent::ToggleOverlay));
+        assert!(!AppEvent::ToggleOverlay.coalesces_with(&AppEvent::ToggleGrid));
+        assert!(AppEvent::SelectHotkeyProfile("standard".into())
+            .coalesces_with(&AppEvent::SelectHotkeyProfile("presentation".into())));
+        assert!(!AppEvent::ZoomIn.coalesces_with(&AppEvent::ZoomIn));
     }
 
     #[test]
@@ -114,21 +429,53 @@ mod tests {
     }
 
     #[test]
-    fn test_all_events_have_descriptions() {
+    fn test_all_events_have_descriptions_and_names() {
         let events = [
             AppEvent::ToggleOverlay,
             AppEvent::OpenSettings,
             AppEvent::RequestQuit,
+            AppEvent::Restart,
             AppEvent::ShowAbout,
             AppEvent::ShowHelp,
+            AppEvent::ZoomIn,
+            AppEvent::ZoomOut,
+            AppEvent::ToggleCoordinates,
+            AppEvent::ToggleColorSampler,
+            AppEvent::ToggleRuler,
+            AppEvent::ClearRuler,
+            AppEvent::ToggleGrid,
+            AppEvent::TogglePresentationLock,
+            AppEvent::ToggleClickRecording,
+            AppEvent::ExportClickLogCsv,
+            AppEvent::ExportClickLogJson,
+            AppEvent::SelectHotkeyProfile("standard".into()),
+            AppEvent::SetPreference("radius".into(), 60.0),
+            AppEvent::SelectLookProfile("standard".into()),
+            AppEvent::CheckForUpdates,
+            AppEvent::SetRadius(60.0),
+            AppEvent::SetColor {
+                r: 1.0,
+                g: 0.0,
+                b: 0.0,
+                a: 0.5,
+            },
+            AppEvent::LoadProfile("presentation".into()),
+            AppEvent::SetMode(DisplayMode::Left),
             AppEvent::SettingsClosed,
             AppEvent::QuitCancelled,
             AppEvent::HelpClosed,
             AppEvent::ReinstallHotkeys,
+            AppEvent::UpdateChecked {
+                status: UpdateStatus::UpToDate,
+                manual: true,
+            },
         ];
 
         for event in events {
             assert!(!event.description().is_empty());
+            // The name is the JSON tag
+            let json = serde_json::to_value(&event).unwrap();
+            assert_eq!(json["event"], event.name());
         }
     }
 }
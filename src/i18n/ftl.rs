@@ -0,0 +1,221 @@
+//! Parser for the subset of Fluent (FTL) used by the Lumbus resources.
+//!
+//! Supported syntax:
+//!
+//! - messages: `id = value`
+//! - attributes: `.name = value` under a message, stored as `id.name`
+//! - multiline values: indented continuation lines, joined with `\n`
+//! - comments: `#`, `##` and `###` lines
+//! - placeables: string literals `{ "text" }` and variables `{ $name }`
+//!
+//! Terms, selectors and functions are not supported; messages using them
+//! are reported as errors and skipped, so a bad entry never hides the rest
+//! of the file. Continuation lines lose their leading whitespace.
+
+/// Part of a message value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Element {
+    /// Literal text.
+    Text(String),
+    /// Reference to a variable passed at format time (`{ $name }`).
+    Var(String),
+}
+
+/// Parsed message value.
+pub type Pattern = Vec<Element>;
+
+/// Messages and errors of one parsed FTL source.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Resource {
+    /// Messages in file order, as (id, value).
+    pub messages: Vec<(String, Pattern)>,
+    /// One line per skipped entry: `line N: reason`.
+    pub errors: Vec<String>,
+}
+
+/// Is `s` a valid Fluent identifier (`[a-zA-Z][a-zA-Z0-9_-]*`)?
+pub fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Message id for an English source string.
+///
+/// Lowercases ASCII letters and digits and turns every other run of
+/// characters into a single `-`: `"Radius (px)"` becomes `radius-px`.
+/// Identifiers map to themselves (lowercased).
+pub fn message_id(key: &str) -> String {
+    let mut id = String::with_capacity(key.len());
+    for c in key.chars() {
+        if c.is_ascii_alphanumeric() {
+            id.push(c.to_ascii_lowercase());
+        } else if !id.is_empty() && !id.ends_with('-') {
+            id.push('-');
+        }
+    }
+    while id.ends_with('-') {
+        id.pop();
+    }
+    id
+}
+
+/// Formats a pattern, substituting variables from `args`.
+///
+/// Unknown variables are rendered as `{$name}` so the gap is visible.
+pub fn format_pattern(pattern: &Pattern, args: &[(&str, &str)]) -> String {
+    let mut out = String::new();
+    for element in pattern {
+        match element {
+            Element::Text(text) => out.push_str(text),
+            Element::Var(name) => match args.iter().find(|(k, _)| k == name) {
+                Some((_, value)) => out.push_str(value),
+                None => {
+                    out.push_str("{$");
+                    out.push_str(name);
+                    out.push('}');
+                }
+            },
+        }
+    }
+    out
+}
+
+/// Parses an FTL source.
+pub fn parse(source: &str) -> Resource {
+    let mut resource = Resource::default();
+    // Message being read: (id, first line number, value lines)
+    let mut current: Option<(String, usize, Vec<String>)> = None;
+    // Parent message of attributes, if any
+    let mut parent: Option<String> = None;
+
+    for (index, raw) in source.lines().enumerate() {
+        let line_no = index + 1;
+        let line = raw.trim_end();
+
+        if line.is_empty() {
+            if let Some((_, _, lines)) = current.as_mut() {
+                lines.push(String::new());
+            }
+            continue;
+        }
+
+        if line.starts_with(char::is_whitespace) {
+            let content = line.trim_start();
+            if let Some(attr) = content.strip_prefix('.') {
+                finish(&mut resource, current.take());
+                let Some(msg) = parent.as_ref() else {
+                    resource
+                        .errors
+                        .push(format!("line {line_no}: attribute outside a message"));
+                    continue;
+                };
+                match split_entry(attr) {
+                    Some((name, value)) if is_identifier(name) => {
+                        current = Some((format!("{msg}.{name}"), line_no, vec![value.to_string()]));
+                    }
+                    _ => resource
+                        .errors
+                        .push(format!("line {line_no}: invalid attribute")),
+                }
+            } else if let Some((_, _, lines)) = current.as_mut() {
+                lines.push(content.to_string());
+            } else if parent.is_none() {
+                resource
+                    .errors
+                    .push(format!("line {line_no}: indented line outside a message"));
+            }
+            continue;
+        }
+
+        finish(&mut resource, current.take());
+        parent = None;
+
+        if line.starts_with('#') {
+            continue;
+        }
+
+        match split_entry(line) {
+            Some((id, _)) if id.starts_with('-') => {
+                resource
+                    .errors
+                    .push(format!("line {line_no}: terms are not supported"));
+            }
+            Some((id, value)) if is_identifier(id) => {
+                parent = Some(id.to_string());
+                current = Some((id.to_string(), line_no, vec![value.to_string()]));
+            }
+            _ => resource
+                .errors
+                .push(format!("line {line_no}: expected `id = value`")),
+        }
+    }
+    finish(&mut resource, current.take());
+    resource
+}
+
+/// Splits `id = value` into its trimmed parts.
+fn split_entry(line: &str) -> Option<(&str, &str)> {
+    let (id, value) = line.split_once('=')?;
+    Some((id.trim(), value.trim_start()))
+}
+
+/// Stores a finished message (or its error) in the resource.
+fn finish(resource: &mut Resource, entry: Option<(String, usize, Vec<String>)>) {
+    let Some((id, line_no, lines)) = entry else {
+        return;
+    };
+    let value = lines
+        .iter()
+        .skip_while(|l| l.is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join("\n");
+    let value = value.trim_end();
+    if value.is_empty() {
+        // A message may consist of attributes only
+        if id.contains('.') {
+            resource
+                .errors
+                .push(format!("line {line_no}: attribute `{id}` has no value"));
+        }
+        return;
+    }
+    match parse_pattern(value) {
+        Ok(pattern) => resource.messages.push((id, pattern)),
+        Err(reason) => resource
+            .errors
+            .push(format!("line {line_no}: `{id}`: {reason}")),
+    }
+}
+
+/// Splits a value into text and placeables.
+fn parse_pattern(value: &str) -> Result<Pattern, String> {
+    let mut pattern = Vec::new();
+    let mut text = String::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let end = after.find('}').ok_or("unclosed placeable")?;
+        let inner = after[..end].trim();
+
+        if let Some(literal) = inner.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            text.push_str(&literal.replace("\\\"", "\"").replace("\\\\", "\\"));
+        } else if let Some(name) = inner.strip_prefix('$').filter(|n| is_identifier(n)) {
+            if !text.is_empty() {
+                pattern.push(Element::Text(std::mem::take(&mut text)));
+            }
+            pattern.push(Element::Var(name.to_string()));
+        } else {
+            return Err(format!("unsupported placeable `{{ {inner} }}`"));
+        }
+        rest = &after[end + 1..];
+    }
+    text.push_str(rest);
+    if !text.is_empty() {
+        pattern.push(Element::Text(text));
+    }
+    Ok(pattern)
+}
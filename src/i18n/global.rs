@@ -0,0 +1,209 @@
+//! Process-wide localizer.
+//!
+//! Built on first use from the built-in resources plus any `.ftl` files in
+//! `$LUMBUS_LOCALES_DIR` or the `locales` directory next to the executable.
+//! Files found there add languages or override built-in strings.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use lumbus::i18n;
+//!
+//! i18n::set_language("es");
+//! assert_eq!(i18n::tr("Settings"), "Configuración");
+//! ```
+
+use std::path::PathBuf;
+use std::sync::{OnceLock, RwLock};
+
+use tracing::{error, warn};
+
+use super::localizer::{normalize_locale, Localizer};
+use crate::model::constants::{
+    BUILTIN_LANGUAGES, DEFAULT_LOCALE, LANG_EN, LANG_SYSTEM, LOCALES_DIR, LOCALES_DIR_ENV,
+};
+
+/// Global localizer, built on first use.
+static LOCALIZER: OnceLock<RwLock<Localizer>> = OnceLock::new();
+
+/// Directory searched for extra resources, if any.
+fn locales_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os(LOCALES_DIR_ENV) {
+        return Some(PathBuf::from(dir));
+    }
+    let exe = std::env::current_exe().ok()?;
+    Some(exe.parent()?.join(LOCALES_DIR))
+}
+
+fn localizer() -> &'static RwLock<Localizer> {
+    LOCALIZER.get_or_init(|| {
+        let mut localizer = Localizer::with_builtin(DEFAULT_LOCALE);
+        if let Some(dir) = locales_dir().filter(|d| d.is_dir()) {
+            match localizer.load_dir(&dir) {
+                Ok(errors) => {
+                    for error in errors {
+                        warn!("Locale resource error: {error}");
+                    }
+                }
+                Err(e) => error!("Failed to load locales from {}: {e}", dir.display()),
+            }
+        }
+        RwLock::new(localizer)
+    })
+}
+
+/// Selects the UI language, e.g. `"es"` or `"pt-BR"`.
+pub fn set_language(locale: &str) {
+    if let Ok(mut l) = localizer().write() {
+        l.set_locale(locale);
+    }
+}
+
+/// Selected UI language (normalized tag).
+pub fn language() -> String {
+    localizer()
+        .read()
+        .map(|l| l.locale().to_string())
+        .unwrap_or_else(|_| DEFAULT_LOCALE.to_string())
+}
+
+/// Translates `key` into the selected language.
+pub fn tr(key: &str) -> String {
+    tr_in(&language(), key)
+}
+
+/// Translates `key` into `locale`.
+pub fn tr_in(locale: &str, key: &str) -> String {
+    localizer()
+        .read()
+        .map(|l| l.translate(locale, key))
+        .unwrap_or_else(|_| key.to_string())
+}
+
+/// Formats message `id` in the selected language with variables.
+pub fn tr_args(id: &str, args: &[(&str, &str)]) -> Option<String> {
+    let l = localizer().read().ok()?;
+    l.format(l.locale(), id, args)
+}
+
+/// Languages with loaded resources, sorted.
+pub fn available_languages() -> Vec<String> {
+    localizer().read().map(|l| l.locales()).unwrap_or_default()
+}
+
+/// Languages offered in the selector, in `PREF_LANG` order.
+///
+/// The built-in languages come first in their fixed order, followed by
+/// any other loaded language, sorted.
+pub fn languages() -> Vec<String> {
+    let mut list: Vec<String> = BUILTIN_LANGUAGES.iter().map(|l| l.to_string()).collect();
+    for locale in available_languages() {
+        if !list.contains(&locale) {
+            list.push(locale);
+        }
+    }
+    list
+}
+
+/// Locale tag for a stored language preference (index into [`languages`]).
+///
+/// Out-of-range values fall back to the default locale.
+pub fn lang_code(lang: i32) -> String {
+    usize::try_from(lang)
+        .ok()
+        .and_then(|i| languages().into_iter().nth(i))
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_string())
+}
+
+/// Language preference for a locale tag; unknown tags give `LANG_EN`.
+pub fn lang_index(locale: &str) -> i32 {
+    let locale = normalize_locale(locale);
+    languages()
+        .iter()
+        .position(|l| *l == locale)
+        .map_or(LANG_EN, |i| i as i32)
+}
+
+/// Locale tag stored in the config for a language preference; empty for
+/// `LANG_SYSTEM`.
+///
+/// Indices into [`languages`] shift when a locale file is added, and
+/// differ between machines, so only the tag is persisted.
+pub fn lang_tag(lang: i32) -> String {
+    if lang == LANG_SYSTEM {
+        String::new()
+    } else {
+        lang_code(lang)
+    }
+}
+
+/// Language preference for a locale tag stored in the config.
+///
+/// An empty tag, or one with no resources on this machine, follows the
+/// system.
+pub fn lang_from_tag(tag: &str) -> i32 {
+    if tag.trim().is_empty() {
+        return LANG_SYSTEM;
+    }
+    let locale = normalize_locale(tag);
+    languages()
+        .iter()
+        .position(|l| *l == locale)
+        .map_or(LANG_SYSTEM, |i| i as i32)
+}
+
+/// Offered language matching an OS locale tag (`pt-BR` gives `pt`).
+pub fn system_language(system_locale: &str) -> Option<String> {
+    let offered = languages();
+    let chain = localizer().read().ok()?.fallback_chain(system_locale);
+    // The chain ends with the default locale; only match the OS tag itself
+    let len = normalize_locale(system_locale).split('-').count();
+    chain
+        .into_iter()
+        .take(len)
+        .find(|tag| offered.contains(tag))
+}
+
+/// Locale tag for a language preference, given the OS locale.
+///
+/// `LANG_SYSTEM` picks the offered language closest to the OS locale and
+/// falls back to the default locale when none matches.
+pub fn resolve_language(lang: i32, system_locale: Option<&str>) -> String {
+    if lang != LANG_SYSTEM {
+        return lang_code(lang);
+    }
+    system_locale
+        .and_then(system_language)
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_string())
+}
+
+/// Name of a language in that language (e.g. `Español`).
+pub fn language_name(locale: &str) -> String {
+    tr_in(locale, "language-name")
+}
+
+/// Letter drawn for a left (`true`) or right click in the selected language.
+pub fn click_letter(left: bool) -> char {
+    click_letter_in(&language(), left)
+}
+
+/// Letter drawn for a left (`true`) or right click in `locale`.
+pub fn click_letter_in(locale: &str, left: bool) -> char {
+    let key = if left {
+        "click-letter-left"
+    } else {
+        "click-letter-right"
+    };
+    let fallback = if left { 'L' } else { 'R' };
+    tr_in(locale, key).chars().next().unwrap_or(fallback)
+}
+
+/// Text drawn for a left (`true`) or right click: `custom` when set,
+/// otherwise the letter of the selected language.
+pub fn click_label(custom: &str, left: bool) -> String {
+    if custom.is_empty() {
+        click_letter(left).to_string()
+    } else {
+        custom.to_string()
+    }
+}
@@ -0,0 +1,171 @@
+//! Message bundles per locale with fallback lookup.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::{fs, io};
+
+use super::ftl::{self, Pattern};
+
+/// Built-in English resource.
+pub const EN_FTL: &str = include_str!("../../locales/en.ftl");
+
+/// Built-in Spanish resource.
+pub const ES_FTL: &str = include_str!("../../locales/es.ftl");
+
+/// Built-in resources, as (locale, source).
+pub const BUILTIN_RESOURCES: [(&str, &str); 6] = [
+    ("en", EN_FTL),
+    ("es", ES_FTL),
+    ("fr", include_str!("../../locales/fr.ftl")),
+    ("de", include_str!("../../locales/de.ftl")),
+    ("pt", include_str!("../../locales/pt.ftl")),
+    ("ja", include_str!("../../locales/ja.ftl")),
+];
+
+/// Normalizes a locale tag: `es_MX.UTF-8` becomes `es-mx`.
+pub fn normalize_locale(locale: &str) -> String {
+    let tag = locale.split(['.', '@']).next().unwrap_or_default().trim();
+    tag.replace('_', "-").to_ascii_lowercase()
+}
+
+/// Loaded translations and the selected language.
+#[derive(Debug, Clone)]
+pub struct Localizer {
+    /// Messages per normalized locale.
+    bundles: BTreeMap<String, HashMap<String, Pattern>>,
+    /// Last link of every fallback chain.
+    default_locale: String,
+    /// Selected language.
+    locale: String,
+}
+
+impl Localizer {
+    /// Creates an empty localizer falling back to `default_locale`.
+    pub fn new(default_locale: &str) -> Self {
+        let default_locale = normalize_locale(default_locale);
+        Self {
+            bundles: BTreeMap::new(),
+            locale: default_locale.clone(),
+            default_locale,
+        }
+    }
+
+    /// Creates a localizer with the built-in resources.
+    pub fn with_builtin(default_locale: &str) -> Self {
+        let mut localizer = Self::new(default_locale);
+        for (locale, source) in BUILTIN_RESOURCES {
+            localizer.add_resource(locale, source);
+        }
+        localizer
+    }
+
+    /// Adds the messages of an FTL source to `locale`.
+    ///
+    /// Messages already present are replaced, so a later resource can
+    /// override single strings. Returns the parse errors.
+    pub fn add_resource(&mut self, locale: &str, source: &str) -> Vec<String> {
+        let resource = ftl::parse(source);
+        self.bundles
+            .entry(normalize_locale(locale))
+            .or_default()
+            .extend(resource.messages);
+        resource.errors
+    }
+
+    /// Loads every `<locale>.ftl` file in `dir`.
+    ///
+    /// Returns the parse errors, prefixed with the file name.
+    pub fn load_dir(&mut self, dir: &Path) -> io::Result<Vec<String>> {
+        let mut paths: Vec<_> = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "ftl"))
+            .collect();
+        paths.sort();
+
+        let mut errors = Vec::new();
+        for path in paths {
+            let Some(locale) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let source = fs::read_to_string(&path)?;
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            errors.extend(
+                self.add_resource(locale, &source)
+                    .into_iter()
+                    .map(|e| format!("{name}: {e}")),
+            );
+        }
+        Ok(errors)
+    }
+
+    /// Locales with at least one message, sorted.
+    pub fn locales(&self) -> Vec<String> {
+        self.bundles
+            .iter()
+            .filter(|(_, messages)| !messages.is_empty())
+            .map(|(locale, _)| locale.clone())
+            .collect()
+    }
+
+    /// Selects the language used by [`Localizer::tr`].
+    ///
+    /// Any tag is accepted; missing messages fall back along
+    /// [`Localizer::fallback_chain`].
+    pub fn set_locale(&mut self, locale: &str) {
+        self.locale = normalize_locale(locale);
+    }
+
+    /// Selected language.
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Locales tried for `locale`, most specific first.
+    ///
+    /// `es-MX` gives `["es-mx", "es", "en"]` with English as default.
+    pub fn fallback_chain(&self, locale: &str) -> Vec<String> {
+        let mut chain = Vec::new();
+        let mut tag = normalize_locale(locale);
+        while !tag.is_empty() {
+            chain.push(tag.clone());
+            match tag.rfind('-') {
+                Some(i) => tag.truncate(i),
+                None => break,
+            }
+        }
+        if !chain.contains(&self.default_locale) {
+            chain.push(self.default_locale.clone());
+        }
+        chain
+    }
+
+    /// Formats message `id` in `locale`, following the fallback chain.
+    pub fn format(&self, locale: &str, id: &str, args: &[(&str, &str)]) -> Option<String> {
+        self.fallback_chain(locale).iter().find_map(|tag| {
+            self.bundles
+                .get(tag)
+                .and_then(|messages| messages.get(id))
+                .map(|pattern| ftl::format_pattern(pattern, args))
+        })
+    }
+
+    /// Translates `key` into `locale`.
+    ///
+    /// `key` is a message id or the English source text it was derived
+    /// from (see [`ftl::message_id`]). Unknown keys are returned as is.
+    pub fn translate(&self, locale: &str, key: &str) -> String {
+        self.format(locale, &ftl::message_id(key), &[])
+            .unwrap_or_else(|| key.to_string())
+    }
+
+    /// Translates `key` into the selected language.
+    pub fn tr(&self, key: &str) -> String {
+        self.translate(&self.locale, key)
+    }
+}
+
+impl Default for Localizer {
+    fn default() -> Self {
+        Self::with_builtin(crate::model::DEFAULT_LOCALE)
+    }
+}
@@ -0,0 +1,30 @@
+//! Localization backed by Fluent (`.ftl`) resources.
+//!
+//! UI strings live in `locales/<locale>.ftl`, not in Rust source. English,
+//! Spanish, French, German, Portuguese and Japanese are compiled in; more
+//! languages, or overrides of single strings, are picked up at runtime
+//! from `$LUMBUS_LOCALES_DIR` or the `locales` directory next to the
+//! executable.
+//!
+//! Message ids are derived from the English text (`"Radius (px)"` is
+//! `radius-px`), so callers keep passing readable keys to [`tr`].
+//! Lookups follow a fallback chain (`es-MX` → `es` → `en`) and the
+//! language can be switched at runtime with [`set_language`].
+//!
+//! # Module Structure
+//!
+//! - [`ftl`]: Parser for the supported Fluent subset
+//! - [`localizer`]: `Localizer` bundles and fallback lookup
+//! - [`global`]: Static access functions
+
+pub mod ftl;
+pub mod global;
+pub mod localizer;
+
+pub use ftl::message_id;
+pub use global::{
+    available_languages, click_label, click_letter, click_letter_in, lang_code, lang_from_tag,
+    lang_index, lang_tag, language, language_name, languages, resolve_language, set_language,
+    system_language, tr, tr_args, tr_in,
+};
+pub use localizer::{normalize_locale, Localizer};
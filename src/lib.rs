@@ -1,16 +1,30 @@
-#![allow(unexpected_cfgs)] // Silence cfg warnings from objc macros
-
 //! Lumbus - Mouse pointer highlighter for macOS and Windows.
 //!
 //! This library contains:
-//! - Pure helpers (clamp, color conversion, i18n)
+//! - Pure helpers (clamp, color conversion)
+//! - Localization from Fluent resources (cross-platform)
 //! - Event system (cross-platform)
+//! - Local control endpoint server (cross-platform)
+//! - Leveled logging to a rotating file (cross-platform)
 //! - Model/state management (cross-platform)
+//! - Shape math and display lists shared by the overlay renderers
+//!   (cross-platform)
+//! - Overlay backend trait, shared event handling and a headless backend
+//!   for tests (cross-platform)
+//! - Settings window UI in egui (cross-platform)
+//! - Preferences file cache and sync (cross-platform)
 //! - Platform-specific implementations
 
+pub mod backend;
+pub mod control;
 pub mod events;
+pub mod i18n;
+pub mod logging;
 pub mod model;
 pub mod platform;
+pub mod render;
+pub mod settings;
+pub mod storage;
 
 // Re-export FFI for backward compatibility (macOS)
 #[cfg(target_os = "macos")]
@@ -21,6 +35,7 @@ pub mod ffi {
 use std::borrow::Cow;
 
 // Re-export model types for convenience
+pub use model::color::parse_color;
 pub use model::OverlayState;
 
 // Re-export event types for convenience
@@ -44,6 +59,11 @@ pub fn parse_hex_color(s: &str) -> Option<(f64, f64, f64, f64)> {
     let t = s.trim();
     let t = t.strip_prefix('#').unwrap_or(t);
     let hex = t.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+    // Checked before slicing by byte: a multi-byte char would split, and
+    // from_str_radix would take a leading '+'
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
     let (r, g, b, a) = match hex.len() {
         6 => {
             let rv = u8::from_str_radix(&hex[0..2], 16).ok()?;
@@ -68,66 +88,12 @@ pub fn parse_hex_color(s: &str) -> Option<(f64, f64, f64, f64)> {
     ))
 }
 
-/// Very small localisation helper used in Settings.
+/// Very small localisation helper: translates `key` into English or Spanish.
+///
+/// `key` is the English text; the translation comes from the Fluent
+/// resources in `locales/`. UI code uses [`i18n::tr`], which follows the
+/// selected language. Unknown keys are returned as is.
 pub fn tr_key(key: &str, es: bool) -> Cow<'static, str> {
-    match (key, es) {
-        ("Settings", true) => Cow::Borrowed("Configuración"),
-        ("Settings", false) => Cow::Borrowed("Settings"),
-
-        ("Language", true) => Cow::Borrowed("Idioma"),
-        ("Language", false) => Cow::Borrowed("Language"),
-
-        ("English", true) => Cow::Borrowed("Inglés"),
-        ("English", false) => Cow::Borrowed("English"),
-
-        ("Spanish", true) => Cow::Borrowed("Español"),
-        ("Spanish", false) => Cow::Borrowed("Spanish"),
-
-        ("Radius (px)", true) => Cow::Borrowed("Radio (px)"),
-        ("Radius (px)", false) => Cow::Borrowed("Radius (px)"),
-
-        ("Border (px)", true) => Cow::Borrowed("Grosor (px)"),
-        ("Border (px)", false) => Cow::Borrowed("Border (px)"),
-
-        ("Color", true) => Cow::Borrowed("Color"),
-        ("Color", false) => Cow::Borrowed("Color"),
-
-        ("Hex", true) => Cow::Borrowed("Hex"),
-        ("Hex", false) => Cow::Borrowed("Hex"),
-
-        ("Fill Transparency (%)", true) => Cow::Borrowed("Transparencia (%)"),
-        ("Fill Transparency (%)", false) => Cow::Borrowed("Fill Transparency (%)"),
-
-        ("Close", true) => Cow::Borrowed("Cerrar"),
-        ("Close", false) => Cow::Borrowed("Close"),
-
-        ("Quit", true) => Cow::Borrowed("Salir"),
-        ("Quit", false) => Cow::Borrowed("Quit"),
-        ("Cancel", true) => Cow::Borrowed("Cancelar"),
-        ("Cancel", false) => Cow::Borrowed("Cancel"),
-
-        // Help overlay
-        ("Help", true) => Cow::Borrowed("Ayuda"),
-        ("Help", false) => Cow::Borrowed("Help"),
-
-        ("Keyboard Shortcuts", true) => Cow::Borrowed("Atajos de teclado"),
-        ("Keyboard Shortcuts", false) => Cow::Borrowed("Keyboard Shortcuts"),
-
-        ("Toggle overlay", true) => Cow::Borrowed("Mostrar/ocultar resaltado"),
-        ("Toggle overlay", false) => Cow::Borrowed("Toggle overlay"),
-
-        ("Open settings", true) => Cow::Borrowed("Abrir configuración"),
-        ("Open settings", false) => Cow::Borrowed("Open settings"),
-
-        ("Show help", true) => Cow::Borrowed("Mostrar ayuda"),
-        ("Show help", false) => Cow::Borrowed("Show help"),
-
-        ("Quit app", true) => Cow::Borrowed("Salir de la app"),
-        ("Quit app", false) => Cow::Borrowed("Quit app"),
-
-        ("Press any key to close", true) => Cow::Borrowed("Pulsa cualquier tecla para cerrar"),
-        ("Press any key to close", false) => Cow::Borrowed("Press any key to close"),
-
-        _ => Cow::Owned(key.to_string()),
-    }
+    let lang = if es { model::LANG_ES } else { model::LANG_EN };
+    Cow::Owned(i18n::tr_in(&i18n::lang_code(lang), key))
 }
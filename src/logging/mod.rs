@@ -0,0 +1,156 @@
+//! Leveled logging to stderr and a rotating log file.
+//!
+//! Hotkey registration, window setup and the other events worth attaching
+//! to a bug report are logged with `tracing`. [`init_logging`] sends them
+//! to stderr and to a daily file in the platform's log folder
+//! (`platform::{macos,windows}::storage::log_dir`), keeping the last
+//! `LOG_MAX_FILES` days. The level is `info` unless the `LUMBUS_LOG`
+//! environment variable says otherwise.
+//!
+//! The `--diagnostics` report (see `model::diagnostics`) is saved to the
+//! same folder, and [`install_panic_hook`] writes crash reports (see
+//! `model::crash`) to its `Crashes` subfolder.
+
+use std::backtrace::Backtrace;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tracing::{error, info};
+
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt;
+use tracing_subscriber::prelude::*;
+
+use crate::model::constants::{
+    CRASH_DIR, DIAGNOSTICS_FILE, LOG_FILE_PREFIX, LOG_FILE_SUFFIX, LOG_LEVEL_ENV, LOG_MAX_FILES,
+};
+use crate::model::{config_snapshot, panic_message, CrashReport, DiagnosticsReport};
+
+/// Level to log at for a `LUMBUS_LOG` value (`error`, `warn`, `info`,
+/// `debug`, `trace` or `off`); `info` if unset or not a level.
+pub fn log_level(value: Option<&str>) -> LevelFilter {
+    value
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(LevelFilter::INFO)
+}
+
+/// Start logging to stderr and to the log files in `dir`.
+///
+/// Keep the returned guard alive until the app exits: dropping it flushes
+/// the file. Without a usable `dir` (or if logging was already started)
+/// only stderr is used, and `None` is returned.
+pub fn init_logging(dir: &Path) -> Option<WorkerGuard> {
+    let level = log_level(std::env::var(LOG_LEVEL_ENV).ok().as_deref());
+    let (file, guard) = match RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .filename_suffix(LOG_FILE_SUFFIX)
+        .max_log_files(LOG_MAX_FILES)
+        .build(dir)
+    {
+        Ok(appender) => {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            (
+                Some(fmt::layer().with_ansi(false).with_writer(writer)),
+                Some(guard),
+            )
+        }
+        Err(e) => {
+            eprintln!("Logging to {} failed: {}", dir.display(), e);
+            (None, None)
+        }
+    };
+    let started = tracing_subscriber::registry()
+        .with(fmt::layer().with_writer(io::stderr))
+        .with(file)
+        .with(level)
+        .try_init()
+        .is_ok();
+    guard.filter(|_| started)
+}
+
+/// Print a diagnostics report to stdout and save it to `diagnostics.txt`
+/// in `dir`, to attach to a bug report.
+pub fn write_diagnostics(report: &DiagnosticsReport, dir: &Path) {
+    let text = report.to_string();
+    print!("{}", text);
+    let path = dir.join(DIAGNOSTICS_FILE);
+    match fs::create_dir_all(dir).and_then(|_| fs::write(&path, &text)) {
+        Ok(()) => info!("Diagnostics saved to {}", path.display()),
+        Err(e) => error!("Failed to write {}: {}", path.display(), e),
+    }
+}
+
+/// Save a crash report in `dir`, returning its path.
+pub fn write_crash_report(report: &CrashReport, dir: &Path) -> io::Result<PathBuf> {
+    let path = dir.join(report.file_name());
+    fs::create_dir_all(dir)?;
+    fs::write(&path, report.to_string())?;
+    Ok(path)
+}
+
+/// Append `line` to the log file `name` in `dir` and flush it at once.
+///
+/// The file layer of [`init_logging`] writes from a worker thread, which
+/// release builds (`panic = "abort"`) never give the chance to catch up
+/// after a panic.
+pub fn append_to_log(dir: &Path, name: &str, line: &str) -> io::Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(name))?;
+    file.write_all(line.as_bytes())?;
+    file.flush()
+}
+
+/// Report panics instead of dying silently.
+///
+/// On the first panic, a crash report with the backtrace, `os` and a
+/// redacted snapshot of the settings file at `config_path` is written to
+/// the `Crashes` folder in `log_dir`, the panic is appended to the day's
+/// log file there and then, and `notify` (the platform's dialog) is called
+/// with the report's path. The default hook still prints the panic to
+/// stderr.
+pub fn install_panic_hook(log_dir: PathBuf, os: String, config_path: PathBuf, notify: fn(&Path)) {
+    static REPORTED: AtomicBool = AtomicBool::new(false);
+
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+        // A panic while reporting one must not report again
+        if REPORTED.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let report = CrashReport {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            os: os.clone(),
+            time: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            thread: thread::current().name().unwrap_or("unnamed").to_string(),
+            message: panic_message(info.payload()),
+            location: info.location().map(|l| l.to_string()).unwrap_or_default(),
+            backtrace: Backtrace::force_capture().to_string(),
+            config: config_snapshot(&fs::read_to_string(&config_path).unwrap_or_default()),
+        };
+        if let Err(e) = append_to_log(&log_dir, &report.log_file_name(), &report.log_line()) {
+            eprintln!("Logging the panic to {} failed: {}", log_dir.display(), e);
+        }
+        match write_crash_report(&report, &log_dir.join(CRASH_DIR)) {
+            Ok(path) => {
+                error!("Crash report saved to {}", path.display());
+                notify(&path);
+            }
+            Err(e) => error!("Failed to write the crash report: {}", e),
+        }
+    }));
+}
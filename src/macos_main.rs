@@ -3,72 +3,111 @@
 //! This module contains the main application loop for macOS.
 //! The CustomView implementation is in platform/macos/ui/overlay/view.rs.
 
+use lumbus::backend::{start_overlay, DispatchContext};
 use lumbus::model::constants::*;
-use lumbus::platform::macos::app::sync_visual_prefs_to_all_views;
-use lumbus::platform::macos::ffi::bridge::{
-    autoreleasepool, get_class, id, msg_send, nil, nsstring_id, NSApp, ObjectExt, NO, YES,
+use lumbus::model::{
+    fresh_start_flag, restarted, DiagnosticsConfig, SoakConfig, RESTART_WAIT_SECS,
 };
-use lumbus::platform::macos::ffi::{
-    display_id_for_screen, ensure_accessibility_prompt, overlay_window_level,
+use lumbus::platform::macos::app::{
+    check_permissions, install_crash_handler, run_diagnostics, set_app_context,
+    start_refresh_timer, start_soak_test, start_timer_watchdog, start_update_checks, AppContext,
 };
+use lumbus::platform::macos::ffi::bridge::{autoreleasepool, msg_send, NSApp};
+use lumbus::platform::macos::ffi::ensure_accessibility_prompt;
+use lumbus::platform::macos::handlers::MacContext;
+use lumbus::platform::macos::input::instance::{
+    another_instance_running, forward_args, install_handoff_observer,
+};
+use lumbus::platform::macos::input::scripting::install_script_handlers;
+use lumbus::platform::macos::input::url_handler::install_url_handler;
 use lumbus::platform::macos::input::{
-    hotkey_event_handler, install_hotkeys, install_local_ctrl_a_monitor, install_mouse_monitors,
-    install_termination_observer, install_wakeup_space_observers, start_hotkey_keepalive,
+    apply_control_server, hotkey_event_handler, install_config_file_observer, install_hotkeys,
+    install_local_ctrl_a_monitor, install_mouse_monitors, install_reduce_motion_observer,
+    install_screen_change_observer, install_termination_observer, install_wakeup_space_observers,
+    installed_hotkey_count, start_hotkey_keepalive,
+};
+use lumbus::platform::macos::storage::{prefs_get_int, restore_defaults};
+use lumbus::platform::macos::ui::{
+    install_status_bar, restore_session_windows, show_config_issues, start_onboarding,
 };
-use lumbus::platform::macos::storage::{prefs_get_double, prefs_get_int};
-use lumbus::platform::macos::ui::{install_status_bar, register_and_create_view};
 
-use objc2::sel;
-use objc2_foundation::NSRect;
+use std::time::Duration;
+
+use tracing::{error, info, info_span, warn};
 
 /// Main entry point for macOS.
-pub fn run() {
+///
+/// `soak` starts the hidden soak-test mode once the app is up,
+/// `diagnostics` prints the diagnostics report (`--diagnostics`) and
+/// `reset` restores the default settings first (`--reset`).
+pub fn run(soak: Option<SoakConfig>, diagnostics: Option<DiagnosticsConfig>, reset: bool) {
     // Event bus is already initialized by main()
 
     autoreleasepool(|| {
         unsafe {
-            // Ask for Accessibility permission (shows a system prompt if needed)
-            ensure_accessibility_prompt();
+            // Already running: hand the arguments (lumbus:// URLs) to that
+            // instance. After a restart, wait for the old instance to quit instead
+            let args: Vec<String> = std::env::args().skip(1).collect();
+            let wait = if restarted(&args) {
+                Duration::from_secs(RESTART_WAIT_SECS)
+            } else {
+                Duration::ZERO
+            };
+            if another_instance_running(wait) {
+                if let Some(flag) = fresh_start_flag(&args) {
+                    warn!("Lumbus is already running; quit it before starting it with {flag}");
+                } else {
+                    forward_args(&args);
+                }
+                return;
+            }
+
+            // Write a crash report instead of dying silently
+            install_crash_handler();
+            if reset {
+                info!("Restoring the default settings (--reset)");
+                restore_defaults();
+            }
+
+            // On first launch the walkthrough asks for Accessibility, with
+            // some context; otherwise ask now (a system prompt if needed)
+            let onboarding = soak.is_none()
+                && diagnostics.is_none()
+                && prefs_get_int(PREF_ONBOARDING_DONE, 0) == 0;
+            if !onboarding {
+                ensure_accessibility_prompt();
+            }
+            // Features needing a missing permission stay off until granted
+            check_permissions();
 
             let app = NSApp();
             // NSApplicationActivationPolicyAccessory = 1
             let _: bool = msg_send![app, setActivationPolicy: 1i64];
 
-            // Create one transparent overlay window per screen
-            let screens: id = msg_send![get_class("NSScreen"), screens];
-            let count: usize = msg_send![screens, count];
-            if count == 0 {
-                eprintln!("No screens available.");
+            // Create one transparent overlay window per screen, owned by
+            // the app's context until the termination observer tears it down
+            let setup = info_span!("window_setup").entered();
+            let Some(mut context) = AppContext::create() else {
+                error!("No screens available.");
                 return;
-            }
-
-            let mut views: Vec<id> = Vec::with_capacity(count);
-            let mut windows: Vec<id> = Vec::with_capacity(count); // Keep windows alive
-            for i in 0..count {
-                let screen: id = msg_send![screens, objectAtIndex: i];
-                let (win, view) = make_window_for_screen(screen);
-                // CRITICAL: Retain window and view to prevent autorelease pool from deallocating them
-                let _: id = msg_send![win, retain];
-                let _: id = msg_send![view, retain];
-                let _: () = msg_send![win, orderFrontRegardless];
-                windows.push(win);
-                views.push(view);
-            }
-            // Intentionally leak windows to keep them alive for app lifetime
-            let _ = Box::leak(windows.into_boxed_slice());
+            };
+            info!("{} overlay windows created", context.window_count());
 
             // Host view
-            let host_view = *views.first().unwrap();
+            let host_view = context.host();
 
-            // Load and broadcast preferences
-            load_preferences_into_view(host_view);
-            sync_visual_prefs_to_all_views(host_view);
+            // Load the preferences into every overlay
+            start_overlay(MacContext::new(host_view).backend());
 
-            // ~60 FPS timer: updates cursor and visibility per screen
-            let _ = create_timer(host_view, sel!(update_cursor_multi), 0.016);
+            // ~60 FPS timer: updates cursor and visibility per screen,
+            // created again if AppKit stops it
+            start_refresh_timer(host_view);
+            context.own_watchdog(start_timer_watchdog(host_view));
+            set_app_context(context);
 
             // Carbon hotkeys + global mouse monitors + termination observer
             install_hotkeys(host_view, hotkey_event_handler);
+            info!("{} hotkeys registered", installed_hotkey_count(host_view));
             install_mouse_monitors(host_view);
             install_termination_observer(host_view, hotkey_event_handler);
             install_local_ctrl_a_monitor(host_view);
@@ -77,109 +116,53 @@ pub fn run() {
             start_hotkey_keepalive(host_view);
             install_wakeup_space_observers(host_view, hotkey_event_handler);
 
+            // Follow the system "Reduce motion" setting
+            install_reduce_motion_observer();
+
+            // Follow displays being connected, disconnected, resized or rearranged
+            install_screen_change_observer(host_view);
+
+            // Apply edits made to the settings file while running
+            install_config_file_observer(host_view);
+
+            // AppleScript commands (Script Editor, Shortcuts, Keyboard Maestro)
+            install_script_handlers(host_view);
+
+            // lumbus:// URLs (launch bars, browsers, shell scripts)
+            install_url_handler(host_view);
+
+            // Arguments of a second launch
+            install_handoff_observer(host_view);
+
+            // Local control endpoint (off unless enabled in the settings file)
+            apply_control_server();
+
             // Status bar item in menu bar
             install_status_bar(host_view);
 
-            let _: () = msg_send![app, run];
-        }
-    });
-}
+            // Daily check for a new release (unless turned off)
+            start_update_checks();
 
-/// Load preferences into a view.
-///
-/// # Safety
-/// The view must be a valid CustomView instance.
-unsafe fn load_preferences_into_view(view: id) {
-    let radius = prefs_get_double(PREF_RADIUS, DEFAULT_DIAMETER / 2.0);
-    let border = prefs_get_double(PREF_BORDER, DEFAULT_BORDER_WIDTH);
-    let r = prefs_get_double(PREF_STROKE_R, DEFAULT_COLOR.0);
-    let g = prefs_get_double(PREF_STROKE_G, DEFAULT_COLOR.1);
-    let b = prefs_get_double(PREF_STROKE_B, DEFAULT_COLOR.2);
-    let a = prefs_get_double(PREF_STROKE_A, DEFAULT_COLOR.3);
-    let fill_t = prefs_get_double(PREF_FILL_TRANSPARENCY, DEFAULT_FILL_TRANSPARENCY_PCT);
-    let lang = prefs_get_int(PREF_LANG, 0); // 0 en, 1 es
-
-    (*view).store_ivar::<f64>("_radius", radius);
-    (*view).store_ivar::<f64>("_borderWidth", border);
-    (*view).store_ivar::<f64>("_strokeR", r);
-    (*view).store_ivar::<f64>("_strokeG", g);
-    (*view).store_ivar::<f64>("_strokeB", b);
-    (*view).store_ivar::<f64>("_strokeA", a);
-    (*view).store_ivar::<f64>("_fillTransparencyPct", fill_t.clamp(0.0, 100.0));
-    (*view).store_ivar::<i32>("_lang", if lang == 1 { 1 } else { 0 });
-}
+            // Reopen auxiliary windows left open in the previous session
+            restore_session_windows();
 
-/// Create a transparent overlay window for a given screen.
-///
-/// # Safety
-/// The screen must be a valid NSScreen instance.
-unsafe fn make_window_for_screen(screen: id) -> (id, id) {
-    let frame: NSRect = msg_send![screen, frame];
-
-    // NSBorderlessWindowMask = 0
-    let style_mask: u64 = 0;
-    // NSBackingStoreBuffered = 2
-    let backing: u64 = 2;
-
-    let window: id = msg_send![get_class("NSWindow"), alloc];
-    let window: id = msg_send![
-        window,
-        initWithContentRect: frame,
-        styleMask: style_mask,
-        backing: backing,
-        defer: NO
-    ];
-
-    let _: () = msg_send![window, setOpaque: NO];
-
-    // Get clear color
-    let clear_color: id = msg_send![get_class("NSColor"), clearColor];
-    let _: () = msg_send![window, setBackgroundColor: clear_color];
-
-    let _: () = msg_send![window, setIgnoresMouseEvents: YES];
-    let _: () = msg_send![window, setAcceptsMouseMovedEvents: YES];
-    let _: () = msg_send![window, setLevel: overlay_window_level()];
-
-    // NSWindowCollectionBehaviorCanJoinAllSpaces = 1 << 0 = 1
-    // NSWindowCollectionBehaviorFullScreenAuxiliary = 1 << 8 = 256
-    // NSWindowCollectionBehaviorStationary = 1 << 4 = 16
-    let collection_behavior: u64 = 1 | 256 | 16;
-    let _: () = msg_send![window, setCollectionBehavior: collection_behavior];
-
-    let view: id = register_and_create_view(window, frame.size.width, frame.size.height);
-    (*view).store_ivar::<id>("_ownScreen", screen);
-    // Store stable DisplayID for this view's screen
-    let did = display_id_for_screen(screen);
-    (*view).store_ivar::<u32>("_ownDisplayID", did);
-
-    (window, view)
-}
+            // Report settings that could not be used as written
+            show_config_issues();
 
-/// Create an AppKit timer that fires even during modal menus.
-///
-/// # Safety
-/// The target must be a valid NSObject that responds to the selector.
-unsafe fn create_timer(target: id, selector: objc2::runtime::Sel, interval: f64) -> id {
-    let prev: id = *(*target).load_ivar::<id>("_updateTimer");
-    if prev != nil {
-        let _: () = msg_send![prev, invalidate];
-        (*target).store_ivar::<id>("_updateTimer", nil);
-    }
-    let timer_class = get_class("NSTimer");
-    // Create timer without auto-scheduling
-    let timer: id = msg_send![
-        timer_class,
-        timerWithTimeInterval: interval,
-        target: target,
-        selector: selector,
-        userInfo: nil,
-        repeats: YES
-    ];
-    // Add to run loop with CommonModes (keeps running during menus)
-    let run_loop: id = msg_send![get_class("NSRunLoop"), currentRunLoop];
-    let common_modes = nsstring_id("kCFRunLoopCommonModes");
-    let _: () = msg_send![run_loop, addTimer: timer, forMode: common_modes];
-
-    (*target).store_ivar::<id>("_updateTimer", timer);
-    timer
+            // First launch: walkthrough before the overlay appears
+            if onboarding {
+                start_onboarding(host_view);
+            }
+
+            if let Some(config) = soak {
+                start_soak_test(host_view, config);
+            }
+            if let Some(config) = diagnostics {
+                run_diagnostics(host_view, config);
+            }
+            drop(setup);
+
+            let _: () = msg_send![app, run];
+        }
+    });
 }
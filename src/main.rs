@@ -1,4 +1,3 @@
-#![allow(unexpected_cfgs)] // Silence cfg warnings inside objc/cocoa macros
 #![windows_subsystem = "windows"] // Hide console on Windows (ignored on non-Windows)
 
 // ============================================================================
@@ -11,15 +10,66 @@ mod macos_main;
 #[cfg(target_os = "windows")]
 mod windows_main;
 
+/// Hidden soak-test mode (`--soak[=minutes]`), see `model::soak`.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn soak_config() -> Option<lumbus::model::SoakConfig> {
+    lumbus::model::SoakConfig::from_args(std::env::args().skip(1))
+}
+
+/// Diagnostics mode (`--diagnostics[=overlay]`), see `model::diagnostics`.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn diagnostics_config() -> Option<lumbus::model::DiagnosticsConfig> {
+    lumbus::model::DiagnosticsConfig::from_args(std::env::args().skip(1))
+}
+
+/// Start from the defaults without touching the settings file (`--safe-mode`).
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn safe_mode_requested() -> bool {
+    lumbus::model::safe_mode_requested(std::env::args().skip(1))
+}
+
+/// Start from the default settings (`--reset`), see `Preferences::restore_defaults`.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn reset_requested() -> bool {
+    lumbus::model::reset_requested(std::env::args().skip(1))
+}
+
 fn main() {
     #[cfg(target_os = "macos")]
     {
+        let _log = lumbus::logging::init_logging(&lumbus::platform::storage::log_dir());
+        let safe_mode = safe_mode_requested();
+        if safe_mode {
+            tracing::info!("Starting in safe mode (--safe-mode), settings file not used");
+            unsafe { lumbus::platform::storage::enter_safe_mode() };
+        }
         lumbus::events::init_event_bus();
-        macos_main::run();
+        // The reset waits for the single-instance check
+        macos_main::run(
+            soak_config(),
+            diagnostics_config(),
+            !safe_mode && reset_requested(),
+        );
     }
 
     #[cfg(target_os = "windows")]
-    windows_main::run();
+    {
+        let log = lumbus::logging::init_logging(&lumbus::platform::storage::log_dir());
+        let safe_mode = safe_mode_requested();
+        if safe_mode {
+            tracing::info!("Starting in safe mode (--safe-mode), settings file not used");
+            lumbus::platform::storage::enter_safe_mode();
+        }
+        lumbus::events::init_event_bus();
+        // The reset waits for the single-instance check
+        let reset = !safe_mode && reset_requested();
+        if let Err(e) = windows_main::run(soak_config(), diagnostics_config(), reset) {
+            tracing::error!("Lumbus error: {}", e);
+            // Flush the log file before exiting
+            drop(log);
+            std::process::exit(1);
+        }
+    }
 
     #[cfg(not(any(target_os = "macos", target_os = "windows")))]
     {
@@ -0,0 +1,43 @@
+//! Contents of the About window (pure Rust, no FFI).
+
+/// Version of the running app.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Commit the app was built from (set by `build.rs`), or empty when it
+/// was built outside a git checkout.
+pub const BUILD_HASH: &str = match option_env!("LUMBUS_BUILD_HASH") {
+    Some(hash) => hash,
+    None => "",
+};
+
+/// Copyright line.
+pub const COPYRIGHT: &str = "© 2026 Rafael Esteve";
+
+/// License the app is distributed under.
+pub const LICENSE: &str = "Apache License 2.0";
+
+/// Project home page.
+pub const REPO_URL: &str = "https://github.com/restevean/lumbus";
+
+/// Where to report problems.
+pub const ISSUES_URL: &str = "https://github.com/restevean/lumbus/issues";
+
+/// Full license text.
+pub const LICENSE_URL: &str = "https://github.com/restevean/lumbus/blob/main/LICENSE";
+
+/// Links shown in the About window: (translation key, URL).
+pub const ABOUT_LINKS: &[(&str, &str)] = &[
+    ("Website", REPO_URL),
+    ("Report an issue", ISSUES_URL),
+    ("License", LICENSE_URL),
+];
+
+/// Version line, e.g. `0.5.3 (a1b2c3d)`; the hash is left out when
+/// unknown.
+pub fn version_label(version: &str, build_hash: &str) -> String {
+    if build_hash.is_empty() {
+        version.to_string()
+    } else {
+        format!("{version} ({build_hash})")
+    }
+}
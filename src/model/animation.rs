@@ -0,0 +1,97 @@
+//! Animation clock and show/hide transition (pure Rust, no FFI).
+//!
+//! The rainbow hue cycle and the pulse take their phase from the
+//! wall-clock time only, so the macOS and Windows renderers, and every
+//! overlay window, are at the same point of each animation at the same
+//! moment. Toggling the overlay scales and fades the highlight in and
+//! out instead of popping; with motion reduced it switches at once.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::constants::TRANSITION_START_SCALE;
+
+/// Seconds since the Unix epoch, the clock the overlay animations take
+/// their phase from.
+pub fn wall_clock_secs() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0.0, |d| d.as_secs_f64())
+}
+
+/// Show/hide transition of the overlay.
+///
+/// Feed it the wanted visibility once per frame; instead of popping in
+/// and out after a toggle, the highlight eases between hidden (0.0) and
+/// shown (1.0) over the configured duration. See [`transition_alpha`]
+/// and [`transition_scale`] for how the progress is drawn.
+#[derive(Debug, Clone, Copy)]
+pub struct ShowHide {
+    shown: bool,
+    progress: f64,
+    last_t: Option<f64>,
+}
+
+impl Default for ShowHide {
+    fn default() -> Self {
+        Self {
+            shown: true,
+            progress: 1.0,
+            last_t: None,
+        }
+    }
+}
+
+impl ShowHide {
+    /// Creates a settled, fully shown transition.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a frame at time `t` (seconds) with the overlay `shown` or
+    /// not, and returns the progress [0.0, 1.0].
+    ///
+    /// A toggle midway reverses the running transition from where it is.
+    /// A `duration_ms` of zero switches at once.
+    pub fn update(&mut self, shown: bool, t: f64, duration_ms: f64) -> f64 {
+        let dt = self.last_t.map_or(0.0, |lt| (t - lt).max(0.0));
+        self.last_t = Some(t);
+        self.shown = shown;
+
+        if duration_ms <= 0.0 {
+            return self.snap();
+        }
+        let step = dt * 1000.0 / duration_ms;
+        self.progress = if shown {
+            (self.progress + step).min(1.0)
+        } else {
+            (self.progress - step).max(0.0)
+        };
+        self.progress
+    }
+
+    /// Skips the running transition (Reduce Motion) and returns the final
+    /// progress.
+    pub fn snap(&mut self) -> f64 {
+        self.progress = if self.shown { 1.0 } else { 0.0 };
+        self.progress
+    }
+
+    /// Current progress: 0.0 hidden, 1.0 shown.
+    pub fn progress(&self) -> f64 {
+        self.progress
+    }
+}
+
+/// Opacity multiplier of the highlight at `progress` of the transition,
+/// eased so it starts and settles gently.
+pub fn transition_alpha(progress: f64) -> f64 {
+    let p = progress.clamp(0.0, 1.0);
+    p * p * (3.0 - 2.0 * p)
+}
+
+/// Radius multiplier of the highlight at `progress` of the transition:
+/// it grows from `TRANSITION_START_SCALE` while showing and shrinks back
+/// while hiding.
+pub fn transition_scale(progress: f64) -> f64 {
+    TRANSITION_START_SCALE + (1.0 - TRANSITION_START_SCALE) * transition_alpha(progress)
+}
@@ -1,12 +1,20 @@
 //! Application state (pure Rust, no FFI).
 //!
 //! This module defines the overlay state structure that can be
-//! serialized to/from NSUserDefaults.
+//! persisted through `model::preferences`.
 
+use serde::{Deserialize, Serialize};
+
+use super::click_icon::click_icon;
 use super::constants::*;
+use super::font::is_letter_weight;
 
-/// Complete overlay state, serializable to/from NSUserDefaults.
-#[derive(Debug, Clone, PartialEq)]
+/// Complete overlay state, persisted through `model::preferences`.
+///
+/// Shared by both platforms and the backend dispatcher. Fields missing
+/// from serialized data take their default values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct OverlayState {
     /// Circle radius in pixels.
     pub radius: f64,
@@ -22,12 +30,74 @@ pub struct OverlayState {
     pub stroke_a: f64,
     /// Fill transparency [0.0, 100.0] (100 = fully transparent).
     pub fill_transparency_pct: f64,
-    /// Language: 0 = EN, 1 = ES.
+    /// Language: `LANG_SYSTEM` or an index into `i18n::languages()`.
     pub lang: i32,
     /// Is overlay globally enabled?
     pub overlay_enabled: bool,
     /// Display mode: 0 = circle, 1 = L, 2 = R.
     pub display_mode: i32,
+    /// Text drawn on a left click (empty = the letter of the language).
+    pub click_letter_left: String,
+    /// Text drawn on a right click (empty = the letter of the language).
+    pub click_letter_right: String,
+    /// Font family of the click letters (empty = system default).
+    pub letter_font: String,
+    /// Font weight of the click letters (see `LETTER_WEIGHT_*`).
+    pub letter_weight: i32,
+    /// Icon drawn on clicks instead of the letters (see `CLICK_ICON_*`).
+    pub click_icon: i32,
+    /// Highlight style: 0 = circle, 1 = laser pointer.
+    pub highlight_style: i32,
+    /// Draw a recording frame around the highlighted screen?
+    pub screen_frame: bool,
+    /// Fade the highlight during fast cursor movement?
+    pub speed_fade: bool,
+    /// Grow the highlight during fast cursor movement?
+    pub speed_size: bool,
+    /// Show a counter badge (x2, x3...) on rapid consecutive clicks?
+    pub click_burst: bool,
+    /// Maximum interval between clicks of a burst, in milliseconds.
+    pub click_burst_ms: f64,
+    /// Fade the highlight out while the cursor is idle?
+    pub idle_hide: bool,
+    /// Idle time before the highlight fades out, in seconds.
+    pub idle_hide_secs: f64,
+    /// Time the L/R indicator stays up after mouse-up, in milliseconds.
+    pub click_hold_ms: f64,
+    /// Draw a ring filling up while a button is held?
+    pub hold_ring: bool,
+    /// Time a button must be held to fill the ring, in milliseconds.
+    pub hold_ring_ms: f64,
+    /// Motion mode: 0 = follow system, 1 = reduced, 2 = full.
+    pub motion: i32,
+    /// Draw a contrasting outline halo around the highlight?
+    pub halo: bool,
+    /// Cycle the stroke hue over time?
+    pub rainbow: bool,
+    /// Rainbow speed in hue cycles per minute.
+    pub rainbow_speed: f64,
+    /// Glow behind the highlight: 0 = off, 1 = soft glow, 2 = drop shadow.
+    pub glow: i32,
+    /// Slowly grow and shrink the highlight ("breathing").
+    pub pulse: bool,
+    /// Pulse amplitude as a percentage of the radius.
+    pub pulse_amplitude: f64,
+    /// Pulse period in milliseconds.
+    pub pulse_period_ms: f64,
+    /// Show/hide transition duration in milliseconds (0 = instant).
+    pub transition_ms: f64,
+    /// Distance between major lines of the alignment grid, in pixels.
+    pub grid_spacing: f64,
+    /// Parts each grid cell is split into by minor lines (1 = none).
+    pub grid_subdivisions: i32,
+    /// Grid line color - red component [0.0, 1.0].
+    pub grid_r: f64,
+    /// Grid line color - green component [0.0, 1.0].
+    pub grid_g: f64,
+    /// Grid line color - blue component [0.0, 1.0].
+    pub grid_b: f64,
+    /// Grid line color - alpha component [0.0, 1.0].
+    pub grid_a: f64,
 }
 
 impl Default for OverlayState {
@@ -40,9 +110,40 @@ impl Default for OverlayState {
             stroke_b: DEFAULT_COLOR.2,
             stroke_a: DEFAULT_COLOR.3,
             fill_transparency_pct: DEFAULT_FILL_TRANSPARENCY_PCT,
-            lang: LANG_EN,
+            lang: LANG_SYSTEM,
             overlay_enabled: true,
             display_mode: DISPLAY_MODE_CIRCLE,
+            click_letter_left: String::new(),
+            click_letter_right: String::new(),
+            letter_font: String::new(),
+            letter_weight: LETTER_WEIGHT_BOLD,
+            click_icon: CLICK_ICON_NONE,
+            highlight_style: HIGHLIGHT_STYLE_CIRCLE,
+            screen_frame: false,
+            speed_fade: false,
+            speed_size: false,
+            click_burst: false,
+            click_burst_ms: DEFAULT_CLICK_BURST_MS,
+            idle_hide: false,
+            idle_hide_secs: DEFAULT_IDLE_HIDE_SECS,
+            click_hold_ms: DEFAULT_CLICK_HOLD_MS,
+            hold_ring: false,
+            hold_ring_ms: DEFAULT_HOLD_RING_MS,
+            motion: MOTION_SYSTEM,
+            halo: false,
+            rainbow: false,
+            rainbow_speed: DEFAULT_RAINBOW_SPEED,
+            glow: GLOW_OFF,
+            pulse: false,
+            pulse_amplitude: DEFAULT_PULSE_AMPLITUDE,
+            pulse_period_ms: DEFAULT_PULSE_PERIOD_MS,
+            transition_ms: DEFAULT_TRANSITION_MS,
+            grid_spacing: DEFAULT_GRID_SPACING,
+            grid_subdivisions: DEFAULT_GRID_SUBDIVISIONS,
+            grid_r: DEFAULT_GRID_COLOR.0,
+            grid_g: DEFAULT_GRID_COLOR.1,
+            grid_b: DEFAULT_GRID_COLOR.2,
+            grid_a: DEFAULT_GRID_COLOR.3,
         }
     }
 }
@@ -55,10 +156,59 @@ impl OverlayState {
         self.fill_transparency_pct = self
             .fill_transparency_pct
             .clamp(MIN_TRANSPARENCY, MAX_TRANSPARENCY);
+        self.click_burst_ms = self
+            .click_burst_ms
+            .clamp(MIN_CLICK_BURST_MS, MAX_CLICK_BURST_MS);
+        self.idle_hide_secs = self
+            .idle_hide_secs
+            .clamp(MIN_IDLE_HIDE_SECS, MAX_IDLE_HIDE_SECS);
+        self.click_hold_ms = self.click_hold_ms.clamp(0.0, MAX_CLICK_HOLD_MS);
+        self.hold_ring_ms = self.hold_ring_ms.clamp(MIN_HOLD_RING_MS, MAX_HOLD_RING_MS);
+        self.rainbow_speed = self
+            .rainbow_speed
+            .clamp(MIN_RAINBOW_SPEED, MAX_RAINBOW_SPEED);
+        self.pulse_amplitude = self
+            .pulse_amplitude
+            .clamp(MIN_PULSE_AMPLITUDE, MAX_PULSE_AMPLITUDE);
+        self.pulse_period_ms = self
+            .pulse_period_ms
+            .clamp(MIN_PULSE_PERIOD_MS, MAX_PULSE_PERIOD_MS);
+        self.transition_ms = self
+            .transition_ms
+            .clamp(MIN_TRANSITION_MS, MAX_TRANSITION_MS);
         self.stroke_r = self.stroke_r.clamp(0.0, 1.0);
         self.stroke_g = self.stroke_g.clamp(0.0, 1.0);
         self.stroke_b = self.stroke_b.clamp(0.0, 1.0);
         self.stroke_a = self.stroke_a.clamp(0.0, 1.0);
+        self.grid_spacing = self.grid_spacing.clamp(MIN_GRID_SPACING, MAX_GRID_SPACING);
+        self.grid_subdivisions = self
+            .grid_subdivisions
+            .clamp(MIN_GRID_SUBDIVISIONS as i32, MAX_GRID_SUBDIVISIONS as i32);
+        self.grid_r = self.grid_r.clamp(0.0, 1.0);
+        self.grid_g = self.grid_g.clamp(0.0, 1.0);
+        self.grid_b = self.grid_b.clamp(0.0, 1.0);
+        self.grid_a = self.grid_a.clamp(0.0, 1.0);
+        if !matches!(
+            self.highlight_style,
+            HIGHLIGHT_STYLE_CIRCLE | HIGHLIGHT_STYLE_LASER
+        ) {
+            self.highlight_style = HIGHLIGHT_STYLE_CIRCLE;
+        }
+        if !matches!(self.motion, MOTION_SYSTEM | MOTION_REDUCED | MOTION_FULL) {
+            self.motion = MOTION_SYSTEM;
+        }
+        if !matches!(self.glow, GLOW_OFF | GLOW_SOFT | GLOW_SHADOW) {
+            self.glow = GLOW_OFF;
+        }
+        if click_icon(self.click_icon).is_none() {
+            self.click_icon = CLICK_ICON_NONE;
+        }
+        if !is_letter_weight(self.letter_weight) {
+            self.letter_weight = LETTER_WEIGHT_BOLD;
+        }
+        self.letter_font = self.letter_font.trim().to_string();
+        self.click_letter_left = click_letter_text(&self.click_letter_left);
+        self.click_letter_right = click_letter_text(&self.click_letter_right);
     }
 
     /// Returns the stroke color as a tuple (r, g, b, a).
@@ -66,6 +216,11 @@ impl OverlayState {
         (self.stroke_r, self.stroke_g, self.stroke_b, self.stroke_a)
     }
 
+    /// Returns the grid line color as a tuple (r, g, b, a).
+    pub fn grid_color(&self) -> (f64, f64, f64, f64) {
+        (self.grid_r, self.grid_g, self.grid_b, self.grid_a)
+    }
+
     /// Calculates fill alpha based on transparency.
     /// 0% transparency = alpha 1.0, 100% transparency = alpha 0.0
     pub fn fill_alpha(&self) -> f64 {
@@ -76,4 +231,25 @@ impl OverlayState {
     pub fn is_spanish(&self) -> bool {
         self.lang == LANG_ES
     }
+
+    /// Returns true if the laser-pointer style is selected.
+    pub fn is_laser(&self) -> bool {
+        self.highlight_style == HIGHLIGHT_STYLE_LASER
+    }
+
+    /// Custom text for a left (`true`) or right click; empty when the
+    /// letter of the selected language is drawn.
+    pub fn custom_click_letter(&self, left: bool) -> &str {
+        if left {
+            &self.click_letter_left
+        } else {
+            &self.click_letter_right
+        }
+    }
+}
+
+/// Custom click letter as stored: trimmed, at most
+/// `MAX_CLICK_LETTER_CHARS` characters.
+pub fn click_letter_text(text: &str) -> String {
+    text.trim().chars().take(MAX_CLICK_LETTER_CHARS).collect()
 }
@@ -0,0 +1,41 @@
+//! Light and dark palettes for the Settings window (pure Rust, no FFI).
+//!
+//! macOS draws the window with semantic system colors and needs no
+//! palette; Windows paints the window background and the label text
+//! itself, so it picks one of these palettes from the system theme.
+
+/// Colors used to paint the Settings window, as 8-bit RGB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SettingsPalette {
+    /// Window and label background.
+    pub background: (u8, u8, u8),
+    /// Label text.
+    pub text: (u8, u8, u8),
+    /// Slider value text, slightly dimmer than the labels.
+    pub value_text: (u8, u8, u8),
+}
+
+/// Palette matching the light system theme.
+pub const LIGHT_PALETTE: SettingsPalette = SettingsPalette {
+    background: (243, 243, 243),
+    text: (0, 0, 0),
+    value_text: (64, 64, 64),
+};
+
+/// Palette matching the dark system theme.
+pub const DARK_PALETTE: SettingsPalette = SettingsPalette {
+    background: (32, 32, 32),
+    text: (255, 255, 255),
+    value_text: (204, 204, 204),
+};
+
+impl SettingsPalette {
+    /// Palette for the dark (`true`) or light system theme.
+    pub fn for_theme(dark: bool) -> Self {
+        if dark {
+            DARK_PALETTE
+        } else {
+            LIGHT_PALETTE
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! Multi-click burst counting (pure Rust, no FFI).
+//!
+//! Consecutive presses of the same button, close together in time and
+//! position, form a burst. The overlay shows the burst length as a small
+//! badge (x2, x3...) next to the click letter, which helps when demoing
+//! double- and triple-click behaviors.
+
+use super::constants::*;
+
+/// Last press of the current burst.
+#[derive(Debug, Clone, Copy)]
+struct Press {
+    button: i32,
+    x: f64,
+    y: f64,
+    t: f64,
+}
+
+/// Click-burst counter.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClickBurst {
+    last: Option<Press>,
+    count: u32,
+}
+
+impl ClickBurst {
+    /// Creates a counter with no burst in progress.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a press of `button` (a `DISPLAY_MODE_*` value) at time `t`
+    /// (seconds) and returns the length of the burst it belongs to.
+    ///
+    /// The burst continues if the same button is pressed again within
+    /// `interval` seconds and `CLICK_BURST_SLOP_PX` pixels of the previous
+    /// press; otherwise a new burst starts at 1.
+    pub fn press(&mut self, button: i32, x: f64, y: f64, t: f64, interval: f64) -> u32 {
+        let continues = self.last.is_some_and(|p| {
+            let dt = t - p.t;
+            p.button == button
+                && (0.0..=interval).contains(&dt)
+                && (x - p.x).hypot(y - p.y) <= CLICK_BURST_SLOP_PX
+        });
+        self.count = if continues {
+            (self.count + 1).min(CLICK_BURST_MAX_COUNT)
+        } else {
+            1
+        };
+        self.last = Some(Press { button, x, y, t });
+        self.count
+    }
+
+    /// Length of the current burst (0 before the first press).
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Forgets the current burst.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Badge text for a burst, or `None` for a single click.
+pub fn badge_label(count: u32) -> Option<String> {
+    (count >= 2).then(|| format!("x{}", count))
+}
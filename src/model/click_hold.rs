@@ -0,0 +1,86 @@
+//! Click indicator hold after mouse-up (pure Rust, no FFI).
+//!
+//! Keeps the L/R indicator of a released button up for a configurable
+//! time instead of snapping back to the circle, so a fast click still
+//! shows in a recording. Each release starts the timer of its own
+//! button; a new press replaces it. The press time also drives the
+//! progress ring of a long press.
+
+use super::constants::*;
+
+/// Button held down and the last one released, with the times they were.
+#[derive(Debug, Clone, Copy)]
+pub struct ClickHold {
+    held: i32,
+    pressed_at: f64,
+    released: i32,
+    released_at: f64,
+}
+
+impl Default for ClickHold {
+    fn default() -> Self {
+        Self {
+            held: DISPLAY_MODE_CIRCLE,
+            pressed_at: 0.0,
+            released: DISPLAY_MODE_CIRCLE,
+            released_at: 0.0,
+        }
+    }
+}
+
+impl ClickHold {
+    /// Creates a tracker with no button down.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a press of `button` (`DISPLAY_MODE_LEFT` or
+    /// `DISPLAY_MODE_RIGHT`) at time `t` (seconds); any hold still
+    /// running ends.
+    pub fn press(&mut self, button: i32, t: f64) {
+        self.held = button;
+        self.pressed_at = t;
+        self.released = DISPLAY_MODE_CIRCLE;
+    }
+
+    /// Records the release of the held button at time `t` (seconds).
+    pub fn release(&mut self, t: f64) {
+        if self.held != DISPLAY_MODE_CIRCLE {
+            self.released = self.held;
+            self.released_at = t;
+        }
+        self.held = DISPLAY_MODE_CIRCLE;
+    }
+
+    /// `DISPLAY_MODE_*` to draw at time `t`: the button held, else the
+    /// one released less than `hold_ms` ago, else the circle.
+    pub fn mode(&self, t: f64, hold_ms: f64) -> i32 {
+        if self.held != DISPLAY_MODE_CIRCLE {
+            self.held
+        } else if (t - self.released_at) * 1000.0 < hold_ms {
+            self.released
+        } else {
+            DISPLAY_MODE_CIRCLE
+        }
+    }
+
+    /// Seconds the current button has been held at time `t` (`None` when
+    /// no button is down).
+    pub fn held_secs(&self, t: f64) -> Option<f64> {
+        (self.held != DISPLAY_MODE_CIRCLE).then(|| (t - self.pressed_at).max(0.0))
+    }
+
+    /// Forgets the buttons (the circle is drawn again).
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Fraction [0.0, 1.0] of the hold ring filled after `held_secs` of a
+/// press, full after `ring_ms`.
+pub fn hold_progress(held_secs: f64, ring_ms: f64) -> f64 {
+    if ring_ms <= 0.0 {
+        return 1.0;
+    }
+    (held_secs * 1000.0 / ring_ms).clamp(0.0, 1.0)
+}
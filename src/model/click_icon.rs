@@ -0,0 +1,47 @@
+//! Icons drawn on clicks instead of the letters (pure Rust, no FFI).
+//!
+//! Each icon names an SF Symbol, drawn on macOS, and the code point of the
+//! same picture in Segoe Fluent Icons (Windows 11), which Segoe MDL2
+//! Assets (Windows 10) shares. The same icon is drawn for both buttons.
+
+use super::constants::*;
+
+/// A click icon of the Settings list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClickIcon {
+    /// `CLICK_ICON_*` value stored in the preferences.
+    pub id: i32,
+    /// English name shown in Settings.
+    pub label: &'static str,
+    /// SF Symbol name (macOS 11+).
+    pub symbol: &'static str,
+    /// Segoe Fluent Icons / MDL2 Assets code point.
+    pub glyph: char,
+}
+
+/// Icons offered in Settings, after "Letters".
+pub const CLICK_ICONS: [ClickIcon; 3] = [
+    ClickIcon {
+        id: CLICK_ICON_TAP,
+        label: "Tap",
+        symbol: "hand.tap.fill",
+        glyph: '\u{E7C9}', // TouchPointer
+    },
+    ClickIcon {
+        id: CLICK_ICON_MOUSE,
+        label: "Mouse",
+        symbol: "computermouse.fill",
+        glyph: '\u{E962}', // Mouse
+    },
+    ClickIcon {
+        id: CLICK_ICON_STAR,
+        label: "Star",
+        symbol: "star.fill",
+        glyph: '\u{E735}', // FavoriteStarFill
+    },
+];
+
+/// Icon drawn for `CLICK_ICON_*` value `id`; `None` draws the letters.
+pub fn click_icon(id: i32) -> Option<&'static ClickIcon> {
+    CLICK_ICONS.iter().find(|icon| icon.id == id)
+}
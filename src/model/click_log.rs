@@ -0,0 +1,216 @@
+//! Click log for usability testing (pure Rust, no FFI).
+//!
+//! While recording, every button press is stored with its timestamp,
+//! position and the display it happened on. The log is bounded (see
+//! `CLICK_LOG_CAPACITY`) and can be exported as CSV or JSON.
+//!
+//! Positions are in the platform's global screen coordinates (Cocoa points
+//! with a bottom-left origin on macOS, virtual-screen pixels on Windows);
+//! displays are identified by `placement::display_key`.
+
+use std::collections::VecDeque;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::constants::*;
+
+/// Export file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClickLogFormat {
+    Csv,
+    Json,
+}
+
+impl ClickLogFormat {
+    /// File extension without the leading dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ClickLogFormat::Csv => "csv",
+            ClickLogFormat::Json => "json",
+        }
+    }
+}
+
+/// A single recorded click.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClickRecord {
+    /// Seconds since the Unix epoch.
+    pub timestamp: f64,
+    pub x: f64,
+    pub y: f64,
+    /// `DISPLAY_MODE_LEFT` or `DISPLAY_MODE_RIGHT`.
+    pub button: i32,
+    /// Display key of the screen the click landed on.
+    pub display: String,
+}
+
+/// Bounded, in-memory click log.
+#[derive(Debug, Clone)]
+pub struct ClickLog {
+    records: VecDeque<ClickRecord>,
+    capacity: usize,
+    recording: bool,
+    started_at: f64,
+}
+
+impl Default for ClickLog {
+    fn default() -> Self {
+        Self::with_capacity(CLICK_LOG_CAPACITY)
+    }
+}
+
+impl ClickLog {
+    /// Creates an empty, stopped log holding up to `CLICK_LOG_CAPACITY` clicks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty, stopped log holding up to `capacity` clicks.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            records: VecDeque::new(),
+            capacity: capacity.max(1),
+            recording: false,
+            started_at: 0.0,
+        }
+    }
+
+    /// Is the log currently recording?
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Starts a new recording at time `now`, discarding the previous one.
+    pub fn start(&mut self, now: f64) {
+        self.records.clear();
+        self.recording = true;
+        self.started_at = now;
+    }
+
+    /// Stops recording; the clicks stay available for export.
+    pub fn stop(&mut self) {
+        self.recording = false;
+    }
+
+    /// Starts a new recording at `now`, or stops the current one.
+    ///
+    /// Returns the new recording state.
+    pub fn toggle(&mut self, now: f64) -> bool {
+        if self.recording {
+            self.stop();
+        } else {
+            self.start(now);
+        }
+        self.recording
+    }
+
+    /// Stores a click if recording. Returns whether it was stored.
+    pub fn record(&mut self, click: ClickRecord) -> bool {
+        if !self.recording {
+            return false;
+        }
+        if self.records.len() == self.capacity {
+            self.records.pop_front();
+        }
+        self.records.push_back(click);
+        true
+    }
+
+    /// Number of stored clicks.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns true if no clicks are stored.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Stored clicks, oldest first.
+    pub fn records(&self) -> impl Iterator<Item = &ClickRecord> {
+        self.records.iter()
+    }
+
+    /// Serializes the log in the given format.
+    pub fn export(&self, format: ClickLogFormat) -> String {
+        match format {
+            ClickLogFormat::Csv => self.to_csv(),
+            ClickLogFormat::Json => self.to_json(),
+        }
+    }
+
+    /// CSV with a header row; `elapsed` is seconds since recording started.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("timestamp,elapsed,x,y,button,display\n");
+        for r in &self.records {
+            out.push_str(&format!(
+                "{:.3},{:.3},{:.1},{:.1},{},{}\n",
+                r.timestamp,
+                r.timestamp - self.started_at,
+                r.x,
+                r.y,
+                button_name(r.button),
+                r.display
+            ));
+        }
+        out
+    }
+
+    /// JSON array of click objects with the same fields as the CSV.
+    pub fn to_json(&self) -> String {
+        let rows: Vec<String> = self
+            .records
+            .iter()
+            .map(|r| {
+                format!(
+                    "  {{\"timestamp\": {:.3}, \"elapsed\": {:.3}, \"x\": {:.1}, \"y\": {:.1}, \"button\": \"{}\", \"display\": \"{}\"}}",
+                    r.timestamp,
+                    r.timestamp - self.started_at,
+                    r.x,
+                    r.y,
+                    button_name(r.button),
+                    json_escape(&r.display)
+                )
+            })
+            .collect();
+        if rows.is_empty() {
+            "[]\n".to_string()
+        } else {
+            format!("[\n{}\n]\n", rows.join(",\n"))
+        }
+    }
+}
+
+/// Name of a recorded button in exports.
+pub fn button_name(button: i32) -> &'static str {
+    match button {
+        DISPLAY_MODE_LEFT => "left",
+        DISPLAY_MODE_RIGHT => "right",
+        _ => "other",
+    }
+}
+
+/// Current wall-clock time in seconds since the Unix epoch.
+pub fn unix_time_now() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0)
+}
+
+/// Default export file name for a format, e.g. `lumbus-clicks.csv`.
+pub fn default_file_name(format: ClickLogFormat) -> String {
+    format!("lumbus-clicks.{}", format.extension())
+}
+
+fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,272 @@
+//! Color input parsing (pure Rust, no FFI).
+//!
+//! The Hex field of the Settings window accepts colors in the formats
+//! presenters paste from brand guides: `#RGB`, `#RGBA`, `#RRGGBB`,
+//! `#RRGGBBAA` (the `#` is optional), `rgb(r, g, b)`, `rgba(r, g, b, a)`
+//! and the CSS named colors. The field then shows the color normalized
+//! back to hex (see [`crate::color_to_hex`]).
+//!
+//! The hue/saturation/brightness sliders convert to and from the stored
+//! RGB with [`rgb_to_hsb`] and [`hsb_to_rgb`].
+
+use super::screen_frame::Rgba;
+use crate::parse_hex_color;
+
+/// Parse a color in any of the accepted formats into normalised floats
+/// [0..1]. Case and surrounding whitespace are ignored.
+pub fn parse_color(s: &str) -> Option<Rgba> {
+    let t = s.trim().to_ascii_lowercase();
+    if let Some(args) = function_args(&t, "rgba").or_else(|| function_args(&t, "rgb")) {
+        return parse_rgb_args(args);
+    }
+    if t == "transparent" {
+        return Some((0.0, 0.0, 0.0, 0.0));
+    }
+    if let Some(rgba) = named_color(&t) {
+        return Some(rgba);
+    }
+    let hex = t.strip_prefix('#').unwrap_or(&t);
+    match hex.len() {
+        3 | 4 => parse_hex_color(&hex.chars().flat_map(|c| [c, c]).collect::<String>()),
+        _ => parse_hex_color(hex),
+    }
+}
+
+/// Hue (degrees, [0, 360)), saturation and brightness ([0.0, 1.0]) of an
+/// RGB color. Grays have hue 0; black also has saturation 0.
+pub fn rgb_to_hsb(r: f64, g: f64, b: f64) -> (f64, f64, f64) {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let hue = if delta <= 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    let saturation = if max > 0.0 { delta / max } else { 0.0 };
+    (hue, saturation, max)
+}
+
+/// RGB color of a hue (degrees, wrapped to [0, 360)), saturation and
+/// brightness ([0.0, 1.0], clamped).
+pub fn hsb_to_rgb(hue: f64, saturation: f64, brightness: f64) -> (f64, f64, f64) {
+    let h = hue.rem_euclid(360.0) / 60.0;
+    let s = saturation.clamp(0.0, 1.0);
+    let v = brightness.clamp(0.0, 1.0);
+    let c = v * s;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = v - c;
+    (r + m, g + m, b + m)
+}
+
+/// Text between the parentheses of `name(...)`.
+fn function_args<'a>(s: &'a str, name: &str) -> Option<&'a str> {
+    s.strip_prefix(name)?
+        .trim_start()
+        .strip_prefix('(')?
+        .strip_suffix(')')
+}
+
+/// Components of `rgb()` / `rgba()`: three channels (0-255 or
+/// percentages) and an optional alpha (0-1 or a percentage), separated by
+/// commas or, in the newer CSS syntax, by spaces with a `/` before alpha.
+/// Out-of-range values are clamped, as browsers do.
+fn parse_rgb_args(args: &str) -> Option<Rgba> {
+    let parts: Vec<&str> = if args.contains(',') {
+        args.split(',').map(str::trim).collect()
+    } else {
+        args.split(|c: char| c.is_whitespace() || c == '/')
+            .filter(|p| !p.is_empty())
+            .collect()
+    };
+    let (channels, alpha) = match parts.as_slice() {
+        [r, g, b] => ([*r, *g, *b], None),
+        [r, g, b, a] => ([*r, *g, *b], Some(*a)),
+        _ => return None,
+    };
+    let [r, g, b] = channels.map(|c| component(c, 255.0));
+    let a = alpha.map_or(Some(1.0), |a| component(a, 1.0))?;
+    Some((r?, g?, b?, a))
+}
+
+/// A number out of `max`, or a percentage, as a fraction in [0.0, 1.0].
+fn component(s: &str, max: f64) -> Option<f64> {
+    let (number, scale) = match s.strip_suffix('%') {
+        Some(pct) => (pct, 100.0),
+        None => (s, max),
+    };
+    let value: f64 = number.trim().parse().ok()?;
+    value.is_finite().then(|| (value / scale).clamp(0.0, 1.0))
+}
+
+/// CSS named color (lowercase), opaque.
+fn named_color(name: &str) -> Option<Rgba> {
+    let index = CSS_COLORS.binary_search_by(|(n, _)| n.cmp(&name)).ok()?;
+    let rgb = CSS_COLORS[index].1;
+    let channel = |shift: u32| ((rgb >> shift) & 0xFF) as f64 / 255.0;
+    Some((channel(16), channel(8), channel(0), 1.0))
+}
+
+/// CSS named colors, sorted by name.
+const CSS_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xF0F8FF),
+    ("antiquewhite", 0xFAEBD7),
+    ("aqua", 0x00FFFF),
+    ("aquamarine", 0x7FFFD4),
+    ("azure", 0xF0FFFF),
+    ("beige", 0xF5F5DC),
+    ("bisque", 0xFFE4C4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xFFEBCD),
+    ("blue", 0x0000FF),
+    ("blueviolet", 0x8A2BE2),
+    ("brown", 0xA52A2A),
+    ("burlywood", 0xDEB887),
+    ("cadetblue", 0x5F9EA0),
+    ("chartreuse", 0x7FFF00),
+    ("chocolate", 0xD2691E),
+    ("coral", 0xFF7F50),
+    ("cornflowerblue", 0x6495ED),
+    ("cornsilk", 0xFFF8DC),
+    ("crimson", 0xDC143C),
+    ("cyan", 0x00FFFF),
+    ("darkblue", 0x00008B),
+    ("darkcyan", 0x008B8B),
+    ("darkgoldenrod", 0xB8860B),
+    ("darkgray", 0xA9A9A9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xA9A9A9),
+    ("darkkhaki", 0xBDB76B),
+    ("darkmagenta", 0x8B008B),
+    ("darkolivegreen", 0x556B2F),
+    ("darkorange", 0xFF8C00),
+    ("darkorchid", 0x9932CC),
+    ("darkred", 0x8B0000),
+    ("darksalmon", 0xE9967A),
+    ("darkseagreen", 0x8FBC8F),
+    ("darkslateblue", 0x483D8B),
+    ("darkslategray", 0x2F4F4F),
+    ("darkslategrey", 0x2F4F4F),
+    ("darkturquoise", 0x00CED1),
+    ("darkviolet", 0x9400D3),
+    ("deeppink", 0xFF1493),
+    ("deepskyblue", 0x00BFFF),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1E90FF),
+    ("firebrick", 0xB22222),
+    ("floralwhite", 0xFFFAF0),
+    ("forestgreen", 0x228B22),
+    ("fuchsia", 0xFF00FF),
+    ("gainsboro", 0xDCDCDC),
+    ("ghostwhite", 0xF8F8FF),
+    ("gold", 0xFFD700),
+    ("goldenrod", 0xDAA520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xADFF2F),
+    ("grey", 0x808080),
+    ("honeydew", 0xF0FFF0),
+    ("hotpink", 0xFF69B4),
+    ("indianred", 0xCD5C5C),
+    ("indigo", 0x4B0082),
+    ("ivory", 0xFFFFF0),
+    ("khaki", 0xF0E68C),
+    ("lavender", 0xE6E6FA),
+    ("lavenderblush", 0xFFF0F5),
+    ("lawngreen", 0x7CFC00),
+    ("lemonchiffon", 0xFFFACD),
+    ("lightblue", 0xADD8E6),
+    ("lightcoral", 0xF08080),
+    ("lightcyan", 0xE0FFFF),
+    ("lightgoldenrodyellow", 0xFAFAD2),
+    ("lightgray", 0xD3D3D3),
+    ("lightgreen", 0x90EE90),
+    ("lightgrey", 0xD3D3D3),
+    ("lightpink", 0xFFB6C1),
+    ("lightsalmon", 0xFFA07A),
+    ("lightseagreen", 0x20B2AA),
+    ("lightskyblue", 0x87CEFA),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xB0C4DE),
+    ("lightyellow", 0xFFFFE0),
+    ("lime", 0x00FF00),
+    ("limegreen", 0x32CD32),
+    ("linen", 0xFAF0E6),
+    ("magenta", 0xFF00FF),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66CDAA),
+    ("mediumblue", 0x0000CD),
+    ("mediumorchid", 0xBA55D3),
+    ("mediumpurple", 0x9370DB),
+    ("mediumseagreen", 0x3CB371),
+    ("mediumslateblue", 0x7B68EE),
+    ("mediumspringgreen", 0x00FA9A),
+    ("mediumturquoise", 0x48D1CC),
+    ("mediumvioletred", 0xC71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xF5FFFA),
+    ("mistyrose", 0xFFE4E1),
+    ("moccasin", 0xFFE4B5),
+    ("navajowhite", 0xFFDEAD),
+    ("navy", 0x000080),
+    ("oldlace", 0xFDF5E6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6B8E23),
+    ("orange", 0xFFA500),
+    ("orangered", 0xFF4500),
+    ("orchid", 0xDA70D6),
+    ("palegoldenrod", 0xEEE8AA),
+    ("palegreen", 0x98FB98),
+    ("paleturquoise", 0xAFEEEE),
+    ("palevioletred", 0xDB7093),
+    ("papayawhip", 0xFFEFD5),
+    ("peachpuff", 0xFFDAB9),
+    ("peru", 0xCD853F),
+    ("pink", 0xFFC0CB),
+    ("plum", 0xDDA0DD),
+    ("powderblue", 0xB0E0E6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xFF0000),
+    ("rosybrown", 0xBC8F8F),
+    ("royalblue", 0x4169E1),
+    ("saddlebrown", 0x8B4513),
+    ("salmon", 0xFA8072),
+    ("sandybrown", 0xF4A460),
+    ("seagreen", 0x2E8B57),
+    ("seashell", 0xFFF5EE),
+    ("sienna", 0xA0522D),
+    ("silver", 0xC0C0C0),
+    ("skyblue", 0x87CEEB),
+    ("slateblue", 0x6A5ACD),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xFFFAFA),
+    ("springgreen", 0x00FF7F),
+    ("steelblue", 0x4682B4),
+    ("tan", 0xD2B48C),
+    ("teal", 0x008080),
+    ("thistle", 0xD8BFD8),
+    ("tomato", 0xFF6347),
+    ("turquoise", 0x40E0D0),
+    ("violet", 0xEE82EE),
+    ("wheat", 0xF5DEB3),
+    ("white", 0xFFFFFF),
+    ("whitesmoke", 0xF5F5F5),
+    ("yellow", 0xFFFF00),
+    ("yellowgreen", 0x9ACD32),
+];
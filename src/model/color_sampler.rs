@@ -0,0 +1,43 @@
+//! Pixel color sampler (pure Rust, no FFI).
+//!
+//! While the eyedropper is on (toggled by a hotkey), the overlay reads the
+//! screen pixel under the pointer with a 1×1 capture that leaves the
+//! overlay out, shows the color on a swatch with its hex next to the
+//! highlight, and a click copies the hex to the clipboard.
+
+/// Sampled color, 8 bits per channel.
+pub type Rgb8 = (u8, u8, u8);
+
+/// Hex of a sample as shown and copied, e.g. `#1E90FF`.
+pub fn sample_hex((r, g, b): Rgb8) -> String {
+    format!("#{r:02X}{g:02X}{b:02X}")
+}
+
+/// Sample of a pixel of a 32-bit GDI capture (blue, green, red, unused).
+pub fn from_bgra(pixel: &[u8]) -> Option<Rgb8> {
+    match pixel {
+        [b, g, r, ..] => Some((*r, *g, *b)),
+        _ => None,
+    }
+}
+
+/// Sample of color components in 0..1, as Cocoa reports them.
+pub fn from_unit(r: f64, g: f64, b: f64) -> Rgb8 {
+    let byte = |v: f64| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    (byte(r), byte(g), byte(b))
+}
+
+/// Components in 0..1 of a sample, for filling its swatch.
+pub fn to_unit((r, g, b): Rgb8) -> (f64, f64, f64) {
+    (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0)
+}
+
+/// Sample packed as `0xRRGGBB`, for storing in a view ivar.
+pub fn pack_rgb((r, g, b): Rgb8) -> i32 {
+    (r as i32) << 16 | (g as i32) << 8 | b as i32
+}
+
+/// Sample of a packed `0xRRGGBB`; negative values mean no sample.
+pub fn unpack_rgb(packed: i32) -> Option<Rgb8> {
+    (packed >= 0).then_some(((packed >> 16) as u8, (packed >> 8) as u8, packed as u8))
+}
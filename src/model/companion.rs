@@ -0,0 +1,188 @@
+//! Companion protocol for Stream Deck plugins and similar tools (pure
+//! Rust, no FFI).
+//!
+//! A line-based protocol on the control endpoint's socket (see
+//! `model::control`): a connection whose first line is not an HTTP
+//! request line is a companion session. Lines are UTF-8, ending in `\n`:
+//!
+//! ```text
+//! > AUTH <token>           < OK
+//! > WATCH                  < ENABLED on / PROFILE standard / LOCKED off / OK
+//! > TOGGLE                 < OK
+//!                          < ENABLED off          (pushed when it changes)
+//! ```
+//!
+//! Commands: `AUTH <token>` (required first), `STATE` (current state),
+//! `WATCH` (current state, then a push whenever it changes), `TOGGLE`,
+//! `PROFILE <name>`, `SET <key> <value>` (overlay settings, by preference
+//! key) and `BYE`. Each command is answered with `OK` or `ERR <reason>`,
+//! after any state lines.
+
+use crate::events::AppEvent;
+
+use super::control::{same_token, ControlState};
+use super::hotkeys::HOTKEY_PROFILES;
+use super::url_scheme::setting_event;
+
+/// Command sent by a companion.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompanionCommand {
+    Auth(String),
+    State,
+    Watch,
+    Toggle,
+    Profile(String),
+    Set(String, f64),
+    Bye,
+}
+
+impl CompanionCommand {
+    /// Parse a command line (the command word is case-insensitive).
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or_default().to_ascii_uppercase();
+        let args: Vec<&str> = words.collect();
+        match (command.as_str(), args.as_slice()) {
+            ("AUTH", [token]) => Ok(Self::Auth(token.to_string())),
+            ("STATE", []) => Ok(Self::State),
+            ("WATCH", []) => Ok(Self::Watch),
+            ("TOGGLE", []) => Ok(Self::Toggle),
+            ("PROFILE", [name]) => Ok(Self::Profile(name.to_ascii_lowercase())),
+            ("SET", [key, value]) => value
+                .parse()
+                .map(|value| Self::Set(key.to_string(), value))
+                .map_err(|_| format!("not a number: {value}")),
+            ("BYE", []) => Ok(Self::Bye),
+            ("", _) => Err("empty command".to_string()),
+            _ => Err(format!("unknown command: {}", line.trim())),
+        }
+    }
+}
+
+/// Lines to send for a command, and the events to publish for it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CompanionReply {
+    pub lines: Vec<String>,
+    pub events: Vec<AppEvent>,
+    /// Close the connection after sending `lines`.
+    pub close: bool,
+}
+
+impl CompanionReply {
+    fn ok(mut lines: Vec<String>, events: Vec<AppEvent>) -> Self {
+        lines.push("OK".to_string());
+        Self {
+            lines,
+            events,
+            close: false,
+        }
+    }
+
+    fn error(reason: &str) -> Self {
+        Self {
+            lines: vec![format!("ERR {reason}")],
+            ..Self::default()
+        }
+    }
+}
+
+/// One companion connection.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompanionSession {
+    authorized: bool,
+    watching: bool,
+}
+
+impl CompanionSession {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Does the companion want state pushes?
+    pub fn is_watching(&self) -> bool {
+        self.watching
+    }
+
+    /// Answer a command line, given the expected token and the app's state.
+    pub fn handle(&mut self, line: &str, token: &str, state: &ControlState) -> CompanionReply {
+        let command = match CompanionCommand::parse(line) {
+            Ok(command) => command,
+            Err(reason) => return CompanionReply::error(&reason),
+        };
+        if let CompanionCommand::Auth(given) = &command {
+            self.authorized = !token.is_empty() && same_token(given, token);
+            return if self.authorized {
+                CompanionReply::ok(Vec::new(), Vec::new())
+            } else {
+                CompanionReply {
+                    close: true,
+                    ..CompanionReply::error("wrong token")
+                }
+            };
+        }
+        if !self.authorized {
+            return CompanionReply {
+                close: true,
+                ..CompanionReply::error("AUTH first")
+            };
+        }
+        match command {
+            CompanionCommand::Auth(_) => unreachable!(),
+            CompanionCommand::State => CompanionReply::ok(state_lines(state), Vec::new()),
+            CompanionCommand::Watch => {
+                self.watching = true;
+                CompanionReply::ok(state_lines(state), Vec::new())
+            }
+            CompanionCommand::Toggle => {
+                CompanionReply::ok(Vec::new(), vec![AppEvent::ToggleOverlay])
+            }
+            CompanionCommand::Profile(name) => {
+                if !HOTKEY_PROFILES.contains(&name.as_str()) {
+                    return CompanionReply::error(&format!("unknown profile: {name}"));
+                }
+                CompanionReply::ok(Vec::new(), vec![AppEvent::SelectHotkeyProfile(name)])
+            }
+            CompanionCommand::Set(key, value) => {
+                let Some(event) = setting_event(&key, value) else {
+                    return CompanionReply::error(&format!("cannot set {key}"));
+                };
+                if state.locked {
+                    return CompanionReply::error("settings are locked");
+                }
+                CompanionReply::ok(Vec::new(), vec![event])
+            }
+            CompanionCommand::Bye => CompanionReply {
+                close: true,
+                ..CompanionReply::ok(Vec::new(), Vec::new())
+            },
+        }
+    }
+}
+
+fn on_off(on: bool) -> &'static str {
+    if on {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+/// State lines describing `state`.
+pub fn state_lines(state: &ControlState) -> Vec<String> {
+    vec![
+        format!("ENABLED {}", on_off(state.visible)),
+        format!("PROFILE {}", state.hotkey_profile),
+        format!("LOCKED {}", on_off(state.locked)),
+    ]
+}
+
+/// State lines to push to a watching companion when the state goes from
+/// `old` to `new` (only what changed).
+pub fn state_changes(old: &ControlState, new: &ControlState) -> Vec<String> {
+    state_lines(old)
+        .into_iter()
+        .zip(state_lines(new))
+        .filter(|(old, new)| old != new)
+        .map(|(_, new)| new)
+        .collect()
+}
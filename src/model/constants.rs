@@ -1,7 +1,7 @@
 //! Configuration constants and default values.
 //!
 //! This module contains all application constants including visual defaults,
-//! NSUserDefaults keys, and validation limits.
+//! preference keys, and validation limits.
 
 // === Visual Defaults ===
 
@@ -18,7 +18,7 @@ pub const DEFAULT_COLOR: (f64, f64, f64, f64) = (0.890, 0.482, 0.0, 1.0);
 /// 10% means 90% opaque.
 pub const DEFAULT_FILL_TRANSPARENCY_PCT: f64 = 10.0;
 
-// === NSUserDefaults Keys ===
+// === Preference Keys ===
 
 /// Key for circle radius preference.
 pub const PREF_RADIUS: &str = "radius";
@@ -44,6 +44,133 @@ pub const PREF_FILL_TRANSPARENCY: &str = "fillTransparencyPct";
 /// Key for language preference (0 = EN, 1 = ES).
 pub const PREF_LANG: &str = "lang";
 
+/// Key for highlight style preference (0 = circle, 1 = laser pointer).
+pub const PREF_HIGHLIGHT_STYLE: &str = "highlightStyle";
+
+/// Key for the auxiliary-window session string (see `model::session`).
+pub const PREF_SESSION: &str = "session";
+
+/// Key for the speed-based opacity ramp toggle (0 = off, 1 = on).
+pub const PREF_SPEED_FADE: &str = "speedFade";
+
+/// Key for the speed-based size scaling toggle (0 = off, 1 = on).
+pub const PREF_SPEED_SIZE: &str = "speedSize";
+
+/// Key for the recording-frame toggle (0 = off, 1 = on).
+pub const PREF_SCREEN_FRAME: &str = "screenFrame";
+
+/// Key for the per-display recording-frame colors (see `model::screen_frame`).
+pub const PREF_SCREEN_FRAME_COLORS: &str = "screenFrameColors";
+
+/// Key for the colors saved to the Settings palette (see `model::palette`).
+pub const PREF_COLOR_PALETTE: &str = "colorPalette";
+
+/// Key for the multi-click counter badge toggle (0 = off, 1 = on).
+pub const PREF_CLICK_BURST: &str = "clickBurst";
+
+/// Key for the maximum interval between clicks of a burst, in milliseconds.
+pub const PREF_CLICK_BURST_MS: &str = "clickBurstMs";
+
+/// Key for how long the L/R indicator stays up after mouse-up, in
+/// milliseconds (0 = back to the circle at once).
+pub const PREF_CLICK_HOLD_MS: &str = "clickHoldMs";
+
+/// Key for the press-and-hold progress ring toggle (0 = off, 1 = on).
+pub const PREF_HOLD_RING: &str = "holdRing";
+
+/// Key for how long a button must be held to fill the ring, in milliseconds.
+pub const PREF_HOLD_RING_MS: &str = "holdRingMs";
+
+/// Key for the idle auto-hide toggle (0 = off, 1 = on).
+pub const PREF_IDLE_HIDE: &str = "idleHide";
+
+/// Key for the idle time before the highlight fades out, in seconds.
+pub const PREF_IDLE_HIDE_SECS: &str = "idleHideSecs";
+
+/// Key for the Reduce Motion mode (see `MOTION_*`).
+pub const PREF_MOTION: &str = "motion";
+
+/// Key for the contrasting outline halo toggle (0 = off, 1 = on).
+pub const PREF_HALO: &str = "halo";
+
+/// Key for the rainbow color mode toggle (0 = off, 1 = on).
+pub const PREF_RAINBOW: &str = "rainbow";
+
+/// Key for the rainbow hue cycles per minute.
+pub const PREF_RAINBOW_SPEED: &str = "rainbowSpeed";
+
+/// Key for the glow behind the highlight (see `GLOW_*`).
+pub const PREF_GLOW: &str = "glow";
+
+/// Key for the pulsing "breathing" animation toggle (0 = off, 1 = on).
+pub const PREF_PULSE: &str = "pulse";
+
+/// Key for the pulse amplitude, as a percentage of the radius.
+pub const PREF_PULSE_AMPLITUDE: &str = "pulseAmplitude";
+
+/// Key for the pulse period in milliseconds.
+pub const PREF_PULSE_PERIOD_MS: &str = "pulsePeriodMs";
+
+/// Key for the show/hide transition duration in milliseconds (0 = instant).
+pub const PREF_TRANSITION_MS: &str = "transitionMs";
+
+/// Key for the distance between major lines of the alignment grid, in pixels.
+pub const PREF_GRID_SPACING: &str = "gridSpacing";
+
+/// Key for the number of parts each grid cell is split into by minor lines.
+pub const PREF_GRID_SUBDIVISIONS: &str = "gridSubdivisions";
+
+/// Key for grid line red component preference.
+pub const PREF_GRID_R: &str = "gridR";
+
+/// Key for grid line green component preference.
+pub const PREF_GRID_G: &str = "gridG";
+
+/// Key for grid line blue component preference.
+pub const PREF_GRID_B: &str = "gridB";
+
+/// Key for grid line alpha component preference.
+pub const PREF_GRID_A: &str = "gridA";
+
+/// Key for the selected hotkey profile (see `model::hotkeys`).
+pub const PREF_HOTKEY_PROFILE: &str = "hotkeyProfile";
+
+/// Key for the hotkeys rebound on top of the profile (see `model::hotkeys`).
+pub const PREF_HOTKEY_OVERRIDES: &str = "hotkeyOverrides";
+
+/// Key for the folder the settings are synced through (see `model::sync`).
+pub const PREF_SYNC_FOLDER: &str = "syncFolder";
+
+/// Key for the local control endpoint toggle (0 = off, 1 = on).
+pub const PREF_CONTROL_SERVER: &str = "controlServer";
+
+/// Key for the local control endpoint's TCP port.
+pub const PREF_CONTROL_PORT: &str = "controlPort";
+
+/// Key for the token clients of the control endpoint must send.
+pub const PREF_CONTROL_TOKEN: &str = "controlToken";
+
+/// Key for the daily update check (0 = off, 1 = on).
+pub const PREF_UPDATE_CHECK: &str = "updateCheck";
+
+/// Key for when updates were last checked, in seconds since the Unix epoch.
+pub const PREF_LAST_UPDATE_CHECK: &str = "lastUpdateCheck";
+
+/// Key for the first-run walkthrough having been shown (0 = not yet, 1 = done).
+pub const PREF_ONBOARDING_DONE: &str = "onboardingDone";
+
+/// Key for the notification shown when a hotkey toggles the highlight
+/// (0 = off, 1 = on; Windows only).
+pub const PREF_TOGGLE_NOTIFICATION: &str = "toggleNotification";
+
+/// Key for keeping the overlay out of screen captures, so recordings and
+/// viewers of a shared screen do not see it (0 = off, 1 = on).
+pub const PREF_EXCLUDE_FROM_CAPTURE: &str = "excludeFromCapture";
+
+/// Key for asking before quitting from the quit hotkey or the status bar /
+/// tray menu (0 = quit at once, 1 = ask).
+pub const PREF_CONFIRM_QUIT: &str = "confirmQuit";
+
 // === Validation Limits ===
 
 /// Minimum radius value in pixels.
@@ -55,6 +182,10 @@ pub const MAX_RADIUS: f64 = 200.0;
 /// Radius slider step in pixels.
 pub const RADIUS_STEP: f64 = 5.0;
 
+/// Radii offered by the Radius submenu of the status bar / tray menu, in
+/// pixels (the default first).
+pub const QUICK_RADII: [f64; 6] = [15.0, 25.0, 40.0, 60.0, 80.0, 120.0];
+
 /// Minimum border width in pixels.
 pub const MIN_BORDER: f64 = 1.0;
 
@@ -73,6 +204,87 @@ pub const MAX_TRANSPARENCY: f64 = 100.0;
 /// Transparency slider step percentage.
 pub const TRANSPARENCY_STEP: f64 = 5.0;
 
+/// Minimum click-burst interval in milliseconds.
+pub const MIN_CLICK_BURST_MS: f64 = 150.0;
+
+/// Maximum click-burst interval in milliseconds.
+pub const MAX_CLICK_BURST_MS: f64 = 1000.0;
+
+/// Click-burst interval slider step in milliseconds.
+pub const CLICK_BURST_STEP_MS: f64 = 50.0;
+
+/// Maximum click indicator hold in milliseconds.
+pub const MAX_CLICK_HOLD_MS: f64 = 1000.0;
+
+/// Click indicator hold slider step in milliseconds.
+pub const CLICK_HOLD_STEP_MS: f64 = 50.0;
+
+/// Minimum time to fill the hold ring in milliseconds.
+pub const MIN_HOLD_RING_MS: f64 = 250.0;
+
+/// Maximum time to fill the hold ring in milliseconds.
+pub const MAX_HOLD_RING_MS: f64 = 3000.0;
+
+/// Hold ring slider step in milliseconds.
+pub const HOLD_RING_STEP_MS: f64 = 250.0;
+
+/// Smallest grid spacing in pixels.
+pub const MIN_GRID_SPACING: f64 = 10.0;
+
+/// Largest grid spacing in pixels.
+pub const MAX_GRID_SPACING: f64 = 400.0;
+
+/// Grid spacing slider step in pixels.
+pub const GRID_SPACING_STEP: f64 = 5.0;
+
+/// Fewest parts a grid cell is split into (no minor lines).
+pub const MIN_GRID_SUBDIVISIONS: f64 = 1.0;
+
+/// Most parts a grid cell is split into.
+pub const MAX_GRID_SUBDIVISIONS: f64 = 10.0;
+
+/// Minimum idle auto-hide delay in seconds.
+pub const MIN_IDLE_HIDE_SECS: f64 = 1.0;
+
+/// Maximum idle auto-hide delay in seconds.
+pub const MAX_IDLE_HIDE_SECS: f64 = 30.0;
+
+/// Slowest rainbow speed in hue cycles per minute.
+pub const MIN_RAINBOW_SPEED: f64 = 1.0;
+
+/// Fastest rainbow speed in hue cycles per minute.
+pub const MAX_RAINBOW_SPEED: f64 = 30.0;
+
+/// Rainbow speed slider step in cycles per minute.
+pub const RAINBOW_SPEED_STEP: f64 = 1.0;
+
+/// Smallest pulse amplitude, as a percentage of the radius.
+pub const MIN_PULSE_AMPLITUDE: f64 = 5.0;
+
+/// Largest pulse amplitude, as a percentage of the radius.
+pub const MAX_PULSE_AMPLITUDE: f64 = 50.0;
+
+/// Pulse amplitude slider step percentage.
+pub const PULSE_AMPLITUDE_STEP: f64 = 5.0;
+
+/// Shortest pulse period in milliseconds.
+pub const MIN_PULSE_PERIOD_MS: f64 = 500.0;
+
+/// Longest pulse period in milliseconds.
+pub const MAX_PULSE_PERIOD_MS: f64 = 5000.0;
+
+/// Pulse period slider step in milliseconds.
+pub const PULSE_PERIOD_STEP_MS: f64 = 100.0;
+
+/// Shortest show/hide transition in milliseconds (instant).
+pub const MIN_TRANSITION_MS: f64 = 0.0;
+
+/// Longest show/hide transition in milliseconds.
+pub const MAX_TRANSITION_MS: f64 = 1000.0;
+
+/// Transition duration slider step in milliseconds.
+pub const TRANSITION_STEP_MS: f64 = 50.0;
+
 // === Display Modes ===
 
 /// Display mode: show circle around cursor.
@@ -84,10 +296,481 @@ pub const DISPLAY_MODE_LEFT: i32 = 1;
 /// Display mode: show "R" for right click.
 pub const DISPLAY_MODE_RIGHT: i32 = 2;
 
+/// Key for the custom text drawn on a left click (empty = the letter of
+/// the selected language).
+pub const PREF_CLICK_LETTER_LEFT: &str = "clickLetterLeft";
+
+/// Key for the custom text drawn on a right click (empty = the letter of
+/// the selected language).
+pub const PREF_CLICK_LETTER_RIGHT: &str = "clickLetterRight";
+
+/// Keys of the custom click letters, left then right.
+pub const CLICK_LETTER_KEYS: [&str; 2] = [PREF_CLICK_LETTER_LEFT, PREF_CLICK_LETTER_RIGHT];
+
+/// Most characters kept in a custom click letter ("LMB", an emoji...).
+pub const MAX_CLICK_LETTER_CHARS: usize = 4;
+
+/// Key for the font family of the click letters (empty = system default).
+pub const PREF_LETTER_FONT: &str = "letterFont";
+
+/// Key for the font weight of the click letters (see `LETTER_WEIGHT_*`).
+pub const PREF_LETTER_WEIGHT: &str = "letterWeight";
+
+/// Click letter weight: light.
+pub const LETTER_WEIGHT_LIGHT: i32 = 300;
+
+/// Click letter weight: regular.
+pub const LETTER_WEIGHT_REGULAR: i32 = 400;
+
+/// Click letter weight: medium.
+pub const LETTER_WEIGHT_MEDIUM: i32 = 500;
+
+/// Click letter weight: bold (default).
+pub const LETTER_WEIGHT_BOLD: i32 = 700;
+
+/// Click letter weight: black.
+pub const LETTER_WEIGHT_BLACK: i32 = 900;
+
+/// Key for the icon drawn on clicks instead of the letters (see
+/// `CLICK_ICON_*`).
+pub const PREF_CLICK_ICON: &str = "clickIcon";
+
+/// Click icon: none, the letters are drawn (default).
+pub const CLICK_ICON_NONE: i32 = 0;
+
+/// Click icon: a tapping hand.
+pub const CLICK_ICON_TAP: i32 = 1;
+
+/// Click icon: a mouse.
+pub const CLICK_ICON_MOUSE: i32 = 2;
+
+/// Click icon: a star.
+pub const CLICK_ICON_STAR: i32 = 3;
+
+/// Text keys edited in the Settings window (the click letters and their
+/// font).
+pub const SETTINGS_STRING_KEYS: [&str; 3] = [
+    PREF_CLICK_LETTER_LEFT,
+    PREF_CLICK_LETTER_RIGHT,
+    PREF_LETTER_FONT,
+];
+
+// === Highlight Styles ===
+
+/// Highlight style: ring around the cursor (classic).
+pub const HIGHLIGHT_STYLE_CIRCLE: i32 = 0;
+
+/// Highlight style: small bright dot with a fading comet trail.
+pub const HIGHLIGHT_STYLE_LASER: i32 = 1;
+
+// === Motion ===
+
+/// Motion mode: follow the OS "reduce motion" accessibility setting.
+pub const MOTION_SYSTEM: i32 = 0;
+
+/// Motion mode: always reduced (static highlight, instant changes).
+pub const MOTION_REDUCED: i32 = 1;
+
+/// Motion mode: always animated, whatever the OS setting.
+pub const MOTION_FULL: i32 = 2;
+
+// === High Contrast ===
+
+/// Width in pixels of the contrasting halo on each side of the stroke.
+pub const HALO_WIDTH: f64 = 3.0;
+
+/// High-contrast preset stroke color (R, G, B, A) - yellow #FFEB00, fully opaque.
+pub const HIGH_CONTRAST_COLOR: (f64, f64, f64, f64) = (1.0, 0.922, 0.0, 1.0);
+
+/// High-contrast preset border width in pixels.
+pub const HIGH_CONTRAST_BORDER_WIDTH: f64 = 6.0;
+
+/// High-contrast preset fill transparency (fully transparent, ring only).
+pub const HIGH_CONTRAST_FILL_TRANSPARENCY_PCT: f64 = 100.0;
+
+// === Glow ===
+
+/// Glow: none.
+pub const GLOW_OFF: i32 = 0;
+
+/// Glow: soft outer glow in the stroke color.
+pub const GLOW_SOFT: i32 = 1;
+
+/// Glow: dark drop shadow below the highlight.
+pub const GLOW_SHADOW: i32 = 2;
+
+/// Blur radius of the glow in pixels (how far it spreads past the shape).
+pub const GLOW_RADIUS: f64 = 8.0;
+
+/// Layered strokes approximating the blur where there is no blur filter.
+pub const GLOW_LAYERS: usize = 6;
+
+/// Opacity of the soft glow relative to the stroke.
+pub const GLOW_ALPHA: f64 = 0.7;
+
+/// Opacity of the drop shadow relative to the stroke.
+pub const GLOW_SHADOW_ALPHA: f64 = 0.6;
+
+/// Distance in pixels the drop shadow is moved down.
+pub const GLOW_SHADOW_OFFSET: f64 = 3.0;
+
+// === Laser Pointer ===
+
+/// Laser dot radius as a fraction of the configured circle radius.
+pub const LASER_DOT_SCALE: f64 = 0.3;
+
+/// Minimum laser dot radius in pixels.
+pub const MIN_LASER_DOT_RADIUS: f64 = 3.0;
+
+/// How long a trail point stays visible, in seconds.
+pub const LASER_TRAIL_LIFETIME_SECS: f64 = 0.35;
+
+/// Maximum number of points kept in the trail buffer.
+pub const LASER_TRAIL_MAX_POINTS: usize = 64;
+
+// === Click Bursts ===
+
+/// Default maximum interval between clicks of a burst, in milliseconds.
+pub const DEFAULT_CLICK_BURST_MS: f64 = 400.0;
+
+/// Default click indicator hold after mouse-up, in milliseconds.
+pub const DEFAULT_CLICK_HOLD_MS: f64 = 0.0;
+
+/// Default time to fill the hold ring, in milliseconds.
+pub const DEFAULT_HOLD_RING_MS: f64 = 1000.0;
+
+/// Maximum pointer travel in pixels between clicks of the same burst.
+pub const CLICK_BURST_SLOP_PX: f64 = 8.0;
+
+/// Highest count shown on the badge (longer bursts stay at this value).
+pub const CLICK_BURST_MAX_COUNT: u32 = 99;
+
+/// Badge glyph height as a fraction of the configured circle radius.
+pub const CLICK_BADGE_SCALE: f64 = 1.2;
+
+/// Glyph outlines of click letters and badges kept between frames.
+pub const GLYPH_CACHE_SIZE: usize = 8;
+
+// === Click Log ===
+
+/// Maximum clicks kept by the click log (oldest are dropped first).
+pub const CLICK_LOG_CAPACITY: usize = 10_000;
+
+// === Cursor Kinematics ===
+
+/// Time constant in seconds of the cursor speed low-pass filter.
+pub const KINEMATICS_SMOOTHING_SECS: f64 = 0.08;
+
+/// Cursor speed (px/s) below which the highlight is fully opaque.
+pub const SPEED_FADE_SLOW_PX_S: f64 = 800.0;
+
+/// Cursor speed (px/s) at which the highlight reaches its minimum opacity.
+pub const SPEED_FADE_FAST_PX_S: f64 = 4000.0;
+
+/// Opacity multiplier applied during the fastest sweeps.
+pub const SPEED_FADE_MIN_ALPHA: f64 = 0.25;
+
+/// Opacity change below which a resting cursor does not trigger a redraw.
+pub const SPEED_FADE_EPSILON: f64 = 0.01;
+
+/// Radius multiplier reached during the fastest sweeps (speed size scaling,
+/// over the same `SPEED_FADE_SLOW_PX_S`..`SPEED_FADE_FAST_PX_S` range).
+pub const SPEED_SIZE_MAX_SCALE: f64 = 1.5;
+
+/// Radius multiplier change below which the size scaling does not trigger a redraw.
+pub const SPEED_SIZE_EPSILON: f64 = 0.01;
+
+// === Idle Auto-Hide ===
+
+/// Default idle time before the highlight fades out, in seconds.
+pub const DEFAULT_IDLE_HIDE_SECS: f64 = 3.0;
+
+/// Duration in seconds of the fade-out once the cursor is idle.
+pub const IDLE_FADE_OUT_SECS: f64 = 0.6;
+
+/// Duration in seconds of the fade-in when the cursor moves again.
+pub const IDLE_FADE_IN_SECS: f64 = 0.15;
+
+// === Rainbow ===
+
+/// Default rainbow speed in hue cycles per minute (one every 10 s).
+pub const DEFAULT_RAINBOW_SPEED: f64 = 6.0;
+
+/// Saturation below which the stroke counts as gray and the rainbow
+/// cycles fully saturated hues instead.
+pub const RAINBOW_MIN_SATURATION: f64 = 0.05;
+
+// === Pulse ===
+
+/// Default pulse amplitude, as a percentage of the radius.
+pub const DEFAULT_PULSE_AMPLITUDE: f64 = 15.0;
+
+/// Default pulse period in milliseconds (a slow breath).
+pub const DEFAULT_PULSE_PERIOD_MS: f64 = 2000.0;
+
+// === Show/Hide Transition ===
+
+/// Default show/hide transition duration in milliseconds.
+pub const DEFAULT_TRANSITION_MS: f64 = 200.0;
+
+/// Radius multiplier of the highlight at the start of a show transition
+/// (and the end of a hide).
+pub const TRANSITION_START_SCALE: f64 = 0.5;
+
+// === Recording Frame ===
+
+/// Width in pixels of the border drawn around the highlighted screen.
+pub const SCREEN_FRAME_WIDTH: f64 = 4.0;
+
+/// Most colors the user can save to the Settings palette.
+pub const MAX_SAVED_COLORS: usize = 12;
+
+/// Default recording-frame color (R, G, B, A) - red #E53935, slightly translucent.
+pub const DEFAULT_SCREEN_FRAME_COLOR: (f64, f64, f64, f64) = (0.898, 0.224, 0.208, 0.9);
+
+// === Zoom ===
+
+/// Magnification steps cycled by the zoom hotkeys (the first step is "off").
+pub const ZOOM_LEVELS: [f64; 7] = [1.0, 1.5, 2.0, 3.0, 4.0, 6.0, 8.0];
+
+/// Time constant in seconds for the magnified view panning after the cursor.
+pub const ZOOM_FOLLOW_SECS: f64 = 0.12;
+
+// === Ruler ===
+
+/// Width in pixels of the ruler's line (drawn over a dark outline).
+pub const RULER_LINE_WIDTH: f64 = 2.0;
+
+/// Length in pixels of the ticks across both ends of the ruler's line.
+pub const RULER_TICK_LENGTH: f64 = 12.0;
+
+// === Alignment Grid ===
+
+/// Default distance between major grid lines in pixels.
+pub const DEFAULT_GRID_SPACING: f64 = 100.0;
+
+/// Default number of parts each grid cell is split into.
+pub const DEFAULT_GRID_SUBDIVISIONS: i32 = 4;
+
+/// Default grid line color (R, G, B, A) - light blue #29B6F6, half transparent.
+pub const DEFAULT_GRID_COLOR: (f64, f64, f64, f64) = (0.161, 0.714, 0.965, 0.5);
+
+/// Width in pixels of the grid lines.
+pub const GRID_LINE_WIDTH: f64 = 1.0;
+
+/// Opacity of the minor grid lines relative to the major ones.
+pub const GRID_MINOR_ALPHA: f64 = 0.4;
+
+// === Pen Input ===
+
+/// Radius multiplier of the highlight with the pen pressed all the way.
+pub const PEN_MAX_SCALE: f64 = 2.0;
+
+/// Length in pixels of each arm of the crosshair marking the pen tip.
+pub const PEN_TIP_SIZE: f64 = 8.0;
+
+/// Width in pixels of the crosshair marking the pen tip.
+pub const PEN_TIP_WIDTH: f64 = 2.0;
+
+// === Touch Input ===
+
+/// Seconds the ring of a lifted finger takes to fade out.
+pub const TOUCH_FADE_SECS: f64 = 0.3;
+
+// === Mouse Hook Watchdog ===
+
+/// Seconds the mouse buttons may disagree with what the mouse hook last
+/// saw before the hook is taken for removed and installed again.
+pub const HOOK_WATCHDOG_SECS: f64 = 0.5;
+
+/// Presses and releases polled in a row without the mouse hook reporting
+/// any before the hook is taken for removed: two whole clicks, so an edge
+/// polled before the hook's callback ran is forgiven.
+pub const HOOK_MISSED_EDGES: u32 = 4;
+
+// === Refresh Timer Watchdog ===
+
+/// Seconds the refresh timer may go without running before it is taken
+/// for stalled and created again (macOS).
+pub const TIMER_WATCHDOG_SECS: f64 = 3.0;
+
+/// Seconds between two checks of the refresh timer (macOS).
+pub const TIMER_WATCHDOG_CHECK_SECS: f64 = 1.0;
+
+// === Tray Icon ===
+
+/// Opacity of the tray icon while the highlight is hidden.
+pub const TRAY_ICON_HIDDEN_ALPHA: f64 = 0.35;
+
+/// Radius of the tray icon's lock badge, as a fraction of the icon size.
+pub const TRAY_BADGE_RADIUS: f64 = 0.22;
+
+/// Color of the tray icon's lock badge.
+pub const TRAY_BADGE_COLOR: (f64, f64, f64, f64) = (0.961, 0.620, 0.043, 1.0);
+
+// === Auxiliary Overlays ===
+
+/// Distance in pixels within which a dragged overlay snaps to a screen edge.
+pub const OVERLAY_SNAP_DISTANCE: f64 = 16.0;
+
+/// Minimum pointer travel in pixels before a press on an overlay becomes a drag.
+pub const OVERLAY_DRAG_THRESHOLD: f64 = 3.0;
+
+// === Local Control Endpoint ===
+
+/// Default TCP port of the control endpoint (localhost only).
+pub const DEFAULT_CONTROL_PORT: i32 = 51730;
+
+/// Lowest control endpoint port (ports below need admin rights).
+pub const MIN_CONTROL_PORT: f64 = 1024.0;
+
+/// Highest control endpoint port.
+pub const MAX_CONTROL_PORT: f64 = 65535.0;
+
+/// Largest control request accepted, in bytes (headers and body).
+pub const MAX_CONTROL_REQUEST: usize = 64 * 1024;
+
+/// Control endpoint connections served at once; later ones are closed.
+pub const MAX_CONTROL_CONNECTIONS: usize = 16;
+
+/// Companion sessions open at once; later ones are refused.
+pub const MAX_COMPANION_SESSIONS: usize = 4;
+
+// === Update Check ===
+
+/// Latest published release of Lumbus (GitHub releases API).
+pub const UPDATE_FEED_URL: &str = "https://api.github.com/repos/restevean/lumbus/releases/latest";
+
+/// Time between automatic update checks.
+pub const UPDATE_CHECK_INTERVAL_SECS: f64 = 24.0 * 60.0 * 60.0;
+
+/// How often the app looks whether an automatic check is due.
+pub const UPDATE_POLL_SECS: f64 = 60.0 * 60.0;
+
+// === Logging ===
+
+/// Environment variable setting the log level (`error`...`trace`, `off`).
+pub const LOG_LEVEL_ENV: &str = "LUMBUS_LOG";
+
+/// Log file name prefix (`lumbus.YYYY-MM-DD.log`).
+pub const LOG_FILE_PREFIX: &str = "lumbus";
+
+/// Log file name extension.
+pub const LOG_FILE_SUFFIX: &str = "log";
+
+/// Daily log files kept in the log folder.
+pub const LOG_MAX_FILES: usize = 7;
+
+// === Reset ===
+
+/// Command-line flag restoring the default settings before starting.
+pub const RESET_FLAG: &str = "--reset";
+
+// === Safe mode ===
+
+/// Command-line flag starting from the defaults without reading or
+/// writing the settings file, with only the toggle hotkey.
+pub const SAFE_MODE_FLAG: &str = "--safe-mode";
+
+// === Diagnostics ===
+
+/// Command-line flag printing a diagnostics report (`--diagnostics[=overlay]`).
+pub const DIAGNOSTICS_FLAG: &str = "--diagnostics";
+
+/// `--diagnostics` value that also draws debug info into the overlay.
+pub const DIAGNOSTICS_OVERLAY: &str = "overlay";
+
+/// File the diagnostics report is saved to, in the log folder.
+pub const DIAGNOSTICS_FILE: &str = "diagnostics.txt";
+
+/// Weight of the newest frame in the average frame time.
+pub const FRAME_TIME_SMOOTHING: f64 = 0.1;
+
+/// Font size of the overlay's debug label, in points.
+pub const DEBUG_LABEL_SIZE: f64 = 13.0;
+
+/// Distance of the debug label from the top-left corner of the display.
+pub const DEBUG_LABEL_MARGIN: f64 = 8.0;
+
+// === Crash Reports ===
+
+/// Folder, in the log folder, that crash reports are written to.
+pub const CRASH_DIR: &str = "Crashes";
+
+/// File name prefix of a crash report (`lumbus-crash-<time>.txt`).
+pub const CRASH_FILE_PREFIX: &str = "lumbus-crash";
+
+// === Soak Test ===
+
+/// Command-line flag enabling the hidden soak-test mode (`--soak[=minutes]`).
+pub const SOAK_FLAG: &str = "--soak";
+
+/// Default soak-test duration in minutes.
+pub const DEFAULT_SOAK_MINUTES: f64 = 240.0;
+
+/// Interval in seconds between scripted soak-test steps.
+pub const SOAK_TICK_SECS: f64 = 0.05;
+
+/// Interval in seconds between resource samples in the soak log.
+pub const SOAK_SAMPLE_SECS: f64 = 60.0;
+
+/// Soak steps between overlay toggles (rapid on/off).
+pub const SOAK_TOGGLE_TICKS: u64 = 10;
+
+/// Soak steps between settings changes.
+pub const SOAK_CHURN_TICKS: u64 = 100;
+
+/// Soak steps between simulated space changes.
+pub const SOAK_SPACE_TICKS: u64 = 200;
+
+/// Soak-test log file, written to the system temp directory.
+pub const SOAK_REPORT_FILE: &str = "lumbus-soak-report.txt";
+
+// === Settings history ===
+
+/// Most changes the Settings window can undo.
+pub const SETTINGS_UNDO_LIMIT: usize = 50;
+
+/// Changes of the same control closer together than this (seconds), such
+/// as the steps of one slider drag, are undone as one.
+pub const SETTINGS_UNDO_MERGE_SECS: f64 = 1.0;
+
 // === Languages ===
 
+/// Language preference that follows the OS language (the default).
+pub const LANG_SYSTEM: i32 = -1;
+
 /// Language code for English.
 pub const LANG_EN: i32 = 0;
 
 /// Language code for Spanish.
 pub const LANG_ES: i32 = 1;
+
+/// Language code for French.
+pub const LANG_FR: i32 = 2;
+
+/// Language code for German.
+pub const LANG_DE: i32 = 3;
+
+/// Language code for Portuguese.
+pub const LANG_PT: i32 = 4;
+
+/// Language code for Japanese.
+pub const LANG_JA: i32 = 5;
+
+/// Built-in locales, indexed by the language codes above.
+///
+/// In memory `PREF_LANG` is an index into this list followed by any extra
+/// languages loaded at runtime (see `i18n::languages`); the config stores
+/// the locale tag instead. Older configs and managed settings hold the
+/// index, so the order of existing entries must never change.
+pub const BUILTIN_LANGUAGES: [&str; 6] = ["en", "es", "fr", "de", "pt", "ja"];
+
+/// Locale used when a message is missing in the selected language.
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Environment variable naming a directory of extra `.ftl` resources.
+pub const LOCALES_DIR_ENV: &str = "LUMBUS_LOCALES_DIR";
+
+/// Directory next to the executable searched for extra `.ftl` resources.
+pub const LOCALES_DIR: &str = "locales";
@@ -0,0 +1,64 @@
+//! High-contrast preset and outline halo (pure Rust, no FFI).
+//!
+//! The halo is a contrasting outline drawn under the stroke, so the
+//! highlight stays visible on any background: a light ring gets a black
+//! halo and a dark ring a white one. The high-contrast preset switches to
+//! a thick yellow ring with no fill and turns the halo on in one click.
+
+use super::constants::*;
+
+/// Preference keys changed by the high-contrast preset.
+pub const HIGH_CONTRAST_KEYS: [&str; 7] = [
+    PREF_STROKE_R,
+    PREF_STROKE_G,
+    PREF_STROKE_B,
+    PREF_STROKE_A,
+    PREF_BORDER,
+    PREF_FILL_TRANSPARENCY,
+    PREF_HALO,
+];
+
+/// Relative luminance of an sRGB color (WCAG 2 definition).
+pub fn relative_luminance(r: f64, g: f64, b: f64) -> f64 {
+    let lin = |c: f64| {
+        let c = c.clamp(0.0, 1.0);
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * lin(r) + 0.7152 * lin(g) + 0.0722 * lin(b)
+}
+
+/// Halo color for a stroke color: black or white, whichever contrasts more.
+pub fn halo_color(r: f64, g: f64, b: f64) -> (f64, f64, f64) {
+    let l = relative_luminance(r, g, b);
+    // Contrast against black is (l + 0.05) / 0.05, against white 1.05 / (l + 0.05)
+    if (l + 0.05) * (l + 0.05) > 0.05 * 1.05 {
+        (0.0, 0.0, 0.0)
+    } else {
+        (1.0, 1.0, 1.0)
+    }
+}
+
+/// Line width of the halo drawn under a stroke of `border_width`.
+pub fn halo_line_width(border_width: f64) -> f64 {
+    border_width + 2.0 * HALO_WIDTH
+}
+
+/// Preference values of the high-contrast preset: thick yellow ring,
+/// no fill, halo on.
+pub fn high_contrast_preset() -> impl Iterator<Item = (&'static str, f64)> {
+    let (r, g, b, a) = HIGH_CONTRAST_COLOR;
+    let values = [
+        r,
+        g,
+        b,
+        a,
+        HIGH_CONTRAST_BORDER_WIDTH,
+        HIGH_CONTRAST_FILL_TRANSPARENCY_PCT,
+        1.0,
+    ];
+    HIGH_CONTRAST_KEYS.into_iter().zip(values)
+}
@@ -0,0 +1,211 @@
+//! Local control endpoint requests (pure Rust, no FFI).
+//!
+//! When enabled, the app serves a small HTTP API on 127.0.0.1 so companion
+//! tools and test rigs can drive the overlay:
+//!
+//! - `GET /state`: visibility, presentation lock and hotkey profile
+//! - `POST /toggle`: show or hide the highlight
+//! - `GET /settings`: the overlay settings, by preference key
+//! - `POST /settings` (or `PUT`/`PATCH`): change settings from a JSON
+//!   object, e.g. `{"radius": 60}`
+//!
+//! The same socket also serves the line-based companion protocol (see
+//! `model::companion`).
+//!
+//! Every request needs `Authorization: Bearer <token>` with the token
+//! stored in the settings file. This module parses requests and decides
+//! the response and the [`AppEvent`]s to publish; the server is in
+//! `control`.
+
+use serde_json::{json, Map, Value};
+
+use crate::events::AppEvent;
+
+use super::url_scheme::setting_event;
+
+/// Snapshot of the running app, answered by `GET /state` and
+/// `GET /settings`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ControlState {
+    /// Is the highlight shown?
+    pub visible: bool,
+    /// Is the presentation lock on?
+    pub locked: bool,
+    /// Selected hotkey profile.
+    pub hotkey_profile: String,
+    /// Overlay settings, by preference key.
+    pub settings: Vec<(String, f64)>,
+}
+
+/// HTTP request read from a client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ControlRequest {
+    pub method: String,
+    /// Path without the query string.
+    pub path: String,
+    /// Token of the `Authorization: Bearer` header, if any.
+    pub token: Option<String>,
+    pub body: String,
+}
+
+/// Response to send, and the events to publish for it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ControlResponse {
+    pub status: u16,
+    /// JSON body.
+    pub body: String,
+    pub events: Vec<AppEvent>,
+}
+
+impl ControlResponse {
+    fn json(status: u16, body: Value) -> Self {
+        Self {
+            status,
+            body: body.to_string(),
+            events: Vec::new(),
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::json(status, json!({ "error": message }))
+    }
+
+    /// Reason phrase of the status code.
+    pub fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            202 => "Accepted",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            _ => "Error",
+        }
+    }
+}
+
+/// Length of the body announced by a request's headers, once they are
+/// complete (`None` while the blank line ending them has not arrived).
+pub fn body_length(head: &str) -> Option<usize> {
+    let end = head.find("\r\n\r\n")?;
+    let length = head[..end]
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse().ok())
+        .unwrap_or(0);
+    Some(length)
+}
+
+/// Is `first_line` an HTTP request line (rather than a companion
+/// command)?
+pub fn is_http_request(first_line: &str) -> bool {
+    first_line
+        .trim_end()
+        .rsplit(' ')
+        .next()
+        .is_some_and(|version| version.starts_with("HTTP/"))
+}
+
+/// Parse a complete HTTP/1.1 request.
+pub fn parse_request(raw: &str) -> Option<ControlRequest> {
+    let (head, body) = raw.split_once("\r\n\r\n")?;
+    let mut lines = head.lines();
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_ascii_uppercase();
+    let target = request_line.next()?;
+    let path = target.split('?').next().unwrap_or(target).to_string();
+    let token = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("authorization"))
+        .and_then(|(_, value)| {
+            let (scheme, token) = value.trim().split_once(' ')?;
+            scheme
+                .eq_ignore_ascii_case("bearer")
+                .then(|| token.trim().to_string())
+        });
+    Some(ControlRequest {
+        method,
+        path,
+        token,
+        body: body.to_string(),
+    })
+}
+
+/// Compare tokens in time independent of where they differ.
+pub fn same_token(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Answer a request, given the expected token and the app's state.
+pub fn handle_request(
+    request: &ControlRequest,
+    token: &str,
+    state: &ControlState,
+) -> ControlResponse {
+    let authorized = !token.is_empty()
+        && request
+            .token
+            .as_deref()
+            .is_some_and(|given| same_token(given, token));
+    if !authorized {
+        return ControlResponse::error(401, "missing or wrong token");
+    }
+
+    let path = request.path.trim_end_matches('/');
+    match (request.method.as_str(), path) {
+        ("GET", "/state") => ControlResponse::json(
+            200,
+            json!({
+                "visible": state.visible,
+                "locked": state.locked,
+                "hotkeyProfile": state.hotkey_profile,
+            }),
+        ),
+        ("POST", "/toggle") => ControlResponse {
+            events: vec![AppEvent::ToggleOverlay],
+            ..ControlResponse::json(202, json!({ "visible": !state.visible }))
+        },
+        ("GET", "/settings") => {
+            let settings: Map<String, Value> = state
+                .settings
+                .iter()
+                .map(|(key, value)| (key.clone(), json!(value)))
+                .collect();
+            ControlResponse::json(200, Value::Object(settings))
+        }
+        ("POST" | "PUT" | "PATCH", "/settings") => set_settings(&request.body, state),
+        (_, "/state" | "/toggle" | "/settings") => {
+            ControlResponse::error(405, "method not allowed")
+        }
+        _ => ControlResponse::error(404, "no such endpoint"),
+    }
+}
+
+/// `POST /settings`: one event per setting of the JSON object, or an
+/// error (and no events) if any of them is not an overlay setting.
+fn set_settings(body: &str, state: &ControlState) -> ControlResponse {
+    let Ok(Value::Object(changes)) = serde_json::from_str::<Value>(body) else {
+        return ControlResponse::error(400, "body must be a JSON object");
+    };
+    let mut events = Vec::new();
+    for (key, value) in &changes {
+        let number = value.as_f64().or_else(|| value.as_bool().map(f64::from));
+        match number.and_then(|n| setting_event(key, n)) {
+            Some(event) => events.push(event),
+            None => return ControlResponse::error(400, &format!("cannot set `{key}`")),
+        }
+    }
+    if state.locked {
+        return ControlResponse::error(400, "settings are locked for a presentation");
+    }
+    ControlResponse {
+        events,
+        ..ControlResponse::json(202, Value::Object(changes))
+    }
+}
@@ -0,0 +1,51 @@
+//! Cursor coordinates readout (pure Rust, no FFI).
+//!
+//! A small label next to the highlight, toggled by a hotkey, shows where
+//! the pointer is for designers and QA engineers measuring layouts: its
+//! global position and, with more than one display, the display under it
+//! and the position on that display. Positions count from the top-left
+//! corner, in points on macOS and pixels on Windows, like design tools.
+
+use super::placement::ScreenRect;
+
+/// Readout text of the pointer at `point`, e.g. `1234, 567` or
+/// `1234, 567  display 2: 314, 87`.
+///
+/// `screens` are the display frames in the same coordinates as `point`,
+/// the primary display first. `y_up` is true for Cocoa coordinates, whose
+/// origin is the bottom-left corner of the primary display.
+pub fn coordinates_label(point: (f64, f64), screens: &[ScreenRect], y_up: bool) -> String {
+    let (x, y) = point;
+    let top = |screen: &ScreenRect| {
+        if y_up {
+            screen.y + screen.h - y
+        } else {
+            y - screen.y
+        }
+    };
+    let global = match screens.first() {
+        Some(primary) if y_up => (x, top(primary)),
+        _ => (x, y),
+    };
+    let label = format!("{}, {}", whole(global.0), whole(global.1));
+    if screens.len() < 2 {
+        return label;
+    }
+    match screens.iter().position(|s| s.contains(x, y)) {
+        Some(i) => {
+            let screen = &screens[i];
+            format!(
+                "{label}  display {}: {}, {}",
+                i + 1,
+                whole(x - screen.x),
+                whole(top(screen))
+            )
+        }
+        None => label,
+    }
+}
+
+/// Whole point or pixel the coordinate falls in.
+fn whole(v: f64) -> i64 {
+    v.floor() as i64
+}
@@ -0,0 +1,146 @@
+//! Crash reports (pure Rust, no FFI).
+//!
+//! When the app panics, the platform's panic hook (see `logging`) writes a
+//! [`CrashReport`] to the `Crashes` folder next to the logs and offers to
+//! open it. The report carries the settings the app was running with, so
+//! a crash can be reproduced without asking for the file; colors and the
+//! control endpoint's token are redacted first.
+
+use std::any::Any;
+use std::fmt;
+
+use super::constants::*;
+use super::preferences::Preferences;
+
+/// Text standing in for a redacted setting.
+pub const REDACTED: &str = "<redacted>";
+
+/// Settings (file keys) left out of the config snapshot.
+const REDACTED_KEYS: &[&str] = &[
+    "stroke_r",
+    "stroke_g",
+    "stroke_b",
+    "stroke_a",
+    "grid_r",
+    "grid_g",
+    "grid_b",
+    "grid_a",
+    "screen_frame_colors",
+    "color_palette",
+    "control_token",
+];
+
+/// Everything known about a panic.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CrashReport {
+    pub version: String,
+    pub os: String,
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    /// Name of the thread that panicked.
+    pub thread: String,
+    pub message: String,
+    /// `file:line:column` of the panic, when known.
+    pub location: String,
+    pub backtrace: String,
+    /// Settings file as returned by [`config_snapshot`].
+    pub config: String,
+}
+
+impl CrashReport {
+    /// File name of the report, unique per second.
+    pub fn file_name(&self) -> String {
+        format!("{}-{}.txt", CRASH_FILE_PREFIX, self.time)
+    }
+
+    /// Name of the day's log file (`lumbus.YYYY-MM-DD.log`), dated in UTC
+    /// like the log rotation.
+    pub fn log_file_name(&self) -> String {
+        let (year, month, day) = utc_date(self.time / 86_400);
+        format!(
+            "{}.{:04}-{:02}-{:02}.{}",
+            LOG_FILE_PREFIX, year, month, day, LOG_FILE_SUFFIX
+        )
+    }
+
+    /// Log line recording the panic, in the format of the other log lines.
+    pub fn log_line(&self) -> String {
+        let (year, month, day) = utc_date(self.time / 86_400);
+        let secs = self.time % 86_400;
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z ERROR lumbus::logging: Panic at {}: {} thread={}\n",
+            year,
+            month,
+            day,
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60,
+            self.location,
+            self.message,
+            self.thread
+        )
+    }
+}
+
+/// Year, month and day of `days` since the Unix epoch.
+fn utc_date(days: u64) -> (u64, u64, u64) {
+    // Days since 0000-03-01, so leap days fall at the end of a year
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = era * 400 + year_of_era + u64::from(month <= 2);
+    (year, month, day)
+}
+
+impl fmt::Display for CrashReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Lumbus {} crash report ({})", self.version, self.os)?;
+        writeln!(f, "Time: {} (Unix)", self.time)?;
+        writeln!(f, "Thread: {}", self.thread)?;
+        writeln!(f, "Panic: {}", self.message)?;
+        writeln!(f, "Location: {}", self.location)?;
+        writeln!(f)?;
+        writeln!(f, "Backtrace:")?;
+        writeln!(f, "{}", self.backtrace.trim_end())?;
+        writeln!(f)?;
+        writeln!(f, "Settings:")?;
+        write!(f, "{}", self.config)
+    }
+}
+
+/// Message of a panic payload (`panic!` with a literal or a format).
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "(no message)".to_string()
+    }
+}
+
+/// Settings file contents for a crash report, with colors and the control
+/// token redacted. Settings that could not be read show as their defaults.
+pub fn config_snapshot(contents: &str) -> String {
+    let prefs = Preferences::decode(contents, Preferences::default());
+    prefs
+        .encode()
+        .lines()
+        .map(|line| match line.split_once(" = ") {
+            Some((key, _)) if REDACTED_KEYS.contains(&key) => {
+                format!("{} = \"{}\"\n", key, REDACTED)
+            }
+            _ => format!("{}\n", line),
+        })
+        .collect()
+}
@@ -0,0 +1,159 @@
+//! Diagnostics mode (pure Rust, no FFI).
+//!
+//! `--diagnostics` prints what the app detected at startup (displays and
+//! their scale, permissions, registered hotkeys, file locations) and saves
+//! it next to the logs, so a multi-monitor bug report can carry it.
+//! `--diagnostics=overlay` also draws the frame time and display ID into
+//! the overlay. The platforms gather the facts; this module parses the
+//! flag, formats the report and averages frame times.
+
+use std::fmt;
+
+use super::constants::*;
+use super::placement::ScreenRect;
+
+/// Diagnostics requested on the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiagnosticsConfig {
+    /// Draw frame time and display ID into the overlay.
+    pub overlay: bool,
+}
+
+impl DiagnosticsConfig {
+    /// Looks for `--diagnostics` or `--diagnostics=overlay` among the
+    /// arguments. Returns `None` when the flag is absent.
+    pub fn from_args<I, S>(args: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        args.into_iter().find_map(|arg| {
+            let arg = arg.as_ref();
+            if arg == DIAGNOSTICS_FLAG {
+                return Some(Self { overlay: false });
+            }
+            let value = arg.strip_prefix(DIAGNOSTICS_FLAG)?.strip_prefix('=')?;
+            Some(Self {
+                overlay: value.eq_ignore_ascii_case(DIAGNOSTICS_OVERLAY),
+            })
+        })
+    }
+}
+
+/// A connected display as the platform reports it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayInfo {
+    /// Platform display ID (CGDirectDisplayID on macOS, 1-based monitor
+    /// number on Windows).
+    pub id: u32,
+    /// Frame in the platform's global screen coordinates.
+    pub frame: ScreenRect,
+    /// Backing scale factor (macOS) or DPI / 96 (Windows).
+    pub scale: f64,
+    pub primary: bool,
+}
+
+/// Everything `--diagnostics` reports.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiagnosticsReport {
+    pub version: String,
+    pub os: String,
+    pub displays: Vec<DisplayInfo>,
+    /// Permission or process checks, e.g. ("Accessibility", "granted").
+    pub permissions: Vec<(String, String)>,
+    pub hotkey_profile: String,
+    /// Hotkeys of the profile: label, action and whether it registered.
+    pub hotkeys: Vec<(String, String, bool)>,
+    /// Time events waited for the dispatcher: name, events taken, mean and
+    /// longest wait in milliseconds.
+    pub event_latency: Vec<(String, u64, f64, f64)>,
+    pub config_path: String,
+    pub log_dir: String,
+}
+
+impl fmt::Display for DiagnosticsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Lumbus {} diagnostics ({})", self.version, self.os)?;
+        writeln!(f, "Displays ({}):", self.displays.len())?;
+        for d in &self.displays {
+            writeln!(
+                f,
+                "  #{} {}x{} at ({}, {}) scale {}{}",
+                d.id,
+                d.frame.w,
+                d.frame.h,
+                d.frame.x,
+                d.frame.y,
+                d.scale,
+                if d.primary { " (primary)" } else { "" }
+            )?;
+        }
+        writeln!(f, "Permissions:")?;
+        for (name, status) in &self.permissions {
+            writeln!(f, "  {}: {}", name, status)?;
+        }
+        writeln!(f, "Hotkeys ({} profile):", self.hotkey_profile)?;
+        for (label, action, registered) in &self.hotkeys {
+            writeln!(
+                f,
+                "  {:<16} {:<24} {}",
+                label,
+                action,
+                if *registered {
+                    "registered"
+                } else {
+                    "NOT registered"
+                }
+            )?;
+        }
+        writeln!(f, "Event latency:")?;
+        if self.event_latency.is_empty() {
+            writeln!(f, "  no events yet")?;
+        }
+        for (name, count, mean_ms, max_ms) in &self.event_latency {
+            writeln!(
+                f,
+                "  {:<24} {:>5} taken  mean {:.1} ms  max {:.1} ms",
+                name, count, mean_ms, max_ms
+            )?;
+        }
+        writeln!(f, "Config: {}", self.config_path)?;
+        writeln!(f, "Logs: {}", self.log_dir)
+    }
+}
+
+/// Running average of the time between overlay frames.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrameTimer {
+    last: Option<f64>,
+    average_ms: f64,
+}
+
+impl FrameTimer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a frame at `now` (seconds).
+    pub fn tick(&mut self, now: f64) {
+        if let Some(last) = self.last {
+            let ms = (now - last) * 1000.0;
+            self.average_ms = if self.average_ms == 0.0 {
+                ms
+            } else {
+                self.average_ms + (ms - self.average_ms) * FRAME_TIME_SMOOTHING
+            };
+        }
+        self.last = Some(now);
+    }
+
+    /// Average frame time in milliseconds (0 before two frames).
+    pub fn average_ms(&self) -> f64 {
+        self.average_ms
+    }
+}
+
+/// Text drawn into the overlay in diagnostics mode.
+pub fn debug_label(display_id: u32, frame_ms: f64) -> String {
+    format!("display {}  {:.1} ms", display_id, frame_ms)
+}
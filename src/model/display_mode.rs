@@ -0,0 +1,57 @@
+//! What the highlight draws: the circle, or the letter of a clicked
+//! button (pure Rust, no FFI).
+//!
+//! The views and renderers keep the mode as its `DISPLAY_MODE_*` code;
+//! [`DisplayMode`] is the form events and remote control use.
+
+use serde::{Deserialize, Serialize};
+
+use super::constants::{DISPLAY_MODE_CIRCLE, DISPLAY_MODE_LEFT, DISPLAY_MODE_RIGHT};
+
+/// What the highlight draws.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplayMode {
+    /// The circle around the cursor.
+    #[default]
+    Circle,
+    /// The letter of the left button.
+    Left,
+    /// The letter of the right button.
+    Right,
+}
+
+impl DisplayMode {
+    /// Every mode, in `DISPLAY_MODE_*` order.
+    pub const ALL: [DisplayMode; 3] = [DisplayMode::Circle, DisplayMode::Left, DisplayMode::Right];
+
+    /// `DISPLAY_MODE_*` code of the mode.
+    pub fn code(self) -> i32 {
+        match self {
+            DisplayMode::Circle => DISPLAY_MODE_CIRCLE,
+            DisplayMode::Left => DISPLAY_MODE_LEFT,
+            DisplayMode::Right => DISPLAY_MODE_RIGHT,
+        }
+    }
+
+    /// Mode of a `DISPLAY_MODE_*` code.
+    pub fn from_code(code: i32) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.code() == code)
+    }
+
+    /// Name of the mode (`circle`, `left`, `right`), as in URLs and JSON.
+    pub fn name(self) -> &'static str {
+        match self {
+            DisplayMode::Circle => "circle",
+            DisplayMode::Left => "left",
+            DisplayMode::Right => "right",
+        }
+    }
+
+    /// Mode called `name`, ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|m| m.name().eq_ignore_ascii_case(name.trim()))
+    }
+}
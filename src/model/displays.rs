@@ -0,0 +1,70 @@
+//! Matching overlay windows to connected displays (pure Rust, no FFI).
+//!
+//! macOS keeps one overlay window per display. When displays are connected
+//! or disconnected, the overlays are reconciled with the new display list:
+//! overlays whose display is still there are kept, overlays of removed
+//! displays move to new displays where possible, and the rest are closed
+//! or created. Displays are identified by their stable display ID.
+
+/// What to do with the overlay windows after a display change.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OverlayPlan {
+    /// Overlays (by index) to move to a display (by index).
+    pub moves: Vec<(usize, usize)>,
+    /// Overlays to close.
+    pub remove: Vec<usize>,
+    /// Displays that need a new overlay.
+    pub create: Vec<usize>,
+}
+
+impl OverlayPlan {
+    /// Returns true if every overlay is already on its own display.
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty() && self.remove.is_empty() && self.create.is_empty()
+    }
+}
+
+/// Plan how `overlays` (the display ID each one covers) follow `displays`.
+///
+/// The overlay at index `host` owns the app's timers and monitors, so it
+/// is never closed: if its display is gone and no display is free, it
+/// takes over the display of another overlay, which is closed instead.
+/// With no displays at all nothing changes.
+pub fn plan_overlays(overlays: &[u32], displays: &[u32], host: usize) -> OverlayPlan {
+    let mut plan = OverlayPlan::default();
+    if displays.is_empty() {
+        return plan;
+    }
+
+    // Display each overlay keeps; a display claimed twice keeps the first
+    let mut owner: Vec<Option<usize>> = vec![None; displays.len()];
+    let mut stale = Vec::new();
+    for (overlay, id) in overlays.iter().enumerate() {
+        match displays.iter().position(|d| d == id) {
+            Some(display) if owner[display].is_none() => owner[display] = Some(overlay),
+            _ => stale.push(overlay),
+        }
+    }
+
+    let mut free: Vec<usize> = (0..displays.len())
+        .filter(|&d| owner[d].is_none())
+        .collect();
+    // The host goes first so it gets a free display if there is one
+    stale.sort_by_key(|&overlay| overlay != host);
+    for overlay in stale {
+        if !free.is_empty() {
+            plan.moves.push((overlay, free.remove(0)));
+        } else if overlay == host {
+            // Every display is covered: take over the first one
+            if let Some(other) = owner[0] {
+                plan.remove.push(other);
+                plan.moves.push((host, 0));
+                owner[0] = Some(host);
+            }
+        } else {
+            plan.remove.push(overlay);
+        }
+    }
+    plan.create = free;
+    plan
+}
@@ -0,0 +1,55 @@
+//! Font of the click letters (pure Rust, no FFI).
+//!
+//! The family is a name as listed by the OS (empty = the bold system font
+//! on macOS, Arial on Windows). Weights are OpenType numbers, 100 (thin)
+//! to 900 (black), which DirectWrite takes as is; macOS needs them
+//! converted for `NSFontManager` ([`appkit_weight`]) and for the system
+//! font ([`system_font_weight`]).
+
+use super::constants::*;
+
+/// Weights offered in Settings, with their English names.
+pub const LETTER_WEIGHTS: [(i32, &str); 5] = [
+    (LETTER_WEIGHT_LIGHT, "Light"),
+    (LETTER_WEIGHT_REGULAR, "Regular"),
+    (LETTER_WEIGHT_MEDIUM, "Medium"),
+    (LETTER_WEIGHT_BOLD, "Bold"),
+    (LETTER_WEIGHT_BLACK, "Black"),
+];
+
+/// Is `weight` one of [`LETTER_WEIGHTS`]?
+pub fn is_letter_weight(weight: i32) -> bool {
+    LETTER_WEIGHTS.iter().any(|(w, _)| *w == weight)
+}
+
+/// `NSFontManager` weight (0–15, 5 = regular, 9 = bold) of an OpenType
+/// weight.
+pub fn appkit_weight(weight: i32) -> i64 {
+    match weight {
+        ..=150 => 2,
+        151..=250 => 3,
+        251..=350 => 4,
+        351..=450 => 5,
+        451..=550 => 6,
+        551..=650 => 8,
+        651..=750 => 9,
+        751..=850 => 10,
+        _ => 11,
+    }
+}
+
+/// `NSFontWeight` (-1.0 to 1.0, 0 = regular) of an OpenType weight, for
+/// the system font.
+pub fn system_font_weight(weight: i32) -> f64 {
+    match weight {
+        ..=150 => -0.8,
+        151..=250 => -0.6,
+        251..=350 => -0.4,
+        351..=450 => 0.0,
+        451..=550 => 0.23,
+        551..=650 => 0.3,
+        651..=750 => 0.4,
+        751..=850 => 0.56,
+        _ => 0.62,
+    }
+}
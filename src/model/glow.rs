@@ -0,0 +1,39 @@
+//! Glow and drop shadow behind the highlight (pure Rust, no FFI).
+//!
+//! A soft blur around the highlight keeps a thin ring visible over light
+//! backgrounds without thickening the border: either an outer glow in the
+//! stroke color or a dark shadow moved slightly down. macOS draws it with
+//! a real blur (`NSShadow`); Windows stacks [`glow_layers`] of growing
+//! width, each faint, which fade out much like the blur.
+
+use super::constants::*;
+use super::screen_frame::Rgba;
+
+/// Color of the glow behind a `stroke` (`None` when `glow` is off).
+pub fn glow_color(glow: i32, stroke: Rgba) -> Option<Rgba> {
+    let (r, g, b, a) = stroke;
+    match glow {
+        GLOW_SOFT => Some((r, g, b, a * GLOW_ALPHA)),
+        GLOW_SHADOW => Some((0.0, 0.0, 0.0, a * GLOW_SHADOW_ALPHA)),
+        _ => None,
+    }
+}
+
+/// Distance in pixels the glow is moved down from the highlight.
+pub fn glow_offset(glow: i32) -> f64 {
+    if glow == GLOW_SHADOW {
+        GLOW_SHADOW_OFFSET
+    } else {
+        0.0
+    }
+}
+
+/// Layers of the glow, widest first: how far each spreads past the
+/// shape's outline, in pixels, and its opacity relative to the glow color.
+///
+/// Stacked, they are most opaque along the outline and fade out over
+/// `GLOW_RADIUS`.
+pub fn glow_layers() -> [(f64, f64); GLOW_LAYERS] {
+    let n = GLOW_LAYERS as f64;
+    std::array::from_fn(|i| (GLOW_RADIUS * (n - i as f64) / n, 1.0 / (n - 1.0)))
+}
@@ -0,0 +1,48 @@
+//! Alignment grid (pure Rust, no FFI).
+//!
+//! While it is on (toggled by a hotkey), lines are drawn across the
+//! display the cursor is on, for lining up elements in design demos:
+//! major lines every `spacing` from the display's top-left corner, and
+//! fainter minor lines splitting each cell into `subdivisions` parts.
+//! Spacing, subdivisions and color are settings; the toggle is not
+//! persisted. Positions are points on macOS and pixels on Windows.
+
+use super::constants::GRID_MINOR_ALPHA;
+use super::screen_frame::Rgba;
+
+/// One grid line, across a display.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridLine {
+    /// Distance from the display's left (or top) edge.
+    pub offset: f64,
+    /// Major line, on a multiple of the spacing?
+    pub major: bool,
+}
+
+/// Lines of a display `length` long (its width for the vertical lines,
+/// its height for the horizontal ones), from its edge at 0 up to, not
+/// including, `length`. Empty for a spacing that is not positive.
+pub fn grid_lines(length: f64, spacing: f64, subdivisions: i32) -> Vec<GridLine> {
+    let parts = subdivisions.max(1) as i64;
+    let step = spacing / parts as f64;
+    if step.is_nan() || step <= 0.0 || !length.is_finite() {
+        return Vec::new();
+    }
+    (0..)
+        .map(|i| GridLine {
+            offset: i as f64 * step,
+            major: i % parts == 0,
+        })
+        .take_while(|line| line.offset < length)
+        .collect()
+}
+
+/// Color of a line: `color` for the major lines, fainter for the minor.
+pub fn grid_line_color(color: Rgba, major: bool) -> Rgba {
+    let (r, g, b, a) = color;
+    if major {
+        color
+    } else {
+        (r, g, b, a * GRID_MINOR_ALPHA)
+    }
+}
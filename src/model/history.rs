@@ -0,0 +1,98 @@
+//! Undo/redo history of the Settings window (pure Rust, no FFI).
+//!
+//! Controls apply their value as soon as they change, so every change
+//! records the settings as they were before it. Changes of the same
+//! control in quick succession (a slider drag, the color picker) are
+//! merged into one step, so undo goes back to where the drag started.
+
+use std::collections::VecDeque;
+
+use super::app_state::OverlayState;
+use super::constants::*;
+use super::screen_frame::Rgba;
+
+/// Settings the Settings window can change: the overlay state and the
+/// recording-frame color of its display.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingsSnapshot {
+    pub state: OverlayState,
+    pub frame_color: Rgba,
+}
+
+/// Change being merged: the control it came from and when it last changed.
+#[derive(Debug, Clone)]
+struct OpenChange {
+    key: String,
+    t: f64,
+}
+
+/// Undo and redo stacks of settings snapshots.
+#[derive(Debug, Clone, Default)]
+pub struct SettingsHistory {
+    undo: VecDeque<SettingsSnapshot>,
+    redo: Vec<SettingsSnapshot>,
+    open: Option<OpenChange>,
+}
+
+impl SettingsHistory {
+    /// Creates an empty history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a change of control `key` at time `t` (seconds), `before`
+    /// being the settings just before it. Clears the redo stack.
+    ///
+    /// A change of the same control within `SETTINGS_UNDO_MERGE_SECS` of
+    /// the previous one extends that step instead of adding one.
+    pub fn record(&mut self, key: &str, before: SettingsSnapshot, t: f64) {
+        let merges = self.open.as_ref().is_some_and(|open| {
+            open.key == key && (0.0..=SETTINGS_UNDO_MERGE_SECS).contains(&(t - open.t))
+        });
+        self.open = Some(OpenChange {
+            key: key.to_string(),
+            t,
+        });
+        self.redo.clear();
+        if merges {
+            return;
+        }
+        self.undo.push_back(before);
+        if self.undo.len() > SETTINGS_UNDO_LIMIT {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Settings to go back to, given the `current` ones; `None` when there
+    /// is nothing to undo.
+    pub fn undo(&mut self, current: SettingsSnapshot) -> Option<SettingsSnapshot> {
+        let previous = self.undo.pop_back()?;
+        self.redo.push(current);
+        self.open = None;
+        Some(previous)
+    }
+
+    /// Settings to go forward to again, given the `current` ones; `None`
+    /// when nothing was undone since the last change.
+    pub fn redo(&mut self, current: SettingsSnapshot) -> Option<SettingsSnapshot> {
+        let next = self.redo.pop()?;
+        self.undo.push_back(current);
+        self.open = None;
+        Some(next)
+    }
+
+    /// Is there a change to undo?
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Is there an undone change to redo?
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Forgets every change.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
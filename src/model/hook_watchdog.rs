@@ -0,0 +1,93 @@
+//! Mouse hook watchdog (pure Rust, no FFI).
+//!
+//! Windows silently removes a low-level mouse hook that takes too long
+//! to return, after which clicks no longer reach the overlay. The
+//! watchdog compares the buttons the hook saw with the ones polled every
+//! frame. The hook is taken for removed when `HOOK_MISSED_EDGES` presses
+//! and releases are polled in a row without it reporting any, which
+//! catches ordinary short clicks, or when the buttons disagree for
+//! `HOOK_WATCHDOG_SECS`, which catches a missed press held down.
+
+use super::constants::{HOOK_MISSED_EDGES, HOOK_WATCHDOG_SECS};
+
+/// Button bit of the left button.
+pub const HOOK_BUTTON_LEFT: u8 = 1;
+/// Button bit of the right button.
+pub const HOOK_BUTTON_RIGHT: u8 = 2;
+
+/// Buttons seen by the hook and by polling, the presses and releases the
+/// hook did not report, and how long the two have disagreed.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HookWatchdog {
+    hook_down: u8,
+    /// Presses and releases the hook reported since the last polled one.
+    hook_edges: u32,
+    polled_down: bool,
+    /// Polled presses and releases in a row the hook reported none for.
+    missed_edges: u32,
+    mismatch_since: Option<f64>,
+    reinstalls: u32,
+}
+
+impl HookWatchdog {
+    /// Watchdog with no buttons down.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The hook saw `button` (a `HOOK_BUTTON_*` bit) pressed or released.
+    pub fn hook_saw(&mut self, button: u8, down: bool) {
+        let was_down = self.hook_down != 0;
+        if down {
+            self.hook_down |= button;
+        } else {
+            self.hook_down &= !button;
+        }
+        if (self.hook_down != 0) != was_down {
+            self.hook_edges += 1;
+        }
+    }
+
+    /// Compare the buttons `polled` down at time `now` (seconds) with the
+    /// hook's. `true` when the hook is taken for removed: it is counted as
+    /// reinstalled and starts over from the polled buttons.
+    ///
+    /// Only whether any button is down is compared, as the hook and the
+    /// polled state may disagree on which one with swapped buttons. A
+    /// click too short for polling to see is reported by the hook alone,
+    /// which is fine.
+    pub fn check(&mut self, polled: u8, now: f64) -> bool {
+        let polled_down = polled != 0;
+        if polled_down != self.polled_down {
+            self.polled_down = polled_down;
+            if self.hook_edges == 0 {
+                self.missed_edges += 1;
+            } else {
+                self.missed_edges = 0;
+            }
+            self.hook_edges = 0;
+        }
+
+        let removed = if (self.hook_down != 0) == polled_down {
+            self.mismatch_since = None;
+            self.missed_edges >= HOOK_MISSED_EDGES
+        } else {
+            let since = *self.mismatch_since.get_or_insert(now);
+            now - since >= HOOK_WATCHDOG_SECS || self.missed_edges >= HOOK_MISSED_EDGES
+        };
+        if !removed {
+            return false;
+        }
+        self.hook_down = polled;
+        self.hook_edges = 0;
+        self.missed_edges = 0;
+        self.mismatch_since = None;
+        self.reinstalls += 1;
+        true
+    }
+
+    /// Times the hook was taken for removed since the start.
+    pub fn reinstalls(&self) -> u32 {
+        self.reinstalls
+    }
+}
@@ -0,0 +1,460 @@
+//! Hotkey profiles (pure Rust, no FFI).
+//!
+//! A profile is the set of global hotkeys bound to the app's commands.
+//! The standard profile keeps each platform's usual shortcuts; the
+//! presentation profile moves every command to F13-F20, which IDEs and
+//! slide tools leave alone, and the color sampler, the ruler and the
+//! grid, for lack of more keys, to Shift+F13 to Shift+F15. The selected
+//! profile is stored under `PREF_HOTKEY_PROFILE`; the platforms register
+//! its bindings and show them in their menus and help overlay.
+//!
+//! Where the Settings window offers it (Windows), single commands can be
+//! rebound on top of the profile. The rebinds are stored under
+//! `PREF_HOTKEY_OVERRIDES` as `action=combination` pairs separated by
+//! `;`, e.g. `toggle=Ctrl+Alt+H;quit=Ctrl+Shift+F12`, and apply to
+//! whichever profile is selected.
+
+use tracing::warn;
+
+use crate::events::AppEvent;
+
+/// Name of the profile with each platform's usual shortcuts.
+pub const HOTKEY_PROFILE_STANDARD: &str = "standard";
+
+/// Name of the profile using F13-F20.
+pub const HOTKEY_PROFILE_PRESENTATION: &str = "presentation";
+
+/// Built-in profiles, in menu order.
+pub const HOTKEY_PROFILES: &[&str] = &[HOTKEY_PROFILE_STANDARD, HOTKEY_PROFILE_PRESENTATION];
+
+/// Control modifier.
+pub const MOD_CTRL: u8 = 1 << 0;
+/// Shift modifier.
+pub const MOD_SHIFT: u8 = 1 << 1;
+/// Alt / Option modifier.
+pub const MOD_ALT: u8 = 1 << 2;
+/// Command modifier (macOS only).
+pub const MOD_CMD: u8 = 1 << 3;
+
+/// Platform whose standard shortcuts a profile uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotkeyPlatform {
+    MacOs,
+    Windows,
+}
+
+/// Command a global hotkey can trigger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotkeyAction {
+    Toggle,
+    Settings,
+    Help,
+    ZoomIn,
+    ZoomOut,
+    Coordinates,
+    ColorSampler,
+    Ruler,
+    Grid,
+    Lock,
+    Quit,
+}
+
+impl HotkeyAction {
+    /// Every action, in help-overlay order.
+    pub const ALL: [HotkeyAction; 11] = [
+        HotkeyAction::Toggle,
+        HotkeyAction::Settings,
+        HotkeyAction::Help,
+        HotkeyAction::ZoomIn,
+        HotkeyAction::ZoomOut,
+        HotkeyAction::Coordinates,
+        HotkeyAction::ColorSampler,
+        HotkeyAction::Ruler,
+        HotkeyAction::Grid,
+        HotkeyAction::Lock,
+        HotkeyAction::Quit,
+    ];
+
+    /// Event published when the hotkey is pressed.
+    pub fn event(self) -> AppEvent {
+        match self {
+            HotkeyAction::Toggle => AppEvent::ToggleOverlay,
+            HotkeyAction::Settings => AppEvent::OpenSettings,
+            HotkeyAction::Help => AppEvent::ShowHelp,
+            HotkeyAction::ZoomIn => AppEvent::ZoomIn,
+            HotkeyAction::ZoomOut => AppEvent::ZoomOut,
+            HotkeyAction::Coordinates => AppEvent::ToggleCoordinates,
+            HotkeyAction::ColorSampler => AppEvent::ToggleColorSampler,
+            HotkeyAction::Ruler => AppEvent::ToggleRuler,
+            HotkeyAction::Grid => AppEvent::ToggleGrid,
+            HotkeyAction::Lock => AppEvent::TogglePresentationLock,
+            HotkeyAction::Quit => AppEvent::RequestQuit,
+        }
+    }
+
+    /// Name of the action in `PREF_HOTKEY_OVERRIDES`.
+    pub fn id(self) -> &'static str {
+        match self {
+            HotkeyAction::Toggle => "toggle",
+            HotkeyAction::Settings => "settings",
+            HotkeyAction::Help => "help",
+            HotkeyAction::ZoomIn => "zoom_in",
+            HotkeyAction::ZoomOut => "zoom_out",
+            HotkeyAction::Coordinates => "coordinates",
+            HotkeyAction::ColorSampler => "color_sampler",
+            HotkeyAction::Ruler => "ruler",
+            HotkeyAction::Grid => "grid",
+            HotkeyAction::Lock => "lock",
+            HotkeyAction::Quit => "quit",
+        }
+    }
+
+    /// Action named `id` in `PREF_HOTKEY_OVERRIDES`.
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.id() == id)
+    }
+
+    /// Translation key describing the action in the help overlay.
+    pub fn description(self) -> &'static str {
+        match self {
+            HotkeyAction::Toggle => "Toggle overlay",
+            HotkeyAction::Settings => "Open settings",
+            HotkeyAction::Help => "Show help",
+            HotkeyAction::ZoomIn => "Zoom in",
+            HotkeyAction::ZoomOut => "Zoom out",
+            HotkeyAction::Coordinates => "Cursor coordinates",
+            HotkeyAction::ColorSampler => "Color sampler",
+            HotkeyAction::Ruler => "Ruler",
+            HotkeyAction::Grid => "Grid",
+            HotkeyAction::Lock => "Lock settings",
+            HotkeyAction::Quit => "Quit app",
+        }
+    }
+}
+
+/// Key of a hotkey, independent of platform key codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    /// Letter key, uppercase (`'A'`..=`'Z'`).
+    Letter(char),
+    Comma,
+    Equal,
+    Minus,
+    /// Function key F1-F20.
+    F(u8),
+}
+
+/// Key plus modifiers (`MOD_*`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hotkey {
+    pub key: Key,
+    pub modifiers: u8,
+}
+
+impl Hotkey {
+    const fn new(key: Key, modifiers: u8) -> Self {
+        Self { key, modifiers }
+    }
+
+    /// Human-readable combination, e.g. `Ctrl + Shift + A` or `F13`.
+    pub fn label(&self) -> String {
+        let mut parts: Vec<String> = [
+            (MOD_CMD, "\u{2318}"),
+            (MOD_CTRL, "Ctrl"),
+            (MOD_ALT, "Alt"),
+            (MOD_SHIFT, "Shift"),
+        ]
+        .iter()
+        .filter(|(m, _)| self.modifiers & m != 0)
+        .map(|(_, name)| name.to_string())
+        .collect();
+        parts.push(match self.key {
+            Key::Letter(c) => c.to_string(),
+            Key::Comma => ",".to_string(),
+            Key::Equal => "=".to_string(),
+            Key::Minus => "-".to_string(),
+            Key::F(n) => format!("F{n}"),
+        });
+        parts.join(" + ")
+    }
+
+    /// Combination as stored in `PREF_HOTKEY_OVERRIDES`, e.g.
+    /// `Ctrl+Shift+A` or `Ctrl+Shift+Equal`.
+    pub fn code(&self) -> String {
+        let mut parts: Vec<String> = MODIFIER_CODES
+            .iter()
+            .filter(|(m, _)| self.modifiers & m != 0)
+            .map(|(_, name)| name.to_string())
+            .collect();
+        parts.push(match self.key {
+            Key::Letter(c) => c.to_string(),
+            Key::Comma => "Comma".to_string(),
+            Key::Equal => "Equal".to_string(),
+            Key::Minus => "Minus".to_string(),
+            Key::F(n) => format!("F{n}"),
+        });
+        parts.join("+")
+    }
+
+    /// Can this combination be a global hotkey without taking over
+    /// typing in other apps? Letters and punctuation need Ctrl, Alt or Cmd
+    /// (Shift alone still types them); function keys do not.
+    pub fn allowed_globally(&self) -> bool {
+        matches!(self.key, Key::F(_)) || self.modifiers & !MOD_SHIFT != 0
+    }
+
+    /// Combination stored as `code`; `None` if malformed.
+    pub fn parse(code: &str) -> Option<Self> {
+        let mut parts: Vec<&str> = code.split('+').map(str::trim).collect();
+        let key = match parts.pop()? {
+            "Comma" => Key::Comma,
+            "Equal" => Key::Equal,
+            "Minus" => Key::Minus,
+            name if name.len() == 1 && name.chars().all(|c| c.is_ascii_uppercase()) => {
+                Key::Letter(name.chars().next()?)
+            }
+            name => match name.strip_prefix('F')?.parse() {
+                Ok(n @ 1..=20) => Key::F(n),
+                _ => return None,
+            },
+        };
+        let mut modifiers = 0;
+        for part in parts {
+            let (m, _) = MODIFIER_CODES.iter().find(|(_, name)| *name == part)?;
+            modifiers |= m;
+        }
+        Some(Self { key, modifiers })
+    }
+}
+
+/// Modifiers by their name in `Hotkey::code`.
+const MODIFIER_CODES: [(u8, &str); 4] = [
+    (MOD_CMD, "Cmd"),
+    (MOD_CTRL, "Ctrl"),
+    (MOD_ALT, "Alt"),
+    (MOD_SHIFT, "Shift"),
+];
+
+/// Hotkey bound to an action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Binding {
+    pub action: HotkeyAction,
+    pub hotkey: Hotkey,
+}
+
+const fn bind(action: HotkeyAction, key: Key, modifiers: u8) -> Binding {
+    Binding {
+        action,
+        hotkey: Hotkey::new(key, modifiers),
+    }
+}
+
+const STANDARD_MACOS: &[Binding] = &[
+    bind(HotkeyAction::Toggle, Key::Letter('A'), MOD_CTRL),
+    bind(HotkeyAction::Settings, Key::Comma, MOD_CMD | MOD_SHIFT),
+    bind(HotkeyAction::Help, Key::Letter('H'), MOD_CMD | MOD_SHIFT),
+    bind(HotkeyAction::ZoomIn, Key::Equal, MOD_CTRL | MOD_SHIFT),
+    bind(HotkeyAction::ZoomOut, Key::Minus, MOD_CTRL | MOD_SHIFT),
+    bind(
+        HotkeyAction::Coordinates,
+        Key::Letter('M'),
+        MOD_CTRL | MOD_SHIFT,
+    ),
+    bind(
+        HotkeyAction::ColorSampler,
+        Key::Letter('E'),
+        MOD_CTRL | MOD_SHIFT,
+    ),
+    bind(HotkeyAction::Ruler, Key::Letter('U'), MOD_CTRL | MOD_SHIFT),
+    bind(HotkeyAction::Grid, Key::Letter('G'), MOD_CTRL | MOD_SHIFT),
+    bind(HotkeyAction::Lock, Key::Letter('L'), MOD_CTRL | MOD_SHIFT),
+    bind(HotkeyAction::Quit, Key::Letter('X'), MOD_CTRL | MOD_SHIFT),
+];
+
+const STANDARD_WINDOWS: &[Binding] = &[
+    bind(HotkeyAction::Toggle, Key::Letter('A'), MOD_CTRL | MOD_SHIFT),
+    bind(
+        HotkeyAction::Settings,
+        Key::Letter('S'),
+        MOD_CTRL | MOD_SHIFT,
+    ),
+    bind(HotkeyAction::Help, Key::Letter('H'), MOD_CTRL | MOD_SHIFT),
+    bind(HotkeyAction::ZoomIn, Key::Equal, MOD_CTRL | MOD_SHIFT),
+    bind(HotkeyAction::ZoomOut, Key::Minus, MOD_CTRL | MOD_SHIFT),
+    bind(
+        HotkeyAction::Coordinates,
+        Key::Letter('M'),
+        MOD_CTRL | MOD_SHIFT,
+    ),
+    bind(
+        HotkeyAction::ColorSampler,
+        Key::Letter('E'),
+        MOD_CTRL | MOD_SHIFT,
+    ),
+    bind(HotkeyAction::Ruler, Key::Letter('U'), MOD_CTRL | MOD_SHIFT),
+    bind(HotkeyAction::Grid, Key::Letter('G'), MOD_CTRL | MOD_SHIFT),
+    bind(HotkeyAction::Lock, Key::Letter('L'), MOD_CTRL | MOD_SHIFT),
+    bind(HotkeyAction::Quit, Key::Letter('Q'), MOD_CTRL | MOD_SHIFT),
+];
+
+const PRESENTATION: &[Binding] = &[
+    bind(HotkeyAction::Toggle, Key::F(13), 0),
+    bind(HotkeyAction::Settings, Key::F(14), 0),
+    bind(HotkeyAction::Help, Key::F(15), 0),
+    bind(HotkeyAction::ZoomIn, Key::F(16), 0),
+    bind(HotkeyAction::ZoomOut, Key::F(17), 0),
+    bind(HotkeyAction::Coordinates, Key::F(20), 0),
+    bind(HotkeyAction::ColorSampler, Key::F(13), MOD_SHIFT),
+    bind(HotkeyAction::Ruler, Key::F(14), MOD_SHIFT),
+    bind(HotkeyAction::Grid, Key::F(15), MOD_SHIFT),
+    bind(HotkeyAction::Lock, Key::F(18), 0),
+    bind(HotkeyAction::Quit, Key::F(19), 0),
+];
+
+/// Bindings of the profile called `name`; unknown names get the
+/// standard profile.
+pub fn profile_bindings(name: &str, platform: HotkeyPlatform) -> &'static [Binding] {
+    match (name, platform) {
+        (HOTKEY_PROFILE_PRESENTATION, _) => PRESENTATION,
+        (_, HotkeyPlatform::MacOs) => STANDARD_MACOS,
+        (_, HotkeyPlatform::Windows) => STANDARD_WINDOWS,
+    }
+}
+
+/// Bindings registered in safe mode: the standard toggle hotkey alone.
+pub fn safe_mode_bindings(platform: HotkeyPlatform) -> &'static [Binding] {
+    let bindings = profile_bindings(HOTKEY_PROFILE_STANDARD, platform);
+    let toggle = bindings
+        .iter()
+        .position(|b| b.action == HotkeyAction::Toggle)
+        .unwrap_or(0);
+    &bindings[toggle..=toggle]
+}
+
+/// Built-in profile called `name`, or the standard one if unknown.
+pub fn resolve_profile(name: &str) -> &'static str {
+    HOTKEY_PROFILES
+        .iter()
+        .find(|p| **p == name)
+        .copied()
+        .unwrap_or(HOTKEY_PROFILE_STANDARD)
+}
+
+/// Translation key of a profile's menu entry.
+pub fn profile_label(name: &str) -> &'static str {
+    match resolve_profile(name) {
+        HOTKEY_PROFILE_PRESENTATION => "Presentation (F13-F20)",
+        _ => "Standard",
+    }
+}
+
+/// Rebinds stored in `PREF_HOTKEY_OVERRIDES`; malformed entries are
+/// skipped, and so are combinations that would take over typing (see
+/// [`Hotkey::allowed_globally`]), with a warning.
+pub fn parse_overrides(text: &str) -> Vec<Binding> {
+    text.split(';')
+        .filter_map(|entry| {
+            let (action, code) = entry.split_once('=')?;
+            let binding = Binding {
+                action: HotkeyAction::from_id(action.trim())?,
+                hotkey: Hotkey::parse(code)?,
+            };
+            if !binding.hotkey.allowed_globally() {
+                warn!(
+                    "Hotkey override `{}` ignored: letters and punctuation need Ctrl, Alt or Cmd",
+                    entry.trim()
+                );
+                return None;
+            }
+            Some(binding)
+        })
+        .collect()
+}
+
+/// Text stored in `PREF_HOTKEY_OVERRIDES` for `overrides`.
+pub fn encode_overrides(overrides: &[Binding]) -> String {
+    overrides
+        .iter()
+        .map(|b| format!("{}={}", b.action.id(), b.hotkey.code()))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// `overrides` with `action` rebound to `hotkey`, or given back its
+/// profile hotkey with `None`.
+pub fn set_override(
+    overrides: &[Binding],
+    action: HotkeyAction,
+    hotkey: Option<Hotkey>,
+) -> Vec<Binding> {
+    let mut overrides: Vec<Binding> = overrides
+        .iter()
+        .filter(|b| b.action != action)
+        .copied()
+        .collect();
+    if let Some(hotkey) = hotkey {
+        overrides.push(Binding { action, hotkey });
+    }
+    overrides
+}
+
+/// `bindings` of a profile with the rebinds of `overrides` in place.
+pub fn apply_overrides(bindings: &[Binding], overrides: &[Binding]) -> Vec<Binding> {
+    bindings
+        .iter()
+        .map(|b| Binding {
+            action: b.action,
+            hotkey: hotkey_for(overrides, b.action).unwrap_or(b.hotkey),
+        })
+        .collect()
+}
+
+/// Action other than `action` bound to `hotkey` in `bindings`, which
+/// rebinding `action` to it would clash with.
+pub fn hotkey_conflict(
+    bindings: &[Binding],
+    action: HotkeyAction,
+    hotkey: Hotkey,
+) -> Option<HotkeyAction> {
+    bindings
+        .iter()
+        .find(|b| b.action != action && b.hotkey == hotkey)
+        .map(|b| b.action)
+}
+
+/// Hotkey bound to `action` in `bindings`, if any.
+pub fn hotkey_for(bindings: &[Binding], action: HotkeyAction) -> Option<Hotkey> {
+    bindings
+        .iter()
+        .find(|b| b.action == action)
+        .map(|b| b.hotkey)
+}
+
+/// One row of the help overlay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HelpEntry {
+    /// Keys to press, e.g. `Ctrl + Shift + A`.
+    pub keys: String,
+    /// Translation key of what they do.
+    pub description: &'static str,
+    /// Did the system take the hotkey? It does nothing when another app
+    /// holds it.
+    pub registered: bool,
+}
+
+/// Rows of the help overlay: one per binding in use (the selected
+/// profile with the user's rebinds, as registered), in profile order.
+/// `registered` tells whether the hotkey of an action was taken.
+pub fn help_entries(
+    bindings: &[Binding],
+    registered: impl Fn(HotkeyAction) -> bool,
+) -> Vec<HelpEntry> {
+    bindings
+        .iter()
+        .map(|b| HelpEntry {
+            keys: b.hotkey.label(),
+            description: b.action.description(),
+            registered: registered(b.action),
+        })
+        .collect()
+}
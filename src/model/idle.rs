@@ -0,0 +1,79 @@
+//! Cursor idle auto-hide (pure Rust, no FFI).
+//!
+//! Fades the highlight out once the cursor has stayed still for a
+//! configurable delay and back in as soon as it moves, so a resting
+//! pointer does not sit on top of the text being read.
+
+use super::constants::*;
+
+/// Idle tracker and fade animation state.
+///
+/// Feed it one sample per frame; it returns the opacity multiplier for
+/// the highlight, easing linearly between 1.0 and 0.0 over
+/// `IDLE_FADE_OUT_SECS` (fade-out) or `IDLE_FADE_IN_SECS` (fade-in).
+#[derive(Debug, Clone, Copy)]
+pub struct IdleFade {
+    last_pos: Option<(f64, f64)>,
+    last_activity: f64,
+    last_t: Option<f64>,
+    hidden: bool,
+    alpha: f64,
+}
+
+impl Default for IdleFade {
+    fn default() -> Self {
+        Self {
+            last_pos: None,
+            last_activity: 0.0,
+            last_t: None,
+            hidden: false,
+            alpha: 1.0,
+        }
+    }
+}
+
+impl IdleFade {
+    /// Creates a fully visible tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a cursor sample at time `t` (seconds) and returns the
+    /// opacity multiplier.
+    ///
+    /// Movement, or `active` (e.g. a held mouse button), restarts the idle
+    /// timer; after `idle_secs` without either the highlight fades out.
+    pub fn update(&mut self, x: f64, y: f64, active: bool, t: f64, idle_secs: f64) -> f64 {
+        if active || self.last_pos != Some((x, y)) {
+            self.last_pos = Some((x, y));
+            self.last_activity = t;
+        }
+
+        let dt = self.last_t.map_or(0.0, |lt| (t - lt).max(0.0));
+        self.last_t = Some(t);
+
+        self.hidden = t - self.last_activity >= idle_secs;
+        if self.hidden {
+            self.alpha = (self.alpha - dt / IDLE_FADE_OUT_SECS).max(0.0);
+        } else {
+            self.alpha = (self.alpha + dt / IDLE_FADE_IN_SECS).min(1.0);
+        }
+        self.alpha
+    }
+
+    /// Skips the running fade (Reduce Motion) and returns the final opacity.
+    pub fn snap(&mut self) -> f64 {
+        self.alpha = if self.hidden { 0.0 } else { 1.0 };
+        self.alpha
+    }
+
+    /// Current opacity multiplier.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Forgets all samples and shows the highlight again.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
@@ -0,0 +1,104 @@
+//! Single instance (pure Rust, no FFI).
+//!
+//! Only one Lumbus runs per user session: a second one would draw a
+//! second highlight and find its hotkeys taken. A second launch hands its
+//! command-line arguments to the running instance and exits (a named
+//! mutex and `WM_COPYDATA` on Windows, the bundle identifier and a
+//! distributed notification on macOS).
+//!
+//! The running instance carries out the `lumbus://` URLs among the
+//! arguments and saves the report `--diagnostics` asks for, which is then
+//! of the instance actually running. A launch asking for neither opens the
+//! Settings window, so starting the app again visibly does something.
+//! `--reset` and `--soak` are refused instead (see [`fresh_start_flag`]),
+//! and `--safe-mode` only applies to a fresh start.
+//!
+//! A restart starts the new process with [`RESTART_FLAG`] before the old
+//! one quits; the new process waits for the old one to be gone instead of
+//! handing its arguments over.
+
+use super::constants::{RESET_FLAG, SOAK_FLAG};
+use super::diagnostics::DiagnosticsConfig;
+use super::preferences::reset_requested;
+use super::soak::SoakConfig;
+use super::url_scheme::URL_SCHEME;
+
+/// Name of the mutex held by the running instance (Windows).
+pub const INSTANCE_MUTEX: &str = "Local\\LumbusSingleInstance";
+
+/// Name of the distributed notification carrying a handoff (macOS).
+pub const HANDOFF_NOTIFICATION: &str = "com.restevean.lumbus.handoff";
+
+/// Argument of a process started by a restart.
+pub const RESTART_FLAG: &str = "--restarted";
+
+/// How long a restarted process waits for the old one to quit.
+pub const RESTART_WAIT_SECS: u64 = 10;
+
+/// Was this process started by a restart?
+pub fn restarted<I, S>(args: I) -> bool
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    args.into_iter().any(|arg| arg.as_ref() == RESTART_FLAG)
+}
+
+/// What a second launch asks of the running instance.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Handoff {
+    /// `lumbus://` URLs to carry out, in order.
+    pub urls: Vec<String>,
+    /// Save a diagnostics report (`--diagnostics`).
+    pub diagnostics: Option<DiagnosticsConfig>,
+}
+
+impl Handoff {
+    /// Nothing was asked for: open the Settings window.
+    pub fn opens_settings(&self) -> bool {
+        self.urls.is_empty() && self.diagnostics.is_none()
+    }
+}
+
+/// Handoff for the command-line arguments of a second launch.
+pub fn handoff<S: AsRef<str>>(args: &[S]) -> Handoff {
+    let prefix = format!("{URL_SCHEME}:");
+    let args: Vec<&str> = args.iter().map(|arg| arg.as_ref()).collect();
+    Handoff {
+        urls: args
+            .iter()
+            .filter(|arg| arg.to_ascii_lowercase().starts_with(&prefix))
+            .map(|arg| arg.to_string())
+            .collect(),
+        diagnostics: DiagnosticsConfig::from_args(&args),
+    }
+}
+
+/// Flag among the arguments of a second launch that only this process
+/// can carry out, as the one running: `--reset` would rewrite the
+/// settings file under the running instance, and `--soak` would churn its
+/// settings and quit it. Such a launch is refused rather than handed off.
+pub fn fresh_start_flag<S: AsRef<str>>(args: &[S]) -> Option<&'static str> {
+    let args = || args.iter().map(|arg| arg.as_ref());
+    if reset_requested(args()) {
+        Some(RESET_FLAG)
+    } else {
+        SoakConfig::from_args(args()).map(|_| SOAK_FLAG)
+    }
+}
+
+/// Arguments as sent to the running instance, one per line.
+pub fn encode_args<S: AsRef<str>>(args: &[S]) -> String {
+    args.iter()
+        .map(|arg| arg.as_ref())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Arguments sent by [`encode_args`].
+pub fn decode_args(text: &str) -> Vec<String> {
+    text.lines()
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect()
+}
@@ -0,0 +1,76 @@
+//! Cursor kinematics (pure Rust, no FFI).
+//!
+//! Tracks a smoothed cursor speed from timestamped position samples, and
+//! derives behaviors from it such as the speed-based opacity ramp and
+//! size scaling.
+
+use super::constants::*;
+
+/// Smoothed cursor speed tracker.
+///
+/// The instantaneous speed between samples is low-pass filtered with a
+/// time constant of `KINEMATICS_SMOOTHING_SECS`, so a resting cursor
+/// decays back to zero speed instead of keeping the last sweep's value.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Kinematics {
+    last: Option<(f64, f64, f64)>,
+    speed: f64,
+}
+
+impl Kinematics {
+    /// Creates a tracker at rest.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a cursor sample at time `t` (seconds) and returns the
+    /// smoothed speed in pixels per second.
+    pub fn update(&mut self, x: f64, y: f64, t: f64) -> f64 {
+        if let Some((lx, ly, lt)) = self.last {
+            let dt = t - lt;
+            if dt <= 0.0 {
+                return self.speed;
+            }
+            let instant = (x - lx).hypot(y - ly) / dt;
+            let k = 1.0 - (-dt / KINEMATICS_SMOOTHING_SECS).exp();
+            self.speed += (instant - self.speed) * k;
+        }
+        self.last = Some((x, y, t));
+        self.speed
+    }
+
+    /// Smoothed speed in pixels per second.
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// Forgets all samples (e.g. after the overlay is hidden).
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Opacity multiplier for the highlight at a given cursor speed.
+///
+/// Fully opaque up to `SPEED_FADE_SLOW_PX_S`, fading smoothly down to
+/// `SPEED_FADE_MIN_ALPHA` at `SPEED_FADE_FAST_PX_S` and above.
+pub fn speed_fade_alpha(speed: f64) -> f64 {
+    1.0 - speed_ramp(speed) * (1.0 - SPEED_FADE_MIN_ALPHA)
+}
+
+/// Radius multiplier for the highlight at a given cursor speed.
+///
+/// Unchanged up to `SPEED_FADE_SLOW_PX_S`, growing smoothly to
+/// `SPEED_SIZE_MAX_SCALE` at `SPEED_FADE_FAST_PX_S` and above, so the
+/// audience can follow quick movements; it shrinks back as the cursor
+/// slows down.
+pub fn speed_size_scale(speed: f64) -> f64 {
+    1.0 + speed_ramp(speed) * (SPEED_SIZE_MAX_SCALE - 1.0)
+}
+
+/// Eased position [0.0, 1.0] of `speed` between the slow and fast speeds.
+fn speed_ramp(speed: f64) -> f64 {
+    let span = SPEED_FADE_FAST_PX_S - SPEED_FADE_SLOW_PX_S;
+    let t = ((speed - SPEED_FADE_SLOW_PX_S) / span).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
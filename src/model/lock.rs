@@ -0,0 +1,42 @@
+//! Presentation lock (pure Rust, no FFI).
+//!
+//! While the lock is on, every event that would change the highlight
+//! settings is ignored and the Settings entry points are hidden, so a stray
+//! hotkey or macro-pad press cannot alter the overlay in the middle of a
+//! talk. Toggling the lock itself is always allowed.
+
+use crate::events::AppEvent;
+
+/// Lock state of the running session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PresentationLock {
+    locked: bool,
+}
+
+impl PresentationLock {
+    /// Creates an unlocked state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Is the lock on?
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Flips the lock and returns the new state.
+    pub fn toggle(&mut self) -> bool {
+        self.locked = !self.locked;
+        self.locked
+    }
+
+    /// Should this event be handled in the current state?
+    pub fn allows(&self, event: &AppEvent) -> bool {
+        !(self.locked && event.mutates_settings())
+    }
+
+    /// Should the Settings window be reachable (menus, hotkeys)?
+    pub fn settings_available(&self) -> bool {
+        !self.locked
+    }
+}
@@ -0,0 +1,194 @@
+//! Admin-managed configuration layer (pure Rust, no FFI).
+//!
+//! IT can deploy a read-only configuration alongside the user's own
+//! preferences. Managed *defaults* seed every setting the user has not
+//! changed; *locked* settings override the user's value and cannot be
+//! edited from the Settings window. The platform loaders read the managed
+//! sources (MDM profile on macOS, HKLM policy key and ProgramData JSON on
+//! Windows) into a [`ManagedConfig`] and resolve each value in this order:
+//!
+//! 1. locked managed value
+//! 2. user preference
+//! 3. managed default
+//! 4. built-in default
+
+use std::collections::BTreeMap;
+
+use super::constants::*;
+use crate::i18n::lang_from_tag;
+
+/// Preference keys an administrator can manage.
+///
+/// Booleans are stored as 0/1, like the rest of the preferences. The
+/// language is a locale tag (`"es"`) or one of the built-in codes.
+pub const MANAGED_KEYS: &[&str] = &[
+    PREF_RADIUS,
+    PREF_BORDER,
+    PREF_STROKE_R,
+    PREF_STROKE_G,
+    PREF_STROKE_B,
+    PREF_STROKE_A,
+    PREF_FILL_TRANSPARENCY,
+    PREF_LANG,
+    PREF_HIGHLIGHT_STYLE,
+    PREF_SCREEN_FRAME,
+    PREF_SPEED_FADE,
+    PREF_SPEED_SIZE,
+    PREF_CLICK_BURST,
+    PREF_CLICK_BURST_MS,
+    PREF_CLICK_HOLD_MS,
+    PREF_HOLD_RING,
+    PREF_HOLD_RING_MS,
+    PREF_IDLE_HIDE,
+    PREF_IDLE_HIDE_SECS,
+    PREF_MOTION,
+    PREF_HALO,
+    PREF_RAINBOW,
+    PREF_RAINBOW_SPEED,
+    PREF_GLOW,
+    PREF_LETTER_WEIGHT,
+    PREF_CLICK_ICON,
+    PREF_PULSE,
+    PREF_PULSE_AMPLITUDE,
+    PREF_PULSE_PERIOD_MS,
+    PREF_TRANSITION_MS,
+    PREF_GRID_SPACING,
+    PREF_GRID_SUBDIVISIONS,
+    PREF_GRID_R,
+    PREF_GRID_G,
+    PREF_GRID_B,
+    PREF_GRID_A,
+];
+
+/// Managed defaults and locked settings, keyed by preference key.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ManagedConfig {
+    defaults: BTreeMap<String, f64>,
+    locked: BTreeMap<String, f64>,
+}
+
+impl ManagedConfig {
+    /// Creates an empty layer (nothing managed).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Can this preference key be managed?
+    pub fn is_managed_key(key: &str) -> bool {
+        MANAGED_KEYS.contains(&key)
+    }
+
+    /// Can `value` be managed for `key`?
+    ///
+    /// As a number, the language only takes the built-in codes: the index
+    /// of a language loaded at runtime differs between machines.
+    fn accepts(key: &str, value: f64) -> bool {
+        let builtin_lang = LANG_SYSTEM as f64..BUILTIN_LANGUAGES.len() as f64;
+        Self::is_managed_key(key)
+            && value.is_finite()
+            && (key != PREF_LANG || builtin_lang.contains(&value))
+    }
+
+    /// Value of a setting written as text: a number, or for the language
+    /// a locale tag, read as this machine's code for it.
+    fn text_value(key: &str, text: &str) -> Option<f64> {
+        let text = text.trim();
+        match text.parse::<f64>() {
+            Ok(value) => Self::accepts(key, value).then_some(value),
+            Err(_) if key == PREF_LANG => Some(lang_from_tag(text) as f64),
+            Err(_) => None,
+        }
+    }
+
+    /// Sets a managed default. Unknown keys and non-finite values are
+    /// ignored; returns whether the value was accepted.
+    pub fn set_default(&mut self, key: &str, value: f64) -> bool {
+        if !Self::accepts(key, value) {
+            return false;
+        }
+        self.defaults.insert(key.to_string(), value);
+        true
+    }
+
+    /// Locks a setting to a value. Unknown keys and non-finite values are
+    /// ignored; returns whether the value was accepted.
+    pub fn lock(&mut self, key: &str, value: f64) -> bool {
+        if !Self::accepts(key, value) {
+            return false;
+        }
+        self.locked.insert(key.to_string(), value);
+        true
+    }
+
+    /// Like [`set_default`](Self::set_default), for a value written as
+    /// text (a number, or a locale tag for the language).
+    pub fn set_default_text(&mut self, key: &str, text: &str) -> bool {
+        let Some(value) = Self::text_value(key, text) else {
+            return false;
+        };
+        self.defaults.insert(key.to_string(), value);
+        true
+    }
+
+    /// Like [`lock`](Self::lock), for a value written as text (a number,
+    /// or a locale tag for the language).
+    pub fn lock_text(&mut self, key: &str, text: &str) -> bool {
+        let Some(value) = Self::text_value(key, text) else {
+            return false;
+        };
+        self.locked.insert(key.to_string(), value);
+        true
+    }
+
+    /// Is nothing managed?
+    pub fn is_empty(&self) -> bool {
+        self.defaults.is_empty() && self.locked.is_empty()
+    }
+
+    /// Managed default for a key, if any.
+    pub fn default_value(&self, key: &str) -> Option<f64> {
+        self.defaults.get(key).copied()
+    }
+
+    /// Locked value for a key, if any.
+    pub fn locked_value(&self, key: &str) -> Option<f64> {
+        self.locked.get(key).copied()
+    }
+
+    /// Is this setting locked by the administrator?
+    pub fn is_locked(&self, key: &str) -> bool {
+        self.locked.contains_key(key)
+    }
+
+    /// Managed defaults as `(key, value)` pairs.
+    pub fn defaults(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.defaults.iter().map(|(k, v)| (k.as_str(), *v))
+    }
+
+    /// Locked settings as `(key, value)` pairs.
+    pub fn locked(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.locked.iter().map(|(k, v)| (k.as_str(), *v))
+    }
+
+    /// Resolves the effective value of a setting.
+    ///
+    /// `user` is the user's stored preference (`None` when never set) and
+    /// `builtin` the application default.
+    pub fn resolve(&self, key: &str, user: Option<f64>, builtin: f64) -> f64 {
+        self.locked_value(key)
+            .or(user)
+            .or_else(|| self.default_value(key))
+            .unwrap_or(builtin)
+    }
+
+    /// Merges a higher-priority source into this one: its entries replace
+    /// ours key by key.
+    pub fn merge(&mut self, higher: &ManagedConfig) {
+        for (k, v) in higher.defaults() {
+            self.defaults.insert(k.to_string(), v);
+        }
+        for (k, v) in higher.locked() {
+            self.locked.insert(k.to_string(), v);
+        }
+    }
+}
@@ -1,12 +1,153 @@
 //! Application domain model.
 //!
 //! This module contains pure business logic (no FFI dependencies)
-//! including overlay state definition and configuration constants.
+//! including overlay state definition, configuration constants, color
+//! input parsing, the auxiliary-window session store and placement
+//! helpers, the matching of
+//! overlay windows to connected displays, the recording-frame colors,
+//! cursor kinematics, idle auto-hide, click-burst counting, the display
+//! modes, the click indicator hold, the click log,
+//! the zoom-follow magnification, Reduce Motion, the high-contrast halo,
+//! the glow and drop shadow, the click letter font and icons, the
+//! rainbow color mode and the pulse with their animation clock, the
+//! show/hide transition, the cursor coordinates readout, the pixel color
+//! sampler, the on-screen ruler, the alignment grid, pen pressure, the
+//! touch point rings, the mouse hook and refresh timer watchdogs, the
+//! tray icon states, the quick-adjust menu entries, the presentation
+//! lock, the single-instance handoff and restart, the hotkey profiles,
+//! the first-run walkthrough, the OS permissions each feature needs, the
+//! AppleScript commands, `lumbus://` URLs, control endpoint requests and
+//! the companion protocol, the diagnostics and crash reports, the
+//! admin-managed configuration layer,
+//! the user preferences file format and its sync across machines, the
+//! update check, the soak-test script, the laser-pointer trail buffer,
+//! the Settings window palettes, undo history and saved colors, and the
+//! About window contents.
 //!
-//! Platform-specific persistence is in `platform::{macos,windows}::storage`.
+//! Caching and syncing the preferences file is in `storage`; locating it,
+//! and reading the managed sources, is in
+//! `platform::{macos,windows}::storage`.
 
+pub mod about;
+pub mod animation;
 pub mod app_state;
+pub mod appearance;
+pub mod click_burst;
+pub mod click_hold;
+pub mod click_icon;
+pub mod click_log;
+pub mod color;
+pub mod color_sampler;
+pub mod companion;
 pub mod constants;
+pub mod contrast;
+pub mod control;
+pub mod coordinates;
+pub mod crash;
+pub mod diagnostics;
+pub mod display_mode;
+pub mod displays;
+pub mod font;
+pub mod glow;
+pub mod grid;
+pub mod history;
+pub mod hook_watchdog;
+pub mod hotkeys;
+pub mod idle;
+pub mod instance;
+pub mod kinematics;
+pub mod lock;
+pub mod managed;
+pub mod motion;
+pub mod onboarding;
+pub mod palette;
+pub mod pen;
+pub mod permissions;
+pub mod placement;
+pub mod preferences;
+pub mod pulse;
+pub mod quick_adjust;
+pub mod rainbow;
+pub mod ruler;
+pub mod screen_frame;
+pub mod script;
+pub mod session;
+pub mod soak;
+pub mod sync;
+pub mod timer_watchdog;
+pub mod touch;
+pub mod trail;
+pub mod tray_icon;
+pub mod update;
+pub mod url_scheme;
+pub mod zoom;
 
+pub use animation::{transition_alpha, transition_scale, wall_clock_secs, ShowHide};
 pub use app_state::OverlayState;
+pub use appearance::SettingsPalette;
+pub use click_burst::ClickBurst;
+pub use click_hold::ClickHold;
+pub use click_log::{ClickLog, ClickLogFormat, ClickRecord};
+pub use color::parse_color;
+pub use color_sampler::{sample_hex, Rgb8};
+pub use companion::{
+    state_changes, state_lines, CompanionCommand, CompanionReply, CompanionSession,
+};
 pub use constants::*;
+pub use contrast::{halo_color, high_contrast_preset, HIGH_CONTRAST_KEYS};
+pub use control::{
+    body_length, handle_request, is_http_request, parse_request, ControlRequest, ControlResponse,
+    ControlState,
+};
+pub use coordinates::coordinates_label;
+pub use crash::{config_snapshot, panic_message, CrashReport};
+pub use diagnostics::{debug_label, DiagnosticsConfig, DiagnosticsReport, DisplayInfo, FrameTimer};
+pub use display_mode::DisplayMode;
+pub use displays::{plan_overlays, OverlayPlan};
+pub use glow::{glow_color, glow_offset};
+pub use grid::{grid_line_color, grid_lines, GridLine};
+pub use history::{SettingsHistory, SettingsSnapshot};
+pub use hook_watchdog::HookWatchdog;
+pub use hotkeys::{
+    apply_overrides, encode_overrides, help_entries, hotkey_conflict, hotkey_for, parse_overrides,
+    profile_bindings, profile_label, resolve_profile, safe_mode_bindings, set_override, Binding,
+    HelpEntry, Hotkey, HotkeyAction, HotkeyPlatform, Key, HOTKEY_PROFILES,
+    HOTKEY_PROFILE_PRESENTATION, HOTKEY_PROFILE_STANDARD,
+};
+pub use idle::IdleFade;
+pub use instance::{
+    decode_args, encode_args, fresh_start_flag, handoff, restarted, Handoff, HANDOFF_NOTIFICATION,
+    INSTANCE_MUTEX, RESTART_FLAG, RESTART_WAIT_SECS,
+};
+pub use kinematics::Kinematics;
+pub use lock::PresentationLock;
+pub use managed::{ManagedConfig, MANAGED_KEYS};
+pub use motion::reduce_motion;
+pub use onboarding::{Onboarding, OnboardingStep};
+pub use palette::{ColorPalette, BUILTIN_SWATCHES};
+pub use pen::{is_pen_extra_info, is_touch_extra_info, pen_scale, pen_tip_color};
+pub use permissions::{Permission, PermissionStatus};
+pub use placement::ScreenRect;
+pub use preferences::{
+    clamp_number, describe_issues, reset_requested, safe_mode_preferences, safe_mode_requested,
+    ConfigIssue, Preferences, PREFERENCES_FILE, STRING_KEYS,
+};
+pub use pulse::{pulse_phase, pulse_radius};
+pub use quick_adjust::{
+    current_look_profile, is_current_radius, look_profile_label, look_profile_values, radius_label,
+    resolve_look_profile, LOOK_PROFILES, LOOK_PROFILE_HIGH_CONTRAST, LOOK_PROFILE_STANDARD,
+};
+pub use rainbow::{rainbow_color, rainbow_phase};
+pub use ruler::{ruler_label, Ruler, RulerLine};
+pub use screen_frame::FrameColors;
+pub use script::{four_cc, script_event, ScriptArgument, SCRIPT_SUITE};
+pub use session::{SessionStore, WindowSession};
+pub use soak::{SoakAction, SoakConfig, SoakReport, SoakSample, SoakScript};
+pub use sync::{merge_synced, synced_copy, SyncConflict, SyncSide, SYNC_CONFLICTS_LOG};
+pub use timer_watchdog::TimerWatchdog;
+pub use touch::{TouchRing, Touches};
+pub use trail::{Trail, TrailPoint, TrailSegment};
+pub use tray_icon::{tray_icon_pixels, TrayIconState};
+pub use update::{check_due, parse_release, parse_version, update_status, Release, UpdateStatus};
+pub use url_scheme::{setting_event, url_events, UrlError, URL_SCHEME};
+pub use zoom::Zoom;
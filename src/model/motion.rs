@@ -0,0 +1,19 @@
+//! Reduce Motion support (pure Rust, no FFI).
+//!
+//! With motion reduced the highlight stays static: the laser trail, the
+//! speed-based opacity ramp and size scaling, the rainbow hue cycle and
+//! the pulse are off, the idle auto-hide and the show/hide toggle switch instantly and
+//! the zoom view jumps to the cursor instead of panning after it.
+
+use super::constants::*;
+
+/// Should motion be reduced for this mode, given the OS setting?
+///
+/// Unknown modes follow the system, like `MOTION_SYSTEM`.
+pub fn reduce_motion(mode: i32, system_reduced: bool) -> bool {
+    match mode {
+        MOTION_REDUCED => true,
+        MOTION_FULL => false,
+        _ => system_reduced,
+    }
+}
@@ -0,0 +1,82 @@
+//! First-run walkthrough: which steps to show and in what order.
+//!
+//! The walkthrough runs once, before the overlay first appears; finishing
+//! or skipping it sets `PREF_ONBOARDING_DONE`. The permission step is only
+//! shown where the OS asks for one (Accessibility on macOS).
+
+/// One page of the walkthrough.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnboardingStep {
+    /// What Lumbus does.
+    Welcome,
+    /// The hotkeys of the selected profile.
+    Hotkeys,
+    /// Why the input permission is needed, with a button asking for it.
+    Permission,
+    /// Size and color of the highlight.
+    Appearance,
+}
+
+impl OnboardingStep {
+    /// Translation key of the step's heading.
+    pub fn title(self) -> &'static str {
+        match self {
+            OnboardingStep::Welcome => "Welcome to Lumbus",
+            OnboardingStep::Hotkeys => "Hotkeys",
+            OnboardingStep::Permission => "Accessibility permission",
+            OnboardingStep::Appearance => "Size and color",
+        }
+    }
+}
+
+/// Position in the walkthrough.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Onboarding {
+    steps: Vec<OnboardingStep>,
+    current: usize,
+}
+
+impl Onboarding {
+    /// Walkthrough from the first step; `permission` adds the permission
+    /// step.
+    pub fn new(permission: bool) -> Self {
+        let mut steps = vec![OnboardingStep::Welcome, OnboardingStep::Hotkeys];
+        if permission {
+            steps.push(OnboardingStep::Permission);
+        }
+        steps.push(OnboardingStep::Appearance);
+        Self { steps, current: 0 }
+    }
+
+    /// Step on screen.
+    pub fn step(&self) -> OnboardingStep {
+        self.steps[self.current]
+    }
+
+    /// 1-based number of the step on screen and the number of steps.
+    pub fn position(&self) -> (usize, usize) {
+        (self.current + 1, self.steps.len())
+    }
+
+    pub fn is_first(&self) -> bool {
+        self.current == 0
+    }
+
+    pub fn is_last(&self) -> bool {
+        self.current + 1 == self.steps.len()
+    }
+
+    /// Move to the next step. Returns `false` on the last step.
+    pub fn advance(&mut self) -> bool {
+        if self.is_last() {
+            return false;
+        }
+        self.current += 1;
+        true
+    }
+
+    /// Move to the previous step, if any.
+    pub fn back(&mut self) {
+        self.current = self.current.saturating_sub(1);
+    }
+}
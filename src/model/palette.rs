@@ -0,0 +1,104 @@
+//! Saved color palette of the Settings window (pure Rust, no FFI).
+//!
+//! A row of swatches next to the stroke color: the built-in
+//! high-visibility colors first, then the colors the user saved, oldest
+//! first. Clicking a swatch applies it; saving more than
+//! `MAX_SAVED_COLORS` drops the oldest.
+//!
+//! Saved colors are persisted as a single compact string:
+//! `#RRGGBB[AA];#RRGGBB[AA];...`.
+
+use super::constants::MAX_SAVED_COLORS;
+use super::screen_frame::Rgba;
+use crate::{color_to_hex, parse_hex_color};
+
+/// Built-in swatches: colors that stand out on most screens.
+pub const BUILTIN_SWATCHES: [Rgba; 6] = [
+    (1.0, 0.92, 0.0, 1.0), // yellow
+    (1.0, 0.0, 1.0, 1.0),  // magenta
+    (0.0, 1.0, 1.0, 1.0),  // cyan
+    (0.2, 1.0, 0.0, 1.0),  // lime
+    (1.0, 0.5, 0.0, 1.0),  // orange
+    (1.0, 0.1, 0.1, 1.0),  // red
+];
+
+/// Colors saved by the user.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ColorPalette {
+    colors: Vec<Rgba>,
+}
+
+impl ColorPalette {
+    /// Creates an empty palette.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Saved colors, oldest first.
+    pub fn colors(&self) -> &[Rgba] {
+        &self.colors
+    }
+
+    /// Built-in swatches followed by the saved colors.
+    pub fn swatches(&self) -> impl Iterator<Item = Rgba> + '_ {
+        BUILTIN_SWATCHES
+            .into_iter()
+            .chain(self.colors.iter().copied())
+    }
+
+    /// Does the palette (built-in swatches included) offer `color`?
+    pub fn contains(&self, color: Rgba) -> bool {
+        let hex = to_hex(color);
+        self.swatches().any(|c| to_hex(c) == hex)
+    }
+
+    /// Saves `color` (components are clamped). Returns false if the
+    /// palette already offers it.
+    pub fn add(&mut self, color: Rgba) -> bool {
+        if self.contains(color) {
+            return false;
+        }
+        let (r, g, b, a) = color;
+        self.colors.push((
+            r.clamp(0.0, 1.0),
+            g.clamp(0.0, 1.0),
+            b.clamp(0.0, 1.0),
+            a.clamp(0.0, 1.0),
+        ));
+        if self.colors.len() > MAX_SAVED_COLORS {
+            self.colors.remove(0);
+        }
+        true
+    }
+
+    /// Removes the saved color at `index` (built-in swatches not counted).
+    pub fn remove(&mut self, index: usize) {
+        if index < self.colors.len() {
+            self.colors.remove(index);
+        }
+    }
+
+    /// Serializes the saved colors to their compact string form.
+    pub fn encode(&self) -> String {
+        self.colors
+            .iter()
+            .map(|&c| to_hex(c))
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+
+    /// Parses saved colors, skipping malformed and repeated entries.
+    pub fn decode(s: &str) -> Self {
+        let mut palette = Self::new();
+        for hex in s.split(';') {
+            if let Some(color) = parse_hex_color(hex) {
+                palette.add(color);
+            }
+        }
+        palette
+    }
+}
+
+fn to_hex((r, g, b, a): Rgba) -> String {
+    color_to_hex(r, g, b, a)
+}
@@ -0,0 +1,48 @@
+//! Pen and tablet input (pure Rust, no FFI).
+//!
+//! While a pen drives the pointer, the highlight grows with the pen's
+//! pressure, from its normal size while the pen hovers up to
+//! `PEN_MAX_SCALE` times it pressed all the way, and a crosshair marks
+//! the tip, in black or white against the highlight. A mouse or
+//! trackpad moving the pointer again ends it.
+
+use super::constants::PEN_MAX_SCALE;
+use super::contrast::halo_color;
+use super::screen_frame::Rgba;
+
+/// Signature Windows puts in the extra info of the mouse messages it
+/// synthesizes from pen and touch input (`MI_WP_SIGNATURE`).
+const PEN_SIGNATURE: usize = 0xFF51_5700;
+const PEN_SIGNATURE_MASK: usize = 0xFFFF_FF00;
+/// Set for touch, clear for pen.
+const TOUCH_FLAG: usize = 0x80;
+
+/// Radius multiplier at `pressure` [0.0, 1.0] (out-of-range values are
+/// clamped, NaN counts as no pressure).
+pub fn pen_scale(pressure: f64) -> f64 {
+    let pressure = if pressure.is_nan() {
+        0.0
+    } else {
+        pressure.clamp(0.0, 1.0)
+    };
+    1.0 + (PEN_MAX_SCALE - 1.0) * pressure
+}
+
+/// Color of the tip crosshair over a highlight of `stroke`: black or
+/// white, whichever contrasts more, as opaque as the stroke.
+pub fn pen_tip_color(stroke: Rgba) -> Rgba {
+    let (r, g, b) = halo_color(stroke.0, stroke.1, stroke.2);
+    (r, g, b, stroke.3)
+}
+
+/// Does the extra info of a Windows mouse message (`dwExtraInfo` of the
+/// low-level hook) say a pen sent it?
+pub fn is_pen_extra_info(extra_info: usize) -> bool {
+    extra_info & PEN_SIGNATURE_MASK == PEN_SIGNATURE && extra_info & TOUCH_FLAG == 0
+}
+
+/// Does the extra info of a Windows mouse message say a touchscreen
+/// sent it?
+pub fn is_touch_extra_info(extra_info: usize) -> bool {
+    extra_info & PEN_SIGNATURE_MASK == PEN_SIGNATURE && extra_info & TOUCH_FLAG != 0
+}
@@ -0,0 +1,86 @@
+//! OS permissions and the features that depend on them.
+//!
+//! Instead of half working without a permission (hotkeys firing but no
+//! click letters, a zoom or color sample showing only the desktop), the
+//! features that need it are turned off until it is granted. The Settings
+//! window shows the status of each permission the OS asks for (macOS only
+//! for now).
+
+/// A permission granted by the user in the OS settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    /// Watch the mouse while other apps are in front (click letters).
+    Accessibility,
+    /// Capture the screen (zoom-follow, color sampler).
+    ScreenRecording,
+}
+
+impl Permission {
+    /// Every permission, in the order the Settings window lists them.
+    pub const ALL: [Permission; 2] = [Permission::Accessibility, Permission::ScreenRecording];
+
+    /// Translation key of the permission's name.
+    pub fn label(self) -> &'static str {
+        match self {
+            Permission::Accessibility => "Accessibility",
+            Permission::ScreenRecording => "Screen Recording",
+        }
+    }
+
+    /// Translation key of the feature turned off while it is missing.
+    pub fn feature(self) -> &'static str {
+        match self {
+            Permission::Accessibility => "Click letters",
+            Permission::ScreenRecording => "Zoom and color sampler",
+        }
+    }
+}
+
+/// Which permissions are granted. Everything is on until checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PermissionStatus {
+    pub accessibility: bool,
+    pub screen_recording: bool,
+}
+
+impl Default for PermissionStatus {
+    fn default() -> Self {
+        Self {
+            accessibility: true,
+            screen_recording: true,
+        }
+    }
+}
+
+impl PermissionStatus {
+    /// Is `permission` granted?
+    pub fn granted(&self, permission: Permission) -> bool {
+        match permission {
+            Permission::Accessibility => self.accessibility,
+            Permission::ScreenRecording => self.screen_recording,
+        }
+    }
+
+    /// Permissions not granted, in `Permission::ALL` order.
+    pub fn missing(&self) -> Vec<Permission> {
+        Permission::ALL
+            .into_iter()
+            .filter(|p| !self.granted(*p))
+            .collect()
+    }
+
+    /// Show the L/R letters on clicks?
+    pub fn click_letters(&self) -> bool {
+        self.accessibility
+    }
+
+    /// Allow zooming in?
+    pub fn zoom(&self) -> bool {
+        self.screen_recording
+    }
+
+    /// Allow sampling screen colors?
+    pub fn color_sampler(&self) -> bool {
+        self.screen_recording
+    }
+}
@@ -0,0 +1,76 @@
+//! Placement of movable auxiliary overlays (pure Rust, no FFI).
+//!
+//! Geometry helpers used when the user drags an auxiliary overlay:
+//! keeping it on its display, snapping it to nearby screen edges and
+//! naming displays so each one can remember its own position.
+
+use super::constants::OVERLAY_SNAP_DISTANCE;
+
+/// Axis-aligned rectangle in global screen coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ScreenRect {
+    pub x: f64,
+    pub y: f64,
+    pub w: f64,
+    pub h: f64,
+}
+
+impl ScreenRect {
+    pub fn new(x: f64, y: f64, w: f64, h: f64) -> Self {
+        Self { x, y, w, h }
+    }
+
+    /// Returns true if the point lies inside the rectangle.
+    pub fn contains(&self, px: f64, py: f64) -> bool {
+        px >= self.x && px < self.x + self.w && py >= self.y && py < self.y + self.h
+    }
+}
+
+/// Keeps a window of `size` fully inside `screen`.
+///
+/// Works for both top-left (Windows) and bottom-left (macOS) origins.
+/// Windows larger than the screen are aligned to the screen origin.
+pub fn clamp_to_screen(origin: (f64, f64), size: (f64, f64), screen: &ScreenRect) -> (f64, f64) {
+    let clamp_axis = |v: f64, len: f64, lo: f64, span: f64| {
+        let hi = lo + (span - len).max(0.0);
+        v.clamp(lo, hi)
+    };
+    (
+        clamp_axis(origin.0, size.0, screen.x, screen.w),
+        clamp_axis(origin.1, size.1, screen.y, screen.h),
+    )
+}
+
+/// Clamps a window to `screen` and snaps each side within
+/// `OVERLAY_SNAP_DISTANCE` pixels of a screen edge onto that edge.
+pub fn snap_to_edges(origin: (f64, f64), size: (f64, f64), screen: &ScreenRect) -> (f64, f64) {
+    let (x, y) = clamp_to_screen(origin, size, screen);
+    let snap_axis = |v: f64, len: f64, lo: f64, span: f64| {
+        let hi = lo + span - len;
+        if (v - lo).abs() <= OVERLAY_SNAP_DISTANCE {
+            lo
+        } else if (hi - v).abs() <= OVERLAY_SNAP_DISTANCE {
+            hi.max(lo)
+        } else {
+            v
+        }
+    };
+    (
+        snap_axis(x, size.0, screen.x, screen.w),
+        snap_axis(y, size.1, screen.y, screen.h),
+    )
+}
+
+/// Stable identifier of a display, derived from its frame.
+///
+/// Used as the per-display key of saved overlay positions, e.g.
+/// `-1920_0_1920_1080`. Only digits, `-` and `_` are produced.
+pub fn display_key(screen: &ScreenRect) -> String {
+    format!(
+        "{}_{}_{}_{}",
+        screen.x.round() as i64,
+        screen.y.round() as i64,
+        screen.w.round() as i64,
+        screen.h.round() as i64
+    )
+}
@@ -0,0 +1,730 @@
+//! User preferences file (pure Rust, no FFI).
+//!
+//! Both platforms persist the user's settings as one TOML file,
+//! `config.toml`, in the per-user application data directory:
+//!
+//! - macOS: `~/Library/Application Support/Lumbus/config.toml`
+//! - Windows: `%APPDATA%/Lumbus/config.toml`
+//!
+//! This module owns the file format and the mapping between preference
+//! keys, the file and [`OverlayState`]. Locating, caching and writing the
+//! file, and applying the admin-managed layer, is up to the platform
+//! storage modules.
+//!
+//! Files edited by hand are checked as they are read: settings with the
+//! wrong type or an unknown name are ignored and out-of-range values are
+//! replaced, each reported as a [`ConfigIssue`] for the platform to show.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::app_state::OverlayState;
+use super::constants::*;
+use super::hotkeys::HOTKEY_PROFILE_STANDARD;
+use crate::i18n::{available_languages, lang_from_tag, lang_tag, normalize_locale};
+
+/// File name of the user preferences file.
+pub const PREFERENCES_FILE: &str = "config.toml";
+
+/// Preference keys stored as strings rather than numbers.
+pub const STRING_KEYS: &[&str] = &[
+    PREF_SCREEN_FRAME_COLORS,
+    PREF_COLOR_PALETTE,
+    PREF_CLICK_LETTER_LEFT,
+    PREF_CLICK_LETTER_RIGHT,
+    PREF_LETTER_FONT,
+    PREF_SESSION,
+    PREF_HOTKEY_PROFILE,
+    PREF_HOTKEY_OVERRIDES,
+    PREF_SYNC_FOLDER,
+    PREF_CONTROL_TOKEN,
+];
+
+/// Allowed values of a numeric setting.
+#[derive(Clone, Copy)]
+enum Limit {
+    /// Any value in the range, inclusive; others are clamped.
+    Range(f64, f64),
+    /// One of the listed values; others fall back to the default.
+    OneOf(&'static [f64]),
+}
+
+/// Limits of the numeric settings, by file key.
+const LIMITS: &[(&str, Limit)] = &[
+    ("radius", Limit::Range(MIN_RADIUS, MAX_RADIUS)),
+    ("border_width", Limit::Range(MIN_BORDER, MAX_BORDER)),
+    ("stroke_r", Limit::Range(0.0, 1.0)),
+    ("stroke_g", Limit::Range(0.0, 1.0)),
+    ("stroke_b", Limit::Range(0.0, 1.0)),
+    ("stroke_a", Limit::Range(0.0, 1.0)),
+    (
+        "fill_transparency_pct",
+        Limit::Range(MIN_TRANSPARENCY, MAX_TRANSPARENCY),
+    ),
+    (
+        "click_burst_ms",
+        Limit::Range(MIN_CLICK_BURST_MS, MAX_CLICK_BURST_MS),
+    ),
+    ("click_hold_ms", Limit::Range(0.0, MAX_CLICK_HOLD_MS)),
+    (
+        "hold_ring_ms",
+        Limit::Range(MIN_HOLD_RING_MS, MAX_HOLD_RING_MS),
+    ),
+    (
+        "idle_hide_secs",
+        Limit::Range(MIN_IDLE_HIDE_SECS, MAX_IDLE_HIDE_SECS),
+    ),
+    (
+        "rainbow_speed",
+        Limit::Range(MIN_RAINBOW_SPEED, MAX_RAINBOW_SPEED),
+    ),
+    (
+        "pulse_amplitude",
+        Limit::Range(MIN_PULSE_AMPLITUDE, MAX_PULSE_AMPLITUDE),
+    ),
+    (
+        "pulse_period_ms",
+        Limit::Range(MIN_PULSE_PERIOD_MS, MAX_PULSE_PERIOD_MS),
+    ),
+    (
+        "transition_ms",
+        Limit::Range(MIN_TRANSITION_MS, MAX_TRANSITION_MS),
+    ),
+    (
+        "grid_spacing",
+        Limit::Range(MIN_GRID_SPACING, MAX_GRID_SPACING),
+    ),
+    (
+        "grid_subdivisions",
+        Limit::Range(MIN_GRID_SUBDIVISIONS, MAX_GRID_SUBDIVISIONS),
+    ),
+    ("grid_r", Limit::Range(0.0, 1.0)),
+    ("grid_g", Limit::Range(0.0, 1.0)),
+    ("grid_b", Limit::Range(0.0, 1.0)),
+    ("grid_a", Limit::Range(0.0, 1.0)),
+    ("highlight_style", Limit::OneOf(&[0.0, 1.0])),
+    ("motion", Limit::OneOf(&[0.0, 1.0, 2.0])),
+    ("glow", Limit::OneOf(&[0.0, 1.0, 2.0])),
+    ("click_icon", Limit::OneOf(&[0.0, 1.0, 2.0, 3.0])),
+    (
+        "letter_weight",
+        Limit::OneOf(&[300.0, 400.0, 500.0, 700.0, 900.0]),
+    ),
+    (
+        "control_port",
+        Limit::Range(MIN_CONTROL_PORT, MAX_CONTROL_PORT),
+    ),
+];
+
+/// Problem found while reading a preferences file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigIssue {
+    /// The file is not valid TOML; none of its settings were used.
+    Syntax { line: usize, message: String },
+    /// No such setting; it was ignored.
+    UnknownKey { key: String },
+    /// The value has the wrong type; the setting was ignored.
+    WrongType { key: String, expected: &'static str },
+    /// The value is out of range; `used` was used instead.
+    OutOfRange {
+        key: String,
+        value: String,
+        used: String,
+    },
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax { line, message } => {
+                write!(f, "line {line}: {message}; all settings ignored")
+            }
+            Self::UnknownKey { key } => write!(f, "`{key}`: unknown setting, ignored"),
+            Self::WrongType { key, expected } => {
+                write!(f, "`{key}`: expected {expected}, ignored")
+            }
+            Self::OutOfRange { key, value, used } => {
+                write!(f, "`{key}` = {value} is out of range, using {used}")
+            }
+        }
+    }
+}
+
+/// One line per issue, for showing them to the user.
+pub fn describe_issues(issues: &[ConfigIssue]) -> String {
+    issues
+        .iter()
+        .map(|issue| format!("• {issue}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Contents of the user preferences file.
+///
+/// Missing fields fall back to defaults so files written by older
+/// versions keep loading as new settings are added.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct Preferences {
+    pub radius: f64,
+    pub border_width: f64,
+    pub stroke_r: f64,
+    pub stroke_g: f64,
+    pub stroke_b: f64,
+    pub stroke_a: f64,
+    pub fill_transparency_pct: f64,
+    /// UI language as a locale tag (e.g. `es`); empty follows the system.
+    pub lang: String,
+    pub highlight_style: i32,
+    pub screen_frame: bool,
+    pub speed_fade: bool,
+    pub speed_size: bool,
+    pub click_burst: bool,
+    pub click_burst_ms: f64,
+    pub idle_hide: bool,
+    pub idle_hide_secs: f64,
+    pub click_hold_ms: f64,
+    pub hold_ring: bool,
+    pub hold_ring_ms: f64,
+    pub motion: i32,
+    pub halo: bool,
+    pub rainbow: bool,
+    pub rainbow_speed: f64,
+    pub glow: i32,
+    pub pulse: bool,
+    pub pulse_amplitude: f64,
+    pub pulse_period_ms: f64,
+    pub transition_ms: f64,
+    pub grid_spacing: f64,
+    pub grid_subdivisions: i32,
+    pub grid_r: f64,
+    pub grid_g: f64,
+    pub grid_b: f64,
+    pub grid_a: f64,
+    pub letter_weight: i32,
+    pub click_icon: i32,
+    /// Per-display recording-frame colors (see `model::screen_frame`).
+    pub screen_frame_colors: String,
+    /// Colors saved to the Settings palette (see `model::palette`).
+    pub color_palette: String,
+    /// Custom text drawn on a left click (empty = language letter).
+    pub click_letter_left: String,
+    /// Custom text drawn on a right click (empty = language letter).
+    pub click_letter_right: String,
+    /// Font family of the click letters (empty = system default).
+    pub letter_font: String,
+    /// Auxiliary-window session (see `model::session`).
+    pub session: String,
+    /// Selected hotkey profile (see `model::hotkeys`).
+    pub hotkey_profile: String,
+    /// Hotkeys rebound on top of the profile (see `model::hotkeys`).
+    pub hotkey_overrides: String,
+    /// Folder holding the synced copy (see `model::sync`); empty when
+    /// sync is off.
+    pub sync_folder: String,
+    /// Serve the local control endpoint (see `control`).
+    pub control_server: bool,
+    /// TCP port of the control endpoint on 127.0.0.1.
+    pub control_port: i32,
+    /// Bearer token of the control endpoint; generated when first needed.
+    pub control_token: String,
+    /// Check the release feed for a new version once a day.
+    pub update_check: bool,
+    /// When updates were last checked, in seconds since the Unix epoch.
+    pub last_update_check: f64,
+    /// The first-run walkthrough was finished or skipped.
+    pub onboarding_done: bool,
+    /// Show a notification when a hotkey toggles the highlight.
+    pub toggle_notification: bool,
+    /// Keep the overlay out of screen captures.
+    pub exclude_from_capture: bool,
+    /// Ask before quitting.
+    pub confirm_quit: bool,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            radius: DEFAULT_DIAMETER / 2.0,
+            border_width: DEFAULT_BORDER_WIDTH,
+            stroke_r: DEFAULT_COLOR.0,
+            stroke_g: DEFAULT_COLOR.1,
+            stroke_b: DEFAULT_COLOR.2,
+            stroke_a: DEFAULT_COLOR.3,
+            fill_transparency_pct: DEFAULT_FILL_TRANSPARENCY_PCT,
+            lang: String::new(),
+            highlight_style: HIGHLIGHT_STYLE_CIRCLE,
+            screen_frame: false,
+            speed_fade: false,
+            speed_size: false,
+            click_burst: false,
+            click_burst_ms: DEFAULT_CLICK_BURST_MS,
+            idle_hide: false,
+            idle_hide_secs: DEFAULT_IDLE_HIDE_SECS,
+            click_hold_ms: DEFAULT_CLICK_HOLD_MS,
+            hold_ring: false,
+            hold_ring_ms: DEFAULT_HOLD_RING_MS,
+            motion: MOTION_SYSTEM,
+            halo: false,
+            rainbow: false,
+            rainbow_speed: DEFAULT_RAINBOW_SPEED,
+            glow: GLOW_OFF,
+            pulse: false,
+            pulse_amplitude: DEFAULT_PULSE_AMPLITUDE,
+            pulse_period_ms: DEFAULT_PULSE_PERIOD_MS,
+            transition_ms: DEFAULT_TRANSITION_MS,
+            grid_spacing: DEFAULT_GRID_SPACING,
+            grid_subdivisions: DEFAULT_GRID_SUBDIVISIONS,
+            grid_r: DEFAULT_GRID_COLOR.0,
+            grid_g: DEFAULT_GRID_COLOR.1,
+            grid_b: DEFAULT_GRID_COLOR.2,
+            grid_a: DEFAULT_GRID_COLOR.3,
+            letter_weight: LETTER_WEIGHT_BOLD,
+            click_icon: CLICK_ICON_NONE,
+            screen_frame_colors: String::new(),
+            color_palette: String::new(),
+            click_letter_left: String::new(),
+            click_letter_right: String::new(),
+            letter_font: String::new(),
+            session: String::new(),
+            hotkey_profile: HOTKEY_PROFILE_STANDARD.to_string(),
+            hotkey_overrides: String::new(),
+            sync_folder: String::new(),
+            control_server: false,
+            control_port: DEFAULT_CONTROL_PORT,
+            control_token: String::new(),
+            update_check: true,
+            last_update_check: 0.0,
+            onboarding_done: false,
+            toggle_notification: false,
+            exclude_from_capture: false,
+            confirm_quit: true,
+        }
+    }
+}
+
+impl Preferences {
+    /// Read a preferences file over `base`, keeping `base` for missing
+    /// fields. An invalid file leaves `base` unchanged.
+    pub fn decode(contents: &str, base: Self) -> Self {
+        Self::load(contents, base).0
+    }
+
+    /// Like [`decode`](Self::decode), also returning the problems found.
+    ///
+    /// Unknown settings and settings with the wrong type are skipped, and
+    /// out-of-range values are clamped (or reset to the default when they
+    /// are not a valid choice). Other settings in the file are still used.
+    pub fn load(contents: &str, base: Self) -> (Self, Vec<ConfigIssue>) {
+        let mut issues = Vec::new();
+        let user = match contents.parse::<toml::Table>() {
+            Ok(user) => user,
+            Err(e) => {
+                let start = e.span().map_or(0, |span| span.start).min(contents.len());
+                issues.push(ConfigIssue::Syntax {
+                    line: contents[..start].matches('\n').count() + 1,
+                    message: e.message().trim().to_string(),
+                });
+                return (base, issues);
+            }
+        };
+        let (Ok(toml::Value::Table(mut merged)), Ok(toml::Value::Table(defaults))) = (
+            toml::Value::try_from(&base),
+            toml::Value::try_from(Self::default()),
+        ) else {
+            return (base, issues);
+        };
+        for (key, mut value) in user {
+            // Older files store the language as an index into the language
+            // list of the machine that wrote them
+            if let (PREF_LANG, toml::Value::Integer(index)) = (key.as_str(), &value) {
+                let lang = i32::try_from(*index).unwrap_or(LANG_SYSTEM);
+                value = toml::Value::String(lang_tag(lang));
+            }
+            let Some(default) = defaults.get(&key) else {
+                issues.push(ConfigIssue::UnknownKey { key });
+                continue;
+            };
+            match checked_value(&key, value, default) {
+                Ok((value, issue)) => {
+                    issues.extend(issue);
+                    merged.insert(key, value);
+                }
+                Err(issue) => issues.push(issue),
+            }
+        }
+        let prefs = toml::Value::Table(merged).try_into().unwrap_or(base);
+        (prefs, issues)
+    }
+
+    /// Contents of the preferences file.
+    pub fn encode(&self) -> String {
+        toml::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Numeric value of a preference key (booleans as 0/1).
+    pub fn number(&self, key: &str) -> Option<f64> {
+        Some(match key {
+            PREF_RADIUS => self.radius,
+            PREF_BORDER => self.border_width,
+            PREF_STROKE_R => self.stroke_r,
+            PREF_STROKE_G => self.stroke_g,
+            PREF_STROKE_B => self.stroke_b,
+            PREF_STROKE_A => self.stroke_a,
+            PREF_FILL_TRANSPARENCY => self.fill_transparency_pct,
+            PREF_CLICK_BURST_MS => self.click_burst_ms,
+            PREF_IDLE_HIDE_SECS => self.idle_hide_secs,
+            PREF_CLICK_HOLD_MS => self.click_hold_ms,
+            PREF_HOLD_RING_MS => self.hold_ring_ms,
+            PREF_LANG => lang_from_tag(&self.lang) as f64,
+            PREF_HIGHLIGHT_STYLE => self.highlight_style as f64,
+            PREF_SCREEN_FRAME => self.screen_frame as i32 as f64,
+            PREF_SPEED_FADE => self.speed_fade as i32 as f64,
+            PREF_SPEED_SIZE => self.speed_size as i32 as f64,
+            PREF_CLICK_BURST => self.click_burst as i32 as f64,
+            PREF_IDLE_HIDE => self.idle_hide as i32 as f64,
+            PREF_HOLD_RING => self.hold_ring as i32 as f64,
+            PREF_MOTION => self.motion as f64,
+            PREF_HALO => self.halo as i32 as f64,
+            PREF_RAINBOW => self.rainbow as i32 as f64,
+            PREF_RAINBOW_SPEED => self.rainbow_speed,
+            PREF_GLOW => self.glow as f64,
+            PREF_LETTER_WEIGHT => self.letter_weight as f64,
+            PREF_CLICK_ICON => self.click_icon as f64,
+            PREF_PULSE => self.pulse as i32 as f64,
+            PREF_PULSE_AMPLITUDE => self.pulse_amplitude,
+            PREF_PULSE_PERIOD_MS => self.pulse_period_ms,
+            PREF_TRANSITION_MS => self.transition_ms,
+            PREF_GRID_SPACING => self.grid_spacing,
+            PREF_GRID_SUBDIVISIONS => self.grid_subdivisions as f64,
+            PREF_GRID_R => self.grid_r,
+            PREF_GRID_G => self.grid_g,
+            PREF_GRID_B => self.grid_b,
+            PREF_GRID_A => self.grid_a,
+            PREF_CONTROL_SERVER => self.control_server as i32 as f64,
+            PREF_CONTROL_PORT => self.control_port as f64,
+            PREF_UPDATE_CHECK => self.update_check as i32 as f64,
+            PREF_LAST_UPDATE_CHECK => self.last_update_check,
+            PREF_ONBOARDING_DONE => self.onboarding_done as i32 as f64,
+            PREF_TOGGLE_NOTIFICATION => self.toggle_notification as i32 as f64,
+            PREF_EXCLUDE_FROM_CAPTURE => self.exclude_from_capture as i32 as f64,
+            PREF_CONFIRM_QUIT => self.confirm_quit as i32 as f64,
+            _ => return None,
+        })
+    }
+
+    /// Set a numeric preference key. Returns false for unknown keys.
+    pub fn set_number(&mut self, key: &str, val: f64) -> bool {
+        match key {
+            PREF_RADIUS => self.radius = val,
+            PREF_BORDER => self.border_width = val,
+            PREF_STROKE_R => self.stroke_r = val,
+            PREF_STROKE_G => self.stroke_g = val,
+            PREF_STROKE_B => self.stroke_b = val,
+            PREF_STROKE_A => self.stroke_a = val,
+            PREF_FILL_TRANSPARENCY => self.fill_transparency_pct = val,
+            PREF_CLICK_BURST_MS => self.click_burst_ms = val,
+            PREF_IDLE_HIDE_SECS => self.idle_hide_secs = val,
+            PREF_CLICK_HOLD_MS => self.click_hold_ms = val,
+            PREF_HOLD_RING_MS => self.hold_ring_ms = val,
+            PREF_LANG => self.set_lang(val.round() as i32),
+            PREF_HIGHLIGHT_STYLE => self.highlight_style = val.round() as i32,
+            PREF_SCREEN_FRAME => self.screen_frame = val != 0.0,
+            PREF_SPEED_FADE => self.speed_fade = val != 0.0,
+            PREF_SPEED_SIZE => self.speed_size = val != 0.0,
+            PREF_CLICK_BURST => self.click_burst = val != 0.0,
+            PREF_IDLE_HIDE => self.idle_hide = val != 0.0,
+            PREF_HOLD_RING => self.hold_ring = val != 0.0,
+            PREF_MOTION => self.motion = val.round() as i32,
+            PREF_HALO => self.halo = val != 0.0,
+            PREF_RAINBOW => self.rainbow = val != 0.0,
+            PREF_RAINBOW_SPEED => self.rainbow_speed = val,
+            PREF_GLOW => self.glow = val.round() as i32,
+            PREF_LETTER_WEIGHT => self.letter_weight = val.round() as i32,
+            PREF_CLICK_ICON => self.click_icon = val.round() as i32,
+            PREF_PULSE => self.pulse = val != 0.0,
+            PREF_PULSE_AMPLITUDE => self.pulse_amplitude = val,
+            PREF_PULSE_PERIOD_MS => self.pulse_period_ms = val,
+            PREF_TRANSITION_MS => self.transition_ms = val,
+            PREF_GRID_SPACING => self.grid_spacing = val,
+            PREF_GRID_SUBDIVISIONS => self.grid_subdivisions = val.round() as i32,
+            PREF_GRID_R => self.grid_r = val,
+            PREF_GRID_G => self.grid_g = val,
+            PREF_GRID_B => self.grid_b = val,
+            PREF_GRID_A => self.grid_a = val,
+            PREF_CONTROL_SERVER => self.control_server = val != 0.0,
+            PREF_CONTROL_PORT => self.control_port = val.round() as i32,
+            PREF_UPDATE_CHECK => self.update_check = val != 0.0,
+            PREF_LAST_UPDATE_CHECK => self.last_update_check = val,
+            PREF_ONBOARDING_DONE => self.onboarding_done = val != 0.0,
+            PREF_TOGGLE_NOTIFICATION => self.toggle_notification = val != 0.0,
+            PREF_EXCLUDE_FROM_CAPTURE => self.exclude_from_capture = val != 0.0,
+            PREF_CONFIRM_QUIT => self.confirm_quit = val != 0.0,
+            _ => return false,
+        }
+        true
+    }
+
+    /// String value of a preference key.
+    pub fn string(&self, key: &str) -> Option<&str> {
+        match key {
+            PREF_SCREEN_FRAME_COLORS => Some(&self.screen_frame_colors),
+            PREF_COLOR_PALETTE => Some(&self.color_palette),
+            PREF_CLICK_LETTER_LEFT => Some(&self.click_letter_left),
+            PREF_CLICK_LETTER_RIGHT => Some(&self.click_letter_right),
+            PREF_LETTER_FONT => Some(&self.letter_font),
+            PREF_LANG => Some(&self.lang),
+            PREF_SESSION => Some(&self.session),
+            PREF_HOTKEY_PROFILE => Some(&self.hotkey_profile),
+            PREF_HOTKEY_OVERRIDES => Some(&self.hotkey_overrides),
+            PREF_SYNC_FOLDER => Some(&self.sync_folder),
+            PREF_CONTROL_TOKEN => Some(&self.control_token),
+            _ => None,
+        }
+    }
+
+    /// Set a string preference key. Returns false for unknown keys.
+    pub fn set_string(&mut self, key: &str, val: &str) -> bool {
+        match key {
+            PREF_SCREEN_FRAME_COLORS => self.screen_frame_colors = val.to_string(),
+            PREF_COLOR_PALETTE => self.color_palette = val.to_string(),
+            PREF_CLICK_LETTER_LEFT => self.click_letter_left = val.to_string(),
+            PREF_CLICK_LETTER_RIGHT => self.click_letter_right = val.to_string(),
+            PREF_LETTER_FONT => self.letter_font = val.to_string(),
+            PREF_LANG => self.lang = val.to_string(),
+            PREF_SESSION => self.session = val.to_string(),
+            PREF_HOTKEY_PROFILE => self.hotkey_profile = val.to_string(),
+            PREF_HOTKEY_OVERRIDES => self.hotkey_overrides = val.to_string(),
+            PREF_SYNC_FOLDER => self.sync_folder = val.to_string(),
+            PREF_CONTROL_TOKEN => self.control_token = val.to_string(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Overlay state described by these preferences, validated.
+    pub fn to_state(&self) -> OverlayState {
+        let mut state = OverlayState {
+            radius: self.radius,
+            border_width: self.border_width,
+            stroke_r: self.stroke_r,
+            stroke_g: self.stroke_g,
+            stroke_b: self.stroke_b,
+            stroke_a: self.stroke_a,
+            fill_transparency_pct: self.fill_transparency_pct,
+            lang: lang_from_tag(&self.lang),
+            highlight_style: self.highlight_style,
+            screen_frame: self.screen_frame,
+            speed_fade: self.speed_fade,
+            speed_size: self.speed_size,
+            click_burst: self.click_burst,
+            click_burst_ms: self.click_burst_ms,
+            idle_hide: self.idle_hide,
+            idle_hide_secs: self.idle_hide_secs,
+            click_hold_ms: self.click_hold_ms,
+            hold_ring: self.hold_ring,
+            hold_ring_ms: self.hold_ring_ms,
+            motion: self.motion,
+            halo: self.halo,
+            rainbow: self.rainbow,
+            rainbow_speed: self.rainbow_speed,
+            glow: self.glow,
+            pulse: self.pulse,
+            pulse_amplitude: self.pulse_amplitude,
+            pulse_period_ms: self.pulse_period_ms,
+            transition_ms: self.transition_ms,
+            grid_spacing: self.grid_spacing,
+            grid_subdivisions: self.grid_subdivisions,
+            grid_r: self.grid_r,
+            grid_g: self.grid_g,
+            grid_b: self.grid_b,
+            grid_a: self.grid_a,
+            click_letter_left: self.click_letter_left.clone(),
+            click_letter_right: self.click_letter_right.clone(),
+            letter_font: self.letter_font.clone(),
+            letter_weight: self.letter_weight,
+            click_icon: self.click_icon,
+            ..OverlayState::default()
+        };
+        state.validate();
+        state
+    }
+
+    /// Store the persisted settings of `state`, keeping the string ones
+    /// that are not part of it.
+    pub fn set_state(&mut self, state: &OverlayState) {
+        self.radius = state.radius;
+        self.border_width = state.border_width;
+        self.stroke_r = state.stroke_r;
+        self.stroke_g = state.stroke_g;
+        self.stroke_b = state.stroke_b;
+        self.stroke_a = state.stroke_a;
+        self.fill_transparency_pct = state.fill_transparency_pct;
+        self.set_lang(state.lang);
+        self.highlight_style = state.highlight_style;
+        self.screen_frame = state.screen_frame;
+        self.speed_fade = state.speed_fade;
+        self.speed_size = state.speed_size;
+        self.click_burst = state.click_burst;
+        self.click_burst_ms = state.click_burst_ms;
+        self.idle_hide = state.idle_hide;
+        self.idle_hide_secs = state.idle_hide_secs;
+        self.click_hold_ms = state.click_hold_ms;
+        self.hold_ring = state.hold_ring;
+        self.hold_ring_ms = state.hold_ring_ms;
+        self.motion = state.motion;
+        self.halo = state.halo;
+        self.rainbow = state.rainbow;
+        self.rainbow_speed = state.rainbow_speed;
+        self.glow = state.glow;
+        self.pulse = state.pulse;
+        self.pulse_amplitude = state.pulse_amplitude;
+        self.pulse_period_ms = state.pulse_period_ms;
+        self.transition_ms = state.transition_ms;
+        self.grid_spacing = state.grid_spacing;
+        self.grid_subdivisions = state.grid_subdivisions;
+        self.grid_r = state.grid_r;
+        self.grid_g = state.grid_g;
+        self.grid_b = state.grid_b;
+        self.grid_a = state.grid_a;
+        self.click_letter_left = state.click_letter_left.clone();
+        self.click_letter_right = state.click_letter_right.clone();
+        self.letter_font = state.letter_font.clone();
+        self.letter_weight = state.letter_weight;
+        self.click_icon = state.click_icon;
+    }
+
+    /// Store the language preference `lang` as its locale tag.
+    ///
+    /// A tag this machine has no resources for reads as `LANG_SYSTEM`; it
+    /// is kept while the preference stays that, so it is not lost for the
+    /// machines that do have them.
+    fn set_lang(&mut self, lang: i32) {
+        if lang_from_tag(&self.lang) != lang {
+            self.lang = lang_tag(lang);
+        }
+    }
+
+    /// Put the settings of the Settings window (the overlay settings and
+    /// the recording-frame colors) back to their value in `defaults`.
+    /// Saved colors, session, hotkey profile and rebinds, sync, control
+    /// endpoint, update, toggle notification, screen capture and quit
+    /// confirmation settings are kept.
+    pub fn restore_defaults(&mut self, defaults: &Self) {
+        self.set_state(&defaults.to_state());
+        self.screen_frame_colors = defaults.screen_frame_colors.clone();
+    }
+}
+
+/// Is `--reset` among the command-line arguments?
+pub fn reset_requested<I, S>(args: I) -> bool
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    args.into_iter().any(|arg| arg.as_ref() == RESET_FLAG)
+}
+
+/// Is `--safe-mode` among the command-line arguments?
+pub fn safe_mode_requested<I, S>(args: I) -> bool
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    args.into_iter().any(|arg| arg.as_ref() == SAFE_MODE_FLAG)
+}
+
+/// Settings of a safe-mode session: `defaults`, without the first-run
+/// walkthrough.
+pub fn safe_mode_preferences(defaults: Preferences) -> Preferences {
+    Preferences {
+        onboarding_done: true,
+        ..defaults
+    }
+}
+
+/// Clamp a value for a numeric preference key to the setting's limits.
+///
+/// Values that are not a valid choice (or not finite) are replaced by the
+/// default. Keys without limits are returned unchanged.
+pub fn clamp_number(key: &str, val: f64) -> f64 {
+    // Preference keys are camelCase, file keys snake_case
+    let mut file_key = String::with_capacity(key.len() + 2);
+    for c in key.chars() {
+        if c.is_ascii_uppercase() {
+            file_key.push('_');
+        }
+        file_key.push(c.to_ascii_lowercase());
+    }
+    let fallback = || Preferences::default().number(key).unwrap_or(0.0);
+    match LIMITS.iter().find(|(k, _)| *k == file_key).map(|(_, l)| *l) {
+        _ if !val.is_finite() => fallback(),
+        Some(Limit::Range(min, max)) => val.clamp(min, max),
+        Some(Limit::OneOf(values)) if !values.contains(&val) => fallback(),
+        _ => val,
+    }
+}
+
+/// Check a setting read from the file against the type of its default and
+/// its limits.
+///
+/// Returns the value to use, with an issue if it had to be replaced, or
+/// the issue that made the setting unusable.
+fn checked_value(
+    key: &str,
+    value: toml::Value,
+    default: &toml::Value,
+) -> Result<(toml::Value, Option<ConfigIssue>), ConfigIssue> {
+    let wrong_type = |expected| ConfigIssue::WrongType {
+        key: key.to_string(),
+        expected,
+    };
+    let number = match (default, &value) {
+        (toml::Value::Float(_), toml::Value::Float(v)) => *v,
+        (toml::Value::Float(_), toml::Value::Integer(v)) => *v as f64,
+        (toml::Value::Float(_), _) => return Err(wrong_type("a number")),
+        (toml::Value::Integer(_), toml::Value::Integer(v)) => *v as f64,
+        (toml::Value::Integer(_), _) => return Err(wrong_type("a whole number")),
+        (toml::Value::Boolean(_), toml::Value::Boolean(_)) => return Ok((value, None)),
+        (toml::Value::Boolean(_), _) => return Err(wrong_type("true or false")),
+        (toml::Value::String(_), toml::Value::String(tag)) if key == "lang" => {
+            // Languages are loaded at runtime; one without resources on
+            // this machine follows the system
+            if tag.trim().is_empty() || available_languages().contains(&normalize_locale(tag)) {
+                return Ok((value, None));
+            }
+            let issue = ConfigIssue::OutOfRange {
+                key: key.to_string(),
+                value: value.to_string(),
+                used: default.to_string(),
+            };
+            return Ok((default.clone(), Some(issue)));
+        }
+        (toml::Value::String(_), toml::Value::String(_)) => return Ok((value, None)),
+        _ => return Err(wrong_type("text")),
+    };
+
+    let fallback = default
+        .as_float()
+        .or(default.as_integer().map(|v| v as f64));
+    let used = match LIMITS.iter().find(|(k, _)| *k == key).map(|(_, l)| *l) {
+        _ if !number.is_finite() => fallback.unwrap_or(0.0),
+        Some(Limit::Range(min, max)) => number.clamp(min, max),
+        Some(Limit::OneOf(values)) if !values.contains(&number) => fallback.unwrap_or(0.0),
+        _ => number,
+    };
+    let make = |v: f64| match default {
+        toml::Value::Integer(_) => toml::Value::Integer(v as i64),
+        _ => toml::Value::Float(v),
+    };
+    if used == number {
+        return Ok((make(number), None));
+    }
+    let issue = ConfigIssue::OutOfRange {
+        key: key.to_string(),
+        value: value.to_string(),
+        used: make(used).to_string(),
+    };
+    Ok((make(used), Some(issue)))
+}
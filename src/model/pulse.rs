@@ -0,0 +1,20 @@
+//! Pulsing "breathing" animation (pure Rust, no FFI).
+//!
+//! The highlight slowly grows and shrinks around its configured radius
+//! to draw the eye without clicking. The phase comes from the shared
+//! animation clock (see `model::animation`), like the rainbow hue. With
+//! motion reduced the radius stays put.
+
+use std::f64::consts::TAU;
+
+/// Position in the pulse cycle [0.0, 1.0) at time `t` (seconds), one
+/// cycle every `period_ms` milliseconds.
+pub fn pulse_phase(t: f64, period_ms: f64) -> f64 {
+    (t * 1000.0 / period_ms).rem_euclid(1.0)
+}
+
+/// `radius` grown or shrunk by up to `amplitude_pct` percent at `phase`
+/// of the cycle: unchanged at phase 0 and 0.5, largest at 0.25.
+pub fn pulse_radius(radius: f64, amplitude_pct: f64, phase: f64) -> f64 {
+    radius * (1.0 + amplitude_pct / 100.0 * (phase * TAU).sin())
+}
@@ -0,0 +1,72 @@
+//! Quick adjustments from the status bar / tray menu (pure Rust, no FFI).
+//!
+//! The Radius submenu offers a few common sizes and the Profile submenu
+//! switches between looks, so the usual changes need no trip to the
+//! Settings window. A look profile sets the keys of the high-contrast
+//! preset: the standard look puts them back to their default, the
+//! high-contrast look applies the preset. The entries matching the
+//! current settings are checked; none is when the settings were changed
+//! by other means.
+
+use super::app_state::OverlayState;
+use super::contrast::{high_contrast_preset, HIGH_CONTRAST_KEYS};
+use super::preferences::Preferences;
+
+/// Name of the look with the default ring.
+pub const LOOK_PROFILE_STANDARD: &str = "standard";
+
+/// Name of the look of the high-contrast preset.
+pub const LOOK_PROFILE_HIGH_CONTRAST: &str = "high_contrast";
+
+/// Built-in looks, in menu order.
+pub const LOOK_PROFILES: &[&str] = &[LOOK_PROFILE_STANDARD, LOOK_PROFILE_HIGH_CONTRAST];
+
+/// Built-in look called `name`, if any.
+pub fn resolve_look_profile(name: &str) -> Option<&'static str> {
+    LOOK_PROFILES.iter().find(|p| **p == name).copied()
+}
+
+/// Translation key of a look's menu entry.
+pub fn look_profile_label(name: &str) -> &'static str {
+    match name {
+        LOOK_PROFILE_HIGH_CONTRAST => "High contrast",
+        _ => "Standard",
+    }
+}
+
+/// Preference values of the look called `name`; unknown names get the
+/// standard look.
+pub fn look_profile_values(name: &str) -> Vec<(&'static str, f64)> {
+    match name {
+        LOOK_PROFILE_HIGH_CONTRAST => high_contrast_preset().collect(),
+        _ => {
+            let defaults = Preferences::default();
+            HIGH_CONTRAST_KEYS
+                .into_iter()
+                .map(|key| (key, defaults.number(key).unwrap_or(0.0)))
+                .collect()
+        }
+    }
+}
+
+/// Look whose values `state` has, if any.
+pub fn current_look_profile(state: &OverlayState) -> Option<&'static str> {
+    let mut prefs = Preferences::default();
+    prefs.set_state(state);
+    LOOK_PROFILES.iter().copied().find(|name| {
+        look_profile_values(name)
+            .into_iter()
+            .all(|(key, value)| prefs.number(key).is_some_and(|v| (v - value).abs() < 1e-6))
+    })
+}
+
+/// Menu entry of a radius, e.g. `40 px`.
+pub fn radius_label(radius: f64) -> String {
+    format!("{} px", radius.round())
+}
+
+/// Is `choice` (one of [`QUICK_RADII`](super::constants::QUICK_RADII))
+/// the current `radius`?
+pub fn is_current_radius(radius: f64, choice: f64) -> bool {
+    (radius - choice).abs() < 0.5
+}
@@ -0,0 +1,33 @@
+//! Rainbow color mode (pure Rust, no FFI).
+//!
+//! Cycles the hue of the stroke color over time, keeping its saturation,
+//! brightness and alpha. The phase comes from the shared animation clock
+//! (see `model::animation`), so the macOS and Windows renderers, and
+//! every overlay window, show the same hue at the same moment. With
+//! motion reduced the stroke keeps its own color.
+
+use super::color::{hsb_to_rgb, rgb_to_hsb};
+use super::constants::RAINBOW_MIN_SATURATION;
+use super::screen_frame::Rgba;
+
+/// Position in the hue cycle [0.0, 1.0) at time `t` (seconds), cycling
+/// `speed` times per minute.
+pub fn rainbow_phase(t: f64, speed: f64) -> f64 {
+    (t * speed / 60.0).rem_euclid(1.0)
+}
+
+/// `base` with its hue turned by `phase` of a full cycle.
+///
+/// A gray stroke has no hue to turn, so it cycles fully saturated hues
+/// at its own brightness.
+pub fn rainbow_color(base: Rgba, phase: f64) -> Rgba {
+    let (r, g, b, a) = base;
+    let (hue, saturation, brightness) = rgb_to_hsb(r, g, b);
+    let saturation = if saturation < RAINBOW_MIN_SATURATION {
+        1.0
+    } else {
+        saturation
+    };
+    let (r, g, b) = hsb_to_rgb(hue + phase * 360.0, saturation, brightness);
+    (r, g, b, a)
+}
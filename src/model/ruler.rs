@@ -0,0 +1,58 @@
+//! On-screen ruler (pure Rust, no FFI).
+//!
+//! While the ruler mode is on (toggled by a hotkey), the overlay takes
+//! left clicks instead of passing them on: the first click places the
+//! start of a line, which follows the pointer until the second click
+//! places its end, and the line is labeled with its length. The next
+//! click starts a new line; Esc clears it and ends the mode. Positions
+//! are in the overlay's coordinates, points on macOS and pixels on
+//! Windows; the label converts them to pixels.
+
+/// Line being measured, from its start to its end.
+pub type RulerLine = ((f64, f64), (f64, f64));
+
+/// Points placed by the clicks of the ruler mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Ruler {
+    start: Option<(f64, f64)>,
+    end: Option<(f64, f64)>,
+}
+
+impl Ruler {
+    /// Ruler with no points placed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Place a point at `point`: the start of a new line, or the end of
+    /// the one being drawn.
+    pub fn click(&mut self, point: (f64, f64)) {
+        match (self.start, self.end) {
+            (Some(_), None) => self.end = Some(point),
+            _ => {
+                self.start = Some(point);
+                self.end = None;
+            }
+        }
+    }
+
+    /// Line to draw: start to end, or start to `pointer` while the end is
+    /// still to be placed. `None` before the first click.
+    pub fn line(&self, pointer: (f64, f64)) -> Option<RulerLine> {
+        self.start.map(|start| (start, self.end.unwrap_or(pointer)))
+    }
+}
+
+/// Label of a line, e.g. `250 px  (150 × 200)`: its length and its
+/// horizontal and vertical extent, in whole pixels. `scale` is the
+/// display's pixels per point (1.0 where positions are pixels).
+pub fn ruler_label((from, to): RulerLine, scale: f64) -> String {
+    let dx = (to.0 - from.0).abs() * scale;
+    let dy = (to.1 - from.1).abs() * scale;
+    format!(
+        "{} px  ({} × {})",
+        dx.hypot(dy).round(),
+        dx.round(),
+        dy.round()
+    )
+}
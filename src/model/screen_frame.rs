@@ -0,0 +1,89 @@
+//! Recording frame around the highlighted screen (pure Rust, no FFI).
+//!
+//! When enabled, a thin border is drawn along the edges of the display the
+//! cursor is on, so the presenter always knows which screen is being
+//! shared. Each display can have its own border color, keyed by
+//! `placement::display_key`.
+//!
+//! Colors are persisted as a single compact string:
+//! `display=#RRGGBB[AA];display=#RRGGBB[AA];...`.
+
+use std::collections::BTreeMap;
+
+use super::constants::DEFAULT_SCREEN_FRAME_COLOR;
+use super::placement::ScreenRect;
+use crate::{color_to_hex, parse_hex_color};
+
+/// RGBA color with components in [0.0, 1.0].
+pub type Rgba = (f64, f64, f64, f64);
+
+/// Per-display border colors.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FrameColors {
+    colors: BTreeMap<String, Rgba>,
+}
+
+impl FrameColors {
+    /// Creates an empty color table (every display uses the default color).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the border color of a display (components are clamped).
+    pub fn set(&mut self, display: &str, color: Rgba) {
+        if display.is_empty() || display.contains([';', '=']) {
+            return;
+        }
+        let (r, g, b, a) = color;
+        let c = (
+            r.clamp(0.0, 1.0),
+            g.clamp(0.0, 1.0),
+            b.clamp(0.0, 1.0),
+            a.clamp(0.0, 1.0),
+        );
+        self.colors.insert(display.to_string(), c);
+    }
+
+    /// Returns the color explicitly configured for a display.
+    pub fn get(&self, display: &str) -> Option<Rgba> {
+        self.colors.get(display).copied()
+    }
+
+    /// Returns the color to draw on a display, falling back to the default.
+    pub fn color_for(&self, display: &str) -> Rgba {
+        self.get(display).unwrap_or(DEFAULT_SCREEN_FRAME_COLOR)
+    }
+
+    /// Serializes the table to its compact string form.
+    pub fn encode(&self) -> String {
+        self.colors
+            .iter()
+            .map(|(display, &(r, g, b, a))| format!("{}={}", display, color_to_hex(r, g, b, a)))
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+
+    /// Parses a color table, skipping malformed entries.
+    pub fn decode(s: &str) -> Self {
+        let mut table = Self::new();
+        for entry in s.split(';') {
+            if let Some((display, hex)) = entry.trim().split_once('=') {
+                if let Some(color) = parse_hex_color(hex) {
+                    table.set(display, color);
+                }
+            }
+        }
+        table
+    }
+}
+
+/// Rectangle to stroke so a border of `width` lies fully inside `screen`.
+pub fn frame_rect(screen: &ScreenRect, width: f64) -> ScreenRect {
+    let inset = (width / 2.0).min(screen.w / 2.0).min(screen.h / 2.0);
+    ScreenRect::new(
+        screen.x + inset,
+        screen.y + inset,
+        screen.w - 2.0 * inset,
+        screen.h - 2.0 * inset,
+    )
+}
@@ -0,0 +1,63 @@
+//! AppleScript commands (pure Rust, no FFI).
+//!
+//! Lumbus' scripting dictionary (`resources/Lumbus.sdef`) defines a small
+//! suite of commands that Script Editor, Shortcuts ("Run AppleScript") and
+//! Keyboard Maestro can send:
+//!
+//! ```applescript
+//! tell application "Lumbus"
+//!     toggle overlay
+//!     set radius 60
+//!     load profile "presentation"
+//!     quit
+//! end tell
+//! ```
+//!
+//! Each command arrives as an Apple event of class [`SCRIPT_SUITE`]; this
+//! module maps it to the [`AppEvent`] published on the event bus. `quit`
+//! is the standard Apple event and is handled by AppKit itself.
+
+use crate::events::AppEvent;
+
+use super::hotkeys::HOTKEY_PROFILES;
+
+/// Four-character code as used by Apple events (`'Lmbs'`).
+pub const fn four_cc(code: &[u8; 4]) -> u32 {
+    u32::from_be_bytes(*code)
+}
+
+/// Event class of the Lumbus suite.
+pub const SCRIPT_SUITE: u32 = four_cc(b"Lmbs");
+
+/// `toggle overlay`
+pub const SCRIPT_TOGGLE: u32 = four_cc(b"togl");
+
+/// `set radius <number>`
+pub const SCRIPT_SET_RADIUS: u32 = four_cc(b"setR");
+
+/// `load profile <text>` (a hotkey profile, see `model::hotkeys`)
+pub const SCRIPT_LOAD_PROFILE: u32 = four_cc(b"prof");
+
+/// Direct parameter of a script command.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptArgument {
+    None,
+    Number(f64),
+    Text(String),
+}
+
+/// Event to publish for a command of the Lumbus suite.
+///
+/// Returns `None` for unknown commands, missing or mistyped parameters,
+/// and profiles that do not exist.
+pub fn script_event(event_id: u32, argument: &ScriptArgument) -> Option<AppEvent> {
+    match (event_id, argument) {
+        (SCRIPT_TOGGLE, _) => Some(AppEvent::ToggleOverlay),
+        (SCRIPT_SET_RADIUS, ScriptArgument::Number(radius)) => Some(AppEvent::SetRadius(*radius)),
+        (SCRIPT_LOAD_PROFILE, ScriptArgument::Text(name)) => HOTKEY_PROFILES
+            .iter()
+            .find(|p| p.eq_ignore_ascii_case(name.trim()))
+            .map(|p| AppEvent::LoadProfile(p.to_string())),
+        _ => None,
+    }
+}
@@ -0,0 +1,159 @@
+//! Session store for auxiliary windows (pure Rust, no FFI).
+//!
+//! Remembers which auxiliary windows were open, and where, so they can be
+//! reopened on the next launch. Windows are identified by a short string id
+//! (see the `SESSION_*` constants) so new overlays can join the session
+//! without changing the storage format.
+//!
+//! The store is persisted next to the other preferences as a single compact
+//! string: `id=open[@x,y];id=open[@x,y];...`, e.g. `help=1@120,340`.
+//!
+//! Positions chosen by dragging an overlay are also kept per display, as
+//! entries named `id#display` (see `placement::display_key`).
+
+use std::collections::BTreeMap;
+
+/// Session id of the help overlay.
+pub const SESSION_HELP_OVERLAY: &str = "help";
+
+/// Saved state of a single auxiliary window.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct WindowSession {
+    /// Was the window open when the session was last saved?
+    pub open: bool,
+    /// Window origin in global screen coordinates, if known.
+    pub position: Option<(f64, f64)>,
+}
+
+/// Open/position state of all auxiliary windows.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SessionStore {
+    windows: BTreeMap<String, WindowSession>,
+}
+
+impl SessionStore {
+    /// Creates an empty session.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that a window was opened, optionally at a given origin.
+    ///
+    /// A `None` position keeps the previously saved one.
+    pub fn mark_open(&mut self, id: &str, position: Option<(f64, f64)>) {
+        if !is_valid_id(id) {
+            return;
+        }
+        let entry = self.windows.entry(id.to_string()).or_default();
+        entry.open = true;
+        if position.is_some() {
+            entry.position = position;
+        }
+    }
+
+    /// Records that a window was closed (its position is kept).
+    pub fn mark_closed(&mut self, id: &str) {
+        if let Some(entry) = self.windows.get_mut(id) {
+            entry.open = false;
+        }
+    }
+
+    /// Updates the saved origin of a window without changing its open state.
+    pub fn set_position(&mut self, id: &str, x: f64, y: f64) {
+        if !is_valid_id(id) || !x.is_finite() || !y.is_finite() {
+            return;
+        }
+        self.windows.entry(id.to_string()).or_default().position = Some((x, y));
+    }
+
+    /// Remembers where a window was placed on a given display.
+    pub fn set_display_position(&mut self, id: &str, display: &str, x: f64, y: f64) {
+        if !is_valid_id(id) || id.contains('#') {
+            return;
+        }
+        self.set_position(&display_slot(id, display), x, y);
+    }
+
+    /// Returns the origin a window was last placed at on a given display.
+    pub fn display_position(&self, id: &str, display: &str) -> Option<(f64, f64)> {
+        self.position(&display_slot(id, display))
+    }
+
+    /// Returns the saved state of a window.
+    pub fn get(&self, id: &str) -> Option<WindowSession> {
+        self.windows.get(id).copied()
+    }
+
+    /// Returns true if the window was open.
+    pub fn is_open(&self, id: &str) -> bool {
+        self.get(id).is_some_and(|w| w.open)
+    }
+
+    /// Returns the saved origin of a window.
+    pub fn position(&self, id: &str) -> Option<(f64, f64)> {
+        self.get(id).and_then(|w| w.position)
+    }
+
+    /// Ids of all windows that were open, in a stable order.
+    pub fn open_windows(&self) -> Vec<&str> {
+        self.windows
+            .iter()
+            .filter(|(_, w)| w.open)
+            .map(|(id, _)| id.as_str())
+            .collect()
+    }
+
+    /// Serializes the session to its compact string form.
+    pub fn encode(&self) -> String {
+        self.windows
+            .iter()
+            .map(|(id, w)| match w.position {
+                Some((x, y)) => format!("{}={}@{},{}", id, w.open as u8, x, y),
+                None => format!("{}={}", id, w.open as u8),
+            })
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+
+    /// Parses a session string, skipping malformed entries.
+    pub fn decode(s: &str) -> Self {
+        let mut store = Self::new();
+        for entry in s.split(';') {
+            let Some((id, rest)) = entry.trim().split_once('=') else {
+                continue;
+            };
+            if !is_valid_id(id) {
+                continue;
+            }
+            let (open, pos) = match rest.split_once('@') {
+                Some((open, pos)) => (open, Some(pos)),
+                None => (rest, None),
+            };
+            let open = match open {
+                "1" => true,
+                "0" => false,
+                _ => continue,
+            };
+            let position = pos.and_then(|p| {
+                let (x, y) = p.split_once(',')?;
+                let x: f64 = x.parse().ok()?;
+                let y: f64 = y.parse().ok()?;
+                (x.is_finite() && y.is_finite()).then_some((x, y))
+            });
+            store
+                .windows
+                .insert(id.to_string(), WindowSession { open, position });
+        }
+        store
+    }
+}
+
+/// Session entry name of a window's position on one display.
+fn display_slot(id: &str, display: &str) -> String {
+    format!("{}#{}", id, display)
+}
+
+/// Ids must be non-empty and free of the separators used by `encode`.
+fn is_valid_id(id: &str) -> bool {
+    !id.is_empty() && !id.contains([';', '=', '@', ',']) && !id.contains(char::is_whitespace)
+}
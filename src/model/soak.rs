@@ -0,0 +1,261 @@
+//! Soak-test script and report (pure Rust, no FFI).
+//!
+//! The hidden `--soak` mode drives the running app for hours: the cursor
+//! sweeps the screen continuously while the overlay is toggled, settings
+//! are changed and space changes are simulated on a fixed schedule. The
+//! platform layer samples memory, handle counts and event-queue depth
+//! into a [`SoakReport`] and logs it, so slow leaks and dead hotkeys show
+//! up without waiting days.
+
+use super::constants::*;
+use super::placement::ScreenRect;
+
+/// Soak-test options parsed from the command line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoakConfig {
+    /// How long to run before writing the report and quitting.
+    pub duration_secs: f64,
+}
+
+impl SoakConfig {
+    /// Looks for `--soak` or `--soak=<minutes>` among the arguments.
+    ///
+    /// Returns `None` when the flag is absent. A missing or invalid
+    /// duration falls back to `DEFAULT_SOAK_MINUTES`.
+    pub fn from_args<I, S>(args: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        args.into_iter().find_map(|arg| {
+            let arg = arg.as_ref();
+            let minutes = if arg == SOAK_FLAG {
+                DEFAULT_SOAK_MINUTES
+            } else {
+                let value = arg.strip_prefix(SOAK_FLAG)?.strip_prefix('=')?;
+                value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|m| m.is_finite() && *m > 0.0)
+                    .unwrap_or(DEFAULT_SOAK_MINUTES)
+            };
+            Some(Self {
+                duration_secs: minutes * 60.0,
+            })
+        })
+    }
+}
+
+/// One scripted step of the soak test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SoakAction {
+    /// Move the cursor to this point (screen coordinates).
+    MoveCursor { x: f64, y: f64 },
+    /// Flip the overlay on or off.
+    ToggleOverlay,
+    /// Apply these settings.
+    ChurnSettings { radius: f64, highlight_style: i32 },
+    /// Simulate a space (virtual desktop) change.
+    SpaceChange,
+}
+
+/// Deterministic soak-test schedule, one step every `SOAK_TICK_SECS`.
+#[derive(Debug, Clone, Default)]
+pub struct SoakScript {
+    tick: u64,
+}
+
+impl SoakScript {
+    /// Creates a script at its first step.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of steps taken so far.
+    pub fn ticks(&self) -> u64 {
+        self.tick
+    }
+
+    /// Actions of the next step. The cursor sweeps a Lissajous curve
+    /// covering most of `screen`.
+    pub fn next_actions(&mut self, screen: &ScreenRect) -> Vec<SoakAction> {
+        let n = self.tick;
+        self.tick += 1;
+
+        let t = n as f64 * SOAK_TICK_SECS;
+        let mut actions = vec![SoakAction::MoveCursor {
+            x: screen.x + screen.w * (0.5 + 0.4 * (t * 0.7).sin()),
+            y: screen.y + screen.h * (0.5 + 0.4 * (t * 1.1).sin()),
+        }];
+
+        if n > 0 && n.is_multiple_of(SOAK_TOGGLE_TICKS) {
+            actions.push(SoakAction::ToggleOverlay);
+        }
+        if n > 0 && n.is_multiple_of(SOAK_CHURN_TICKS) {
+            let step = n / SOAK_CHURN_TICKS;
+            let steps = (MAX_RADIUS - MIN_RADIUS) / RADIUS_STEP;
+            actions.push(SoakAction::ChurnSettings {
+                radius: MIN_RADIUS + ((step * 7) as f64 % steps) * RADIUS_STEP,
+                highlight_style: if step.is_multiple_of(2) {
+                    HIGHLIGHT_STYLE_CIRCLE
+                } else {
+                    HIGHLIGHT_STYLE_LASER
+                },
+            });
+        }
+        if n > 0 && n.is_multiple_of(SOAK_SPACE_TICKS) {
+            actions.push(SoakAction::SpaceChange);
+        }
+        actions
+    }
+}
+
+/// Resource usage at one point of the soak test.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SoakSample {
+    /// Seconds since the soak test started.
+    pub elapsed_secs: f64,
+    /// Resident memory in bytes.
+    pub memory_bytes: u64,
+    /// Open kernel handles (Windows) or file descriptors (macOS).
+    pub handles: u64,
+    /// GDI + USER objects (Windows only).
+    pub gui_objects: Option<u64>,
+    /// Events waiting on the event bus (macOS only).
+    pub queue_depth: Option<usize>,
+    /// Installed global hotkeys (macOS only).
+    pub hotkeys: Option<u32>,
+}
+
+impl SoakSample {
+    /// One log line for this sample.
+    pub fn log_line(&self) -> String {
+        let opt = |v: Option<String>| v.unwrap_or_else(|| "n/a".to_string());
+        format!(
+            "[soak] t={:.0}s mem={:.1}MB handles={} gui={} queue={} hotkeys={}",
+            self.elapsed_secs,
+            self.memory_bytes as f64 / (1024.0 * 1024.0),
+            self.handles,
+            opt(self.gui_objects.map(|v| v.to_string())),
+            opt(self.queue_depth.map(|v| v.to_string())),
+            opt(self.hotkeys.map(|v| v.to_string())),
+        )
+    }
+}
+
+/// Accumulated soak-test results.
+#[derive(Debug, Clone, Default)]
+pub struct SoakReport {
+    baseline: Option<SoakSample>,
+    last: Option<SoakSample>,
+    samples: usize,
+    peak_memory: u64,
+    peak_handles: u64,
+    peak_queue: usize,
+    min_hotkeys: Option<u32>,
+    moves: u64,
+    toggles: u64,
+    churns: u64,
+    space_changes: u64,
+}
+
+impl SoakReport {
+    /// Creates an empty report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts a performed action.
+    pub fn count(&mut self, action: &SoakAction) {
+        match action {
+            SoakAction::MoveCursor { .. } => self.moves += 1,
+            SoakAction::ToggleOverlay => self.toggles += 1,
+            SoakAction::ChurnSettings { .. } => self.churns += 1,
+            SoakAction::SpaceChange => self.space_changes += 1,
+        }
+    }
+
+    /// Records a resource sample. The first one is the baseline.
+    pub fn record(&mut self, sample: SoakSample) {
+        if self.baseline.is_none() {
+            self.baseline = Some(sample);
+        }
+        self.last = Some(sample);
+        self.samples += 1;
+        self.peak_memory = self.peak_memory.max(sample.memory_bytes);
+        self.peak_handles = self.peak_handles.max(sample.handles);
+        self.peak_queue = self.peak_queue.max(sample.queue_depth.unwrap_or(0));
+        if let Some(h) = sample.hotkeys {
+            self.min_hotkeys = Some(self.min_hotkeys.map_or(h, |m| m.min(h)));
+        }
+    }
+
+    /// Number of samples recorded.
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// Resident memory change since the baseline, in bytes.
+    pub fn memory_growth(&self) -> i64 {
+        self.growth(|s| s.memory_bytes)
+    }
+
+    /// Handle count change since the baseline.
+    pub fn handle_growth(&self) -> i64 {
+        self.growth(|s| s.handles)
+    }
+
+    /// GUI object count change since the baseline (Windows only).
+    pub fn gui_object_growth(&self) -> Option<i64> {
+        match (self.baseline?.gui_objects, self.last?.gui_objects) {
+            (Some(first), Some(last)) => Some(last as i64 - first as i64),
+            _ => None,
+        }
+    }
+
+    /// Did the installed hotkey count ever drop below the baseline?
+    pub fn hotkeys_lost(&self) -> bool {
+        match (self.baseline.and_then(|s| s.hotkeys), self.min_hotkeys) {
+            (Some(first), Some(min)) => min < first,
+            _ => false,
+        }
+    }
+
+    fn growth(&self, field: impl Fn(&SoakSample) -> u64) -> i64 {
+        match (self.baseline, self.last) {
+            (Some(first), Some(last)) => field(&last) as i64 - field(&first) as i64,
+            _ => 0,
+        }
+    }
+
+    /// Multi-line summary for the log and the report file.
+    pub fn summary(&self) -> String {
+        let elapsed = self.last.map_or(0.0, |s| s.elapsed_secs);
+        let mut out = format!(
+            "Lumbus soak test\n\
+             duration: {:.0}s, samples: {}\n\
+             actions: {} moves, {} toggles, {} settings changes, {} space changes\n\
+             memory: {:+.1}MB since start (peak {:.1}MB)\n\
+             handles: {:+} since start (peak {})\n\
+             peak event queue: {}\n",
+            elapsed,
+            self.samples,
+            self.moves,
+            self.toggles,
+            self.churns,
+            self.space_changes,
+            self.memory_growth() as f64 / (1024.0 * 1024.0),
+            self.peak_memory as f64 / (1024.0 * 1024.0),
+            self.handle_growth(),
+            self.peak_handles,
+            self.peak_queue,
+        );
+        if let Some(growth) = self.gui_object_growth() {
+            out.push_str(&format!("gui objects: {:+} since start\n", growth));
+        }
+        if self.hotkeys_lost() {
+            out.push_str("WARNING: global hotkeys were lost during the run\n");
+        }
+        out
+    }
+}
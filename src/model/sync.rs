@@ -0,0 +1,157 @@
+//! Settings sync across machines (pure Rust, no FFI).
+//!
+//! With sync on, a copy of `config.toml` is kept in a folder the user
+//! chooses (iCloud Drive, OneDrive, Dropbox...), and their sync client
+//! carries it between machines. Each machine keeps its own `config.toml`
+//! and merges the synced copy into it whenever the copy changes, against
+//! the copy as it last read or wrote it:
+//!
+//! - a setting changed on one side only is taken from that side;
+//! - a setting changed on both sides goes to the side written last (the
+//!   local side on a tie), and the conflict is logged.
+//!
+//! The auxiliary-window session, the sync folder itself, the control
+//! endpoint's token and the time of the last update check belong to each
+//! machine and are never synced.
+
+use std::fmt;
+
+use super::constants::*;
+use super::managed::MANAGED_KEYS;
+use super::preferences::Preferences;
+
+/// File, next to `config.toml`, that sync conflicts are appended to.
+pub const SYNC_CONFLICTS_LOG: &str = "sync-conflicts.log";
+
+/// String preference keys carried by the synced copy.
+///
+/// The language is compared by its locale tag: the index it reads as
+/// depends on the languages each machine has.
+const SYNCED_STRING_KEYS: &[&str] = &[
+    PREF_LANG,
+    PREF_SCREEN_FRAME_COLORS,
+    PREF_COLOR_PALETTE,
+    PREF_CLICK_LETTER_LEFT,
+    PREF_CLICK_LETTER_RIGHT,
+    PREF_LETTER_FONT,
+    PREF_HOTKEY_PROFILE,
+];
+
+/// Copy a conflicting setting was taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncSide {
+    /// This machine's `config.toml`.
+    Local,
+    /// The synced copy, written by another machine.
+    Remote,
+}
+
+/// Setting changed on both sides since the last sync.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncConflict {
+    pub key: String,
+    pub local: String,
+    pub remote: String,
+    pub kept: SyncSide,
+}
+
+impl fmt::Display for SyncConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kept = match self.kept {
+            SyncSide::Local => &self.local,
+            SyncSide::Remote => &self.remote,
+        };
+        write!(
+            f,
+            "`{}`: {} here, {} in the synced copy; kept {} (written last)",
+            self.key, self.local, self.remote, kept
+        )
+    }
+}
+
+/// What this machine writes to the sync folder: `prefs` without the
+/// settings that are never synced.
+pub fn synced_copy(prefs: &Preferences) -> Preferences {
+    Preferences {
+        session: String::new(),
+        sync_folder: String::new(),
+        control_token: String::new(),
+        last_update_check: 0.0,
+        ..prefs.clone()
+    }
+}
+
+/// Merge the synced copy `remote` into `local`.
+///
+/// `base` is the synced copy as this machine last read or wrote it.
+/// `local_modified` and `remote_modified` are when each side was last
+/// written, in seconds since the Unix epoch. Returns the merged settings
+/// and the conflicts that were resolved.
+pub fn merge_synced(
+    base: &Preferences,
+    local: &Preferences,
+    local_modified: f64,
+    remote: &Preferences,
+    remote_modified: f64,
+) -> (Preferences, Vec<SyncConflict>) {
+    let mut merged = local.clone();
+    let mut conflicts = Vec::new();
+    let remote_wins = remote_modified > local_modified;
+
+    for &key in MANAGED_KEYS
+        .iter()
+        .filter(|k| !SYNCED_STRING_KEYS.contains(k))
+    {
+        let (Some(b), Some(l), Some(r)) = (base.number(key), local.number(key), remote.number(key))
+        else {
+            continue;
+        };
+        if r == b || r == l {
+            continue;
+        }
+        if l != b {
+            conflicts.push(conflict(key, l.to_string(), r.to_string(), remote_wins));
+            if !remote_wins {
+                continue;
+            }
+        }
+        merged.set_number(key, r);
+    }
+
+    for &key in SYNCED_STRING_KEYS {
+        let (Some(b), Some(l), Some(r)) = (base.string(key), local.string(key), remote.string(key))
+        else {
+            continue;
+        };
+        if r == b || r == l {
+            continue;
+        }
+        if l != b {
+            conflicts.push(conflict(
+                key,
+                format!("{l:?}"),
+                format!("{r:?}"),
+                remote_wins,
+            ));
+            if !remote_wins {
+                continue;
+            }
+        }
+        merged.set_string(key, r);
+    }
+
+    (merged, conflicts)
+}
+
+fn conflict(key: &str, local: String, remote: String, remote_wins: bool) -> SyncConflict {
+    SyncConflict {
+        key: key.to_string(),
+        local,
+        remote,
+        kept: if remote_wins {
+            SyncSide::Remote
+        } else {
+            SyncSide::Local
+        },
+    }
+}
@@ -0,0 +1,55 @@
+//! Refresh timer watchdog (pure Rust, no FFI).
+//!
+//! On macOS the ~60 FPS timer that moves the highlight can be
+//! invalidated by AppKit edge cases, after which the overlay freezes. A
+//! slower check compares the last tick of that timer with the clock:
+//! when it has not run for `TIMER_WATCHDOG_SECS`, the timer is taken for
+//! stalled and created again. A check that itself comes late (sleep, a
+//! modal loop) only starts over, as the refresh timer was held up too.
+
+use super::constants::TIMER_WATCHDOG_SECS;
+
+/// Last tick of the refresh timer and the restarts it needed.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TimerWatchdog {
+    last_tick: Option<f64>,
+    last_check: Option<f64>,
+    restarts: u32,
+}
+
+impl TimerWatchdog {
+    /// Watchdog that has not seen a tick yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The refresh timer ran at time `now` (seconds).
+    pub fn tick(&mut self, now: f64) {
+        self.last_tick = Some(now);
+    }
+
+    /// Check the refresh timer at time `now` (seconds). `true` when it is
+    /// taken for stalled: it is counted as restarted and starts over.
+    pub fn check(&mut self, now: f64) -> bool {
+        let late = self
+            .last_check
+            .is_some_and(|last| now - last >= TIMER_WATCHDOG_SECS);
+        self.last_check = Some(now);
+        let last_tick = *self.last_tick.get_or_insert(now);
+        if late {
+            self.last_tick = Some(now);
+            return false;
+        }
+        if now - last_tick < TIMER_WATCHDOG_SECS {
+            return false;
+        }
+        self.last_tick = Some(now);
+        self.restarts += 1;
+        true
+    }
+
+    /// Times the refresh timer was restarted since the start.
+    pub fn restarts(&self) -> u32 {
+        self.restarts
+    }
+}
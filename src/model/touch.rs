@@ -0,0 +1,99 @@
+//! Touch point indicators (pure Rust, no FFI).
+//!
+//! Each finger on a touchscreen gets a ring of its own, following it
+//! while it stays down and fading out over `TOUCH_FADE_SECS` once it
+//! lifts, so quick taps still show. Fingers are told apart by the
+//! pointer ID the system gives them. Positions are in the overlay's
+//! coordinates (pixels on Windows).
+
+use super::constants::TOUCH_FADE_SECS;
+
+/// Ring to draw for one touch point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TouchRing {
+    /// Touch point position.
+    pub x: f64,
+    pub y: f64,
+    /// Opacity multiplier [0.0, 1.0]: 1.0 while down, fading once lifted.
+    pub alpha: f64,
+}
+
+/// One finger, down or lifted at `lifted_at`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Touch {
+    id: u32,
+    x: f64,
+    y: f64,
+    lifted_at: Option<f64>,
+}
+
+/// Fingers down and those still fading out.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Touches {
+    touches: Vec<Touch>,
+}
+
+impl Touches {
+    /// Tracker with no fingers down.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Finger `id` touched down at (`x`, `y`).
+    pub fn down(&mut self, id: u32, x: f64, y: f64) {
+        self.touches.retain(|t| t.id != id);
+        self.touches.push(Touch {
+            id,
+            x,
+            y,
+            lifted_at: None,
+        });
+    }
+
+    /// Finger `id`, still down, moved to (`x`, `y`). Ignored for a finger
+    /// that is not down.
+    pub fn moved(&mut self, id: u32, x: f64, y: f64) {
+        if let Some(t) = self.find_down(id) {
+            t.x = x;
+            t.y = y;
+        }
+    }
+
+    /// Finger `id` lifted at time `t` (seconds); its ring starts fading.
+    pub fn up(&mut self, id: u32, t: f64) {
+        if let Some(touch) = self.find_down(id) {
+            touch.lifted_at = Some(t);
+        }
+    }
+
+    /// Rings to draw at time `t`, in touch order; rings faded out are
+    /// dropped.
+    pub fn rings(&mut self, t: f64) -> Vec<TouchRing> {
+        self.touches.retain(|touch| match touch.lifted_at {
+            Some(lifted) => t - lifted < TOUCH_FADE_SECS,
+            None => true,
+        });
+        self.touches
+            .iter()
+            .map(|touch| TouchRing {
+                x: touch.x,
+                y: touch.y,
+                alpha: match touch.lifted_at {
+                    Some(lifted) => (1.0 - (t - lifted) / TOUCH_FADE_SECS).clamp(0.0, 1.0),
+                    None => 1.0,
+                },
+            })
+            .collect()
+    }
+
+    /// No fingers down and none fading?
+    pub fn is_empty(&self) -> bool {
+        self.touches.is_empty()
+    }
+
+    fn find_down(&mut self, id: u32) -> Option<&mut Touch> {
+        self.touches
+            .iter_mut()
+            .find(|t| t.id == id && t.lifted_at.is_none())
+    }
+}
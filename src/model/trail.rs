@@ -0,0 +1,123 @@
+//! Laser-pointer trail (pure Rust, no FFI).
+//!
+//! A time-stamped buffer of recent cursor positions. Consecutive points
+//! form segments whose alpha fades with age, which the platform renderers
+//! draw behind the laser dot to produce a comet-like tail.
+
+use std::collections::VecDeque;
+
+use super::constants::*;
+
+/// A cursor sample recorded at time `t` (seconds, any monotonic origin).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrailPoint {
+    pub x: f64,
+    pub y: f64,
+    pub t: f64,
+}
+
+/// A renderable trail segment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrailSegment {
+    pub x0: f64,
+    pub y0: f64,
+    pub x1: f64,
+    pub y1: f64,
+    /// Opacity multiplier [0.0, 1.0]: 1.0 at the head, fading towards the tail.
+    pub alpha: f64,
+}
+
+/// Fixed-capacity buffer of recent cursor positions.
+#[derive(Debug, Clone)]
+pub struct Trail {
+    points: VecDeque<TrailPoint>,
+    lifetime: f64,
+    capacity: usize,
+}
+
+impl Default for Trail {
+    fn default() -> Self {
+        Self::new(LASER_TRAIL_LIFETIME_SECS, LASER_TRAIL_MAX_POINTS)
+    }
+}
+
+impl Trail {
+    /// Creates an empty trail whose points expire after `lifetime` seconds.
+    pub fn new(lifetime: f64, capacity: usize) -> Self {
+        let capacity = capacity.max(2);
+        Self {
+            points: VecDeque::with_capacity(capacity),
+            lifetime: lifetime.max(f64::EPSILON),
+            capacity,
+        }
+    }
+
+    /// Records a cursor position. Returns true if a point was added.
+    ///
+    /// Repeated samples at the same position are ignored so the tail
+    /// shrinks back into the dot while the cursor rests.
+    pub fn push(&mut self, x: f64, y: f64, t: f64) -> bool {
+        if let Some(last) = self.points.back() {
+            if last.x == x && last.y == y {
+                return false;
+            }
+        }
+        while self.points.len() >= self.capacity {
+            self.points.pop_front();
+        }
+        self.points.push_back(TrailPoint { x, y, t });
+        true
+    }
+
+    /// Drops points older than the lifetime. Returns true if any were removed.
+    pub fn prune(&mut self, now: f64) -> bool {
+        let before = self.points.len();
+        while let Some(first) = self.points.front() {
+            if now - first.t > self.lifetime {
+                self.points.pop_front();
+            } else {
+                break;
+            }
+        }
+        self.points.len() != before
+    }
+
+    /// Builds the segments to draw at time `now`, oldest first.
+    pub fn segments(&self, now: f64) -> Vec<TrailSegment> {
+        self.points
+            .iter()
+            .zip(self.points.iter().skip(1))
+            .filter_map(|(a, b)| {
+                let age = now - (a.t + b.t) / 2.0;
+                let alpha = (1.0 - age / self.lifetime).clamp(0.0, 1.0);
+                (alpha > 0.0).then_some(TrailSegment {
+                    x0: a.x,
+                    y0: a.y,
+                    x1: b.x,
+                    y1: b.y,
+                    alpha,
+                })
+            })
+            .collect()
+    }
+
+    /// Removes all points.
+    pub fn clear(&mut self) {
+        self.points.clear();
+    }
+
+    /// Number of buffered points.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns true if no points are buffered.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+}
+
+/// Laser dot radius derived from the configured circle radius.
+pub fn laser_dot_radius(radius: f64) -> f64 {
+    (radius * LASER_DOT_SCALE).max(MIN_LASER_DOT_RADIUS)
+}
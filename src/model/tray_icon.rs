@@ -0,0 +1,76 @@
+//! Tray icon states (pure Rust, no FFI).
+//!
+//! The Windows tray icon shows the state of the highlight at a glance:
+//! drawn as is while the highlight is shown, faded while it is hidden,
+//! and with a badge in its bottom-right corner while the presentation
+//! lock is on. The variants are made from the theme's icon at runtime.
+//! The macOS status item, a template image, is dimmed instead while the
+//! highlight is hidden; both tooltips name the state.
+
+use super::constants::{TRAY_BADGE_COLOR, TRAY_BADGE_RADIUS, TRAY_ICON_HIDDEN_ALPHA};
+
+/// State shown by the tray icon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrayIconState {
+    /// Is the highlight shown?
+    pub visible: bool,
+    /// Is the presentation lock on?
+    pub locked: bool,
+}
+
+impl TrayIconState {
+    /// Number of states, for caching one icon per state.
+    pub const COUNT: usize = 4;
+
+    /// Position of the state in a cache of `COUNT` icons.
+    pub fn index(self) -> usize {
+        self.visible as usize | (self.locked as usize) << 1
+    }
+
+    /// Message describing the state, for the tooltip.
+    pub fn label(self) -> &'static str {
+        if self.visible {
+            "Highlight shown"
+        } else {
+            "Highlight hidden"
+        }
+    }
+}
+
+/// Turn the pixels of the tray icon into its look for `state`.
+///
+/// `pixels` are `size` rows of `size` 32-bit BGRA pixels, top row first,
+/// with straight (not premultiplied) alpha.
+pub fn tray_icon_pixels(pixels: &mut [u8], size: usize, state: TrayIconState) {
+    if !state.visible {
+        for pixel in pixels.chunks_exact_mut(4) {
+            pixel[3] = (pixel[3] as f64 * TRAY_ICON_HIDDEN_ALPHA).round() as u8;
+        }
+    }
+    if state.locked {
+        draw_badge(pixels, size);
+    }
+}
+
+/// Blend a filled circle over the bottom-right corner.
+fn draw_badge(pixels: &mut [u8], size: usize) {
+    let radius = size as f64 * TRAY_BADGE_RADIUS;
+    let center = size as f64 - radius;
+    let (r, g, b, a) = TRAY_BADGE_COLOR;
+    let badge = [b, g, r];
+    for (i, pixel) in pixels.chunks_exact_mut(4).take(size * size).enumerate() {
+        let (x, y) = ((i % size) as f64 + 0.5, (i / size) as f64 + 0.5);
+        // Antialiased edge: full coverage inside, none half a pixel out
+        let coverage = (radius + 0.5 - (x - center).hypot(y - center)).clamp(0.0, 1.0) * a;
+        if coverage <= 0.0 {
+            continue;
+        }
+        let under = pixel[3] as f64 / 255.0;
+        let alpha = coverage + under * (1.0 - coverage);
+        for (channel, value) in pixel.iter_mut().zip(badge) {
+            let mixed = value * 255.0 * coverage + *channel as f64 * under * (1.0 - coverage);
+            *channel = (mixed / alpha).round() as u8;
+        }
+        pixel[3] = (alpha * 255.0).round() as u8;
+    }
+}
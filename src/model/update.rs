@@ -0,0 +1,95 @@
+//! Update check against the release feed (pure Rust, no FFI).
+//!
+//! Once a day (unless turned off with `update_check`), and whenever the
+//! user picks "Check for updates" in the menu, the platform fetches the
+//! latest GitHub release (`UPDATE_FEED_URL`) on a background thread and
+//! publishes the outcome as `AppEvent::UpdateChecked`. This module decides
+//! when a check is due and reads the feed.
+
+use serde::{Deserialize, Serialize};
+
+use super::constants::UPDATE_CHECK_INTERVAL_SECS;
+
+/// A published release.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Release {
+    /// Version without the tag's `v` prefix, e.g. `0.6.0`.
+    pub version: String,
+    /// Release page to download it from.
+    pub url: String,
+}
+
+/// Outcome of an update check.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateStatus {
+    /// The running version is the latest (or newer).
+    UpToDate,
+    /// A newer release is available.
+    Available(Release),
+    /// The feed could not be fetched or read.
+    Failed(String),
+}
+
+/// Fields of the GitHub release JSON that are used.
+#[derive(Deserialize)]
+struct FeedRelease {
+    tag_name: String,
+    html_url: String,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+}
+
+/// Major, minor and patch numbers of a version such as `v1.2.3` or
+/// `1.2.3-beta`. Missing parts count as 0.
+pub fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let version = version.trim();
+    let version = version.strip_prefix(['v', 'V']).unwrap_or(version);
+    let core = version.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|p| p.parse::<u64>());
+    let major = parts.next()?.ok()?;
+    let minor = parts.next().unwrap_or(Ok(0)).ok()?;
+    let patch = parts.next().unwrap_or(Ok(0)).ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch))
+}
+
+/// Read the latest release from the feed's JSON.
+pub fn parse_release(json: &str) -> Result<Release, String> {
+    let feed: FeedRelease =
+        serde_json::from_str(json).map_err(|e| format!("unexpected release feed: {e}"))?;
+    if feed.draft || feed.prerelease {
+        return Err("latest release is not published".to_string());
+    }
+    let version = feed.tag_name.trim();
+    let version = version.strip_prefix(['v', 'V']).unwrap_or(version);
+    Ok(Release {
+        version: version.to_string(),
+        url: feed.html_url,
+    })
+}
+
+/// Compare the running version `current` with the fetched feed.
+pub fn update_status(current: &str, feed: Result<String, String>) -> UpdateStatus {
+    let release = match feed.and_then(|json| parse_release(&json)) {
+        Ok(release) => release,
+        Err(e) => return UpdateStatus::Failed(e),
+    };
+    match (parse_version(&release.version), parse_version(current)) {
+        (Some(latest), Some(running)) if latest > running => UpdateStatus::Available(release),
+        (Some(_), Some(_)) => UpdateStatus::UpToDate,
+        _ => UpdateStatus::Failed(format!("unexpected version {:?}", release.version)),
+    }
+}
+
+/// Is an automatic check due at `now`, given the last one at
+/// `last_check` (both in seconds since the Unix epoch)?
+///
+/// A last check in the future (clock changed) counts as long ago.
+pub fn check_due(enabled: bool, last_check: f64, now: f64) -> bool {
+    enabled && (now - last_check >= UPDATE_CHECK_INTERVAL_SECS || last_check > now)
+}
@@ -0,0 +1,153 @@
+//! `lumbus://` URLs (pure Rust, no FFI).
+//!
+//! Launch bars, browsers and shell scripts can control the running
+//! instance by opening a URL:
+//!
+//! - `lumbus://toggle`: show or hide the highlight
+//! - `lumbus://restart`: quit and start again
+//! - `lumbus://profile/presentation`: switch hotkey profile
+//! - `lumbus://color/ff8800`: set the highlight color, in any format of
+//!   the Settings window's Hex field (`model::color`), percent-encoded
+//! - `lumbus://mode/left`: show a button's letter as after a click
+//!   (`left`, `right`), or the circle again (`circle`)
+//! - `lumbus://set?radius=60&borderWidth=4`: change overlay settings, by
+//!   preference key (the keys of `MANAGED_KEYS`)
+//!
+//! This module turns a URL into the [`AppEvent`]s to publish on the event
+//! bus; the platforms receive the URL (Apple event on macOS, command line
+//! forwarded to the running instance on Windows).
+
+use std::fmt;
+
+use crate::events::AppEvent;
+
+use super::color::parse_color;
+use super::display_mode::DisplayMode;
+use super::hotkeys::HOTKEY_PROFILES;
+use super::managed::MANAGED_KEYS;
+
+/// URL scheme registered by the app.
+pub const URL_SCHEME: &str = "lumbus";
+
+/// Reason a `lumbus://` URL was not carried out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrlError {
+    /// Not a `lumbus://` URL.
+    WrongScheme,
+    /// No such command (`lumbus://<command>`).
+    UnknownCommand(String),
+    /// No hotkey profile with this name.
+    UnknownProfile(String),
+    /// Unknown setting, or a value that is not a number.
+    BadSetting(String),
+    /// Not a color.
+    BadColor(String),
+    /// No display mode with this name.
+    UnknownMode(String),
+}
+
+impl fmt::Display for UrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UrlError::WrongScheme => write!(f, "not a {URL_SCHEME}:// URL"),
+            UrlError::UnknownCommand(cmd) => write!(f, "unknown command `{cmd}`"),
+            UrlError::UnknownProfile(name) => write!(f, "unknown hotkey profile `{name}`"),
+            UrlError::BadSetting(pair) => write!(f, "cannot set `{pair}`"),
+            UrlError::BadColor(color) => write!(f, "not a color: `{color}`"),
+            UrlError::UnknownMode(name) => write!(f, "unknown display mode `{name}`"),
+        }
+    }
+}
+
+/// Event setting an overlay setting from outside the app (URLs, the
+/// control endpoint), or `None` if `key` is not an overlay setting.
+///
+/// App-level settings such as the control endpoint's own port and token
+/// cannot be changed this way.
+pub fn setting_event(key: &str, value: f64) -> Option<AppEvent> {
+    (MANAGED_KEYS.contains(&key) && value.is_finite())
+        .then(|| AppEvent::SetPreference(key.to_string(), value))
+}
+
+/// Events to publish for a `lumbus://` URL.
+///
+/// A `set` URL with several settings yields one event per setting; if any
+/// of them is invalid, none is applied.
+pub fn url_events(url: &str) -> Result<Vec<AppEvent>, UrlError> {
+    let rest = url
+        .split_once("://")
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case(URL_SCHEME))
+        .map(|(_, rest)| rest)
+        .ok_or(UrlError::WrongScheme)?;
+    let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let mut segments = path.split('/').filter(|s| !s.is_empty());
+    let command = segments.next().unwrap_or("");
+
+    match command.to_ascii_lowercase().as_str() {
+        "toggle" => Ok(vec![AppEvent::ToggleOverlay]),
+        "restart" => Ok(vec![AppEvent::Restart]),
+        "profile" => {
+            let name = segments.next().unwrap_or("");
+            HOTKEY_PROFILES
+                .iter()
+                .find(|p| p.eq_ignore_ascii_case(name))
+                .map(|p| vec![AppEvent::LoadProfile(p.to_string())])
+                .ok_or_else(|| UrlError::UnknownProfile(name.to_string()))
+        }
+        "color" => {
+            let color = percent_decode(segments.next().unwrap_or(""));
+            parse_color(&color)
+                .map(|(r, g, b, a)| vec![AppEvent::SetColor { r, g, b, a }])
+                .ok_or(UrlError::BadColor(color))
+        }
+        "mode" => {
+            let name = segments.next().unwrap_or("");
+            DisplayMode::from_name(name)
+                .map(|mode| vec![AppEvent::SetMode(mode)])
+                .ok_or_else(|| UrlError::UnknownMode(name.to_string()))
+        }
+        "set" => {
+            let events = query
+                .split('&')
+                .filter(|pair| !pair.is_empty())
+                .map(|pair| {
+                    let bad = || UrlError::BadSetting(pair.to_string());
+                    let (key, value) = pair.split_once('=').ok_or_else(bad)?;
+                    let value: f64 = value.parse().map_err(|_| bad())?;
+                    setting_event(key, value).ok_or_else(bad)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if events.is_empty() {
+                return Err(UrlError::BadSetting(query.to_string()));
+            }
+            Ok(events)
+        }
+        _ => Err(UrlError::UnknownCommand(command.to_string())),
+    }
+}
+
+/// `text` with its `%XX` escapes decoded; malformed escapes are kept.
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| text.get(i + 1..i + 3))
+            .flatten()
+            // `from_str_radix` alone would take a sign (`%+1`)
+            .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
@@ -0,0 +1,118 @@
+//! Zoom-follow magnification (pure Rust, no FFI).
+//!
+//! The screen under the cursor is magnified around a focus point that eases
+//! towards the cursor. The focus is a fixed point of the magnification:
+//! whatever lies under it on the real screen is drawn at the same place in
+//! the magnified view, so once the pan settles the content under the
+//! pointer is exactly what a click would hit.
+
+use super::constants::*;
+use super::placement::ScreenRect;
+
+/// Zoom level and smoothed focus point.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Zoom {
+    step: usize,
+    focus: Option<(f64, f64, f64)>,
+}
+
+impl Zoom {
+    /// Creates an inactive zoom (1x).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current magnification factor (1.0 when inactive).
+    pub fn level(&self) -> f64 {
+        ZOOM_LEVELS[self.step]
+    }
+
+    /// Returns true if the screen is being magnified.
+    pub fn is_active(&self) -> bool {
+        self.step > 0
+    }
+
+    /// Moves to the next magnification step. Returns false at the maximum.
+    pub fn zoom_in(&mut self) -> bool {
+        if self.step + 1 >= ZOOM_LEVELS.len() {
+            return false;
+        }
+        self.step += 1;
+        true
+    }
+
+    /// Moves to the previous magnification step. Returns false when already
+    /// inactive; leaving the last magnified step forgets the focus point.
+    pub fn zoom_out(&mut self) -> bool {
+        if self.step == 0 {
+            return false;
+        }
+        self.step -= 1;
+        if self.step == 0 {
+            self.focus = None;
+        }
+        true
+    }
+
+    /// Turns the zoom off.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Eases the focus towards the cursor at time `t` (seconds) and returns
+    /// the new focus. The first sample after activation snaps to the cursor.
+    pub fn follow(&mut self, x: f64, y: f64, t: f64) -> (f64, f64) {
+        let (fx, fy) = match self.focus {
+            Some((fx, fy, ft)) => {
+                let dt = t - ft;
+                if dt <= 0.0 {
+                    return (fx, fy);
+                }
+                let k = 1.0 - (-dt / ZOOM_FOLLOW_SECS).exp();
+                (fx + (x - fx) * k, fy + (y - fy) * k)
+            }
+            None => (x, y),
+        };
+        self.focus = Some((fx, fy, t));
+        (fx, fy)
+    }
+
+    /// Moves the focus straight to the cursor (Reduce Motion) and returns it.
+    pub fn snap(&mut self, x: f64, y: f64, t: f64) -> (f64, f64) {
+        self.focus = Some((x, y, t));
+        (x, y)
+    }
+
+    /// Current focus point, if the cursor has been followed since activation.
+    pub fn focus(&self) -> Option<(f64, f64)> {
+        self.focus.map(|(x, y, _)| (x, y))
+    }
+}
+
+/// Clamps a focus point into the screen.
+fn clamp_focus(screen: &ScreenRect, focus: (f64, f64)) -> (f64, f64) {
+    (
+        focus.0.clamp(screen.x, screen.x + screen.w),
+        focus.1.clamp(screen.y, screen.y + screen.h),
+    )
+}
+
+/// Part of `screen` that fills the whole screen when magnified by `level`
+/// around `focus`. Always lies within the screen.
+pub fn source_rect(screen: &ScreenRect, level: f64, focus: (f64, f64)) -> ScreenRect {
+    let level = level.max(1.0);
+    let (fx, fy) = clamp_focus(screen, focus);
+    ScreenRect::new(
+        fx - (fx - screen.x) / level,
+        fy - (fy - screen.y) / level,
+        screen.w / level,
+        screen.h / level,
+    )
+}
+
+/// Where a point of the real screen appears in the magnified view.
+pub fn to_zoomed(screen: &ScreenRect, level: f64, focus: (f64, f64), x: f64, y: f64) -> (f64, f64) {
+    let level = level.max(1.0);
+    let (fx, fy) = clamp_focus(screen, focus);
+    (fx + (x - fx) * level, fy + (y - fy) * level)
+}
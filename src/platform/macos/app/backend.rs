@@ -0,0 +1,152 @@
+//! `OverlayBackend` over the overlay views and `config.toml`.
+
+use crate::backend::OverlayBackend;
+use crate::model::{DisplayMode, OverlayState};
+use crate::platform::macos::app::{
+    apply_capture_exclusion, apply_state_to_all_views, apply_to_all_views, permission_status,
+    sync_overlay_windows,
+};
+use crate::platform::macos::ffi::bridge::{get_bool_ivar, id, msg_send, set_bool_ivar, YES};
+use crate::platform::macos::ffi::{get_mouse_position_cocoa, system_locale};
+use crate::platform::macos::input::{ruler_on, show_display_mode, start_ruler, stop_ruler};
+use crate::platform::macos::storage::{
+    flush_config, load_state, prefs_set_double, prefs_set_string,
+};
+use crate::platform::macos::ui::update_status_bar_language;
+use tracing::info;
+
+/// The overlay views, reached through the host view of the status bar.
+///
+/// Only lives for one dispatch, on the main thread.
+pub struct MacBackend {
+    view: id,
+    reinstall_hotkeys: unsafe fn(id),
+}
+
+impl MacBackend {
+    /// Backend over the host `view`; `reinstall_hotkeys` registers the
+    /// Carbon hotkeys of the selected profile.
+    ///
+    /// # Safety
+    /// - `view` must be a valid, non-null pointer to a CustomViewMulti.
+    /// - The backend must only be used from the main thread.
+    pub unsafe fn new(view: id, reinstall_hotkeys: unsafe fn(id)) -> Self {
+        Self {
+            view,
+            reinstall_hotkeys,
+        }
+    }
+}
+
+impl OverlayBackend for MacBackend {
+    fn load_state(&self) -> OverlayState {
+        unsafe { load_state() }
+    }
+
+    fn set_double(&mut self, key: &str, value: f64) {
+        unsafe { prefs_set_double(key, value) }
+    }
+
+    fn set_string(&mut self, key: &str, value: &str) {
+        unsafe { prefs_set_string(key, value) }
+    }
+
+    fn flush(&mut self) {
+        flush_config();
+    }
+
+    fn apply_state(&mut self, state: &OverlayState) {
+        unsafe { apply_state_to_all_views(state) }
+    }
+
+    /// New overlays take the visual settings of the host view.
+    fn sync_windows(&mut self) -> usize {
+        unsafe {
+            sync_overlay_windows(self.view);
+            let mut count = 0;
+            apply_to_all_views(|_| count += 1);
+            count
+        }
+    }
+
+    fn apply_capture_exclusion(&mut self) {
+        unsafe { apply_capture_exclusion() }
+    }
+
+    fn cursor_position(&self) -> (f64, f64) {
+        get_mouse_position_cocoa()
+    }
+
+    fn toggle_overlay(&mut self) {
+        unsafe {
+            let _: () = msg_send![self.view, requestToggle];
+        }
+    }
+
+    fn toggle_coordinates(&mut self) {
+        unsafe {
+            let show = !get_bool_ivar(self.view, "_showCoordinates");
+            apply_to_all_views(|v| set_bool_ivar(v, "_showCoordinates", show));
+        }
+    }
+
+    fn toggle_color_sampler(&mut self) {
+        unsafe {
+            let on = !get_bool_ivar(self.view, "_colorSampler");
+            // Without Screen Recording the sample would only show the desktop
+            if on && !permission_status().color_sampler() {
+                info!("Color sampler needs the Screen Recording permission");
+                return;
+            }
+            apply_to_all_views(|v| set_bool_ivar(v, "_colorSampler", on));
+        }
+    }
+
+    fn toggle_ruler(&mut self) {
+        unsafe {
+            if ruler_on() {
+                stop_ruler(self.view);
+            } else if get_bool_ivar(self.view, "_overlayEnabled") {
+                start_ruler(self.view);
+            } else {
+                // Its clicks would vanish without a trace
+                info!("Ruler needs the overlay to be shown");
+            }
+        }
+    }
+
+    fn clear_ruler(&mut self) {
+        unsafe { stop_ruler(self.view) }
+    }
+
+    fn toggle_grid(&mut self) {
+        unsafe {
+            let show = !get_bool_ivar(self.view, "_showGrid");
+            apply_to_all_views(|v| set_bool_ivar(v, "_showGrid", show));
+        }
+    }
+
+    fn show_display_mode(&mut self, mode: DisplayMode) {
+        unsafe { show_display_mode(self.view, mode) }
+    }
+
+    fn reinstall_hotkeys(&mut self) {
+        unsafe { (self.reinstall_hotkeys)(self.view) }
+    }
+
+    fn refresh_menus(&mut self) {
+        unsafe { update_status_bar_language(self.view) }
+    }
+
+    fn redraw(&mut self) {
+        unsafe {
+            apply_to_all_views(|v| {
+                let _: () = msg_send![v, setNeedsDisplay: YES];
+            });
+        }
+    }
+
+    fn system_locale(&self) -> Option<String> {
+        unsafe { system_locale() }
+    }
+}
@@ -0,0 +1,157 @@
+//! Ownership of the objects that live as long as the app.
+//!
+//! `AppContext` owns the overlay windows and views created at startup and
+//! the watchdog timer; the refresh and keep-alive timers, the event
+//! monitors and the Carbon hotkeys hang off the host view (its ivars). The
+//! termination observer tears it all down explicitly, so nothing is left
+//! behind for a leak checker (Instruments, `leaks`) at exit.
+
+use std::cell::RefCell;
+
+use tracing::info;
+
+use crate::platform::macos::app::displays::make_window_for_screen;
+use crate::platform::macos::app::helpers::apply_to_all_views;
+use crate::platform::macos::ffi::bridge::{
+    get_class, id, msg_send, nil, AnyObject, ObjectExt, Retained, NO,
+};
+use crate::platform::macos::input::hotkeys::uninstall_hotkeys;
+
+/// Timers of the host view, invalidated on teardown.
+const TIMER_IVARS: [&str; 2] = ["_updateTimer", "_hkKeepAliveTimer"];
+
+/// Event monitors of the host view, removed on teardown.
+const MONITOR_IVARS: [&str; 8] = [
+    "_monLeftDown",
+    "_monLeftUp",
+    "_monRightDown",
+    "_monRightUp",
+    "_monMove",
+    "_monPen",
+    "_localKeyMonitor",
+    "_rulerMonitor",
+];
+
+thread_local! {
+    static APP_CONTEXT: RefCell<Option<AppContext>> = const { RefCell::new(None) };
+}
+
+/// The overlay windows and views created at startup, and the timers,
+/// monitors and hotkeys of the host view.
+pub struct AppContext {
+    windows: Vec<Retained<AnyObject>>,
+    views: Vec<Retained<AnyObject>>,
+    watchdog: Option<Retained<AnyObject>>,
+}
+
+impl AppContext {
+    /// Create one overlay window per screen and bring it to the front.
+    /// Returns `None` when there is no screen.
+    ///
+    /// # Safety
+    /// Must be called from main thread with valid autorelease pool.
+    pub unsafe fn create() -> Option<Self> {
+        let screens: id = msg_send![get_class("NSScreen"), screens];
+        let count: usize = msg_send![screens, count];
+        if count == 0 {
+            return None;
+        }
+        let mut windows = Vec::with_capacity(count);
+        let mut views = Vec::with_capacity(count);
+        for i in 0..count {
+            let screen: id = msg_send![screens, objectAtIndex: i];
+            let (win, view) = make_window_for_screen(screen);
+            // Owned (+1) from alloc/init; released when the context drops,
+            // not when the window closes
+            let _: () = msg_send![win, setReleasedWhenClosed: NO];
+            let _: () = msg_send![win, orderFrontRegardless];
+            windows.extend(Retained::<AnyObject>::from_raw(win));
+            views.extend(Retained::<AnyObject>::retain(view));
+        }
+        if views.is_empty() {
+            return None;
+        }
+        Some(Self {
+            windows,
+            views,
+            watchdog: None,
+        })
+    }
+
+    /// The host view: it owns the timers, monitors and status bar item,
+    /// and is never closed.
+    pub fn host(&self) -> id {
+        Retained::as_ptr(&self.views[0]) as id
+    }
+
+    /// Number of overlay windows created at startup.
+    pub fn window_count(&self) -> usize {
+        self.windows.len()
+    }
+
+    /// Keep the watchdog timer, to invalidate it on teardown.
+    ///
+    /// # Safety
+    /// `timer` must be a valid NSTimer.
+    pub unsafe fn own_watchdog(&mut self, timer: id) {
+        self.watchdog = Retained::<AnyObject>::retain(timer);
+    }
+
+    /// Stop the timers, remove the monitors and hotkeys, and close every
+    /// overlay window, including those added for displays connected later.
+    ///
+    /// # Safety
+    /// Must be called from main thread with valid autorelease pool.
+    unsafe fn teardown(self) {
+        let host = self.host();
+        // The watchdog first, so it does not start the refresh timer again
+        if let Some(watchdog) = &self.watchdog {
+            let _: () = msg_send![&**watchdog, invalidate];
+        }
+        for ivar in TIMER_IVARS {
+            let timer: id = *(*host).load_ivar::<id>(ivar);
+            if timer != nil {
+                let _: () = msg_send![timer, invalidate];
+                (*host).store_ivar::<id>(ivar, nil);
+            }
+        }
+        for ivar in MONITOR_IVARS {
+            let monitor: id = *(*host).load_ivar::<id>(ivar);
+            if monitor != nil {
+                let _: () = msg_send![get_class("NSEvent"), removeMonitor: monitor];
+                (*host).store_ivar::<id>(ivar, nil);
+            }
+        }
+        uninstall_hotkeys(host);
+
+        let mut views: Vec<id> = Vec::new();
+        apply_to_all_views(|v| views.push(v));
+        for view in views.iter() {
+            let window: id = msg_send![*view, window];
+            let _: () = msg_send![window, orderOut: nil];
+            let _: () = msg_send![window, close];
+        }
+        info!(
+            "Closed {} overlay windows ({} from startup)",
+            views.len(),
+            self.window_count()
+        );
+        // Dropping `self` releases the startup windows and views
+    }
+}
+
+/// Make `context` the app's, for [`teardown_app_context`].
+pub fn set_app_context(context: AppContext) {
+    APP_CONTEXT.with(|c| *c.borrow_mut() = Some(context));
+}
+
+/// Tear down the app's context (see [`AppContext`]). Does nothing when it
+/// was already torn down, or never set.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn teardown_app_context() {
+    if let Some(context) = APP_CONTEXT.with(|c| c.borrow_mut().take()) {
+        context.teardown();
+    }
+}
@@ -0,0 +1,18 @@
+//! Crash reports for macOS.
+//!
+//! Lumbus runs as a menu bar accessory, so a panic used to end it without
+//! a trace. The panic hook writes a report to `~/Library/Logs/Lumbus/Crashes`
+//! and offers to reveal it.
+
+use crate::logging::install_panic_hook;
+use crate::platform::macos::ffi::os_version;
+use crate::platform::macos::storage::{config_path, log_dir};
+use crate::platform::macos::ui::show_crash_report;
+
+/// Install the panic hook writing crash reports.
+///
+/// # Safety
+/// Must be called with a valid autorelease pool.
+pub unsafe fn install_crash_handler() {
+    install_panic_hook(log_dir(), os_version(), config_path(), show_crash_report);
+}
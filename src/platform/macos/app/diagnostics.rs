@@ -0,0 +1,141 @@
+//! `--diagnostics` report for macOS.
+//!
+//! Gathers the displays, permissions and hotkeys the app sees into a
+//! `model::DiagnosticsReport`, and turns on the overlay's debug label when
+//! asked to. At quit the report is saved again, with the event latency of
+//! the session.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::refresh::refresh_restarts;
+use crate::events::event_latency;
+use crate::logging::write_diagnostics;
+use crate::model::constants::PREF_HOTKEY_PROFILE;
+use crate::model::{
+    DiagnosticsConfig, DiagnosticsReport, DisplayInfo, ScreenRect, HOTKEY_PROFILE_STANDARD,
+};
+use crate::platform::macos::ffi::bridge::{get_class, id, msg_send};
+use crate::platform::macos::ffi::{
+    accessibility_trusted, display_id_for_screen, os_version, CGPreflightListenEventAccess,
+    CGPreflightScreenCaptureAccess,
+};
+use crate::platform::macos::input::{current_hotkey_bindings, hotkey_registered};
+use crate::platform::macos::storage::{config_path, log_dir, prefs_get_string};
+use crate::platform::macos::ui::enable_debug_overlay;
+
+use objc2_foundation::NSRect;
+use tracing::info;
+
+/// Set by [`run_diagnostics`]: save the report again at quit.
+static DIAGNOSTICS_RUN: AtomicBool = AtomicBool::new(false);
+
+fn granted(ok: bool) -> String {
+    if ok { "granted" } else { "NOT granted" }.to_string()
+}
+
+/// Name, events taken, mean and longest wait (ms) of each kind of event.
+fn latency_rows() -> Vec<(String, u64, f64, f64)> {
+    event_latency()
+        .iter()
+        .map(|l| {
+            (
+                l.name.to_string(),
+                l.count,
+                l.mean().as_secs_f64() * 1000.0,
+                l.max.as_secs_f64() * 1000.0,
+            )
+        })
+        .collect()
+}
+
+/// Collect the diagnostics report.
+///
+/// # Safety
+/// - `view` must be a valid, non-null pointer to a CustomViewMulti.
+/// - Must be called from main thread with valid autorelease pool.
+pub unsafe fn diagnostics_report(view: id) -> DiagnosticsReport {
+    let screens: id = msg_send![get_class("NSScreen"), screens];
+    let count: usize = msg_send![screens, count];
+    let displays = (0..count)
+        .map(|i| {
+            let screen: id = msg_send![screens, objectAtIndex: i];
+            let f: NSRect = msg_send![screen, frame];
+            let scale: f64 = msg_send![screen, backingScaleFactor];
+            DisplayInfo {
+                id: display_id_for_screen(screen),
+                frame: ScreenRect::new(f.origin.x, f.origin.y, f.size.width, f.size.height),
+                scale,
+                // The first screen holds the menu bar
+                primary: i == 0,
+            }
+        })
+        .collect();
+    let refresh = match refresh_restarts() {
+        0 => "running".to_string(),
+        n => format!("running (restarted {} times)", n),
+    };
+
+    DiagnosticsReport {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        os: os_version(),
+        displays,
+        permissions: vec![
+            ("Accessibility".into(), granted(accessibility_trusted())),
+            (
+                "Input Monitoring".into(),
+                granted(CGPreflightListenEventAccess()),
+            ),
+            (
+                "Screen Recording".into(),
+                granted(CGPreflightScreenCaptureAccess()),
+            ),
+            ("Refresh timer".into(), refresh),
+        ],
+        hotkey_profile: prefs_get_string(PREF_HOTKEY_PROFILE, HOTKEY_PROFILE_STANDARD),
+        hotkeys: current_hotkey_bindings()
+            .iter()
+            .map(|b| {
+                (
+                    b.hotkey.label(),
+                    b.action.description().to_string(),
+                    hotkey_registered(view, b.action),
+                )
+            })
+            .collect(),
+        event_latency: latency_rows(),
+        config_path: config_path().display().to_string(),
+        log_dir: log_dir().display().to_string(),
+    }
+}
+
+/// Print and save the diagnostics report, and draw the debug label if
+/// `config` asks for it.
+///
+/// # Safety
+/// - `view` must be a valid, non-null pointer to a CustomViewMulti.
+/// - Must be called from main thread with valid autorelease pool.
+pub unsafe fn run_diagnostics(view: id, config: DiagnosticsConfig) {
+    write_diagnostics(&diagnostics_report(view), &log_dir());
+    DIAGNOSTICS_RUN.store(true, Ordering::SeqCst);
+    if config.overlay {
+        enable_debug_overlay();
+    }
+}
+
+/// Log the event latency of the session, and save the diagnostics report
+/// again if `--diagnostics` was given. Called at termination.
+///
+/// # Safety
+/// - `view` must be a valid, non-null pointer to a CustomViewMulti.
+/// - Must be called from main thread with valid autorelease pool.
+pub unsafe fn finish_diagnostics(view: id) {
+    for (name, count, mean_ms, max_ms) in latency_rows() {
+        info!(
+            "Event {}: {} taken, mean {:.1} ms, max {:.1} ms",
+            name, count, mean_ms, max_ms
+        );
+    }
+    if DIAGNOSTICS_RUN.load(Ordering::SeqCst) {
+        write_diagnostics(&diagnostics_report(view), &log_dir());
+    }
+}
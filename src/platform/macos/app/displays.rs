@@ -0,0 +1,182 @@
+//! Overlay windows per display.
+//!
+//! Each connected screen gets its own transparent overlay window. When
+//! screens are connected, disconnected, resized or rearranged,
+//! `sync_overlay_windows` brings the overlays in line with the new screen
+//! list (see `model::displays`) and their current frames.
+
+use crate::model::constants::PREF_EXCLUDE_FROM_CAPTURE;
+use crate::model::displays::plan_overlays;
+use crate::platform::macos::app::helpers::{apply_to_all_views, sync_visual_prefs_to_all_views};
+use crate::platform::macos::ffi::bridge::{
+    get_bool_ivar, get_class, id, msg_send, nil, set_bool_ivar, NSRect, ObjectExt, NO, YES,
+};
+use crate::platform::macos::ffi::{display_id_for_screen, overlay_window_level};
+use crate::platform::macos::storage::prefs_get_int;
+use crate::platform::macos::ui::register_and_create_view;
+
+/// Create a transparent overlay window for a given screen.
+///
+/// # Safety
+/// The screen must be a valid NSScreen instance.
+pub unsafe fn make_window_for_screen(screen: id) -> (id, id) {
+    let frame: NSRect = msg_send![screen, frame];
+
+    // NSBorderlessWindowMask = 0
+    let style_mask: u64 = 0;
+    // NSBackingStoreBuffered = 2
+    let backing: u64 = 2;
+
+    let window: id = msg_send![get_class("NSWindow"), alloc];
+    let window: id = msg_send![
+        window,
+        initWithContentRect: frame,
+        styleMask: style_mask,
+        backing: backing,
+        defer: NO
+    ];
+
+    let _: () = msg_send![window, setOpaque: NO];
+
+    // Get clear color
+    let clear_color: id = msg_send![get_class("NSColor"), clearColor];
+    let _: () = msg_send![window, setBackgroundColor: clear_color];
+
+    let _: () = msg_send![window, setIgnoresMouseEvents: YES];
+    let _: () = msg_send![window, setAcceptsMouseMovedEvents: YES];
+    let _: () = msg_send![window, setLevel: overlay_window_level()];
+
+    // NSWindowCollectionBehaviorCanJoinAllSpaces = 1 << 0 = 1
+    // NSWindowCollectionBehaviorFullScreenAuxiliary = 1 << 8 = 256
+    // NSWindowCollectionBehaviorStationary = 1 << 4 = 16
+    let collection_behavior: u64 = 1 | 256 | 16;
+    let _: () = msg_send![window, setCollectionBehavior: collection_behavior];
+    let _: () = msg_send![window, setSharingType: sharing_type()];
+
+    let view: id = register_and_create_view(window, frame.size.width, frame.size.height);
+    assign_screen(view, screen);
+
+    (window, view)
+}
+
+/// Sharing type of the overlay windows: `NSWindowSharingNone` while they
+/// are kept out of screen captures, `NSWindowSharingReadOnly` otherwise.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+unsafe fn sharing_type() -> u64 {
+    if prefs_get_int(PREF_EXCLUDE_FROM_CAPTURE, 0) != 0 {
+        0
+    } else {
+        1
+    }
+}
+
+/// Keep the overlay windows out of screenshots and screen recordings, or
+/// let them be captured again, as `PREF_EXCLUDE_FROM_CAPTURE` says.
+///
+/// Capture tools that ignore the sharing type (ScreenCaptureKit ones on
+/// macOS 15 and later) still see the overlay.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn apply_capture_exclusion() {
+    let sharing = sharing_type();
+    apply_to_all_views(|v| {
+        let window: id = msg_send![v, window];
+        let _: () = msg_send![window, setSharingType: sharing];
+    });
+}
+
+/// Make `view` the overlay of `screen`, covering its whole frame.
+///
+/// # Safety
+/// `view` must be a CustomViewMulti and `screen` a valid NSScreen.
+unsafe fn assign_screen(view: id, screen: id) {
+    let frame: NSRect = msg_send![screen, frame];
+    let window: id = msg_send![view, window];
+    let _: () = msg_send![window, setFrame: frame, display: NO];
+    (*view).store_ivar::<id>("_ownScreen", screen);
+    // Store stable DisplayID for this view's screen
+    (*view).store_ivar::<u32>("_ownDisplayID", display_id_for_screen(screen));
+}
+
+/// Close the overlay window of `view`.
+///
+/// # Safety
+/// `view` must be a CustomViewMulti other than the host view.
+unsafe fn close_overlay(view: id) {
+    let window: id = msg_send![view, window];
+    let _: () = msg_send![window, orderOut: nil];
+    // Without a content view the window is skipped by apply_to_all_views
+    let _: () = msg_send![window, setContentView: nil];
+    let _: () = msg_send![window, close];
+}
+
+/// Create, move, resize or close overlay windows so each screen has
+/// exactly one, covering its current frame.
+///
+/// Called when screens are connected or disconnected and when their
+/// resolution, scaling or arrangement changes. New overlays take the
+/// visual settings and state of `host`, which is never closed (it owns the
+/// timers, monitors and status bar item).
+///
+/// # Safety
+/// - `host` must be a valid, non-null pointer to a CustomViewMulti.
+/// - Must be called from main thread with valid autorelease pool.
+pub unsafe fn sync_overlay_windows(host: id) {
+    let screens: id = msg_send![get_class("NSScreen"), screens];
+    let count: usize = msg_send![screens, count];
+    let screens: Vec<id> = (0..count)
+        .map(|i| msg_send![screens, objectAtIndex: i])
+        .collect();
+    let display_ids: Vec<u32> = screens.iter().map(|&s| display_id_for_screen(s)).collect();
+
+    let mut views: Vec<id> = Vec::new();
+    apply_to_all_views(|v| views.push(v));
+    let Some(host_index) = views.iter().position(|&v| v == host) else {
+        return;
+    };
+    let view_ids: Vec<u32> = views
+        .iter()
+        .map(|&v| *(*v).load_ivar::<u32>("_ownDisplayID"))
+        .collect();
+
+    let plan = plan_overlays(&view_ids, &display_ids, host_index);
+    for &view in plan.remove.iter() {
+        close_overlay(views[view]);
+    }
+    for &(view, screen) in plan.moves.iter() {
+        assign_screen(views[view], screens[screen]);
+    }
+    for &screen in plan.create.iter() {
+        let (window, _view) = make_window_for_screen(screens[screen]);
+        let _: () = msg_send![window, orderFrontRegardless];
+    }
+
+    // Kept overlays follow resolution, scaling and arrangement changes
+    apply_to_all_views(|v| {
+        let own_id = *(*v).load_ivar::<u32>("_ownDisplayID");
+        let Some(i) = display_ids.iter().position(|&d| d == own_id) else {
+            return;
+        };
+        let window: id = msg_send![v, window];
+        let current: NSRect = msg_send![window, frame];
+        let frame: NSRect = msg_send![screens[i], frame];
+        let own_screen = *(*v).load_ivar::<id>("_ownScreen");
+        if current != frame || own_screen != screens[i] {
+            assign_screen(v, screens[i]);
+        }
+    });
+
+    // New overlays follow the host's settings and current state
+    sync_visual_prefs_to_all_views(host);
+    let enabled = get_bool_ivar(host, "_overlayEnabled");
+    let reduce_motion = get_bool_ivar(host, "_systemReduceMotion");
+    apply_to_all_views(|v| {
+        set_bool_ivar(v, "_overlayEnabled", enabled);
+        set_bool_ivar(v, "_systemReduceMotion", reduce_motion);
+    });
+    // Force a redraw on the next tick, on whichever screen has the cursor
+    (*host).store_ivar::<f64>("_lastCursorX", f64::MIN);
+}
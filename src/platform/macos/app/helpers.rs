@@ -3,13 +3,15 @@
 //! This module contains helper functions that operate on all views
 //! and are used across multiple modules (input, ui).
 
-use crate::platform::macos::ffi::bridge::{get_class, id, msg_send, nil, NSApp, ObjectExt};
+use crate::model::OverlayState;
+use crate::platform::macos::ffi::bridge::{get_class, id, msg_send, nil, NSApp, ObjectExt, YES};
+use crate::platform::macos::ui::{set_click_letters, set_letter_font};
 
 /// Apply a closure to every contentView whose class is CustomViewMulti.
 ///
 /// # Safety
 /// Must be called from main thread with valid autorelease pool.
-pub unsafe fn apply_to_all_views<F: Fn(id)>(f: F) {
+pub unsafe fn apply_to_all_views<F: FnMut(id)>(mut f: F) {
     let app: id = NSApp();
     let windows: id = msg_send![app, windows];
     let wcount: usize = msg_send![windows, count];
@@ -41,6 +43,36 @@ pub unsafe fn sync_visual_prefs_to_all_views(src: id) {
     let a = *(*src).load_ivar::<f64>("_strokeA");
     let fill_t = *(*src).load_ivar::<f64>("_fillTransparencyPct");
     let lang = *(*src).load_ivar::<i32>("_lang");
+    let style = *(*src).load_ivar::<i32>("_highlightStyle");
+    let frame = *(*src).load_ivar::<u8>("_screenFrame");
+    let speed_fade = *(*src).load_ivar::<u8>("_speedFade");
+    let speed_size = *(*src).load_ivar::<u8>("_speedSize");
+    let click_burst = *(*src).load_ivar::<u8>("_clickBurst");
+    let click_burst_ms = *(*src).load_ivar::<f64>("_clickBurstMs");
+    let idle_hide = *(*src).load_ivar::<u8>("_idleHide");
+    let idle_hide_secs = *(*src).load_ivar::<f64>("_idleHideSecs");
+    let click_hold_ms = *(*src).load_ivar::<f64>("_clickHoldMs");
+    let hold_ring = *(*src).load_ivar::<u8>("_holdRing");
+    let hold_ring_ms = *(*src).load_ivar::<f64>("_holdRingMs");
+    let show_coordinates = *(*src).load_ivar::<u8>("_showCoordinates");
+    let show_grid = *(*src).load_ivar::<u8>("_showGrid");
+    let grid_spacing = *(*src).load_ivar::<f64>("_gridSpacing");
+    let grid_subdivisions = *(*src).load_ivar::<i32>("_gridSubdivisions");
+    let grid_r = *(*src).load_ivar::<f64>("_gridR");
+    let grid_g = *(*src).load_ivar::<f64>("_gridG");
+    let grid_b = *(*src).load_ivar::<f64>("_gridB");
+    let grid_a = *(*src).load_ivar::<f64>("_gridA");
+    let color_sampler = *(*src).load_ivar::<u8>("_colorSampler");
+    let motion = *(*src).load_ivar::<i32>("_motion");
+    let halo = *(*src).load_ivar::<u8>("_halo");
+    let glow = *(*src).load_ivar::<i32>("_glow");
+    let click_icon = *(*src).load_ivar::<i32>("_clickIcon");
+    let pulse = *(*src).load_ivar::<u8>("_pulse");
+    let pulse_amplitude = *(*src).load_ivar::<f64>("_pulseAmplitude");
+    let pulse_period_ms = *(*src).load_ivar::<f64>("_pulsePeriodMs");
+    let transition_ms = *(*src).load_ivar::<f64>("_transitionMs");
+    let rainbow = *(*src).load_ivar::<u8>("_rainbow");
+    let rainbow_speed = *(*src).load_ivar::<f64>("_rainbowSpeed");
 
     apply_to_all_views(|v| {
         (*v).store_ivar::<f64>("_radius", radius);
@@ -51,14 +83,82 @@ pub unsafe fn sync_visual_prefs_to_all_views(src: id) {
         (*v).store_ivar::<f64>("_strokeA", a);
         (*v).store_ivar::<f64>("_fillTransparencyPct", fill_t);
         (*v).store_ivar::<i32>("_lang", lang);
+        (*v).store_ivar::<i32>("_highlightStyle", style);
+        (*v).store_ivar::<u8>("_screenFrame", frame);
+        (*v).store_ivar::<u8>("_speedFade", speed_fade);
+        (*v).store_ivar::<u8>("_speedSize", speed_size);
+        (*v).store_ivar::<u8>("_clickBurst", click_burst);
+        (*v).store_ivar::<f64>("_clickBurstMs", click_burst_ms);
+        (*v).store_ivar::<u8>("_idleHide", idle_hide);
+        (*v).store_ivar::<f64>("_idleHideSecs", idle_hide_secs);
+        (*v).store_ivar::<f64>("_clickHoldMs", click_hold_ms);
+        (*v).store_ivar::<u8>("_holdRing", hold_ring);
+        (*v).store_ivar::<f64>("_holdRingMs", hold_ring_ms);
+        (*v).store_ivar::<u8>("_showCoordinates", show_coordinates);
+        (*v).store_ivar::<u8>("_showGrid", show_grid);
+        (*v).store_ivar::<f64>("_gridSpacing", grid_spacing);
+        (*v).store_ivar::<i32>("_gridSubdivisions", grid_subdivisions);
+        (*v).store_ivar::<f64>("_gridR", grid_r);
+        (*v).store_ivar::<f64>("_gridG", grid_g);
+        (*v).store_ivar::<f64>("_gridB", grid_b);
+        (*v).store_ivar::<f64>("_gridA", grid_a);
+        (*v).store_ivar::<u8>("_colorSampler", color_sampler);
+        (*v).store_ivar::<i32>("_motion", motion);
+        (*v).store_ivar::<u8>("_halo", halo);
+        (*v).store_ivar::<i32>("_glow", glow);
+        (*v).store_ivar::<i32>("_clickIcon", click_icon);
+        (*v).store_ivar::<u8>("_pulse", pulse);
+        (*v).store_ivar::<f64>("_pulseAmplitude", pulse_amplitude);
+        (*v).store_ivar::<f64>("_pulsePeriodMs", pulse_period_ms);
+        (*v).store_ivar::<f64>("_transitionMs", transition_ms);
+        (*v).store_ivar::<u8>("_rainbow", rainbow);
+        (*v).store_ivar::<f64>("_rainbowSpeed", rainbow_speed);
     });
 }
 
-/// Check if current language is Spanish.
+/// Apply the visual prefs of `state` to all views and redraw them.
 ///
 /// # Safety
-/// `view` must be a valid, non-null pointer to an NSView with `_lang` ivar.
-pub unsafe fn lang_is_es(view: id) -> bool {
-    let l = *(*view).load_ivar::<i32>("_lang");
-    l == 1
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn apply_state_to_all_views(state: &OverlayState) {
+    apply_to_all_views(|v| {
+        (*v).store_ivar::<f64>("_radius", state.radius);
+        (*v).store_ivar::<f64>("_borderWidth", state.border_width);
+        (*v).store_ivar::<f64>("_strokeR", state.stroke_r);
+        (*v).store_ivar::<f64>("_strokeG", state.stroke_g);
+        (*v).store_ivar::<f64>("_strokeB", state.stroke_b);
+        (*v).store_ivar::<f64>("_strokeA", state.stroke_a);
+        (*v).store_ivar::<f64>("_fillTransparencyPct", state.fill_transparency_pct);
+        (*v).store_ivar::<i32>("_lang", state.lang);
+        (*v).store_ivar::<i32>("_highlightStyle", state.highlight_style);
+        (*v).store_ivar::<u8>("_screenFrame", state.screen_frame as u8);
+        (*v).store_ivar::<u8>("_speedFade", state.speed_fade as u8);
+        (*v).store_ivar::<u8>("_speedSize", state.speed_size as u8);
+        (*v).store_ivar::<u8>("_clickBurst", state.click_burst as u8);
+        (*v).store_ivar::<f64>("_clickBurstMs", state.click_burst_ms);
+        (*v).store_ivar::<u8>("_idleHide", state.idle_hide as u8);
+        (*v).store_ivar::<f64>("_idleHideSecs", state.idle_hide_secs);
+        (*v).store_ivar::<f64>("_clickHoldMs", state.click_hold_ms);
+        (*v).store_ivar::<u8>("_holdRing", state.hold_ring as u8);
+        (*v).store_ivar::<f64>("_holdRingMs", state.hold_ring_ms);
+        (*v).store_ivar::<f64>("_gridSpacing", state.grid_spacing);
+        (*v).store_ivar::<i32>("_gridSubdivisions", state.grid_subdivisions);
+        (*v).store_ivar::<f64>("_gridR", state.grid_r);
+        (*v).store_ivar::<f64>("_gridG", state.grid_g);
+        (*v).store_ivar::<f64>("_gridB", state.grid_b);
+        (*v).store_ivar::<f64>("_gridA", state.grid_a);
+        (*v).store_ivar::<i32>("_motion", state.motion);
+        (*v).store_ivar::<u8>("_halo", state.halo as u8);
+        (*v).store_ivar::<i32>("_glow", state.glow);
+        (*v).store_ivar::<i32>("_clickIcon", state.click_icon);
+        (*v).store_ivar::<u8>("_pulse", state.pulse as u8);
+        (*v).store_ivar::<f64>("_pulseAmplitude", state.pulse_amplitude);
+        (*v).store_ivar::<f64>("_pulsePeriodMs", state.pulse_period_ms);
+        (*v).store_ivar::<f64>("_transitionMs", state.transition_ms);
+        (*v).store_ivar::<u8>("_rainbow", state.rainbow as u8);
+        (*v).store_ivar::<f64>("_rainbowSpeed", state.rainbow_speed);
+        let _: () = msg_send![v, setNeedsDisplay: YES];
+    });
+    set_click_letters(&state.click_letter_left, &state.click_letter_right);
+    set_letter_font(&state.letter_font, state.letter_weight);
 }
@@ -1,8 +1,34 @@
 //! Application-wide shared functionality.
 //!
 //! This module contains helpers that are used across multiple modules
-//! (input, ui) and operate on the global application state.
+//! (input, ui) and operate on the global application state, the overlay
+//! window of each display, their refresh timer and the `OverlayBackend`
+//! over them. `AppContext` owns what lives as long as the app.
 
+pub mod backend;
+pub mod context;
+pub mod crash;
+pub mod diagnostics;
+pub mod displays;
 pub mod helpers;
+pub mod permissions;
+pub mod refresh;
+pub mod soak;
+pub mod update;
 
-pub use helpers::{apply_to_all_views, lang_is_es, sync_visual_prefs_to_all_views};
+pub use backend::MacBackend;
+pub use context::{set_app_context, teardown_app_context, AppContext};
+pub use crash::install_crash_handler;
+pub use diagnostics::{diagnostics_report, finish_diagnostics, run_diagnostics};
+pub use displays::{apply_capture_exclusion, make_window_for_screen, sync_overlay_windows};
+pub use helpers::{apply_state_to_all_views, apply_to_all_views, sync_visual_prefs_to_all_views};
+pub use permissions::{
+    check_permissions, open_permission_settings, permission_granted, permission_status,
+    request_permission,
+};
+pub use refresh::{refresh_restarts, refresh_ticked, start_refresh_timer, start_timer_watchdog};
+pub use soak::start_soak_test;
+pub use update::{
+    available_update, check_for_updates, offer_update, open_release_page, start_update_checks,
+    update_checked,
+};
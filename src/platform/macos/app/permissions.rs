@@ -0,0 +1,94 @@
+//! Permissions the app needs on macOS, and the features turned off while
+//! they are missing (see `model::permissions`).
+
+use std::cell::Cell;
+
+use tracing::{info, warn};
+
+use crate::model::{Permission, PermissionStatus};
+use crate::platform::macos::ffi::bridge::{get_class, id, msg_send, nil, nsstring_id};
+use crate::platform::macos::ffi::{
+    accessibility_trusted, ensure_accessibility_prompt, CGPreflightScreenCaptureAccess,
+    CGRequestScreenCaptureAccess,
+};
+
+thread_local! {
+    /// Status as last checked (main thread only).
+    static STATUS: Cell<PermissionStatus> = Cell::new(PermissionStatus::default());
+}
+
+/// Permissions as last checked by [`check_permissions`].
+pub fn permission_status() -> PermissionStatus {
+    STATUS.with(|s| s.get())
+}
+
+/// Is `permission` granted right now? Never prompts.
+///
+/// # Safety
+/// Must be called from main thread.
+pub unsafe fn permission_granted(permission: Permission) -> bool {
+    match permission {
+        Permission::Accessibility => accessibility_trusted(),
+        Permission::ScreenRecording => CGPreflightScreenCaptureAccess(),
+    }
+}
+
+/// Check the permissions again, logging which features go off or back on.
+///
+/// # Safety
+/// Must be called from main thread.
+pub unsafe fn check_permissions() -> PermissionStatus {
+    let status = PermissionStatus {
+        accessibility: permission_granted(Permission::Accessibility),
+        screen_recording: permission_granted(Permission::ScreenRecording),
+    };
+    let previous = STATUS.with(|s| s.replace(status));
+    for permission in Permission::ALL {
+        match (previous.granted(permission), status.granted(permission)) {
+            (true, false) => warn!(
+                permission = permission.label(),
+                "Permission missing; {} turned off",
+                permission.feature()
+            ),
+            (false, true) => info!(
+                permission = permission.label(),
+                "Permission granted; {} turned on",
+                permission.feature()
+            ),
+            _ => {}
+        }
+    }
+    status
+}
+
+/// Ask for `permission`: the system prompt, shown once per permission.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn request_permission(permission: Permission) {
+    match permission {
+        Permission::Accessibility => ensure_accessibility_prompt(),
+        Permission::ScreenRecording => {
+            CGRequestScreenCaptureAccess();
+        }
+    }
+}
+
+/// Open the Privacy & Security pane of System Settings granting
+/// `permission`.
+///
+/// # Safety
+/// Must be called with a valid autorelease pool.
+pub unsafe fn open_permission_settings(permission: Permission) {
+    let anchor = match permission {
+        Permission::Accessibility => "Privacy_Accessibility",
+        Permission::ScreenRecording => "Privacy_ScreenCapture",
+    };
+    let link = format!("x-apple.systempreferences:com.apple.preference.security?{anchor}");
+    let url: id = msg_send![get_class("NSURL"), URLWithString: nsstring_id(&link)];
+    if url == nil {
+        return;
+    }
+    let workspace: id = msg_send![get_class("NSWorkspace"), sharedWorkspace];
+    let _: bool = msg_send![workspace, openURL: url];
+}
@@ -0,0 +1,97 @@
+//! Refresh timer and its watchdog.
+//!
+//! A ~60 FPS timer calls the host view's `update_cursor_multi`, which
+//! moves the highlight and dispatches the event bus. A slower timer
+//! checks that it still runs and creates it again when AppKit has
+//! invalidated it (see `model::timer_watchdog`).
+
+use std::cell::RefCell;
+use std::time::Instant;
+
+use block2::RcBlock;
+use tracing::warn;
+
+use crate::model::constants::{TIMER_WATCHDOG_CHECK_SECS, TIMER_WATCHDOG_SECS};
+use crate::model::TimerWatchdog;
+use crate::platform::macos::ffi::bridge::{
+    get_class, id, msg_send, nil, nsstring_id, sel, ObjectExt, YES,
+};
+
+/// Seconds between two refreshes (~60 FPS).
+const REFRESH_SECS: f64 = 0.016;
+
+thread_local! {
+    static WATCHDOG: RefCell<TimerWatchdog> = RefCell::new(TimerWatchdog::new());
+    /// Start of the watchdog's clock (monotonic, unlike the wall clock).
+    static EPOCH: Instant = Instant::now();
+}
+
+/// Seconds on the watchdog's clock.
+fn now() -> f64 {
+    EPOCH.with(|e| e.elapsed().as_secs_f64())
+}
+
+/// Create the refresh timer of `view`, replacing the previous one. It
+/// keeps firing during menus and modal windows.
+///
+/// # Safety
+/// - `view` must be a valid, non-null pointer to a CustomViewMulti.
+/// - Must be called from main thread with valid autorelease pool.
+pub unsafe fn start_refresh_timer(view: id) {
+    let prev: id = *(*view).load_ivar::<id>("_updateTimer");
+    if prev != nil {
+        let _: () = msg_send![prev, invalidate];
+        (*view).store_ivar::<id>("_updateTimer", nil);
+    }
+    // Create timer without auto-scheduling
+    let timer: id = msg_send![
+        get_class("NSTimer"),
+        timerWithTimeInterval: REFRESH_SECS,
+        target: view,
+        selector: sel!(update_cursor_multi),
+        userInfo: nil,
+        repeats: YES
+    ];
+    // Add to run loop with CommonModes (keeps running during menus)
+    let run_loop: id = msg_send![get_class("NSRunLoop"), currentRunLoop];
+    let common_modes = nsstring_id("kCFRunLoopCommonModes");
+    let _: () = msg_send![run_loop, addTimer: timer, forMode: common_modes];
+
+    (*view).store_ivar::<id>("_updateTimer", timer);
+}
+
+/// Record a run of the refresh timer, for the watchdog.
+pub fn refresh_ticked() {
+    WATCHDOG.with(|w| w.borrow_mut().tick(now()));
+}
+
+/// Times the refresh timer was found stalled and created again.
+pub fn refresh_restarts() -> u32 {
+    WATCHDOG.with(|w| w.borrow().restarts())
+}
+
+/// Check every `TIMER_WATCHDOG_CHECK_SECS` that the refresh timer of
+/// `view` still runs, and create it again if it stalled. Returns the
+/// watchdog's timer.
+///
+/// # Safety
+/// - `view` must be a valid, non-null pointer to a CustomViewMulti.
+/// - Must be called from main thread with valid autorelease pool.
+pub unsafe fn start_timer_watchdog(view: id) -> id {
+    let block = RcBlock::new(move |_timer: id| unsafe {
+        let stalled = WATCHDOG.with(|w| w.borrow_mut().check(now()));
+        if stalled {
+            warn!(
+                "Refresh timer did not run for {}s, restarting it",
+                TIMER_WATCHDOG_SECS
+            );
+            start_refresh_timer(view);
+        }
+    });
+    msg_send![
+        get_class("NSTimer"),
+        scheduledTimerWithTimeInterval: TIMER_WATCHDOG_CHECK_SECS,
+        repeats: YES,
+        block: &*block
+    ]
+}
@@ -0,0 +1,192 @@
+//! Hidden soak-test mode (`--soak[=minutes]`).
+//!
+//! Runs the scripted actions of `model::soak` against the live overlay and
+//! logs resource samples to stderr and to `SOAK_REPORT_FILE` in the temp
+//! directory. The churned preferences are restored before quitting.
+
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::Write;
+
+use block2::RcBlock;
+use tracing::info;
+
+use crate::events::{pending_events, publish, AppEvent};
+use crate::model::constants::*;
+use crate::model::{ScreenRect, SoakAction, SoakConfig, SoakReport, SoakSample, SoakScript};
+use crate::platform::macos::app::apply_to_all_views;
+use crate::platform::macos::ffi::bridge::{
+    get_class, id, msg_send, nil, nsstring_id, ObjectExt, YES,
+};
+use crate::platform::macos::ffi::{
+    open_descriptor_count, resident_memory_bytes, CFAbsoluteTimeGetCurrent,
+    CGWarpMouseCursorPosition,
+};
+use crate::platform::macos::input::installed_hotkey_count;
+use crate::platform::macos::storage::{
+    prefs_get_double, prefs_get_int, prefs_set_double, prefs_set_int,
+};
+
+use objc2_foundation::{NSPoint, NSRect};
+
+/// A soak test in progress.
+struct SoakRun {
+    config: SoakConfig,
+    script: SoakScript,
+    report: SoakReport,
+    start: f64,
+    next_sample: f64,
+    saved_radius: f64,
+    saved_style: i32,
+    log: Option<File>,
+}
+
+impl SoakRun {
+    /// Writes a line to the log and the report file.
+    fn log(&mut self, line: &str) {
+        info!(target: "soak", "{}", line);
+        if let Some(file) = self.log.as_mut() {
+            let _ = writeln!(file, "{}", line);
+        }
+    }
+}
+
+thread_local! {
+    static SOAK: RefCell<Option<SoakRun>> = const { RefCell::new(None) };
+}
+
+/// Start the soak test on a repeating timer.
+///
+/// # Safety
+/// - `view` must be a valid, non-null pointer to a CustomViewMulti.
+/// - Must be called from main thread with valid autorelease pool.
+pub unsafe fn start_soak_test(view: id, config: SoakConfig) {
+    let path = std::env::temp_dir().join(SOAK_REPORT_FILE);
+    let start = CFAbsoluteTimeGetCurrent();
+    let mut run = SoakRun {
+        config,
+        script: SoakScript::new(),
+        report: SoakReport::new(),
+        start,
+        next_sample: start,
+        saved_radius: prefs_get_double(PREF_RADIUS, DEFAULT_DIAMETER / 2.0),
+        saved_style: prefs_get_int(PREF_HIGHLIGHT_STYLE, HIGHLIGHT_STYLE_CIRCLE),
+        log: File::create(&path).ok(),
+    };
+    run.log(&format!(
+        "[soak] running for {:.0} min, report: {}",
+        config.duration_secs / 60.0,
+        path.display()
+    ));
+    SOAK.with(|s| *s.borrow_mut() = Some(run));
+
+    let block = RcBlock::new(move |_timer: id| unsafe {
+        soak_tick(view);
+    });
+    let _: id = msg_send![
+        get_class("NSTimer"),
+        scheduledTimerWithTimeInterval: SOAK_TICK_SECS,
+        repeats: YES,
+        block: &*block
+    ];
+}
+
+/// Run one scripted step, sample resources when due and finish when the
+/// duration is over.
+unsafe fn soak_tick(view: id) {
+    let now = CFAbsoluteTimeGetCurrent();
+    let screen: id = msg_send![get_class("NSScreen"), mainScreen];
+    let f: NSRect = msg_send![screen, frame];
+    let area = ScreenRect::new(0.0, 0.0, f.size.width, f.size.height);
+
+    let Some((actions, elapsed, sample_due, finished)) = SOAK.with(|s| {
+        let mut s = s.borrow_mut();
+        let run = s.as_mut()?;
+        let elapsed = now - run.start;
+        let finished = elapsed >= run.config.duration_secs;
+        let sample_due = finished || now >= run.next_sample;
+        if sample_due {
+            run.next_sample = now + SOAK_SAMPLE_SECS;
+        }
+        let actions = if finished {
+            Vec::new()
+        } else {
+            run.script.next_actions(&area)
+        };
+        for action in &actions {
+            run.report.count(action);
+        }
+        Some((actions, elapsed, sample_due, finished))
+    }) else {
+        return;
+    };
+
+    for action in actions {
+        perform(action);
+    }
+
+    if sample_due {
+        let sample = SoakSample {
+            elapsed_secs: elapsed,
+            memory_bytes: resident_memory_bytes(),
+            handles: open_descriptor_count(),
+            gui_objects: None,
+            queue_depth: Some(pending_events()),
+            hotkeys: Some(installed_hotkey_count(view)),
+        };
+        SOAK.with(|s| {
+            if let Some(run) = s.borrow_mut().as_mut() {
+                run.report.record(sample);
+                run.log(&sample.log_line());
+            }
+        });
+    }
+
+    if finished {
+        finish();
+    }
+}
+
+/// Apply one scripted action to the running app.
+unsafe fn perform(action: SoakAction) {
+    match action {
+        SoakAction::MoveCursor { x, y } => {
+            let _ = CGWarpMouseCursorPosition(NSPoint::new(x, y));
+        }
+        SoakAction::ToggleOverlay => publish(AppEvent::ToggleOverlay),
+        SoakAction::ChurnSettings {
+            radius,
+            highlight_style,
+        } => {
+            prefs_set_double(PREF_RADIUS, radius);
+            prefs_set_int(PREF_HIGHLIGHT_STYLE, highlight_style);
+            apply_to_all_views(|v| {
+                (*v).store_ivar::<f64>("_radius", radius);
+                (*v).store_ivar::<i32>("_highlightStyle", highlight_style);
+                let _: () = msg_send![v, setNeedsDisplay: YES];
+            });
+        }
+        SoakAction::SpaceChange => {
+            // Same notification Mission Control posts, so the real observers run
+            let ws: id = msg_send![get_class("NSWorkspace"), sharedWorkspace];
+            let nc: id = msg_send![ws, notificationCenter];
+            let name = nsstring_id("NSWorkspaceActiveSpaceDidChangeNotification");
+            let _: () = msg_send![nc, postNotificationName: name, object: ws];
+        }
+    }
+}
+
+/// Log the summary, restore the churned preferences and quit.
+unsafe fn finish() {
+    let Some(mut run) = SOAK.with(|s| s.borrow_mut().take()) else {
+        return;
+    };
+    let summary = run.report.summary();
+    run.log(summary.trim_end());
+
+    prefs_set_double(PREF_RADIUS, run.saved_radius);
+    prefs_set_int(PREF_HIGHLIGHT_STYLE, run.saved_style);
+
+    let app: id = msg_send![get_class("NSApplication"), sharedApplication];
+    let _: () = msg_send![app, terminate: nil];
+}
@@ -0,0 +1,214 @@
+//! Update check: fetches the release feed with NSURLSession.
+//!
+//! The request runs on the session's own queue; its outcome comes back
+//! through the event bus as `AppEvent::UpdateChecked`, and an available
+//! update is then offered at the top of the status bar menu.
+
+use std::cell::RefCell;
+
+use block2::RcBlock;
+use tracing::{info, warn};
+
+use crate::events::{self, AppEvent};
+use crate::i18n::{tr, tr_args};
+use crate::model::click_log::unix_time_now;
+use crate::model::constants::{
+    PREF_LAST_UPDATE_CHECK, PREF_UPDATE_CHECK, UPDATE_FEED_URL, UPDATE_POLL_SECS,
+};
+use crate::model::{check_due, update_status, Release, UpdateStatus};
+use crate::platform::macos::ffi::bridge::{get_class, id, msg_send, nil, nsstring_id, NSApp, YES};
+use crate::platform::macos::storage::{
+    flush_config, prefs_get_double, prefs_get_int, prefs_set_double,
+};
+
+/// Version of the running app.
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Give up on the feed after this long, in seconds.
+const FETCH_TIMEOUT_SECS: f64 = 30.0;
+
+/// `NSAlertFirstButtonReturn`.
+const FIRST_BUTTON: i64 = 1000;
+
+thread_local! {
+    /// Newer release found by the last check, if any.
+    static AVAILABLE: RefCell<Option<Release>> = const { RefCell::new(None) };
+}
+
+/// Newer release found by the last check, shown in the status bar menu.
+pub fn available_update() -> Option<Release> {
+    AVAILABLE.with(|a| a.borrow().clone())
+}
+
+/// Check now if automatic checks are on and the last one is a day old,
+/// then look again every `UPDATE_POLL_SECS`.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn start_update_checks() {
+    check_if_due();
+    let block = RcBlock::new(move |_timer: id| unsafe {
+        check_if_due();
+    });
+    let _: id = msg_send![
+        get_class("NSTimer"),
+        scheduledTimerWithTimeInterval: UPDATE_POLL_SECS,
+        repeats: YES,
+        block: &*block
+    ];
+}
+
+unsafe fn check_if_due() {
+    let enabled = prefs_get_int(PREF_UPDATE_CHECK, 1) != 0;
+    let last_check = prefs_get_double(PREF_LAST_UPDATE_CHECK, 0.0);
+    if check_due(enabled, last_check, unix_time_now()) {
+        check_for_updates(false);
+    }
+}
+
+/// Fetch the release feed in the background; `manual` when the user
+/// asked for it from the menu.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn check_for_updates(manual: bool) {
+    info!(manual, "Checking for updates");
+    prefs_set_double(PREF_LAST_UPDATE_CHECK, unix_time_now());
+    flush_config();
+
+    let url: id = msg_send![get_class("NSURL"), URLWithString: nsstring_id(UPDATE_FEED_URL)];
+    let request: id = msg_send![get_class("NSMutableURLRequest"), requestWithURL: url];
+    let _: () = msg_send![request, setTimeoutInterval: FETCH_TIMEOUT_SECS];
+    // GitHub's API rejects requests without a User-Agent
+    let agent = format!("Lumbus/{VERSION}");
+    let _: () = msg_send![
+        request,
+        setValue: nsstring_id(&agent),
+        forHTTPHeaderField: nsstring_id("User-Agent")
+    ];
+    let _: () = msg_send![
+        request,
+        setValue: nsstring_id("application/vnd.github+json"),
+        forHTTPHeaderField: nsstring_id("Accept")
+    ];
+
+    let block = RcBlock::new(move |data: id, response: id, error: id| {
+        let feed = unsafe { feed_body(data, response, error) };
+        events::publish(AppEvent::UpdateChecked {
+            status: update_status(VERSION, feed),
+            manual,
+        });
+    });
+    let session: id = msg_send![get_class("NSURLSession"), sharedSession];
+    let task: id = msg_send![session, dataTaskWithRequest: request, completionHandler: &*block];
+    let _: () = msg_send![task, resume];
+}
+
+/// Body of a successful response, or why the request failed.
+unsafe fn feed_body(data: id, response: id, error: id) -> Result<String, String> {
+    if error != nil {
+        let description: id = msg_send![error, localizedDescription];
+        return Err(nsstring_to_string(description));
+    }
+    let status: isize = msg_send![response, statusCode];
+    if status != 200 {
+        return Err(format!("HTTP {status}"));
+    }
+    let bytes: *const u8 = msg_send![data, bytes];
+    let length: usize = msg_send![data, length];
+    if bytes.is_null() {
+        return Ok(String::new());
+    }
+    Ok(String::from_utf8_lossy(std::slice::from_raw_parts(bytes, length)).into_owned())
+}
+
+unsafe fn nsstring_to_string(string: id) -> String {
+    if string == nil {
+        return String::new();
+    }
+    let utf8: *const std::ffi::c_char = msg_send![string, UTF8String];
+    if utf8.is_null() {
+        return String::new();
+    }
+    std::ffi::CStr::from_ptr(utf8)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Record the outcome of a check. A manual check always gets an answer;
+/// an automatic one only changes the menu.
+///
+/// Returns `true` when the status bar menu needs rebuilding.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn update_checked(status: &UpdateStatus, manual: bool) -> bool {
+    let previous = available_update();
+    match status {
+        UpdateStatus::Available(release) => {
+            info!(version = %release.version, "Update available");
+            AVAILABLE.with(|a| *a.borrow_mut() = Some(release.clone()));
+            if manual {
+                offer_update(release);
+            }
+        }
+        UpdateStatus::UpToDate => {
+            info!("Lumbus is up to date");
+            AVAILABLE.with(|a| *a.borrow_mut() = None);
+            if manual {
+                let detail = tr_args("up-to-date-detail", &[("version", VERSION)])
+                    .unwrap_or_else(|| VERSION.to_string());
+                run_alert(&tr("Lumbus is up to date"), &detail, &[tr("Close")]);
+            }
+        }
+        UpdateStatus::Failed(reason) => {
+            warn!(%reason, "Update check failed");
+            if manual {
+                run_alert(&tr("Could not check for updates"), reason, &[tr("Close")]);
+            }
+        }
+    }
+    previous != available_update()
+}
+
+/// Ask whether to download `release`, opening its page if so.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn offer_update(release: &Release) {
+    let title = tr_args("update-available", &[("version", &release.version)])
+        .unwrap_or_else(|| release.version.clone());
+    let detail = tr_args(
+        "update-available-detail",
+        &[("version", &release.version), ("current", VERSION)],
+    )
+    .unwrap_or_default();
+    if run_alert(&title, &detail, &[tr("Download"), tr("Close")]) == FIRST_BUTTON {
+        open_release_page(release);
+    }
+}
+
+/// Open the release page in the default browser.
+///
+/// # Safety
+/// Must be called with a valid autorelease pool.
+pub unsafe fn open_release_page(release: &Release) {
+    let url: id = msg_send![get_class("NSURL"), URLWithString: nsstring_id(&release.url)];
+    if url == nil {
+        return;
+    }
+    let workspace: id = msg_send![get_class("NSWorkspace"), sharedWorkspace];
+    let _: bool = msg_send![workspace, openURL: url];
+}
+
+/// Run an informational alert and return the button pressed.
+unsafe fn run_alert(title: &str, detail: &str, buttons: &[String]) -> i64 {
+    let alert: id = msg_send![get_class("NSAlert"), new];
+    let _: () = msg_send![alert, setMessageText: nsstring_id(title)];
+    let _: () = msg_send![alert, setInformativeText: nsstring_id(detail)];
+    for button in buttons {
+        let _: id = msg_send![alert, addButtonWithTitle: nsstring_id(button)];
+    }
+    let _: () = msg_send![NSApp(), activateIgnoringOtherApps: YES];
+    msg_send![alert, runModal]
+}
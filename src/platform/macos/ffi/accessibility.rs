@@ -17,6 +17,14 @@ extern "C" {
     pub static kAXTrustedCheckOptionPrompt: *const std::ffi::c_void;
 }
 
+/// Is the app trusted for Accessibility? Never prompts.
+///
+/// # Safety
+/// Must be called from main thread.
+pub unsafe fn accessibility_trusted() -> bool {
+    AXIsProcessTrustedWithOptions(std::ptr::null())
+}
+
 /// Ensure accessibility permissions are granted, prompting the user if needed.
 ///
 /// This function triggers the macOS accessibility permission dialog if
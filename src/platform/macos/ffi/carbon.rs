@@ -38,13 +38,20 @@ pub const TYPE_EVENT_HOTKEY_ID: u32 = 0x686B_6964; // 'hkid'
 // Modifiers
 pub const CMD_KEY: u32 = 1 << 8;
 pub const SHIFT_KEY: u32 = 1 << 9;
+pub const OPTION_KEY: u32 = 1 << 11;
 pub const CONTROL_KEY: u32 = 1 << 12;
 
 // ANSI keycodes
-pub const KC_A: u32 = 0;
-pub const KC_X: u32 = 7;
+/// Keycodes of the letters A-Z, in alphabetical order.
+pub const KC_LETTERS: [u32; 26] = [
+    0, 11, 8, 2, 14, 3, 5, 4, 34, 38, 40, 37, 46, 45, 31, 35, 12, 15, 1, 17, 32, 9, 13, 7, 16, 6,
+];
 pub const KC_COMMA: u32 = 43;
-pub const KC_H: u32 = 4; // H key for Help hotkey
+pub const KC_EQUAL: u32 = 24; // = key for Zoom in
+pub const KC_MINUS: u32 = 27; // - key for Zoom out
+pub const KC_ESCAPE: u32 = 53; // Esc clears the ruler
+/// Keycodes of F13-F20 (presentation hotkey profile).
+pub const KC_F13_TO_F20: [u32; 8] = [105, 107, 113, 106, 64, 79, 80, 90];
 
 // Hotkey signature: 'mhlt'
 pub const SIG_MHLT: u32 = 0x6D68_6C74;
@@ -54,6 +61,14 @@ pub const HKID_TOGGLE: u32 = 1;
 pub const HKID_SETTINGS_COMMA: u32 = 2;
 pub const HKID_QUIT: u32 = 4;
 pub const HKID_HELP: u32 = 5; // Cmd+Shift+H
+pub const HKID_ZOOM_IN: u32 = 6; // Ctrl+Shift+=
+pub const HKID_ZOOM_OUT: u32 = 7; // Ctrl+Shift+-
+pub const HKID_LOCK: u32 = 8; // Ctrl+Shift+L
+pub const HKID_COORDINATES: u32 = 9; // Ctrl+Shift+M
+pub const HKID_COLOR_SAMPLER: u32 = 10; // Ctrl+Shift+E
+pub const HKID_RULER: u32 = 11; // Ctrl+Shift+U
+pub const HKID_RULER_ESCAPE: u32 = 12; // Esc, while the ruler mode is on
+pub const HKID_GRID: u32 = 13; // Ctrl+Shift+G
 
 // === FFI Declarations ===
 
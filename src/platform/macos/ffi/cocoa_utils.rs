@@ -62,3 +62,89 @@ pub unsafe fn display_id_for_screen(screen: Id) -> u32 {
         v as u32
     }
 }
+
+/// Is the "Reduce motion" accessibility setting on?
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn system_reduce_motion() -> bool {
+    let ws: Id = msg_send![objc2::class!(NSWorkspace), sharedWorkspace];
+    let reduced: bool = msg_send![ws, accessibilityDisplayShouldReduceMotion];
+    reduced
+}
+
+/// Locale tag of the user's preferred language (e.g. `es-ES`), if known.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn system_locale() -> Option<String> {
+    let langs: Id = msg_send![objc2::class!(NSLocale), preferredLanguages];
+    let first: Id = msg_send![langs, firstObject];
+    if first == NIL {
+        return None;
+    }
+    let utf8: *const std::ffi::c_char = msg_send![first, UTF8String];
+    if utf8.is_null() {
+        return None;
+    }
+    Some(
+        std::ffi::CStr::from_ptr(utf8)
+            .to_string_lossy()
+            .into_owned(),
+    )
+}
+
+/// Font families installed on the system, sorted by name.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn font_families() -> Vec<String> {
+    let manager: Id = msg_send![objc2::class!(NSFontManager), sharedFontManager];
+    let names: Id = msg_send![manager, availableFontFamilies];
+    if names == NIL {
+        return Vec::new();
+    }
+    let count: usize = msg_send![names, count];
+    let mut families: Vec<String> = (0..count)
+        .filter_map(|i| {
+            let name: Id = msg_send![names, objectAtIndex: i];
+            let utf8: *const std::ffi::c_char = msg_send![name, UTF8String];
+            (!utf8.is_null()).then(|| {
+                std::ffi::CStr::from_ptr(utf8)
+                    .to_string_lossy()
+                    .into_owned()
+            })
+        })
+        // Hidden system families (".SF NS"...) cannot be picked by name
+        .filter(|name| !name.starts_with('.'))
+        .collect();
+    families.sort_by_key(|name| name.to_lowercase());
+    families
+}
+
+/// macOS version, e.g. "macOS Version 14.5 (Build 23F79)".
+///
+/// # Safety
+/// Must be called with a valid autorelease pool.
+pub unsafe fn os_version() -> String {
+    let info: Id = msg_send![objc2::class!(NSProcessInfo), processInfo];
+    let version: Id = msg_send![info, operatingSystemVersionString];
+    let utf8: *const std::ffi::c_char = msg_send![version, UTF8String];
+    if utf8.is_null() {
+        return "macOS".to_string();
+    }
+    format!("macOS {}", std::ffi::CStr::from_ptr(utf8).to_string_lossy())
+}
+
+/// Replace the general pasteboard contents with plain `text`.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn copy_to_pasteboard(text: &str) -> bool {
+    let pasteboard: Id = msg_send![objc2::class!(NSPasteboard), generalPasteboard];
+    let _: isize = msg_send![pasteboard, clearContents];
+    let string = nsstring(text);
+    let kind = nsstring("public.utf8-plain-text");
+    let copied: bool = msg_send![pasteboard, setString: &*string, forType: &*kind];
+    copied
+}
@@ -1,15 +1,89 @@
 //! FFI bindings for CoreGraphics and CoreFoundation.
 //!
-//! This module provides the CoreGraphics API for path management
-//! and CoreFoundation utilities (CFRelease, CFDictionary, etc.).
+//! This module provides the CoreGraphics API for path management,
+//! window capture, transparency layers and pointer warping, and CoreFoundation utilities (CFRelease, CFDictionary, etc.).
+
+use objc2::encode::{Encoding, RefEncode};
+use objc2_foundation::{NSPoint, NSRect};
 
 use super::coretext::CGPathRef;
 
+// === Types ===
+
+/// Opaque CGImage type for correct objc2 encoding.
+/// objc2 expects `^{CGImage=}` not `^v` (void pointer).
+#[repr(C)]
+pub struct CGImage {
+    _private: [u8; 0],
+}
+
+// SAFETY: CGImage is an opaque Core Graphics type
+unsafe impl RefEncode for CGImage {
+    const ENCODING_REF: Encoding = Encoding::Pointer(&Encoding::Struct("CGImage", &[]));
+}
+
+pub type CGImageRef = *const CGImage;
+
+/// Opaque CGContext type (`^{CGContext=}`), as returned by
+/// `-[NSGraphicsContext CGContext]`.
+#[repr(C)]
+pub struct CGContext {
+    _private: [u8; 0],
+}
+
+// SAFETY: CGContext is an opaque Core Graphics type
+unsafe impl RefEncode for CGContext {
+    const ENCODING_REF: Encoding = Encoding::Pointer(&Encoding::Struct("CGContext", &[]));
+}
+
+pub type CGContextRef = *mut CGContext;
+
+// === Constants ===
+
+/// Capture only the on-screen windows below a given window.
+pub const K_CG_WINDOW_LIST_OPTION_ON_SCREEN_BELOW_WINDOW: u32 = 1 << 2;
+
+/// Default capture options (window shadows included).
+pub const K_CG_WINDOW_IMAGE_DEFAULT: u32 = 0;
+
 // === FFI Declarations - CoreGraphics ===
 
 #[link(name = "CoreGraphics", kind = "framework")]
 extern "C" {
     pub fn CGPathRelease(path: CGPathRef);
+
+    /// `screenBounds` is in global display coordinates (top-left origin).
+    pub fn CGWindowListCreateImage(
+        screenBounds: NSRect,
+        listOption: u32,
+        windowID: u32,
+        imageOption: u32,
+    ) -> CGImageRef;
+
+    pub fn CGImageRelease(image: CGImageRef);
+
+    /// Draw into an offscreen layer composited as a whole (with the
+    /// current shadow) by `CGContextEndTransparencyLayer`.
+    pub fn CGContextBeginTransparencyLayer(c: CGContextRef, auxiliaryInfo: *const std::ffi::c_void);
+
+    pub fn CGContextEndTransparencyLayer(c: CGContextRef);
+
+    /// Move the origin of later drawing by (`tx`, `ty`).
+    pub fn CGContextTranslateCTM(c: CGContextRef, tx: f64, ty: f64);
+
+    /// Moves the pointer without generating mouse events.
+    /// `newCursorPosition` is in global display coordinates (top-left origin).
+    pub fn CGWarpMouseCursorPosition(newCursorPosition: NSPoint) -> i32;
+
+    /// Screen Recording permission granted (macOS 10.15+), without prompting.
+    pub fn CGPreflightScreenCaptureAccess() -> bool;
+
+    /// Ask for Screen Recording permission (macOS 10.15+); prompts once,
+    /// then the user has to grant it in System Settings.
+    pub fn CGRequestScreenCaptureAccess() -> bool;
+
+    /// Input Monitoring permission granted (macOS 10.15+), without prompting.
+    pub fn CGPreflightListenEventAccess() -> bool;
 }
 
 // === FFI Declarations - CoreFoundation ===
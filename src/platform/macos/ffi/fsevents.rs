@@ -0,0 +1,120 @@
+//! FFI bindings for FSEvents (file system change notifications).
+//!
+//! Used to notice edits to the settings file made outside the app.
+
+use std::ffi::c_void;
+
+use super::bridge::{get_class, id, msg_send, nsstring_id};
+
+// === Types ===
+
+pub type FSEventStreamRef = *mut c_void;
+
+/// Called on the run loop the stream is scheduled on.
+pub type FSEventStreamCallback = extern "C-unwind" fn(
+    stream: FSEventStreamRef,
+    info: *mut c_void,
+    num_events: usize,
+    event_paths: *mut c_void,
+    event_flags: *const u32,
+    event_ids: *const u64,
+);
+
+/// `FSEventStreamContext` from `<CoreServices/FSEvents.h>`.
+#[repr(C)]
+pub struct FSEventStreamContext {
+    pub version: isize,
+    pub info: *mut c_void,
+    pub retain: *const c_void,
+    pub release: *const c_void,
+    pub copy_description: *const c_void,
+}
+
+// === Constants ===
+
+/// Report changes since the stream was created.
+pub const K_FS_EVENT_STREAM_EVENT_ID_SINCE_NOW: u64 = u64::MAX;
+
+/// Report changes to single files, not just their directory.
+pub const K_FS_EVENT_STREAM_CREATE_FLAG_FILE_EVENTS: u32 = 0x10;
+
+/// Seconds FSEvents waits to coalesce changes before reporting them.
+const WATCH_LATENCY_SECS: f64 = 0.3;
+
+// === FFI Declarations ===
+
+#[link(name = "CoreServices", kind = "framework")]
+extern "C" {
+    pub fn FSEventStreamCreate(
+        allocator: *const c_void,
+        callback: FSEventStreamCallback,
+        context: *mut FSEventStreamContext,
+        paths_to_watch: id,
+        since_when: u64,
+        latency: f64,
+        flags: u32,
+    ) -> FSEventStreamRef;
+
+    pub fn FSEventStreamScheduleWithRunLoop(
+        stream: FSEventStreamRef,
+        run_loop: *mut c_void,
+        run_loop_mode: *const c_void,
+    );
+
+    pub fn FSEventStreamStart(stream: FSEventStreamRef) -> bool;
+}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    pub fn CFRunLoopGetMain() -> *mut c_void;
+
+    pub static kCFRunLoopDefaultMode: *const c_void;
+}
+
+/// Forwards a batch of FSEvents to the closure stored in `info`.
+extern "C-unwind" fn forward_events(
+    _stream: FSEventStreamRef,
+    info: *mut c_void,
+    _num_events: usize,
+    _event_paths: *mut c_void,
+    _event_flags: *const u32,
+    _event_ids: *const u64,
+) {
+    // SAFETY: `info` is the closure leaked by `watch_directory`
+    let on_change = unsafe { &*(info as *const Box<dyn Fn()>) };
+    on_change();
+}
+
+/// Call `on_change` on the main thread whenever something in `dir` changes.
+///
+/// The stream and `on_change` live for the rest of the process. Returns
+/// false if the stream could not be started.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn watch_directory(dir: &str, on_change: impl Fn() + 'static) -> bool {
+    let paths: id = msg_send![get_class("NSArray"), arrayWithObject: nsstring_id(dir)];
+    let info = Box::into_raw(Box::new(Box::new(on_change) as Box<dyn Fn()>));
+    let mut context = FSEventStreamContext {
+        version: 0,
+        info: info as *mut c_void,
+        retain: std::ptr::null(),
+        release: std::ptr::null(),
+        copy_description: std::ptr::null(),
+    };
+    let stream = FSEventStreamCreate(
+        std::ptr::null(),
+        forward_events,
+        &mut context,
+        paths,
+        K_FS_EVENT_STREAM_EVENT_ID_SINCE_NOW,
+        WATCH_LATENCY_SECS,
+        K_FS_EVENT_STREAM_CREATE_FLAG_FILE_EVENTS,
+    );
+    if stream.is_null() {
+        drop(Box::from_raw(info));
+        return false;
+    }
+    FSEventStreamScheduleWithRunLoop(stream, CFRunLoopGetMain(), kCFRunLoopDefaultMode);
+    FSEventStreamStart(stream)
+}
@@ -1,7 +1,8 @@
 //! FFI bindings for macOS frameworks.
 //!
 //! This module encapsulates all `extern "C"` declarations and types
-//! needed to interact with Carbon, CoreText, CoreGraphics, and Cocoa.
+//! needed to interact with Carbon, CoreText, CoreGraphics, FSEvents and
+//! Cocoa.
 //!
 //! ## Migration Note
 //! The `bridge` module provides compatibility types for migrating from
@@ -13,6 +14,8 @@ pub mod carbon;
 pub mod cocoa_utils;
 pub mod coregraphics;
 pub mod coretext;
+pub mod fsevents;
+pub mod process;
 pub mod types;
 
 // Re-exports for convenient access
@@ -21,6 +24,8 @@ pub use carbon::*;
 pub use cocoa_utils::*;
 pub use coregraphics::*;
 pub use coretext::*;
+pub use fsevents::watch_directory;
+pub use process::*;
 #[allow(unused_imports)]
 pub use types::*;
 
@@ -0,0 +1,49 @@
+//! Process resource usage (Mach task info, file descriptors).
+//!
+//! Used by the soak test to watch memory and descriptor counts over time.
+
+/// `MACH_TASK_BASIC_INFO` flavor for `task_info`.
+const MACH_TASK_BASIC_INFO: u32 = 20;
+
+/// `struct mach_task_basic_info` from `<mach/task_info.h>`.
+#[repr(C)]
+#[derive(Default)]
+struct MachTaskBasicInfo {
+    virtual_size: u64,
+    resident_size: u64,
+    resident_size_max: u64,
+    user_time: [i32; 2],
+    system_time: [i32; 2],
+    policy: i32,
+    suspend_count: i32,
+}
+
+extern "C" {
+    static mach_task_self_: u32;
+
+    fn task_info(target_task: u32, flavor: u32, task_info_out: *mut i32, count: *mut u32) -> i32;
+}
+
+/// Resident memory of this process in bytes (0 if unavailable).
+pub fn resident_memory_bytes() -> u64 {
+    let mut info = MachTaskBasicInfo::default();
+    let mut count = (std::mem::size_of::<MachTaskBasicInfo>() / std::mem::size_of::<i32>()) as u32;
+    let status = unsafe {
+        task_info(
+            mach_task_self_,
+            MACH_TASK_BASIC_INFO,
+            &mut info as *mut _ as *mut i32,
+            &mut count,
+        )
+    };
+    if status == 0 {
+        info.resident_size
+    } else {
+        0
+    }
+}
+
+/// Open file descriptors of this process.
+pub fn open_descriptor_count() -> u64 {
+    std::fs::read_dir("/dev/fd").map_or(0, |entries| entries.count() as u64)
+}
@@ -7,18 +7,29 @@
 //! # Architecture
 //!
 //! ```text
-//! EventBus::drain() → dispatch_events() → action handlers
+//! take_event() → backend::dispatch_pending() → MacContext handlers
 //! ```
 //!
-//! The dispatcher acts as the central coordinator, translating
-//! high-level events into concrete macOS actions.
+//! The shared dispatcher decides what each event does; [`MacContext`]
+//! carries it out with the overlay views, dialogs and status bar.
 
+use std::cell::RefCell;
 use std::sync::atomic::{AtomicBool, Ordering};
 
-use crate::platform::macos::ffi::bridge::{id, msg_send, nil, NSApp, YES};
+use crate::platform::macos::ffi::bridge::{id, msg_send};
 
+use crate::backend::{dispatch_pending, DispatchContext, OverlayBackend};
 use crate::events::{take_event, AppEvent};
-use crate::platform::macos::ui::show_help_overlay;
+use crate::model::{ClickLogFormat, PresentationLock, UpdateStatus};
+use crate::platform::macos::app::{check_for_updates, update_checked, MacBackend};
+use crate::platform::macos::input::instance::restart;
+use crate::platform::macos::input::{
+    hotkey_event_handler, reinstall_hotkeys, toggle_click_recording,
+};
+use crate::platform::macos::ui::{
+    confirm_and_maybe_quit, export_click_log, open_settings_window, show_about_window,
+    show_help_overlay, update_status_bar_language,
+};
 
 /// Guard to prevent concurrent dispatch_events calls from racing.
 ///
@@ -31,151 +42,150 @@ use crate::platform::macos::ui::show_help_overlay;
 /// callbacks both take events before either reaches the modal.
 static DISPATCH_GUARD: AtomicBool = AtomicBool::new(false);
 
-/// Callback type for reinstalling hotkeys.
+thread_local! {
+    /// Presentation lock of the running session (not persisted).
+    static LOCK: RefCell<PresentationLock> = RefCell::new(PresentationLock::new());
+}
+
+/// Is the presentation lock on?
+///
+/// Used by the status bar menu to hide the Settings entry point.
+pub fn presentation_locked() -> bool {
+    LOCK.with(|l| l.borrow().is_locked())
+}
+
+/// Register the Carbon hotkeys of the selected profile again.
 ///
 /// After certain UI operations (settings closed, quit cancelled),
 /// Carbon hotkeys need to be reinstalled.
-pub type ReinstallHotkeysCallback = unsafe fn(id);
+unsafe fn reinstall_hotkeys_of(view: id) {
+    reinstall_hotkeys(view, hotkey_event_handler);
+}
 
-/// Dispatch all pending events from the global event bus.
-///
-/// This should be called from the main loop timer (60fps).
-/// It drains all pending events and executes the appropriate actions.
-///
-/// # Arguments
+/// The actions of the dispatcher, carried out through the host view.
 ///
-/// * `view` - The host CustomView for UI operations
-/// * `open_settings_fn` - Function to open settings window
-/// * `confirm_quit_fn` - Function to show quit confirmation dialog
-/// * `reinstall_hotkeys_fn` - Function to reinstall Carbon hotkeys
-///
-/// # Safety
-///
-/// Must be called from the main thread. The view pointer must be valid.
-pub unsafe fn dispatch_events(
+/// Only lives for one dispatch, on the main thread.
+pub struct MacContext {
     view: id,
-    open_settings_fn: unsafe fn(id),
-    confirm_quit_fn: unsafe fn(id),
-    reinstall_hotkeys_fn: ReinstallHotkeysCallback,
-) {
-    // CRITICAL: Acquire exclusive access to event processing.
-    // This prevents race conditions where two timer callbacks both
-    // enter dispatch_events and take events before either can block.
-    if DISPATCH_GUARD
-        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
-        .is_err()
-    {
-        // Another dispatch_events call is already running - skip this tick
-        return;
-    }
+    backend: MacBackend,
+}
 
-    // Process all pending events
-    while let Some(event) = take_event() {
-        let was_modal = dispatch_single_event(
+impl MacContext {
+    /// Context over the host `view` of the status bar.
+    ///
+    /// # Safety
+    /// - `view` must be a valid, non-null pointer to a CustomViewMulti.
+    /// - The context must only be used from the main thread.
+    pub unsafe fn new(view: id) -> Self {
+        Self {
             view,
-            &event,
-            open_settings_fn,
-            confirm_quit_fn,
-            reinstall_hotkeys_fn,
-        );
-
-        // After a modal closes, drain any duplicate modal events that accumulated
-        // while the user was spamming the hotkey. This prevents the second window
-        // from opening after the first one closes.
-        if was_modal {
-            drain_duplicate_modal_events();
-            break;
+            backend: MacBackend::new(view, reinstall_hotkeys_of),
         }
     }
-
-    // Release the guard so next timer tick can process events
-    DISPATCH_GUARD.store(false, Ordering::SeqCst);
 }
 
-/// Drain and discard any pending modal events from the queue.
-///
-/// Called after a modal closes to prevent duplicate windows from opening.
-fn drain_duplicate_modal_events() {
-    while let Some(event) = take_event() {
-        match event {
-            // Discard duplicate modal-opening events
-            AppEvent::OpenSettings
-            | AppEvent::RequestQuit
-            | AppEvent::ShowHelp
-            | AppEvent::ShowAbout => {
-                // Silently discard
-            }
-            // Re-queue non-modal events? No, we can't easily re-queue.
-            // These events (SettingsClosed, etc.) are posted AFTER the modal,
-            // so they shouldn't be in the queue at this point anyway.
-            _ => {
-                // This shouldn't happen, but log if it does
-                #[cfg(debug_assertions)]
-                eprintln!("[DISPATCH] Unexpected event after modal: {:?}", event);
-            }
-        }
+impl DispatchContext for MacContext {
+    fn backend(&mut self) -> &mut dyn OverlayBackend {
+        &mut self.backend
     }
-}
 
-/// Dispatch a single event.
-///
-/// Returns `true` if the event was a modal (blocking) event.
-///
-/// # Safety
-///
-/// Must be called from the main thread. The view pointer must be valid.
-unsafe fn dispatch_single_event(
-    view: id,
-    event: &AppEvent,
-    open_settings_fn: unsafe fn(id),
-    confirm_quit_fn: unsafe fn(id),
-    reinstall_hotkeys_fn: ReinstallHotkeysCallback,
-) -> bool {
-    match event {
-        AppEvent::ToggleOverlay => {
-            // Toggle overlay visibility via the view's method
-            let _: () = msg_send![view, requestToggle];
-            false
-        }
+    fn allows(&self, event: &AppEvent) -> bool {
+        LOCK.with(|l| l.borrow().allows(event))
+    }
 
-        AppEvent::OpenSettings => {
-            // Open settings window - blocks until closed
-            open_settings_fn(view);
-            true
-        }
+    fn toggle_presentation_lock(&mut self) -> bool {
+        LOCK.with(|l| l.borrow_mut().toggle())
+    }
 
-        AppEvent::RequestQuit => {
-            // Show quit dialog - blocks until closed
-            confirm_quit_fn(view);
-            true
-        }
+    fn open_settings(&mut self) -> bool {
+        // Blocks until closed
+        unsafe { open_settings_window(self.view) };
+        true
+    }
+
+    fn confirm_quit(&mut self) -> bool {
+        // Blocks until closed
+        unsafe { confirm_and_maybe_quit(self.view) };
+        true
+    }
+
+    fn restart(&mut self) {
+        // Terminates the app once the new instance is started
+        unsafe { restart() }
+    }
+
+    fn show_about(&mut self) {
+        // Non-blocking
+        unsafe { show_about_window(self.view) }
+    }
+
+    fn show_help(&mut self) -> bool {
+        // Blocks until closed
+        unsafe { show_help_overlay(self.view) };
+        true
+    }
 
-        AppEvent::ShowAbout => {
-            // Activate app and show the standard macOS About panel
-            let app: id = NSApp();
-            let _: () = msg_send![app, activateIgnoringOtherApps: YES];
-            let _: () = msg_send![app, orderFrontStandardAboutPanel: nil];
-            // About panel is non-blocking
-            false
+    fn zoom_in(&mut self) {
+        unsafe {
+            let _: () = msg_send![self.view, zoomIn];
         }
+    }
 
-        AppEvent::ShowHelp => {
-            // Show help overlay - blocks until closed
-            show_help_overlay(view);
-            true
+    fn zoom_out(&mut self) {
+        unsafe {
+            let _: () = msg_send![self.view, zoomOut];
         }
+    }
 
-        AppEvent::SettingsClosed
-        | AppEvent::QuitCancelled
-        | AppEvent::HelpClosed
-        | AppEvent::ReinstallHotkeys => {
-            // These events require hotkey reinstallation
-            reinstall_hotkeys_fn(view);
-            false
+    fn toggle_click_recording(&mut self) -> bool {
+        toggle_click_recording()
+    }
+
+    fn export_click_log(&mut self, format: ClickLogFormat) -> bool {
+        // Save panel - blocks until closed
+        unsafe { export_click_log(format) };
+        true
+    }
+
+    fn check_for_updates(&mut self) {
+        // Events are dispatched on the main thread, inside its run loop
+        unsafe { check_for_updates(true) };
+    }
+
+    fn update_checked(&mut self, status: &UpdateStatus, manual: bool) -> bool {
+        // A manual check answers with an alert - blocks until closed
+        unsafe {
+            if update_checked(status, manual) {
+                update_status_bar_language(self.view);
+            }
         }
+        manual
     }
 }
 
+/// Dispatch all pending events from the global event bus.
+///
+/// This should be called from the main loop timer (60fps).
+/// It takes all pending events and executes the appropriate actions
+/// through `context` (see [`dispatch_pending`]).
+pub fn dispatch_events(context: &mut dyn DispatchContext) {
+    // CRITICAL: Acquire exclusive access to event processing.
+    // This prevents race conditions where two timer callbacks both
+    // enter dispatch_events and take events before either can block.
+    if DISPATCH_GUARD
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        // Another dispatch_events call is already running - skip this tick
+        return;
+    }
+
+    dispatch_pending(context, take_event);
+
+    // Release the guard so next timer tick can process events
+    DISPATCH_GUARD.store(false, Ordering::SeqCst);
+}
+
 #[cfg(test)]
 mod tests {
     use crate::events::AppEvent;
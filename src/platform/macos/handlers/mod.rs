@@ -5,4 +5,4 @@
 
 pub mod dispatcher;
 
-pub use dispatcher::dispatch_events;
+pub use dispatcher::{dispatch_events, presentation_locked, MacContext};
@@ -0,0 +1,64 @@
+//! Local control endpoint glue.
+//!
+//! Starts the endpoint of `control` to match the settings and keeps the
+//! state it reports current. Its requests arrive as events on the event
+//! bus.
+
+use tracing::error;
+
+use crate::control::{self, configure, new_token};
+use crate::model::constants::{
+    DEFAULT_CONTROL_PORT, PREF_CONTROL_PORT, PREF_CONTROL_SERVER, PREF_CONTROL_TOKEN,
+    PREF_HOTKEY_PROFILE,
+};
+use crate::model::{ControlState, HOTKEY_PROFILE_STANDARD, MANAGED_KEYS};
+use crate::platform::macos::ffi::bridge::{get_bool_ivar, id};
+use crate::platform::macos::handlers::presentation_locked;
+use crate::platform::macos::storage::{
+    flush_config, prefs_get_double, prefs_get_int, prefs_get_string, prefs_set_string,
+};
+
+/// Start, restart or stop the control endpoint to match the settings,
+/// generating its token the first time it is turned on.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn apply_control_server() {
+    let enabled = prefs_get_int(PREF_CONTROL_SERVER, 0) != 0;
+    let port = prefs_get_int(PREF_CONTROL_PORT, DEFAULT_CONTROL_PORT) as u16;
+    let mut token = prefs_get_string(PREF_CONTROL_TOKEN, "");
+    if enabled && token.is_empty() {
+        token = match new_token() {
+            Ok(token) => token,
+            Err(e) => {
+                error!("No token for the control endpoint, not starting it: {}", e);
+                return;
+            }
+        };
+        prefs_set_string(PREF_CONTROL_TOKEN, &token);
+        flush_config();
+    }
+    if let Err(e) = configure(enabled, port, &token) {
+        error!("Control endpoint on port {} failed: {}", port, e);
+    }
+}
+
+/// Report the overlay's state to the control endpoint, if it is running.
+///
+/// # Safety
+/// - `view` must be a valid, non-null pointer to a CustomViewMulti.
+/// - Must be called from main thread with valid autorelease pool.
+pub unsafe fn publish_control_state(view: id) {
+    if !control::is_running() {
+        return;
+    }
+    control::update_state(ControlState {
+        visible: get_bool_ivar(view, "_overlayEnabled"),
+        locked: presentation_locked(),
+        hotkey_profile: prefs_get_string(PREF_HOTKEY_PROFILE, HOTKEY_PROFILE_STANDARD),
+        settings: MANAGED_KEYS
+            .iter()
+            .map(|key| (key.to_string(), prefs_get_double(key, 0.0)))
+            .collect(),
+    });
+}
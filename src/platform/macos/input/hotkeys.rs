@@ -1,18 +1,42 @@
 //! Carbon hotkey management.
 //!
 //! This module handles registration, unregistration, and reinstallation
-//! of global hotkeys using the Carbon Event Manager API.
+//! of global hotkeys using the Carbon Event Manager API, through
+//! [`CarbonHotkeys`] (see `backend::hotkeys`).
 
+use std::cell::{Cell, RefCell};
+
+use tracing::{debug_span, error, warn};
+
+use crate::backend::{
+    register_bindings, unregister_all, HotkeyError, HotkeyManager, TriggerHandler,
+};
 use crate::events::{publish, AppEvent};
+use crate::model::constants::PREF_HOTKEY_PROFILE;
+use crate::model::hotkeys::{MOD_ALT, MOD_CMD, MOD_CTRL, MOD_SHIFT};
+use crate::model::{
+    profile_bindings, safe_mode_bindings, Binding, Hotkey, HotkeyAction, HotkeyPlatform, Key,
+    HOTKEY_PROFILE_STANDARD,
+};
 use crate::platform::macos::ffi::bridge::{id, ObjectExt};
 use crate::platform::macos::ffi::{
     EventHandlerCallRef, EventHandlerRef, EventHotKeyID, EventHotKeyRef, EventRef, EventTypeSpec,
     GetApplicationEventTarget, GetEventClass, GetEventKind, GetEventParameter, InstallEventHandler,
     RegisterEventHotKey, RemoveEventHandler, UnregisterEventHotKey, CMD_KEY, CONTROL_KEY,
-    HKID_HELP, HKID_QUIT, HKID_SETTINGS_COMMA, HKID_TOGGLE, KC_A, KC_COMMA, KC_H, KC_X,
-    K_EVENT_CLASS_KEYBOARD, K_EVENT_HOTKEY_PRESSED, K_EVENT_PARAM_DIRECT_OBJECT, NO_ERR, SHIFT_KEY,
-    SIG_MHLT, TYPE_EVENT_HOTKEY_ID,
+    HKID_COLOR_SAMPLER, HKID_COORDINATES, HKID_GRID, HKID_HELP, HKID_LOCK, HKID_QUIT, HKID_RULER,
+    HKID_RULER_ESCAPE, HKID_SETTINGS_COMMA, HKID_TOGGLE, HKID_ZOOM_IN, HKID_ZOOM_OUT, KC_COMMA,
+    KC_EQUAL, KC_ESCAPE, KC_F13_TO_F20, KC_LETTERS, KC_MINUS, K_EVENT_CLASS_KEYBOARD,
+    K_EVENT_HOTKEY_PRESSED, K_EVENT_PARAM_DIRECT_OBJECT, NO_ERR, OPTION_KEY, SHIFT_KEY, SIG_MHLT,
+    TYPE_EVENT_HOTKEY_ID,
 };
+use crate::platform::macos::storage::{prefs_get_string, safe_mode};
+
+thread_local! {
+    /// Hotkeys registered through [`CarbonHotkeys`].
+    static REGISTERED: RefCell<Vec<Binding>> = const { RefCell::new(Vec::new()) };
+    /// Handler of pressed hotkeys, set with [`HotkeyManager::on_trigger`].
+    static TRIGGER_HANDLER: Cell<Option<TriggerHandler>> = const { Cell::new(None) };
+}
 
 /// Type alias for the hotkey event handler function signature.
 pub type HotkeyHandler = extern "C" fn(
@@ -21,17 +45,155 @@ pub type HotkeyHandler = extern "C" fn(
     *mut std::ffi::c_void,
 ) -> i32;
 
+/// Hotkey bindings of the selected profile; only the toggle hotkey in
+/// safe mode.
+///
+/// # Safety
+/// Must be called from main thread.
+pub unsafe fn current_hotkey_bindings() -> &'static [Binding] {
+    if safe_mode() {
+        return safe_mode_bindings(HotkeyPlatform::MacOs);
+    }
+    let profile = prefs_get_string(PREF_HOTKEY_PROFILE, HOTKEY_PROFILE_STANDARD);
+    profile_bindings(&profile, HotkeyPlatform::MacOs)
+}
+
+/// Carbon hotkey ID and ivar slot of an action.
+fn hotkey_slot(action: HotkeyAction) -> (u32, &'static str) {
+    match action {
+        HotkeyAction::Toggle => (HKID_TOGGLE, "_hkToggle"),
+        HotkeyAction::Settings => (HKID_SETTINGS_COMMA, "_hkComma"),
+        HotkeyAction::Help => (HKID_HELP, "_hkHelp"),
+        HotkeyAction::ZoomIn => (HKID_ZOOM_IN, "_hkZoomIn"),
+        HotkeyAction::ZoomOut => (HKID_ZOOM_OUT, "_hkZoomOut"),
+        HotkeyAction::Coordinates => (HKID_COORDINATES, "_hkCoordinates"),
+        HotkeyAction::ColorSampler => (HKID_COLOR_SAMPLER, "_hkColorSampler"),
+        HotkeyAction::Ruler => (HKID_RULER, "_hkRuler"),
+        HotkeyAction::Grid => (HKID_GRID, "_hkGrid"),
+        HotkeyAction::Lock => (HKID_LOCK, "_hkLock"),
+        HotkeyAction::Quit => (HKID_QUIT, "_hkQuit"),
+    }
+}
+
+/// ANSI keycode of a key, if it has one.
+fn key_code(key: Key) -> Option<u32> {
+    match key {
+        Key::Letter(c) if c.is_ascii_uppercase() => Some(KC_LETTERS[(c as u8 - b'A') as usize]),
+        Key::Letter(_) => None,
+        Key::Comma => Some(KC_COMMA),
+        Key::Equal => Some(KC_EQUAL),
+        Key::Minus => Some(KC_MINUS),
+        Key::F(n @ 13..=20) => Some(KC_F13_TO_F20[(n - 13) as usize]),
+        Key::F(_) => None,
+    }
+}
+
+/// Carbon modifier mask of a hotkey.
+fn carbon_modifiers(hotkey: &Hotkey) -> u32 {
+    [
+        (MOD_CMD, CMD_KEY),
+        (MOD_SHIFT, SHIFT_KEY),
+        (MOD_ALT, OPTION_KEY),
+        (MOD_CTRL, CONTROL_KEY),
+    ]
+    .iter()
+    .filter(|(m, _)| hotkey.modifiers & m != 0)
+    .fold(0, |mask, (_, carbon)| mask | carbon)
+}
+
+/// Global hotkeys registered with the Carbon Event Manager. Their refs
+/// are kept in the host view's `_hk*` ivars (see [`hotkey_slot`]); the
+/// handler installed by [`install_hotkeys`] gets them when pressed.
+pub struct CarbonHotkeys {
+    view: id,
+}
+
+impl CarbonHotkeys {
+    /// Hotkeys kept in the ivars of `view`.
+    ///
+    /// # Safety
+    /// `view` must be a valid, non-null pointer to a CustomViewMulti, used
+    /// from the main thread only.
+    pub unsafe fn new(view: id) -> Self {
+        Self { view }
+    }
+}
+
+impl HotkeyManager for CarbonHotkeys {
+    fn register(&mut self, binding: Binding) -> Result<(), HotkeyError> {
+        let keycode =
+            key_code(binding.hotkey.key).ok_or(HotkeyError::Unsupported(binding.hotkey))?;
+        self.unregister(binding.action);
+        let mods = carbon_modifiers(&binding.hotkey);
+        let (hkid, slot) = hotkey_slot(binding.action);
+        let hk_id = EventHotKeyID {
+            signature: SIG_MHLT,
+            id: hkid,
+        };
+        let mut out_ref: EventHotKeyRef = std::ptr::null_mut();
+        let st = unsafe {
+            RegisterEventHotKey(
+                keycode,
+                mods,
+                hk_id,
+                GetApplicationEventTarget(),
+                0,
+                &mut out_ref,
+            )
+        };
+        if st != NO_ERR || out_ref.is_null() {
+            return Err(HotkeyError::Refused {
+                hotkey: binding.hotkey,
+                reason: format!("code={}, mods={}, id={}: {}", keycode, mods, hkid, st),
+            });
+        }
+        unsafe { (*self.view).store_ivar::<*mut std::ffi::c_void>(slot, out_ref as *mut _) };
+        REGISTERED.with(|r| r.borrow_mut().push(binding));
+        Ok(())
+    }
+
+    fn unregister(&mut self, action: HotkeyAction) {
+        let (_, slot) = hotkey_slot(action);
+        unsafe {
+            let hk: *mut std::ffi::c_void = *(*self.view).load_ivar(slot);
+            if !hk.is_null() {
+                let _ = UnregisterEventHotKey(hk);
+                (*self.view).store_ivar::<*mut std::ffi::c_void>(slot, std::ptr::null_mut());
+            }
+        }
+        REGISTERED.with(|r| r.borrow_mut().retain(|b| b.action != action));
+    }
+
+    fn list(&self) -> Vec<Binding> {
+        REGISTERED.with(|r| r.borrow().clone())
+    }
+
+    fn on_trigger(&mut self, handler: TriggerHandler) {
+        TRIGGER_HANDLER.with(|h| h.set(Some(handler)));
+    }
+}
+
 /// Install Carbon hotkeys for the application.
 ///
-/// Registers:
+/// Registers the bindings of the selected hotkey profile (see
+/// `model::hotkeys`). The standard profile is:
 /// - Ctrl+A: Toggle overlay
 /// - Cmd+Shift+,: Open Settings
 /// - Cmd+Shift+H: Show Help
+/// - Ctrl+Shift+= / Ctrl+Shift+-: Zoom in / out
+/// - Ctrl+Shift+M: Cursor coordinates
+/// - Ctrl+Shift+E: Color sampler
+/// - Ctrl+Shift+U: Ruler
+/// - Ctrl+Shift+G: Alignment grid
+/// - Ctrl+Shift+L: Presentation lock
 /// - Ctrl+Shift+X: Quit confirmation
 ///
 /// # Safety
 /// Must be called from main thread. The handler function pointer must remain valid.
 pub unsafe fn install_hotkeys(view: id, handler: HotkeyHandler) {
+    let profile = prefs_get_string(PREF_HOTKEY_PROFILE, HOTKEY_PROFILE_STANDARD);
+    let _span = debug_span!("install_hotkeys", %profile).entered();
+
     // Install Carbon handler for hotkey events
     let types = [EventTypeSpec {
         event_class: K_EVENT_CLASS_KEYBOARD,
@@ -47,45 +209,12 @@ pub unsafe fn install_hotkeys(view: id, handler: HotkeyHandler) {
         &mut handler_ref,
     );
     if status != NO_ERR {
-        eprintln!("InstallEventHandler failed: {}", status);
+        error!("InstallEventHandler failed: {}", status);
         return;
     }
     (*view).store_ivar::<*mut std::ffi::c_void>("_hkHandler", handler_ref as *mut _);
 
-    macro_rules! register_hotkey {
-        ($keycode:expr, $mods:expr, $idconst:expr, $slot:literal) => {{
-            let hk_id = EventHotKeyID {
-                signature: SIG_MHLT,
-                id: $idconst,
-            };
-            let mut out_ref: EventHotKeyRef = std::ptr::null_mut();
-            let st = RegisterEventHotKey(
-                $keycode as u32,
-                $mods as u32,
-                hk_id,
-                GetApplicationEventTarget(),
-                0,
-                &mut out_ref,
-            );
-            if st != NO_ERR || out_ref.is_null() {
-                eprintln!(
-                    "RegisterEventHotKey failed (code={}, mods={}, id={}): {}",
-                    $keycode, $mods, $idconst, st
-                );
-            } else {
-                (*view).store_ivar::<*mut std::ffi::c_void>($slot, out_ref as *mut _);
-            }
-        }};
-    }
-
-    // Ctrl + A (toggle)
-    register_hotkey!(KC_A, CONTROL_KEY, HKID_TOGGLE, "_hkToggle");
-    // Cmd + Shift + , → Settings
-    register_hotkey!(KC_COMMA, CMD_KEY | SHIFT_KEY, HKID_SETTINGS_COMMA, "_hkComma");
-    // Cmd + Shift + H → Help
-    register_hotkey!(KC_H, CMD_KEY | SHIFT_KEY, HKID_HELP, "_hkHelp");
-    // Ctrl + Shift + X → Quit confirmation
-    register_hotkey!(KC_X, CONTROL_KEY | SHIFT_KEY, HKID_QUIT, "_hkQuit");
+    register_bindings(&mut CarbonHotkeys::new(view), current_hotkey_bindings());
 }
 
 /// Uninstall all registered Carbon hotkeys.
@@ -93,34 +222,91 @@ pub unsafe fn install_hotkeys(view: id, handler: HotkeyHandler) {
 /// # Safety
 /// Must be called from main thread.
 pub unsafe fn uninstall_hotkeys(view: id) {
-    let hk_toggle: *mut std::ffi::c_void = *(*view).load_ivar("_hkToggle");
-    let hk_comma: *mut std::ffi::c_void = *(*view).load_ivar("_hkComma");
-    let hk_help: *mut std::ffi::c_void = *(*view).load_ivar("_hkHelp");
-    let hk_quit: *mut std::ffi::c_void = *(*view).load_ivar("_hkQuit");
+    unregister_all(&mut CarbonHotkeys::new(view));
     let hk_handler: *mut std::ffi::c_void = *(*view).load_ivar("_hkHandler");
-
-    if !hk_toggle.is_null() {
-        let _ = UnregisterEventHotKey(hk_toggle);
-        (*view).store_ivar::<*mut std::ffi::c_void>("_hkToggle", std::ptr::null_mut());
-    }
-    if !hk_comma.is_null() {
-        let _ = UnregisterEventHotKey(hk_comma);
-        (*view).store_ivar::<*mut std::ffi::c_void>("_hkComma", std::ptr::null_mut());
-    }
-    if !hk_help.is_null() {
-        let _ = UnregisterEventHotKey(hk_help);
-        (*view).store_ivar::<*mut std::ffi::c_void>("_hkHelp", std::ptr::null_mut());
-    }
-    if !hk_quit.is_null() {
-        let _ = UnregisterEventHotKey(hk_quit);
-        (*view).store_ivar::<*mut std::ffi::c_void>("_hkQuit", std::ptr::null_mut());
-    }
     if !hk_handler.is_null() {
         let _ = RemoveEventHandler(hk_handler);
         (*view).store_ivar::<*mut std::ffi::c_void>("_hkHandler", std::ptr::null_mut());
     }
 }
 
+/// Is the hotkey of `action` currently registered?
+///
+/// # Safety
+/// `view` must be a valid, non-null pointer to a CustomViewMulti.
+pub unsafe fn hotkey_registered(view: id, action: HotkeyAction) -> bool {
+    let (_, slot) = hotkey_slot(action);
+    !(*(*view).load_ivar::<*mut std::ffi::c_void>(slot)).is_null()
+}
+
+/// Number of global hotkeys currently registered.
+///
+/// # Safety
+/// `view` must be a valid, non-null pointer to a CustomViewMulti.
+pub unsafe fn installed_hotkey_count(view: id) -> u32 {
+    [
+        "_hkToggle",
+        "_hkComma",
+        "_hkHelp",
+        "_hkZoomIn",
+        "_hkZoomOut",
+        "_hkCoordinates",
+        "_hkColorSampler",
+        "_hkRuler",
+        "_hkGrid",
+        "_hkLock",
+        "_hkQuit",
+    ]
+    .iter()
+    .filter(|slot| !(*(*view).load_ivar::<*mut std::ffi::c_void>(slot)).is_null())
+    .count() as u32
+}
+
+/// Take Esc as a global hotkey, to clear the ruler, while its mode is on.
+///
+/// Kept apart from the profile's hotkeys, so reinstalling them leaves it
+/// alone.
+///
+/// # Safety
+/// - `view` must be a valid, non-null pointer to a CustomViewMulti.
+/// - Must be called from main thread.
+pub unsafe fn register_ruler_escape(view: id) {
+    if !(*(*view).load_ivar::<*mut std::ffi::c_void>("_hkRulerEscape")).is_null() {
+        return;
+    }
+    let hk_id = EventHotKeyID {
+        signature: SIG_MHLT,
+        id: HKID_RULER_ESCAPE,
+    };
+    let mut out_ref: EventHotKeyRef = std::ptr::null_mut();
+    let st = RegisterEventHotKey(
+        KC_ESCAPE,
+        0,
+        hk_id,
+        GetApplicationEventTarget(),
+        0,
+        &mut out_ref,
+    );
+    if st != NO_ERR || out_ref.is_null() {
+        warn!("RegisterEventHotKey failed for Esc: {}", st);
+    } else {
+        (*view).store_ivar::<*mut std::ffi::c_void>("_hkRulerEscape", out_ref as *mut _);
+    }
+}
+
+/// Give Esc back to the other apps.
+///
+/// # Safety
+/// - `view` must be a valid, non-null pointer to a CustomViewMulti.
+/// - Must be called from main thread.
+pub unsafe fn unregister_ruler_escape(view: id) {
+    let hk_escape: *mut std::ffi::c_void = *(*view).load_ivar("_hkRulerEscape");
+    if !hk_escape.is_null() {
+        let _ = UnregisterEventHotKey(hk_escape);
+        (*view).store_ivar::<*mut std::ffi::c_void>("_hkRulerEscape", std::ptr::null_mut());
+    }
+}
+
 /// Re-install hotkeys safely (unregister first to avoid leaks).
 ///
 /// # Safety
@@ -163,20 +349,16 @@ pub extern "C" fn hotkey_event_handler(
             );
             if status == NO_ERR && hot_id.signature == SIG_MHLT {
                 // Publish events to the bus - they'll be processed in the main loop
-                match hot_id.id {
-                    HKID_TOGGLE => {
-                        publish(AppEvent::ToggleOverlay);
-                    }
-                    HKID_SETTINGS_COMMA => {
-                        publish(AppEvent::OpenSettings);
-                    }
-                    HKID_HELP => {
-                        publish(AppEvent::ShowHelp);
-                    }
-                    HKID_QUIT => {
-                        publish(AppEvent::RequestQuit);
+                if let Some(action) = HotkeyAction::ALL
+                    .into_iter()
+                    .find(|a| hotkey_slot(*a).0 == hot_id.id)
+                {
+                    match TRIGGER_HANDLER.with(Cell::get) {
+                        Some(handler) => handler(action),
+                        None => publish(action.event()),
                     }
-                    _ => {}
+                } else if hot_id.id == HKID_RULER_ESCAPE {
+                    publish(AppEvent::ClearRuler);
                 }
             }
         }
@@ -0,0 +1,128 @@
+//! Single instance on macOS.
+//!
+//! Launch Services only starts one copy of the app bundle, but the binary
+//! can still be run a second time from a shell. Such a launch finds
+//! another running application with the same bundle identifier, posts its
+//! command-line arguments as a distributed notification and exits; the
+//! running instance observes that notification (see `model::instance`).
+//! A restarted process waits for the old one to be gone instead.
+
+use std::ffi::{c_char, CStr};
+use std::time::{Duration, Instant};
+
+use block2::RcBlock;
+use tracing::{info, warn};
+
+use crate::events::{publish, AppEvent};
+use crate::model::{
+    decode_args, encode_args, handoff, url_events, HANDOFF_NOTIFICATION, RESTART_FLAG,
+};
+use crate::platform::macos::app::run_diagnostics;
+use crate::platform::macos::ffi::bridge::{get_class, id, msg_send, nil, nsstring_id, YES};
+
+/// Is another instance of this app running, after waiting up to `wait`
+/// for it to quit?
+///
+/// False when not run from an app bundle: there is no identifier to
+/// match the running instance with.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn another_instance_running(wait: Duration) -> bool {
+    let current: id = msg_send![get_class("NSRunningApplication"), currentApplication];
+    let bundle_id: id = msg_send![current, bundleIdentifier];
+    if bundle_id == nil {
+        return false;
+    }
+    let deadline = Instant::now() + wait;
+    loop {
+        let running: id = msg_send![
+            get_class("NSRunningApplication"),
+            runningApplicationsWithBundleIdentifier: bundle_id
+        ];
+        let count: usize = msg_send![running, count];
+        if count <= 1 {
+            return false;
+        }
+        if Instant::now() >= deadline {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+/// Start a new instance of the app, then quit this one; the new instance
+/// waits for this one to be gone.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn restart() {
+    let relaunched = std::env::current_exe()
+        .and_then(|exe| std::process::Command::new(exe).arg(RESTART_FLAG).spawn());
+    if let Err(e) = relaunched {
+        warn!("Could not restart: {e}");
+        return;
+    }
+    info!("Restarting");
+    let app: id = msg_send![get_class("NSApplication"), sharedApplication];
+    let _: () = msg_send![app, terminate: nil];
+}
+
+/// Hand `args` to the running instance.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn forward_args(args: &[String]) {
+    let center: id = msg_send![get_class("NSDistributedNotificationCenter"), defaultCenter];
+    let _: () = msg_send![
+        center,
+        postNotificationName: nsstring_id(HANDOFF_NOTIFICATION),
+        object: nsstring_id(&encode_args(args)),
+        userInfo: nil,
+        deliverImmediately: YES
+    ];
+    info!("Lumbus is already running");
+}
+
+/// Carry out the arguments handed over by a second launch; `view` is the
+/// host view, which diagnostics are reported for.
+///
+/// # Safety
+/// - `view` must be the host view, alive as long as the app.
+/// - Must be called from main thread with valid autorelease pool.
+/// - Must be called once, at startup.
+pub unsafe fn install_handoff_observer(view: id) {
+    let center: id = msg_send![get_class("NSDistributedNotificationCenter"), defaultCenter];
+    let queue: id = msg_send![get_class("NSOperationQueue"), mainQueue];
+    let block = RcBlock::new(move |note: id| unsafe {
+        let object: id = msg_send![note, object];
+        if object == nil {
+            return;
+        }
+        let text: *const c_char = msg_send![object, UTF8String];
+        if text.is_null() {
+            return;
+        }
+        let args = decode_args(&CStr::from_ptr(text).to_string_lossy());
+        let request = handoff(&args);
+        if request.opens_settings() {
+            publish(AppEvent::OpenSettings);
+        }
+        for url in request.urls {
+            match url_events(&url) {
+                Ok(events) => events.into_iter().for_each(publish),
+                Err(e) => warn!("Ignoring {url}: {e}"),
+            }
+        }
+        if let Some(config) = request.diagnostics {
+            run_diagnostics(view, config);
+        }
+    });
+    let _: id = msg_send![
+        center,
+        addObserverForName: nsstring_id(HANDOFF_NOTIFICATION),
+        object: nil,
+        queue: queue,
+        usingBlock: &*block
+    ];
+}
@@ -1,19 +1,40 @@
 //! Input handling module.
 //!
 //! This module contains input handlers for:
+//! - control.rs: Local control endpoint settings and state
 //! - hotkeys.rs: Carbon hotkey registration and handling
-//! - observers.rs: System observers (wake, space change, termination)
+//! - instance.rs: Single instance, arguments of a second launch
+//! - observers.rs: System observers (wake, space change, termination, reduce motion,
+//!   display changes, settings file edits)
 //! - keyboard_monitors.rs: Local keyboard monitors (Ctrl+A backup)
 //! - mouse_monitors.rs: Global mouse event monitors
+//! - ruler.rs: Clicks taken by the overlay in the ruler mode
+//! - scripting.rs: AppleScript command handlers
+//! - url_handler.rs: `lumbus://` URL handler
 
+pub mod control;
 pub mod hotkeys;
+pub mod instance;
 pub mod keyboard_monitors;
 pub mod mouse_monitors;
 pub mod observers;
+pub mod ruler;
+pub mod scripting;
+pub mod url_handler;
 
-pub use hotkeys::{hotkey_event_handler, install_hotkeys, reinstall_hotkeys};
+pub use control::{apply_control_server, publish_control_state};
+pub use hotkeys::{
+    current_hotkey_bindings, hotkey_event_handler, hotkey_registered, install_hotkeys,
+    installed_hotkey_count, reinstall_hotkeys, CarbonHotkeys,
+};
 pub use keyboard_monitors::install_local_ctrl_a_monitor;
-pub use mouse_monitors::install_mouse_monitors;
+pub use mouse_monitors::{
+    click_held_secs, click_hold_mode, click_recording, export_click_log_text,
+    install_mouse_monitors, pen_pressure, show_display_mode, toggle_click_recording,
+};
 pub use observers::{
+    install_config_file_observer, install_reduce_motion_observer, install_screen_change_observer,
     install_termination_observer, install_wakeup_space_observers, start_hotkey_keepalive,
+    watch_config_directory,
 };
+pub use ruler::{ruler_line, ruler_on, start_ruler, stop_ruler};
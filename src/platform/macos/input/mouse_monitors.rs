@@ -1,21 +1,199 @@
 //! Global mouse event monitors.
 //!
 //! This module handles global mouse events to show L/R indicators
-//! when clicking and to track mouse movement.
+//! when clicking and to track mouse movement. Presses also feed the
+//! click-burst counter and, while recording, the click log. A released
+//! button's letter stays up for the click hold time. While the color
+//! sampler is on, a left click copies the sampled color. Tablet events
+//! track the pressure of a pen driving the pointer.
 
-use crate::platform::macos::ffi::bridge::{get_class, id, msg_send, nil, sel, ObjectExt, NO, YES};
+use std::cell::{Cell, RefCell};
+
+use crate::platform::macos::ffi::bridge::{
+    get_bool_ivar, get_class, id, msg_send, nil, sel, Bool, NSRect, ObjectExt, NO, YES,
+};
+use crate::platform::macos::ffi::{
+    copy_to_pasteboard, get_mouse_position_cocoa, CFAbsoluteTimeGetCurrent,
+};
 use block2::RcBlock;
+use tracing::info;
+
+use crate::model::click_log::unix_time_now;
+use crate::model::color_sampler::{sample_hex, unpack_rgb};
+use crate::model::placement::{display_key, ScreenRect};
+use crate::model::{ClickBurst, ClickHold, ClickLog, ClickLogFormat, ClickRecord, DisplayMode};
+use crate::platform::macos::app::{apply_to_all_views, permission_status};
+
+thread_local! {
+    static CLICK_BURST: RefCell<ClickBurst> = RefCell::new(ClickBurst::new());
+    static CLICK_HOLD: RefCell<ClickHold> = RefCell::new(ClickHold::new());
+    static CLICK_LOG: RefCell<ClickLog> = RefCell::new(ClickLog::new());
+    static PEN_PRESSURE: Cell<Option<f64>> = const { Cell::new(None) };
+}
+
+/// Pressure of the pen driving the pointer (0.0 while it hovers), when
+/// it is a pen.
+pub fn pen_pressure() -> Option<f64> {
+    PEN_PRESSURE.with(|p| p.get())
+}
+
+/// Follow the pen in a mouse or tablet `event`: its pressure while it
+/// drives the pointer, nothing once the mouse moves it again.
+unsafe fn track_pen(event: id) {
+    const TABLET_POINT: u64 = 23; // NSEventTypeTabletPoint
+    const TABLET_PROXIMITY: u64 = 24; // NSEventTypeTabletProximity
+    const SUBTYPE_TABLET_POINT: i16 = 1; // NSEventSubtypeTabletPoint
+    const SUBTYPE_TABLET_PROXIMITY: i16 = 2; // NSEventSubtypeTabletProximity
+    const DEVICE_CURSOR: u64 = 2; // NSPointingDeviceTypeCursor (a puck)
+
+    let kind: u64 = msg_send![event, type];
+    let subtype: i16 = if matches!(kind, TABLET_POINT | TABLET_PROXIMITY) {
+        0
+    } else {
+        msg_send![event, subtype]
+    };
+    let pressure = if kind == TABLET_PROXIMITY || subtype == SUBTYPE_TABLET_PROXIMITY {
+        let entering: Bool = msg_send![event, isEnteringProximity];
+        let device: u64 = msg_send![event, pointingDeviceType];
+        (entering.as_bool() && device != DEVICE_CURSOR).then_some(0.0)
+    } else if kind == TABLET_POINT || subtype == SUBTYPE_TABLET_POINT {
+        let pressure: f32 = msg_send![event, pressure];
+        Some(pressure as f64)
+    } else {
+        None
+    };
+    PEN_PRESSURE.with(|p| p.set(pressure));
+}
+
+/// Is the click log recording?
+pub fn click_recording() -> bool {
+    CLICK_LOG.with(|l| l.borrow().is_recording())
+}
+
+/// Start a new click recording, or stop the current one.
+///
+/// Returns the new recording state.
+pub fn toggle_click_recording() -> bool {
+    CLICK_LOG.with(|l| l.borrow_mut().toggle(unix_time_now()))
+}
+
+/// Serialize the recorded clicks.
+pub fn export_click_log_text(format: ClickLogFormat) -> String {
+    CLICK_LOG.with(|l| l.borrow().export(format))
+}
+
+/// Display mode to draw now: the held button, else the one released
+/// less than the click hold time ago, else the circle.
+///
+/// # Safety
+/// `host` must be a valid CustomViewMulti.
+pub unsafe fn click_hold_mode(host: id) -> i32 {
+    let hold_ms = *(*host).load_ivar::<f64>("_clickHoldMs");
+    CLICK_HOLD.with(|h| h.borrow().mode(CFAbsoluteTimeGetCurrent(), hold_ms))
+}
 
-use crate::platform::macos::app::apply_to_all_views;
+/// Seconds the current button has been held (`None` when no button is
+/// down).
+pub fn click_held_secs() -> Option<f64> {
+    // Same clock as the presses recorded by the monitors
+    let now = unsafe { CFAbsoluteTimeGetCurrent() };
+    CLICK_HOLD.with(|h| h.borrow().held_secs(now))
+}
+
+/// Publish the current display mode to every view and redraw them.
+unsafe fn show_click_mode(host: id) {
+    let mode = click_hold_mode(host);
+    apply_to_all_views(|v| *(*v).load_ivar_mut::<i32>("_displayMode") = mode);
+    apply_to_all_views(|v| {
+        let _: () = msg_send![v, setNeedsDisplay: YES];
+    });
+}
+
+/// Draw `mode` as after a click of its button (the circle takes down a
+/// letter still shown), on every view.
+///
+/// # Safety
+/// `host` must be a valid CustomViewMulti.
+pub unsafe fn show_display_mode(host: id, mode: DisplayMode) {
+    let now = CFAbsoluteTimeGetCurrent();
+    CLICK_HOLD.with(|h| {
+        let mut hold = h.borrow_mut();
+        match mode {
+            DisplayMode::Circle => hold.reset(),
+            button => {
+                hold.press(button.code(), now);
+                hold.release(now);
+            }
+        }
+    });
+    show_click_mode(host);
+}
+
+/// Display key of the screen containing a point in Cocoa coordinates.
+unsafe fn display_key_at(x: f64, y: f64) -> String {
+    let screens: id = msg_send![get_class("NSScreen"), screens];
+    let count: usize = msg_send![screens, count];
+    for i in 0..count {
+        let scr: id = msg_send![screens, objectAtIndex: i];
+        let f: NSRect = msg_send![scr, frame];
+        let rect = ScreenRect::new(f.origin.x, f.origin.y, f.size.width, f.size.height);
+        if rect.contains(x, y) {
+            return display_key(&rect);
+        }
+    }
+    String::new()
+}
+
+/// Count a button press towards the current click burst and publish the
+/// count to every view (1 when the counter badge is disabled). The press
+/// is also added to the click log while recording.
+unsafe fn register_press(host: id, button: i32) {
+    let (x, y) = get_mouse_position_cocoa();
+    let count = if get_bool_ivar(host, "_clickBurst") {
+        let interval = *(*host).load_ivar::<f64>("_clickBurstMs") / 1000.0;
+        CLICK_BURST.with(|b| {
+            b.borrow_mut()
+                .press(button, x, y, CFAbsoluteTimeGetCurrent(), interval)
+        })
+    } else {
+        CLICK_BURST.with(|b| b.borrow_mut().reset());
+        1
+    };
+    apply_to_all_views(|v| (*v).store_ivar::<u32>("_clickCount", count));
+
+    if click_recording() {
+        let click = ClickRecord {
+            timestamp: unix_time_now(),
+            x,
+            y,
+            button,
+            display: display_key_at(x, y),
+        };
+        CLICK_LOG.with(|l| l.borrow_mut().record(click));
+    }
+}
+
+/// Copy the hex of the sampled color, while the color sampler is on.
+unsafe fn copy_color_sample(host: id) {
+    if let Some(color) = unpack_rgb(*(*host).load_ivar::<i32>("_sampleRgb")) {
+        let hex = sample_hex(color);
+        if copy_to_pasteboard(&hex) {
+            info!("Copied {} to the pasteboard", hex);
+        }
+    }
+}
 
 /// Install global mouse monitors for tracking clicks and movement.
 ///
 /// Sets up monitors for:
-/// - Left mouse down → show "L"
-/// - Left mouse up → show circle
-/// - Right mouse down → show "R"
-/// - Right mouse up → show circle
+/// - Left mouse down → show "L" (unless Accessibility is missing), copy
+///   the sampled color
+/// - Left mouse up → show circle (after the click hold time)
+/// - Right mouse down → show "R" (unless Accessibility is missing)
+/// - Right mouse up → show circle (after the click hold time)
 /// - Mouse moved → update cursor position
+/// - Pen moved, dragged or entering or leaving the tablet → track its
+///   pressure
 ///
 /// # Safety
 /// - `view` must be a valid, non-null pointer to a CustomViewMulti.
@@ -29,24 +207,26 @@ pub unsafe fn install_mouse_monitors(view: id) {
     const MOUSE_MOVED_MASK: u64 = 1 << 6;
 
     let cls = get_class("NSEvent");
+    let host = view;
 
     // LEFT DOWN -> L mode
     let h1 = RcBlock::new(move |_e: id| unsafe {
-        apply_to_all_views(|v| *(*v).load_ivar_mut::<i32>("_displayMode") = 1);
-        apply_to_all_views(|v| {
-            let _: () = msg_send![v, setNeedsDisplay: YES];
-        });
+        register_press(host, 1);
+        copy_color_sample(host);
+        if !permission_status().click_letters() {
+            return;
+        }
+        CLICK_HOLD.with(|h| h.borrow_mut().press(1, CFAbsoluteTimeGetCurrent()));
+        show_click_mode(host);
     });
     let mon_ld: id =
         msg_send![cls, addGlobalMonitorForEventsMatchingMask: LEFT_DOWN_MASK, handler: &*h1];
     (*view).store_ivar::<id>("_monLeftDown", mon_ld);
 
-    // LEFT UP -> circle
+    // LEFT UP -> circle once the hold runs out
     let h2 = RcBlock::new(move |_e: id| unsafe {
-        apply_to_all_views(|v| *(*v).load_ivar_mut::<i32>("_displayMode") = 0);
-        apply_to_all_views(|v| {
-            let _: () = msg_send![v, setNeedsDisplay: YES];
-        });
+        CLICK_HOLD.with(|h| h.borrow_mut().release(CFAbsoluteTimeGetCurrent()));
+        show_click_mode(host);
     });
     let mon_lu: id =
         msg_send![cls, addGlobalMonitorForEventsMatchingMask: LEFT_UP_MASK, handler: &*h2];
@@ -54,28 +234,27 @@ pub unsafe fn install_mouse_monitors(view: id) {
 
     // RIGHT DOWN -> R mode
     let h3 = RcBlock::new(move |_e: id| unsafe {
-        apply_to_all_views(|v| *(*v).load_ivar_mut::<i32>("_displayMode") = 2);
-        apply_to_all_views(|v| {
-            let _: () = msg_send![v, setNeedsDisplay: YES];
-        });
+        register_press(host, 2);
+        if !permission_status().click_letters() {
+            return;
+        }
+        CLICK_HOLD.with(|h| h.borrow_mut().press(2, CFAbsoluteTimeGetCurrent()));
+        show_click_mode(host);
     });
     let mon_rd: id =
         msg_send![cls, addGlobalMonitorForEventsMatchingMask: RIGHT_DOWN_MASK, handler: &*h3];
     (*view).store_ivar::<id>("_monRightDown", mon_rd);
 
-    // RIGHT UP -> circle
+    // RIGHT UP -> circle once the hold runs out
     let h4 = RcBlock::new(move |_e: id| unsafe {
-        apply_to_all_views(|v| *(*v).load_ivar_mut::<i32>("_displayMode") = 0);
-        apply_to_all_views(|v| {
-            let _: () = msg_send![v, setNeedsDisplay: YES];
-        });
+        CLICK_HOLD.with(|h| h.borrow_mut().release(CFAbsoluteTimeGetCurrent()));
+        show_click_mode(host);
     });
     let mon_ru: id =
         msg_send![cls, addGlobalMonitorForEventsMatchingMask: RIGHT_UP_MASK, handler: &*h4];
     (*view).store_ivar::<id>("_monRightUp", mon_ru);
 
     // mouseMoved → schedule update on the main thread
-    let host = view;
     let hmove = RcBlock::new(move |_e: id| unsafe {
         let _: () = msg_send![
             host,
@@ -87,4 +266,26 @@ pub unsafe fn install_mouse_monitors(view: id) {
     let mon_move: id =
         msg_send![cls, addGlobalMonitorForEventsMatchingMask: MOUSE_MOVED_MASK, handler: &*hmove];
     (*view).store_ivar::<id>("_monMove", mon_move);
+
+    // Pen and mouse movement → pen pressure, or no pen
+    const POINTER_MASK: u64 = (1 << 1) | (1 << 2) | (1 << 5) | (1 << 6);
+    const TABLET_MASK: u64 = (1 << 23) | (1 << 24);
+    let hpen = RcBlock::new(move |e: id| unsafe {
+        let was = pen_pressure();
+        track_pen(e);
+        if pen_pressure() != was {
+            let _: () = msg_send![
+                host,
+                performSelectorOnMainThread: sel!(update_cursor_multi),
+                withObject: nil,
+                waitUntilDone: NO
+            ];
+        }
+    });
+    let mon_pen: id = msg_send![
+        cls,
+        addGlobalMonitorForEventsMatchingMask: POINTER_MASK | TABLET_MASK,
+        handler: &*hpen
+    ];
+    (*view).store_ivar::<id>("_monPen", mon_pen);
 }
@@ -1,14 +1,40 @@
-//! System observers for hotkey keep-alive and wakeup events.
+//! System observers for hotkey keep-alive, wakeup and accessibility events.
 //!
 //! This module installs observers that help maintain Carbon hotkeys
-//! across system events like sleep/wake, session changes, and space changes.
+//! across system events like sleep/wake, session changes, and space changes,
+//! tracks the system "Reduce motion" setting, follows display changes and
+//! applies edits made to the settings file outside the app.
 
-use crate::platform::macos::ffi::bridge::{get_class, id, msg_send, nil, sel, ObjectExt, YES};
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+
+use crate::backend::{load_settings, DispatchContext};
+use crate::platform::macos::app::{
+    apply_to_all_views, finish_diagnostics, sync_overlay_windows, teardown_app_context,
+};
+use crate::platform::macos::ffi::bridge::{
+    get_class, id, msg_send, nil, sel, set_bool_ivar, ObjectExt, YES,
+};
+use crate::platform::macos::ffi::{system_reduce_motion, watch_directory};
 use block2::RcBlock;
 
-use crate::platform::macos::input::hotkeys::{reinstall_hotkeys, uninstall_hotkeys, HotkeyHandler};
+use crate::platform::macos::handlers::MacContext;
+use crate::platform::macos::input::control::apply_control_server;
+use crate::platform::macos::input::hotkeys::{reinstall_hotkeys, HotkeyHandler};
+use crate::platform::macos::storage::{
+    config_path, ensure_config_dir, flush_config, reload_config, sync_folder,
+};
+use crate::platform::macos::ui::overlay::reload_frame_colors;
+use crate::platform::macos::ui::{show_config_issues, update_status_bar_language};
+
+thread_local! {
+    /// Directories whose settings files are watched.
+    static WATCHED_DIRS: RefCell<Vec<PathBuf>> = const { RefCell::new(Vec::new()) };
+}
 
-/// Install an observer that cleans up Carbon resources when app terminates.
+/// Install an observer that logs the event latency, tears down the app's
+/// context (windows, timers, monitors, Carbon hotkeys) and saves the
+/// preferences when app terminates.
 ///
 /// # Safety
 /// - `view` must be a valid, non-null pointer to a CustomViewMulti.
@@ -18,9 +44,12 @@ pub unsafe fn install_termination_observer(view: id, handler: HotkeyHandler) {
     let queue: id = nil; // main thread
 
     // Use handler indirectly to avoid capturing it
-    let _ = handler; // We only use uninstall_hotkeys in termination
+    let _ = handler; // We only uninstall hotkeys in termination
     let block = RcBlock::new(move |_note: id| unsafe {
-        uninstall_hotkeys(view);
+        // Before the teardown: the report asks the host view for hotkeys
+        finish_diagnostics(view);
+        teardown_app_context();
+        flush_config();
     });
 
     let name: id = msg_send![
@@ -91,3 +120,89 @@ pub unsafe fn install_wakeup_space_observers(view: id, handler: HotkeyHandler) {
     // Active Space changed (Mission Control / Spaces)
     add_obs(c"NSWorkspaceActiveSpaceDidChangeNotification");
 }
+
+/// Track the system "Reduce motion" setting in the `_systemReduceMotion` ivar.
+///
+/// Reads it once now and again whenever the accessibility display options change.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool, after the
+/// overlay views have been created.
+pub unsafe fn install_reduce_motion_observer() {
+    let store = || unsafe {
+        let reduced = system_reduce_motion();
+        apply_to_all_views(|v| set_bool_ivar(v, "_systemReduceMotion", reduced));
+    };
+    store();
+
+    let ws: id = msg_send![get_class("NSWorkspace"), sharedWorkspace];
+    let nc: id = msg_send![ws, notificationCenter];
+    let name: id = msg_send![
+        get_class("NSString"),
+        stringWithUTF8String: c"NSWorkspaceAccessibilityDisplayOptionsDidChangeNotification".as_ptr()
+    ];
+    let block = RcBlock::new(move |_note: id| store());
+    let _: id =
+        msg_send![nc, addObserverForName: name, object: nil, queue: nil, usingBlock: &*block];
+}
+
+/// Keep one overlay per screen, covering its current frame, as displays are
+/// connected, disconnected, resized or rearranged.
+///
+/// # Safety
+/// - `view` must be a valid, non-null pointer to a CustomViewMulti.
+/// - Must be called from main thread with valid autorelease pool.
+pub unsafe fn install_screen_change_observer(view: id) {
+    let center: id = msg_send![get_class("NSNotificationCenter"), defaultCenter];
+    let name: id = msg_send![
+        get_class("NSString"),
+        stringWithUTF8String: c"NSApplicationDidChangeScreenParametersNotification".as_ptr()
+    ];
+    let block = RcBlock::new(move |_note: id| unsafe { sync_overlay_windows(view) });
+    let _: id =
+        msg_send![center, addObserverForName: name, object: nil, queue: nil, usingBlock: &*block];
+}
+
+/// Apply edits made to the settings file outside the app (by hand, by a
+/// dotfiles sync, or synced from another machine) to every overlay view.
+///
+/// # Safety
+/// - `view` must be a valid, non-null pointer to a CustomViewMulti.
+/// - Must be called from main thread with valid autorelease pool.
+pub unsafe fn install_config_file_observer(view: id) {
+    // The directory must exist to be watched
+    let _ = ensure_config_dir();
+    if let Some(dir) = config_path().parent() {
+        watch_config_directory(view, dir);
+    }
+    if let Some(folder) = sync_folder() {
+        watch_config_directory(view, &folder);
+    }
+}
+
+/// Apply changes to the settings files in `dir` to every overlay view.
+///
+/// Each directory is watched once, for the rest of the process.
+///
+/// # Safety
+/// - `view` must be a valid, non-null pointer to a CustomViewMulti.
+/// - Must be called from main thread with valid autorelease pool.
+pub unsafe fn watch_config_directory(view: id, dir: &Path) {
+    if WATCHED_DIRS.with(|w| w.borrow().iter().any(|d| d == dir)) {
+        return;
+    }
+    let Some(dir_str) = dir.to_str() else {
+        return;
+    };
+    WATCHED_DIRS.with(|w| w.borrow_mut().push(dir.to_path_buf()));
+    watch_directory(dir_str, move || unsafe {
+        if !reload_config() {
+            return;
+        }
+        load_settings(MacContext::new(view).backend());
+        reload_frame_colors();
+        update_status_bar_language(view);
+        apply_control_server();
+        show_config_issues();
+    });
+}
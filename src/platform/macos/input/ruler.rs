@@ -0,0 +1,116 @@
+//! Ruler mode input.
+//!
+//! The overlay windows normally ignore the mouse, so clicks reach the
+//! apps below. While the ruler mode is on they accept it, and a local
+//! monitor turns each left click on them into a point of the measured
+//! line instead. Esc is a Carbon hotkey for as long as the mode lasts.
+
+use std::cell::RefCell;
+
+use crate::platform::macos::ffi::bridge::{
+    get_class, id, msg_send, nil, NSPoint, ObjectExt, NO, YES,
+};
+use block2::RcBlock;
+
+use crate::model::{Ruler, RulerLine};
+use crate::platform::macos::app::apply_to_all_views;
+use crate::platform::macos::input::hotkeys::{register_ruler_escape, unregister_ruler_escape};
+
+thread_local! {
+    static RULER: RefCell<Option<Ruler>> = const { RefCell::new(None) };
+}
+
+/// Is the ruler mode on?
+pub fn ruler_on() -> bool {
+    RULER.with(|r| r.borrow().is_some())
+}
+
+/// Line to draw with the pointer at `pointer` (Cocoa coordinates), while
+/// the mode is on and a point has been placed.
+pub fn ruler_line(pointer: (f64, f64)) -> Option<RulerLine> {
+    RULER.with(|r| r.borrow().and_then(|ruler| ruler.line(pointer)))
+}
+
+/// Is `event` in one of the overlay windows?
+unsafe fn on_overlay(event: id) -> bool {
+    let window: id = msg_send![event, window];
+    if window == nil {
+        return false;
+    }
+    let view: id = msg_send![window, contentView];
+    view != nil && msg_send![view, isKindOfClass: get_class("CustomViewMulti")]
+}
+
+/// Redraw every overlay view.
+unsafe fn redraw_all() {
+    apply_to_all_views(|v| {
+        let _: () = msg_send![v, setNeedsDisplay: YES];
+    });
+}
+
+/// Start the ruler mode: the overlay windows take the mouse, left clicks
+/// place points and Esc clears the line.
+///
+/// # Safety
+/// - `host` must be a valid, non-null pointer to a CustomViewMulti.
+/// - Must be called from main thread.
+pub unsafe fn start_ruler(host: id) {
+    if ruler_on() {
+        return;
+    }
+    RULER.with(|r| *r.borrow_mut() = Some(Ruler::new()));
+    apply_to_all_views(|v| {
+        let win: id = msg_send![v, window];
+        let _: () = msg_send![win, setIgnoresMouseEvents: NO];
+    });
+
+    const LEFT_DOWN: u64 = 1; // NSEventTypeLeftMouseDown
+    const LEFT_DOWN_MASK: u64 = 1 << LEFT_DOWN;
+    const LEFT_UP_MASK: u64 = 1 << 2;
+    let block = RcBlock::new(move |event: id| -> id {
+        unsafe {
+            if !on_overlay(event) {
+                return event;
+            }
+            // The release is swallowed too, it has no press to go with
+            let kind: u64 = msg_send![event, type];
+            if kind == LEFT_DOWN {
+                let p: NSPoint = msg_send![get_class("NSEvent"), mouseLocation];
+                RULER.with(|r| {
+                    if let Some(ruler) = r.borrow_mut().as_mut() {
+                        ruler.click((p.x, p.y));
+                    }
+                });
+                redraw_all();
+            }
+        }
+        nil
+    });
+    let mon: id = msg_send![
+        get_class("NSEvent"),
+        addLocalMonitorForEventsMatchingMask: LEFT_DOWN_MASK | LEFT_UP_MASK,
+        handler: &*block
+    ];
+    (*host).store_ivar::<id>("_rulerMonitor", mon);
+    register_ruler_escape(host);
+}
+
+/// End the ruler mode and clear its line; clicks reach the apps again.
+///
+/// # Safety
+/// - `host` must be a valid, non-null pointer to a CustomViewMulti.
+/// - Must be called from main thread.
+pub unsafe fn stop_ruler(host: id) {
+    RULER.with(|r| *r.borrow_mut() = None);
+    unregister_ruler_escape(host);
+    let mon: id = *(*host).load_ivar::<id>("_rulerMonitor");
+    if mon != nil {
+        let _: () = msg_send![get_class("NSEvent"), removeMonitor: mon];
+        (*host).store_ivar::<id>("_rulerMonitor", nil);
+    }
+    apply_to_all_views(|v| {
+        let win: id = msg_send![v, window];
+        let _: () = msg_send![win, setIgnoresMouseEvents: YES];
+    });
+    redraw_all();
+}
@@ -0,0 +1,108 @@
+//! AppleScript command handlers.
+//!
+//! Registers the overlay view with `NSAppleEventManager` for the Apple
+//! events of Lumbus' scripting dictionary (`resources/Lumbus.sdef`) and
+//! turns each one into an event-bus event (see `model::script`).
+
+use std::ffi::{c_char, CStr};
+
+use tracing::warn;
+
+use crate::events::publish;
+use crate::model::script::{SCRIPT_LOAD_PROFILE, SCRIPT_SET_RADIUS, SCRIPT_TOGGLE};
+use crate::model::{four_cc, script_event, ScriptArgument, SCRIPT_SUITE};
+use crate::platform::macos::ffi::bridge::{get_class, id, msg_send, nil, sel};
+
+/// keyDirectObject ('----')
+const KEY_DIRECT_OBJECT: u32 = four_cc(b"----");
+/// keyErrorNumber ('errn')
+const KEY_ERROR_NUMBER: u32 = four_cc(b"errn");
+/// typeNull ('null'): the sender expects no reply
+const TYPE_NULL: u32 = four_cc(b"null");
+/// typeIEEE64BitFloatingPoint ('doub')
+const TYPE_DOUBLE: u32 = four_cc(b"doub");
+/// typeUnicodeText ('utxt')
+const TYPE_UNICODE_TEXT: u32 = four_cc(b"utxt");
+/// typeChar ('TEXT')
+const TYPE_CHAR: u32 = four_cc(b"TEXT");
+/// errAEEventNotHandled: AppleScript reports "Lumbus got an error"
+const ERR_EVENT_NOT_HANDLED: i32 = -1708;
+
+/// Route the Lumbus suite's Apple events to `view`'s
+/// `handleScriptEvent:withReplyEvent:`.
+///
+/// # Safety
+/// - `view` must be a valid, non-null pointer to a CustomViewMulti.
+/// - Must be called from main thread with valid autorelease pool.
+pub unsafe fn install_script_handlers(view: id) {
+    let manager: id = msg_send![get_class("NSAppleEventManager"), sharedAppleEventManager];
+    for event_id in [SCRIPT_TOGGLE, SCRIPT_SET_RADIUS, SCRIPT_LOAD_PROFILE] {
+        let _: () = msg_send![
+            manager,
+            setEventHandler: view,
+            andSelector: sel!(handleScriptEvent:withReplyEvent:),
+            forEventClass: SCRIPT_SUITE,
+            andEventID: event_id
+        ];
+    }
+}
+
+/// Direct parameter of an Apple event, as text or a number.
+///
+/// # Safety
+/// `event` must be a valid NSAppleEventDescriptor. Must be called from
+/// main thread.
+pub unsafe fn direct_argument(event: id) -> ScriptArgument {
+    let direct: id = msg_send![event, paramDescriptorForKeyword: KEY_DIRECT_OBJECT];
+    if direct == nil {
+        return ScriptArgument::None;
+    }
+    let kind: u32 = msg_send![direct, descriptorType];
+    if kind == TYPE_UNICODE_TEXT || kind == TYPE_CHAR {
+        let text: id = msg_send![direct, stringValue];
+        if text == nil {
+            return ScriptArgument::None;
+        }
+        let cstr_ptr: *const c_char = msg_send![text, UTF8String];
+        if cstr_ptr.is_null() {
+            return ScriptArgument::None;
+        }
+        return ScriptArgument::Text(CStr::from_ptr(cstr_ptr).to_string_lossy().into_owned());
+    }
+    let number: id = msg_send![direct, coerceToDescriptorType: TYPE_DOUBLE];
+    if number == nil {
+        return ScriptArgument::None;
+    }
+    ScriptArgument::Number(msg_send![number, doubleValue])
+}
+
+/// Publish the event for a Lumbus suite command, or report an error to
+/// the script if the command cannot be carried out.
+///
+/// # Safety
+/// `event` and `reply` must be the NSAppleEventDescriptors passed to the
+/// handler by `NSAppleEventManager`. Must be called from main thread.
+pub unsafe fn handle_script_event(event: id, reply: id) {
+    let event_id: u32 = msg_send![event, eventID];
+    let argument = direct_argument(event);
+    if let Some(app_event) = script_event(event_id, &argument) {
+        publish(app_event);
+        return;
+    }
+    warn!(
+        "Unsupported script command {:08x} ({:?})",
+        event_id, argument
+    );
+    let reply_type: u32 = if reply == nil {
+        TYPE_NULL
+    } else {
+        msg_send![reply, descriptorType]
+    };
+    if reply_type != TYPE_NULL {
+        let error: id = msg_send![
+            get_class("NSAppleEventDescriptor"),
+            descriptorWithInt32: ERR_EVENT_NOT_HANDLED
+        ];
+        let _: () = msg_send![reply, setParamDescriptor: error, forKeyword: KEY_ERROR_NUMBER];
+    }
+}
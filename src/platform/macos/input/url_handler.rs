@@ -0,0 +1,46 @@
+//! `lumbus://` URL handler.
+//!
+//! macOS delivers URLs opened with the scheme registered in `Info.plist`
+//! (`CFBundleURLTypes`) as a get-URL Apple event. The handler publishes
+//! the URL's events on the event bus (see `model::url_scheme`).
+
+use tracing::warn;
+
+use crate::events::publish;
+use crate::model::{four_cc, url_events, ScriptArgument};
+use crate::platform::macos::ffi::bridge::{get_class, id, msg_send, sel};
+use crate::platform::macos::input::scripting::direct_argument;
+
+/// kInternetEventClass / kAEGetURL ('GURL')
+const GET_URL: u32 = four_cc(b"GURL");
+
+/// Route get-URL Apple events to `view`'s `handleURLEvent:withReplyEvent:`.
+///
+/// # Safety
+/// - `view` must be a valid, non-null pointer to a CustomViewMulti.
+/// - Must be called from main thread with valid autorelease pool.
+pub unsafe fn install_url_handler(view: id) {
+    let manager: id = msg_send![get_class("NSAppleEventManager"), sharedAppleEventManager];
+    let _: () = msg_send![
+        manager,
+        setEventHandler: view,
+        andSelector: sel!(handleURLEvent:withReplyEvent:),
+        forEventClass: GET_URL,
+        andEventID: GET_URL
+    ];
+}
+
+/// Publish the events of the URL carried by a get-URL Apple event.
+///
+/// # Safety
+/// `event` must be the NSAppleEventDescriptor passed to the handler by
+/// `NSAppleEventManager`. Must be called from main thread.
+pub unsafe fn handle_url_event(event: id) {
+    let ScriptArgument::Text(url) = direct_argument(event) else {
+        return;
+    };
+    match url_events(&url) {
+        Ok(events) => events.into_iter().for_each(publish),
+        Err(e) => warn!("Ignoring {url}: {e}"),
+    }
+}
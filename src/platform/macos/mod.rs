@@ -4,7 +4,7 @@
 //! - FFI bindings to Cocoa, Carbon, CoreText, CoreGraphics
 //! - UI components (NSWindow overlays, settings, dialogs)
 //! - Input handling (Carbon hotkeys, NSEvent monitors)
-//! - Storage (NSUserDefaults persistence)
+//! - Storage (config.toml persistence)
 
 pub mod app;
 pub mod ffi;
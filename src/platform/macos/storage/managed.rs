@@ -0,0 +1,159 @@
+//! Admin-managed configuration from the MDM profile and /Library/Preferences.
+//!
+//! Configuration profiles for `com.restevean.lumbus` land in the managed
+//! preferences domain, which NSUserDefaults reports as forced. Machines
+//! without MDM can use `/Library/Preferences/com.restevean.lumbus.plist`,
+//! which only administrators can write. Two dictionaries are read from
+//! each source:
+//!
+//! - `ManagedDefaults`: values for settings the user has not changed.
+//! - `LockedSettings`: values that override the user's and cannot be edited.
+//!
+//! Values are numbers, or a locale tag (`es`) for `lang`. A preference key
+//! forced directly by the profile is treated as locked.
+//! The profile wins over the plist key by key. Precedence rules are
+//! documented in `model::managed`.
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr};
+
+use crate::model::{ManagedConfig, MANAGED_KEYS};
+use crate::platform::macos::ffi::bridge::{get_class, id, msg_send, nil, nsstring_id, sel, Bool};
+
+/// Profile key holding the managed defaults dictionary.
+pub const MANAGED_DEFAULTS_KEY: &str = "ManagedDefaults";
+
+/// Profile key holding the locked settings dictionary.
+pub const LOCKED_SETTINGS_KEY: &str = "LockedSettings";
+
+/// Machine-wide preferences file, writable only by administrators.
+pub const SYSTEM_PREFERENCES_PATH: &str = "/Library/Preferences/com.restevean.lumbus.plist";
+
+thread_local! {
+    static MANAGED: RefCell<Option<ManagedConfig>> = const { RefCell::new(None) };
+}
+
+/// Is this key set by the managed (MDM) domain?
+unsafe fn is_forced(ud: id, key: &str) -> bool {
+    let forced: Bool = msg_send![ud, objectIsForcedForKey: nsstring_id(key)];
+    forced.as_bool()
+}
+
+/// Value of a managed setting as text: a string as is, a number in
+/// decimal.
+unsafe fn value_text(obj: id) -> Option<String> {
+    let is_string: Bool = msg_send![obj, isKindOfClass: get_class("NSString")];
+    if is_string.as_bool() {
+        let utf8: *const c_char = msg_send![obj, UTF8String];
+        return (!utf8.is_null()).then(|| CStr::from_ptr(utf8).to_string_lossy().into_owned());
+    }
+    let numeric: Bool = msg_send![obj, respondsToSelector: sel!(doubleValue)];
+    if !numeric.as_bool() {
+        return None;
+    }
+    let v: f64 = msg_send![obj, doubleValue];
+    Some(v.to_string())
+}
+
+/// Reads the managed keys of a forced dictionary into `store`.
+///
+/// Dictionaries the user wrote themselves (not forced) are ignored.
+unsafe fn read_forced_dict(ud: id, dict_key: &str, store: impl FnMut(&str, &str)) {
+    if !is_forced(ud, dict_key) {
+        return;
+    }
+    let dict: id = msg_send![ud, dictionaryForKey: nsstring_id(dict_key)];
+    read_dict(dict, store);
+}
+
+/// Reads the managed keys of `dict` into `store`, as text.
+unsafe fn read_dict(dict: id, mut store: impl FnMut(&str, &str)) {
+    if dict == nil {
+        return;
+    }
+    let is_dict: Bool = msg_send![dict, isKindOfClass: get_class("NSDictionary")];
+    if !is_dict.as_bool() {
+        return;
+    }
+    for key in MANAGED_KEYS {
+        let obj: id = msg_send![dict, objectForKey: nsstring_id(key)];
+        if obj == nil {
+            continue;
+        }
+        if let Some(text) = value_text(obj) {
+            store(key, &text);
+        }
+    }
+}
+
+/// Reads the managed layer of the machine-wide preferences file.
+unsafe fn load_system_plist() -> ManagedConfig {
+    let mut managed = ManagedConfig::new();
+    let plist: id = msg_send![
+        get_class("NSDictionary"),
+        dictionaryWithContentsOfFile: nsstring_id(SYSTEM_PREFERENCES_PATH)
+    ];
+    if plist == nil {
+        return managed;
+    }
+    let defaults: id = msg_send![plist, objectForKey: nsstring_id(MANAGED_DEFAULTS_KEY)];
+    read_dict(defaults, |k, v| {
+        managed.set_default_text(k, v);
+    });
+    let locked: id = msg_send![plist, objectForKey: nsstring_id(LOCKED_SETTINGS_KEY)];
+    read_dict(locked, |k, v| {
+        managed.lock_text(k, v);
+    });
+    managed
+}
+
+/// Reads the managed layer: the machine-wide plist, then the profile.
+unsafe fn load_managed() -> ManagedConfig {
+    let mut managed = load_system_plist();
+    managed.merge(&load_profile());
+    managed
+}
+
+/// Reads the managed layer of the MDM profile from NSUserDefaults.
+unsafe fn load_profile() -> ManagedConfig {
+    let ud: id = msg_send![get_class("NSUserDefaults"), standardUserDefaults];
+    let mut managed = ManagedConfig::new();
+
+    read_forced_dict(ud, MANAGED_DEFAULTS_KEY, |k, v| {
+        managed.set_default_text(k, v);
+    });
+    read_forced_dict(ud, LOCKED_SETTINGS_KEY, |k, v| {
+        managed.lock_text(k, v);
+    });
+    for key in MANAGED_KEYS {
+        if is_forced(ud, key) {
+            let obj: id = msg_send![ud, objectForKey: nsstring_id(key)];
+            if let Some(text) = (obj != nil).then(|| value_text(obj)).flatten() {
+                managed.lock_text(key, &text);
+            }
+        }
+    }
+    managed
+}
+
+/// Runs `f` with the managed layer, loading it on first use.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn with_managed<R>(f: impl FnOnce(&ManagedConfig) -> R) -> R {
+    MANAGED.with(|m| {
+        let mut m = m.borrow_mut();
+        if m.is_none() {
+            *m = Some(load_managed());
+        }
+        f(m.as_ref().unwrap())
+    })
+}
+
+/// Is this setting locked by the administrator?
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn is_setting_locked(key: &str) -> bool {
+    with_managed(|m| m.is_locked(key))
+}
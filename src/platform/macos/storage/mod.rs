@@ -1,7 +1,11 @@
-//! Storage for macOS using NSUserDefaults.
+//! Configuration storage for macOS.
 //!
-//! Persists overlay settings to the macOS preferences system.
+//! Persists settings to a TOML file in
+//! ~/Library/Application Support/Lumbus/config.toml and reads the
+//! admin-managed layer deployed by MDM.
 
+pub mod managed;
 pub mod preferences;
 
+pub use managed::*;
 pub use preferences::*;
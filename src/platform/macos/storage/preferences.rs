@@ -1,101 +1,317 @@
-//! Persistence of state to NSUserDefaults.
+//! Persistence of state to the preferences file.
 //!
-//! This module provides functions to load and save overlay state
-//! to macOS NSUserDefaults.
+//! Stores settings in ~/Library/Application Support/Lumbus/config.toml
+//! (format shared with Windows, see `model::preferences`). Settings saved
+//! by older versions in NSUserDefaults are imported when the file does
+//! not exist yet.
+//!
+//! The cache, sync and safe mode are shared with Windows (see
+//! `storage::ConfigFile`); this file locates the file, imports the
+//! NSUserDefaults settings and supplies the admin-managed layer (see
+//! `managed`).
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr};
+use std::fs;
+use std::path::{Path, PathBuf};
 
+use super::managed::with_managed;
 use crate::platform::macos::ffi::bridge::{get_class, id, msg_send, nil, nsstring_id};
 
 use crate::model::constants::*;
-use crate::model::OverlayState;
+use crate::model::{
+    ColorPalette, ConfigIssue, FrameColors, ManagedConfig, OverlayState, Preferences, SessionStore,
+    MANAGED_KEYS, PREFERENCES_FILE, STRING_KEYS,
+};
+use crate::storage::ConfigFile;
+
+thread_local! {
+    // In-memory config cache. Loaded once, written on flush.
+    static CONFIG: RefCell<ConfigFile> = RefCell::new(ConfigFile::new(config_path(), |base| {
+        // SAFETY: the file is only loaded from the main thread, by the
+        // functions below
+        unsafe { import_user_defaults(base) }
+    }));
+}
+
+/// Get config file path: ~/Library/Application Support/Lumbus/config.toml
+pub fn config_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(home)
+        .join("Library")
+        .join("Application Support")
+        .join("Lumbus")
+        .join(PREFERENCES_FILE)
+}
+
+/// Get log folder path: ~/Library/Logs/Lumbus
+pub fn log_dir() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(home)
+        .join("Library")
+        .join("Logs")
+        .join("Lumbus")
+}
 
-/// Reads a double from NSUserDefaults, returns default if not set.
+/// Ensure the config directory exists.
+pub fn ensure_config_dir() -> std::io::Result<()> {
+    let path = config_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    Ok(())
+}
+
+/// Copies the settings older versions saved in NSUserDefaults over `base`.
+///
+/// Returns None if none were saved.
 ///
 /// # Safety
 /// Must be called from main thread with valid autorelease pool.
-pub unsafe fn prefs_get_double(key: &str, default: f64) -> f64 {
+unsafe fn import_user_defaults(base: &Preferences) -> Option<Preferences> {
     let ud: id = msg_send![get_class("NSUserDefaults"), standardUserDefaults];
-    let k = nsstring_id(key);
-    let obj: id = msg_send![ud, objectForKey: k];
-    if obj == nil {
-        default
-    } else {
-        msg_send![ud, doubleForKey: k]
+    let mut prefs = base.clone();
+    let mut found = false;
+    // The managed keys are every numeric preference
+    for key in MANAGED_KEYS {
+        let k = nsstring_id(key);
+        let obj: id = msg_send![ud, objectForKey: k];
+        if obj != nil {
+            let val: f64 = msg_send![ud, doubleForKey: k];
+            found |= prefs.set_number(key, val);
+        }
+    }
+    for key in STRING_KEYS {
+        let s: id = msg_send![ud, stringForKey: nsstring_id(key)];
+        if s == nil {
+            continue;
+        }
+        let cstr_ptr: *const c_char = msg_send![s, UTF8String];
+        if !cstr_ptr.is_null() {
+            let val = CStr::from_ptr(cstr_ptr).to_string_lossy();
+            found |= prefs.set_string(key, &val);
+        }
     }
+    found.then_some(prefs)
 }
 
-/// Saves a double to NSUserDefaults.
+/// Run `f` on the cached config and the admin-managed layer.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+unsafe fn with_config<R>(f: impl FnOnce(&mut ConfigFile, &ManagedConfig) -> R) -> R {
+    with_managed(|managed| CONFIG.with(|config| f(&mut config.borrow_mut(), managed)))
+}
+
+/// Re-read the config file, and the synced copy, after either changed on
+/// disk.
+///
+/// Returns true if the settings may have changed.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn reload_config() -> bool {
+    with_config(|config, managed| config.reload(managed))
+}
+
+/// Folder the settings are synced through, if sync is on.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn sync_folder() -> Option<PathBuf> {
+    with_config(|config, managed| config.sync_folder(managed))
+}
+
+/// Sync the settings through `folder`, or stop syncing with `None`.
+///
+/// Settings already synced to the folder by another machine replace this
+/// machine's; otherwise this machine's settings are written there.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn set_sync_folder(folder: Option<&Path>) {
+    with_config(|config, managed| config.set_sync_folder(folder, managed));
+}
+
+/// Problems found in the config file when it was loaded.
+///
+/// Returned once; later calls return nothing.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn take_config_issues() -> Vec<ConfigIssue> {
+    with_config(|config, managed| config.take_issues(managed))
+}
+
+/// Get the cached config, loading from disk if needed.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+unsafe fn get_config() -> Preferences {
+    with_config(|config, managed| config.get(managed))
+}
+
+/// Get the cached config with the admin-locked settings applied.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+unsafe fn effective_config() -> Preferences {
+    with_config(|config, managed| config.effective(managed))
+}
+
+/// Start a safe-mode session: the defaults (the admin-managed ones where
+/// set) replace the settings file, which is not read or written until
+/// the app quits.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn enter_safe_mode() {
+    with_config(|config, managed| config.enter_safe_mode(managed));
+}
+
+/// Is this a safe-mode session?
+pub fn safe_mode() -> bool {
+    CONFIG.with(|config| config.borrow().safe_mode())
+}
+
+/// Flush the config cache to disk if dirty.
+///
+/// Call this when the settings window closes or the app exits.
+pub fn flush_config() {
+    CONFIG.with(|config| config.borrow_mut().flush());
+}
+
+/// Puts the Settings window's settings back to their defaults (the
+/// admin-managed ones where set) and saves them at once.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn restore_defaults() {
+    with_config(|config, managed| config.restore_defaults(managed));
+}
+
+/// Reads a double from config (from cache, locked settings applied).
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn prefs_get_double(key: &str, default: f64) -> f64 {
+    effective_config().number(key).unwrap_or(default)
+}
+
+/// Saves a double to config (to cache, flush later).
+///
+/// Ignored for settings locked by the administrator.
 ///
 /// # Safety
 /// Must be called from main thread with valid autorelease pool.
 pub unsafe fn prefs_set_double(key: &str, val: f64) {
-    let ud: id = msg_send![get_class("NSUserDefaults"), standardUserDefaults];
-    let k = nsstring_id(key);
-    let _: () = msg_send![ud, setDouble: val, forKey: k];
+    with_config(|config, managed| config.set_number(key, val, managed));
 }
 
-/// Reads an integer from NSUserDefaults, returns default if not set.
+/// Reads an integer from config (from cache, locked settings applied).
 ///
 /// # Safety
 /// Must be called from main thread with valid autorelease pool.
 pub unsafe fn prefs_get_int(key: &str, default: i32) -> i32 {
-    let ud: id = msg_send![get_class("NSUserDefaults"), standardUserDefaults];
-    let k = nsstring_id(key);
-    let obj: id = msg_send![ud, objectForKey: k];
-    if obj == nil {
-        default
-    } else {
-        // NSInteger is i64 on 64-bit macOS
-        let val: i64 = msg_send![ud, integerForKey: k];
-        val as i32
-    }
+    effective_config()
+        .number(key)
+        .map(|v| v.round() as i32)
+        .unwrap_or(default)
 }
 
-/// Saves an integer to NSUserDefaults.
+/// Saves an integer to config (to cache, flush later).
+///
+/// Ignored for settings locked by the administrator.
 ///
 /// # Safety
 /// Must be called from main thread with valid autorelease pool.
 pub unsafe fn prefs_set_int(key: &str, val: i32) {
-    let ud: id = msg_send![get_class("NSUserDefaults"), standardUserDefaults];
-    let k = nsstring_id(key);
-    // NSInteger is i64 on 64-bit macOS
-    let _: () = msg_send![ud, setInteger: val as i64, forKey: k];
+    prefs_set_double(key, val as f64);
+}
+
+/// Reads a string from config (from cache).
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn prefs_get_string(key: &str, default: &str) -> String {
+    get_config().string(key).unwrap_or(default).to_string()
+}
+
+/// Saves a string to config (to cache, flush later).
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn prefs_set_string(key: &str, val: &str) {
+    with_config(|config, managed| config.set_string(key, val, managed));
+}
+
+/// Loads the auxiliary-window session.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn load_session() -> SessionStore {
+    SessionStore::decode(&prefs_get_string(PREF_SESSION, ""))
+}
+
+/// Saves the auxiliary-window session and flushes it to disk.
+///
+/// Session changes are rare and must survive an abrupt exit, so they are
+/// written immediately instead of waiting for the next flush.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn save_session(session: &SessionStore) {
+    prefs_set_string(PREF_SESSION, &session.encode());
+    flush_config();
+}
+
+/// Loads the per-display recording-frame colors.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn load_frame_colors() -> FrameColors {
+    FrameColors::decode(&prefs_get_string(PREF_SCREEN_FRAME_COLORS, ""))
+}
+
+/// Saves the per-display recording-frame colors (to cache, flush later).
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn save_frame_colors(colors: &FrameColors) {
+    prefs_set_string(PREF_SCREEN_FRAME_COLORS, &colors.encode());
 }
 
-/// Loads complete state from NSUserDefaults.
+/// Loads the colors saved to the Settings palette.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn load_color_palette() -> ColorPalette {
+    ColorPalette::decode(&prefs_get_string(PREF_COLOR_PALETTE, ""))
+}
+
+/// Saves the colors of the Settings palette and flushes them to disk.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn save_color_palette(palette: &ColorPalette) {
+    prefs_set_string(PREF_COLOR_PALETTE, &palette.encode());
+    flush_config();
+}
+
+/// Loads complete state from config.
 ///
 /// # Safety
 /// Must be called from main thread with valid autorelease pool.
 pub unsafe fn load_state() -> OverlayState {
-    let mut state = OverlayState {
-        radius: prefs_get_double(PREF_RADIUS, DEFAULT_DIAMETER / 2.0),
-        border_width: prefs_get_double(PREF_BORDER, DEFAULT_BORDER_WIDTH),
-        stroke_r: prefs_get_double(PREF_STROKE_R, DEFAULT_COLOR.0),
-        stroke_g: prefs_get_double(PREF_STROKE_G, DEFAULT_COLOR.1),
-        stroke_b: prefs_get_double(PREF_STROKE_B, DEFAULT_COLOR.2),
-        stroke_a: prefs_get_double(PREF_STROKE_A, DEFAULT_COLOR.3),
-        fill_transparency_pct: prefs_get_double(
-            PREF_FILL_TRANSPARENCY,
-            DEFAULT_FILL_TRANSPARENCY_PCT,
-        ),
-        lang: prefs_get_int(PREF_LANG, LANG_EN),
-        overlay_enabled: true,
-        display_mode: DISPLAY_MODE_CIRCLE,
-    };
-    state.validate();
-    state
+    effective_config().to_state()
 }
 
-/// Saves complete state to NSUserDefaults.
+/// Saves complete state to config (to cache, flush later).
+///
+/// Locked settings keep their stored value.
 ///
 /// # Safety
 /// Must be called from main thread with valid autorelease pool.
 pub unsafe fn save_state(state: &OverlayState) {
-    prefs_set_double(PREF_RADIUS, state.radius);
-    prefs_set_double(PREF_BORDER, state.border_width);
-    prefs_set_double(PREF_STROKE_R, state.stroke_r);
-    prefs_set_double(PREF_STROKE_G, state.stroke_g);
-    prefs_set_double(PREF_STROKE_B, state.stroke_b);
-    prefs_set_double(PREF_STROKE_A, state.stroke_a);
-    prefs_set_double(PREF_FILL_TRANSPARENCY, state.fill_transparency_pct);
-    prefs_set_int(PREF_LANG, state.lang);
+    with_config(|config, managed| config.save_state(state, managed));
 }
@@ -0,0 +1,157 @@
+//! About window: version, build hash, license and project links.
+//!
+//! A small regular window (not modal). Opening it again while it is open
+//! replaces it, so it follows a language change.
+
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+use objc2::runtime::AnyObject;
+
+use crate::i18n::{tr, tr_args};
+use crate::model::about::{version_label, ABOUT_LINKS, BUILD_HASH, COPYRIGHT, LICENSE, VERSION};
+use crate::platform::macos::ffi::bridge::{
+    get_class, id, msg_send, nil, nsstring_id, sel, NSApp, NSPoint, NSRect, NSSize, NO, YES,
+};
+
+/// The open About window, if any (retained).
+static ABOUT_WINDOW: AtomicPtr<AnyObject> = AtomicPtr::new(std::ptr::null_mut());
+
+const WINDOW_W: f64 = 340.0;
+const WINDOW_H: f64 = 300.0;
+
+/// Show the About window, bringing the app to the front.
+///
+/// The link buttons send `aboutLink:` to `view`, with the index in
+/// `ABOUT_LINKS` as their tag.
+///
+/// # Safety
+/// - `view` must be a valid, non-null pointer to a CustomViewMulti.
+/// - Must be called from main thread with valid autorelease pool.
+pub unsafe fn show_about_window(view: id) {
+    let previous = ABOUT_WINDOW.swap(std::ptr::null_mut(), Ordering::AcqRel);
+    if !previous.is_null() {
+        let _: () = msg_send![previous, close];
+        let _: () = msg_send![previous, release];
+    }
+
+    // NSWindowStyleMaskTitled (1) | NSWindowStyleMaskClosable (2)
+    let window: id = msg_send![get_class("NSWindow"), alloc];
+    let window: id = msg_send![
+        window,
+        initWithContentRect: NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(WINDOW_W, WINDOW_H)),
+        styleMask: 3u64,
+        backing: 2u64,  // NSBackingStoreBuffered
+        defer: NO
+    ];
+    let _: () = msg_send![window, setReleasedWhenClosed: NO];
+    let _: () = msg_send![window, setTitle: nsstring_id(&tr("About Lumbus"))];
+    let content: id = msg_send![window, contentView];
+
+    // App icon
+    let icon: id = msg_send![NSApp(), applicationIconImage];
+    if icon != nil {
+        let image_view: id = msg_send![get_class("NSImageView"), alloc];
+        let image_view: id = msg_send![image_view, initWithFrame: NSRect::new(
+            NSPoint::new((WINDOW_W - 64.0) / 2.0, WINDOW_H - 84.0),
+            NSSize::new(64.0, 64.0)
+        )];
+        let _: () = msg_send![image_view, setImage: icon];
+        let _: () = msg_send![content, addSubview: image_view];
+    }
+
+    let bold_font: id = msg_send![get_class("NSFont"), boldSystemFontOfSize: 18.0f64];
+    let regular_font: id = msg_send![get_class("NSFont"), systemFontOfSize: 13.0f64];
+    let small_font: id = msg_send![get_class("NSFont"), systemFontOfSize: 11.0f64];
+    let secondary: id = msg_send![get_class("NSColor"), secondaryLabelColor];
+
+    add_label(content, WINDOW_H - 116.0, 24.0, "Lumbus", bold_font, nil);
+    // Selectable so the build hash can be copied into a bug report
+    let version = version_label(VERSION, BUILD_HASH);
+    let version = tr_args("version-label", &[("version", &version)]).unwrap_or(version);
+    let version_field = add_label(
+        content,
+        WINDOW_H - 140.0,
+        18.0,
+        &version,
+        small_font,
+        secondary,
+    );
+    let _: () = msg_send![version_field, setSelectable: YES];
+    add_label(
+        content,
+        WINDOW_H - 190.0,
+        36.0,
+        &tr("Mouse pointer highlighter for presentations and screen recordings."),
+        regular_font,
+        nil,
+    );
+    add_label(
+        content,
+        WINDOW_H - 220.0,
+        18.0,
+        &format!("{COPYRIGHT} · {LICENSE}"),
+        small_font,
+        secondary,
+    );
+
+    // Links, side by side
+    let link_color: id = msg_send![get_class("NSColor"), linkColor];
+    let link_w = (WINDOW_W - 40.0) / ABOUT_LINKS.len() as f64;
+    for (i, (key, _)) in ABOUT_LINKS.iter().enumerate() {
+        let button: id = msg_send![get_class("NSButton"), alloc];
+        let button: id = msg_send![button, initWithFrame: NSRect::new(
+            NSPoint::new(20.0 + i as f64 * link_w, 24.0),
+            NSSize::new(link_w, 24.0)
+        )];
+        let _: () = msg_send![button, setTitle: nsstring_id(&tr(key))];
+        let _: () = msg_send![button, setBordered: NO];
+        let _: () = msg_send![button, setFont: regular_font];
+        let _: () = msg_send![button, setContentTintColor: link_color];
+        let _: () = msg_send![button, setTag: i as isize];
+        let _: () = msg_send![button, setTarget: view];
+        let _: () = msg_send![button, setAction: sel!(aboutLink:)];
+        let _: () = msg_send![content, addSubview: button];
+    }
+
+    let _: () = msg_send![window, center];
+    let _: () = msg_send![NSApp(), activateIgnoringOtherApps: YES];
+    let _: () = msg_send![window, makeKeyAndOrderFront: nil];
+    ABOUT_WINDOW.store(window, Ordering::Release);
+}
+
+/// Add a centered, wrapping, read-only label `height` points tall at `y`.
+unsafe fn add_label(content: id, y: f64, height: f64, text: &str, font: id, color: id) -> id {
+    let label: id = msg_send![get_class("NSTextField"), alloc];
+    let label: id = msg_send![label, initWithFrame: NSRect::new(
+        NSPoint::new(20.0, y),
+        NSSize::new(WINDOW_W - 40.0, height)
+    )];
+    let _: () = msg_send![label, setStringValue: nsstring_id(text)];
+    let _: () = msg_send![label, setBezeled: NO];
+    let _: () = msg_send![label, setDrawsBackground: NO];
+    let _: () = msg_send![label, setEditable: NO];
+    let _: () = msg_send![label, setSelectable: NO];
+    let _: () = msg_send![label, setAlignment: 1i64]; // NSTextAlignmentCenter
+    let _: () = msg_send![label, setFont: font];
+    if color != nil {
+        let _: () = msg_send![label, setTextColor: color];
+    }
+    let _: () = msg_send![content, addSubview: label];
+    label
+}
+
+/// Open link `index` of `ABOUT_LINKS` in the default browser.
+///
+/// # Safety
+/// Must be called with a valid autorelease pool.
+pub unsafe fn open_about_link(index: usize) {
+    let Some((_, link)) = ABOUT_LINKS.get(index) else {
+        return;
+    };
+    let url: id = msg_send![get_class("NSURL"), URLWithString: nsstring_id(link)];
+    if url == nil {
+        return;
+    }
+    let workspace: id = msg_send![get_class("NSWorkspace"), sharedWorkspace];
+    let _: bool = msg_send![workspace, openURL: url];
+}
@@ -0,0 +1,54 @@
+//! Save panel for exporting the click log.
+
+use std::ffi::{c_char, CStr};
+
+use tracing::error;
+
+use crate::platform::macos::ffi::bridge::{get_class, id, msg_send, nil, nsstring_id, NSApp, YES};
+
+use crate::i18n::tr;
+use crate::model::click_log::default_file_name;
+use crate::model::ClickLogFormat;
+use crate::platform::macos::ffi::overlay_window_level;
+use crate::platform::macos::input::export_click_log_text;
+
+/// Ask for a destination file and write the recorded clicks to it.
+///
+/// Blocks until the save panel is dismissed. Write errors are logged.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn export_click_log(format: ClickLogFormat) {
+    let app: id = NSApp();
+    let _: () = msg_send![app, activateIgnoringOtherApps: YES];
+
+    let panel: id = msg_send![get_class("NSSavePanel"), savePanel];
+    let _: () = msg_send![panel, setTitle: nsstring_id(&tr("Export click log"))];
+    let _: () = msg_send![
+        panel,
+        setNameFieldStringValue: nsstring_id(&default_file_name(format))
+    ];
+    let _: () = msg_send![panel, setCanCreateDirectories: YES];
+    let _: () = msg_send![panel, setLevel: overlay_window_level()];
+
+    // NSModalResponseOK = 1
+    let response: isize = msg_send![panel, runModal];
+    if response != 1 {
+        return;
+    }
+
+    let url: id = msg_send![panel, URL];
+    if url == nil {
+        return;
+    }
+    let path: id = msg_send![url, path];
+    let cstr_ptr: *const c_char = msg_send![path, UTF8String];
+    if cstr_ptr.is_null() {
+        return;
+    }
+    let path = CStr::from_ptr(cstr_ptr).to_string_lossy().into_owned();
+
+    if let Err(e) = std::fs::write(&path, export_click_log_text(format)) {
+        error!("Failed to export click log to {}: {}", path, e);
+    }
+}
@@ -0,0 +1,41 @@
+//! Dialog reporting problems in the settings file.
+//!
+//! Shown once at startup when `config.toml` had settings that could not
+//! be used as written (see `model::preferences`).
+
+use crate::platform::macos::ffi::bridge::{get_class, id, msg_send, nsstring_id, NSApp, YES};
+use block2::RcBlock;
+
+use crate::i18n::{tr, tr_args};
+use crate::model::describe_issues;
+use crate::platform::macos::storage::{config_path, take_config_issues};
+
+/// Show the problems found in the settings file, if any.
+///
+/// The alert opens once the application run loop has started.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn show_config_issues() {
+    let issues = take_config_issues();
+    if issues.is_empty() {
+        return;
+    }
+
+    let path = config_path().display().to_string();
+    let title = tr("Settings file problems");
+    let intro = tr_args("settings-file-problems-detail", &[("path", &path)]).unwrap_or(path);
+    let message = format!("{}\n\n{}", intro, describe_issues(&issues));
+
+    let block = RcBlock::new(move || unsafe {
+        let alert: id = msg_send![get_class("NSAlert"), new];
+        // NSAlertStyleWarning = 0
+        let _: () = msg_send![alert, setAlertStyle: 0u64];
+        let _: () = msg_send![alert, setMessageText: nsstring_id(&title)];
+        let _: () = msg_send![alert, setInformativeText: nsstring_id(&message)];
+        let _: () = msg_send![NSApp(), activateIgnoringOtherApps: YES];
+        let _: i64 = msg_send![alert, runModal];
+    });
+    let queue: id = msg_send![get_class("NSOperationQueue"), mainQueue];
+    let _: () = msg_send![queue, addOperationWithBlock: &*block];
+}
@@ -0,0 +1,60 @@
+//! Alert shown after a panic, offering to reveal the crash report.
+//!
+//! Called from the panic hook (see `logging::install_panic_hook`) once the
+//! report is written. The alert runs on the main thread, and the hook
+//! waits until the user closes it: release builds abort on panic, so a
+//! hook that returned early would take the alert down with the app.
+
+use std::path::Path;
+
+use block2::RcBlock;
+
+use crate::i18n::{tr, tr_args};
+use crate::platform::macos::ffi::bridge::{get_class, id, msg_send, nsstring_id, NSApp, YES};
+
+/// `NSAlertFirstButtonReturn`.
+const FIRST_BUTTON: i64 = 1000;
+
+/// Tell the user the app crashed and offer to show the report at `path`
+/// in Finder.
+pub fn show_crash_report(path: &Path) {
+    let path = path.display().to_string();
+    unsafe {
+        let main: bool = msg_send![get_class("NSThread"), isMainThread];
+        if main {
+            run_alert(&path);
+        } else {
+            let block = RcBlock::new(move || run_alert(&path));
+            let operation: id =
+                msg_send![get_class("NSBlockOperation"), blockOperationWithBlock: &*block];
+            let queue: id = msg_send![get_class("NSOperationQueue"), mainQueue];
+            let _: () = msg_send![queue, addOperation: operation];
+            let _: () = msg_send![operation, waitUntilFinished];
+        }
+    }
+}
+
+fn run_alert(path: &str) {
+    let title = tr("Lumbus crashed");
+    let detail =
+        tr_args("crash-report-detail", &[("path", path)]).unwrap_or_else(|| path.to_string());
+    unsafe {
+        let alert: id = msg_send![get_class("NSAlert"), new];
+        // NSAlertStyleCritical = 2
+        let _: () = msg_send![alert, setAlertStyle: 2u64];
+        let _: () = msg_send![alert, setMessageText: nsstring_id(&title)];
+        let _: () = msg_send![alert, setInformativeText: nsstring_id(&detail)];
+        let _: id = msg_send![alert, addButtonWithTitle: nsstring_id(&tr("Show report"))];
+        let _: id = msg_send![alert, addButtonWithTitle: nsstring_id(&tr("Close"))];
+        let _: () = msg_send![NSApp(), activateIgnoringOtherApps: YES];
+        let response: i64 = msg_send![alert, runModal];
+        if response == FIRST_BUTTON {
+            let workspace: id = msg_send![get_class("NSWorkspace"), sharedWorkspace];
+            let _: bool = msg_send![
+                workspace,
+                selectFile: nsstring_id(path),
+                inFileViewerRootedAtPath: nsstring_id("")
+            ];
+        }
+    }
+}
@@ -1,9 +1,15 @@
 //! Help overlay showing keyboard shortcuts.
 //!
-//! Displays a semi-transparent overlay with all available hotkeys.
-//! Dismisses on any key press.
-
+//! Displays a semi-transparent overlay with the hotkeys in use: the
+//! selected profile with the user's rebinds, those another app holds
+//! marked as unavailable.
+//! Can be dragged anywhere (snapping to screen edges); the position is
+//! remembered per display. Dismisses on any key press or plain click.
+
+use std::cell::Cell;
+use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 
 use crate::platform::macos::ffi::bridge::{
     get_bool_ivar, get_class, id, msg_send, nil, nsstring_id, sel, set_bool_ivar, NSApp, NSPoint,
@@ -12,45 +18,79 @@ use crate::platform::macos::ffi::bridge::{
 use block2::RcBlock;
 
 use crate::events::{publish, AppEvent};
-use crate::platform::macos::app::{apply_to_all_views, lang_is_es};
+use crate::i18n::tr;
+use crate::model::placement::{clamp_to_screen, display_key, snap_to_edges, ScreenRect};
+use crate::model::session::SESSION_HELP_OVERLAY;
+use crate::model::{help_entries, OVERLAY_DRAG_THRESHOLD};
+use crate::platform::macos::app::apply_to_all_views;
 use crate::platform::macos::ffi::overlay_window_level;
-use crate::tr_key;
+use crate::platform::macos::input::{current_hotkey_bindings, hotkey_registered};
+use crate::platform::macos::storage::{load_session, save_session};
 
 /// Guard to prevent multiple help overlays
 static HELP_OPENING: AtomicBool = AtomicBool::new(false);
 
-/// Data structure for a single hotkey entry to display.
-struct HotkeyEntry {
-    /// The key combination to display (e.g., "Ctrl + A")
-    keys: &'static str,
-    /// Translation key for the description
-    description_key: &'static str,
+/// Origin to use for the next help overlay (set when restoring a session).
+static PENDING_ORIGIN: Mutex<Option<(f64, f64)>> = Mutex::new(None);
+
+/// Reopen the help overlay restored from a previous session.
+///
+/// The overlay is shown through the event bus (so it goes through the
+/// dispatcher like a hotkey would) at `origin` if it is still on screen.
+pub fn restore_help_overlay(origin: Option<(f64, f64)>) {
+    if let Ok(mut pending) = PENDING_ORIGIN.lock() {
+        *pending = origin;
+    }
+    publish(AppEvent::ShowHelp);
 }
 
-/// All hotkeys to display in the help overlay.
-const HOTKEYS: &[HotkeyEntry] = &[
-    HotkeyEntry {
-        keys: "Ctrl + A",
-        description_key: "Toggle overlay",
-    },
-    HotkeyEntry {
-        keys: "\u{2318} + Shift + ,",
-        description_key: "Open settings",
-    },
-    HotkeyEntry {
-        keys: "\u{2318} + Shift + H",
-        description_key: "Show help",
-    },
-    HotkeyEntry {
-        keys: "Ctrl + Shift + X",
-        description_key: "Quit app",
-    },
-];
+/// Pointer and window origin captured when a mouse press starts.
+#[derive(Clone, Copy)]
+struct DragStart {
+    mouse: (f64, f64),
+    origin: (f64, f64),
+}
+
+fn screen_rect(frame: NSRect) -> ScreenRect {
+    ScreenRect::new(
+        frame.origin.x,
+        frame.origin.y,
+        frame.size.width,
+        frame.size.height,
+    )
+}
+
+/// Snap a dragged overlay to the edges of its screen and persist the
+/// new position, both for session restore and for that display.
+///
+/// # Safety
+/// `dialog` must be a valid NSWindow. Must be called from main thread.
+unsafe fn finish_drag(dialog: id) {
+    let scr: id = msg_send![dialog, screen];
+    if scr == nil {
+        return;
+    }
+    let screen = screen_rect(msg_send![scr, frame]);
+    let frame: NSRect = msg_send![dialog, frame];
+    let (x, y) = snap_to_edges(
+        (frame.origin.x, frame.origin.y),
+        (frame.size.width, frame.size.height),
+        &screen,
+    );
+    let _: () = msg_send![dialog, setFrameOrigin: NSPoint::new(x, y)];
+
+    let mut session = load_session();
+    session.set_position(SESSION_HELP_OVERLAY, x, y);
+    session.set_display_position(SESSION_HELP_OVERLAY, &display_key(&screen), x, y);
+    save_session(&session);
+}
 
 /// Show the help overlay with keyboard shortcuts.
 ///
-/// The overlay appears centered on the screen where the cursor is.
-/// It dismisses when the user presses any key.
+/// The overlay appears where it was last dragged to on the screen where the
+/// cursor is (centered the first time), unless it is being restored from a
+/// previous session. It can be dragged to a new position and dismisses when
+/// the user presses any key or clicks without dragging.
 ///
 /// Publishes `AppEvent::HelpClosed` when dismissed.
 ///
@@ -74,11 +114,10 @@ pub unsafe fn show_help_overlay(view: id) {
         let _: () = msg_send![v, setNeedsDisplay: YES];
     });
 
-    let es = lang_is_es(view);
-
     // Dialog dimensions
     let dialog_w: f64 = 380.0;
-    let dialog_h: f64 = 260.0;
+    // Title and footer, plus a 32-pt row per hotkey of the profile
+    let dialog_h: f64 = 132.0 + 32.0 * current_hotkey_bindings().len() as f64;
 
     // Create BORDERLESS window (key to appearing over fullscreen apps)
     // NSBorderlessWindowMask = 0
@@ -101,30 +140,43 @@ pub unsafe fn show_help_overlay(view: id) {
     // Allow the window to receive mouse events
     let _: () = msg_send![dialog, setIgnoresMouseEvents: NO];
 
-    // Find screen where cursor is
+    // Find screen where cursor is (and whether a restored origin is still visible)
+    let restored = PENDING_ORIGIN.lock().ok().and_then(|mut p| p.take());
     let mouse_loc: NSPoint = msg_send![get_class("NSEvent"), mouseLocation];
     let screens: id = msg_send![get_class("NSScreen"), screens];
     let screen_count: usize = msg_send![screens, count];
-    let mut target_screen: id = msg_send![get_class("NSScreen"), mainScreen];
+    let main_screen: id = msg_send![get_class("NSScreen"), mainScreen];
+    let mut target_frame = screen_rect(msg_send![main_screen, frame]);
+    let mut restored_visible = false;
 
     for i in 0..screen_count {
         let scr: id = msg_send![screens, objectAtIndex: i];
-        let frame: NSRect = msg_send![scr, frame];
-        if mouse_loc.x >= frame.origin.x
-            && mouse_loc.x < frame.origin.x + frame.size.width
-            && mouse_loc.y >= frame.origin.y
-            && mouse_loc.y < frame.origin.y + frame.size.height
-        {
-            target_screen = scr;
-            break;
+        let frame = screen_rect(msg_send![scr, frame]);
+        if let Some((rx, ry)) = restored {
+            restored_visible |= frame.contains(rx, ry);
+        }
+        if frame.contains(mouse_loc.x, mouse_loc.y) {
+            target_frame = frame;
         }
     }
 
-    // Center dialog on target screen
-    let screen_frame: NSRect = msg_send![target_screen, frame];
-    let centered_x = screen_frame.origin.x + (screen_frame.size.width - dialog_w) / 2.0;
-    let centered_y = screen_frame.origin.y + (screen_frame.size.height - dialog_h) / 2.0;
-    let _: () = msg_send![dialog, setFrameOrigin: NSPoint { x: centered_x, y: centered_y }];
+    // Restore the previous origin, else the position last used on this
+    // display, else center dialog on target screen
+    let mut session = load_session();
+    let saved = session.display_position(SESSION_HELP_OVERLAY, &display_key(&target_frame));
+    let (x, y) = match (restored, saved) {
+        (Some(pos), _) if restored_visible => pos,
+        (_, Some(pos)) => clamp_to_screen(pos, (dialog_w, dialog_h), &target_frame),
+        _ => (
+            target_frame.x + (target_frame.w - dialog_w) / 2.0,
+            target_frame.y + (target_frame.h - dialog_h) / 2.0,
+        ),
+    };
+    let _: () = msg_send![dialog, setFrameOrigin: NSPoint { x, y }];
+
+    // Remember that help is open (restored on next launch if we never close it)
+    session.mark_open(SESSION_HELP_OVERLAY, Some((x, y)));
+    save_session(&session);
 
     // Create content view with rounded background
     let content: id = msg_send![dialog, contentView];
@@ -149,7 +201,7 @@ pub unsafe fn show_help_overlay(view: id) {
     let _: () = msg_send![content, addSubview: bg_box];
 
     // Title label
-    let title_text = tr_key("Keyboard Shortcuts", es);
+    let title_text = tr("Keyboard Shortcuts");
     let title_label: id = msg_send![get_class("NSTextField"), alloc];
     let title_label: id = msg_send![title_label, initWithFrame: NSRect::new(
         NSPoint::new(20.0, dialog_h - 50.0),
@@ -184,7 +236,11 @@ pub unsafe fn show_help_overlay(view: id) {
         alpha: 1.0f64
     ];
 
-    for (i, entry) in HOTKEYS.iter().enumerate() {
+    // Hotkeys in use, those another app holds marked
+    let entries = help_entries(current_hotkey_bindings(), |action| {
+        hotkey_registered(view, action)
+    });
+    for (i, entry) in entries.iter().enumerate() {
         let y = start_y - (i as f64 * row_height);
 
         // Key combination label (left)
@@ -193,7 +249,7 @@ pub unsafe fn show_help_overlay(view: id) {
             NSPoint::new(key_x, y),
             NSSize::new(key_w, 24.0)
         )];
-        let _: () = msg_send![key_label, setStringValue: nsstring_id(entry.keys)];
+        let _: () = msg_send![key_label, setStringValue: nsstring_id(&entry.keys)];
         let _: () = msg_send![key_label, setBezeled: NO];
         let _: () = msg_send![key_label, setDrawsBackground: NO];
         let _: () = msg_send![key_label, setEditable: NO];
@@ -204,7 +260,10 @@ pub unsafe fn show_help_overlay(view: id) {
         let _: () = msg_send![content, addSubview: key_label];
 
         // Description label (right)
-        let desc_text = tr_key(entry.description_key, es);
+        let mut desc_text = tr(entry.description);
+        if !entry.registered {
+            desc_text = format!("{desc_text} ({})", tr("unavailable"));
+        }
         let desc_label: id = msg_send![get_class("NSTextField"), alloc];
         let desc_label: id = msg_send![desc_label, initWithFrame: NSRect::new(
             NSPoint::new(desc_x, y),
@@ -222,7 +281,7 @@ pub unsafe fn show_help_overlay(view: id) {
     }
 
     // Footer label
-    let footer_text = tr_key("Press any key to close", es);
+    let footer_text = tr("Press any key to close");
     let footer_label: id = msg_send![get_class("NSTextField"), alloc];
     let footer_label: id = msg_send![footer_label, initWithFrame: NSRect::new(
         NSPoint::new(20.0, 20.0),
@@ -267,22 +326,75 @@ pub unsafe fn show_help_overlay(view: id) {
         handler: &*key_block
     ];
 
-    // Also close on mouse click anywhere
+    // Mouse: dragging moves the overlay (snapping to screen edges),
+    // a plain click anywhere closes it
     const LEFT_DOWN_MASK: u64 = 1 << 1;
+    const LEFT_UP_MASK: u64 = 1 << 2;
+    const LEFT_DRAGGED_MASK: u64 = 1 << 6;
+    let drag = Rc::new(Cell::new(None::<DragStart>));
+    let moved = Rc::new(Cell::new(false));
+
+    let (drag_down, moved_down) = (drag.clone(), moved.clone());
+    let down_block = RcBlock::new(move |_event: id| -> id {
+        unsafe {
+            let mouse: NSPoint = msg_send![get_class("NSEvent"), mouseLocation];
+            let frame: NSRect = msg_send![dialog, frame];
+            drag_down.set(Some(DragStart {
+                mouse: (mouse.x, mouse.y),
+                origin: (frame.origin.x, frame.origin.y),
+            }));
+            moved_down.set(false);
+        }
+        nil
+    });
+
+    let (drag_move, moved_move) = (drag.clone(), moved.clone());
+    let dragged_block = RcBlock::new(move |_event: id| -> id {
+        unsafe {
+            if let Some(start) = drag_move.get() {
+                let mouse: NSPoint = msg_send![get_class("NSEvent"), mouseLocation];
+                let (dx, dy) = (mouse.x - start.mouse.0, mouse.y - start.mouse.1);
+                if moved_move.get() || dx.hypot(dy) > OVERLAY_DRAG_THRESHOLD {
+                    moved_move.set(true);
+                    let origin = NSPoint::new(start.origin.0 + dx, start.origin.1 + dy);
+                    let _: () = msg_send![dialog, setFrameOrigin: origin];
+                }
+            }
+        }
+        nil
+    });
+
     let flag_ptr2 = should_close;
-    let click_block = RcBlock::new(move |_event: id| -> id {
+    let up_block = RcBlock::new(move |_event: id| -> id {
         unsafe {
-            *flag_ptr2 = true;
-            let app: id = NSApp();
-            let _: () = msg_send![app, stopModal];
+            if drag.take().is_none() {
+                return nil;
+            }
+            if !moved.get() {
+                *flag_ptr2 = true;
+                let app: id = NSApp();
+                let _: () = msg_send![app, stopModal];
+                return nil;
+            }
+            finish_drag(dialog);
         }
         nil
     });
-    let click_mon: id = msg_send![
-        get_class("NSEvent"),
-        addLocalMonitorForEventsMatchingMask: LEFT_DOWN_MASK,
-        handler: &*click_block
-    ];
+
+    let mouse_mons: Vec<id> = [
+        (LEFT_DOWN_MASK, &*down_block),
+        (LEFT_DRAGGED_MASK, &*dragged_block),
+        (LEFT_UP_MASK, &*up_block),
+    ]
+    .into_iter()
+    .map(|(mask, block)| {
+        msg_send![
+            get_class("NSEvent"),
+            addLocalMonitorForEventsMatchingMask: mask,
+            handler: block
+        ]
+    })
+    .collect();
 
     // Show dialog and run modal
     let app: id = NSApp();
@@ -292,7 +404,9 @@ pub unsafe fn show_help_overlay(view: id) {
 
     // Clean up monitors
     let _: () = msg_send![get_class("NSEvent"), removeMonitor: key_mon];
-    let _: () = msg_send![get_class("NSEvent"), removeMonitor: click_mon];
+    for mon in mouse_mons {
+        let _: () = msg_send![get_class("NSEvent"), removeMonitor: mon];
+    }
 
     // Clean up flag
     let _ = Box::from_raw(should_close);
@@ -300,6 +414,10 @@ pub unsafe fn show_help_overlay(view: id) {
     // Close dialog
     let _: () = msg_send![dialog, orderOut: nil];
 
+    let mut session = load_session();
+    session.mark_closed(SESSION_HELP_OVERLAY);
+    save_session(&session);
+
     // Restore overlay if it was enabled
     if was_enabled {
         apply_to_all_views(|v| {
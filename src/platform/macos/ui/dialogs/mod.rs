@@ -1,9 +1,21 @@
 //! Dialog windows.
 //!
-//! This module contains dialog windows like quit confirmation and help overlay.
+//! This module contains dialog windows like quit confirmation, help overlay,
+//! the click log export panel, the settings file problems alert, the
+//! sync folder picker, the crash report alert and the About window.
 
+pub mod about_window;
+pub mod click_log_export;
+pub mod config_issues;
+pub mod crash_report;
 pub mod help_overlay;
 pub mod quit_dialog;
+pub mod sync_folder;
 
-pub use help_overlay::show_help_overlay;
+pub use about_window::{open_about_link, show_about_window};
+pub use click_log_export::export_click_log;
+pub use config_issues::show_config_issues;
+pub use crash_report::show_crash_report;
+pub use help_overlay::{restore_help_overlay, show_help_overlay};
 pub use quit_dialog::confirm_and_maybe_quit;
+pub use sync_folder::choose_sync_folder;
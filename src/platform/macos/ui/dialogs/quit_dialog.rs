@@ -1,6 +1,7 @@
 //! Quit confirmation dialog.
 //!
-//! Custom borderless dialog that can appear over fullscreen apps.
+//! Custom borderless dialog that can appear over fullscreen apps, shown
+//! while the `confirm_quit` setting is on.
 
 use std::sync::atomic::{AtomicBool, Ordering};
 
@@ -12,14 +13,17 @@ use crate::platform::macos::ffi::CGColorRef;
 use block2::RcBlock;
 
 use crate::events::{publish, AppEvent};
-use crate::platform::macos::app::{apply_to_all_views, lang_is_es};
+use crate::i18n::tr;
+use crate::model::constants::PREF_CONFIRM_QUIT;
+use crate::platform::macos::app::apply_to_all_views;
 use crate::platform::macos::ffi::overlay_window_level;
-use crate::tr_key;
+use crate::platform::macos::storage::prefs_get_int;
 
 /// Guard to prevent multiple quit dialogs
 static QUIT_DIALOG_OPENING: AtomicBool = AtomicBool::new(false);
 
-/// Show a quit confirmation dialog.
+/// Show a quit confirmation dialog, or quit at once while
+/// `PREF_CONFIRM_QUIT` is off.
 ///
 /// If user cancels, publishes `AppEvent::QuitCancelled` to the event bus.
 /// The dispatcher handles hotkey reinstallation.
@@ -29,6 +33,12 @@ static QUIT_DIALOG_OPENING: AtomicBool = AtomicBool::new(false);
 /// - `view` must be a valid, non-null pointer to a CustomViewMulti.
 /// - Must be called from main thread with valid autorelease pool.
 pub unsafe fn confirm_and_maybe_quit(view: id) {
+    if prefs_get_int(PREF_CONFIRM_QUIT, 1) == 0 {
+        let app: id = msg_send![get_class("NSApplication"), sharedApplication];
+        let _: () = msg_send![app, terminate: nil];
+        return;
+    }
+
     // Atomic guard: only one quit dialog can be opening at a time
     if QUIT_DIALOG_OPENING
         .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
@@ -45,8 +55,6 @@ pub unsafe fn confirm_and_maybe_quit(view: id) {
         let _: () = msg_send![v, setNeedsDisplay: YES];
     });
 
-    let es = lang_is_es(view);
-
     // Dialog dimensions
     let dialog_w: f64 = 320.0;
     let dialog_h: f64 = 140.0;
@@ -119,17 +127,13 @@ pub unsafe fn confirm_and_maybe_quit(view: id) {
     let _: () = msg_send![content, addSubview: bg_box];
 
     // Title label
-    let title_text = if es {
-        "¿Salir de la aplicación?"
-    } else {
-        "Quit the app?"
-    };
+    let title_text = tr("Quit the app?");
     let title_label: id = msg_send![get_class("NSTextField"), alloc];
     let title_label: id = msg_send![title_label, initWithFrame: NSRect::new(
         NSPoint::new(20.0, dialog_h - 45.0),
         NSSize::new(dialog_w - 40.0, 30.0)
     )];
-    let _: () = msg_send![title_label, setStringValue: nsstring_id(title_text)];
+    let _: () = msg_send![title_label, setStringValue: nsstring_id(&title_text)];
     let _: () = msg_send![title_label, setBezeled: NO];
     let _: () = msg_send![title_label, setDrawsBackground: NO];
     let _: () = msg_send![title_label, setEditable: NO];
@@ -142,17 +146,13 @@ pub unsafe fn confirm_and_maybe_quit(view: id) {
     let _: () = msg_send![content, addSubview: title_label];
 
     // Message label
-    let msg_text = if es {
-        "Se cerrará la app"
-    } else {
-        "The app will close"
-    };
+    let msg_text = tr("The app will close");
     let msg_label: id = msg_send![get_class("NSTextField"), alloc];
     let msg_label: id = msg_send![msg_label, initWithFrame: NSRect::new(
         NSPoint::new(20.0, dialog_h - 75.0),
         NSSize::new(dialog_w - 40.0, 20.0)
     )];
-    let _: () = msg_send![msg_label, setStringValue: nsstring_id(msg_text)];
+    let _: () = msg_send![msg_label, setStringValue: nsstring_id(&msg_text)];
     let _: () = msg_send![msg_label, setBezeled: NO];
     let _: () = msg_send![msg_label, setDrawsBackground: NO];
     let _: () = msg_send![msg_label, setEditable: NO];
@@ -177,8 +177,8 @@ pub unsafe fn confirm_and_maybe_quit(view: id) {
     let btn_start_x = (dialog_w - total_btn_w) / 2.0;
 
     // Button titles
-    let cancel_title = tr_key("Cancel", es);
-    let quit_title = tr_key("Quit", es);
+    let cancel_title = tr("Cancel");
+    let quit_title = tr("Quit");
 
     // Cancel button (left) - not focused initially (gray)
     let cancel_btn: id = msg_send![get_class("NSButton"), alloc];
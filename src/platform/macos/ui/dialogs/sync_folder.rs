@@ -0,0 +1,46 @@
+//! Open panel for choosing the folder the settings are synced through.
+
+use std::ffi::{c_char, CStr};
+
+use crate::platform::macos::ffi::bridge::{
+    get_class, id, msg_send, nil, nsstring_id, NSApp, NO, YES,
+};
+
+use crate::i18n::tr;
+use crate::platform::macos::ffi::overlay_window_level;
+
+/// Ask for a folder to sync the settings through.
+///
+/// Blocks until the open panel is dismissed; returns None if cancelled.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn choose_sync_folder() -> Option<String> {
+    let app: id = NSApp();
+    let _: () = msg_send![app, activateIgnoringOtherApps: YES];
+
+    let panel: id = msg_send![get_class("NSOpenPanel"), openPanel];
+    let _: () = msg_send![panel, setTitle: nsstring_id(&tr("Choose sync folder"))];
+    let _: () = msg_send![panel, setCanChooseDirectories: YES];
+    let _: () = msg_send![panel, setCanChooseFiles: NO];
+    let _: () = msg_send![panel, setAllowsMultipleSelection: NO];
+    let _: () = msg_send![panel, setCanCreateDirectories: YES];
+    let _: () = msg_send![panel, setLevel: overlay_window_level()];
+
+    // NSModalResponseOK = 1
+    let response: isize = msg_send![panel, runModal];
+    if response != 1 {
+        return None;
+    }
+
+    let url: id = msg_send![panel, URL];
+    if url == nil {
+        return None;
+    }
+    let path: id = msg_send![url, path];
+    let cstr_ptr: *const c_char = msg_send![path, UTF8String];
+    if cstr_ptr.is_null() {
+        return None;
+    }
+    Some(CStr::from_ptr(cstr_ptr).to_string_lossy().into_owned())
+}
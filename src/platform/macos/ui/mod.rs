@@ -3,24 +3,40 @@
 //! This module contains UI components:
 //!
 //! ## overlay/
-//! - drawing.rs: Circle and letter drawing logic
+//! - drawing.rs: Circle, laser and letter drawing logic
 //!
 //! ## dialogs/
 //! - quit_dialog.rs: confirm_and_maybe_quit
 //! - help_overlay.rs: show_help_overlay
+//! - crash_report.rs: show_crash_report
+//! - about_window.rs: show_about_window
 //!
 //! ## settings/
-//! - window.rs: open_settings_window, close_settings_window
+//! - window.rs: open_settings_window, start_onboarding (host the shared egui panels)
+//!
+//! ## session.rs
+//! - Restores auxiliary windows open in the previous session
 //!
 //! ## status_bar.rs
 //! - Status bar icon with dropdown menu
 
 pub mod dialogs;
 pub mod overlay;
+pub mod session;
 pub mod settings;
 pub mod status_bar;
 
-pub use dialogs::{confirm_and_maybe_quit, show_help_overlay};
-pub use overlay::{draw_circle, draw_letter, register_and_create_view, ClickLetter, DrawParams};
-pub use settings::{close_settings_window, open_settings_window};
-pub use status_bar::{install_status_bar, update_status_bar_language};
+pub use dialogs::{
+    choose_sync_folder, confirm_and_maybe_quit, export_click_log, open_about_link,
+    show_about_window, show_config_issues, show_crash_report, show_help_overlay,
+};
+pub use overlay::{
+    draw_badge, draw_circle, draw_color_sample, draw_coordinates_label, draw_debug_label,
+    draw_grid, draw_hold_ring, draw_icon, draw_laser, draw_letter, draw_pen_tip, draw_ruler,
+    draw_screen_frame, draw_zoom, enable_debug_overlay, frame_color_for_screen,
+    register_and_create_view, sample_screen_color, set_click_letters, set_letter_font, ClickLetter,
+    DrawParams,
+};
+pub use session::restore_session_windows;
+pub use settings::{open_settings_window, start_onboarding};
+pub use status_bar::{install_status_bar, update_status_bar_language, update_status_bar_state};
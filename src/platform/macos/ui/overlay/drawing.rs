@@ -4,11 +4,41 @@
 //! draw_rect method. While still using unsafe FFI calls to Cocoa, the
 //! logic is isolated and easier to understand/maintain.
 
-use crate::platform::macos::ffi::bridge::{get_class, id, msg_send, NSPoint, NSRect, NSSize};
+use std::cell::RefCell;
+
+#[cfg(test)]
+use crate::i18n::click_letter_in;
+use crate::i18n::{click_label, click_letter};
+use crate::model::color_sampler::{from_unit, sample_hex, to_unit, Rgb8};
+use crate::model::constants::{
+    DEBUG_LABEL_MARGIN, DEBUG_LABEL_SIZE, GLOW_RADIUS, GLYPH_CACHE_SIZE, LETTER_WEIGHT_BOLD,
+};
+use crate::model::contrast::halo_color;
+use crate::model::coordinates::coordinates_label;
+use crate::model::font::{appkit_weight, system_font_weight};
+use crate::model::glow::{glow_color, glow_offset};
+use crate::model::placement::ScreenRect;
+use crate::model::ruler::RulerLine;
+use crate::model::screen_frame::Rgba;
+use crate::model::trail::TrailSegment;
+use crate::platform::macos::ffi::bridge::{
+    get_class, id, msg_send, nil, nsstring, nsstring_id, sel, AnyObject, Bool, NSPoint, NSRect,
+    NSSize, Retained, YES,
+};
 use crate::platform::macos::ffi::{
-    CFRelease, CGPathRef, CGPathRelease, CTFontCreatePathForGlyph, CTFontCreateWithName,
-    CTFontGetGlyphsForCharacters, CTFontRef,
+    CFRelease, CGContextBeginTransparencyLayer, CGContextEndTransparencyLayer, CGContextRef,
+    CGContextTranslateCTM, CGImageRelease, CGPathRef, CGPathRelease, CGWindowListCreateImage,
+    CTFontCreatePathForGlyph, CTFontCreateWithName, CTFontGetGlyphsForCharacters, CTFontRef,
+    K_CG_WINDOW_IMAGE_DEFAULT, K_CG_WINDOW_LIST_OPTION_ON_SCREEN_BELOW_WINDOW,
+};
+use crate::render::display_list::{DisplayItem, DisplayList, Paint, Shape};
+use crate::render::effects;
+use crate::render::geometry::{
+    badge_anchor, badge_gap, badge_size, center_offset, circle_rect, fill_alpha, halo_line_width,
+    laser_core_radius, laser_dot_radius, letter_fit_scale, letter_gap, letter_height,
+    outline_halo_width, readout_origin, ruler_label_origin, sample_origin,
 };
+use crate::render::lru::LruCache;
 
 /// Drawing parameters extracted from view ivars.
 ///
@@ -32,14 +62,219 @@ pub struct DrawParams {
     pub stroke_a: f64,
     /// Fill transparency percentage (0 = opaque, 100 = fully transparent)
     pub fill_transparency: f64,
+    /// Draw a contrasting outline halo under the stroke
+    pub halo: bool,
+    /// Glow or drop shadow behind the highlight (`GLOW_*`)
+    pub glow: i32,
 }
 
 impl DrawParams {
     /// Calculate the fill alpha based on stroke alpha and transparency setting.
     #[inline]
     pub fn fill_alpha(&self) -> f64 {
-        self.stroke_a * (1.0 - self.fill_transparency.clamp(0.0, 100.0) / 100.0)
+        fill_alpha(self.stroke_a, self.fill_transparency)
+    }
+}
+
+/// `ScreenRect` as an `NSRect`.
+fn ns_rect(rect: ScreenRect) -> NSRect {
+    NSRect::new(NSPoint::new(rect.x, rect.y), NSSize::new(rect.w, rect.h))
+}
+
+/// `NSRect` as a `ScreenRect`.
+fn screen_rect(rect: NSRect) -> ScreenRect {
+    ScreenRect::new(
+        rect.origin.x,
+        rect.origin.y,
+        rect.size.width,
+        rect.size.height,
+    )
+}
+
+/// Stroke color of the highlight.
+fn stroke_of(params: &DrawParams) -> Rgba {
+    (
+        params.stroke_r,
+        params.stroke_g,
+        params.stroke_b,
+        params.stroke_a,
+    )
+}
+
+/// `NSColor` of `color`.
+unsafe fn calibrated_color((r, g, b, a): Rgba) -> id {
+    msg_send![
+        get_class("NSColor"),
+        colorWithCalibratedRed: r,
+        green: g,
+        blue: b,
+        alpha: a
+    ]
+}
+
+/// Outline of `shape` as a new `NSBezierPath`.
+unsafe fn shape_path(shape: Shape) -> id {
+    let ns_bezier = get_class("NSBezierPath");
+    match shape {
+        Shape::Line((x0, y0), (x1, y1)) => {
+            let path: id = msg_send![ns_bezier, bezierPath];
+            let _: () = msg_send![path, moveToPoint: NSPoint::new(x0, y0)];
+            let _: () = msg_send![path, lineToPoint: NSPoint::new(x1, y1)];
+            path
+        }
+        Shape::Circle { center, radius } => {
+            let rect = ns_rect(circle_rect(center, radius));
+            msg_send![ns_bezier, bezierPathWithOvalInRect: rect]
+        }
+        Shape::Arc {
+            center,
+            radius,
+            sweep,
+        } => {
+            // AppKit angles are in degrees, counterclockwise from 3 o'clock
+            let path: id = msg_send![ns_bezier, bezierPath];
+            let _: () = msg_send![
+                path,
+                appendBezierPathWithArcWithCenter: NSPoint::new(center.0, center.1),
+                radius: radius,
+                startAngle: 90.0f64,
+                endAngle: 90.0 - sweep.to_degrees(),
+                clockwise: YES
+            ];
+            path
+        }
+        Shape::Rect(rect) => msg_send![ns_bezier, bezierPathWithRect: ns_rect(rect)],
+    }
+}
+
+/// Replay `list` in the current graphics context.
+///
+/// Pushed transforms are concatenated onto the context's and undone at
+/// the matching pop by restoring the saved graphics state.
+///
+/// # Safety
+///
+/// Must be called from the main thread within a valid drawing context.
+pub unsafe fn draw_display_list(list: &DisplayList) {
+    let ns_context = get_class("NSGraphicsContext");
+    for item in list.items() {
+        match *item {
+            DisplayItem::Draw { shape, paint } => {
+                let path = shape_path(shape);
+                match paint {
+                    Paint::Stroke {
+                        color,
+                        width,
+                        round,
+                    } => {
+                        let _: () = msg_send![calibrated_color(color), setStroke];
+                        let _: () = msg_send![path, setLineWidth: width];
+                        if round {
+                            // NSLineCapStyleRound = 1
+                            let _: () = msg_send![path, setLineCapStyle: 1u64];
+                        }
+                        let _: () = msg_send![path, stroke];
+                    }
+                    Paint::Fill(color) => {
+                        let _: () = msg_send![calibrated_color(color), setFill];
+                        let _: () = msg_send![path, fill];
+                    }
+                }
+            }
+            DisplayItem::Push(t) => {
+                let _: () = msg_send![ns_context, saveGraphicsState];
+                // Concatenated transforms apply last call first: scale,
+                // then translate
+                let transform: id = msg_send![get_class("NSAffineTransform"), transform];
+                let _: () = msg_send![transform, translateXBy: t.offset.0, yBy: t.offset.1];
+                let _: () = msg_send![transform, scaleBy: t.scale];
+                let _: () = msg_send![transform, concat];
+            }
+            DisplayItem::Pop => {
+                let _: () = msg_send![ns_context, restoreGraphicsState];
+            }
+        }
+    }
+}
+
+/// Set the halo color (black or white, contrasting with the stroke) as the
+/// current drawing color.
+///
+/// # Safety
+///
+/// Must be called from the main thread within a valid drawing context.
+unsafe fn set_halo_color(params: &DrawParams) {
+    let (r, g, b) = halo_color(params.stroke_r, params.stroke_g, params.stroke_b);
+    let color: id = msg_send![
+        get_class("NSColor"),
+        colorWithCalibratedRed: r,
+        green: g,
+        blue: b,
+        alpha: params.stroke_a
+    ];
+    let _: () = msg_send![color, set];
+}
+
+/// Start the glow behind everything drawn until [`end_glow`].
+///
+/// An `NSShadow` in the glow color is set and the drawing goes to a
+/// transparency layer, so the fill, halo and stroke cast a single blurred
+/// shadow instead of one each. Returns whether the glow is on.
+///
+/// # Safety
+///
+/// Must be called from the main thread within a valid drawing context.
+unsafe fn begin_glow(params: &DrawParams) -> bool {
+    let stroke = (
+        params.stroke_r,
+        params.stroke_g,
+        params.stroke_b,
+        params.stroke_a,
+    );
+    let Some((r, g, b, a)) = glow_color(params.glow, stroke) else {
+        return false;
+    };
+    let ns_context = get_class("NSGraphicsContext");
+    let _: () = msg_send![ns_context, saveGraphicsState];
+
+    let color: id = msg_send![
+        get_class("NSColor"),
+        colorWithCalibratedRed: r,
+        green: g,
+        blue: b,
+        alpha: a
+    ];
+    let shadow: id = msg_send![get_class("NSShadow"), new];
+    // Owned (+1) from new; `set` copies it into the graphics state
+    if let Some(shadow) = Retained::<AnyObject>::from_raw(shadow) {
+        let _: () = msg_send![&*shadow, setShadowColor: color];
+        let _: () = msg_send![&*shadow, setShadowBlurRadius: GLOW_RADIUS];
+        // View coordinates are y-up: a drop shadow moves towards -y
+        let offset = NSSize::new(0.0, -glow_offset(params.glow));
+        let _: () = msg_send![&*shadow, setShadowOffset: offset];
+        let _: () = msg_send![&*shadow, set];
+    }
+
+    let context: id = msg_send![ns_context, currentContext];
+    let cg: CGContextRef = msg_send![context, CGContext];
+    CGContextBeginTransparencyLayer(cg, std::ptr::null());
+    true
+}
+
+/// Composite the shapes drawn since [`begin_glow`] with their glow.
+///
+/// # Safety
+///
+/// Must be called from the main thread within the same drawing context.
+unsafe fn end_glow(glowing: bool) {
+    if !glowing {
+        return;
     }
+    let ns_context = get_class("NSGraphicsContext");
+    let context: id = msg_send![ns_context, currentContext];
+    let cg: CGContextRef = msg_send![context, CGContext];
+    CGContextEndTransparencyLayer(cg);
+    let _: () = msg_send![ns_context, restoreGraphicsState];
 }
 
 /// Draw a circle at the specified position.
@@ -51,15 +286,12 @@ pub unsafe fn draw_circle(params: &DrawParams) {
     let ns_color = get_class("NSColor");
     let ns_bezier = get_class("NSBezierPath");
 
-    let rect = NSRect::new(
-        NSPoint::new(
-            params.center.x - params.radius,
-            params.center.y - params.radius,
-        ),
-        NSSize::new(params.radius * 2.0, params.radius * 2.0),
-    );
-
+    let rect = ns_rect(circle_rect(
+        (params.center.x, params.center.y),
+        params.radius,
+    ));
     let circle: id = msg_send![ns_bezier, bezierPathWithOvalInRect: rect];
+    let glowing = begin_glow(params);
 
     // Fill
     let fill_alpha = params.fill_alpha();
@@ -75,6 +307,13 @@ pub unsafe fn draw_circle(params: &DrawParams) {
         let _: () = msg_send![circle, fill];
     }
 
+    // Halo (wider contrasting stroke underneath)
+    if params.halo {
+        set_halo_color(params);
+        let _: () = msg_send![circle, setLineWidth: halo_line_width(params.border_width)];
+        let _: () = msg_send![circle, stroke];
+    }
+
     // Stroke
     let stroke: id = msg_send![
         ns_color,
@@ -86,6 +325,181 @@ pub unsafe fn draw_circle(params: &DrawParams) {
     let _: () = msg_send![stroke, set];
     let _: () = msg_send![circle, setLineWidth: params.border_width];
     let _: () = msg_send![circle, stroke];
+    end_glow(glowing);
+}
+
+/// Draw the recording frame along the edges of the view.
+///
+/// # Safety
+///
+/// Must be called from the main thread within a valid drawing context.
+pub unsafe fn draw_screen_frame(bounds: NSRect, color: Rgba) {
+    let mut list = DisplayList::new();
+    effects::screen_frame(&mut list, &screen_rect(bounds), color);
+    draw_display_list(&list);
+}
+
+/// Draw the alignment grid across the view, from its top-left corner.
+///
+/// # Safety
+///
+/// Must be called from the main thread within a valid drawing context.
+pub unsafe fn draw_grid(bounds: NSRect, spacing: f64, subdivisions: i32, color: Rgba) {
+    let mut list = DisplayList::new();
+    effects::grid(
+        &mut list,
+        &screen_rect(bounds),
+        spacing,
+        subdivisions,
+        color,
+        true,
+    );
+    draw_display_list(&list);
+}
+
+/// Draw the screen content of `source` magnified to fill the view.
+///
+/// `source` is in global Cocoa coordinates. Only windows below
+/// `window_number` are captured, so the overlay never magnifies itself.
+/// Without the Screen Recording permission macOS returns just the desktop.
+///
+/// # Safety
+///
+/// Must be called from the main thread within a valid drawing context.
+pub unsafe fn draw_zoom(bounds: NSRect, source: ScreenRect, window_number: u32) {
+    // CoreGraphics uses a top-left origin on the primary display
+    let screens: id = msg_send![get_class("NSScreen"), screens];
+    let primary: id = msg_send![screens, objectAtIndex: 0usize];
+    let primary_frame: NSRect = msg_send![primary, frame];
+    let cg_rect = NSRect::new(
+        NSPoint::new(source.x, primary_frame.size.height - (source.y + source.h)),
+        NSSize::new(source.w, source.h),
+    );
+
+    let image = CGWindowListCreateImage(
+        cg_rect,
+        K_CG_WINDOW_LIST_OPTION_ON_SCREEN_BELOW_WINDOW,
+        window_number,
+        K_CG_WINDOW_IMAGE_DEFAULT,
+    );
+    if image.is_null() {
+        return;
+    }
+
+    let ns_image: id = msg_send![get_class("NSImage"), alloc];
+    let ns_image: id = msg_send![
+        ns_image,
+        initWithCGImage: image,
+        size: NSSize::new(source.w, source.h)
+    ];
+    // Owned (+1) from alloc/init; released when dropped
+    if let Some(ns_image) = Retained::<AnyObject>::from_raw(ns_image) {
+        let zero = NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(0.0, 0.0));
+        // NSCompositingOperationCopy = 1: the capture replaces the clear background
+        let _: () = msg_send![
+            &*ns_image,
+            drawInRect: bounds,
+            fromRect: zero,
+            operation: 1usize,
+            fraction: 1.0f64
+        ];
+    }
+    CGImageRelease(image);
+}
+
+/// Color of the screen pixel at (`x`, `y`) in global Cocoa coordinates,
+/// from a 1×1 capture of the windows below `window_number` (the overlay
+/// on that screen), so the highlight never tints it.
+///
+/// # Safety
+///
+/// Must be called from the main thread.
+pub unsafe fn sample_screen_color(x: f64, y: f64, window_number: u32) -> Option<Rgb8> {
+    let screens: id = msg_send![get_class("NSScreen"), screens];
+    let primary: id = msg_send![screens, objectAtIndex: 0usize];
+    let primary_frame: NSRect = msg_send![primary, frame];
+    let cg_rect = NSRect::new(
+        NSPoint::new(x.floor(), (primary_frame.size.height - y).floor()),
+        NSSize::new(1.0, 1.0),
+    );
+
+    let image = CGWindowListCreateImage(
+        cg_rect,
+        K_CG_WINDOW_LIST_OPTION_ON_SCREEN_BELOW_WINDOW,
+        window_number,
+        K_CG_WINDOW_IMAGE_DEFAULT,
+    );
+    if image.is_null() {
+        return None;
+    }
+    let rep: id = msg_send![get_class("NSBitmapImageRep"), alloc];
+    let rep: id = msg_send![rep, initWithCGImage: image];
+    CGImageRelease(image);
+    // Owned (+1) from alloc/init; released when dropped
+    let rep = Retained::<AnyObject>::from_raw(rep)?;
+    let color: id = msg_send![&*rep, colorAtX: 0isize, y: 0isize];
+    if color == nil {
+        return None;
+    }
+    let srgb: id = msg_send![get_class("NSColorSpace"), sRGBColorSpace];
+    let color: id = msg_send![color, colorUsingColorSpace: srgb];
+    if color == nil {
+        return None;
+    }
+    let r: f64 = msg_send![color, redComponent];
+    let g: f64 = msg_send![color, greenComponent];
+    let b: f64 = msg_send![color, blueComponent];
+    Some(from_unit(r, g, b))
+}
+
+/// Draw the laser-pointer dot with its fading trail.
+///
+/// `segments` must already be in view coordinates; the trail comes from
+/// [`effects::laser_trail`] and the dot is drawn on top with a light core.
+///
+/// # Safety
+///
+/// Must be called from the main thread within a valid drawing context.
+pub unsafe fn draw_laser(params: &DrawParams, segments: &[TrailSegment]) {
+    let ns_color = get_class("NSColor");
+    let ns_bezier = get_class("NSBezierPath");
+    let dot_radius = laser_dot_radius(params.radius);
+
+    let mut trail = DisplayList::new();
+    effects::laser_trail(&mut trail, segments, dot_radius, stroke_of(params));
+    draw_display_list(&trail);
+
+    // Dot, glowing on its own (the trail fades too fast to need it)
+    let glowing = begin_glow(params);
+    let center = (params.center.x, params.center.y);
+    let rect = ns_rect(circle_rect(center, dot_radius));
+    let dot: id = msg_send![ns_bezier, bezierPathWithOvalInRect: rect];
+    if params.halo {
+        set_halo_color(params);
+        let _: () = msg_send![dot, setLineWidth: outline_halo_width()];
+        let _: () = msg_send![dot, stroke];
+    }
+    let color: id = msg_send![
+        ns_color,
+        colorWithCalibratedRed: params.stroke_r,
+        green: params.stroke_g,
+        blue: params.stroke_b,
+        alpha: params.stroke_a
+    ];
+    let _: () = msg_send![color, set];
+    let _: () = msg_send![dot, fill];
+
+    // Bright core
+    let core_rect = ns_rect(circle_rect(center, laser_core_radius(dot_radius)));
+    let core: id = msg_send![ns_bezier, bezierPathWithOvalInRect: core_rect];
+    let white: id = msg_send![
+        ns_color,
+        colorWithCalibratedWhite: 1.0f64,
+        alpha: 0.85 * params.stroke_a
+    ];
+    let _: () = msg_send![white, set];
+    let _: () = msg_send![core, fill];
+    end_glow(glowing);
 }
 
 /// The letter to draw when mouse button is pressed.
@@ -97,112 +511,608 @@ pub enum ClickLetter {
     Right,
 }
 
+thread_local! {
+    /// Custom text of the left and right click letters (empty = the letter
+    /// of the selected language).
+    static CUSTOM_LETTERS: RefCell<[String; 2]> = const { RefCell::new([String::new(), String::new()]) };
+
+    /// Font family (empty = system font) and OpenType weight of the click
+    /// letters.
+    static LETTER_FONT: RefCell<(String, i32)> = const { RefCell::new((String::new(), LETTER_WEIGHT_BOLD)) };
+
+    /// Outlines of the click letters drawn recently, by text, fallback
+    /// letter and height, scaled to fit and not yet placed.
+    static LETTER_PATHS: RefCell<LruCache<(String, char, u64), Retained<AnyObject>>> =
+        const { RefCell::new(LruCache::new(GLYPH_CACHE_SIZE)) };
+
+    /// Outlines of the badges drawn recently, by text and height.
+    static BADGE_PATHS: RefCell<LruCache<(String, u64), Retained<AnyObject>>> =
+        const { RefCell::new(LruCache::new(GLYPH_CACHE_SIZE)) };
+}
+
+/// Set the custom text drawn for left and right clicks; empty text draws
+/// the letter of the selected language.
+pub fn set_click_letters(left: &str, right: &str) {
+    CUSTOM_LETTERS.with(|c| *c.borrow_mut() = [left.to_string(), right.to_string()]);
+}
+
+/// Set the font family (empty = the system font) and weight of the click
+/// letters.
+pub fn set_letter_font(family: &str, weight: i32) {
+    LETTER_FONT.with(|f| *f.borrow_mut() = (family.to_string(), weight));
+    LETTER_PATHS.with(|p| p.borrow_mut().clear());
+}
+
+/// Font of the click letters at `size`: the chosen family at the closest
+/// weight it has, or the system font when none is chosen or it is not
+/// installed.
+///
+/// # Safety
+///
+/// Must be called from the main thread.
+unsafe fn letter_font(size: f64) -> id {
+    let (family, weight) = LETTER_FONT.with(|f| f.borrow().clone());
+    if !family.is_empty() {
+        let manager: id = msg_send![get_class("NSFontManager"), sharedFontManager];
+        let name = nsstring(&family);
+        let font: id = msg_send![
+            manager,
+            fontWithFamily: &*name,
+            traits: 0u64,
+            weight: appkit_weight(weight),
+            size: size
+        ];
+        if !font.is_null() {
+            return font;
+        }
+    }
+    msg_send![
+        get_class("NSFont"),
+        systemFontOfSize: size,
+        weight: system_font_weight(weight)
+    ]
+}
+
 impl ClickLetter {
-    /// Get the character to render based on language.
+    /// Get the text to render: the custom letters when set, otherwise the
+    /// letter of the selected language.
+    fn as_text(self) -> String {
+        let left = self == ClickLetter::Left;
+        CUSTOM_LETTERS.with(|c| click_label(&c.borrow()[usize::from(!left)], left))
+    }
+
+    /// Get the character to render in `locale`:
     ///
-    /// - English (es=false): "L" for left, "R" for right
-    /// - Spanish (es=true): "I" for izquierdo, "D" for derecho
-    fn as_char(self, es: bool) -> char {
-        match (self, es) {
-            (ClickLetter::Left, false) => 'L',
-            (ClickLetter::Left, true) => 'I',
-            (ClickLetter::Right, false) => 'R',
-            (ClickLetter::Right, true) => 'D',
+    /// - English: "L" for left, "R" for right
+    /// - Spanish: "I" for izquierdo, "D" for derecho
+    #[cfg(test)]
+    fn as_char_in(self, locale: &str) -> char {
+        click_letter_in(locale, self == ClickLetter::Left)
+    }
+}
+
+/// Outline of `text` in `ct_font`, glyphs laid out left to right from the
+/// origin using their ink bounds and `gap` between them.
+///
+/// Returns `None` when the font has no glyph for some character or no
+/// outline for any of them (bitmap glyphs such as color emoji).
+///
+/// # Safety
+///
+/// Must be called from the main thread with a valid `ct_font`.
+unsafe fn glyph_run_path(ct_font: CTFontRef, text: &str, gap: f64) -> Option<id> {
+    let ns_bezier = get_class("NSBezierPath");
+    let ns_affine = get_class("NSAffineTransform");
+
+    let chars: Vec<u16> = text.encode_utf16().collect();
+    let mut glyphs = vec![0u16; chars.len()];
+    let mapped = CTFontGetGlyphsForCharacters(
+        ct_font,
+        chars.as_ptr(),
+        glyphs.as_mut_ptr(),
+        chars.len() as isize,
+    );
+    if !mapped {
+        return None;
+    }
+
+    let path: id = msg_send![ns_bezier, bezierPath];
+    let mut pen_x = 0.0;
+    // Characters outside the BMP map to one glyph followed by a 0
+    for glyph in glyphs.into_iter().filter(|g| *g != 0) {
+        let cg_path: CGPathRef = CTFontCreatePathForGlyph(ct_font, glyph, std::ptr::null());
+        if cg_path.is_null() {
+            continue;
         }
+        let glyph_path: id = msg_send![ns_bezier, bezierPathWithCGPath: cg_path];
+        let gb: NSRect = msg_send![glyph_path, bounds];
+        let transform: id = msg_send![ns_affine, transform];
+        let _: () = msg_send![transform, translateXBy: pen_x - gb.origin.x, yBy: 0.0f64];
+        let _: () = msg_send![glyph_path, transformUsingAffineTransform: transform];
+        let _: () = msg_send![path, appendBezierPath: glyph_path];
+        pen_x += gb.size.width + gap;
+        CGPathRelease(cg_path);
     }
+    (pen_x > 0.0).then_some(path)
+}
+
+/// Run `draw` with the origin moved by (`dx`, `dy`), so a cached outline
+/// is drawn in place without being copied.
+///
+/// # Safety
+///
+/// Must be called from the main thread within a valid drawing context.
+unsafe fn draw_translated(dx: f64, dy: f64, draw: impl FnOnce()) {
+    let ns_context = get_class("NSGraphicsContext");
+    let context: id = msg_send![ns_context, currentContext];
+    let cg: CGContextRef = msg_send![context, CGContext];
+    let _: () = msg_send![ns_context, saveGraphicsState];
+    CGContextTranslateCTM(cg, dx, dy);
+    draw();
+    let _: () = msg_send![ns_context, restoreGraphicsState];
 }
 
-/// Draw a letter (L/R or I/D) at the specified position.
+/// Outline of `letter` at `height`, shrunk to fit the width a circle of
+/// `radius` allows; `None` when the font cannot draw it.
+///
+/// Kept in [`LETTER_PATHS`], so a held button lays the glyphs out once.
+///
+/// # Safety
+///
+/// Must be called from the main thread.
+unsafe fn letter_path(letter: ClickLetter, height: f64, radius: f64) -> Option<id> {
+    let left = letter == ClickLetter::Left;
+    let text = letter.as_text();
+    let fallback = click_letter(left);
+    let key = (text, fallback, height.to_bits());
+    LETTER_PATHS.with(|paths| {
+        let mut paths = paths.borrow_mut();
+        let path = paths.get_or_insert_with(key.clone(), || {
+            let font = letter_font(height);
+            let font_name: id = msg_send![font, fontName];
+            let ct_font: CTFontRef =
+                CTFontCreateWithName(font_name as *const _, height, std::ptr::null());
+            let gap = letter_gap(height);
+            let path = glyph_run_path(ct_font, &key.0, gap)
+                .or_else(|| glyph_run_path(ct_font, &fallback.to_string(), gap));
+            CFRelease(ct_font as *const _);
+            let path = path?;
+
+            // Shrink wide text
+            let pbounds: NSRect = msg_send![path, bounds];
+            let scale = letter_fit_scale(pbounds.size.width, radius);
+            if scale < 1.0 {
+                let transform: id = msg_send![get_class("NSAffineTransform"), transform];
+                let _: () = msg_send![transform, scaleBy: scale];
+                let _: () = msg_send![path, transformUsingAffineTransform: transform];
+            }
+            // Round line joins for smoother appearance
+            let _: () = msg_send![path, setLineJoinStyle: 1u64];
+            Retained::<AnyObject>::retain(path)
+        })?;
+        Some(Retained::as_ptr(path) as id)
+    })
+}
+
+/// Draw a letter (L/R or I/D, or the custom text) at the specified position.
 ///
 /// Uses CoreText for glyph rendering, producing high-quality
 /// vector letters that scale with the radius setting.
 ///
-/// The letter displayed depends on the language:
-/// - English (es=false): "L" for left click, "R" for right click
-/// - Spanish (es=true): "I" for left click (izquierdo), "D" for right click (derecho)
+/// The letter displayed depends on the language (`click-letter-left` /
+/// `click-letter-right` in the locale files), e.g. "L"/"R" in English and
+/// "I"/"D" (izquierdo/derecho) in Spanish, unless custom text is set (see
+/// [`set_click_letters`]). Custom text the font cannot draw falls back to
+/// the letter of the language; text wider than `letter_max_width` is
+/// scaled down to fit. The font is the one chosen in Settings (see
+/// [`set_letter_font`]).
 ///
 /// # Safety
 ///
 /// Must be called from the main thread within a valid drawing context.
-pub unsafe fn draw_letter(params: &DrawParams, letter: ClickLetter, es: bool) {
+pub unsafe fn draw_letter(params: &DrawParams, letter: ClickLetter) {
     let ns_color = get_class("NSColor");
-    let ns_bezier = get_class("NSBezierPath");
-    let ns_affine = get_class("NSAffineTransform");
-    let font_class = get_class("NSFont");
 
-    // Letter height = 1.5 x diameter (3 x radius)
-    let target_letter_height = 3.0 * params.radius;
+    let Some(path) = letter_path(letter, letter_height(params.radius), params.radius) else {
+        return;
+    };
 
-    let font: id = msg_send![font_class, boldSystemFontOfSize: target_letter_height];
-    let font_name: id = msg_send![font, fontName];
+    // Center it on the cursor position
+    let pbounds: NSRect = msg_send![path, bounds];
+    let (dx, dy) = center_offset(&screen_rect(pbounds), (params.center.x, params.center.y));
 
-    let ct_font: CTFontRef = CTFontCreateWithName(
-        font_name as *const _,
-        target_letter_height,
-        std::ptr::null(),
-    );
+    let glowing = begin_glow(params);
+    draw_translated(dx, dy, || {
+        // Fill (same style as circle)
+        let fill_alpha = params.fill_alpha();
+        if fill_alpha > 0.0 {
+            let fill: id = msg_send![
+                ns_color,
+                colorWithCalibratedRed: params.stroke_r,
+                green: params.stroke_g,
+                blue: params.stroke_b,
+                alpha: fill_alpha
+            ];
+            let _: () = msg_send![fill, set];
+            let _: () = msg_send![path, fill];
+        }
 
-    let ch_u16: u16 = letter.as_char(es) as u16;
-    let mut glyph: u16 = 0;
+        // Halo (wider contrasting stroke underneath)
+        if params.halo {
+            set_halo_color(params);
+            let _: () = msg_send![path, setLineWidth: halo_line_width(params.border_width)];
+            let _: () = msg_send![path, stroke];
+        }
 
-    let mapped =
-        CTFontGetGlyphsForCharacters(ct_font, &ch_u16 as *const u16, &mut glyph as *mut u16, 1);
+        // Stroke
+        let stroke: id = msg_send![
+            ns_color,
+            colorWithCalibratedRed: params.stroke_r,
+            green: params.stroke_g,
+            blue: params.stroke_b,
+            alpha: params.stroke_a
+        ];
+        let _: () = msg_send![stroke, set];
+        let _: () = msg_send![path, setLineWidth: params.border_width];
+        let _: () = msg_send![path, stroke];
+    });
+    end_glow(glowing);
+}
 
-    if !mapped || glyph == 0 {
-        CFRelease(ct_font as *const _);
-        return;
+/// Draw the SF Symbol `symbol` centered on the cursor, as tall as a click
+/// letter and tinted with the stroke color (over the halo color, offset
+/// all around, when the halo is on).
+///
+/// Returns `false` without drawing when the symbol is not available
+/// (macOS 10.15, or a name this macOS does not know), so the caller can
+/// draw the letter instead.
+///
+/// # Safety
+///
+/// Must be called from the main thread within a valid drawing context.
+pub unsafe fn draw_icon(params: &DrawParams, symbol: &str) -> bool {
+    let ns_image = get_class("NSImage");
+    // SF Symbols need macOS 11
+    let available: Bool = msg_send![
+        ns_image,
+        respondsToSelector: sel!(imageWithSystemSymbolName:accessibilityDescription:)
+    ];
+    if !available.as_bool() {
+        return false;
+    }
+    let name = nsstring(symbol);
+    let image: id = msg_send![
+        ns_image,
+        imageWithSystemSymbolName: &*name,
+        accessibilityDescription: nil
+    ];
+    if image.is_null() {
+        return false;
+    }
+    let height = letter_height(params.radius);
+    let config: id = msg_send![
+        get_class("NSImageSymbolConfiguration"),
+        configurationWithPointSize: height,
+        weight: 0.0f64
+    ];
+    let image: id = msg_send![image, imageWithSymbolConfiguration: config];
+    if image.is_null() {
+        return false;
     }
+    let size: NSSize = msg_send![image, size];
+    if size.height <= 0.0 {
+        return false;
+    }
+    let width = size.width * height / size.height;
+    let rect = NSRect::new(
+        NSPoint::new(
+            params.center.x - width / 2.0,
+            params.center.y - height / 2.0,
+        ),
+        NSSize::new(width, height),
+    );
 
-    let cg_path: CGPathRef = CTFontCreatePathForGlyph(ct_font, glyph, std::ptr::null());
-    if cg_path.is_null() {
-        CFRelease(ct_font as *const _);
-        return;
+    let glowing = begin_glow(params);
+    if params.halo {
+        let d = halo_line_width(params.border_width) / 2.0;
+        for (dx, dy) in [(-d, 0.0), (d, 0.0), (0.0, -d), (0.0, d)] {
+            let moved = NSRect::new(
+                NSPoint::new(rect.origin.x + dx, rect.origin.y + dy),
+                rect.size,
+            );
+            draw_tinted(image, moved, || set_halo_color(params));
+        }
     }
+    draw_tinted(image, rect, || {
+        let color: id = msg_send![
+            get_class("NSColor"),
+            colorWithCalibratedRed: params.stroke_r,
+            green: params.stroke_g,
+            blue: params.stroke_b,
+            alpha: params.stroke_a
+        ];
+        let _: () = msg_send![color, set];
+    });
+    end_glow(glowing);
+    true
+}
 
-    let path: id = msg_send![ns_bezier, bezierPathWithCGPath: cg_path];
+/// Draw the template `image` in `rect` in the color `set_color` makes
+/// current: the image goes to a transparency layer and the color is
+/// filled over it keeping only its shape (source-in).
+///
+/// # Safety
+///
+/// Must be called from the main thread within a valid drawing context.
+unsafe fn draw_tinted(image: id, rect: NSRect, set_color: impl Fn()) {
+    /// `NSCompositingOperationSourceOver`
+    const SOURCE_OVER: u64 = 2;
+    /// `NSCompositingOperationSourceIn`
+    const SOURCE_IN: u64 = 3;
 
-    // Center the letter on the cursor position
-    let pbounds: NSRect = msg_send![path, bounds];
-    let mid_x = pbounds.origin.x + pbounds.size.width / 2.0;
-    let mid_y = pbounds.origin.y + pbounds.size.height / 2.0;
+    let ns_context = get_class("NSGraphicsContext");
+    let context: id = msg_send![ns_context, currentContext];
+    let cg: CGContextRef = msg_send![context, CGContext];
+    let _: () = msg_send![ns_context, saveGraphicsState];
+    CGContextBeginTransparencyLayer(cg, std::ptr::null());
+    let whole = NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(0.0, 0.0));
+    let _: () = msg_send![
+        image,
+        drawInRect: rect,
+        fromRect: whole,
+        operation: SOURCE_OVER,
+        fraction: 1.0f64
+    ];
+    set_color();
+    let _: () = msg_send![context, setCompositingOperation: SOURCE_IN];
+    let _: () = msg_send![get_class("NSBezierPath"), fillRect: rect];
+    CGContextEndTransparencyLayer(cg);
+    let _: () = msg_send![ns_context, restoreGraphicsState];
+}
 
-    let transform: id = msg_send![ns_affine, transform];
-    let dx = params.center.x - mid_x;
-    let dy = params.center.y - mid_y;
-    let _: () = msg_send![transform, translateXBy: dx, yBy: dy];
-    let _: () = msg_send![path, transformUsingAffineTransform: transform];
+/// Outline of the badge `text` at `size`, in the bold system font; `None`
+/// when the font cannot draw it.
+///
+/// Kept in [`BADGE_PATHS`], so a burst lays each count out once.
+///
+/// # Safety
+///
+/// Must be called from the main thread.
+unsafe fn badge_path(text: &str, size: f64) -> Option<id> {
+    BADGE_PATHS.with(|paths| {
+        let mut paths = paths.borrow_mut();
+        let path = paths.get_or_insert_with((text.to_string(), size.to_bits()), || {
+            let font: id = msg_send![get_class("NSFont"), boldSystemFontOfSize: size];
+            let font_name: id = msg_send![font, fontName];
+            let ct_font: CTFontRef =
+                CTFontCreateWithName(font_name as *const _, size, std::ptr::null());
+            // Lay the glyphs out left to right using their ink bounds
+            let path = glyph_run_path(ct_font, text, badge_gap(size));
+            CFRelease(ct_font as *const _);
+            let path = path?;
+            Retained::<AnyObject>::retain(path)
+        })?;
+        Some(Retained::as_ptr(path) as id)
+    })
+}
 
-    // Round line joins for smoother appearance
-    let _: () = msg_send![path, setLineJoinStyle: 1u64];
+/// Draw the multi-click counter badge (e.g. "x3") beside the click letter.
+///
+/// The badge is a smaller bold glyph run anchored to the lower-right of the
+/// letter and filled with the stroke color.
+///
+/// # Safety
+///
+/// Must be called from the main thread within a valid drawing context.
+pub unsafe fn draw_badge(params: &DrawParams, text: &str) {
+    let ns_color = get_class("NSColor");
+
+    let Some(path) = badge_path(text, badge_size(params.radius)) else {
+        return;
+    };
+
+    // Bottom-left corner of the badge sits next to the letter's baseline
+    let pbounds: NSRect = msg_send![path, bounds];
+    let (left, bottom) = badge_anchor((params.center.x, params.center.y), params.radius, true);
+    let dx = left - pbounds.origin.x;
+    let dy = bottom - pbounds.origin.y;
+
+    draw_translated(dx, dy, || {
+        if params.halo {
+            set_halo_color(params);
+            let _: () = msg_send![path, setLineJoinStyle: 1u64];
+            let _: () = msg_send![path, setLineWidth: outline_halo_width()];
+            let _: () = msg_send![path, stroke];
+        }
 
-    // Fill (same style as circle)
-    let fill_alpha = params.fill_alpha();
-    if fill_alpha > 0.0 {
         let fill: id = msg_send![
             ns_color,
             colorWithCalibratedRed: params.stroke_r,
             green: params.stroke_g,
             blue: params.stroke_b,
-            alpha: fill_alpha
+            alpha: params.stroke_a
         ];
         let _: () = msg_send![fill, set];
         let _: () = msg_send![path, fill];
-    }
+    });
+}
 
-    // Stroke
-    let stroke: id = msg_send![
-        ns_color,
-        colorWithCalibratedRed: params.stroke_r,
-        green: params.stroke_g,
-        blue: params.stroke_b,
-        alpha: params.stroke_a
+/// Draw the hold ring: an arc around the letter filling clockwise from
+/// 12 o'clock, `progress` [0.0, 1.0] of the way round.
+///
+/// # Safety
+///
+/// Must be called from the main thread within a valid drawing context.
+pub unsafe fn draw_hold_ring(params: &DrawParams, progress: f64) {
+    let mut list = DisplayList::new();
+    effects::hold_ring(
+        &mut list,
+        (params.center.x, params.center.y),
+        params.radius,
+        params.border_width,
+        progress,
+        stroke_of(params),
+        params.halo,
+    );
+    draw_display_list(&list);
+}
+
+/// Draw the crosshair marking the tip of the pen driving the pointer.
+///
+/// # Safety
+///
+/// Must be called from the main thread within a valid drawing context.
+pub unsafe fn draw_pen_tip(params: &DrawParams) {
+    let mut list = DisplayList::new();
+    effects::pen_tip(
+        &mut list,
+        (params.center.x, params.center.y),
+        stroke_of(params),
+    );
+    draw_display_list(&list);
+}
+
+/// Draw the diagnostics label in the top-left corner of `bounds`, white on
+/// a dark plate so it reads on any background.
+///
+/// # Safety
+/// Must be called from within a view's drawRect: method.
+pub unsafe fn draw_debug_label(bounds: NSRect, text: &str) {
+    draw_plated_label(text, None, |size| {
+        NSPoint::new(
+            bounds.origin.x + DEBUG_LABEL_MARGIN,
+            bounds.origin.y + bounds.size.height - DEBUG_LABEL_MARGIN - size.height,
+        )
+    });
+}
+
+/// Draw the coordinates readout of the pointer at global Cocoa position
+/// `cursor` beside the highlight, on the same plate as the debug label.
+///
+/// # Safety
+/// Must be called from within a view's drawRect: method.
+pub unsafe fn draw_coordinates_label(params: &DrawParams, bounds: NSRect, cursor: (f64, f64)) {
+    let screens: id = msg_send![get_class("NSScreen"), screens];
+    let count: usize = msg_send![screens, count];
+    let frames: Vec<ScreenRect> = (0..count)
+        .map(|i| {
+            let screen: id = msg_send![screens, objectAtIndex: i];
+            let frame: NSRect = msg_send![screen, frame];
+            screen_rect(frame)
+        })
+        .collect();
+    let text = coordinates_label(cursor, &frames, true);
+
+    let pad = DEBUG_LABEL_MARGIN / 2.0;
+    draw_plated_label(&text, None, |size| {
+        let plate = (size.width + 2.0 * pad, size.height + 2.0 * pad);
+        let center = (params.center.x, params.center.y);
+        let (x, y) = readout_origin(center, params.radius, plate, &screen_rect(bounds), true);
+        NSPoint::new(x + pad, y + pad)
+    });
+}
+
+/// Draw the hex of the sampled `color` on a swatch of it, beside the
+/// highlight opposite the coordinates readout.
+///
+/// # Safety
+/// Must be called from within a view's drawRect: method.
+pub unsafe fn draw_color_sample(params: &DrawParams, bounds: NSRect, color: Rgb8) {
+    let pad = DEBUG_LABEL_MARGIN / 2.0;
+    draw_plated_label(&sample_hex(color), Some(color), |size| {
+        // The swatch is a square as tall as the text
+        let plate = (
+            size.width + size.height + 3.0 * pad,
+            size.height + 2.0 * pad,
+        );
+        let center = (params.center.x, params.center.y);
+        let (x, y) = sample_origin(center, params.radius, plate, &screen_rect(bounds), true);
+        NSPoint::new(x + size.height + 2.0 * pad, y + pad)
+    });
+}
+
+/// Draw the ruler's `line` (view coordinates) white over a dark outline,
+/// with ticks across its ends and `label` on the plate of the other
+/// labels beside its middle.
+///
+/// # Safety
+/// Must be called from within a view's drawRect: method.
+pub unsafe fn draw_ruler(line: RulerLine, label: &str, bounds: NSRect) {
+    let mut lines = DisplayList::new();
+    effects::ruler(&mut lines, line);
+    draw_display_list(&lines);
+
+    let pad = DEBUG_LABEL_MARGIN / 2.0;
+    draw_plated_label(label, None, |size| {
+        let plate = (size.width + 2.0 * pad, size.height + 2.0 * pad);
+        let (x, y) = ruler_label_origin(line, plate, &screen_rect(bounds));
+        NSPoint::new(x + pad, y + pad)
+    });
+}
+
+/// Draw `text` white on a dark plate, at the origin `place` picks for its
+/// size, with a square `swatch` of that color left of the text.
+unsafe fn draw_plated_label(
+    text: &str,
+    swatch: Option<Rgb8>,
+    place: impl FnOnce(NSSize) -> NSPoint,
+) {
+    let ns_color = get_class("NSColor");
+    let font: id = msg_send![
+        get_class("NSFont"),
+        monospacedDigitSystemFontOfSize: DEBUG_LABEL_SIZE,
+        weight: 0.0f64
     ];
-    let _: () = msg_send![stroke, set];
-    let _: () = msg_send![path, setLineWidth: params.border_width];
-    let _: () = msg_send![path, stroke];
+    let white: id = msg_send![ns_color, whiteColor];
+    let attrs: id = msg_send![get_class("NSMutableDictionary"), dictionary];
+    let _: () = msg_send![attrs, setObject: font, forKey: nsstring_id("NSFont")];
+    let _: () = msg_send![attrs, setObject: white, forKey: nsstring_id("NSColor")];
+
+    let label = nsstring(text);
+    let size: NSSize = msg_send![&*label, sizeWithAttributes: attrs];
+    let origin = place(size);
+
+    let pad = DEBUG_LABEL_MARGIN / 2.0;
+    let side = size.height;
+    let swatch_width = swatch.map_or(0.0, |_| side + pad);
+    let plate = NSRect::new(
+        NSPoint::new(origin.x - pad - swatch_width, origin.y - pad),
+        NSSize::new(
+            size.width + swatch_width + 2.0 * pad,
+            size.height + 2.0 * pad,
+        ),
+    );
+    let dark: id = msg_send![ns_color, colorWithCalibratedWhite: 0.0f64, alpha: 0.6f64];
+    let _: () = msg_send![dark, setFill];
+    let path: id = msg_send![
+        get_class("NSBezierPath"),
+        bezierPathWithRoundedRect: plate,
+        xRadius: pad,
+        yRadius: pad
+    ];
+    let _: () = msg_send![path, fill];
+
+    if let Some(color) = swatch {
+        let (r, g, b) = to_unit(color);
+        let square = NSRect::new(
+            NSPoint::new(origin.x - pad - side, origin.y),
+            NSSize::new(side, side),
+        );
+        let fill: id = msg_send![
+            ns_color,
+            colorWithSRGBRed: r,
+            green: g,
+            blue: b,
+            alpha: 1.0f64
+        ];
+        let _: () = msg_send![fill, setFill];
+        let _: () = msg_send![get_class("NSBezierPath"), fillRect: square];
+        // White edge keeps dark samples visible on the plate
+        let _: () = msg_send![white, setStroke];
+        let _: () = msg_send![get_class("NSBezierPath"), strokeRect: square];
+    }
 
-    CGPathRelease(cg_path);
-    CFRelease(ct_font as *const _);
+    let _: () = msg_send![&*label, drawAtPoint: origin, withAttributes: attrs];
 }
 
 #[cfg(test)]
@@ -220,6 +1130,8 @@ mod tests {
             stroke_b: 0.0,
             stroke_a: 1.0,
             fill_transparency: 0.0,
+            halo: false,
+            glow: 0,
         };
         assert!((params.fill_alpha() - 1.0).abs() < 0.001);
     }
@@ -235,6 +1147,8 @@ mod tests {
             stroke_b: 0.0,
             stroke_a: 1.0,
             fill_transparency: 100.0,
+            halo: false,
+            glow: 0,
         };
         assert!(params.fill_alpha().abs() < 0.001);
     }
@@ -250,6 +1164,8 @@ mod tests {
             stroke_b: 0.0,
             stroke_a: 1.0,
             fill_transparency: 50.0,
+            halo: false,
+            glow: 0,
         };
         assert!((params.fill_alpha() - 0.5).abs() < 0.001);
     }
@@ -257,10 +1173,10 @@ mod tests {
     #[test]
     fn test_click_letter_as_char() {
         // English
-        assert_eq!(ClickLetter::Left.as_char(false), 'L');
-        assert_eq!(ClickLetter::Right.as_char(false), 'R');
+        assert_eq!(ClickLetter::Left.as_char_in("en"), 'L');
+        assert_eq!(ClickLetter::Right.as_char_in("en"), 'R');
         // Spanish
-        assert_eq!(ClickLetter::Left.as_char(true), 'I');
-        assert_eq!(ClickLetter::Right.as_char(true), 'D');
+        assert_eq!(ClickLetter::Left.as_char_in("es"), 'I');
+        assert_eq!(ClickLetter::Right.as_char_in("es"), 'D');
     }
 }
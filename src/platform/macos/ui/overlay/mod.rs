@@ -5,5 +5,13 @@
 pub mod drawing;
 pub mod view;
 
-pub use drawing::{draw_circle, draw_letter, ClickLetter, DrawParams};
-pub use view::register_and_create_view;
+pub use drawing::{
+    draw_badge, draw_circle, draw_color_sample, draw_coordinates_label, draw_debug_label,
+    draw_display_list, draw_grid, draw_hold_ring, draw_icon, draw_laser, draw_letter, draw_pen_tip,
+    draw_ruler, draw_screen_frame, draw_zoom, sample_screen_color, set_click_letters,
+    set_letter_font, ClickLetter, DrawParams,
+};
+pub use view::{
+    enable_debug_overlay, frame_color_for_screen, register_and_create_view, reload_frame_colors,
+    set_frame_color_for_screen,
+};
@@ -1,32 +1,94 @@
 //! CustomView class for the overlay.
 //!
 //! This module contains the NSView subclass that handles:
-//! - Drawing the cursor overlay (circle or L/R letter)
+//! - Drawing the cursor overlay (circle, laser dot with trail, or L/R letter)
+//!   and the recording frame around the highlighted screen
+//! - Magnifying the screen under the cursor in zoom-follow mode
 //! - Processing input events from the event bus
 //! - Settings UI actions (sliders, color picker, etc.)
 //! - Status bar menu actions
 
-use std::ffi::{c_char, CStr};
+use std::cell::RefCell;
 
 use crate::events::{publish, AppEvent};
+use crate::model::animation::{transition_alpha, transition_scale, wall_clock_secs};
+use crate::model::click_burst::badge_label;
+use crate::model::click_hold::hold_progress;
+use crate::model::click_icon::click_icon;
+use crate::model::color_sampler::{pack_rgb, unpack_rgb};
 use crate::model::constants::*;
-use crate::platform::macos::app::apply_to_all_views;
-use crate::platform::macos::ffi::bridge::{get_bool_ivar, get_class, id, msg_send, nil, nsstring_id, set_bool_ivar, ObjectExt, NO, YES};
+use crate::model::diagnostics::debug_label;
+use crate::model::kinematics::{speed_fade_alpha, speed_size_scale};
+use crate::model::motion::reduce_motion;
+use crate::model::pen::pen_scale;
+use crate::model::placement::{display_key, ScreenRect};
+use crate::model::pulse::{pulse_phase, pulse_radius};
+use crate::model::rainbow::{rainbow_color, rainbow_phase};
+use crate::model::ruler::ruler_label;
+use crate::model::zoom::{source_rect, to_zoomed};
+use crate::model::{
+    FrameColors, FrameTimer, IdleFade, Kinematics, ShowHide, Trail, Zoom, HOTKEY_PROFILES,
+    LOOK_PROFILES,
+};
+use crate::platform::macos::app::{
+    apply_to_all_views, available_update, check_permissions, open_release_page, permission_status,
+    refresh_ticked,
+};
+use crate::platform::macos::ffi::bridge::{
+    get_bool_ivar, get_class, id, msg_send, nil, set_bool_ivar, ObjectExt, NO, YES,
+};
 use crate::platform::macos::ffi::{
     display_id_for_screen, get_mouse_position_cocoa, CFAbsoluteTimeGetCurrent,
 };
-use crate::platform::macos::handlers::dispatch_events;
-use crate::platform::macos::input::{hotkey_event_handler, reinstall_hotkeys};
-use crate::platform::macos::storage::{prefs_set_double, prefs_set_int};
+use crate::platform::macos::handlers::{dispatch_events, MacContext};
+use crate::platform::macos::input::scripting::handle_script_event;
+use crate::platform::macos::input::url_handler::handle_url_event;
+use crate::platform::macos::input::{
+    click_held_secs, click_hold_mode, hotkey_event_handler, pen_pressure, publish_control_state,
+    reinstall_hotkeys, ruler_line,
+};
+use crate::platform::macos::storage::{load_frame_colors, prefs_get_int, save_frame_colors};
 use crate::platform::macos::ui::{
-    close_settings_window, confirm_and_maybe_quit, draw_circle, draw_letter,
-    open_settings_window, update_status_bar_language, ClickLetter, DrawParams,
+    draw_badge, draw_circle, draw_color_sample, draw_coordinates_label, draw_debug_label,
+    draw_grid, draw_hold_ring, draw_icon, draw_laser, draw_letter, draw_pen_tip, draw_ruler,
+    draw_screen_frame, draw_zoom, open_about_link, sample_screen_color, update_status_bar_state,
+    ClickLetter, DrawParams,
 };
-use crate::{color_to_hex, parse_hex_color, tr_key};
+use crate::render::geometry::map_segment;
 
 use objc2::runtime::{AnyClass, AnyObject, ClassBuilder, Sel};
 use objc2::sel;
 use objc2_foundation::{NSPoint, NSRect, NSSize};
+use tracing::info;
+
+thread_local! {
+    /// Laser-pointer trail in global screen coordinates (main thread only).
+    static LASER_TRAIL: RefCell<Trail> = RefCell::new(Trail::default());
+
+    /// Cursor speed tracker for the opacity ramp (main thread only).
+    static KINEMATICS: RefCell<Kinematics> = RefCell::new(Kinematics::new());
+
+    /// Idle tracker and fade animation for the auto-hide (main thread only).
+    static IDLE: RefCell<IdleFade> = RefCell::new(IdleFade::new());
+
+    /// Show/hide transition after the overlay is toggled (main thread only).
+    static SHOW_HIDE: RefCell<ShowHide> = RefCell::new(ShowHide::new());
+
+    /// Zoom level and pan focus in global screen coordinates (main thread only).
+    static ZOOM: RefCell<Zoom> = RefCell::new(Zoom::new());
+
+    /// Per-display recording-frame colors, loaded from preferences on first use.
+    static FRAME_COLORS: RefCell<FrameColors> = RefCell::new(unsafe { load_frame_colors() });
+
+    /// Frame timer of `--diagnostics=overlay`, `None` unless enabled (main thread only).
+    static DEBUG_OVERLAY: RefCell<Option<FrameTimer>> = const { RefCell::new(None) };
+}
+
+/// Draw the display id and average frame time into the overlay
+/// (`--diagnostics=overlay`). Redraws every frame while enabled.
+pub fn enable_debug_overlay() {
+    DEBUG_OVERLAY.with(|d| *d.borrow_mut() = Some(FrameTimer::new()));
+}
 
 // ============================================================================
 // CustomView registration and creation
@@ -80,7 +142,7 @@ unsafe fn register_ivars(builder: &mut ClassBuilder) {
     builder.add_ivar::<i32>(c"_displayMode"); // 0=circle, 1=L, 2=R
     builder.add_ivar::<id>(c"_ownScreen"); // owning NSScreen
     builder.add_ivar::<u32>(c"_ownDisplayID"); // stable DisplayID
-    builder.add_ivar::<i32>(c"_lang"); // 0=en, 1=es
+    builder.add_ivar::<i32>(c"_lang"); // index into i18n::languages()
 
     // Visual parameters
     builder.add_ivar::<f64>(c"_radius");
@@ -90,12 +152,61 @@ unsafe fn register_ivars(builder: &mut ClassBuilder) {
     builder.add_ivar::<f64>(c"_strokeB");
     builder.add_ivar::<f64>(c"_strokeA");
     builder.add_ivar::<f64>(c"_fillTransparencyPct"); // 0..100
+    builder.add_ivar::<i32>(c"_highlightStyle"); // 0=circle, 1=laser
+    builder.add_ivar::<u8>(c"_screenFrame"); // recording frame (bool as u8)
+    builder.add_ivar::<u8>(c"_speedFade"); // opacity ramp by speed (bool as u8)
+    builder.add_ivar::<f64>(c"_fadeAlpha"); // speed ramp x idle fade multiplier 0..1
+    builder.add_ivar::<u8>(c"_speedSize"); // radius scaling by speed (bool as u8)
+    builder.add_ivar::<f64>(c"_speedScale"); // speed radius multiplier, 1 when off
+    builder.add_ivar::<u8>(c"_zoomed"); // magnifying this screen (bool as u8)
+    builder.add_ivar::<u8>(c"_clickBurst"); // multi-click counter badge (bool as u8)
+    builder.add_ivar::<f64>(c"_clickBurstMs"); // max gap between burst clicks
+    builder.add_ivar::<u32>(c"_clickCount"); // clicks in the current burst
+    builder.add_ivar::<u8>(c"_idleHide"); // fade out while idle (bool as u8)
+    builder.add_ivar::<f64>(c"_idleHideSecs"); // idle delay before fading
+    builder.add_ivar::<f64>(c"_clickHoldMs"); // L/R letter hold after mouse-up
+    builder.add_ivar::<u8>(c"_holdRing"); // press-and-hold progress ring (bool as u8)
+    builder.add_ivar::<f64>(c"_holdRingMs"); // hold time that fills the ring
+    builder.add_ivar::<f64>(c"_holdProgress"); // ring fill 0..1, < 0 when no ring
+    builder.add_ivar::<f64>(c"_penPressure"); // pen pressure 0..1, < 0 when no pen
+    builder.add_ivar::<u8>(c"_showCoordinates"); // cursor coordinates readout (bool as u8)
+    builder.add_ivar::<u8>(c"_showGrid"); // alignment grid (bool as u8)
+    builder.add_ivar::<f64>(c"_gridSpacing"); // between major lines
+    builder.add_ivar::<i32>(c"_gridSubdivisions"); // parts of a cell
+    builder.add_ivar::<f64>(c"_gridR");
+    builder.add_ivar::<f64>(c"_gridG");
+    builder.add_ivar::<f64>(c"_gridB");
+    builder.add_ivar::<f64>(c"_gridA");
+    builder.add_ivar::<u8>(c"_colorSampler"); // eyedropper mode (bool as u8)
+    builder.add_ivar::<i32>(c"_sampleRgb"); // sampled 0xRRGGBB, < 0 when not sampling
+    builder.add_ivar::<i32>(c"_motion"); // 0=follow system, 1=reduced, 2=full
+    builder.add_ivar::<u8>(c"_systemReduceMotion"); // OS reduce-motion setting (bool as u8)
+    builder.add_ivar::<u8>(c"_halo"); // contrasting outline halo (bool as u8)
+    builder.add_ivar::<i32>(c"_glow"); // 0=off, 1=soft glow, 2=drop shadow
+    builder.add_ivar::<i32>(c"_clickIcon"); // CLICK_ICON_*, 0 = letters
+    builder.add_ivar::<u8>(c"_pulse"); // breathing radius (bool as u8)
+    builder.add_ivar::<f64>(c"_pulseAmplitude"); // percentage of the radius
+    builder.add_ivar::<f64>(c"_pulsePeriodMs"); // one breath, in milliseconds
+    builder.add_ivar::<f64>(c"_pulsePhase"); // pulse phase 0..1, < 0 when not pulsing
+    builder.add_ivar::<f64>(c"_transitionMs"); // show/hide transition duration
+    builder.add_ivar::<f64>(c"_transition"); // show/hide progress 0..1 (1 = shown)
+    builder.add_ivar::<u8>(c"_rainbow"); // cycle the stroke hue (bool as u8)
+    builder.add_ivar::<f64>(c"_rainbowSpeed"); // hue cycles per minute
+    builder.add_ivar::<f64>(c"_rainbowPhase"); // hue phase 0..1, < 0 when not cycling
 
     // Carbon refs
     builder.add_ivar::<*mut std::ffi::c_void>(c"_hkHandler");
     builder.add_ivar::<*mut std::ffi::c_void>(c"_hkToggle");
     builder.add_ivar::<*mut std::ffi::c_void>(c"_hkComma");
     builder.add_ivar::<*mut std::ffi::c_void>(c"_hkHelp");
+    builder.add_ivar::<*mut std::ffi::c_void>(c"_hkZoomIn");
+    builder.add_ivar::<*mut std::ffi::c_void>(c"_hkZoomOut");
+    builder.add_ivar::<*mut std::ffi::c_void>(c"_hkCoordinates");
+    builder.add_ivar::<*mut std::ffi::c_void>(c"_hkColorSampler");
+    builder.add_ivar::<*mut std::ffi::c_void>(c"_hkRuler");
+    builder.add_ivar::<*mut std::ffi::c_void>(c"_hkRulerEscape"); // Esc in the ruler mode
+    builder.add_ivar::<*mut std::ffi::c_void>(c"_hkGrid");
+    builder.add_ivar::<*mut std::ffi::c_void>(c"_hkLock");
     builder.add_ivar::<*mut std::ffi::c_void>(c"_hkQuit");
 
     // Keep-alive timer for hotkeys
@@ -107,35 +218,11 @@ unsafe fn register_ivars(builder: &mut ClassBuilder) {
     builder.add_ivar::<id>(c"_monRightDown");
     builder.add_ivar::<id>(c"_monRightUp");
     builder.add_ivar::<id>(c"_monMove");
-
-    // Settings UI refs
-    builder.add_ivar::<id>(c"_settingsWindow");
-    builder.add_ivar::<id>(c"_labelLang");
-    builder.add_ivar::<id>(c"_popupLang");
-
-    builder.add_ivar::<id>(c"_labelRadius");
-    builder.add_ivar::<id>(c"_fieldRadius"); // now a label
-    builder.add_ivar::<id>(c"_sliderRadius");
-
-    builder.add_ivar::<id>(c"_labelBorder");
-    builder.add_ivar::<id>(c"_fieldBorder"); // now a label
-    builder.add_ivar::<id>(c"_sliderBorder");
-
-    builder.add_ivar::<id>(c"_labelColor");
-    builder.add_ivar::<id>(c"_colorWell");
-
-    builder.add_ivar::<id>(c"_labelHex");
-    builder.add_ivar::<id>(c"_fieldHex"); // remains editable
-
-    builder.add_ivar::<id>(c"_labelFillT");
-    builder.add_ivar::<id>(c"_fieldFillT"); // now a label
-    builder.add_ivar::<id>(c"_sliderFillT");
-
-    builder.add_ivar::<id>(c"_btnClose");
-    builder.add_ivar::<id>(c"_previousApp"); // App to restore focus to when closing settings
+    builder.add_ivar::<id>(c"_monPen"); // pen pressure and proximity
 
     // Local key monitor (for Ctrl+A redundancy)
     builder.add_ivar::<id>(c"_localKeyMonitor");
+    builder.add_ivar::<id>(c"_rulerMonitor"); // left clicks, while the ruler mode is on
 
     // Debounce toggles
     builder.add_ivar::<f64>(c"_lastToggleTs");
@@ -148,13 +235,10 @@ unsafe fn register_ivars(builder: &mut ClassBuilder) {
     builder.add_ivar::<f64>(c"_lastCursorY");
     builder.add_ivar::<i32>(c"_lastDisplayMode");
     builder.add_ivar::<u8>(c"_lastOverlayEnabled"); // bool as u8, init to 2 (neither T/F)
+    builder.add_ivar::<f64>(c"_lastZoomLevel");
 
     // Refresh timer
     builder.add_ivar::<id>(c"_updateTimer");
-
-    // Monitors used while settings window is open (ESC/Enter)
-    builder.add_ivar::<id>(c"_settingsEscMonitor");
-    builder.add_ivar::<id>(c"_settingsGlobalMonitor");
 }
 
 /// Register all methods for the CustomView.
@@ -179,73 +263,85 @@ unsafe fn register_methods(builder: &mut ClassBuilder) {
         sel!(hotkeyKeepAlive),
         hotkey_keepalive as unsafe extern "C-unwind" fn(_, _),
     );
+    builder.add_method(
+        sel!(handleScriptEvent:withReplyEvent:),
+        script_event as unsafe extern "C-unwind" fn(_, _, _, _),
+    );
+    builder.add_method(
+        sel!(handleURLEvent:withReplyEvent:),
+        url_event as unsafe extern "C-unwind" fn(_, _, _, _),
+    );
+    builder.add_method(sel!(zoomIn), zoom_in as unsafe extern "C-unwind" fn(_, _));
+    builder.add_method(sel!(zoomOut), zoom_out as unsafe extern "C-unwind" fn(_, _));
 
-    // Settings slider actions
+    // Status bar menu actions
     builder.add_method(
-        sel!(setRadius:),
-        set_radius as unsafe extern "C-unwind" fn(_, _, _),
+        sel!(menuWillOpen:),
+        status_menu_will_open as unsafe extern "C-unwind" fn(_, _, _),
     );
     builder.add_method(
-        sel!(setRadiusFromField:),
-        set_radius_from_field as unsafe extern "C-unwind" fn(_, _, _),
+        sel!(statusBarSettings:),
+        status_bar_settings as unsafe extern "C-unwind" fn(_, _, _),
     );
     builder.add_method(
-        sel!(setBorderWidth:),
-        set_border_width as unsafe extern "C-unwind" fn(_, _, _),
+        sel!(statusBarHelp:),
+        status_bar_help as unsafe extern "C-unwind" fn(_, _, _),
     );
     builder.add_method(
-        sel!(setBorderFromField:),
-        set_border_from_field as unsafe extern "C-unwind" fn(_, _, _),
+        sel!(statusBarLock:),
+        status_bar_lock as unsafe extern "C-unwind" fn(_, _, _),
     );
     builder.add_method(
-        sel!(setFillTransparency:),
-        set_fill_transparency as unsafe extern "C-unwind" fn(_, _, _),
+        sel!(statusBarHotkeyProfile:),
+        status_bar_hotkey_profile as unsafe extern "C-unwind" fn(_, _, _),
     );
     builder.add_method(
-        sel!(setFillTransparencyFromField:),
-        set_fill_transparency_from_field as unsafe extern "C-unwind" fn(_, _, _),
+        sel!(statusBarRadius:),
+        status_bar_radius as unsafe extern "C-unwind" fn(_, _, _),
     );
-
-    // Color actions
     builder.add_method(
-        sel!(colorChanged:),
-        color_changed as unsafe extern "C-unwind" fn(_, _, _),
+        sel!(statusBarLookProfile:),
+        status_bar_look_profile as unsafe extern "C-unwind" fn(_, _, _),
     );
     builder.add_method(
-        sel!(hexChanged:),
-        hex_changed as unsafe extern "C-unwind" fn(_, _, _),
+        sel!(statusBarRecordClicks:),
+        status_bar_record_clicks as unsafe extern "C-unwind" fn(_, _, _),
     );
     builder.add_method(
-        sel!(closeSettings:),
-        close_settings as unsafe extern "C-unwind" fn(_, _, _),
+        sel!(statusBarExportCsv:),
+        status_bar_export_csv as unsafe extern "C-unwind" fn(_, _, _),
     );
-
-    // Status bar menu actions
     builder.add_method(
-        sel!(statusBarSettings:),
-        status_bar_settings as unsafe extern "C-unwind" fn(_, _, _),
+        sel!(statusBarExportJson:),
+        status_bar_export_json as unsafe extern "C-unwind" fn(_, _, _),
     );
     builder.add_method(
-        sel!(statusBarHelp:),
-        status_bar_help as unsafe extern "C-unwind" fn(_, _, _),
+        sel!(statusBarUpdate:),
+        status_bar_update as unsafe extern "C-unwind" fn(_, _, _),
+    );
+    builder.add_method(
+        sel!(statusBarCheckUpdates:),
+        status_bar_check_updates as unsafe extern "C-unwind" fn(_, _, _),
+    );
+    builder.add_method(
+        sel!(statusBarAutoUpdate:),
+        status_bar_auto_update as unsafe extern "C-unwind" fn(_, _, _),
     );
     builder.add_method(
         sel!(statusBarAbout:),
         status_bar_about as unsafe extern "C-unwind" fn(_, _, _),
     );
     builder.add_method(
-        sel!(statusBarQuit:),
-        status_bar_quit as unsafe extern "C-unwind" fn(_, _, _),
+        sel!(aboutLink:),
+        about_link as unsafe extern "C-unwind" fn(_, _, _),
     );
-
-    // Language change
     builder.add_method(
-        sel!(langChanged:),
-        lang_changed as unsafe extern "C-unwind" fn(_, _, _),
+        sel!(statusBarRestart:),
+        status_bar_restart as unsafe extern "C-unwind" fn(_, _, _),
     );
     builder.add_method(
-        sel!(controlTextDidChange:),
-        control_text_did_change as unsafe extern "C-unwind" fn(_, _, _),
+        sel!(statusBarQuit:),
+        status_bar_quit as unsafe extern "C-unwind" fn(_, _, _),
     );
 
     // Drawing
@@ -277,12 +373,61 @@ unsafe fn initialize_view_ivars(view: id) {
     (*view).store_ivar::<f64>("_strokeB", DEFAULT_COLOR.2);
     (*view).store_ivar::<f64>("_strokeA", DEFAULT_COLOR.3);
     (*view).store_ivar::<f64>("_fillTransparencyPct", DEFAULT_FILL_TRANSPARENCY_PCT);
+    (*view).store_ivar::<i32>("_highlightStyle", HIGHLIGHT_STYLE_CIRCLE);
+    set_bool_ivar(view, "_screenFrame", false);
+    set_bool_ivar(view, "_speedFade", false);
+    (*view).store_ivar::<f64>("_fadeAlpha", 1.0);
+    set_bool_ivar(view, "_speedSize", false);
+    (*view).store_ivar::<f64>("_speedScale", 1.0);
+    set_bool_ivar(view, "_zoomed", false);
+    set_bool_ivar(view, "_clickBurst", false);
+    (*view).store_ivar::<f64>("_clickBurstMs", DEFAULT_CLICK_BURST_MS);
+    (*view).store_ivar::<u32>("_clickCount", 1);
+    set_bool_ivar(view, "_idleHide", false);
+    (*view).store_ivar::<f64>("_idleHideSecs", DEFAULT_IDLE_HIDE_SECS);
+    (*view).store_ivar::<f64>("_clickHoldMs", DEFAULT_CLICK_HOLD_MS);
+    set_bool_ivar(view, "_holdRing", false);
+    (*view).store_ivar::<f64>("_holdRingMs", DEFAULT_HOLD_RING_MS);
+    (*view).store_ivar::<f64>("_holdProgress", -1.0);
+    (*view).store_ivar::<f64>("_penPressure", -1.0);
+    set_bool_ivar(view, "_showCoordinates", false);
+    set_bool_ivar(view, "_showGrid", false);
+    (*view).store_ivar::<f64>("_gridSpacing", DEFAULT_GRID_SPACING);
+    (*view).store_ivar::<i32>("_gridSubdivisions", DEFAULT_GRID_SUBDIVISIONS);
+    (*view).store_ivar::<f64>("_gridR", DEFAULT_GRID_COLOR.0);
+    (*view).store_ivar::<f64>("_gridG", DEFAULT_GRID_COLOR.1);
+    (*view).store_ivar::<f64>("_gridB", DEFAULT_GRID_COLOR.2);
+    (*view).store_ivar::<f64>("_gridA", DEFAULT_GRID_COLOR.3);
+    set_bool_ivar(view, "_colorSampler", false);
+    (*view).store_ivar::<i32>("_sampleRgb", -1);
+    (*view).store_ivar::<i32>("_motion", MOTION_SYSTEM);
+    set_bool_ivar(view, "_systemReduceMotion", false);
+    set_bool_ivar(view, "_halo", false);
+    (*view).store_ivar::<i32>("_glow", GLOW_OFF);
+    (*view).store_ivar::<i32>("_clickIcon", CLICK_ICON_NONE);
+    set_bool_ivar(view, "_pulse", false);
+    (*view).store_ivar::<f64>("_pulseAmplitude", DEFAULT_PULSE_AMPLITUDE);
+    (*view).store_ivar::<f64>("_pulsePeriodMs", DEFAULT_PULSE_PERIOD_MS);
+    (*view).store_ivar::<f64>("_pulsePhase", -1.0);
+    (*view).store_ivar::<f64>("_transitionMs", DEFAULT_TRANSITION_MS);
+    (*view).store_ivar::<f64>("_transition", 1.0);
+    set_bool_ivar(view, "_rainbow", false);
+    (*view).store_ivar::<f64>("_rainbowSpeed", DEFAULT_RAINBOW_SPEED);
+    (*view).store_ivar::<f64>("_rainbowPhase", -1.0);
 
     // Carbon refs
     (*view).store_ivar::<*mut std::ffi::c_void>("_hkHandler", std::ptr::null_mut());
     (*view).store_ivar::<*mut std::ffi::c_void>("_hkToggle", std::ptr::null_mut());
     (*view).store_ivar::<*mut std::ffi::c_void>("_hkComma", std::ptr::null_mut());
     (*view).store_ivar::<*mut std::ffi::c_void>("_hkHelp", std::ptr::null_mut());
+    (*view).store_ivar::<*mut std::ffi::c_void>("_hkZoomIn", std::ptr::null_mut());
+    (*view).store_ivar::<*mut std::ffi::c_void>("_hkZoomOut", std::ptr::null_mut());
+    (*view).store_ivar::<*mut std::ffi::c_void>("_hkCoordinates", std::ptr::null_mut());
+    (*view).store_ivar::<*mut std::ffi::c_void>("_hkColorSampler", std::ptr::null_mut());
+    (*view).store_ivar::<*mut std::ffi::c_void>("_hkRuler", std::ptr::null_mut());
+    (*view).store_ivar::<*mut std::ffi::c_void>("_hkRulerEscape", std::ptr::null_mut());
+    (*view).store_ivar::<*mut std::ffi::c_void>("_hkGrid", std::ptr::null_mut());
+    (*view).store_ivar::<*mut std::ffi::c_void>("_hkLock", std::ptr::null_mut());
     (*view).store_ivar::<*mut std::ffi::c_void>("_hkQuit", std::ptr::null_mut());
 
     // Keep-alive timer ref
@@ -294,33 +439,10 @@ unsafe fn initialize_view_ivars(view: id) {
     (*view).store_ivar::<id>("_monRightDown", nil);
     (*view).store_ivar::<id>("_monRightUp", nil);
     (*view).store_ivar::<id>("_monMove", nil);
-
-    // Settings UI refs
-    (*view).store_ivar::<id>("_settingsWindow", nil);
-    (*view).store_ivar::<id>("_labelLang", nil);
-    (*view).store_ivar::<id>("_popupLang", nil);
-
-    (*view).store_ivar::<id>("_labelRadius", nil);
-    (*view).store_ivar::<id>("_fieldRadius", nil);
-    (*view).store_ivar::<id>("_sliderRadius", nil);
-
-    (*view).store_ivar::<id>("_labelBorder", nil);
-    (*view).store_ivar::<id>("_fieldBorder", nil);
-    (*view).store_ivar::<id>("_sliderBorder", nil);
-
-    (*view).store_ivar::<id>("_labelColor", nil);
-    (*view).store_ivar::<id>("_colorWell", nil);
-
-    (*view).store_ivar::<id>("_labelHex", nil);
-    (*view).store_ivar::<id>("_fieldHex", nil);
-
-    (*view).store_ivar::<id>("_labelFillT", nil);
-    (*view).store_ivar::<id>("_fieldFillT", nil);
-    (*view).store_ivar::<id>("_sliderFillT", nil);
-
-    (*view).store_ivar::<id>("_btnClose", nil);
+    (*view).store_ivar::<id>("_monPen", nil);
 
     (*view).store_ivar::<id>("_localKeyMonitor", nil);
+    (*view).store_ivar::<id>("_rulerMonitor", nil);
     (*view).store_ivar::<f64>("_lastToggleTs", 0.0);
     set_bool_ivar(view, "_menuInstalled", false);
 
@@ -329,41 +451,37 @@ unsafe fn initialize_view_ivars(view: id) {
     (*view).store_ivar::<f64>("_lastCursorY", f64::MIN);
     (*view).store_ivar::<i32>("_lastDisplayMode", -1);
     (*view).store_ivar::<u8>("_lastOverlayEnabled", 2); // neither true(1) nor false(0)
+    (*view).store_ivar::<f64>("_lastZoomLevel", 1.0);
 
     // Refresh timer
     (*view).store_ivar::<id>("_updateTimer", nil);
-
-    // ESC monitor (settings)
-    (*view).store_ivar::<id>("_settingsEscMonitor", nil);
 }
 
 // ============================================================================
 // CustomView methods (extern "C-unwind" for Objective-C runtime)
 // ============================================================================
 
-/// Wrapper callback for reinstalling hotkeys after dialogs close.
-unsafe fn reinstall_hotkeys_callback(view: id) {
-    reinstall_hotkeys(view, hotkey_event_handler);
-}
-
 /// Process all pending events from the event bus.
 unsafe fn process_pending_events(view: id) {
-    dispatch_events(
-        view,
-        open_settings_window,
-        confirm_and_maybe_quit,
-        reinstall_hotkeys_callback,
-    );
+    dispatch_events(&mut MacContext::new(view));
+    publish_control_state(view);
 }
 
 unsafe extern "C-unwind" fn update_cursor_multi(this: &mut AnyObject, _cmd: Sel) {
+    refresh_ticked();
+
     // Process any pending events from the event bus (cheap — always run)
     process_pending_events(this as *mut _ as id);
 
     // Read current state for idle-skip comparison
     let (x, y) = get_mouse_position_cocoa();
     let enabled = get_bool_ivar(this as *mut _ as id, "_overlayEnabled");
-    let display_mode = *this.load_ivar::<i32>("_displayMode");
+
+    // A released button's letter stays up until its hold time runs out
+    let display_mode = click_hold_mode(this as *mut _ as id);
+    if display_mode != *this.load_ivar::<i32>("_displayMode") {
+        apply_to_all_views(|v| (*v).store_ivar::<i32>("_displayMode", display_mode));
+    }
 
     // Skip expensive screen iteration + redraw if nothing changed
     let host = this as *mut _ as id;
@@ -373,7 +491,177 @@ unsafe extern "C-unwind" fn update_cursor_multi(this: &mut AnyObject, _cmd: Sel)
     let last_enabled = *(*host).load_ivar::<u8>("_lastOverlayEnabled");
     let enabled_u8 = if enabled { 1u8 } else { 0u8 };
 
-    if x == last_x && y == last_y && display_mode == last_mode && enabled_u8 == last_enabled {
+    // Reduce Motion: no trail or speed ramp, instant idle fade and zoom pan
+    let reduced = reduce_motion(
+        *this.load_ivar::<i32>("_motion"),
+        get_bool_ivar(host, "_systemReduceMotion"),
+    );
+
+    // A toggle scales and fades the highlight in or out; it is drawn until fully hidden
+    let transition = SHOW_HIDE.with(|s| {
+        let mut show_hide = s.borrow_mut();
+        let duration_ms = *(*host).load_ivar::<f64>("_transitionMs");
+        let progress = show_hide.update(enabled, CFAbsoluteTimeGetCurrent(), duration_ms);
+        if reduced {
+            show_hide.snap()
+        } else {
+            progress
+        }
+    });
+    let last_transition = *(*host).load_ivar::<f64>("_transition");
+    let drawn = transition > 0.0;
+
+    // Laser trail keeps animating (fading) even while the cursor rests
+    let laser =
+        drawn && !reduced && *this.load_ivar::<i32>("_highlightStyle") == HIGHLIGHT_STYLE_LASER;
+    let trail_changed = LASER_TRAIL.with(|t| {
+        let mut trail = t.borrow_mut();
+        if !laser {
+            let had_points = !trail.is_empty();
+            trail.clear();
+            return had_points;
+        }
+        let now = CFAbsoluteTimeGetCurrent();
+        let pushed = trail.push(x, y, now);
+        let pruned = trail.prune(now);
+        pushed || pruned
+    });
+
+    // Opacity ramp and size scaling keep easing back after a fast sweep stops
+    let speed_fade = drawn && !reduced && get_bool_ivar(host, "_speedFade");
+    let speed_size = drawn && !reduced && get_bool_ivar(host, "_speedSize");
+    let speed = KINEMATICS.with(|k| {
+        let mut kin = k.borrow_mut();
+        if !speed_fade && !speed_size {
+            kin.reset();
+            return 0.0;
+        }
+        kin.update(x, y, CFAbsoluteTimeGetCurrent())
+    });
+    let speed_alpha = if speed_fade {
+        speed_fade_alpha(speed)
+    } else {
+        1.0
+    };
+    let speed_scale = if speed_size {
+        speed_size_scale(speed)
+    } else {
+        1.0
+    };
+    let last_scale = *(*host).load_ivar::<f64>("_speedScale");
+    let scale_changed = (speed_scale - last_scale).abs() > SPEED_SIZE_EPSILON
+        || (speed_scale == 1.0) != (last_scale == 1.0);
+
+    // Idle auto-hide fades out while the cursor rests (a held button counts as activity)
+    let idle_hide = drawn && get_bool_ivar(host, "_idleHide");
+    let idle_secs = *(*host).load_ivar::<f64>("_idleHideSecs");
+    let idle_alpha = IDLE.with(|i| {
+        let mut idle = i.borrow_mut();
+        if !idle_hide {
+            idle.reset();
+            return 1.0;
+        }
+        let active = display_mode != DISPLAY_MODE_CIRCLE;
+        let alpha = idle.update(x, y, active, CFAbsoluteTimeGetCurrent(), idle_secs);
+        if reduced {
+            idle.snap()
+        } else {
+            alpha
+        }
+    });
+
+    let alpha = speed_alpha * idle_alpha;
+    let last_alpha = *(*host).load_ivar::<f64>("_fadeAlpha");
+    let fade_changed = (alpha - last_alpha).abs() > SPEED_FADE_EPSILON
+        || (alpha == 1.0) != (last_alpha == 1.0)
+        || (alpha == 0.0) != (last_alpha == 0.0);
+
+    // Rainbow mode turns the hue every frame, with the same phase on every screen
+    let phase = if drawn && !reduced && get_bool_ivar(host, "_rainbow") {
+        rainbow_phase(
+            wall_clock_secs(),
+            *(*host).load_ivar::<f64>("_rainbowSpeed"),
+        )
+    } else {
+        -1.0
+    };
+    let last_phase = *(*host).load_ivar::<f64>("_rainbowPhase");
+
+    // The pulse grows and shrinks the radius on the same clock
+    let pulse = if drawn && !reduced && get_bool_ivar(host, "_pulse") {
+        pulse_phase(
+            wall_clock_secs(),
+            *(*host).load_ivar::<f64>("_pulsePeriodMs"),
+        )
+    } else {
+        -1.0
+    };
+    let last_pulse = *(*host).load_ivar::<f64>("_pulsePhase");
+
+    // The hold ring fills while a button stays down
+    let hold = match click_held_secs() {
+        Some(secs) if drawn && get_bool_ivar(host, "_holdRing") => {
+            hold_progress(secs, *(*host).load_ivar::<f64>("_holdRingMs"))
+        }
+        _ => -1.0,
+    };
+    let last_hold = *(*host).load_ivar::<f64>("_holdProgress");
+
+    // A pen grows the highlight with its pressure
+    let pen = pen_pressure().unwrap_or(-1.0);
+    let last_pen = *(*host).load_ivar::<f64>("_penPressure");
+
+    // The color sampler reads the pixel under the pointer, below its overlay
+    let sample = if drawn && get_bool_ivar(host, "_colorSampler") {
+        overlay_window_number(display_id_at(x, y))
+            .and_then(|window| sample_screen_color(x, y, window))
+            .map_or(-1, pack_rgb)
+    } else {
+        -1
+    };
+    let last_sample = *(*host).load_ivar::<i32>("_sampleRgb");
+
+    // While zoomed, the view pans after the cursor and shows live screen content
+    let zoom_level = ZOOM.with(|z| {
+        let mut zoom = z.borrow_mut();
+        if zoom.is_active() {
+            if reduced {
+                zoom.snap(x, y, CFAbsoluteTimeGetCurrent());
+            } else {
+                zoom.follow(x, y, CFAbsoluteTimeGetCurrent());
+            }
+        }
+        zoom.level()
+    });
+    let zoom_active = zoom_level > 1.0;
+    let last_zoom_level = *(*host).load_ivar::<f64>("_lastZoomLevel");
+
+    // The debug label measures every frame, so it never skips one
+    let debug = DEBUG_OVERLAY.with(|d| match d.borrow_mut().as_mut() {
+        Some(timer) => {
+            timer.tick(CFAbsoluteTimeGetCurrent());
+            true
+        }
+        None => false,
+    });
+
+    if x == last_x
+        && y == last_y
+        && display_mode == last_mode
+        && enabled_u8 == last_enabled
+        && !trail_changed
+        && !fade_changed
+        && !scale_changed
+        && phase == last_phase
+        && pulse == last_pulse
+        && hold == last_hold
+        && pen == last_pen
+        && sample == last_sample
+        && transition == last_transition
+        && !zoom_active
+        && zoom_level == last_zoom_level
+        && !debug
+    {
         return;
     }
 
@@ -382,12 +670,44 @@ unsafe extern "C-unwind" fn update_cursor_multi(this: &mut AnyObject, _cmd: Sel)
     (*host).store_ivar::<f64>("_lastCursorY", y);
     (*host).store_ivar::<i32>("_lastDisplayMode", display_mode);
     (*host).store_ivar::<u8>("_lastOverlayEnabled", enabled_u8);
+    (*host).store_ivar::<f64>("_lastZoomLevel", zoom_level);
 
     // Find which screen the cursor is on
+    let target_id = display_id_at(x, y);
+
+    apply_to_all_views(|v| {
+        *(*v).load_ivar_mut::<f64>("_cursorXScreen") = x;
+        *(*v).load_ivar_mut::<f64>("_cursorYScreen") = y;
+        *(*v).load_ivar_mut::<f64>("_fadeAlpha") = alpha;
+        *(*v).load_ivar_mut::<f64>("_speedScale") = speed_scale;
+        *(*v).load_ivar_mut::<f64>("_rainbowPhase") = phase;
+        *(*v).load_ivar_mut::<f64>("_pulsePhase") = pulse;
+        *(*v).load_ivar_mut::<f64>("_holdProgress") = hold;
+        *(*v).load_ivar_mut::<f64>("_penPressure") = pen;
+        *(*v).load_ivar_mut::<i32>("_sampleRgb") = sample;
+        *(*v).load_ivar_mut::<f64>("_transition") = transition;
+        let own_id = *(*v).load_ivar::<u32>("_ownDisplayID");
+        let on_target = own_id == target_id && target_id != 0;
+        let was_shown = get_bool_ivar(v, "_visible") || get_bool_ivar(v, "_zoomed");
+        let visible = drawn && on_target;
+        let zoomed = zoom_active && on_target;
+        set_bool_ivar(v, "_visible", visible);
+        set_bool_ivar(v, "_zoomed", zoomed);
+        // Other screens draw nothing: only the screen with the cursor and
+        // the one it just left (to clear) need a refresh
+        if visible || zoomed || was_shown {
+            let _: () = msg_send![v, setNeedsDisplay: YES];
+            let win: id = msg_send![v, window];
+            let _: () = msg_send![win, displayIfNeeded];
+        }
+    });
+}
+
+/// Display ID of the screen containing a point in Cocoa coordinates (0
+/// when the point is on no screen).
+unsafe fn display_id_at(x: f64, y: f64) -> u32 {
     let screens: id = msg_send![get_class("NSScreen"), screens];
     let count: usize = msg_send![screens, count];
-
-    let mut target_id: u32 = 0;
     for i in 0..count {
         let s: id = msg_send![screens, objectAtIndex: i];
         let f: NSRect = msg_send![s, frame];
@@ -396,21 +716,23 @@ unsafe extern "C-unwind" fn update_cursor_multi(this: &mut AnyObject, _cmd: Sel)
             && y >= f.origin.y
             && y <= f.origin.y + f.size.height
         {
-            target_id = display_id_for_screen(s);
-            break;
+            return display_id_for_screen(s);
         }
     }
+    0
+}
 
+/// Window number of the overlay on the display `display_id`, if any.
+unsafe fn overlay_window_number(display_id: u32) -> Option<u32> {
+    let mut number = None;
     apply_to_all_views(|v| {
-        *(*v).load_ivar_mut::<f64>("_cursorXScreen") = x;
-        *(*v).load_ivar_mut::<f64>("_cursorYScreen") = y;
-        let own_id = *(*v).load_ivar::<u32>("_ownDisplayID");
-        let vis = enabled && own_id == target_id && target_id != 0;
-        set_bool_ivar(v, "_visible", vis);
-        let _: () = msg_send![v, setNeedsDisplay: YES];
-        let win: id = msg_send![v, window];
-        let _: () = msg_send![win, displayIfNeeded];
+        if display_id != 0 && *(*v).load_ivar::<u32>("_ownDisplayID") == display_id {
+            let win: id = msg_send![v, window];
+            let n: isize = msg_send![win, windowNumber];
+            number = Some(n as u32);
+        }
     });
+    number
 }
 
 unsafe extern "C-unwind" fn toggle_visibility(this: &mut AnyObject, _cmd: Sel) {
@@ -421,22 +743,15 @@ unsafe extern "C-unwind" fn toggle_visibility(this: &mut AnyObject, _cmd: Sel) {
         apply_to_all_views(|v| {
             set_bool_ivar(v, "_overlayEnabled", new_enabled);
         });
+        update_status_bar_state(this as *mut _ as id);
 
-        if new_enabled {
-            let _: () = msg_send![
-                this,
-                performSelectorOnMainThread: sel!(update_cursor_multi),
-                withObject: nil,
-                waitUntilDone: NO
-            ];
-        } else {
-            apply_to_all_views(|v| {
-                set_bool_ivar(v, "_visible", false);
-                let _: () = msg_send![v, setNeedsDisplay: YES];
-                let win: id = msg_send![v, window];
-                let _: () = msg_send![win, displayIfNeeded];
-            });
-        }
+        // The next frames animate the highlight in or out (see `ShowHide`)
+        let _: () = msg_send![
+            this,
+            performSelectorOnMainThread: sel!(update_cursor_multi),
+            withObject: nil,
+            waitUntilDone: NO
+        ];
     }
 }
 
@@ -464,320 +779,392 @@ unsafe extern "C-unwind" fn request_toggle(this: &mut AnyObject, _cmd: Sel) {
 // Hotkey keep-alive: periodically re-install (idempotent)
 unsafe extern "C-unwind" fn hotkey_keepalive(this: &mut AnyObject, _cmd: Sel) {
     reinstall_hotkeys(this as *mut _ as id, hotkey_event_handler);
+    // Also notice permissions granted or revoked in System Settings
+    check_permissions();
 }
 
-// ===== Settings actions (apply to ALL views) =====
+// AppleScript commands (NSAppleEventManager handler)
+unsafe extern "C-unwind" fn script_event(_this: &mut AnyObject, _cmd: Sel, event: id, reply: id) {
+    handle_script_event(event, reply);
+}
 
-unsafe extern "C-unwind" fn set_radius(this: &mut AnyObject, _cmd: Sel, sender: id) {
-    unsafe {
-        let mut v: f64 = msg_send![sender, doubleValue];
-        // snap to 5 to match desired increments (no visual ticks)
-        v = (v / 5.0).round() * 5.0;
-        v = v.clamp(5.0, 200.0);
-
-        // update non-interactive label immediately
-        let field: id = *this.load_ivar("_fieldRadius");
-        if field != nil {
-            let _: () = msg_send![field, setStringValue: nsstring_id(&format!("{:.0}", v))];
-        }
+// lumbus:// URLs (NSAppleEventManager handler)
+unsafe extern "C-unwind" fn url_event(_this: &mut AnyObject, _cmd: Sel, event: id, _reply: id) {
+    handle_url_event(event);
+}
 
-        prefs_set_double(PREF_RADIUS, v);
-        apply_to_all_views(|vv| (*vv).store_ivar::<f64>("_radius", v));
-        apply_to_all_views(|vv| {
-            let _: () = msg_send![vv, setNeedsDisplay: YES];
-        });
+// ===== Zoom-follow (Ctrl+Shift+= / Ctrl+Shift+-) =====
+unsafe extern "C-unwind" fn zoom_in(this: &mut AnyObject, _cmd: Sel) {
+    // Without Screen Recording the zoom would only show the desktop
+    if !permission_status().zoom() {
+        info!("Zoom needs the Screen Recording permission");
+        return;
+    }
+    if ZOOM.with(|z| z.borrow_mut().zoom_in()) {
+        let _: () = msg_send![this, update_cursor_multi];
     }
 }
 
-// These are no-ops now (text fields became labels)
-unsafe extern "C-unwind" fn set_radius_from_field(_this: &mut AnyObject, _cmd: Sel, _sender: id) {}
-
-unsafe extern "C-unwind" fn set_border_width(this: &mut AnyObject, _cmd: Sel, sender: id) {
-    unsafe {
-        let mut v: f64 = msg_send![sender, doubleValue];
-        v = v.round().clamp(1.0, 20.0); // integer steps
-
-        let field: id = *this.load_ivar("_fieldBorder");
-        if field != nil {
-            let _: () = msg_send![field, setStringValue: nsstring_id(&format!("{:.0}", v))];
-        }
-
-        prefs_set_double(PREF_BORDER, v);
-        apply_to_all_views(|vv| (*vv).store_ivar::<f64>("_borderWidth", v));
-        apply_to_all_views(|vv| {
-            let _: () = msg_send![vv, setNeedsDisplay: YES];
-        });
+unsafe extern "C-unwind" fn zoom_out(this: &mut AnyObject, _cmd: Sel) {
+    if ZOOM.with(|z| z.borrow_mut().zoom_out()) {
+        let _: () = msg_send![this, update_cursor_multi];
     }
 }
 
-unsafe extern "C-unwind" fn set_border_from_field(_this: &mut AnyObject, _cmd: Sel, _sender: id) {}
+// ===== Status bar menu actions =====
 
-unsafe extern "C-unwind" fn set_fill_transparency(this: &mut AnyObject, _cmd: Sel, sender: id) {
-    unsafe {
-        let mut v: f64 = msg_send![sender, doubleValue];
-        v = (v / 5.0).round() * 5.0; // 5% steps
-        v = v.clamp(0.0, 100.0);
+unsafe extern "C-unwind" fn status_bar_settings(_this: &mut AnyObject, _cmd: Sel, _sender: id) {
+    // Publish OpenSettings event - dispatcher will handle it
+    publish(AppEvent::OpenSettings);
+}
 
-        let field: id = *this.load_ivar("_fieldFillT");
-        if field != nil {
-            let _: () = msg_send![field, setStringValue: nsstring_id(&format!("{:.0}", v))];
-        }
+unsafe extern "C-unwind" fn status_bar_about(_this: &mut AnyObject, _cmd: Sel, _sender: id) {
+    // Publish ShowAbout event - dispatcher will handle it
+    publish(AppEvent::ShowAbout);
+}
 
-        prefs_set_double(PREF_FILL_TRANSPARENCY, v);
-        apply_to_all_views(|vv| (*vv).store_ivar::<f64>("_fillTransparencyPct", v));
-        apply_to_all_views(|vv| {
-            let _: () = msg_send![vv, setNeedsDisplay: YES];
-        });
+unsafe extern "C-unwind" fn status_bar_update(_this: &mut AnyObject, _cmd: Sel, _sender: id) {
+    // Open the download page of the release found by the last check
+    if let Some(release) = available_update() {
+        open_release_page(&release);
     }
 }
 
-unsafe extern "C-unwind" fn set_fill_transparency_from_field(
+unsafe extern "C-unwind" fn status_bar_check_updates(
     _this: &mut AnyObject,
     _cmd: Sel,
     _sender: id,
 ) {
+    // Publish CheckForUpdates event - dispatcher will handle it
+    publish(AppEvent::CheckForUpdates);
 }
 
-unsafe extern "C-unwind" fn color_changed(this: &mut AnyObject, _cmd: Sel, sender: id) {
-    unsafe {
-        let color: id = msg_send![sender, color];
-        let r: f64 = msg_send![color, redComponent];
-        let g: f64 = msg_send![color, greenComponent];
-        let b: f64 = msg_send![color, blueComponent];
-        let a: f64 = msg_send![color, alphaComponent];
-
-        prefs_set_double(PREF_STROKE_R, r);
-        prefs_set_double(PREF_STROKE_G, g);
-        prefs_set_double(PREF_STROKE_B, b);
-        prefs_set_double(PREF_STROKE_A, a);
-
-        apply_to_all_views(|vv| {
-            (*vv).store_ivar::<f64>("_strokeR", r);
-            (*vv).store_ivar::<f64>("_strokeG", g);
-            (*vv).store_ivar::<f64>("_strokeB", b);
-            (*vv).store_ivar::<f64>("_strokeA", a);
-        });
-
-        let hex_field: id = *this.load_ivar("_fieldHex");
-        if hex_field != nil {
-            let s = color_to_hex(r, g, b, a);
-            let _: () = msg_send![hex_field, setStringValue: nsstring_id(&s)];
-        }
-        apply_to_all_views(|vv| {
-            let _: () = msg_send![vv, setNeedsDisplay: YES];
-        });
-    }
+unsafe extern "C-unwind" fn status_bar_auto_update(_this: &mut AnyObject, _cmd: Sel, _sender: id) {
+    // Flip the daily check; the dispatcher saves it and rebuilds the menu
+    let enabled = prefs_get_int(PREF_UPDATE_CHECK, 1) != 0;
+    publish(AppEvent::SetPreference(
+        PREF_UPDATE_CHECK.to_string(),
+        if enabled { 0.0 } else { 1.0 },
+    ));
 }
 
-unsafe extern "C-unwind" fn hex_changed(this: &mut AnyObject, _cmd: Sel, sender: id) {
-    unsafe {
-        let s: id = msg_send![sender, stringValue];
-        let cstr_ptr: *const c_char = msg_send![s, UTF8String];
-        if !cstr_ptr.is_null() {
-            let txt = CStr::from_ptr(cstr_ptr).to_string_lossy();
-            if let Some((r, g, b, a)) = parse_hex_color(&txt) {
-                prefs_set_double(PREF_STROKE_R, r);
-                prefs_set_double(PREF_STROKE_G, g);
-                prefs_set_double(PREF_STROKE_B, b);
-                prefs_set_double(PREF_STROKE_A, a);
-
-                apply_to_all_views(|vv| {
-                    (*vv).store_ivar::<f64>("_strokeR", r);
-                    (*vv).store_ivar::<f64>("_strokeG", g);
-                    (*vv).store_ivar::<f64>("_strokeB", b);
-                    (*vv).store_ivar::<f64>("_strokeA", a);
-                });
-
-                let col: id = msg_send![
-                    get_class("NSColor"),
-                    colorWithCalibratedRed: r,
-                    green: g,
-                    blue: b,
-                    alpha: a
-                ];
-                let well: id = *this.load_ivar("_colorWell");
-                if well != nil {
-                    let _: () = msg_send![well, setColor: col];
-                }
-                let norm = color_to_hex(r, g, b, a);
-                let _: () = msg_send![sender, setStringValue: nsstring_id(&norm)];
-                apply_to_all_views(|vv| {
-                    let _: () = msg_send![vv, setNeedsDisplay: YES];
-                });
-            } else {
-                let r = *this.load_ivar::<f64>("_strokeR");
-                let g = *this.load_ivar::<f64>("_strokeG");
-                let b = *this.load_ivar::<f64>("_strokeB");
-                let a = *this.load_ivar::<f64>("_strokeA");
-                let norm = color_to_hex(r, g, b, a);
-                let _: () = msg_send![sender, setStringValue: nsstring_id(&norm)];
-            }
-        }
-    }
+unsafe extern "C-unwind" fn about_link(_this: &mut AnyObject, _cmd: Sel, sender: id) {
+    // The button's tag is the link's index in ABOUT_LINKS
+    let tag: isize = msg_send![sender, tag];
+    open_about_link(tag as usize);
 }
 
-unsafe extern "C-unwind" fn close_settings(this: &mut AnyObject, _cmd: Sel, _sender: id) {
-    let view: id = this as *mut _ as id;
-    close_settings_window(view);
+unsafe extern "C-unwind" fn status_bar_help(_this: &mut AnyObject, _cmd: Sel, _sender: id) {
+    // Publish ShowHelp event - dispatcher will handle it
+    publish(AppEvent::ShowHelp);
 }
 
-// ===== Status bar menu actions =====
-
-unsafe extern "C-unwind" fn status_bar_settings(_this: &mut AnyObject, _cmd: Sel, _sender: id) {
-    // Publish OpenSettings event - dispatcher will handle it
-    publish(AppEvent::OpenSettings);
+unsafe extern "C-unwind" fn status_bar_lock(_this: &mut AnyObject, _cmd: Sel, _sender: id) {
+    // Publish TogglePresentationLock event - dispatcher will handle it
+    publish(AppEvent::TogglePresentationLock);
 }
 
-unsafe extern "C-unwind" fn status_bar_about(_this: &mut AnyObject, _cmd: Sel, _sender: id) {
-    // Publish ShowAbout event - dispatcher will handle it
-    publish(AppEvent::ShowAbout);
+unsafe extern "C-unwind" fn status_bar_hotkey_profile(
+    _this: &mut AnyObject,
+    _cmd: Sel,
+    sender: id,
+) {
+    // The item's tag is the profile's index in HOTKEY_PROFILES
+    let tag: isize = msg_send![sender, tag];
+    if let Some(profile) = HOTKEY_PROFILES.get(tag as usize) {
+        publish(AppEvent::SelectHotkeyProfile(profile.to_string()));
+    }
 }
 
-unsafe extern "C-unwind" fn status_bar_help(_this: &mut AnyObject, _cmd: Sel, _sender: id) {
-    // Publish ShowHelp event - dispatcher will handle it
-    publish(AppEvent::ShowHelp);
+unsafe extern "C-unwind" fn status_menu_will_open(_this: &mut AnyObject, _cmd: Sel, menu: id) {
+    // Option-click toggles the highlight instead of opening the menu
+    // NSEventModifierFlagOption = 1 << 19
+    let flags: u64 = msg_send![get_class("NSEvent"), modifierFlags];
+    if flags & (1 << 19) != 0 {
+        let _: () = msg_send![menu, cancelTrackingWithoutAnimation];
+        publish(AppEvent::ToggleOverlay);
+    }
 }
 
-unsafe extern "C-unwind" fn status_bar_quit(_this: &mut AnyObject, _cmd: Sel, _sender: id) {
-    // Quit directly without confirmation dialog
-    unsafe {
-        let app: id = msg_send![get_class("NSApplication"), sharedApplication];
-        let _: () = msg_send![app, terminate: nil];
+unsafe extern "C-unwind" fn status_bar_radius(_this: &mut AnyObject, _cmd: Sel, sender: id) {
+    // The item's tag is the radius' index in QUICK_RADII
+    let tag: isize = msg_send![sender, tag];
+    if let Some(radius) = QUICK_RADII.get(tag as usize) {
+        publish(AppEvent::SetRadius(*radius));
     }
 }
 
-// Change language (0=en,1=es), update labels and Hex field layout
-unsafe extern "C-unwind" fn lang_changed(this: &mut AnyObject, _cmd: Sel, sender: id) {
-    unsafe {
-        let idx: isize = msg_send![sender, indexOfSelectedItem];
-        let new_lang = if idx == 1 { 1 } else { 0 };
+unsafe extern "C-unwind" fn status_bar_look_profile(_this: &mut AnyObject, _cmd: Sel, sender: id) {
+    // The item's tag is the look's index in LOOK_PROFILES
+    let tag: isize = msg_send![sender, tag];
+    if let Some(name) = LOOK_PROFILES.get(tag as usize) {
+        publish(AppEvent::SelectLookProfile(name.to_string()));
+    }
+}
 
-        prefs_set_int(PREF_LANG, new_lang);
-        apply_to_all_views(|v| (*v).store_ivar::<i32>("_lang", new_lang));
+unsafe extern "C-unwind" fn status_bar_record_clicks(
+    _this: &mut AnyObject,
+    _cmd: Sel,
+    _sender: id,
+) {
+    publish(AppEvent::ToggleClickRecording);
+}
 
-        let es = new_lang == 1;
+unsafe extern "C-unwind" fn status_bar_export_csv(_this: &mut AnyObject, _cmd: Sel, _sender: id) {
+    publish(AppEvent::ExportClickLogCsv);
+}
 
-        let settings: id = *this.load_ivar("_settingsWindow");
-        if settings != nil {
-            let _: () = msg_send![settings, setTitle: nsstring_id(tr_key("Settings", es).as_ref())];
-        }
+unsafe extern "C-unwind" fn status_bar_export_json(_this: &mut AnyObject, _cmd: Sel, _sender: id) {
+    publish(AppEvent::ExportClickLogJson);
+}
 
-        let label_lang: id = *this.load_ivar("_labelLang");
-        if label_lang != nil {
-            let _: () = msg_send![
-                label_lang,
-                setStringValue: nsstring_id(tr_key("Language", es).as_ref())
-            ];
-        }
+unsafe extern "C-unwind" fn status_bar_restart(_this: &mut AnyObject, _cmd: Sel, _sender: id) {
+    publish(AppEvent::Restart);
+}
 
-        let popup: id = *this.load_ivar("_popupLang");
-        if popup != nil {
-            let _: () = msg_send![popup, removeAllItems];
-            let _: () = msg_send![
-                popup,
-                addItemWithTitle: nsstring_id(tr_key("English", es).as_ref())
-            ];
-            let _: () = msg_send![
-                popup,
-                addItemWithTitle: nsstring_id(tr_key("Spanish", es).as_ref())
-            ];
-            let _: () = msg_send![popup, selectItemAtIndex: if es { 1_isize } else { 0_isize }];
-        }
+unsafe extern "C-unwind" fn status_bar_quit(_this: &mut AnyObject, _cmd: Sel, _sender: id) {
+    // Publish RequestQuit event - dispatcher asks first if confirm_quit is on
+    publish(AppEvent::RequestQuit);
+}
 
-        let lr: id = *this.load_ivar("_labelRadius");
-        if lr != nil {
-            let _: () =
-                msg_send![lr, setStringValue: nsstring_id(tr_key("Radius (px)", es).as_ref())];
-        }
-        let lb: id = *this.load_ivar("_labelBorder");
-        if lb != nil {
-            let _: () =
-                msg_send![lb, setStringValue: nsstring_id(tr_key("Border (px)", es).as_ref())];
-        }
-        let lc: id = *this.load_ivar("_labelColor");
-        if lc != nil {
-            let _: () = msg_send![lc, setStringValue: nsstring_id(tr_key("Color", es).as_ref())];
-        }
-        let lhex: id = *this.load_ivar("_labelHex");
-        if lhex != nil {
-            let _: () = msg_send![lhex, setStringValue: nsstring_id(tr_key("Hex", es).as_ref())];
-            let _: () = msg_send![lhex, sizeToFit];
-
-            let field_hex: id = *this.load_ivar("_fieldHex");
-            if field_hex != nil && settings != nil {
-                let wframe: NSRect = msg_send![settings, frame];
-                let w = wframe.size.width;
-
-                let label_hex_frame: NSRect = msg_send![lhex, frame];
-                let padding: f64 = 8.0;
-                let right_margin: f64 = 175.0;
-                let field_x = label_hex_frame.origin.x + label_hex_frame.size.width + padding;
-                let field_w = (w - right_margin) - field_x;
-
-                let mut fh_frame: NSRect = msg_send![field_hex, frame];
-                fh_frame.origin.x = field_x;
-                fh_frame.size.width = field_w;
-                let _: () = msg_send![field_hex, setFrame: fh_frame];
-            }
-        }
-        let lfill: id = *this.load_ivar("_labelFillT");
-        if lfill != nil {
-            let _: () = msg_send![
-                lfill,
-                setStringValue: nsstring_id(tr_key("Fill Transparency (%)", es).as_ref())
-            ];
-        }
+/// Display key of the screen with the given frame.
+fn screen_key(frame: NSRect) -> String {
+    display_key(&ScreenRect::new(
+        frame.origin.x,
+        frame.origin.y,
+        frame.size.width,
+        frame.size.height,
+    ))
+}
 
-        let btn: id = *this.load_ivar("_btnClose");
-        if btn != nil {
-            let _: () = msg_send![btn, setTitle: nsstring_id(tr_key("Close", es).as_ref())];
-        }
+/// Recording-frame color of the display with the given frame.
+///
+/// Used by the settings window to initialise its color button.
+pub fn frame_color_for_screen(frame: NSRect) -> (f64, f64, f64, f64) {
+    let key = screen_key(frame);
+    FRAME_COLORS.with(|c| c.borrow().color_for(&key))
+}
 
-        // Update status bar menu language
-        update_status_bar_language(this as *const _ as id);
-    }
+/// Set and save the recording-frame color of the display with the given frame.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn set_frame_color_for_screen(frame: NSRect, color: (f64, f64, f64, f64)) {
+    let key = screen_key(frame);
+    FRAME_COLORS.with(|c| {
+        let mut colors = c.borrow_mut();
+        colors.set(&key, color);
+        save_frame_colors(&colors);
+    });
 }
 
-// Live delegate not required any more (value fields are labels)
-unsafe extern "C-unwind" fn control_text_did_change(_this: &mut AnyObject, _cmd: Sel, _notif: id) {}
+/// Reload the recording-frame colors after the settings file changed.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn reload_frame_colors() {
+    let colors = load_frame_colors();
+    FRAME_COLORS.with(|c| *c.borrow_mut() = colors);
+}
 
-// ===== Drawing (circle or L/R letter) =====
+// ===== Drawing (circle, laser or L/R letter) =====
 unsafe extern "C-unwind" fn draw_rect(this: &AnyObject, _cmd: Sel, _rect: NSRect) {
     unsafe {
         let sx = *this.load_ivar::<f64>("_cursorXScreen");
         let sy = *this.load_ivar::<f64>("_cursorYScreen");
         let visible = get_bool_ivar(this as *const _ as id, "_visible");
-        if !visible {
+        let zoomed = get_bool_ivar(this as *const _ as id, "_zoomed");
+        if !visible && !zoomed {
             return;
         }
 
-        // Convert screen -> window -> view
-        let screen_pt = NSPoint::new(sx, sy);
-        let screen_rect = NSRect::new(screen_pt, NSSize::new(0.0, 0.0));
         let win: id = msg_send![this, window];
-        let win_rect: NSRect = msg_send![win, convertRectFromScreen: screen_rect];
-        let win_pt = win_rect.origin;
-        let view_pt: NSPoint = msg_send![this, convertPoint: win_pt, fromView: nil];
+        let bounds: NSRect = msg_send![this, bounds];
+        let win_frame: NSRect = msg_send![win, frame];
+        let screen = ScreenRect::new(
+            win_frame.origin.x,
+            win_frame.origin.y,
+            win_frame.size.width,
+            win_frame.size.height,
+        );
+
+        // Magnified screen content goes below everything else
+        let zoom = if zoomed {
+            ZOOM.with(|z| {
+                let z = z.borrow();
+                z.focus().map(|focus| (z.level(), focus))
+            })
+        } else {
+            None
+        };
+        if let Some((level, focus)) = zoom {
+            let window_number: isize = msg_send![win, windowNumber];
+            draw_zoom(
+                bounds,
+                source_rect(&screen, level, focus),
+                window_number as u32,
+            );
+        }
+        if !visible {
+            return;
+        }
+
+        // Alignment grid across the highlighted screen (behind the pointer)
+        if get_bool_ivar(this as *const _ as id, "_showGrid") {
+            draw_grid(
+                bounds,
+                *this.load_ivar::<f64>("_gridSpacing"),
+                *this.load_ivar::<i32>("_gridSubdivisions"),
+                (
+                    *this.load_ivar::<f64>("_gridR"),
+                    *this.load_ivar::<f64>("_gridG"),
+                    *this.load_ivar::<f64>("_gridB"),
+                    *this.load_ivar::<f64>("_gridA"),
+                ),
+            );
+        }
+
+        // Recording frame around the highlighted screen (behind the pointer)
+        if get_bool_ivar(this as *const _ as id, "_screenFrame") {
+            draw_screen_frame(bounds, frame_color_for_screen(win_frame));
+        }
+
+        // Convert screen -> (magnified) screen -> window -> view
+        let to_view = |px: f64, py: f64| -> NSPoint {
+            let (px, py) = match zoom {
+                Some((level, focus)) => to_zoomed(&screen, level, focus, px, py),
+                None => (px, py),
+            };
+            let screen_rect = NSRect::new(NSPoint::new(px, py), NSSize::new(0.0, 0.0));
+            let win_rect: NSRect = msg_send![win, convertRectFromScreen: screen_rect];
+            msg_send![this, convertPoint: win_rect.origin, fromView: nil]
+        };
+        let view_pt = to_view(sx, sy);
+
+        // Measured line under the highlight, its length in the display's pixels
+        if let Some(line) = ruler_line((sx, sy)) {
+            let scale: f64 = msg_send![win, backingScaleFactor];
+            let (from, to) = (to_view(line.0 .0, line.0 .1), to_view(line.1 .0, line.1 .1));
+            draw_ruler(
+                ((from.x, from.y), (to.x, to.y)),
+                &ruler_label(line, scale),
+                bounds,
+            );
+        }
 
         let mode = *this.load_ivar::<i32>("_displayMode");
 
+        // Stroke color, its hue turned while the rainbow mode cycles
+        let stroke = (
+            *this.load_ivar::<f64>("_strokeR"),
+            *this.load_ivar::<f64>("_strokeG"),
+            *this.load_ivar::<f64>("_strokeB"),
+            1.0,
+        );
+        let phase = *this.load_ivar::<f64>("_rainbowPhase");
+        let (stroke_r, stroke_g, stroke_b, _) = if phase >= 0.0 {
+            rainbow_color(stroke, phase)
+        } else {
+            stroke
+        };
+
+        // Radius, breathing while the pulse animates and grown by fast
+        // movement or pen pressure
+        let radius = *this.load_ivar::<f64>("_radius");
+        let pulse = *this.load_ivar::<f64>("_pulsePhase");
+        let pen = *this.load_ivar::<f64>("_penPressure");
+        let radius = if pulse >= 0.0 {
+            pulse_radius(radius, *this.load_ivar::<f64>("_pulseAmplitude"), pulse)
+        } else {
+            radius
+        } * *this.load_ivar::<f64>("_speedScale")
+            * if pen >= 0.0 { pen_scale(pen) } else { 1.0 };
+
+        // Scaled and faded while the overlay is being shown or hidden
+        let transition = *this.load_ivar::<f64>("_transition");
+        let radius = radius * transition_scale(transition);
+
         // Build drawing parameters from view ivars
         let params = DrawParams {
             center: view_pt,
-            radius: *this.load_ivar::<f64>("_radius"),
+            radius,
             border_width: *this.load_ivar::<f64>("_borderWidth"),
-            stroke_r: *this.load_ivar::<f64>("_strokeR"),
-            stroke_g: *this.load_ivar::<f64>("_strokeG"),
-            stroke_b: *this.load_ivar::<f64>("_strokeB"),
-            stroke_a: *this.load_ivar::<f64>("_strokeA"),
+            stroke_r,
+            stroke_g,
+            stroke_b,
+            stroke_a: *this.load_ivar::<f64>("_strokeA")
+                * *this.load_ivar::<f64>("_fadeAlpha")
+                * transition_alpha(transition),
             fill_transparency: *this.load_ivar::<f64>("_fillTransparencyPct"),
+            halo: get_bool_ivar(this as *const _ as id, "_halo"),
+            glow: *this.load_ivar::<i32>("_glow"),
         };
 
-        let es = *this.load_ivar::<i32>("_lang") == 1;
+        let laser = *this.load_ivar::<i32>("_highlightStyle") == HIGHLIGHT_STYLE_LASER;
         match mode {
+            0 if laser => {
+                let now = CFAbsoluteTimeGetCurrent();
+                let segments: Vec<_> = LASER_TRAIL.with(|t| {
+                    t.borrow()
+                        .segments(now)
+                        .into_iter()
+                        .map(|seg| {
+                            map_segment(seg, |x, y| {
+                                let p = to_view(x, y);
+                                (p.x, p.y)
+                            })
+                        })
+                        .collect()
+                });
+                draw_laser(&params, &segments);
+            }
             0 => draw_circle(&params),
-            1 => draw_letter(&params, ClickLetter::Left, es),
-            _ => draw_letter(&params, ClickLetter::Right, es),
+            mode => {
+                let icon = click_icon(*this.load_ivar::<i32>("_clickIcon"));
+                // The letter stands in where the symbol is not available
+                if !icon.is_some_and(|icon| draw_icon(&params, icon.symbol)) {
+                    let letter = if mode == 1 {
+                        ClickLetter::Left
+                    } else {
+                        ClickLetter::Right
+                    };
+                    draw_letter(&params, letter);
+                }
+            }
+        }
+
+        // Ring filling up while the button stays down
+        let hold = *this.load_ivar::<f64>("_holdProgress");
+        if mode != 0 && hold >= 0.0 {
+            draw_hold_ring(&params, hold);
+        }
+
+        // Multi-click counter next to the L/R letter
+        if mode != 0 && get_bool_ivar(this as *const _ as id, "_clickBurst") {
+            if let Some(label) = badge_label(*this.load_ivar::<u32>("_clickCount")) {
+                draw_badge(&params, &label);
+            }
+        }
+
+        // Crosshair on the pen tip
+        if pen >= 0.0 {
+            draw_pen_tip(&params);
+        }
+
+        // Coordinates readout beside the pointer
+        if get_bool_ivar(this as *const _ as id, "_showCoordinates") {
+            draw_coordinates_label(&params, bounds, (sx, sy));
+        }
+
+        // Sampled color opposite the coordinates
+        if let Some(color) = unpack_rgb(*this.load_ivar::<i32>("_sampleRgb")) {
+            draw_color_sample(&params, bounds, color);
+        }
+
+        let frame_ms = DEBUG_OVERLAY.with(|d| d.borrow().as_ref().map(FrameTimer::average_ms));
+        if let Some(frame_ms) = frame_ms {
+            let own_id = *this.load_ivar::<u32>("_ownDisplayID");
+            draw_debug_label(bounds, &debug_label(own_id, frame_ms));
         }
     }
 }
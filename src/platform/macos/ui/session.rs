@@ -0,0 +1,24 @@
+//! Session restore for auxiliary windows.
+//!
+//! Reopens the auxiliary windows that were open when the app last ran,
+//! using the `model::session` store persisted in config.toml.
+
+use crate::model::session::SESSION_HELP_OVERLAY;
+use crate::platform::macos::storage::load_session;
+use crate::platform::macos::ui::dialogs::restore_help_overlay;
+
+/// Reopen auxiliary windows saved in the previous session.
+///
+/// Windows are reopened through the event bus, so this must run after the
+/// refresh timer (which drives the dispatcher) has been created.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+pub unsafe fn restore_session_windows() {
+    let session = load_session();
+    for id in session.open_windows() {
+        if id == SESSION_HELP_OVERLAY {
+            restore_help_overlay(session.position(id));
+        }
+    }
+}
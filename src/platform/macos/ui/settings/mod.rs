@@ -1,7 +1,7 @@
 //! Settings window module.
 //!
-//! This module contains the settings window and its controls.
+//! This module hosts the shared egui Settings panel in a native window.
 
 pub mod window;
 
-pub use window::{close_settings_window, open_settings_window};
+pub use window::{open_settings_window, start_onboarding};
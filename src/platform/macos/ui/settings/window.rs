@@ -1,34 +1,232 @@
-//! Settings window management.
+//! Settings window and first-run walkthrough for macOS.
 //!
-//! This module contains functions for opening and managing the settings window.
+//! Hosts the shared egui panels (`crate::settings`); this module only
+//! adapts the preferences file and the overlay views to [`SettingsStore`].
 
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 
 use crate::platform::macos::ffi::bridge::{
     get_bool_ivar, get_class, id, msg_send, nil, nsstring_id, sel, set_bool_ivar, NSApp, NSPoint,
-    NSRect, NSSize, ObjectExt, NO, YES,
+    NSRect, NO, YES,
 };
 use block2::RcBlock;
 
 use crate::events::{publish, AppEvent};
-use crate::platform::macos::app::{apply_to_all_views, lang_is_es};
-use crate::platform::macos::ffi::overlay_window_level;
-use crate::{color_to_hex, tr_key};
+use crate::model::constants::{PREF_CONFIRM_QUIT, PREF_EXCLUDE_FROM_CAPTURE, PREF_ONBOARDING_DONE};
+use crate::model::{Binding, ColorPalette, OverlayState, Permission};
+use crate::platform::macos::app::{
+    apply_capture_exclusion, apply_state_to_all_views, apply_to_all_views, check_permissions,
+    open_permission_settings, permission_granted, request_permission,
+};
+use crate::platform::macos::ffi::{font_families, overlay_window_level, system_locale};
+use crate::platform::macos::input::{current_hotkey_bindings, watch_config_directory};
+use crate::platform::macos::storage::{
+    flush_config, is_setting_locked, load_color_palette, load_state, prefs_get_int,
+    prefs_set_double, prefs_set_int, prefs_set_string, restore_defaults, save_color_palette,
+    set_sync_folder, sync_folder,
+};
+use crate::platform::macos::ui::overlay::{
+    frame_color_for_screen, reload_frame_colors, set_frame_color_for_screen,
+};
+use crate::platform::macos::ui::{choose_sync_folder, update_status_bar_language};
+use crate::settings::{run_onboarding_window, run_settings_window, SettingsStore};
 
 /// Guard to prevent multiple settings windows
 static SETTINGS_OPENING: AtomicBool = AtomicBool::new(false);
 
-/// Configure a hex color text field.
-unsafe fn configure_hex_field(view: id, field_hex: id) {
-    let _: () = msg_send![field_hex, setBezeled: YES];
-    let _: () = msg_send![field_hex, setDrawsBackground: YES];
-    let _: () = msg_send![field_hex, setEditable: YES];
-    let _: () = msg_send![field_hex, setSelectable: YES];
-    let _: () = msg_send![field_hex, setTarget: view];
-    let _: () = msg_send![field_hex, setAction: sel!(hexChanged:)];
+/// Has the launch notification been replayed for the settings event loop?
+static LAUNCH_REPLAYED: AtomicBool = AtomicBool::new(false);
+
+/// Settings backed by the preferences file, applied live to every overlay view.
+struct MacSettingsStore {
+    /// View whose status bar menu is relabelled on language changes.
+    view: id,
+    /// Frame of the screen the window opens on.
+    screen: NSRect,
+}
+
+impl SettingsStore for MacSettingsStore {
+    fn load_state(&self) -> OverlayState {
+        unsafe { load_state() }
+    }
+
+    fn set_double(&mut self, key: &str, value: f64) {
+        unsafe { prefs_set_double(key, value) }
+    }
+
+    fn set_int(&mut self, key: &str, value: i32) {
+        unsafe { prefs_set_int(key, value) }
+    }
+
+    fn set_string(&mut self, key: &str, value: &str) {
+        unsafe { prefs_set_string(key, value) }
+    }
+
+    fn is_locked(&self, key: &str) -> bool {
+        unsafe { is_setting_locked(key) }
+    }
+
+    fn flush(&mut self) {
+        flush_config();
+    }
+
+    fn frame_color(&self) -> (f64, f64, f64, f64) {
+        frame_color_for_screen(self.screen)
+    }
+
+    fn set_frame_color(&mut self, color: (f64, f64, f64, f64)) {
+        unsafe { set_frame_color_for_screen(self.screen, color) }
+    }
+
+    fn restore_defaults(&mut self) {
+        unsafe {
+            restore_defaults();
+            reload_frame_colors();
+        }
+    }
+
+    fn font_families(&self) -> Vec<String> {
+        unsafe { font_families() }
+    }
+
+    fn color_palette(&self) -> ColorPalette {
+        unsafe { load_color_palette() }
+    }
+
+    fn set_color_palette(&mut self, palette: &ColorPalette) {
+        unsafe { save_color_palette(palette) }
+    }
+
+    fn system_locale(&self) -> Option<String> {
+        unsafe { system_locale() }
+    }
+
+    fn settings_changed(&mut self) {
+        unsafe {
+            apply_state_to_all_views(&load_state());
+            apply_capture_exclusion();
+        }
+    }
+
+    fn language_changed(&mut self) {
+        unsafe { update_status_bar_language(self.view) }
+    }
+
+    fn confirm_quit(&self) -> Option<bool> {
+        Some(unsafe { prefs_get_int(PREF_CONFIRM_QUIT, 1) } != 0)
+    }
+
+    fn exclude_from_capture(&self) -> Option<bool> {
+        Some(unsafe { prefs_get_int(PREF_EXCLUDE_FROM_CAPTURE, 0) } != 0)
+    }
+
+    fn sync_folder(&self) -> Option<String> {
+        unsafe { sync_folder() }.map(|f| f.display().to_string())
+    }
+
+    fn choose_sync_folder(&mut self) -> Option<String> {
+        unsafe { choose_sync_folder() }
+    }
+
+    fn set_sync_folder(&mut self, folder: Option<&str>) {
+        unsafe {
+            set_sync_folder(folder.map(Path::new));
+            reload_frame_colors();
+            if let Some(folder) = sync_folder() {
+                watch_config_directory(self.view, &folder);
+            }
+        }
+    }
+
+    fn window_center(&self) -> Option<(f32, f32)> {
+        // Cocoa screen coordinates grow upwards from the primary display's
+        // bottom edge; the window is placed from its top edge
+        let primary: NSRect = unsafe {
+            let screens: id = msg_send![get_class("NSScreen"), screens];
+            let first: id = msg_send![screens, firstObject];
+            msg_send![first, frame]
+        };
+        let f = self.screen;
+        let x = f.origin.x + f.size.width / 2.0;
+        let y = primary.size.height - (f.origin.y + f.size.height / 2.0);
+        Some((x as f32, y as f32))
+    }
+
+    fn hotkey_bindings(&self) -> Vec<Binding> {
+        unsafe { current_hotkey_bindings() }.to_vec()
+    }
+
+    fn permissions(&self) -> &'static [Permission] {
+        &Permission::ALL
+    }
+
+    fn permission_granted(&self, permission: Permission) -> bool {
+        unsafe { permission_granted(permission) }
+    }
+
+    fn request_permission(&mut self, permission: Permission) {
+        unsafe { request_permission(permission) }
+    }
+
+    fn open_permission_settings(&mut self, permission: Permission) {
+        unsafe { open_permission_settings(permission) }
+    }
+
+    fn permissions_changed(&mut self) {
+        unsafe {
+            check_permissions();
+        }
+    }
+}
+
+/// Frame of the screen under the cursor, or of the main screen.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+unsafe fn screen_under_cursor() -> NSRect {
+    let mouse_loc: NSPoint = msg_send![get_class("NSEvent"), mouseLocation];
+    let screens: id = msg_send![get_class("NSScreen"), screens];
+    let screen_count: usize = msg_send![screens, count];
+
+    for i in 0..screen_count {
+        let scr: id = msg_send![screens, objectAtIndex: i];
+        let frame: NSRect = msg_send![scr, frame];
+        if mouse_loc.x >= frame.origin.x
+            && mouse_loc.x < frame.origin.x + frame.size.width
+            && mouse_loc.y >= frame.origin.y
+            && mouse_loc.y < frame.origin.y + frame.size.height
+        {
+            return frame;
+        }
+    }
+    let main: id = msg_send![get_class("NSScreen"), mainScreen];
+    msg_send![main, frame]
+}
+
+/// Let the settings event loop see the application launch.
+///
+/// The egui window runs on a winit event loop, which waits for
+/// `applicationDidFinishLaunching:` before it starts; lumbus finished
+/// launching long before. The notification is posted again, once, as
+/// soon as the event loop starts running.
+///
+/// # Safety
+/// Must be called from main thread with valid autorelease pool.
+unsafe fn replay_launch_notification() {
+    if LAUNCH_REPLAYED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let block = RcBlock::new(|| unsafe {
+        let center: id = msg_send![get_class("NSNotificationCenter"), defaultCenter];
+        let name = nsstring_id("NSApplicationDidFinishLaunchingNotification");
+        let _: () = msg_send![center, postNotificationName: name, object: NSApp()];
+    });
+    let queue: id = msg_send![get_class("NSOperationQueue"), mainQueue];
+    let _: () = msg_send![queue, addOperationWithBlock: &*block];
 }
 
-/// Open the settings window.
+/// Open the settings window and return once it is closed.
 ///
 /// When the window closes, publishes `AppEvent::SettingsClosed` to the event bus.
 /// The dispatcher handles hotkey reinstallation.
@@ -46,305 +244,81 @@ pub unsafe fn open_settings_window(view: id) {
         return;
     }
 
-    // Save current overlay state and hide circle during settings
+    // Hide the circle during settings
+    let was_enabled = hide_overlays(view);
+
+    // Open on the screen where the cursor is (not just main screen)
+    let screen = screen_under_cursor();
+    replay_launch_notification();
+    run_settings_window(Box::new(MacSettingsStore { view, screen }));
+    flush_config();
+
+    restore_overlays(was_enabled);
+
+    // Reset atomic guard
+    SETTINGS_OPENING.store(false, Ordering::SeqCst);
+
+    // Publish event - dispatcher will handle hotkey reinstallation
+    publish(AppEvent::SettingsClosed);
+}
+
+/// Show the first-run walkthrough as soon as the app runs. The overlay
+/// stays hidden until the walkthrough is finished or skipped, which is
+/// then recorded in `PREF_ONBOARDING_DONE`.
+///
+/// The window waits for the run loop: its event loop needs the launch
+/// notification (see `replay_launch_notification`).
+///
+/// # Safety
+/// - `view` must be a valid, non-null pointer to a CustomViewMulti.
+/// - Must be called from main thread with valid autorelease pool.
+pub unsafe fn start_onboarding(view: id) {
+    hide_overlays(view);
+    let block = RcBlock::new(move || unsafe {
+        open_onboarding_window(view);
+    });
+    let queue: id = msg_send![get_class("NSOperationQueue"), mainQueue];
+    let _: () = msg_send![queue, addOperationWithBlock: &*block];
+}
+
+unsafe fn open_onboarding_window(view: id) {
+    if SETTINGS_OPENING
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return;
+    }
+
+    let screen = screen_under_cursor();
+    replay_launch_notification();
+    run_onboarding_window(Box::new(MacSettingsStore { view, screen }));
+    prefs_set_int(PREF_ONBOARDING_DONE, 1);
+    flush_config();
+    check_permissions();
+
+    // First appearance of the overlay
+    restore_overlays(true);
+
+    SETTINGS_OPENING.store(false, Ordering::SeqCst);
+    publish(AppEvent::SettingsClosed);
+}
+
+/// Hide the circle on every display, returning whether the overlay was
+/// enabled.
+unsafe fn hide_overlays(view: id) -> bool {
     let was_enabled = get_bool_ivar(view, "_overlayEnabled");
     apply_to_all_views(|v| {
         set_bool_ivar(v, "_overlayEnabled", false);
         set_bool_ivar(v, "_visible", false);
         let _: () = msg_send![v, setNeedsDisplay: YES];
     });
+    was_enabled
+}
 
-    let es = lang_is_es(view);
-
-    // NSTitledWindowMask (1) | NSClosableWindowMask (2) = 3
-    let style: u64 = 1 | 2;
-    let w = 520.0;
-    let h = 330.0;
-
-    let settings: id = msg_send![get_class("NSWindow"), alloc];
-    let settings: id = msg_send![
-        settings,
-        initWithContentRect: NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(w, h)),
-        styleMask: style,
-        backing: 2u64,  // NSBackingStoreBuffered
-        defer: NO
-    ];
-    let _: () = msg_send![settings, setTitle: nsstring_id(tr_key("Settings", es).as_ref())];
-
-    // High level and collection behavior to appear over fullscreen apps
-    let _: () = msg_send![settings, setLevel: overlay_window_level()];
-    // CanJoinAllSpaces (1) + FullScreenAuxiliary (256) = 257
-    let _: () = msg_send![settings, setCollectionBehavior: 257u64];
-
-    // Center on the screen where the cursor is (not just main screen)
-    let mouse_loc: NSPoint = msg_send![get_class("NSEvent"), mouseLocation];
-    let screens: id = msg_send![get_class("NSScreen"), screens];
-    let screen_count: usize = msg_send![screens, count];
-    let mut target_screen: id = msg_send![get_class("NSScreen"), mainScreen];
-
-    for i in 0..screen_count {
-        let scr: id = msg_send![screens, objectAtIndex: i];
-        let frame: NSRect = msg_send![scr, frame];
-        if mouse_loc.x >= frame.origin.x
-            && mouse_loc.x < frame.origin.x + frame.size.width
-            && mouse_loc.y >= frame.origin.y
-            && mouse_loc.y < frame.origin.y + frame.size.height
-        {
-            target_screen = scr;
-            break;
-        }
-    }
-
-    let screen_frame: NSRect = msg_send![target_screen, frame];
-    let settings_frame: NSRect = msg_send![settings, frame];
-    let centered_x =
-        screen_frame.origin.x + (screen_frame.size.width - settings_frame.size.width) / 2.0;
-    let centered_y =
-        screen_frame.origin.y + (screen_frame.size.height - settings_frame.size.height) / 2.0;
-    let centered_origin = NSPoint {
-        x: centered_x,
-        y: centered_y,
-    };
-    let _: () = msg_send![settings, setFrameOrigin: centered_origin];
-
-    let content: id = msg_send![settings, contentView];
-
-    let radius: f64 = *(*view).load_ivar::<f64>("_radius");
-    let border: f64 = *(*view).load_ivar::<f64>("_borderWidth");
-    let r: f64 = *(*view).load_ivar::<f64>("_strokeR");
-    let g: f64 = *(*view).load_ivar::<f64>("_strokeG");
-    let b: f64 = *(*view).load_ivar::<f64>("_strokeB");
-    let a: f64 = *(*view).load_ivar::<f64>("_strokeA");
-    let fill_t: f64 = *(*view).load_ivar::<f64>("_fillTransparencyPct");
-    let cur_lang: i32 = *(*view).load_ivar::<i32>("_lang");
-
-    // Helper: static label
-    let mk_label = |x, y, text: &str| -> id {
-        let lbl: id = msg_send![get_class("NSTextField"), alloc];
-        let lbl: id = msg_send![
-            lbl,
-            initWithFrame: NSRect::new(NSPoint::new(x, y), NSSize::new(180.0, 20.0))
-        ];
-        let _: () = msg_send![lbl, setBezeled: NO];
-        let _: () = msg_send![lbl, setDrawsBackground: NO];
-        let _: () = msg_send![lbl, setEditable: NO];
-        let _: () = msg_send![lbl, setSelectable: NO];
-        let _: () = msg_send![lbl, setStringValue: nsstring_id(text)];
-        lbl
-    };
-
-    // Helper: value as a non-interactive label
-    let mk_value_label = |x, y, w_lbl, h_lbl, val: &str| -> id {
-        let tf: id = msg_send![get_class("NSTextField"), alloc];
-        let tf: id = msg_send![tf, initWithFrame: NSRect::new(NSPoint::new(x, y), NSSize::new(w_lbl, h_lbl))];
-        let _: () = msg_send![tf, setBezeled: NO];
-        let _: () = msg_send![tf, setDrawsBackground: NO];
-        let _: () = msg_send![tf, setEditable: NO];
-        let _: () = msg_send![tf, setSelectable: NO];
-        let _: () = msg_send![tf, setStringValue: nsstring_id(val)];
-        tf
-    };
-
-    // Language selector
-    let label_lang = mk_label(20.0, h - 40.0, tr_key("Language", es).as_ref());
-    let popup_lang: id = msg_send![get_class("NSPopUpButton"), alloc];
-    let popup_lang: id = msg_send![
-        popup_lang,
-        initWithFrame: NSRect::new(NSPoint::new(160.0, h - 44.0), NSSize::new(160.0, 24.0))
-    ];
-    let _: () =
-        msg_send![popup_lang, addItemWithTitle: nsstring_id(tr_key("English", es).as_ref())];
-    let _: () =
-        msg_send![popup_lang, addItemWithTitle: nsstring_id(tr_key("Spanish", es).as_ref())];
-    let _: () = msg_send![popup_lang, selectItemAtIndex: (if cur_lang == 1 { 1i64 } else { 0i64 })];
-    let _: () = msg_send![popup_lang, setTarget: view];
-    let _: () = msg_send![popup_lang, setAction: sel!(langChanged:)];
-
-    // Static labels
-    let label_radius = mk_label(20.0, h - 80.0, tr_key("Radius (px)", es).as_ref());
-    let label_border = mk_label(20.0, h - 130.0, tr_key("Border (px)", es).as_ref());
-    let label_color = mk_label(20.0, h - 180.0, tr_key("Color", es).as_ref());
-    let label_hex = mk_label(220.0, h - 180.0, tr_key("Hex", es).as_ref());
-    let _: () = msg_send![label_hex, sizeToFit];
-    let label_fill_t = mk_label(
-        20.0,
-        h - 230.0,
-        tr_key("Fill Transparency (%)", es).as_ref(),
-    );
-
-    // Value labels (non-interactive) + sliders (NO tick marks, still snapping in code)
-    // Radius
-    let field_radius = mk_value_label(160.0, h - 84.0, 60.0, 24.0, &format!("{:.0}", radius));
-    let slider_radius: id = msg_send![get_class("NSSlider"), alloc];
-    let slider_radius: id = msg_send![
-        slider_radius,
-        initWithFrame: NSRect::new(NSPoint::new(230.0, h - 85.0), NSSize::new(260.0, 24.0))
-    ];
-    let _: () = msg_send![slider_radius, setMinValue: 5.0f64];
-    let _: () = msg_send![slider_radius, setMaxValue: 200.0f64];
-    let _: () = msg_send![slider_radius, setDoubleValue: radius];
-    let _: () = msg_send![slider_radius, setTarget: view];
-    let _: () = msg_send![slider_radius, setAction: sel!(setRadius:)];
-    let _: () = msg_send![slider_radius, setContinuous: YES];
-
-    // Border
-    let field_border = mk_value_label(160.0, h - 134.0, 60.0, 24.0, &format!("{:.0}", border));
-    let slider_border: id = msg_send![get_class("NSSlider"), alloc];
-    let slider_border: id = msg_send![
-        slider_border,
-        initWithFrame: NSRect::new(NSPoint::new(230.0, h - 135.0), NSSize::new(260.0, 24.0))
-    ];
-    let _: () = msg_send![slider_border, setMinValue: 1.0f64];
-    let _: () = msg_send![slider_border, setMaxValue: 20.0f64];
-    let _: () = msg_send![slider_border, setDoubleValue: border];
-    let _: () = msg_send![slider_border, setTarget: view];
-    let _: () = msg_send![slider_border, setAction: sel!(setBorderWidth:)];
-    let _: () = msg_send![slider_border, setContinuous: YES];
-
-    // Colour + Hex (Hex remains editable)
-    let color_well: id = msg_send![get_class("NSColorWell"), alloc];
-    let color_well: id = msg_send![
-        color_well,
-        initWithFrame: NSRect::new(NSPoint::new(160.0, h - 185.0), NSSize::new(50.0, 25.0))
-    ];
-    let ns_color = get_class("NSColor");
-    let current_color: id =
-        msg_send![ns_color, colorWithCalibratedRed: r, green: g, blue: b, alpha: a];
-    let _: () = msg_send![color_well, setColor: current_color];
-    let _: () = msg_send![color_well, setTarget: view];
-    let _: () = msg_send![color_well, setAction: sel!(colorChanged:)];
-
-    let hex_str = color_to_hex(r, g, b, a);
-    // Place Hex field right after "Hex" label
-    let label_hex_frame: NSRect = msg_send![label_hex, frame];
-    let padding: f64 = 8.0;
-    let right_margin: f64 = 175.0;
-    let field_x = label_hex_frame.origin.x + label_hex_frame.size.width + padding;
-    let field_w = (w - right_margin) - field_x;
-
-    let field_hex: id = msg_send![get_class("NSTextField"), alloc];
-    let field_hex: id = msg_send![
-        field_hex,
-        initWithFrame: NSRect::new(NSPoint::new(field_x, h - 185.0), NSSize::new(field_w, 24.0))
-    ];
-    let _: () = msg_send![field_hex, setStringValue: nsstring_id(&hex_str)];
-    configure_hex_field(view, field_hex);
-
-    // Fill transparency
-    let field_fill_t = mk_value_label(160.0, h - 234.0, 60.0, 24.0, &format!("{:.0}", fill_t));
-    let slider_fill_t: id = msg_send![get_class("NSSlider"), alloc];
-    let slider_fill_t: id = msg_send![
-        slider_fill_t,
-        initWithFrame: NSRect::new(NSPoint::new(230.0, h - 235.0), NSSize::new(260.0, 24.0))
-    ];
-    let _: () = msg_send![slider_fill_t, setMinValue: 0.0f64];
-    let _: () = msg_send![slider_fill_t, setMaxValue: 100.0f64];
-    let _: () = msg_send![slider_fill_t, setDoubleValue: fill_t];
-    let _: () = msg_send![slider_fill_t, setTarget: view];
-    let _: () = msg_send![slider_fill_t, setAction: sel!(setFillTransparency:)];
-    let _: () = msg_send![slider_fill_t, setContinuous: YES];
-
-    // Close button
-    let btn_close: id = msg_send![get_class("NSButton"), alloc];
-    let btn_close: id = msg_send![
-        btn_close,
-        initWithFrame: NSRect::new(NSPoint::new(w - 100.0, 15.0), NSSize::new(80.0, 28.0))
-    ];
-    let _: () = msg_send![btn_close, setTitle: nsstring_id(tr_key("Close", es).as_ref())];
-    let _: () = msg_send![btn_close, setTarget: view];
-    let _: () = msg_send![btn_close, setAction: sel!(closeSettings:)];
-
-    // Enter/Return activates "Close"
-    let _: () = msg_send![btn_close, setKeyEquivalent: nsstring_id("\r")];
-    let cell: id = msg_send![btn_close, cell];
-    let _: () = msg_send![settings, setDefaultButtonCell: cell];
-
-    // Add subviews
-    let _: () = msg_send![content, addSubview: label_lang];
-    let _: () = msg_send![content, addSubview: popup_lang];
-
-    let _: () = msg_send![content, addSubview: label_radius];
-    let _: () = msg_send![content, addSubview: field_radius];
-    let _: () = msg_send![content, addSubview: slider_radius];
-
-    let _: () = msg_send![content, addSubview: label_border];
-    let _: () = msg_send![content, addSubview: field_border];
-    let _: () = msg_send![content, addSubview: slider_border];
-
-    let _: () = msg_send![content, addSubview: label_color];
-    let _: () = msg_send![content, addSubview: color_well];
-    let _: () = msg_send![content, addSubview: label_hex];
-    let _: () = msg_send![content, addSubview: field_hex];
-
-    let _: () = msg_send![content, addSubview: label_fill_t];
-    let _: () = msg_send![content, addSubview: field_fill_t];
-    let _: () = msg_send![content, addSubview: slider_fill_t];
-
-    let _: () = msg_send![content, addSubview: btn_close];
-
-    // Save refs for later sync
-    (*view).store_ivar::<id>("_settingsWindow", settings);
-    (*view).store_ivar::<id>("_labelLang", label_lang);
-    (*view).store_ivar::<id>("_popupLang", popup_lang);
-
-    (*view).store_ivar::<id>("_labelRadius", label_radius);
-    (*view).store_ivar::<id>("_fieldRadius", field_radius); // label
-    (*view).store_ivar::<id>("_sliderRadius", slider_radius);
-
-    (*view).store_ivar::<id>("_labelBorder", label_border);
-    (*view).store_ivar::<id>("_fieldBorder", field_border); // label
-    (*view).store_ivar::<id>("_sliderBorder", slider_border);
-
-    (*view).store_ivar::<id>("_labelColor", label_color);
-    (*view).store_ivar::<id>("_colorWell", color_well);
-
-    (*view).store_ivar::<id>("_labelHex", label_hex);
-    (*view).store_ivar::<id>("_fieldHex", field_hex); // editable
-
-    (*view).store_ivar::<id>("_labelFillT", label_fill_t);
-    (*view).store_ivar::<id>("_fieldFillT", field_fill_t); // label
-    (*view).store_ivar::<id>("_sliderFillT", slider_fill_t);
-
-    (*view).store_ivar::<id>("_btnClose", btn_close);
-
-    // Local monitor for ESC/Enter to close modal
-    const KEY_DOWN_MASK: u64 = 1 << 10;
-    let key_block = RcBlock::new(move |event: id| -> id {
-        unsafe {
-            let keycode: u16 = msg_send![event, keyCode];
-            if keycode == 53 || keycode == 36 {
-                // 53 = Escape, 36 = Return/Enter - stop modal
-                let app: id = NSApp();
-                let _: () = msg_send![app, stopModal];
-                return nil;
-            }
-        }
-        event
-    });
-    let key_mon: id = msg_send![
-        get_class("NSEvent"),
-        addLocalMonitorForEventsMatchingMask: KEY_DOWN_MASK,
-        handler: &*key_block
-    ];
-
-    // Force activation and show window, then run modal
-    let app: id = NSApp();
-    let _: () = msg_send![app, activateIgnoringOtherApps: YES];
-    let _: () = msg_send![settings, makeKeyAndOrderFront: nil];
-
-    let _modal_result: i64 = msg_send![app, runModalForWindow: settings];
-
-    // Modal ended - clean up
-    let _: () = msg_send![get_class("NSEvent"), removeMonitor: key_mon];
-    let _: () = msg_send![settings, orderOut: nil];
-
-    // Clear stored references
-    (*view).store_ivar::<id>("_settingsWindow", nil);
-    (*view).store_ivar::<id>("_settingsEscMonitor", nil);
-    (*view).store_ivar::<id>("_settingsGlobalMonitor", nil);
-
-    // Restore overlay if it was enabled
-    if was_enabled {
+/// Re-enable the overlay if `enabled`, and bring the overlay windows back
+/// in front.
+unsafe fn restore_overlays(enabled: bool) {
+    if enabled {
         apply_to_all_views(|v| {
             set_bool_ivar(v, "_overlayEnabled", true);
         });
@@ -362,20 +336,4 @@ pub unsafe fn open_settings_window(view: id) {
             waitUntilDone: NO
         ];
     });
-
-    // Reset atomic guard
-    SETTINGS_OPENING.store(false, Ordering::SeqCst);
-
-    // Publish event - dispatcher will handle hotkey reinstallation
-    publish(AppEvent::SettingsClosed);
-}
-
-/// Close the settings window by stopping the modal.
-///
-/// # Safety
-/// Must be called from main thread with valid autorelease pool.
-pub unsafe fn close_settings_window(_view: id) {
-    // Just stop the modal - cleanup happens in open_settings_window after runModalForWindow returns
-    let app: id = NSApp();
-    let _: () = msg_send![app, stopModal];
 }
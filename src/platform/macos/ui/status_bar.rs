@@ -1,21 +1,40 @@
 //! Status bar (menu bar) item with dropdown menu.
 //!
-//! Creates a clickable icon in the macOS menu bar with options:
-//! - Settings (Ajustes)
+//! Creates a clickable icon in the macOS menu bar; a click opens a menu
+//! with the options below, an Option-click toggles the highlight instead:
+//! - Update available (only after a check found a newer version)
+//! - Settings (Ajustes), hidden while the presentation lock is on
+//! - Radius and Profile submenus (quick size and look changes), hidden
+//!   while the presentation lock is on
 //! - Help (Ayuda)
+//! - Lock settings (Bloquear ajustes)
+//! - Hotkeys submenu (one checked item per hotkey profile)
+//! - Record clicks / Export clicks (CSV, JSON)
+//! - Check for updates / Check for updates automatically
 //! - About (Acerca de...)
+//! - Restart (Reiniciar)
 //! - Quit (Salir)
 
 use std::sync::atomic::{AtomicPtr, Ordering};
 
-use objc2::runtime::AnyObject;
+use objc2::runtime::{AnyObject, Sel};
 
 use crate::platform::macos::ffi::bridge::{
-    get_class, id, msg_send, nil, nsstring_id, sel, NSSize, YES,
+    get_bool_ivar, get_class, id, msg_send, nil, nsstring_id, sel, NSSize, NO, YES,
 };
 
-use crate::platform::macos::app::lang_is_es;
-use crate::tr_key;
+use crate::i18n::{tr, tr_args};
+use crate::model::constants::{PREF_HOTKEY_PROFILE, PREF_UPDATE_CHECK, QUICK_RADII};
+use crate::model::hotkeys::{MOD_ALT, MOD_CMD, MOD_CTRL, MOD_SHIFT};
+use crate::model::{
+    current_look_profile, hotkey_for, is_current_radius, look_profile_label, profile_label,
+    radius_label, resolve_profile, Hotkey, HotkeyAction, Key, TrayIconState, HOTKEY_PROFILES,
+    HOTKEY_PROFILE_STANDARD, LOOK_PROFILES,
+};
+use crate::platform::macos::app::available_update;
+use crate::platform::macos::handlers::presentation_locked;
+use crate::platform::macos::input::{click_recording, current_hotkey_bindings};
+use crate::platform::macos::storage::{load_state, prefs_get_int, prefs_get_string};
 
 /// Global reference to the status item (must be kept alive).
 static STATUS_ITEM: AtomicPtr<AnyObject> = AtomicPtr::new(std::ptr::null_mut());
@@ -65,51 +84,274 @@ pub unsafe fn install_status_bar(view: id) {
     // Create menu
     let menu = create_status_menu(view);
     let _: () = msg_send![status_item, setMenu: menu];
+    update_status_bar_state(view);
+}
+
+/// Show the state of the highlight on the status item: the icon is
+/// dimmed while the highlight is hidden, and the tooltip tells whether it
+/// is shown and whether the settings are locked (see `model::tray_icon`).
+///
+/// Called whenever the highlight is toggled and the menu is rebuilt.
+///
+/// # Safety
+/// - `view` must be a valid, non-null pointer to a CustomViewMulti.
+/// - Must be called from main thread with valid autorelease pool.
+pub unsafe fn update_status_bar_state(view: id) {
+    let item = STATUS_ITEM.load(Ordering::Acquire);
+    if item.is_null() {
+        return;
+    }
+    let button: id = msg_send![item, button];
+    if button == nil {
+        return;
+    }
+    let state = TrayIconState {
+        visible: get_bool_ivar(view, "_overlayEnabled"),
+        locked: presentation_locked(),
+    };
+    let _: () = msg_send![button, setAppearsDisabled: if state.visible { NO } else { YES }];
+    let mut tip = format!("Lumbus - {}", tr(state.label()));
+    if state.locked {
+        tip = format!("{tip} ({})", tr("Settings locked"));
+    }
+    let _: () = msg_send![button, setToolTip: nsstring_id(&tip)];
+}
+
+/// NSMenuItem key equivalent and modifier mask showing a hotkey.
+fn key_equivalent(hotkey: Option<Hotkey>) -> (String, u64) {
+    let Some(hotkey) = hotkey else {
+        return (String::new(), 0);
+    };
+    let key = match hotkey.key {
+        Key::Letter(c) => c.to_string(),
+        Key::Comma => ",".to_string(),
+        Key::Equal => "=".to_string(),
+        Key::Minus => "-".to_string(),
+        // NSF1FunctionKey = 0xF704
+        Key::F(n) => char::from_u32(0xF703 + n as u32)
+            .map(String::from)
+            .unwrap_or_default(),
+    };
+    // NSEventModifierFlagShift (1 << 17), Control (1 << 18), Option (1 << 19), Command (1 << 20)
+    let mask = [
+        (MOD_SHIFT, 1u64 << 17),
+        (MOD_CTRL, 1u64 << 18),
+        (MOD_ALT, 1u64 << 19),
+        (MOD_CMD, 1u64 << 20),
+    ]
+    .iter()
+    .filter(|(m, _)| hotkey.modifiers & m != 0)
+    .fold(0, |mask, (_, flag)| mask | flag);
+    (key, mask)
+}
+
+/// Item opening a submenu called `title`, with one (title, checked) entry
+/// per item; entry `i` sends `action` to `view` with the tag `i`.
+unsafe fn submenu_item(
+    title: &str,
+    action: Sel,
+    view: id,
+    entries: impl Iterator<Item = (String, bool)>,
+) -> id {
+    let submenu: id = msg_send![get_class("NSMenu"), alloc];
+    let submenu: id = msg_send![submenu, init];
+    for (i, (entry, checked)) in entries.enumerate() {
+        let item: id = msg_send![get_class("NSMenuItem"), alloc];
+        let item: id = msg_send![
+            item,
+            initWithTitle: nsstring_id(&entry),
+            action: action,
+            keyEquivalent: nsstring_id("")
+        ];
+        let _: () = msg_send![item, setTag: i as isize];
+        let _: () = msg_send![item, setState: if checked { 1isize } else { 0isize }];
+        let _: () = msg_send![item, setTarget: view];
+        let _: () = msg_send![submenu, addItem: item];
+    }
+    let item: id = msg_send![get_class("NSMenuItem"), alloc];
+    let item: id = msg_send![
+        item,
+        initWithTitle: nsstring_id(title),
+        action: None::<Sel>,
+        keyEquivalent: nsstring_id("")
+    ];
+    let _: () = msg_send![item, setSubmenu: submenu];
+    item
 }
 
 /// Create the dropdown menu for the status bar item.
 unsafe fn create_status_menu(view: id) -> id {
-    let es = lang_is_es(view);
+    let locked = presentation_locked();
+    let bindings = current_hotkey_bindings();
 
     let menu: id = msg_send![get_class("NSMenu"), alloc];
     let menu: id = msg_send![menu, init];
+    // The view's menuWillOpen: turns an Option-click into a toggle
+    let _: () = msg_send![menu, setDelegate: view];
+
+    // Newer version found by the last update check: opens its download page
+    if let Some(release) = available_update() {
+        let title = tr_args("update-available", &[("version", &release.version)])
+            .unwrap_or_else(|| release.version.clone());
+        let update_item: id = msg_send![get_class("NSMenuItem"), alloc];
+        let update_item: id = msg_send![
+            update_item,
+            initWithTitle: nsstring_id(&title),
+            action: sel!(statusBarUpdate:),
+            keyEquivalent: nsstring_id("")
+        ];
+        let _: () = msg_send![update_item, setTarget: view];
+        let _: () = msg_send![menu, addItem: update_item];
+        let separator_update: id = msg_send![get_class("NSMenuItem"), separatorItem];
+        let _: () = msg_send![menu, addItem: separator_update];
+    }
 
     // Settings item
-    let settings_title = tr_key("Settings", es);
+    let settings_title = tr("Settings");
+    let (settings_key, settings_mask) =
+        key_equivalent(hotkey_for(bindings, HotkeyAction::Settings));
     let settings_item: id = msg_send![get_class("NSMenuItem"), alloc];
     let settings_item: id = msg_send![
         settings_item,
         initWithTitle: nsstring_id(&settings_title),
         action: sel!(statusBarSettings:),
-        keyEquivalent: nsstring_id(",")
+        keyEquivalent: nsstring_id(&settings_key)
     ];
+    let _: () = msg_send![settings_item, setKeyEquivalentModifierMask: settings_mask];
     let _: () = msg_send![settings_item, setTarget: view];
+    let _: () = msg_send![settings_item, setHidden: if locked { YES } else { NO }];
     let _: () = msg_send![menu, addItem: settings_item];
 
-    // Help item (Cmd+Shift+H)
-    let help_title = tr_key("Help", es);
+    // Radius and Profile submenus: quick changes, the current ones checked
+    let state = load_state();
+    let radii = QUICK_RADII
+        .iter()
+        .map(|&r| (radius_label(r), is_current_radius(state.radius, r)));
+    let radius_item = submenu_item(&tr("Radius"), sel!(statusBarRadius:), view, radii);
+    let look = current_look_profile(&state);
+    let looks = LOOK_PROFILES
+        .iter()
+        .map(|name| (tr(look_profile_label(name)), look == Some(*name)));
+    let look_item = submenu_item(&tr("Profile"), sel!(statusBarLookProfile:), view, looks);
+    for item in [radius_item, look_item] {
+        let _: () = msg_send![item, setHidden: if locked { YES } else { NO }];
+        let _: () = msg_send![menu, addItem: item];
+    }
+
+    // Help item
+    let help_title = tr("Help");
+    let (help_key, help_mask) = key_equivalent(hotkey_for(bindings, HotkeyAction::Help));
     let help_item: id = msg_send![get_class("NSMenuItem"), alloc];
     let help_item: id = msg_send![
         help_item,
         initWithTitle: nsstring_id(&help_title),
         action: sel!(statusBarHelp:),
-        keyEquivalent: nsstring_id("H")
+        keyEquivalent: nsstring_id(&help_key)
     ];
-    // NSEventModifierFlagCommand (1 << 20) + NSEventModifierFlagShift (1 << 17)
-    let _: () = msg_send![help_item, setKeyEquivalentModifierMask: (1u64 << 20) | (1u64 << 17)];
+    let _: () = msg_send![help_item, setKeyEquivalentModifierMask: help_mask];
     let _: () = msg_send![help_item, setTarget: view];
     let _: () = msg_send![menu, addItem: help_item];
 
+    // Presentation lock item, checked while locked
+    let lock_title = tr("Lock settings");
+    let (lock_key, lock_mask) = key_equivalent(hotkey_for(bindings, HotkeyAction::Lock));
+    let lock_item: id = msg_send![get_class("NSMenuItem"), alloc];
+    let lock_item: id = msg_send![
+        lock_item,
+        initWithTitle: nsstring_id(&lock_title),
+        action: sel!(statusBarLock:),
+        keyEquivalent: nsstring_id(&lock_key)
+    ];
+    let _: () = msg_send![lock_item, setKeyEquivalentModifierMask: lock_mask];
+    let _: () = msg_send![lock_item, setState: if locked { 1isize } else { 0isize }];
+    let _: () = msg_send![lock_item, setTarget: view];
+    let _: () = msg_send![menu, addItem: lock_item];
+
+    // Hotkeys submenu: one item per profile, the selected one checked
+    let selected = resolve_profile(&prefs_get_string(
+        PREF_HOTKEY_PROFILE,
+        HOTKEY_PROFILE_STANDARD,
+    ));
+    let profiles = HOTKEY_PROFILES
+        .iter()
+        .map(|profile| (tr(profile_label(profile)), *profile == selected));
+    let hotkeys_item = submenu_item(
+        &tr("Hotkeys"),
+        sel!(statusBarHotkeyProfile:),
+        view,
+        profiles,
+    );
+    let _: () = msg_send![menu, addItem: hotkeys_item];
+
+    // Separator
+    let separator_log: id = msg_send![get_class("NSMenuItem"), separatorItem];
+    let _: () = msg_send![menu, addItem: separator_log];
+
+    // Click log: recording toggle (checked while recording) + exports
+    let record_title = tr("Record clicks");
+    let record_item: id = msg_send![get_class("NSMenuItem"), alloc];
+    let record_item: id = msg_send![
+        record_item,
+        initWithTitle: nsstring_id(&record_title),
+        action: sel!(statusBarRecordClicks:),
+        keyEquivalent: nsstring_id("")
+    ];
+    let recording = click_recording();
+    let _: () = msg_send![record_item, setState: if recording { 1isize } else { 0isize }];
+    let _: () = msg_send![record_item, setTarget: view];
+    let _: () = msg_send![menu, addItem: record_item];
+
+    for (key, action) in [
+        ("Export clicks (CSV)...", sel!(statusBarExportCsv:)),
+        ("Export clicks (JSON)...", sel!(statusBarExportJson:)),
+    ] {
+        let title = tr(key);
+        let item: id = msg_send![get_class("NSMenuItem"), alloc];
+        let item: id = msg_send![
+            item,
+            initWithTitle: nsstring_id(&title),
+            action: action,
+            keyEquivalent: nsstring_id("")
+        ];
+        let _: () = msg_send![item, setTarget: view];
+        let _: () = msg_send![menu, addItem: item];
+    }
+
     // Separator
     let separator: id = msg_send![get_class("NSMenuItem"), separatorItem];
     let _: () = msg_send![menu, addItem: separator];
 
+    // Update check: check now + daily check toggle (checked while on)
+    let check_title = tr("Check for updates");
+    let check_item: id = msg_send![get_class("NSMenuItem"), alloc];
+    let check_item: id = msg_send![
+        check_item,
+        initWithTitle: nsstring_id(&check_title),
+        action: sel!(statusBarCheckUpdates:),
+        keyEquivalent: nsstring_id("")
+    ];
+    let _: () = msg_send![check_item, setTarget: view];
+    let _: () = msg_send![menu, addItem: check_item];
+
+    let auto_title = tr("Check for updates automatically");
+    let auto_item: id = msg_send![get_class("NSMenuItem"), alloc];
+    let auto_item: id = msg_send![
+        auto_item,
+        initWithTitle: nsstring_id(&auto_title),
+        action: sel!(statusBarAutoUpdate:),
+        keyEquivalent: nsstring_id("")
+    ];
+    let auto_check = prefs_get_int(PREF_UPDATE_CHECK, 1) != 0;
+    let _: () = msg_send![auto_item, setState: if auto_check { 1isize } else { 0isize }];
+    let _: () = msg_send![auto_item, setTarget: view];
+    let _: () = msg_send![menu, addItem: auto_item];
+
     // About item
-    let about_title = if es { "Acerca de..." } else { "About..." };
+    let about_title = tr("About...");
     let about_item: id = msg_send![get_class("NSMenuItem"), alloc];
     let about_item: id = msg_send![
         about_item,
-        initWithTitle: nsstring_id(about_title),
+        initWithTitle: nsstring_id(&about_title),
         action: sel!(statusBarAbout:),
         keyEquivalent: nsstring_id("")
     ];
@@ -120,8 +362,20 @@ unsafe fn create_status_menu(view: id) -> id {
     let separator2: id = msg_send![get_class("NSMenuItem"), separatorItem];
     let _: () = msg_send![menu, addItem: separator2];
 
-    // Quit item (no shortcut - direct quit without confirmation)
-    let quit_title = tr_key("Quit", es);
+    // Restart item (quits and starts again, no confirmation)
+    let restart_title = tr("Restart");
+    let restart_item: id = msg_send![get_class("NSMenuItem"), alloc];
+    let restart_item: id = msg_send![
+        restart_item,
+        initWithTitle: nsstring_id(&restart_title),
+        action: sel!(statusBarRestart:),
+        keyEquivalent: nsstring_id("")
+    ];
+    let _: () = msg_send![restart_item, setTarget: view];
+    let _: () = msg_send![menu, addItem: restart_item];
+
+    // Quit item (no shortcut; asks first if confirm_quit is on)
+    let quit_title = tr("Quit");
     let quit_item: id = msg_send![get_class("NSMenuItem"), alloc];
     let quit_item: id = msg_send![
         quit_item,
@@ -137,7 +391,9 @@ unsafe fn create_status_menu(view: id) -> id {
 
 /// Update the status bar menu language.
 ///
-/// Call this when the language changes in settings.
+/// Call this when the language changes in settings, the presentation
+/// lock is toggled or the hotkey profile changes (the menu is rebuilt
+/// from scratch, and the status item's state refreshed).
 ///
 /// # Safety
 /// - `view` must be a valid, non-null pointer to a CustomViewMulti.
@@ -150,4 +406,5 @@ pub unsafe fn update_status_bar_language(view: id) {
 
     let menu = create_status_menu(view);
     let _: () = msg_send![item, setMenu: menu];
+    update_status_bar_state(view);
 }
@@ -0,0 +1,170 @@
+//! `OverlayBackend` over the layered overlay window and `config.toml`.
+
+use tracing::info;
+use windows::Win32::Foundation::{HWND, POINT};
+use windows::Win32::UI::WindowsAndMessaging::GetCursorPos;
+
+use crate::backend::OverlayBackend;
+use crate::model::animation::wall_clock_secs;
+use crate::model::{DisplayMode, OverlayState, Ruler};
+use crate::platform::windows::app::displays::{apply_capture_exclusion, fit_overlay_to_displays};
+use crate::platform::windows::app::state::{reload_settings_from_config, STATE};
+use crate::platform::windows::ffi::system_locale;
+use crate::platform::windows::input::{
+    register_hotkeys, register_ruler_escape, unregister_hotkeys, unregister_ruler_escape,
+};
+use crate::platform::windows::storage::config;
+use crate::platform::windows::ui::overlay::update_overlay;
+use crate::platform::windows::ui::tray;
+
+/// The overlay and tray of the main window `hwnd`.
+pub struct WindowsBackend {
+    hwnd: HWND,
+}
+
+impl WindowsBackend {
+    /// Backend over the main window, which owns the hotkeys and the tray icon.
+    pub fn new(hwnd: HWND) -> Self {
+        Self { hwnd }
+    }
+}
+
+impl OverlayBackend for WindowsBackend {
+    fn load_state(&self) -> OverlayState {
+        config::load_state()
+    }
+
+    fn set_double(&mut self, key: &str, value: f64) {
+        config::prefs_set_double(key, value);
+    }
+
+    fn set_string(&mut self, key: &str, value: &str) {
+        config::prefs_set_string(key, value);
+    }
+
+    fn flush(&mut self) {
+        config::flush_config();
+    }
+
+    /// Re-reads the whole config, frame colors included.
+    fn apply_state(&mut self, _state: &OverlayState) {
+        reload_settings_from_config();
+    }
+
+    /// The one overlay window spans every monitor.
+    fn sync_windows(&mut self) -> usize {
+        fit_overlay_to_displays();
+        1
+    }
+
+    fn apply_capture_exclusion(&mut self) {
+        apply_capture_exclusion(self.hwnd);
+    }
+
+    fn cursor_position(&self) -> (f64, f64) {
+        let mut cursor = POINT::default();
+        unsafe {
+            let _ = GetCursorPos(&mut cursor);
+        }
+        (cursor.x as f64, cursor.y as f64)
+    }
+
+    fn toggle_overlay(&mut self) {
+        let visible = STATE.with(|s| {
+            let mut state = s.borrow_mut();
+            state.overlay.overlay_enabled = !state.overlay.overlay_enabled;
+            state.overlay.overlay_enabled
+        });
+        info!(
+            "Toggle: overlay {}",
+            if visible { "visible" } else { "hidden" }
+        );
+        tray::update_tray_state();
+    }
+
+    fn toggle_coordinates(&mut self) {
+        STATE.with(|s| {
+            let mut state = s.borrow_mut();
+            state.show_coordinates = !state.show_coordinates;
+            state.dirty = true;
+        });
+    }
+
+    fn toggle_color_sampler(&mut self) {
+        STATE.with(|s| {
+            let mut state = s.borrow_mut();
+            state.color_sampler = !state.color_sampler;
+            state.dirty = true;
+        });
+    }
+
+    /// Esc is a global hotkey only while the mode is on. The mode does
+    /// not start while the overlay is hidden: its clicks would vanish
+    /// without a trace.
+    fn toggle_ruler(&mut self) {
+        let on = STATE.with(|s| {
+            let mut state = s.borrow_mut();
+            state.ruler = match state.ruler {
+                None if state.overlay.overlay_enabled => Some(Ruler::new()),
+                _ => None,
+            };
+            state.dirty = true;
+            state.ruler.is_some()
+        });
+        if on {
+            register_ruler_escape(self.hwnd);
+        } else {
+            unregister_ruler_escape(self.hwnd);
+        }
+    }
+
+    fn clear_ruler(&mut self) {
+        STATE.with(|s| {
+            let mut state = s.borrow_mut();
+            state.ruler = None;
+            state.dirty = true;
+        });
+        unregister_ruler_escape(self.hwnd);
+    }
+
+    fn toggle_grid(&mut self) {
+        STATE.with(|s| {
+            let mut state = s.borrow_mut();
+            state.show_grid = !state.show_grid;
+            state.dirty = true;
+        });
+    }
+
+    fn show_display_mode(&mut self, mode: DisplayMode) {
+        STATE.with(|s| {
+            let mut state = s.borrow_mut();
+            let now = wall_clock_secs();
+            match mode {
+                DisplayMode::Circle => state.click_hold.reset(),
+                button => {
+                    state.click_hold.press(button.code(), now);
+                    state.click_hold.release(now);
+                }
+            }
+            state.overlay.display_mode = state.click_hold.mode(now, state.overlay.click_hold_ms);
+            state.dirty = true;
+        });
+    }
+
+    fn reinstall_hotkeys(&mut self) {
+        unregister_hotkeys(self.hwnd);
+        register_hotkeys(self.hwnd);
+    }
+
+    fn refresh_menus(&mut self) {
+        tray::update_tray_language();
+    }
+
+    fn redraw(&mut self) {
+        update_overlay();
+    }
+
+    fn system_locale(&self) -> Option<String> {
+        system_locale()
+    }
+}
@@ -0,0 +1,15 @@
+//! Crash reports for Windows.
+//!
+//! Lumbus runs from the tray without a console, so a panic used to end it
+//! without a trace. The panic hook writes a report to
+//! `%LOCALAPPDATA%\Lumbus\Logs\Crashes` and offers to reveal it.
+
+use crate::logging::install_panic_hook;
+use crate::platform::windows::ffi::os_version;
+use crate::platform::windows::storage::{config_path, log_dir};
+use crate::platform::windows::ui::dialogs::show_crash_report;
+
+/// Install the panic hook writing crash reports.
+pub fn install_crash_handler() {
+    install_panic_hook(log_dir(), os_version(), config_path(), show_crash_report);
+}
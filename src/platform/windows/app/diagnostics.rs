@@ -0,0 +1,102 @@
+//! `--diagnostics` report for Windows.
+//!
+//! Gathers the monitors, process checks and hotkeys the app sees into a
+//! `model::DiagnosticsReport`, and turns on the overlay's debug label when
+//! asked to. The app has no console, so the saved file is the report to
+//! attach. At quit it is saved again, with the event latency of the
+//! session.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tracing::info;
+use windows::Win32::UI::Shell::IsUserAnAdmin;
+
+use crate::events::event_latency;
+use crate::logging::write_diagnostics;
+use crate::model::constants::PREF_HOTKEY_PROFILE;
+use crate::model::{DiagnosticsConfig, DiagnosticsReport, FrameTimer, HOTKEY_PROFILE_STANDARD};
+use crate::platform::windows::app::state::STATE;
+use crate::platform::windows::ffi::{monitors, os_version};
+use crate::platform::windows::input::{current_hotkey_bindings, hotkey_registered, MOUSE_HOOK};
+use crate::platform::windows::storage::{config_path, log_dir, prefs_get_string};
+
+/// Set by [`run_diagnostics`]: save the report again at quit.
+static DIAGNOSTICS_RUN: AtomicBool = AtomicBool::new(false);
+
+/// Name, events taken, mean and longest wait (ms) of each kind of event.
+fn latency_rows() -> Vec<(String, u64, f64, f64)> {
+    event_latency()
+        .iter()
+        .map(|l| {
+            (
+                l.name.to_string(),
+                l.count,
+                l.mean().as_secs_f64() * 1000.0,
+                l.max.as_secs_f64() * 1000.0,
+            )
+        })
+        .collect()
+}
+
+/// Collect the diagnostics report.
+pub fn diagnostics_report() -> DiagnosticsReport {
+    let reinstalls = STATE.with(|s| s.borrow().hook_watchdog.reinstalls());
+    let hook = match (MOUSE_HOOK.load(Ordering::SeqCst) != 0, reinstalls) {
+        (false, _) => "NOT installed".to_string(),
+        (true, 0) => "installed".to_string(),
+        (true, n) => format!("installed (reinstalled {} times)", n),
+    };
+    let admin = if unsafe { IsUserAnAdmin() }.as_bool() {
+        "yes"
+    } else {
+        "no"
+    };
+
+    DiagnosticsReport {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        os: os_version(),
+        displays: monitors(),
+        permissions: vec![
+            ("Mouse hook".into(), hook),
+            ("Administrator".into(), admin.into()),
+        ],
+        hotkey_profile: prefs_get_string(PREF_HOTKEY_PROFILE, HOTKEY_PROFILE_STANDARD),
+        hotkeys: current_hotkey_bindings()
+            .iter()
+            .map(|b| {
+                (
+                    b.hotkey.label(),
+                    b.action.description().to_string(),
+                    hotkey_registered(b.action),
+                )
+            })
+            .collect(),
+        event_latency: latency_rows(),
+        config_path: config_path().display().to_string(),
+        log_dir: log_dir().display().to_string(),
+    }
+}
+
+/// Print and save the diagnostics report, and draw the debug label if
+/// `config` asks for it.
+pub fn run_diagnostics(config: DiagnosticsConfig) {
+    write_diagnostics(&diagnostics_report(), &log_dir());
+    DIAGNOSTICS_RUN.store(true, Ordering::SeqCst);
+    if config.overlay {
+        STATE.with(|s| s.borrow_mut().diagnostics = Some(FrameTimer::new()));
+    }
+}
+
+/// Log the event latency of the session, and save the diagnostics report
+/// again if `--diagnostics` was given. Called at quit.
+pub fn finish_diagnostics() {
+    for (name, count, mean_ms, max_ms) in latency_rows() {
+        info!(
+            "Event {}: {} taken, mean {:.1} ms, max {:.1} ms",
+            name, count, mean_ms, max_ms
+        );
+    }
+    if DIAGNOSTICS_RUN.load(Ordering::SeqCst) {
+        write_diagnostics(&diagnostics_report(), &log_dir());
+    }
+}
@@ -0,0 +1,60 @@
+//! Overlay geometry across display changes.
+//!
+//! The overlay is a single window spanning the virtual screen (every
+//! monitor). Windows sends `WM_DISPLAYCHANGE` when a monitor is connected,
+//! disconnected, rearranged or changes resolution; the overlay is then
+//! refitted to the new virtual screen.
+
+use tracing::warn;
+use windows::Win32::Foundation::HWND;
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetSystemMetrics, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN,
+};
+
+use crate::model::constants::PREF_EXCLUDE_FROM_CAPTURE;
+use crate::platform::windows::app::state::STATE;
+use crate::platform::windows::ffi::set_excluded_from_capture;
+use crate::platform::windows::storage::config;
+
+/// Bounds of the overlay window: x, y, width, height.
+///
+/// The virtual screen (all monitors), one pixel short in each direction to
+/// prevent Windows from detecting this as a "fullscreen app" and
+/// activating Focus Assist.
+pub fn virtual_screen_rect() -> (i32, i32, i32, i32) {
+    unsafe {
+        (
+            GetSystemMetrics(SM_XVIRTUALSCREEN),
+            GetSystemMetrics(SM_YVIRTUALSCREEN),
+            GetSystemMetrics(SM_CXVIRTUALSCREEN) - 1,
+            GetSystemMetrics(SM_CYVIRTUALSCREEN) - 1,
+        )
+    }
+}
+
+/// Refit the overlay to the virtual screen after a display change.
+///
+/// The window itself is moved and resized by the next `update_overlay`,
+/// which positions it with `UpdateLayeredWindow`.
+pub fn fit_overlay_to_displays() {
+    let (x, y, width, height) = virtual_screen_rect();
+    STATE.with(|s| {
+        let mut state = s.borrow_mut();
+        if (state.offset_x, state.offset_y, state.width, state.height) != (x, y, width, height) {
+            state.offset_x = x;
+            state.offset_y = y;
+            state.width = width;
+            state.height = height;
+            state.dirty = true;
+        }
+    });
+}
+
+/// Keep the overlay window `hwnd` out of screen captures and recordings,
+/// or let them see it again, as `PREF_EXCLUDE_FROM_CAPTURE` says.
+pub fn apply_capture_exclusion(hwnd: HWND) {
+    let exclude = config::prefs_get_int(PREF_EXCLUDE_FROM_CAPTURE, 0) != 0;
+    if !hwnd.is_invalid() && !set_excluded_from_capture(hwnd, exclude) {
+        warn!("Could not change the screen capture exclusion");
+    }
+}
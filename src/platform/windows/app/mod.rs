@@ -1,5 +1,20 @@
 //! Application-level helpers for Windows.
 
+pub mod backend;
+pub mod crash;
+pub mod diagnostics;
+pub mod displays;
+pub mod soak;
 pub mod state;
+pub mod update;
 
+pub use backend::WindowsBackend;
+pub use crash::install_crash_handler;
+pub use diagnostics::{diagnostics_report, finish_diagnostics, run_diagnostics};
+pub use displays::{apply_capture_exclusion, fit_overlay_to_displays, virtual_screen_rect};
+pub use soak::{soak_tick, start_soak_test};
 pub use state::{reload_settings_from_config, WindowsRuntimeState, STATE};
+pub use update::{
+    available_update, check_for_updates, check_if_due, offer_update, open_release_page,
+    start_update_checks, update_checked,
+};
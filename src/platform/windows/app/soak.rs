@@ -0,0 +1,190 @@
+//! Hidden soak-test mode (`--soak[=minutes]`).
+//!
+//! Runs the scripted actions of `model::soak` against the live overlay and
+//! logs resource samples to `SOAK_REPORT_FILE` in the temp directory (the
+//! app has no console). The churned settings are restored before quitting.
+//!
+//! Windows has no public API to switch virtual desktops, so a simulated
+//! space change sends the overlay a `WM_SETTINGCHANGE` instead.
+
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::Write;
+use std::time::Instant;
+
+use tracing::info;
+use windows::Win32::Foundation::{HWND, LPARAM, WPARAM};
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetSystemMetrics, KillTimer, SendMessageW, SetCursorPos, SetTimer, SM_CXSCREEN, SM_CYSCREEN,
+    WM_SETTINGCHANGE,
+};
+
+use crate::events::pending_events;
+use crate::model::constants::*;
+use crate::model::{ScreenRect, SoakAction, SoakConfig, SoakReport, SoakSample, SoakScript};
+use crate::platform::windows::app::state::{reload_settings_from_config, STATE};
+use crate::platform::windows::ffi::{gui_object_count, handle_count, working_set_bytes};
+use crate::platform::windows::input::TIMER_SOAK;
+use crate::platform::windows::storage::config;
+
+/// A soak test in progress.
+struct SoakRun {
+    config: SoakConfig,
+    script: SoakScript,
+    report: SoakReport,
+    start: Instant,
+    next_sample: f64,
+    saved_radius: f64,
+    saved_style: i32,
+    log: Option<File>,
+}
+
+impl SoakRun {
+    /// Writes a line to the log and the report file.
+    fn log(&mut self, line: &str) {
+        info!(target: "soak", "{}", line);
+        if let Some(file) = self.log.as_mut() {
+            let _ = writeln!(file, "{}", line);
+        }
+    }
+}
+
+thread_local! {
+    static SOAK: RefCell<Option<SoakRun>> = const { RefCell::new(None) };
+}
+
+/// Start the soak test on the overlay's `TIMER_SOAK` timer.
+pub fn start_soak_test(hwnd: HWND, config: SoakConfig) {
+    let path = std::env::temp_dir().join(SOAK_REPORT_FILE);
+    let mut run = SoakRun {
+        config,
+        script: SoakScript::new(),
+        report: SoakReport::new(),
+        start: Instant::now(),
+        next_sample: 0.0,
+        saved_radius: config::prefs_get_double(PREF_RADIUS, DEFAULT_DIAMETER / 2.0),
+        saved_style: config::prefs_get_int(PREF_HIGHLIGHT_STYLE, HIGHLIGHT_STYLE_CIRCLE),
+        log: File::create(&path).ok(),
+    };
+    run.log(&format!(
+        "[soak] running for {:.0} min, report: {}",
+        config.duration_secs / 60.0,
+        path.display()
+    ));
+    SOAK.with(|s| *s.borrow_mut() = Some(run));
+
+    unsafe {
+        SetTimer(
+            Some(hwnd),
+            TIMER_SOAK,
+            (SOAK_TICK_SECS * 1000.0) as u32,
+            None,
+        );
+    }
+}
+
+/// Run one scripted step, sample resources when due and finish when the
+/// duration is over. Called on every `TIMER_SOAK` tick.
+///
+/// Returns true once the run has finished; the caller then quits the app
+/// through the main loop's own quit, which the modeless Settings window's
+/// pump cannot swallow.
+pub fn soak_tick(hwnd: HWND) -> bool {
+    let area = unsafe {
+        ScreenRect::new(
+            0.0,
+            0.0,
+            GetSystemMetrics(SM_CXSCREEN) as f64,
+            GetSystemMetrics(SM_CYSCREEN) as f64,
+        )
+    };
+
+    let Some((actions, elapsed, sample_due, finished)) = SOAK.with(|s| {
+        let mut s = s.borrow_mut();
+        let run = s.as_mut()?;
+        let elapsed = run.start.elapsed().as_secs_f64();
+        let finished = elapsed >= run.config.duration_secs;
+        let sample_due = finished || elapsed >= run.next_sample;
+        if sample_due {
+            run.next_sample = elapsed + SOAK_SAMPLE_SECS;
+        }
+        let actions = if finished {
+            Vec::new()
+        } else {
+            run.script.next_actions(&area)
+        };
+        for action in &actions {
+            run.report.count(action);
+        }
+        Some((actions, elapsed, sample_due, finished))
+    }) else {
+        return false;
+    };
+
+    for action in actions {
+        perform(hwnd, action);
+    }
+
+    if sample_due {
+        let sample = SoakSample {
+            elapsed_secs: elapsed,
+            memory_bytes: working_set_bytes(),
+            handles: handle_count(),
+            gui_objects: Some(gui_object_count()),
+            queue_depth: Some(pending_events()),
+            hotkeys: None,
+        };
+        SOAK.with(|s| {
+            if let Some(run) = s.borrow_mut().as_mut() {
+                run.report.record(sample);
+                run.log(&sample.log_line());
+            }
+        });
+    }
+
+    if finished {
+        finish(hwnd);
+    }
+    finished
+}
+
+/// Apply one scripted action to the running app.
+fn perform(hwnd: HWND, action: SoakAction) {
+    match action {
+        SoakAction::MoveCursor { x, y } => unsafe {
+            let _ = SetCursorPos(x as i32, y as i32);
+        },
+        SoakAction::ToggleOverlay => STATE.with(|s| {
+            let mut state = s.borrow_mut();
+            state.overlay.overlay_enabled = !state.overlay.overlay_enabled;
+            state.dirty = true;
+        }),
+        SoakAction::ChurnSettings {
+            radius,
+            highlight_style,
+        } => {
+            config::prefs_set_double(PREF_RADIUS, radius);
+            config::prefs_set_int(PREF_HIGHLIGHT_STYLE, highlight_style);
+            reload_settings_from_config();
+        }
+        SoakAction::SpaceChange => unsafe {
+            SendMessageW(hwnd, WM_SETTINGCHANGE, Some(WPARAM(0)), Some(LPARAM(0)));
+        },
+    }
+}
+
+/// Log the summary, restore the churned settings and stop the timer.
+fn finish(hwnd: HWND) {
+    let Some(mut run) = SOAK.with(|s| s.borrow_mut().take()) else {
+        return;
+    };
+    let summary = run.report.summary();
+    run.log(summary.trim_end());
+
+    config::prefs_set_double(PREF_RADIUS, run.saved_radius);
+    config::prefs_set_int(PREF_HIGHLIGHT_STYLE, run.saved_style);
+
+    unsafe {
+        let _ = KillTimer(Some(hwnd), TIMER_SOAK);
+    }
+}
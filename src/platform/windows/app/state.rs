@@ -6,18 +6,22 @@ use std::cell::RefCell;
 
 use windows::Win32::Foundation::HWND;
 
-use crate::model::constants::*;
+use crate::model::animation::{transition_alpha, transition_scale};
+use crate::model::pen::pen_scale;
+use crate::model::pulse::pulse_radius;
+use crate::model::rainbow::rainbow_color;
+use crate::model::screen_frame::Rgba;
+use crate::model::touch::{TouchRing, Touches};
+use crate::model::{
+    ClickBurst, ClickHold, ClickLog, FrameColors, FrameTimer, HookWatchdog, IdleFade, Kinematics,
+    OverlayState, PresentationLock, Rgb8, Ruler, ShowHide, Trail, Zoom,
+};
 
 /// Windows-specific runtime state.
 ///
-/// Contains both persistent settings (loaded from config) and transient
-/// window state (hwnd, dimensions). The settings fields mirror
-/// `model::OverlayState` but use `f32` for colors as required by Direct2D.
-///
-/// Note: This is intentionally separate from `model::OverlayState` to avoid
-/// type conversion overhead during rendering. Settings are synced via
-/// `config::load_state()` which converts f64 -> f32.
-#[allow(dead_code)]
+/// Wraps the shared `model::OverlayState` (settings loaded from config,
+/// visibility and display mode) with the window and per-frame state of the
+/// Direct2D overlay. Direct2D colors are converted to `f32` when drawing.
 pub struct WindowsRuntimeState {
     // Window-specific fields (not persisted)
     pub hwnd: HWND,
@@ -26,20 +30,73 @@ pub struct WindowsRuntimeState {
     pub offset_x: i32,
     pub offset_y: i32,
 
-    // Settings fields (persisted via config.json)
-    // Note: colors are f32 for Direct2D compatibility
-    pub radius: f64,
-    pub border_width: f64,
-    pub stroke_r: f32,
-    pub stroke_g: f32,
-    pub stroke_b: f32,
-    pub stroke_a: f32,
-    pub fill_transparency_pct: f64,
-    pub lang: i32,
+    /// Settings (persisted via config.toml), visibility and display mode.
+    pub overlay: OverlayState,
+    /// Recording-frame color of each display.
+    pub frame_colors: FrameColors,
 
     // Runtime state (not persisted)
-    pub visible: bool,
-    pub display_mode: i32,
+    /// Laser-pointer trail in virtual-screen coordinates.
+    pub trail: Trail,
+    /// Cursor speed tracker for the opacity ramp.
+    pub kinematics: Kinematics,
+    /// Idle tracker and fade animation for the auto-hide.
+    pub idle: IdleFade,
+    /// System "animation effects" turned off (refreshed on WM_SETTINGCHANGE).
+    pub system_reduce_motion: bool,
+    /// Opacity multiplier (speed ramp x idle fade) of the last drawn frame.
+    pub fade_alpha: f64,
+    /// Speed size multiplier of the last drawn frame (1.0 when off).
+    pub speed_scale: f64,
+    /// Rainbow hue phase of the last drawn frame (`None` when not cycling).
+    pub rainbow_phase: Option<f64>,
+    /// Pulse phase of the last drawn frame (`None` when not pulsing).
+    pub pulse_phase: Option<f64>,
+    /// Show/hide transition after the overlay is toggled.
+    pub show_hide: ShowHide,
+    /// Show/hide progress of the last drawn frame (1.0 when shown).
+    pub transition: f64,
+    /// Zoom-follow level and pan focus in virtual-screen coordinates.
+    pub zoom: Zoom,
+    /// Multi-click burst tracker for the counter badge.
+    pub burst: ClickBurst,
+    /// Clicks in the current burst (1 when the badge is disabled).
+    pub click_count: u32,
+    /// Held and just-released buttons for the click indicator hold.
+    pub click_hold: ClickHold,
+    /// Hold ring fill of the last drawn frame (`None` when no button is
+    /// held or the ring is off).
+    pub hold_progress: Option<f64>,
+    /// Pressure of the pen driving the pointer (0.0 while it hovers or
+    /// Windows does not report it), `None` when the mouse drives it.
+    pub pen_pressure: Option<f64>,
+    /// Fingers on the touchscreen, and those lifted still fading out.
+    pub touches: Touches,
+    /// Rings of the last drawn frame, one per touch point.
+    pub touch_rings: Vec<TouchRing>,
+    /// Does the overlay window observe all touch input? Otherwise only
+    /// the primary touch is tracked, through the mouse hook.
+    pub touch_observed: bool,
+    /// Buttons the mouse hook saw, to notice when Windows removes it.
+    pub hook_watchdog: HookWatchdog,
+    /// Cursor coordinates readout shown beside the highlight.
+    pub show_coordinates: bool,
+    /// Color sampler on (eyedropper mode).
+    pub color_sampler: bool,
+    /// Screen color under the pointer in the last drawn frame (`None`
+    /// while the sampler is off).
+    pub color_sample: Option<Rgb8>,
+    /// Points placed in the ruler mode (`None` while it is off, when
+    /// clicks pass through).
+    pub ruler: Option<Ruler>,
+    /// Alignment grid drawn across the monitor under the cursor.
+    pub show_grid: bool,
+    /// Presentation lock (hides Settings, ignores settings hotkeys).
+    pub lock: PresentationLock,
+    /// Click log for usability testing.
+    pub click_log: ClickLog,
+    /// Frame timer of `--diagnostics=overlay` (`None` unless enabled).
+    pub diagnostics: Option<FrameTimer>,
 
     // Frame tracking (skip redundant redraws)
     pub last_cursor_x: i32,
@@ -57,16 +114,36 @@ impl Default for WindowsRuntimeState {
             height: 0,
             offset_x: 0,
             offset_y: 0,
-            radius: DEFAULT_DIAMETER / 2.0,
-            border_width: DEFAULT_BORDER_WIDTH,
-            stroke_r: DEFAULT_COLOR.0 as f32,
-            stroke_g: DEFAULT_COLOR.1 as f32,
-            stroke_b: DEFAULT_COLOR.2 as f32,
-            stroke_a: DEFAULT_COLOR.3 as f32,
-            fill_transparency_pct: DEFAULT_FILL_TRANSPARENCY_PCT,
-            lang: LANG_EN,
-            visible: true,
-            display_mode: DISPLAY_MODE_CIRCLE,
+            overlay: OverlayState::default(),
+            frame_colors: FrameColors::new(),
+            trail: Trail::default(),
+            kinematics: Kinematics::new(),
+            idle: IdleFade::new(),
+            system_reduce_motion: false,
+            fade_alpha: 1.0,
+            speed_scale: 1.0,
+            rainbow_phase: None,
+            pulse_phase: None,
+            show_hide: ShowHide::new(),
+            transition: 1.0,
+            zoom: Zoom::new(),
+            burst: ClickBurst::new(),
+            click_count: 1,
+            click_hold: ClickHold::new(),
+            hold_progress: None,
+            pen_pressure: None,
+            touches: Touches::new(),
+            touch_rings: Vec::new(),
+            touch_observed: false,
+            hook_watchdog: HookWatchdog::new(),
+            show_coordinates: false,
+            color_sampler: false,
+            color_sample: None,
+            ruler: None,
+            show_grid: false,
+            lock: PresentationLock::new(),
+            click_log: ClickLog::new(),
+            diagnostics: None,
             last_cursor_x: i32::MIN,
             last_cursor_y: i32::MIN,
             last_display_mode: -1,
@@ -76,26 +153,60 @@ impl Default for WindowsRuntimeState {
     }
 }
 
+impl WindowsRuntimeState {
+    /// Stroke color of the frame, its hue turned while the rainbow mode
+    /// cycles and faded while the overlay is shown or hidden.
+    pub fn stroke_color(&self) -> Rgba {
+        let (r, g, b, a) = match self.rainbow_phase {
+            Some(phase) => rainbow_color(self.overlay.stroke_color(), phase),
+            None => self.overlay.stroke_color(),
+        };
+        (r, g, b, a * transition_alpha(self.transition))
+    }
+
+    /// Radius of the frame, breathing while the pulse animates, grown by
+    /// fast movement and pen pressure and scaled while the overlay is
+    /// shown or hidden.
+    pub fn radius(&self) -> f64 {
+        let radius = match self.pulse_phase {
+            Some(phase) => pulse_radius(self.overlay.radius, self.overlay.pulse_amplitude, phase),
+            None => self.overlay.radius,
+        };
+        radius
+            * self.speed_scale
+            * self.pen_pressure.map_or(1.0, pen_scale)
+            * transition_scale(self.transition)
+    }
+}
+
 thread_local! {
     /// Global application state for the Windows overlay.
     pub static STATE: RefCell<WindowsRuntimeState> = RefCell::new(WindowsRuntimeState::default());
 }
 
-/// Reload settings from JSON config file into the thread-local state.
+/// Reload settings from the config file into the thread-local state.
 pub fn reload_settings_from_config() {
+    use crate::platform::windows::app::displays::apply_capture_exclusion;
+    use crate::platform::windows::ffi::system_locale;
     use crate::platform::windows::storage::config;
 
     let loaded = config::load_state();
-    STATE.with(|s| {
+    let frame_colors = config::load_frame_colors();
+    crate::i18n::set_language(&crate::i18n::resolve_language(
+        loaded.lang,
+        system_locale().as_deref(),
+    ));
+    let hwnd = STATE.with(|s| {
         let mut state = s.borrow_mut();
-        state.radius = loaded.radius;
-        state.border_width = loaded.border_width;
-        state.stroke_r = loaded.stroke_r as f32;
-        state.stroke_g = loaded.stroke_g as f32;
-        state.stroke_b = loaded.stroke_b as f32;
-        state.stroke_a = loaded.stroke_a as f32;
-        state.fill_transparency_pct = loaded.fill_transparency_pct;
-        state.lang = loaded.lang;
+        // Visibility and display mode are not persisted
+        state.overlay = OverlayState {
+            overlay_enabled: state.overlay.overlay_enabled,
+            display_mode: state.overlay.display_mode,
+            ..loaded
+        };
+        state.frame_colors = frame_colors;
         state.dirty = true;
+        state.hwnd
     });
+    apply_capture_exclusion(hwnd);
 }
@@ -0,0 +1,318 @@
+//! Update check: fetches the release feed with WinHTTP.
+//!
+//! The request runs on a thread of its own; its outcome comes back through
+//! the event bus as `AppEvent::UpdateChecked`. An available update is
+//! announced with a tray notification and offered at the top of the tray
+//! menu.
+
+use std::cell::RefCell;
+use std::ffi::c_void;
+use std::thread;
+
+use tracing::{info, warn};
+use windows::core::{w, PCWSTR};
+use windows::Win32::Foundation::HWND;
+use windows::Win32::Networking::WinHttp::{
+    WinHttpCloseHandle, WinHttpConnect, WinHttpOpen, WinHttpOpenRequest, WinHttpQueryHeaders,
+    WinHttpReadData, WinHttpReceiveResponse, WinHttpSendRequest, WinHttpSetTimeouts,
+    INTERNET_DEFAULT_HTTPS_PORT, WINHTTP_ACCESS_TYPE_AUTOMATIC_PROXY, WINHTTP_FLAG_SECURE,
+    WINHTTP_QUERY_FLAG_NUMBER, WINHTTP_QUERY_STATUS_CODE,
+};
+use windows::Win32::UI::Shell::ShellExecuteW;
+use windows::Win32::UI::WindowsAndMessaging::{
+    KillTimer, MessageBoxW, SetTimer, IDYES, MB_ICONINFORMATION, MB_ICONWARNING, MB_OK, MB_YESNO,
+    SW_SHOWNORMAL,
+};
+
+use crate::events::{self, AppEvent};
+use crate::i18n::{tr, tr_args};
+use crate::model::click_log::unix_time_now;
+use crate::model::constants::{
+    PREF_LAST_UPDATE_CHECK, PREF_UPDATE_CHECK, UPDATE_FEED_URL, UPDATE_POLL_SECS,
+};
+use crate::model::{check_due, update_status, Release, UpdateStatus};
+use crate::platform::windows::input::TIMER_UPDATE;
+use crate::platform::windows::storage::config;
+use crate::platform::windows::ui::tray;
+
+/// Version of the running app.
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Give up on each step of the request after this long, in milliseconds.
+const FETCH_TIMEOUT_MS: i32 = 30_000;
+
+thread_local! {
+    /// Newer release found by the last check, if any.
+    static AVAILABLE: RefCell<Option<Release>> = const { RefCell::new(None) };
+}
+
+/// Newer release found by the last check, shown in the tray menu.
+pub fn available_update() -> Option<Release> {
+    AVAILABLE.with(|a| a.borrow().clone())
+}
+
+/// Check now if automatic checks are on and the last one is a day old,
+/// then look again on every `TIMER_UPDATE` tick.
+pub fn start_update_checks(hwnd: HWND) {
+    check_if_due();
+    unsafe {
+        let _ = KillTimer(Some(hwnd), TIMER_UPDATE);
+        SetTimer(
+            Some(hwnd),
+            TIMER_UPDATE,
+            (UPDATE_POLL_SECS * 1000.0) as u32,
+            None,
+        );
+    }
+}
+
+/// Start an automatic check if one is due. Called on every `TIMER_UPDATE`
+/// tick.
+pub fn check_if_due() {
+    let enabled = config::prefs_get_int(PREF_UPDATE_CHECK, 1) != 0;
+    let last_check = config::prefs_get_double(PREF_LAST_UPDATE_CHECK, 0.0);
+    if check_due(enabled, last_check, unix_time_now()) {
+        check_for_updates(false);
+    }
+}
+
+/// Fetch the release feed in the background; `manual` when the user
+/// asked for it from the tray menu.
+pub fn check_for_updates(manual: bool) {
+    info!(manual, "Checking for updates");
+    config::prefs_set_double(PREF_LAST_UPDATE_CHECK, unix_time_now());
+    config::flush_config();
+
+    let spawned = thread::Builder::new()
+        .name("lumbus-update".into())
+        .spawn(move || {
+            let feed = unsafe { fetch_feed() };
+            events::publish(AppEvent::UpdateChecked {
+                status: update_status(VERSION, feed),
+                manual,
+            });
+        });
+    if let Err(e) = spawned {
+        warn!("Could not start update check: {}", e);
+    }
+}
+
+/// Open WinHTTP handle, closed when dropped.
+struct Handle(*mut c_void);
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        if !self.0.is_null() {
+            unsafe {
+                let _ = WinHttpCloseHandle(self.0);
+            }
+        }
+    }
+}
+
+fn opened(handle: *mut c_void, step: &str) -> Result<Handle, String> {
+    if handle.is_null() {
+        Err(format!("{step}: {}", windows::core::Error::from_thread()))
+    } else {
+        Ok(Handle(handle))
+    }
+}
+
+/// `GET` the release feed, returning the body of a successful response or
+/// why the request failed.
+unsafe fn fetch_feed() -> Result<String, String> {
+    let rest = UPDATE_FEED_URL
+        .strip_prefix("https://")
+        .ok_or("feed is not https")?;
+    let (host, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    let wide = |s: &str| {
+        s.encode_utf16()
+            .chain(std::iter::once(0))
+            .collect::<Vec<u16>>()
+    };
+    // GitHub's API rejects requests without a User-Agent
+    let agent = wide(&format!("Lumbus/{VERSION}"));
+    let host = wide(host);
+    let path = wide(path);
+
+    let session = opened(
+        WinHttpOpen(
+            PCWSTR(agent.as_ptr()),
+            WINHTTP_ACCESS_TYPE_AUTOMATIC_PROXY,
+            PCWSTR::null(),
+            PCWSTR::null(),
+            0,
+        ),
+        "open",
+    )?;
+    let _ = WinHttpSetTimeouts(
+        session.0,
+        FETCH_TIMEOUT_MS,
+        FETCH_TIMEOUT_MS,
+        FETCH_TIMEOUT_MS,
+        FETCH_TIMEOUT_MS,
+    );
+    let connection = opened(
+        WinHttpConnect(
+            session.0,
+            PCWSTR(host.as_ptr()),
+            INTERNET_DEFAULT_HTTPS_PORT,
+            0,
+        ),
+        "connect",
+    )?;
+    let request = opened(
+        WinHttpOpenRequest(
+            connection.0,
+            w!("GET"),
+            PCWSTR(path.as_ptr()),
+            PCWSTR::null(),
+            PCWSTR::null(),
+            std::ptr::null(),
+            WINHTTP_FLAG_SECURE,
+        ),
+        "request",
+    )?;
+    let headers: Vec<u16> = "Accept: application/vnd.github+json"
+        .encode_utf16()
+        .collect();
+    WinHttpSendRequest(request.0, Some(&headers), None, 0, 0, 0)
+        .map_err(|e| format!("send: {e}"))?;
+    WinHttpReceiveResponse(request.0, std::ptr::null_mut()).map_err(|e| format!("receive: {e}"))?;
+
+    let mut status = 0u32;
+    let mut size = std::mem::size_of::<u32>() as u32;
+    WinHttpQueryHeaders(
+        request.0,
+        WINHTTP_QUERY_STATUS_CODE | WINHTTP_QUERY_FLAG_NUMBER,
+        PCWSTR::null(),
+        Some(&mut status as *mut u32 as *mut c_void),
+        &mut size,
+        std::ptr::null_mut(),
+    )
+    .map_err(|e| format!("status: {e}"))?;
+    if status != 200 {
+        return Err(format!("HTTP {status}"));
+    }
+
+    let mut body = Vec::new();
+    let mut chunk = [0u8; 8192];
+    loop {
+        let mut read = 0u32;
+        WinHttpReadData(
+            request.0,
+            chunk.as_mut_ptr() as *mut c_void,
+            chunk.len() as u32,
+            &mut read,
+        )
+        .map_err(|e| format!("read: {e}"))?;
+        if read == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..read as usize]);
+    }
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// Record the outcome of a check. A manual check always gets an answer;
+/// an automatic one that finds a new version shows a tray notification.
+pub fn update_checked(hwnd: HWND, status: &UpdateStatus, manual: bool) {
+    let previous = available_update();
+    match status {
+        UpdateStatus::Available(release) => {
+            info!(version = %release.version, "Update available");
+            AVAILABLE.with(|a| *a.borrow_mut() = Some(release.clone()));
+            if manual {
+                offer_update(hwnd, release);
+            } else if previous.as_ref() != Some(release) {
+                let title = tr_args("update-available", &[("version", &release.version)])
+                    .unwrap_or_else(|| release.version.clone());
+                tray::show_tray_notification(&title, &update_detail(release));
+            }
+        }
+        UpdateStatus::UpToDate => {
+            info!("Lumbus is up to date");
+            AVAILABLE.with(|a| *a.borrow_mut() = None);
+            if manual {
+                let detail = tr_args("up-to-date-detail", &[("version", VERSION)])
+                    .unwrap_or_else(|| VERSION.to_string());
+                message_box(
+                    hwnd,
+                    &tr("Lumbus is up to date"),
+                    &detail,
+                    MB_OK | MB_ICONINFORMATION,
+                );
+            }
+        }
+        UpdateStatus::Failed(reason) => {
+            warn!(%reason, "Update check failed");
+            if manual {
+                message_box(
+                    hwnd,
+                    &tr("Could not check for updates"),
+                    reason,
+                    MB_OK | MB_ICONWARNING,
+                );
+            }
+        }
+    }
+    if previous != available_update() {
+        tray::update_tray_language();
+    }
+}
+
+fn update_detail(release: &Release) -> String {
+    tr_args(
+        "update-available-detail",
+        &[("version", &release.version), ("current", VERSION)],
+    )
+    .unwrap_or_default()
+}
+
+/// Ask whether to download `release`, opening its page if so.
+pub fn offer_update(hwnd: HWND, release: &Release) {
+    let title = tr_args("update-available", &[("version", &release.version)])
+        .unwrap_or_else(|| release.version.clone());
+    let message = format!("{}\n\n{}", update_detail(release), tr("Download it now?"));
+    if message_box(hwnd, &title, &message, MB_YESNO | MB_ICONINFORMATION) == IDYES.0 {
+        open_release_page(release);
+    }
+}
+
+/// Open the release page in the default browser.
+pub fn open_release_page(release: &Release) {
+    let url: Vec<u16> = release
+        .url
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+    unsafe {
+        ShellExecuteW(
+            None,
+            w!("open"),
+            PCWSTR(url.as_ptr()),
+            PCWSTR::null(),
+            PCWSTR::null(),
+            SW_SHOWNORMAL,
+        );
+    }
+}
+
+fn message_box(
+    hwnd: HWND,
+    title: &str,
+    message: &str,
+    style: windows::Win32::UI::WindowsAndMessaging::MESSAGEBOX_STYLE,
+) -> i32 {
+    let title_wide: Vec<u16> = title.encode_utf16().chain(std::iter::once(0)).collect();
+    let message_wide: Vec<u16> = message.encode_utf16().chain(std::iter::once(0)).collect();
+    unsafe {
+        MessageBoxW(
+            Some(hwnd),
+            PCWSTR(message_wide.as_ptr()),
+            PCWSTR(title_wide.as_ptr()),
+            style,
+        )
+        .0
+    }
+}
@@ -0,0 +1,24 @@
+//! Accessibility settings.
+//!
+//! Windows has no dedicated "reduce motion" flag; the closest equivalent
+//! is Settings > Accessibility > Visual effects > "Animation effects",
+//! exposed as the client-area animation parameter.
+
+use windows::core::BOOL;
+use windows::Win32::UI::WindowsAndMessaging::{
+    SystemParametersInfoW, SPI_GETCLIENTAREAANIMATION, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+};
+
+/// Has the user turned animation effects off?
+pub fn system_reduce_motion() -> bool {
+    let mut animate = BOOL(1);
+    let ok = unsafe {
+        SystemParametersInfoW(
+            SPI_GETCLIENTAREAANIMATION,
+            0,
+            Some(&mut animate as *mut BOOL as *mut _),
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        )
+    };
+    ok.is_ok() && !animate.as_bool()
+}
@@ -0,0 +1,64 @@
+//! One-pixel screen captures for the color sampler, and keeping a window
+//! out of the captures of other apps.
+
+use windows::Win32::Foundation::HWND;
+use windows::Win32::Graphics::Gdi::{
+    BitBlt, CreateCompatibleDC, CreateDIBSection, DeleteDC, DeleteObject, GetDC, ReleaseDC,
+    SelectObject, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, SRCCOPY,
+};
+
+use windows::Win32::UI::WindowsAndMessaging::{
+    SetWindowDisplayAffinity, WDA_EXCLUDEFROMCAPTURE, WDA_NONE,
+};
+
+use crate::model::color_sampler::{from_bgra, Rgb8};
+
+/// Keep `hwnd` out of screen captures and recordings (`on`), or let them
+/// see it again. Returns false when the display affinity could not be
+/// set, as before Windows 10 2004.
+pub fn set_excluded_from_capture(hwnd: HWND, on: bool) -> bool {
+    let affinity = if on { WDA_EXCLUDEFROMCAPTURE } else { WDA_NONE };
+    unsafe { SetWindowDisplayAffinity(hwnd, affinity).is_ok() }
+}
+
+/// Color of the screen pixel at (`x`, `y`) in virtual-screen coordinates.
+///
+/// Copied without `CAPTUREBLT`, so layered windows (the overlay) are left
+/// out, as in the zoom capture.
+pub fn screen_pixel(x: i32, y: i32) -> Option<Rgb8> {
+    unsafe {
+        let screen_dc = GetDC(None);
+        if screen_dc.is_invalid() {
+            return None;
+        }
+        let capture_dc = CreateCompatibleDC(Some(screen_dc));
+        let bmi = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: 1,
+                biHeight: -1,
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut bits: *mut std::ffi::c_void = std::ptr::null_mut();
+        let mut sample = None;
+        if let Ok(capture) =
+            CreateDIBSection(Some(capture_dc), &bmi, DIB_RGB_COLORS, &mut bits, None, 0)
+        {
+            let previous = SelectObject(capture_dc, capture.into());
+            let copied = BitBlt(capture_dc, 0, 0, 1, 1, Some(screen_dc), x, y, SRCCOPY).is_ok();
+            if copied && !bits.is_null() {
+                sample = from_bgra(std::slice::from_raw_parts(bits as *const u8, 4));
+            }
+            SelectObject(capture_dc, previous);
+            let _ = DeleteObject(capture.into());
+        }
+        let _ = DeleteDC(capture_dc);
+        ReleaseDC(None, screen_dc);
+        sample
+    }
+}
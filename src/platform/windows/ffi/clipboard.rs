@@ -0,0 +1,46 @@
+//! Plain-text clipboard writes.
+
+use windows::Win32::Foundation::{GlobalFree, HANDLE, HWND};
+use windows::Win32::System::DataExchange::{
+    CloseClipboard, EmptyClipboard, OpenClipboard, SetClipboardData,
+};
+use windows::Win32::System::Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE};
+use windows::Win32::System::Ole::CF_UNICODETEXT;
+
+/// Replace the clipboard contents with `text`, owned by `hwnd`.
+///
+/// Returns false if another app holds the clipboard open.
+pub fn set_clipboard_text(hwnd: HWND, text: &str) -> bool {
+    let wide: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+    unsafe {
+        if OpenClipboard(Some(hwnd)).is_err() {
+            return false;
+        }
+        let copied = copy_to_clipboard(&wide);
+        let _ = CloseClipboard();
+        copied
+    }
+}
+
+/// Hand a NUL-terminated UTF-16 copy of the text to the open clipboard.
+unsafe fn copy_to_clipboard(wide: &[u16]) -> bool {
+    if EmptyClipboard().is_err() {
+        return false;
+    }
+    let Ok(memory) = GlobalAlloc(GMEM_MOVEABLE, std::mem::size_of_val(wide)) else {
+        return false;
+    };
+    let target = GlobalLock(memory) as *mut u16;
+    if target.is_null() {
+        let _ = GlobalFree(Some(memory));
+        return false;
+    }
+    std::ptr::copy_nonoverlapping(wide.as_ptr(), target, wide.len());
+    let _ = GlobalUnlock(memory);
+    // The clipboard owns the memory once the call succeeds
+    if SetClipboardData(CF_UNICODETEXT.0 as u32, Some(HANDLE(memory.0))).is_err() {
+        let _ = GlobalFree(Some(memory));
+        return false;
+    }
+    true
+}
@@ -0,0 +1,49 @@
+//! Font families installed on the system.
+
+use windows::core::{w, BOOL};
+use windows::Win32::Graphics::DirectWrite::{
+    DWriteCreateFactory, IDWriteFactory, IDWriteFontCollection, IDWriteLocalizedStrings,
+    DWRITE_FACTORY_TYPE_SHARED,
+};
+
+/// Names of the font families in the DirectWrite system collection,
+/// sorted. Empty if the collection cannot be read.
+pub fn font_families() -> Vec<String> {
+    let mut families = unsafe { system_families() }.unwrap_or_default();
+    families.sort_by_key(|name| name.to_lowercase());
+    families.dedup();
+    families
+}
+
+unsafe fn system_families() -> Option<Vec<String>> {
+    let factory: IDWriteFactory = DWriteCreateFactory(DWRITE_FACTORY_TYPE_SHARED).ok()?;
+    let mut collection: Option<IDWriteFontCollection> = None;
+    factory
+        .GetSystemFontCollection(&mut collection, false)
+        .ok()?;
+    let collection = collection?;
+    let families = (0..collection.GetFontFamilyCount())
+        .filter_map(|i| {
+            let names = collection.GetFontFamily(i).ok()?.GetFamilyNames().ok()?;
+            family_name(&names)
+        })
+        .collect();
+    Some(families)
+}
+
+/// English name of a family, or its first name when it has no English one.
+unsafe fn family_name(names: &IDWriteLocalizedStrings) -> Option<String> {
+    let mut index = 0u32;
+    let mut exists = BOOL::default();
+    names
+        .FindLocaleName(w!("en-us"), &mut index, &mut exists)
+        .ok()?;
+    if !exists.as_bool() {
+        index = 0;
+    }
+    let len = names.GetStringLength(index).ok()? as usize;
+    // The buffer includes the terminator
+    let mut buf = vec![0u16; len + 1];
+    names.GetString(index, &mut buf).ok()?;
+    Some(String::from_utf16_lossy(&buf[..len]))
+}
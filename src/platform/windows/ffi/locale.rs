@@ -0,0 +1,20 @@
+//! User interface language of the OS.
+
+use windows::Win32::Globalization::{GetUserDefaultUILanguage, LCIDToLocaleName};
+
+/// Maximum length of a locale name, including the terminator.
+const LOCALE_NAME_MAX_LENGTH: usize = 85;
+
+/// Locale tag of the Windows display language (e.g. `es-ES`), if known.
+pub fn system_locale() -> Option<String> {
+    let mut name = [0u16; LOCALE_NAME_MAX_LENGTH];
+    let len = unsafe {
+        // A LANGID is a valid LCID with the default sort order
+        let lcid = GetUserDefaultUILanguage() as u32;
+        LCIDToLocaleName(lcid, Some(&mut name), 0)
+    };
+    // `len` counts the terminator; 0 means failure
+    let len = usize::try_from(len).ok()?.checked_sub(1)?;
+    let locale = String::from_utf16_lossy(&name[..len]);
+    (!locale.is_empty()).then_some(locale)
+}
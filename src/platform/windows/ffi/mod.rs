@@ -5,9 +5,39 @@
 //! - GDI rendering (circles, text)
 //! - Global hotkeys
 //! - Mouse position tracking
+//! - Monitor geometry
+//! - One-pixel screen captures and clipboard text (color sampler)
+//! - Screen capture exclusion
+//! - Accessibility settings (reduce motion)
+//! - OS display language
+//! - Installed font families
+//! - System light/dark theme
+//! - Process resource usage
+//! - Run at Windows startup
+//! - Windows version
 
+pub mod accessibility;
+pub mod capture;
+pub mod clipboard;
+pub mod fonts;
+pub mod locale;
+pub mod monitor;
+pub mod process;
+pub mod startup;
+pub mod theme;
 pub mod types;
+pub mod version;
 
+pub use accessibility::system_reduce_motion;
+pub use capture::{screen_pixel, set_excluded_from_capture};
+pub use clipboard::set_clipboard_text;
+pub use fonts::font_families;
+pub use locale::system_locale;
+pub use monitor::{monitor_rect_at, monitor_rect_for_window, monitors};
+pub use process::{gui_object_count, handle_count, working_set_bytes};
+pub use startup::{run_at_startup, set_run_at_startup};
+pub use theme::is_light_theme;
 pub use types::*;
+pub use version::os_version;
 
 // Re-exports and helpers will be added as needed
@@ -0,0 +1,104 @@
+//! Monitor geometry helpers.
+//!
+//! Resolve the display under a point or window to its rectangle in
+//! virtual-screen coordinates, as used by `model::placement`, and list the
+//! connected displays for the diagnostics report.
+
+use windows::core::BOOL;
+use windows::Win32::Foundation::{HWND, LPARAM, POINT, RECT};
+use windows::Win32::Graphics::Gdi::{
+    EnumDisplayMonitors, GetMonitorInfoW, MonitorFromPoint, MonitorFromWindow, HDC, HMONITOR,
+    MONITORINFO, MONITOR_DEFAULTTONEAREST,
+};
+use windows::Win32::UI::HiDpi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI};
+
+use crate::model::placement::ScreenRect;
+use crate::model::DisplayInfo;
+
+/// DPI of a monitor at 100% scaling.
+const BASE_DPI: f64 = 96.0;
+
+/// `MONITORINFO::dwFlags` bit of the primary monitor.
+const MONITORINFOF_PRIMARY: u32 = 1;
+
+/// Rectangle of a monitor in virtual-screen coordinates.
+fn monitor_rect(monitor: HMONITOR) -> Option<ScreenRect> {
+    let mut info = MONITORINFO {
+        cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+        ..Default::default()
+    };
+    unsafe {
+        if !GetMonitorInfoW(monitor, &mut info).as_bool() {
+            return None;
+        }
+    }
+    let r = info.rcMonitor;
+    Some(ScreenRect::new(
+        r.left as f64,
+        r.top as f64,
+        (r.right - r.left) as f64,
+        (r.bottom - r.top) as f64,
+    ))
+}
+
+/// Rectangle of the monitor containing (or nearest to) a point.
+pub fn monitor_rect_at(x: i32, y: i32) -> Option<ScreenRect> {
+    let monitor = unsafe { MonitorFromPoint(POINT { x, y }, MONITOR_DEFAULTTONEAREST) };
+    monitor_rect(monitor)
+}
+
+/// Rectangle of the monitor showing (most of) a window.
+pub fn monitor_rect_for_window(hwnd: HWND) -> Option<ScreenRect> {
+    let monitor = unsafe { MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST) };
+    monitor_rect(monitor)
+}
+
+/// Connected monitors in enumeration order, numbered from 1.
+pub fn monitors() -> Vec<DisplayInfo> {
+    unsafe extern "system" fn collect(
+        monitor: HMONITOR,
+        _hdc: HDC,
+        _rect: *mut RECT,
+        data: LPARAM,
+    ) -> BOOL {
+        let handles = unsafe { &mut *(data.0 as *mut Vec<HMONITOR>) };
+        handles.push(monitor);
+        true.into()
+    }
+
+    let mut handles: Vec<HMONITOR> = Vec::new();
+    unsafe {
+        let _ = EnumDisplayMonitors(
+            None,
+            None,
+            Some(collect),
+            LPARAM(&mut handles as *mut _ as isize),
+        );
+    }
+
+    handles
+        .into_iter()
+        .zip(1..)
+        .filter_map(|(monitor, id)| {
+            let mut info = MONITORINFO {
+                cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+                ..Default::default()
+            };
+            let (mut dpi_x, mut dpi_y) = (0u32, 0u32);
+            unsafe {
+                if !GetMonitorInfoW(monitor, &mut info).as_bool() {
+                    return None;
+                }
+                if GetDpiForMonitor(monitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y).is_err() {
+                    dpi_x = BASE_DPI as u32;
+                }
+            }
+            Some(DisplayInfo {
+                id,
+                frame: monitor_rect(monitor)?,
+                scale: dpi_x as f64 / BASE_DPI,
+                primary: info.dwFlags & MONITORINFOF_PRIMARY != 0,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,41 @@
+//! Process resource usage (memory, kernel handles, GDI/USER objects).
+//!
+//! Used by the soak test to watch for slow leaks over time.
+
+use windows::Win32::System::ProcessStatus::{K32GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS};
+use windows::Win32::System::Threading::{
+    GetCurrentProcess, GetGuiResources, GetProcessHandleCount, GR_GDIOBJECTS, GR_USEROBJECTS,
+};
+
+/// Working set of this process in bytes (0 if unavailable).
+pub fn working_set_bytes() -> u64 {
+    let mut counters = PROCESS_MEMORY_COUNTERS {
+        cb: std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32,
+        ..Default::default()
+    };
+    unsafe {
+        if K32GetProcessMemoryInfo(GetCurrentProcess(), &mut counters, counters.cb).as_bool() {
+            counters.WorkingSetSize as u64
+        } else {
+            0
+        }
+    }
+}
+
+/// Open kernel handles of this process.
+pub fn handle_count() -> u64 {
+    let mut count = 0u32;
+    unsafe {
+        let _ = GetProcessHandleCount(GetCurrentProcess(), &mut count);
+    }
+    count as u64
+}
+
+/// GDI plus USER objects owned by this process.
+pub fn gui_object_count() -> u64 {
+    unsafe {
+        let process = GetCurrentProcess();
+        GetGuiResources(process, GR_GDIOBJECTS) as u64
+            + GetGuiResources(process, GR_USEROBJECTS) as u64
+    }
+}
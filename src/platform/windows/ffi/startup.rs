@@ -0,0 +1,63 @@
+//! Run at Windows startup.
+//!
+//! Registered per user as the `Lumbus` value of
+//! `HKCU\Software\Microsoft\Windows\CurrentVersion\Run`, holding the
+//! quoted path of this executable.
+
+use windows::core::{w, PCWSTR};
+use windows::Win32::System::Registry::{
+    RegCloseKey, RegDeleteValueW, RegOpenKeyExW, RegQueryValueExW, RegSetValueExW, HKEY,
+    HKEY_CURRENT_USER, KEY_READ, KEY_WRITE, REG_SAM_FLAGS, REG_SZ,
+};
+
+const RUN_KEY: PCWSTR = w!("Software\\Microsoft\\Windows\\CurrentVersion\\Run");
+const RUN_VALUE: PCWSTR = w!("Lumbus");
+
+/// Open the Run key of the current user with `access`.
+unsafe fn open_run_key(access: REG_SAM_FLAGS) -> Option<HKEY> {
+    let mut hkey = HKEY::default();
+    RegOpenKeyExW(HKEY_CURRENT_USER, RUN_KEY, Some(0), access, &mut hkey)
+        .is_ok()
+        .then_some(hkey)
+}
+
+/// Is Lumbus registered to run at startup?
+pub fn run_at_startup() -> bool {
+    unsafe {
+        let Some(hkey) = open_run_key(KEY_READ) else {
+            return false;
+        };
+        let found = RegQueryValueExW(hkey, RUN_VALUE, None, None, None, None).is_ok();
+        let _ = RegCloseKey(hkey);
+        found
+    }
+}
+
+/// Register this executable to run at startup, or remove the
+/// registration. Returns whether the registry could be changed.
+pub fn set_run_at_startup(on: bool) -> bool {
+    unsafe {
+        let Some(hkey) = open_run_key(KEY_WRITE) else {
+            return false;
+        };
+        let ok = if on {
+            match std::env::current_exe() {
+                Ok(exe) => {
+                    let command = format!("\"{}\"", exe.display());
+                    let data: Vec<u8> = command
+                        .encode_utf16()
+                        .chain(std::iter::once(0))
+                        .flat_map(|c| c.to_le_bytes())
+                        .collect();
+                    RegSetValueExW(hkey, RUN_VALUE, None, REG_SZ, Some(&data)).is_ok()
+                }
+                Err(_) => false,
+            }
+        } else {
+            // Already removed counts as removed
+            RegDeleteValueW(hkey, RUN_VALUE).is_ok() || !run_at_startup()
+        };
+        let _ = RegCloseKey(hkey);
+        ok
+    }
+}
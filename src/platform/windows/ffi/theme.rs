@@ -0,0 +1,48 @@
+//! System light/dark theme.
+//!
+//! Windows exposes the app theme as the `AppsUseLightTheme` registry value.
+
+use windows::core::w;
+use windows::Win32::System::Registry::{
+    RegCloseKey, RegOpenKeyExW, RegQueryValueExW, HKEY, HKEY_CURRENT_USER, KEY_READ, REG_VALUE_TYPE,
+};
+
+/// Detect if Windows is using light theme.
+/// Returns true for light theme, false for dark theme.
+pub fn is_light_theme() -> bool {
+    unsafe {
+        let mut hkey = HKEY::default();
+        let result = RegOpenKeyExW(
+            HKEY_CURRENT_USER,
+            w!("Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize"),
+            Some(0),
+            KEY_READ,
+            &mut hkey,
+        );
+
+        if result.is_err() {
+            return true; // Default to light theme if can't read registry
+        }
+
+        let mut data: u32 = 1;
+        let mut data_size: u32 = std::mem::size_of::<u32>() as u32;
+        let mut data_type = REG_VALUE_TYPE::default();
+
+        let query_result = RegQueryValueExW(
+            hkey,
+            w!("AppsUseLightTheme"),
+            None,
+            Some(&mut data_type),
+            Some(&mut data as *mut u32 as *mut u8),
+            Some(&mut data_size),
+        );
+
+        let _ = RegCloseKey(hkey);
+
+        if query_result.is_err() {
+            return true; // Default to light theme
+        }
+
+        data == 1 // 1 = light theme, 0 = dark theme
+    }
+}
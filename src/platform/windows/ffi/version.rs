@@ -0,0 +1,37 @@
+//! Windows version, as shown in the diagnostics report.
+//!
+//! Read from the `CurrentVersion` registry key, which unlike
+//! `GetVersionEx` is not capped by the app manifest.
+
+use windows::core::{w, PCWSTR};
+use windows::Win32::System::Registry::{RegGetValueW, HKEY_LOCAL_MACHINE, RRF_RT_REG_SZ};
+
+/// Read a string value of the `CurrentVersion` key.
+fn current_version_value(name: PCWSTR) -> Option<String> {
+    let mut buf = [0u16; 128];
+    let mut size = std::mem::size_of_val(&buf) as u32;
+    let result = unsafe {
+        RegGetValueW(
+            HKEY_LOCAL_MACHINE,
+            w!("SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion"),
+            name,
+            RRF_RT_REG_SZ,
+            None,
+            Some(buf.as_mut_ptr().cast()),
+            Some(&mut size),
+        )
+    };
+    if result.is_err() {
+        return None;
+    }
+    let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+    Some(String::from_utf16_lossy(&buf[..len]))
+}
+
+/// Product name and build, e.g. "Windows 10 Pro 23H2 (build 22631)".
+pub fn os_version() -> String {
+    let name = current_version_value(w!("ProductName")).unwrap_or_else(|| "Windows".into());
+    let release = current_version_value(w!("DisplayVersion")).unwrap_or_default();
+    let build = current_version_value(w!("CurrentBuild")).unwrap_or_default();
+    format!("{} {} (build {})", name, release, build)
+}
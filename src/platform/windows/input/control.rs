@@ -0,0 +1,58 @@
+//! Local control endpoint glue.
+//!
+//! Starts the endpoint of `control` to match the settings and keeps the
+//! state it reports current. Its requests arrive as events on the event
+//! bus, handled on the cursor timer.
+
+use tracing::error;
+
+use crate::control::{self, configure, new_token};
+use crate::model::constants::{
+    DEFAULT_CONTROL_PORT, PREF_CONTROL_PORT, PREF_CONTROL_SERVER, PREF_CONTROL_TOKEN,
+    PREF_HOTKEY_PROFILE,
+};
+use crate::model::{ControlState, HOTKEY_PROFILE_STANDARD, MANAGED_KEYS};
+use crate::platform::windows::app::STATE;
+use crate::platform::windows::storage::config;
+
+/// Start, restart or stop the control endpoint to match the settings,
+/// generating its token the first time it is turned on.
+pub fn apply_control_server() {
+    let enabled = config::prefs_get_int(PREF_CONTROL_SERVER, 0) != 0;
+    let port = config::prefs_get_int(PREF_CONTROL_PORT, DEFAULT_CONTROL_PORT) as u16;
+    let mut token = config::prefs_get_string(PREF_CONTROL_TOKEN, "");
+    if enabled && token.is_empty() {
+        token = match new_token() {
+            Ok(token) => token,
+            Err(e) => {
+                error!("No token for the control endpoint, not starting it: {}", e);
+                return;
+            }
+        };
+        config::prefs_set_string(PREF_CONTROL_TOKEN, &token);
+        config::flush_config();
+    }
+    if let Err(e) = configure(enabled, port, &token) {
+        error!("Control endpoint on port {} failed: {}", port, e);
+    }
+}
+
+/// Report the overlay's state to the control endpoint, if it is running.
+pub fn publish_control_state() {
+    if !control::is_running() {
+        return;
+    }
+    let (visible, locked) = STATE.with(|s| {
+        let state = s.borrow();
+        (state.overlay.overlay_enabled, state.lock.is_locked())
+    });
+    control::update_state(ControlState {
+        visible,
+        locked,
+        hotkey_profile: config::prefs_get_string(PREF_HOTKEY_PROFILE, HOTKEY_PROFILE_STANDARD),
+        settings: MANAGED_KEYS
+            .iter()
+            .map(|key| (key.to_string(), config::prefs_get_double(key, 0.0)))
+            .collect(),
+    });
+}
@@ -1,32 +1,448 @@
 //! Global hotkeys and mouse hooks for Windows.
+//!
+//! Hotkeys are registered through [`WindowsHotkeys`] (see
+//! `backend::hotkeys`).
 
 use std::sync::atomic::{AtomicIsize, Ordering};
+use std::sync::{Mutex, MutexGuard};
 
-use windows::Win32::Foundation::{LPARAM, LRESULT, WPARAM};
+use tracing::{info, info_span, warn};
+use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+use windows::Win32::UI::Accessibility::RegisterPointerInputTargetEx;
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+    GetAsyncKeyState, RegisterHotKey, UnregisterHotKey, HOT_KEY_MODIFIERS, MOD_ALT, MOD_CONTROL,
+    MOD_NOREPEAT, MOD_SHIFT, VK_ESCAPE, VK_LBUTTON, VK_RBUTTON,
+};
+use windows::Win32::UI::Input::Pointer::{
+    GetPointerInfo, GetPointerPenInfo, GetPointerType, POINTER_FLAG_INCONTACT, POINTER_INFO,
+    POINTER_PEN_INFO,
+};
 use windows::Win32::UI::WindowsAndMessaging::{
-    CallNextHookEx, HHOOK, WM_LBUTTONDOWN, WM_LBUTTONUP, WM_RBUTTONDOWN, WM_RBUTTONUP,
+    CallNextHookEx, SetWindowsHookExW, UnhookWindowsHookEx, HHOOK, MSLLHOOKSTRUCT,
+    POINTER_INPUT_TYPE, PT_PEN, PT_TOUCH, WH_MOUSE_LL, WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MOUSEMOVE,
+    WM_POINTERDOWN, WM_POINTERUP, WM_RBUTTONDOWN, WM_RBUTTONUP,
 };
 
+use crate::backend::{
+    register_bindings, unregister_all, HotkeyError, HotkeyManager, TriggerHandler,
+};
+use crate::events::publish;
+use crate::model::animation::wall_clock_secs;
+use crate::model::click_log::unix_time_now;
 use crate::model::constants::*;
+use crate::model::hook_watchdog::{HOOK_BUTTON_LEFT, HOOK_BUTTON_RIGHT};
+use crate::model::hotkeys;
+use crate::model::pen::{is_pen_extra_info, is_touch_extra_info};
+use crate::model::placement::display_key;
+use crate::model::ClickRecord;
+use crate::model::{
+    apply_overrides, parse_overrides, profile_bindings, safe_mode_bindings, sample_hex, Binding,
+    Hotkey, HotkeyAction, HotkeyPlatform, Key, HOTKEY_PROFILE_STANDARD,
+};
 use crate::platform::windows::app::state::STATE;
+use crate::platform::windows::ffi::{monitor_rect_at, set_clipboard_text};
+use crate::platform::windows::storage::config;
 
 // Hotkey IDs
 pub const HOTKEY_TOGGLE: i32 = 1;
 pub const HOTKEY_SETTINGS: i32 = 2;
 pub const HOTKEY_QUIT: i32 = 3;
 pub const HOTKEY_HELP: i32 = 4;
+pub const HOTKEY_ZOOM_IN: i32 = 5;
+pub const HOTKEY_ZOOM_OUT: i32 = 6;
+pub const HOTKEY_LOCK: i32 = 7;
+pub const HOTKEY_COORDINATES: i32 = 8;
+pub const HOTKEY_COLOR_SAMPLER: i32 = 9;
+pub const HOTKEY_RULER: i32 = 10;
+/// Esc, registered only while the ruler mode is on.
+pub const HOTKEY_RULER_ESCAPE: i32 = 11;
+pub const HOTKEY_GRID: i32 = 12;
+
+/// Hotkeys registered on the overlay window.
+static REGISTERED: Mutex<Vec<Binding>> = Mutex::new(Vec::new());
+
+/// Handler of pressed hotkeys, set with [`HotkeyManager::on_trigger`].
+static TRIGGER_HANDLER: Mutex<Option<TriggerHandler>> = Mutex::new(None);
+
+fn registered() -> MutexGuard<'static, Vec<Binding>> {
+    REGISTERED.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Hotkey ID registered for an action.
+fn hotkey_id(action: HotkeyAction) -> i32 {
+    match action {
+        HotkeyAction::Toggle => HOTKEY_TOGGLE,
+        HotkeyAction::Settings => HOTKEY_SETTINGS,
+        HotkeyAction::Help => HOTKEY_HELP,
+        HotkeyAction::ZoomIn => HOTKEY_ZOOM_IN,
+        HotkeyAction::ZoomOut => HOTKEY_ZOOM_OUT,
+        HotkeyAction::Coordinates => HOTKEY_COORDINATES,
+        HotkeyAction::ColorSampler => HOTKEY_COLOR_SAMPLER,
+        HotkeyAction::Ruler => HOTKEY_RULER,
+        HotkeyAction::Grid => HOTKEY_GRID,
+        HotkeyAction::Lock => HOTKEY_LOCK,
+        HotkeyAction::Quit => HOTKEY_QUIT,
+    }
+}
+
+/// Virtual-key code of a key, if it has one.
+fn virtual_key(key: Key) -> Option<u32> {
+    match key {
+        Key::Letter(c) if c.is_ascii_uppercase() => Some(c as u32),
+        Key::Letter(_) => None,
+        Key::Comma => Some(0xBC),                    // VK_OEM_COMMA
+        Key::Equal => Some(0xBB),                    // VK_OEM_PLUS
+        Key::Minus => Some(0xBD),                    // VK_OEM_MINUS
+        Key::F(n @ 1..=24) => Some(0x6F + n as u32), // VK_F1 = 0x70
+        Key::F(_) => None,
+    }
+}
+
+/// RegisterHotKey modifiers of a hotkey.
+fn hotkey_modifiers(hotkey: &Hotkey) -> HOT_KEY_MODIFIERS {
+    let mut mods = HOT_KEY_MODIFIERS(0);
+    if hotkey.modifiers & hotkeys::MOD_CTRL != 0 {
+        mods |= MOD_CONTROL;
+    }
+    if hotkey.modifiers & hotkeys::MOD_SHIFT != 0 {
+        mods |= MOD_SHIFT;
+    }
+    if hotkey.modifiers & hotkeys::MOD_ALT != 0 {
+        mods |= MOD_ALT;
+    }
+    // Bare function keys would otherwise repeat while held
+    if hotkey.modifiers == 0 {
+        mods |= MOD_NOREPEAT;
+    }
+    mods
+}
+
+/// Hotkey bindings of the selected profile, with the rebinds made in
+/// Settings in place. Only the toggle hotkey in safe mode.
+pub fn current_hotkey_bindings() -> Vec<Binding> {
+    if config::safe_mode() {
+        return safe_mode_bindings(HotkeyPlatform::Windows).to_vec();
+    }
+    let profile = config::prefs_get_string(PREF_HOTKEY_PROFILE, HOTKEY_PROFILE_STANDARD);
+    let overrides = parse_overrides(&config::prefs_get_string(PREF_HOTKEY_OVERRIDES, ""));
+    apply_overrides(
+        profile_bindings(&profile, HotkeyPlatform::Windows),
+        &overrides,
+    )
+}
+
+/// Global hotkeys registered with `RegisterHotKey` on the overlay window,
+/// which gets `WM_HOTKEY` with the [`hotkey_id`] of the action.
+pub struct WindowsHotkeys {
+    hwnd: HWND,
+}
+
+impl WindowsHotkeys {
+    pub fn new(hwnd: HWND) -> Self {
+        Self { hwnd }
+    }
+}
+
+impl HotkeyManager for WindowsHotkeys {
+    fn register(&mut self, binding: Binding) -> Result<(), HotkeyError> {
+        let vk = virtual_key(binding.hotkey.key).ok_or(HotkeyError::Unsupported(binding.hotkey))?;
+        self.unregister(binding.action);
+        let mods = hotkey_modifiers(&binding.hotkey);
+        unsafe { RegisterHotKey(Some(self.hwnd), hotkey_id(binding.action), mods, vk) }.map_err(
+            |e| HotkeyError::Refused {
+                hotkey: binding.hotkey,
+                reason: e.to_string(),
+            },
+        )?;
+        registered().push(binding);
+        Ok(())
+    }
+
+    fn unregister(&mut self, action: HotkeyAction) {
+        let _ = unsafe { UnregisterHotKey(Some(self.hwnd), hotkey_id(action)) };
+        registered().retain(|b| b.action != action);
+    }
+
+    fn list(&self) -> Vec<Binding> {
+        registered().clone()
+    }
+
+    fn on_trigger(&mut self, handler: TriggerHandler) {
+        *TRIGGER_HANDLER.lock().unwrap_or_else(|e| e.into_inner()) = Some(handler);
+    }
+}
+
+/// Carry out the `WM_HOTKEY` of hotkey ID `id`: call the trigger handler
+/// with its action, or publish the action's event. Returns false for IDs
+/// of no command (the ruler's Esc).
+pub fn hotkey_pressed(id: i32) -> bool {
+    let Some(action) = HotkeyAction::ALL.into_iter().find(|a| hotkey_id(*a) == id) else {
+        return false;
+    };
+    let handler = *TRIGGER_HANDLER.lock().unwrap_or_else(|e| e.into_inner());
+    match handler {
+        Some(handler) => handler(action),
+        None => publish(action.event()),
+    }
+    true
+}
+
+/// Register the global hotkeys of the selected profile.
+pub fn register_hotkeys(hwnd: HWND) {
+    let profile = config::prefs_get_string(PREF_HOTKEY_PROFILE, HOTKEY_PROFILE_STANDARD);
+    let _span = info_span!("register_hotkeys", %profile).entered();
+    register_bindings(&mut WindowsHotkeys::new(hwnd), &current_hotkey_bindings());
+}
+
+/// Unregister every global hotkey, whichever profile registered it.
+pub fn unregister_hotkeys(hwnd: HWND) {
+    unregister_all(&mut WindowsHotkeys::new(hwnd));
+}
+
+/// Take Esc as a global hotkey, to clear the ruler, while its mode is on.
+pub fn register_ruler_escape(hwnd: HWND) {
+    if let Err(e) = unsafe {
+        RegisterHotKey(
+            Some(hwnd),
+            HOTKEY_RULER_ESCAPE,
+            MOD_NOREPEAT,
+            VK_ESCAPE.0 as u32,
+        )
+    } {
+        warn!(
+            "RegisterHotKey failed for Esc (taken by another app?): {}",
+            e
+        );
+    }
+}
+
+/// Give Esc back to the other apps.
+pub fn unregister_ruler_escape(hwnd: HWND) {
+    let _ = unsafe { UnregisterHotKey(Some(hwnd), HOTKEY_RULER_ESCAPE) };
+}
+
+/// Is the hotkey of `action` currently registered?
+pub fn hotkey_registered(action: HotkeyAction) -> bool {
+    registered().iter().any(|b| b.action == action)
+}
 
 // Timer constants
 pub const TIMER_CURSOR: usize = 1;
 pub const TIMER_INTERVAL_MS: u32 = 16; // ~60 FPS
+pub const TIMER_SOAK: usize = 2; // hidden soak-test mode
+pub const TIMER_UPDATE: usize = 3; // is an update check due?
 
 /// Global mouse hook handle (must be static for the hook callback).
 pub static MOUSE_HOOK: AtomicIsize = AtomicIsize::new(0);
 
+/// Install the low-level mouse hook, in place of the one installed
+/// before if any.
+pub fn install_mouse_hook() -> windows::core::Result<()> {
+    unsafe {
+        let old = MOUSE_HOOK.swap(0, Ordering::SeqCst);
+        if old != 0 {
+            // Fails when Windows already removed it
+            let _ = UnhookWindowsHookEx(HHOOK(old as *mut _));
+        }
+        let hook = SetWindowsHookExW(WH_MOUSE_LL, Some(mouse_hook_proc), None, 0)?;
+        MOUSE_HOOK.store(hook.0 as isize, Ordering::SeqCst);
+    }
+    Ok(())
+}
+
+/// Mouse buttons down right now, as `HOOK_BUTTON_*` bits.
+fn polled_buttons() -> u8 {
+    let down = |key: i32| unsafe { GetAsyncKeyState(key) } < 0;
+    let mut buttons = 0;
+    if down(VK_LBUTTON.0 as i32) {
+        buttons |= HOOK_BUTTON_LEFT;
+    }
+    if down(VK_RBUTTON.0 as i32) {
+        buttons |= HOOK_BUTTON_RIGHT;
+    }
+    buttons
+}
+
+/// Install the mouse hook again once it misses the presses and releases
+/// polled, or the buttons polled disagree with the ones it saw for too
+/// long: Windows silently removes a hook that exceeds its timeout. Called
+/// every frame.
+pub fn check_mouse_hook() {
+    let polled = polled_buttons();
+    let removed = STATE.with(|s| {
+        let mut state = s.borrow_mut();
+        state
+            .hook_watchdog
+            .check(polled, wall_clock_secs())
+            .then(|| state.hook_watchdog.reinstalls())
+    });
+    let Some(count) = removed else {
+        return;
+    };
+    match install_mouse_hook() {
+        Ok(()) => warn!(
+            "Mouse hook stopped getting events, reinstalled it ({} so far)",
+            count
+        ),
+        Err(e) => warn!("Mouse hook stopped getting events, reinstall failed: {}", e),
+    }
+}
+
+/// Full pressure in `POINTER_PEN_INFO`.
+const PEN_PRESSURE_MAX: f64 = 1024.0;
+
+/// Touch ID of the primary touch tracked through the mouse hook (pointer
+/// IDs of observed touches start at 1).
+const PRIMARY_TOUCH_ID: u32 = 0;
+
+/// Store the pen pressure, or `None` for the mouse, redrawing on a change.
+fn set_pen_pressure(pressure: Option<f64>) {
+    STATE.with(|s| {
+        let mut state = s.borrow_mut();
+        if state.pen_pressure != pressure {
+            state.pen_pressure = pressure;
+            state.dirty = true;
+        }
+    });
+}
+
+/// Have the overlay window observe touch input wherever it lands, while
+/// the apps under it still get it, so that each finger gets a ring.
+///
+/// Windows allows it only to UIAccess processes; elsewhere the mouse hook
+/// tracks the primary touch alone.
+pub fn observe_touch(hwnd: HWND) {
+    let observed = unsafe { RegisterPointerInputTargetEx(hwnd, PT_TOUCH, true) }.as_bool();
+    if observed {
+        info!("Observing touch input");
+    } else {
+        info!("Touch input not observable, tracking the primary touch only");
+    }
+    STATE.with(|s| s.borrow_mut().touch_observed = observed);
+}
+
+/// Update touch `id` for a `WM_POINTERDOWN`, `WM_POINTERUP` or other
+/// `msg` (a move) at (`x`, `y`).
+fn set_touch(msg: u32, id: u32, x: f64, y: f64) {
+    STATE.with(|s| {
+        let mut state = s.borrow_mut();
+        match msg {
+            WM_POINTERDOWN => state.touches.down(id, x, y),
+            WM_POINTERUP => state.touches.up(id, wall_clock_secs()),
+            _ => state.touches.moved(id, x, y),
+        }
+        state.dirty = true;
+    });
+}
+
+/// Track a `WM_POINTERDOWN`, `WM_POINTERUPDATE` or `WM_POINTERUP`
+/// message `msg` to the overlay window: the pressure of a pen, or the
+/// position of a finger on the touchscreen.
+///
+/// Windows sends them only to the window under the pointer, which the
+/// click-through overlay rarely is unless it observes touch input (see
+/// `observe_touch`); elsewhere the mouse hook still recognizes the pen,
+/// at no pressure.
+pub fn track_pointer(msg: u32, wparam: WPARAM) {
+    let pointer_id = (wparam.0 & 0xFFFF) as u32;
+    let mut kind = POINTER_INPUT_TYPE::default();
+    unsafe {
+        if GetPointerType(pointer_id, &mut kind).is_err() {
+            return;
+        }
+    }
+    if kind == PT_TOUCH {
+        let mut info = POINTER_INFO::default();
+        if unsafe { GetPointerInfo(pointer_id, &mut info) }.is_ok() {
+            let at = info.ptPixelLocation;
+            set_touch(msg, pointer_id, at.x as f64, at.y as f64);
+        }
+        return;
+    }
+    let mut info = POINTER_PEN_INFO::default();
+    unsafe {
+        if kind != PT_PEN || GetPointerPenInfo(pointer_id, &mut info).is_err() {
+            return;
+        }
+    }
+    let pressure = if info
+        .pointerInfo
+        .pointerFlags
+        .contains(POINTER_FLAG_INCONTACT)
+    {
+        info.pressure as f64 / PEN_PRESSURE_MAX
+    } else {
+        0.0
+    };
+    set_pen_pressure(Some(pressure));
+}
+
 /// Low-level mouse hook procedure for detecting mouse button presses.
+///
+/// While the ruler mode is on, left clicks place its points and are not
+/// passed on to the window under the pointer. Messages Windows synthesizes
+/// from pen input mark the pen as driving the pointer, others the mouse;
+/// those from touch input move the primary touch while touch input is not
+/// observed.
 pub extern "system" fn mouse_hook_proc(ncode: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
     unsafe {
         if ncode >= 0 {
+            let info = &*(lparam.0 as *const MSLLHOOKSTRUCT);
+            let pen = is_pen_extra_info(info.dwExtraInfo);
+            // A lifted pen presses no more
+            let known = match wparam.0 as u32 {
+                WM_LBUTTONUP => None,
+                _ => STATE.with(|s| s.borrow().pen_pressure),
+            };
+            set_pen_pressure(pen.then(|| known.unwrap_or(0.0)));
+
+            let observed = STATE.with(|s| s.borrow().touch_observed);
+            if !observed && is_touch_extra_info(info.dwExtraInfo) {
+                let msg = match wparam.0 as u32 {
+                    WM_LBUTTONDOWN => Some(WM_POINTERDOWN),
+                    WM_LBUTTONUP => Some(WM_POINTERUP),
+                    WM_MOUSEMOVE => Some(WM_MOUSEMOVE),
+                    _ => None,
+                };
+                if let Some(msg) = msg {
+                    let (x, y) = (info.pt.x as f64, info.pt.y as f64);
+                    set_touch(msg, PRIMARY_TOUCH_ID, x, y);
+                }
+            }
+        }
+
+        if ncode >= 0 && matches!(wparam.0 as u32, WM_LBUTTONDOWN | WM_LBUTTONUP) {
+            let info = &*(lparam.0 as *const MSLLHOOKSTRUCT);
+            let taken = STATE.with(|s| {
+                let mut state = s.borrow_mut();
+                let Some(ruler) = state.ruler.as_mut() else {
+                    return false;
+                };
+                if wparam.0 as u32 == WM_LBUTTONDOWN {
+                    ruler.click((info.pt.x as f64, info.pt.y as f64));
+                    state.dirty = true;
+                }
+                true
+            });
+            // Swallow the press and its release alike
+            if taken {
+                return LRESULT(1);
+            }
+        }
+
+        if ncode >= 0 {
+            // Buttons that reached the hook, for the watchdog
+            let button = match wparam.0 as u32 {
+                WM_LBUTTONDOWN => Some((HOOK_BUTTON_LEFT, true)),
+                WM_LBUTTONUP => Some((HOOK_BUTTON_LEFT, false)),
+                WM_RBUTTONDOWN => Some((HOOK_BUTTON_RIGHT, true)),
+                WM_RBUTTONUP => Some((HOOK_BUTTON_RIGHT, false)),
+                _ => None,
+            };
+            if let Some((button, down)) = button {
+                STATE.with(|s| s.borrow_mut().hook_watchdog.hook_saw(button, down));
+            }
+
             let new_mode = match wparam.0 as u32 {
                 WM_LBUTTONDOWN => Some(DISPLAY_MODE_LEFT),
                 WM_RBUTTONDOWN => Some(DISPLAY_MODE_RIGHT),
@@ -35,10 +451,60 @@ pub extern "system" fn mouse_hook_proc(ncode: i32, wparam: WPARAM, lparam: LPARA
             };
 
             if let Some(mode) = new_mode {
-                STATE.with(|s| {
+                // Button presses feed the multi-click burst counter and the click log
+                let press =
+                    (mode != DISPLAY_MODE_CIRCLE).then(|| &*(lparam.0 as *const MSLLHOOKSTRUCT));
+                let sampled = STATE.with(|s| {
                     let mut state = s.borrow_mut();
-                    state.display_mode = mode;
+                    let now = wall_clock_secs();
+                    if mode == DISPLAY_MODE_CIRCLE {
+                        state.click_hold.release(now);
+                    } else {
+                        state.click_hold.press(mode, now);
+                    }
+                    state.overlay.display_mode =
+                        state.click_hold.mode(now, state.overlay.click_hold_ms);
+                    if let Some(info) = press {
+                        state.click_count = if state.overlay.click_burst {
+                            let interval = state.overlay.click_burst_ms / 1000.0;
+                            state.burst.press(
+                                mode,
+                                info.pt.x as f64,
+                                info.pt.y as f64,
+                                info.time as f64 / 1000.0,
+                                interval,
+                            )
+                        } else {
+                            state.burst.reset();
+                            1
+                        };
+                        if state.click_log.is_recording() {
+                            let display = monitor_rect_at(info.pt.x, info.pt.y)
+                                .map(|r| display_key(&r))
+                                .unwrap_or_default();
+                            state.click_log.record(ClickRecord {
+                                timestamp: unix_time_now(),
+                                x: info.pt.x as f64,
+                                y: info.pt.y as f64,
+                                button: mode,
+                                display,
+                            });
+                        }
+                    }
+                    // A left click picks the color under the sampler
+                    (mode == DISPLAY_MODE_LEFT)
+                        .then_some(state.color_sample)
+                        .flatten()
+                        .map(|color| (state.hwnd, sample_hex(color)))
                 });
+                // Outside the state borrow: the clipboard messages the old owner
+                if let Some((hwnd, hex)) = sampled {
+                    if set_clipboard_text(hwnd, &hex) {
+                        info!("Copied {} to the clipboard", hex);
+                    } else {
+                        warn!("Clipboard busy, {} not copied", hex);
+                    }
+                }
             }
         }
 
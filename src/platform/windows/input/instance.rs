@@ -0,0 +1,94 @@
+//! Single instance on Windows.
+//!
+//! The running instance holds a named mutex for as long as it lives. A
+//! second launch finds the mutex taken, hands its command-line arguments
+//! to the running instance's overlay window with `WM_COPYDATA` and exits
+//! (see `model::instance`). A restarted process waits for the mutex
+//! instead: Windows hands it over when the old process exits.
+
+use std::time::Duration;
+
+use windows::core::{w, PCWSTR};
+use windows::Win32::Foundation::{
+    GetLastError, ERROR_ALREADY_EXISTS, LPARAM, WAIT_ABANDONED, WAIT_OBJECT_0, WPARAM,
+};
+use windows::Win32::System::DataExchange::COPYDATASTRUCT;
+use windows::Win32::System::Threading::{CreateMutexW, WaitForSingleObject};
+use windows::Win32::UI::WindowsAndMessaging::{FindWindowW, SendMessageW, WM_COPYDATA};
+
+use crate::model::{decode_args, encode_args, INSTANCE_MUTEX, RESTART_FLAG};
+
+/// `COPYDATASTRUCT::dwData` tag of forwarded arguments.
+const COPYDATA_ARGS: usize = 0x4C41_5247; // 'LARG'
+
+/// Take the single-instance mutex, waiting up to `wait` for another
+/// instance to quit. Returns false if another instance still holds it.
+///
+/// The mutex is never released: Windows hands it to the next waiter when
+/// the process exits.
+pub fn claim_instance(wait: Duration) -> bool {
+    let name: Vec<u16> = INSTANCE_MUTEX
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+    unsafe {
+        let Ok(mutex) = CreateMutexW(None, true, PCWSTR(name.as_ptr())) else {
+            // Should not happen; run rather than refuse to start
+            return true;
+        };
+        // Created: owned by this process
+        if GetLastError() != ERROR_ALREADY_EXISTS {
+            return true;
+        }
+        // Opened: owned by another instance, until it exits
+        let ms = wait.as_millis().min(u32::MAX as u128) as u32;
+        let result = WaitForSingleObject(mutex, ms);
+        result == WAIT_OBJECT_0 || result == WAIT_ABANDONED
+    }
+}
+
+/// Start a new instance of the app, which takes over once this one has
+/// quit.
+pub fn relaunch() -> std::io::Result<()> {
+    let exe = std::env::current_exe()?;
+    std::process::Command::new(exe).arg(RESTART_FLAG).spawn()?;
+    Ok(())
+}
+
+/// Hand `args` to the running instance.
+///
+/// Returns false if its overlay window was not found (still starting, or
+/// quitting).
+pub fn forward_args(args: &[String]) -> bool {
+    unsafe {
+        let Ok(hwnd) = FindWindowW(w!("LumbusOverlay"), PCWSTR::null()) else {
+            return false;
+        };
+        let text: Vec<u16> = encode_args(args).encode_utf16().collect();
+        let data = COPYDATASTRUCT {
+            dwData: COPYDATA_ARGS,
+            cbData: (text.len() * 2) as u32,
+            lpData: text.as_ptr() as *mut _,
+        };
+        SendMessageW(
+            hwnd,
+            WM_COPYDATA,
+            Some(WPARAM(0)),
+            Some(LPARAM(&data as *const _ as isize)),
+        );
+        true
+    }
+}
+
+/// Arguments carried by a `WM_COPYDATA` message from [`forward_args`].
+///
+/// # Safety
+/// `lparam` must be the `LPARAM` of a `WM_COPYDATA` message.
+pub unsafe fn copied_args(lparam: LPARAM) -> Option<Vec<String>> {
+    let data = (lparam.0 as *const COPYDATASTRUCT).as_ref()?;
+    if data.dwData != COPYDATA_ARGS || data.lpData.is_null() {
+        return None;
+    }
+    let units = std::slice::from_raw_parts(data.lpData as *const u16, data.cbData as usize / 2);
+    Some(decode_args(&String::from_utf16_lossy(units)))
+}
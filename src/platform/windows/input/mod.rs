@@ -1,8 +1,19 @@
-//! Input handling for Windows (hotkeys, mouse hooks).
+//! Input handling for Windows (hotkeys, mouse hooks, `lumbus://` URLs,
+//! the single-instance handoff, the local control endpoint).
 
+pub mod control;
 pub mod hotkeys;
+pub mod instance;
+pub mod url_scheme;
 
+pub use control::{apply_control_server, publish_control_state};
 pub use hotkeys::{
-    mouse_hook_proc, HOTKEY_HELP, HOTKEY_QUIT, HOTKEY_SETTINGS, HOTKEY_TOGGLE, MOUSE_HOOK,
-    TIMER_CURSOR, TIMER_INTERVAL_MS,
+    check_mouse_hook, current_hotkey_bindings, hotkey_pressed, hotkey_registered,
+    install_mouse_hook, mouse_hook_proc, observe_touch, register_hotkeys, register_ruler_escape,
+    track_pointer, unregister_hotkeys, unregister_ruler_escape, WindowsHotkeys,
+    HOTKEY_COLOR_SAMPLER, HOTKEY_COORDINATES, HOTKEY_GRID, HOTKEY_HELP, HOTKEY_LOCK, HOTKEY_QUIT,
+    HOTKEY_RULER, HOTKEY_RULER_ESCAPE, HOTKEY_SETTINGS, HOTKEY_TOGGLE, HOTKEY_ZOOM_IN,
+    HOTKEY_ZOOM_OUT, MOUSE_HOOK, TIMER_CURSOR, TIMER_INTERVAL_MS, TIMER_SOAK, TIMER_UPDATE,
 };
+pub use instance::{claim_instance, copied_args, forward_args, relaunch};
+pub use url_scheme::{publish_url, register_url_scheme, url_argument};
@@ -0,0 +1,87 @@
+//! `lumbus://` URLs on Windows.
+//!
+//! The scheme is registered per user (`HKCU\Software\Classes\lumbus`) to
+//! start `lumbus.exe "<url>"`. A second instance started that way hands
+//! the URL to the running one (see `input::instance`), which publishes
+//! the URL's events on the event bus (see `model::url_scheme`).
+
+use tracing::warn;
+use windows::core::PCWSTR;
+use windows::Win32::System::Registry::{
+    RegCloseKey, RegCreateKeyExW, RegSetValueExW, HKEY, HKEY_CURRENT_USER, KEY_WRITE,
+    REG_OPTION_NON_VOLATILE, REG_SZ,
+};
+
+use crate::events::publish;
+use crate::model::{url_events, URL_SCHEME};
+
+fn wide(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// Set a REG_SZ value (`None` = default value) under `HKCU\<path>`.
+fn set_user_value(path: &str, name: Option<&str>, value: &str) -> bool {
+    unsafe {
+        let path = wide(path);
+        let mut hkey = HKEY::default();
+        if RegCreateKeyExW(
+            HKEY_CURRENT_USER,
+            PCWSTR(path.as_ptr()),
+            None,
+            PCWSTR::null(),
+            REG_OPTION_NON_VOLATILE,
+            KEY_WRITE,
+            None,
+            &mut hkey,
+            None,
+        )
+        .is_err()
+        {
+            return false;
+        }
+        let name = name.map(wide);
+        let data: Vec<u8> = wide(value).iter().flat_map(|c| c.to_le_bytes()).collect();
+        let ok = RegSetValueExW(
+            hkey,
+            name.as_ref().map_or(PCWSTR::null(), |n| PCWSTR(n.as_ptr())),
+            None,
+            REG_SZ,
+            Some(&data),
+        )
+        .is_ok();
+        let _ = RegCloseKey(hkey);
+        ok
+    }
+}
+
+/// Register the `lumbus://` scheme for the current user, pointing at this
+/// executable (refreshed at every start, so a moved app keeps working).
+pub fn register_url_scheme() {
+    let Ok(exe) = std::env::current_exe() else {
+        return;
+    };
+    let root = format!("Software\\Classes\\{URL_SCHEME}");
+    let command = format!("\"{}\" \"%1\"", exe.display());
+    let ok = set_user_value(&root, None, "URL:Lumbus")
+        && set_user_value(&root, Some("URL Protocol"), "")
+        && set_user_value(&format!("{root}\\shell\\open\\command"), None, &command);
+    if !ok {
+        warn!("Could not register the {URL_SCHEME}:// scheme");
+    }
+}
+
+/// `lumbus://` URL among the command-line arguments, if any.
+pub fn url_argument() -> Option<String> {
+    let prefix = format!("{URL_SCHEME}:");
+    std::env::args()
+        .skip(1)
+        .find(|arg| arg.to_ascii_lowercase().starts_with(&prefix))
+}
+
+/// Publish the events of a `lumbus://` URL on the event bus.
+pub fn publish_url(url: &str) {
+    match url_events(url) {
+        Ok(events) => events.into_iter().for_each(publish),
+        Err(e) => warn!("Ignoring {url}: {e}"),
+    }
+}
@@ -1,59 +1,54 @@
-//! JSON configuration file for Windows.
+//! Configuration file for Windows.
 //!
-//! Stores settings in %APPDATA%/Lumbus/config.json
+//! Stores settings in %APPDATA%/Lumbus/config.toml (format shared with
+//! macOS, see `model::preferences`). Settings from an older
+//! %APPDATA%/Lumbus/config.json are picked up until the first save.
 //!
-//! Uses an in-memory cache to avoid disk I/O on every slider change.
-//! Call `flush_config()` to persist changes to disk.
+//! The cache, sync and safe mode are shared with macOS (see
+//! `storage::ConfigFile`); this file locates the file, imports the legacy
+//! JSON and supplies the admin-managed layer (see `managed`).
 
+use super::managed::with_managed;
 use crate::model::constants::*;
-use crate::model::OverlayState;
-use serde::{Deserialize, Serialize};
+use crate::model::{
+    ColorPalette, ConfigIssue, FrameColors, ManagedConfig, OverlayState, Preferences, SessionStore,
+    PREFERENCES_FILE,
+};
+use crate::storage::ConfigFile;
 use std::cell::RefCell;
 use std::fs;
-use std::path::PathBuf;
-
-/// Serializable config structure for JSON persistence.
-#[derive(Serialize, Deserialize, Debug, Clone)]
-struct Config {
-    radius: f64,
-    border_width: f64,
-    stroke_r: f64,
-    stroke_g: f64,
-    stroke_b: f64,
-    stroke_a: f64,
-    fill_transparency_pct: f64,
-    lang: i32,
-}
-
-impl Default for Config {
-    fn default() -> Self {
-        Self {
-            radius: DEFAULT_DIAMETER / 2.0,
-            border_width: DEFAULT_BORDER_WIDTH,
-            stroke_r: DEFAULT_COLOR.0,
-            stroke_g: DEFAULT_COLOR.1,
-            stroke_b: DEFAULT_COLOR.2,
-            stroke_a: DEFAULT_COLOR.3,
-            fill_transparency_pct: DEFAULT_FILL_TRANSPARENCY_PCT,
-            lang: LANG_EN,
-        }
-    }
-}
+use std::path::{Path, PathBuf};
 
-// In-memory config cache. Loaded once, written on flush.
 thread_local! {
-    static CONFIG_CACHE: RefCell<Option<Config>> = const { RefCell::new(None) };
-    static CONFIG_DIRTY: RefCell<bool> = const { RefCell::new(false) };
+    // In-memory config cache. Loaded once, written on flush.
+    static CONFIG: RefCell<ConfigFile> =
+        RefCell::new(ConfigFile::new(config_path(), import_legacy_config));
 }
 
-/// Get config file path: %APPDATA%/Lumbus/config.json
-fn config_path() -> PathBuf {
+/// Directory holding the config file: %APPDATA%/Lumbus
+fn config_dir() -> PathBuf {
     let appdata = std::env::var("APPDATA").unwrap_or_else(|_| ".".to_string());
-    PathBuf::from(appdata).join("Lumbus").join("config.json")
+    PathBuf::from(appdata).join("Lumbus")
+}
+
+/// Get config file path: %APPDATA%/Lumbus/config.toml
+pub fn config_path() -> PathBuf {
+    config_dir().join(PREFERENCES_FILE)
+}
+
+/// Get log folder path: %LOCALAPPDATA%/Lumbus/Logs
+pub fn log_dir() -> PathBuf {
+    let local = std::env::var("LOCALAPPDATA").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(local).join("Lumbus").join("Logs")
+}
+
+/// Config file written by versions before config.toml.
+fn legacy_config_path() -> PathBuf {
+    config_dir().join("config.json")
 }
 
 /// Ensure the config directory exists.
-fn ensure_config_dir() -> std::io::Result<()> {
+pub fn ensure_config_dir() -> std::io::Result<()> {
     let path = config_path();
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
@@ -61,159 +56,175 @@ fn ensure_config_dir() -> std::io::Result<()> {
     Ok(())
 }
 
-/// Load config from JSON file, returning defaults if not found or invalid.
-fn load_config_from_disk() -> Config {
-    let path = config_path();
-    match fs::read_to_string(&path) {
-        Ok(contents) => serde_json::from_str(&contents).unwrap_or_default(),
-        Err(_) => Config::default(),
-    }
+/// Settings from the legacy JSON file over `base`, if there is one.
+fn import_legacy_config(base: &Preferences) -> Option<Preferences> {
+    let contents = fs::read_to_string(legacy_config_path()).ok()?;
+    Some(overlay_legacy_config(base.clone(), &contents))
 }
 
-/// Save config to JSON file.
-fn save_config_to_disk(config: &Config) {
-    if ensure_config_dir().is_err() {
-        eprintln!("Failed to create config directory");
-        return;
-    }
+/// Run `f` on the cached config and the admin-managed layer.
+fn with_config<R>(f: impl FnOnce(&mut ConfigFile, &ManagedConfig) -> R) -> R {
+    with_managed(|managed| CONFIG.with(|config| f(&mut config.borrow_mut(), managed)))
+}
 
-    let path = config_path();
-    match serde_json::to_string_pretty(config) {
-        Ok(json) => {
-            if let Err(e) = fs::write(&path, json) {
-                eprintln!("Failed to write config: {}", e);
-            }
-        }
-        Err(e) => eprintln!("Failed to serialize config: {}", e),
-    }
+/// Re-read the config file, and the synced copy, after either changed on
+/// disk.
+///
+/// Returns true if the settings may have changed.
+pub fn reload_config() -> bool {
+    with_config(|config, managed| config.reload(managed))
+}
+
+/// Folder the settings are synced through, if sync is on.
+pub fn sync_folder() -> Option<PathBuf> {
+    with_config(|config, managed| config.sync_folder(managed))
+}
+
+/// Sync the settings through `folder`, or stop syncing with `None`.
+///
+/// Settings already synced to the folder by another machine replace this
+/// machine's; otherwise this machine's settings are written there.
+pub fn set_sync_folder(folder: Option<&Path>) {
+    with_config(|config, managed| config.set_sync_folder(folder, managed));
+}
+
+/// Problems found in the config file when it was loaded.
+///
+/// Returned once; later calls return nothing.
+pub fn take_config_issues() -> Vec<ConfigIssue> {
+    with_config(|config, managed| config.take_issues(managed))
+}
+
+/// Overlay the user's legacy JSON on `base`, keeping `base` for missing fields.
+fn overlay_legacy_config(base: Preferences, contents: &str) -> Preferences {
+    let Ok(serde_json::Value::Object(user)) = serde_json::from_str(contents) else {
+        return base;
+    };
+    let Ok(serde_json::Value::Object(mut merged)) = serde_json::to_value(&base) else {
+        return base;
+    };
+    merged.extend(user);
+    serde_json::from_value(serde_json::Value::Object(merged)).unwrap_or(base)
 }
 
 /// Get the cached config, loading from disk if needed.
-fn get_config() -> Config {
-    CONFIG_CACHE.with(|cache| {
-        let mut cache = cache.borrow_mut();
-        if cache.is_none() {
-            *cache = Some(load_config_from_disk());
-        }
-        cache.clone().unwrap()
-    })
-}
-
-/// Update the cached config and mark it dirty.
-fn set_config(config: Config) {
-    CONFIG_CACHE.with(|cache| {
-        *cache.borrow_mut() = Some(config);
-    });
-    CONFIG_DIRTY.with(|dirty| {
-        *dirty.borrow_mut() = true;
-    });
+fn get_config() -> Preferences {
+    with_config(|config, managed| config.get(managed))
+}
+
+/// Get the cached config with the admin-locked settings applied.
+fn effective_config() -> Preferences {
+    with_config(|config, managed| config.effective(managed))
+}
+
+/// Start a safe-mode session: the defaults (the admin-managed ones where
+/// set) replace the settings file, which is not read or written until
+/// the app quits.
+pub fn enter_safe_mode() {
+    with_config(|config, managed| config.enter_safe_mode(managed));
+}
+
+/// Is this a safe-mode session?
+pub fn safe_mode() -> bool {
+    CONFIG.with(|config| config.borrow().safe_mode())
 }
 
 /// Flush the config cache to disk if dirty.
 ///
 /// Call this when settings window closes or app exits.
 pub fn flush_config() {
-    let is_dirty = CONFIG_DIRTY.with(|dirty| *dirty.borrow());
-    if !is_dirty {
-        return;
-    }
-
-    CONFIG_CACHE.with(|cache| {
-        if let Some(ref config) = *cache.borrow() {
-            save_config_to_disk(config);
-        }
-    });
+    CONFIG.with(|config| config.borrow_mut().flush());
+}
 
-    CONFIG_DIRTY.with(|dirty| {
-        *dirty.borrow_mut() = false;
-    });
+/// Put the Settings window's settings back to their defaults (the
+/// admin-managed ones where set) and save them at once.
+pub fn restore_defaults() {
+    with_config(|config, managed| config.restore_defaults(managed));
 }
 
 /// Load state from config file.
 pub fn load_state() -> OverlayState {
-    let config = get_config();
-    let mut state = OverlayState {
-        radius: config.radius,
-        border_width: config.border_width,
-        stroke_r: config.stroke_r,
-        stroke_g: config.stroke_g,
-        stroke_b: config.stroke_b,
-        stroke_a: config.stroke_a,
-        fill_transparency_pct: config.fill_transparency_pct,
-        lang: config.lang,
-        overlay_enabled: true,
-        display_mode: DISPLAY_MODE_CIRCLE,
-    };
-    state.validate();
-    state
+    effective_config().to_state()
 }
 
 /// Save state to config file.
 ///
 /// Note: This updates the cache immediately but only writes to disk
-/// when `flush_config()` is called.
+/// when `flush_config()` is called. Locked settings keep their stored value.
 pub fn save_state(state: &OverlayState) {
-    let config = Config {
-        radius: state.radius,
-        border_width: state.border_width,
-        stroke_r: state.stroke_r,
-        stroke_g: state.stroke_g,
-        stroke_b: state.stroke_b,
-        stroke_a: state.stroke_a,
-        fill_transparency_pct: state.fill_transparency_pct,
-        lang: state.lang,
-    };
-    set_config(config);
+    with_config(|config, managed| config.save_state(state, managed));
 }
 
-/// Read a double from config (from cache).
+/// Read a double from config (from cache, locked settings applied).
 pub fn prefs_get_double(key: &str, default: f64) -> f64 {
-    let config = get_config();
-    match key {
-        PREF_RADIUS => config.radius,
-        PREF_BORDER => config.border_width,
-        PREF_STROKE_R => config.stroke_r,
-        PREF_STROKE_G => config.stroke_g,
-        PREF_STROKE_B => config.stroke_b,
-        PREF_STROKE_A => config.stroke_a,
-        PREF_FILL_TRANSPARENCY => config.fill_transparency_pct,
-        _ => default,
-    }
+    effective_config().number(key).unwrap_or(default)
 }
 
 /// Write a double to config (to cache, flush later).
+///
+/// Ignored for settings locked by the administrator.
 pub fn prefs_set_double(key: &str, val: f64) {
-    let mut config = get_config();
-    match key {
-        PREF_RADIUS => config.radius = val,
-        PREF_BORDER => config.border_width = val,
-        PREF_STROKE_R => config.stroke_r = val,
-        PREF_STROKE_G => config.stroke_g = val,
-        PREF_STROKE_B => config.stroke_b = val,
-        PREF_STROKE_A => config.stroke_a = val,
-        PREF_FILL_TRANSPARENCY => config.fill_transparency_pct = val,
-        _ => return,
-    }
-    set_config(config);
+    with_config(|config, managed| config.set_number(key, val, managed));
+}
+
+/// Read a string from config (from cache).
+pub fn prefs_get_string(key: &str, default: &str) -> String {
+    get_config().string(key).unwrap_or(default).to_string()
+}
+
+/// Write a string to config (to cache, flush later).
+pub fn prefs_set_string(key: &str, val: &str) {
+    with_config(|config, managed| config.set_string(key, val, managed));
+}
+
+/// Load the auxiliary-window session.
+pub fn load_session() -> SessionStore {
+    SessionStore::decode(&prefs_get_string(PREF_SESSION, ""))
+}
+
+/// Save the auxiliary-window session and flush it to disk.
+///
+/// Session changes are rare and must survive an abrupt exit, so they are
+/// written immediately instead of waiting for the next flush.
+pub fn save_session(session: &SessionStore) {
+    prefs_set_string(PREF_SESSION, &session.encode());
+    flush_config();
+}
+
+/// Load the per-display recording-frame colors.
+pub fn load_frame_colors() -> FrameColors {
+    FrameColors::decode(&prefs_get_string(PREF_SCREEN_FRAME_COLORS, ""))
+}
+
+/// Save the per-display recording-frame colors (to cache, flush later).
+pub fn save_frame_colors(colors: &FrameColors) {
+    prefs_set_string(PREF_SCREEN_FRAME_COLORS, &colors.encode());
 }
 
-/// Read an integer from config (from cache).
+/// Load the colors saved to the Settings palette.
+pub fn load_color_palette() -> ColorPalette {
+    ColorPalette::decode(&prefs_get_string(PREF_COLOR_PALETTE, ""))
+}
+
+/// Save the colors of the Settings palette and flush them to disk.
+pub fn save_color_palette(palette: &ColorPalette) {
+    prefs_set_string(PREF_COLOR_PALETTE, &palette.encode());
+    flush_config();
+}
+
+/// Read an integer from config (from cache, locked settings applied).
 pub fn prefs_get_int(key: &str, default: i32) -> i32 {
-    let config = get_config();
-    match key {
-        PREF_LANG => config.lang,
-        _ => default,
-    }
+    effective_config()
+        .number(key)
+        .map(|v| v.round() as i32)
+        .unwrap_or(default)
 }
 
 /// Write an integer to config (to cache, flush later).
+///
+/// Ignored for settings locked by the administrator.
 pub fn prefs_set_int(key: &str, val: i32) {
-    let mut config = get_config();
-    match key {
-        PREF_LANG => config.lang = val,
-        _ => return,
-    }
-    set_config(config);
+    prefs_set_double(key, val as f64);
 }
 
 #[cfg(test)]
@@ -221,27 +232,25 @@ mod tests {
     use super::*;
 
     #[test]
-    fn config_default_values() {
-        let config = Config::default();
-        assert!((config.radius - DEFAULT_DIAMETER / 2.0).abs() < f64::EPSILON);
-        assert_eq!(config.lang, LANG_EN);
+    fn legacy_config_overlays_managed_defaults() {
+        let mut base = Preferences::default();
+        base.set_number(PREF_RADIUS, 30.0);
+        base.set_number(PREF_SPEED_FADE, 1.0);
+        let loaded = overlay_legacy_config(base, r#"{"radius": 45.0}"#);
+        assert!((loaded.radius - 45.0).abs() < f64::EPSILON);
+        assert!(loaded.speed_fade);
     }
 
     #[test]
-    fn config_serialization_roundtrip() {
-        let config = Config {
-            radius: 42.0,
-            border_width: 3.0,
-            stroke_r: 0.5,
-            stroke_g: 0.6,
-            stroke_b: 0.7,
-            stroke_a: 1.0,
-            fill_transparency_pct: 50.0,
-            lang: LANG_ES,
-        };
-        let json = serde_json::to_string(&config).unwrap();
-        let loaded: Config = serde_json::from_str(&json).unwrap();
+    fn legacy_config_keeps_every_setting() {
+        let json = r#"{"radius": 42.0, "lang": 1, "highlight_style": 1,
+            "halo": true, "session": "help=1"}"#;
+        let loaded = overlay_legacy_config(Preferences::default(), json);
         assert!((loaded.radius - 42.0).abs() < f64::EPSILON);
-        assert_eq!(loaded.lang, LANG_ES);
+        assert_eq!(loaded.lang, "es");
+        assert_eq!(loaded.highlight_style, HIGHLIGHT_STYLE_LASER);
+        assert!(loaded.halo);
+        assert_eq!(loaded.session, "help=1");
+        assert_eq!(loaded.motion, MOTION_SYSTEM);
     }
 }
@@ -0,0 +1,179 @@
+//! Admin-managed configuration for Windows.
+//!
+//! Two read-only sources are merged, the policy key winning per setting:
+//!
+//! - `%ProgramData%/Lumbus/managed.json`:
+//!   `{"defaults": {"radius": 40}, "locked": {"highlightStyle": 1}}`
+//! - `HKLM\SOFTWARE\Policies\Lumbus\Defaults` and `...\Locked`: one value
+//!   per preference key, as REG_DWORD or a numeric REG_SZ (a locale tag
+//!   such as `es` for `lang`).
+//!
+//! Precedence rules are documented in `model::managed`.
+
+use crate::model::{ManagedConfig, MANAGED_KEYS};
+use std::cell::RefCell;
+use std::fs;
+use std::path::PathBuf;
+use windows::core::PCWSTR;
+use windows::Win32::System::Registry::{
+    RegCloseKey, RegOpenKeyExW, RegQueryValueExW, HKEY, HKEY_LOCAL_MACHINE, KEY_READ, REG_DWORD,
+    REG_SZ, REG_VALUE_TYPE,
+};
+
+/// Policy key holding the managed values.
+const POLICY_KEY: &str = "SOFTWARE\\Policies\\Lumbus";
+
+thread_local! {
+    static MANAGED: RefCell<Option<ManagedConfig>> = const { RefCell::new(None) };
+}
+
+/// Get managed file path: %ProgramData%/Lumbus/managed.json
+fn managed_json_path() -> PathBuf {
+    let data = std::env::var("ProgramData").unwrap_or_else(|_| "C:\\ProgramData".to_string());
+    PathBuf::from(data).join("Lumbus").join("managed.json")
+}
+
+/// Parse the ProgramData JSON. Invalid files or entries are ignored.
+fn parse_managed_json(contents: &str) -> ManagedConfig {
+    let mut managed = ManagedConfig::new();
+    let Ok(root) = serde_json::from_str::<serde_json::Value>(contents) else {
+        return managed;
+    };
+    let number = |v: &serde_json::Value| v.as_f64().or_else(|| v.as_bool().map(f64::from));
+    if let Some(defaults) = root.get("defaults").and_then(|v| v.as_object()) {
+        for (key, value) in defaults {
+            if let Some(v) = number(value) {
+                managed.set_default(key, v);
+            } else if let Some(text) = value.as_str() {
+                managed.set_default_text(key, text);
+            }
+        }
+    }
+    if let Some(locked) = root.get("locked").and_then(|v| v.as_object()) {
+        for (key, value) in locked {
+            if let Some(v) = number(value) {
+                managed.lock(key, v);
+            } else if let Some(text) = value.as_str() {
+                managed.lock_text(key, text);
+            }
+        }
+    }
+    managed
+}
+
+fn wide(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// Read the managed keys under an HKLM policy subkey into `store`, as
+/// text.
+fn read_policy_key(subkey: &str, mut store: impl FnMut(&str, &str)) {
+    unsafe {
+        let path = wide(&format!("{}\\{}", POLICY_KEY, subkey));
+        let mut hkey = HKEY::default();
+        if RegOpenKeyExW(
+            HKEY_LOCAL_MACHINE,
+            PCWSTR(path.as_ptr()),
+            Some(0),
+            KEY_READ,
+            &mut hkey,
+        )
+        .is_err()
+        {
+            return;
+        }
+
+        for key in MANAGED_KEYS {
+            let name = wide(key);
+            let mut data = [0u8; 128];
+            let mut data_size = data.len() as u32;
+            let mut data_type = REG_VALUE_TYPE::default();
+            if RegQueryValueExW(
+                hkey,
+                PCWSTR(name.as_ptr()),
+                None,
+                Some(&mut data_type),
+                Some(data.as_mut_ptr()),
+                Some(&mut data_size),
+            )
+            .is_err()
+            {
+                continue;
+            }
+
+            let value = if data_type == REG_DWORD && data_size >= 4 {
+                Some(u32::from_le_bytes([data[0], data[1], data[2], data[3]]).to_string())
+            } else if data_type == REG_SZ {
+                let units: Vec<u16> = data[..data_size as usize]
+                    .chunks_exact(2)
+                    .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                    .take_while(|&c| c != 0)
+                    .collect();
+                Some(String::from_utf16_lossy(&units))
+            } else {
+                None
+            };
+            if let Some(v) = value {
+                store(key, &v);
+            }
+        }
+
+        let _ = RegCloseKey(hkey);
+    }
+}
+
+/// Load both managed sources, the policy key taking precedence.
+fn load_managed() -> ManagedConfig {
+    let mut managed = fs::read_to_string(managed_json_path())
+        .map(|contents| parse_managed_json(&contents))
+        .unwrap_or_default();
+
+    let mut policy = ManagedConfig::new();
+    read_policy_key("Defaults", |k, v| {
+        policy.set_default_text(k, v);
+    });
+    read_policy_key("Locked", |k, v| {
+        policy.lock_text(k, v);
+    });
+    managed.merge(&policy);
+    managed
+}
+
+/// Run `f` with the managed layer, loading it on first use.
+pub fn with_managed<R>(f: impl FnOnce(&ManagedConfig) -> R) -> R {
+    MANAGED.with(|m| {
+        let mut m = m.borrow_mut();
+        if m.is_none() {
+            *m = Some(load_managed());
+        }
+        f(m.as_ref().unwrap())
+    })
+}
+
+/// Is this setting locked by the administrator?
+pub fn is_setting_locked(key: &str) -> bool {
+    with_managed(|m| m.is_locked(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::constants::*;
+
+    #[test]
+    fn parses_defaults_and_locked_sections() {
+        let managed = parse_managed_json(
+            r#"{"defaults": {"radius": 40, "speedFade": true}, "locked": {"highlightStyle": 1}}"#,
+        );
+        assert_eq!(managed.default_value(PREF_RADIUS), Some(40.0));
+        assert_eq!(managed.default_value(PREF_SPEED_FADE), Some(1.0));
+        assert_eq!(managed.locked_value(PREF_HIGHLIGHT_STYLE), Some(1.0));
+    }
+
+    #[test]
+    fn ignores_invalid_json_and_unknown_entries() {
+        assert!(parse_managed_json("not json").is_empty());
+        let managed = parse_managed_json(r#"{"locked": {"session": 1, "radius": "big"}}"#);
+        assert!(managed.is_empty());
+    }
+}
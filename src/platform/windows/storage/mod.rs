@@ -1,7 +1,13 @@
 //! Configuration storage for Windows.
 //!
-//! Persists settings to a JSON file in %APPDATA%/Lumbus/config.json
+//! Persists settings to a TOML file in %APPDATA%/Lumbus/config.toml and
+//! reads the admin-managed layer (HKLM policy key, ProgramData JSON).
+//! Edits made to the file while running are picked up by `watch`.
 
 pub mod config;
+pub mod managed;
+pub mod watch;
 
 pub use config::*;
+pub use managed::*;
+pub use watch::*;
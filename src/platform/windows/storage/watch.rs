@@ -0,0 +1,101 @@
+//! Watching the config file for edits made outside the app.
+//!
+//! A background thread per directory (the config directory and the sync
+//! folder) waits on ReadDirectoryChangesW and posts `WM_CONFIG_CHANGED`
+//! to the main window; the main thread then re-reads the files (see
+//! `config::reload_config`).
+
+use std::cell::RefCell;
+use std::os::windows::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+
+use tracing::warn;
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{CloseHandle, HWND, LPARAM, WPARAM};
+use windows::Win32::Storage::FileSystem::{
+    CreateFileW, ReadDirectoryChangesW, FILE_FLAG_BACKUP_SEMANTICS, FILE_LIST_DIRECTORY,
+    FILE_NOTIFY_CHANGE_FILE_NAME, FILE_NOTIFY_CHANGE_LAST_WRITE, FILE_SHARE_DELETE,
+    FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
+};
+use windows::Win32::UI::WindowsAndMessaging::{PostMessageW, WM_USER};
+
+use super::config::{config_path, ensure_config_dir, sync_folder};
+
+/// Posted to the main window when something in a watched directory changed.
+pub const WM_CONFIG_CHANGED: u32 = WM_USER + 2;
+
+thread_local! {
+    static WATCHED: RefCell<Vec<PathBuf>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Post `WM_CONFIG_CHANGED` to `hwnd` whenever the config directory or
+/// the sync folder changes.
+pub fn watch_config(hwnd: HWND) {
+    // The directory must exist to be watched
+    let _ = ensure_config_dir();
+    if let Some(dir) = config_path().parent() {
+        watch_directory(hwnd, dir);
+    }
+    if let Some(folder) = sync_folder() {
+        watch_directory(hwnd, &folder);
+    }
+}
+
+/// Post `WM_CONFIG_CHANGED` to `hwnd` whenever `dir` changes.
+///
+/// Each directory is watched once; the watcher thread runs for the rest
+/// of the process.
+pub fn watch_directory(hwnd: HWND, dir: &Path) {
+    let dir = dir.to_path_buf();
+    if WATCHED.with(|w| w.borrow().contains(&dir)) {
+        return;
+    }
+    WATCHED.with(|w| w.borrow_mut().push(dir.clone()));
+    // HWND is not Send; pass it as an integer
+    let hwnd_raw = hwnd.0 as isize;
+    std::thread::spawn(move || {
+        let wide: Vec<u16> = dir.as_os_str().encode_wide().chain(Some(0)).collect();
+        let Ok(handle) = (unsafe {
+            CreateFileW(
+                PCWSTR(wide.as_ptr()),
+                FILE_LIST_DIRECTORY.0,
+                FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE,
+                None,
+                OPEN_EXISTING,
+                FILE_FLAG_BACKUP_SEMANTICS,
+                None,
+            )
+        }) else {
+            warn!("Failed to watch {}", dir.display());
+            return;
+        };
+        let hwnd = HWND(hwnd_raw as *mut _);
+        // The notifications themselves are not needed, only that one arrived
+        let mut buffer = [0u32; 1024];
+        loop {
+            let mut returned = 0u32;
+            let ok = unsafe {
+                ReadDirectoryChangesW(
+                    handle,
+                    buffer.as_mut_ptr().cast(),
+                    std::mem::size_of_val(&buffer) as u32,
+                    false,
+                    FILE_NOTIFY_CHANGE_LAST_WRITE | FILE_NOTIFY_CHANGE_FILE_NAME,
+                    Some(&mut returned),
+                    None,
+                    None,
+                )
+            };
+            if ok.is_err() {
+                break;
+            }
+            if unsafe { PostMessageW(Some(hwnd), WM_CONFIG_CHANGED, WPARAM(0), LPARAM(0)) }.is_err()
+            {
+                break;
+            }
+        }
+        unsafe {
+            let _ = CloseHandle(handle);
+        }
+    });
+}
@@ -1,31 +1,31 @@
 //! About dialog for Windows.
 //!
-//! Shows application name, version, and description.
+//! Shows application name, version, build hash, description, license and
+//! project links.
 
 use windows::core::PCWSTR;
 use windows::Win32::Foundation::HWND;
 use windows::Win32::UI::WindowsAndMessaging::{MessageBoxW, MB_ICONINFORMATION, MB_OK};
 
-/// Application version from Cargo.toml
-const VERSION: &str = env!("CARGO_PKG_VERSION");
-
-/// GitHub repository URL
-const REPO_URL: &str = "https://github.com/restevean/lumbus";
+use crate::i18n::{tr, tr_args};
+use crate::model::about::{version_label, ABOUT_LINKS, BUILD_HASH, COPYRIGHT, LICENSE, VERSION};
 
 /// Show the About dialog.
 pub fn show_about_dialog(hwnd: HWND) {
-    let title = "About Lumbus";
+    let title = tr("About Lumbus");
+    let version = version_label(VERSION, BUILD_HASH);
+    let version = tr_args("version-label", &[("version", &version)]).unwrap_or(version);
+    let links: Vec<String> = ABOUT_LINKS
+        .iter()
+        .map(|(key, url)| format!("{}: {}", tr(key), url))
+        .collect();
     let message = format!(
-        "Lumbus v{}\n\n\
-        Mouse pointer highlighter for presentations\n\
-        and screen recordings.\n\n\
-        Features:\n\
-        • Configurable circle overlay\n\
-        • Click indicators (L/R)\n\
-        • Customizable colors and size\n\n\
-        {}\n\n\
-        © 2026 Rafael Esteve. Apache License 2.0.",
-        VERSION, REPO_URL
+        "Lumbus\n{}\n\n{}\n\n{}\n\n{} · {}",
+        version,
+        tr("Mouse pointer highlighter for presentations and screen recordings."),
+        links.join("\n"),
+        COPYRIGHT,
+        LICENSE
     );
 
     let title_wide: Vec<u16> = title.encode_utf16().chain(std::iter::once(0)).collect();
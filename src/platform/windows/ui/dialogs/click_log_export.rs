@@ -0,0 +1,63 @@
+//! Save dialog for exporting the click log on Windows.
+
+use tracing::error;
+use windows::core::{PCWSTR, PWSTR};
+use windows::Win32::Foundation::HWND;
+use windows::Win32::UI::Controls::Dialogs::{
+    GetSaveFileNameW, OFN_NOCHANGEDIR, OFN_OVERWRITEPROMPT, OFN_PATHMUSTEXIST, OPENFILENAMEW,
+};
+
+use crate::i18n::tr;
+use crate::model::click_log::default_file_name;
+use crate::model::ClickLogFormat;
+use crate::platform::windows::app::state::STATE;
+
+/// Ask for a destination file and write the recorded clicks to it.
+///
+/// Blocks until the dialog is dismissed. Write errors are logged.
+pub fn export_click_log(hwnd: HWND, format: ClickLogFormat) {
+    let (filter, title) = match format {
+        ClickLogFormat::Csv => ("CSV (*.csv)\0*.csv\0", "CSV"),
+        ClickLogFormat::Json => ("JSON (*.json)\0*.json\0", "JSON"),
+    };
+    let title = format!("{} ({title})", tr("Export click log"));
+    let filter: Vec<u16> = filter.encode_utf16().chain(std::iter::once(0)).collect();
+    let title: Vec<u16> = title.encode_utf16().chain(std::iter::once(0)).collect();
+    let def_ext: Vec<u16> = format
+        .extension()
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+
+    // File name buffer, pre-filled with the default name
+    let mut file_buf = [0u16; 1024];
+    for (i, c) in default_file_name(format).encode_utf16().enumerate() {
+        file_buf[i] = c;
+    }
+
+    let mut ofn = OPENFILENAMEW {
+        lStructSize: std::mem::size_of::<OPENFILENAMEW>() as u32,
+        hwndOwner: hwnd,
+        lpstrFilter: PCWSTR(filter.as_ptr()),
+        lpstrFile: PWSTR(file_buf.as_mut_ptr()),
+        nMaxFile: file_buf.len() as u32,
+        lpstrTitle: PCWSTR(title.as_ptr()),
+        lpstrDefExt: PCWSTR(def_ext.as_ptr()),
+        Flags: OFN_OVERWRITEPROMPT | OFN_PATHMUSTEXIST | OFN_NOCHANGEDIR,
+        ..Default::default()
+    };
+
+    if !unsafe { GetSaveFileNameW(&mut ofn) }.as_bool() {
+        return;
+    }
+
+    let len = file_buf
+        .iter()
+        .position(|&c| c == 0)
+        .unwrap_or(file_buf.len());
+    let path = String::from_utf16_lossy(&file_buf[..len]);
+    let text = STATE.with(|s| s.borrow().click_log.export(format));
+    if let Err(e) = std::fs::write(&path, text) {
+        error!("Failed to export click log to {}: {}", path, e);
+    }
+}
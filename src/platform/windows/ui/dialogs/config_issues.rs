@@ -0,0 +1,39 @@
+//! Dialog reporting problems in the settings file.
+//!
+//! Shown once at startup when `config.toml` had settings that could not
+//! be used as written (see `model::preferences`).
+
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::HWND;
+use windows::Win32::UI::WindowsAndMessaging::{
+    MessageBoxW, MB_ICONWARNING, MB_OK, MB_SETFOREGROUND,
+};
+
+use crate::i18n::{tr, tr_args};
+use crate::model::describe_issues;
+use crate::platform::windows::storage::config;
+
+/// Show the problems found in the settings file, if any.
+pub fn show_config_issues(hwnd: HWND) {
+    let issues = config::take_config_issues();
+    if issues.is_empty() {
+        return;
+    }
+
+    let path = config::config_path().display().to_string();
+    let title = tr("Settings file problems");
+    let intro = tr_args("settings-file-problems-detail", &[("path", &path)]).unwrap_or(path);
+    let message = format!("{}\n\n{}", intro, describe_issues(&issues));
+
+    let title_wide: Vec<u16> = title.encode_utf16().chain(std::iter::once(0)).collect();
+    let message_wide: Vec<u16> = message.encode_utf16().chain(std::iter::once(0)).collect();
+
+    unsafe {
+        MessageBoxW(
+            Some(hwnd),
+            PCWSTR(message_wide.as_ptr()),
+            PCWSTR(title_wide.as_ptr()),
+            MB_OK | MB_ICONWARNING | MB_SETFOREGROUND,
+        );
+    }
+}
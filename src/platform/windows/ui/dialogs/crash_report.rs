@@ -0,0 +1,43 @@
+//! Message box shown after a panic, offering to reveal the crash report.
+//!
+//! Called from the panic hook (see `logging::install_panic_hook`) once the
+//! report is written. The box runs on a thread of its own so its message
+//! loop cannot re-enter the overlay while the panicking code still holds
+//! the app state.
+
+use std::path::Path;
+use std::process::Command;
+use std::thread;
+
+use windows::core::PCWSTR;
+use windows::Win32::UI::WindowsAndMessaging::{
+    MessageBoxW, IDYES, MB_ICONERROR, MB_SETFOREGROUND, MB_TOPMOST, MB_YESNO,
+};
+
+use crate::i18n::{tr, tr_args};
+
+/// Tell the user the app crashed and offer to show the report at `path`
+/// in Explorer.
+pub fn show_crash_report(path: &Path) {
+    let path = path.display().to_string();
+    let title = tr("Lumbus crashed");
+    let detail = tr_args("crash-report-detail", &[("path", &path)]).unwrap_or(path.clone());
+    let message = format!("{}\n\n{}", detail, tr("Show the report now?"));
+
+    let title_wide: Vec<u16> = title.encode_utf16().chain(std::iter::once(0)).collect();
+    let message_wide: Vec<u16> = message.encode_utf16().chain(std::iter::once(0)).collect();
+
+    let dialog = thread::spawn(move || unsafe {
+        MessageBoxW(
+            None,
+            PCWSTR(message_wide.as_ptr()),
+            PCWSTR(title_wide.as_ptr()),
+            MB_YESNO | MB_ICONERROR | MB_SETFOREGROUND | MB_TOPMOST,
+        )
+    });
+    if dialog.join().is_ok_and(|answer| answer == IDYES) {
+        let _ = Command::new("explorer")
+            .arg(format!("/select,{}", path))
+            .spawn();
+    }
+}
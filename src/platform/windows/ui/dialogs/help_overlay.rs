@@ -1,37 +1,126 @@
 //! Help overlay showing keyboard shortcuts for Windows.
 //!
-//! Displays a dialog with all available hotkeys.
+//! Displays a dialog with the hotkeys in use: the selected profile with
+//! the user's rebinds, those another app holds marked as unavailable.
 //! Dismisses on button click or Enter key.
+//!
+//! The dialog can be dragged by its title bar. Where it is dropped, it
+//! snaps to nearby edges of its monitor and the position is remembered
+//! for that monitor, as on macOS.
 
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicIsize, Ordering};
 
 use windows::core::PCWSTR;
-use windows::Win32::Foundation::HWND;
-use windows::Win32::UI::WindowsAndMessaging::{MessageBoxW, MB_ICONINFORMATION, MB_OK};
+use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, POINT, RECT, WPARAM};
+use windows::Win32::System::Threading::GetCurrentThreadId;
+use windows::Win32::UI::WindowsAndMessaging::{
+    CallNextHookEx, GetCursorPos, GetWindowRect, MessageBoxW, SetWindowPos, SetWindowsHookExW,
+    UnhookWindowsHookEx, CWPRETSTRUCT, HCBT_ACTIVATE, HHOOK, MB_ICONINFORMATION, MB_OK,
+    SWP_NOACTIVATE, SWP_NOSIZE, SWP_NOZORDER, WH_CALLWNDPROCRET, WH_CBT, WM_EXITSIZEMOVE,
+};
+
+use crate::i18n::tr;
+use crate::model::help_entries;
+use crate::model::placement::{clamp_to_screen, display_key, snap_to_edges};
+use crate::model::session::SESSION_HELP_OVERLAY;
+use crate::platform::windows::ffi::{monitor_rect_at, monitor_rect_for_window};
+use crate::platform::windows::input::{current_hotkey_bindings, hotkey_registered};
+use crate::platform::windows::storage::config;
 
 /// Guard to prevent multiple help overlays
 static HELP_OPENING: AtomicBool = AtomicBool::new(false);
 
-/// All hotkeys to display (Windows versions)
-const HOTKEYS_EN: &[(&str, &str)] = &[
-    ("Ctrl + Shift + A", "Toggle overlay"),
-    ("Ctrl + Shift + S", "Open settings"),
-    ("Ctrl + Shift + H", "Show help"),
-    ("Ctrl + Shift + Q", "Quit application"),
-];
-
-const HOTKEYS_ES: &[(&str, &str)] = &[
-    ("Ctrl + Shift + A", "Alternar overlay"),
-    ("Ctrl + Shift + S", "Abrir configuración"),
-    ("Ctrl + Shift + H", "Mostrar ayuda"),
-    ("Ctrl + Shift + Q", "Salir de la aplicación"),
-];
+/// The help dialog once it has been placed (0 before).
+static HELP_DIALOG: AtomicIsize = AtomicIsize::new(0);
+
+/// Origin and size of a window, in virtual-screen pixels.
+fn window_frame(hwnd: HWND) -> Option<((f64, f64), (f64, f64))> {
+    let mut rect = RECT::default();
+    unsafe { GetWindowRect(hwnd, &mut rect) }.ok()?;
+    Some((
+        (rect.left as f64, rect.top as f64),
+        (
+            (rect.right - rect.left) as f64,
+            (rect.bottom - rect.top) as f64,
+        ),
+    ))
+}
+
+/// Move a window to `origin` without resizing or activating it.
+fn move_window(hwnd: HWND, (x, y): (f64, f64)) {
+    let flags = SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE;
+    unsafe {
+        let _ = SetWindowPos(hwnd, None, x as i32, y as i32, 0, 0, flags);
+    }
+}
+
+/// Put the dialog where it was last dropped on the monitor under the
+/// cursor; the first time, the MessageBox stays where it centered itself.
+/// Returns the origin used.
+fn place_dialog(dialog: HWND) -> Option<(f64, f64)> {
+    let (origin, size) = window_frame(dialog)?;
+    let mut cursor = POINT::default();
+    unsafe { GetCursorPos(&mut cursor) }.ok()?;
+    let monitor = monitor_rect_at(cursor.x, cursor.y)?;
+    let session = config::load_session();
+    let Some(saved) = session.display_position(SESSION_HELP_OVERLAY, &display_key(&monitor)) else {
+        return Some(origin);
+    };
+    let origin = clamp_to_screen(saved, size, &monitor);
+    move_window(dialog, origin);
+    Some(origin)
+}
+
+/// Snap the dropped dialog to the edges of its monitor and persist the
+/// new position, both for session restore and for that monitor.
+fn finish_drag(dialog: HWND) {
+    let (Some((origin, size)), Some(monitor)) =
+        (window_frame(dialog), monitor_rect_for_window(dialog))
+    else {
+        return;
+    };
+    let (x, y) = snap_to_edges(origin, size, &monitor);
+    move_window(dialog, (x, y));
+
+    let mut session = config::load_session();
+    session.set_position(SESSION_HELP_OVERLAY, x, y);
+    session.set_display_position(SESSION_HELP_OVERLAY, &display_key(&monitor), x, y);
+    config::save_session(&session);
+}
+
+/// CBT hook of the help dialog's thread: places the MessageBox the first
+/// time it is activated.
+unsafe extern "system" fn help_cbt_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code == HCBT_ACTIVATE as i32 && HELP_DIALOG.load(Ordering::SeqCst) == 0 {
+        let dialog = HWND(wparam.0 as *mut _);
+        HELP_DIALOG.store(dialog.0 as isize, Ordering::SeqCst);
+        let origin = place_dialog(dialog);
+        let mut session = config::load_session();
+        session.mark_open(SESSION_HELP_OVERLAY, origin);
+        config::save_session(&session);
+    }
+    CallNextHookEx(None, code, wparam, lparam)
+}
+
+/// Window procedure hook of the help dialog's thread: snaps the dialog
+/// when a drag of its title bar ends.
+unsafe extern "system" fn help_drag_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code >= 0 {
+        let msg = &*(lparam.0 as *const CWPRETSTRUCT);
+        let dialog = HELP_DIALOG.load(Ordering::SeqCst);
+        if msg.message == WM_EXITSIZEMOVE && dialog != 0 && msg.hwnd.0 as isize == dialog {
+            finish_drag(msg.hwnd);
+        }
+    }
+    CallNextHookEx(None, code, wparam, lparam)
+}
 
 /// Show the help overlay with keyboard shortcuts.
 ///
-/// Uses a MessageBox for simplicity on Windows.
+/// Uses a MessageBox for simplicity on Windows, placed and snapped
+/// through hooks on this thread while it is open.
 /// Could be replaced with a custom dialog for a more polished look.
-pub fn show_help_overlay(hwnd: HWND, is_spanish: bool) {
+pub fn show_help_overlay(hwnd: HWND) {
     // Atomic guard: only one help overlay can be opening at a time
     if HELP_OPENING
         .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
@@ -40,29 +129,41 @@ pub fn show_help_overlay(hwnd: HWND, is_spanish: bool) {
         return;
     }
 
-    let hotkeys = if is_spanish { HOTKEYS_ES } else { HOTKEYS_EN };
-
-    let title = if is_spanish {
-        "Atajos de Teclado"
-    } else {
-        "Keyboard Shortcuts"
-    };
+    let title = tr("Keyboard Shortcuts");
 
     // Build message with aligned columns
     let mut message = String::new();
-    for (keys, desc) in hotkeys {
-        message.push_str(&format!("{:<20} {}\n", keys, desc));
+    for entry in help_entries(&current_hotkey_bindings(), hotkey_registered) {
+        let mut description = tr(entry.description);
+        if !entry.registered {
+            description = format!("{description} ({})", tr("unavailable"));
+        }
+        message.push_str(&format!("{:<20} {}\n", entry.keys, description));
     }
     message.push('\n');
-    message.push_str(if is_spanish {
-        "Pulsa Aceptar para cerrar"
-    } else {
-        "Press OK to close"
-    });
+    message.push_str(&tr("Press OK to close"));
 
     let title_wide: Vec<u16> = title.encode_utf16().chain(std::iter::once(0)).collect();
     let message_wide: Vec<u16> = message.encode_utf16().chain(std::iter::once(0)).collect();
 
+    // Remember that help is open (restored on next launch if we never close
+    // it); the origin is stored once the dialog is placed
+    let mut session = config::load_session();
+    session.mark_open(SESSION_HELP_OVERLAY, None);
+    config::save_session(&session);
+
+    HELP_DIALOG.store(0, Ordering::SeqCst);
+    let hooks: Vec<HHOOK> = unsafe {
+        let thread = GetCurrentThreadId();
+        [
+            SetWindowsHookExW(WH_CBT, Some(help_cbt_proc), None, thread),
+            SetWindowsHookExW(WH_CALLWNDPROCRET, Some(help_drag_proc), None, thread),
+        ]
+        .into_iter()
+        .filter_map(Result::ok)
+        .collect()
+    };
+
     unsafe {
         MessageBoxW(
             Some(hwnd),
@@ -72,6 +173,17 @@ pub fn show_help_overlay(hwnd: HWND, is_spanish: bool) {
         );
     }
 
+    for hook in hooks {
+        unsafe {
+            let _ = UnhookWindowsHookEx(hook);
+        }
+    }
+    HELP_DIALOG.store(0, Ordering::SeqCst);
+
+    let mut session = config::load_session();
+    session.mark_closed(SESSION_HELP_OVERLAY);
+    config::save_session(&session);
+
     // Reset atomic guard
     HELP_OPENING.store(false, Ordering::SeqCst);
 }
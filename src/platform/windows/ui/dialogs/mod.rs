@@ -1,7 +1,17 @@
 //! Dialog windows for Windows.
 
 mod about_dialog;
+mod click_log_export;
+mod config_issues;
+mod crash_report;
 mod help_overlay;
+mod quit_dialog;
+mod sync_folder;
 
 pub use about_dialog::*;
+pub use click_log_export::*;
+pub use config_issues::*;
+pub use crash_report::*;
 pub use help_overlay::*;
+pub use quit_dialog::*;
+pub use sync_folder::*;
@@ -0,0 +1,35 @@
+//! Quit confirmation for Windows.
+//!
+//! Asked before quitting from the hotkey or the tray menu while the
+//! `confirm_quit` setting is on.
+
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::HWND;
+use windows::Win32::UI::WindowsAndMessaging::{
+    MessageBoxW, IDYES, MB_DEFBUTTON2, MB_ICONQUESTION, MB_SETFOREGROUND, MB_TOPMOST, MB_YESNO,
+};
+
+use crate::i18n::tr;
+use crate::model::constants::PREF_CONFIRM_QUIT;
+use crate::platform::windows::storage::config;
+
+/// Should the app quit? Asks first while `PREF_CONFIRM_QUIT` is on,
+/// with Cancel as the default button.
+pub fn confirm_quit(hwnd: HWND) -> bool {
+    if config::prefs_get_int(PREF_CONFIRM_QUIT, 1) == 0 {
+        return true;
+    }
+    let title = tr("Quit the app?");
+    let message = tr("The app will close");
+    let title_wide: Vec<u16> = title.encode_utf16().chain(std::iter::once(0)).collect();
+    let message_wide: Vec<u16> = message.encode_utf16().chain(std::iter::once(0)).collect();
+    let answer = unsafe {
+        MessageBoxW(
+            Some(hwnd),
+            PCWSTR(message_wide.as_ptr()),
+            PCWSTR(title_wide.as_ptr()),
+            MB_YESNO | MB_ICONQUESTION | MB_DEFBUTTON2 | MB_TOPMOST | MB_SETFOREGROUND,
+        )
+    };
+    answer == IDYES
+}
@@ -0,0 +1,35 @@
+//! Folder picker for choosing the folder the settings are synced through.
+
+use windows::core::PCWSTR;
+use windows::Win32::System::Com::{CoCreateInstance, CoTaskMemFree, CLSCTX_INPROC_SERVER};
+use windows::Win32::UI::Shell::{
+    FileOpenDialog, IFileOpenDialog, FOS_FORCEFILESYSTEM, FOS_PICKFOLDERS, SIGDN_FILESYSPATH,
+};
+
+use crate::i18n::tr;
+
+/// Ask for a folder to sync the settings through.
+///
+/// Blocks until the dialog is dismissed; returns None if cancelled.
+pub fn choose_sync_folder() -> Option<String> {
+    let title: Vec<u16> = tr("Choose sync folder")
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+    unsafe {
+        let dialog: IFileOpenDialog =
+            CoCreateInstance(&FileOpenDialog, None, CLSCTX_INPROC_SERVER).ok()?;
+        let options = dialog.GetOptions().ok()?;
+        dialog
+            .SetOptions(options | FOS_PICKFOLDERS | FOS_FORCEFILESYSTEM)
+            .ok()?;
+        dialog.SetTitle(PCWSTR(title.as_ptr())).ok()?;
+        // Fails when the dialog is cancelled
+        dialog.Show(None).ok()?;
+        let item = dialog.GetResult().ok()?;
+        let path = item.GetDisplayName(SIGDN_FILESYSPATH).ok()?;
+        let folder = path.to_string().ok();
+        CoTaskMemFree(Some(path.0 as *const _));
+        folder
+    }
+}
@@ -2,10 +2,12 @@
 
 pub mod dialogs;
 pub mod overlay;
+pub mod session;
 pub mod settings;
 pub mod tray;
 
 pub use dialogs::*;
 pub use overlay::*;
+pub use session::*;
 pub use settings::*;
 pub use tray::*;
@@ -3,6 +3,6 @@
 pub mod renderer;
 
 pub use renderer::{
-    create_arial_bold_font_face, release_render_cache, update_overlay, D2D_FACTORY, DWRITE_FACTORY,
-    FONT_FACE,
+    create_arial_bold_font_face, invalidate_render_target, release_render_cache, update_overlay,
+    D2D_FACTORY, DWRITE_FACTORY, FONT_FACE,
 };
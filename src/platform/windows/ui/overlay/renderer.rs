@@ -3,30 +3,37 @@
 //! GPU-accelerated, high-quality anti-aliased rendering with per-pixel alpha
 //! transparency via UpdateLayeredWindow.
 //!
-//! Expensive resources (memory DC, bitmap, render target, stroke style) are
-//! cached in thread-local storage and reused across frames. Only brushes
-//! are created per-frame since colors can change via settings.
+//! Expensive resources (memory DC, bitmap, render target, stroke style,
+//! font faces, click letter and badge outlines) are cached in thread-local
+//! storage and reused across frames. Only brushes are created per-frame
+//! since colors can change via settings, plus the screen capture while
+//! zoom-follow is active.
 
 use std::cell::RefCell;
+use std::time::Instant;
 
-use windows::core::{w, BOOL};
-use windows::Win32::Foundation::{COLORREF, POINT, SIZE};
+use windows::core::{Interface, BOOL, HSTRING};
+use windows::Win32::Foundation::{COLORREF, D2DERR_RECREATE_TARGET, POINT, SIZE};
 use windows::Win32::Graphics::Direct2D::Common::{
-    D2D1_ALPHA_MODE_PREMULTIPLIED, D2D1_COLOR_F, D2D1_PIXEL_FORMAT,
+    D2D1_ALPHA_MODE_IGNORE, D2D1_ALPHA_MODE_PREMULTIPLIED, D2D1_COLOR_F, D2D1_FIGURE_BEGIN_HOLLOW,
+    D2D1_FIGURE_END_OPEN, D2D1_PIXEL_FORMAT, D2D_RECT_F, D2D_SIZE_F, D2D_SIZE_U,
 };
 use windows::Win32::Graphics::Direct2D::{
-    ID2D1DCRenderTarget, ID2D1Factory, ID2D1PathGeometry, ID2D1RenderTarget, ID2D1StrokeStyle,
-    D2D1_ANTIALIAS_MODE_PER_PRIMITIVE, D2D1_CAP_STYLE_ROUND, D2D1_DASH_STYLE_SOLID, D2D1_ELLIPSE,
-    D2D1_LINE_JOIN_ROUND, D2D1_RENDER_TARGET_PROPERTIES, D2D1_RENDER_TARGET_TYPE_DEFAULT,
-    D2D1_RENDER_TARGET_USAGE_NONE, D2D1_STROKE_STYLE_PROPERTIES,
+    ID2D1DCRenderTarget, ID2D1Factory, ID2D1PathGeometry, ID2D1RenderTarget, ID2D1SolidColorBrush,
+    ID2D1StrokeStyle, D2D1_ANTIALIAS_MODE_PER_PRIMITIVE, D2D1_ARC_SEGMENT, D2D1_ARC_SIZE_LARGE,
+    D2D1_ARC_SIZE_SMALL, D2D1_BITMAP_INTERPOLATION_MODE_LINEAR, D2D1_BITMAP_PROPERTIES,
+    D2D1_CAP_STYLE_ROUND, D2D1_DASH_STYLE_SOLID, D2D1_ELLIPSE, D2D1_LINE_JOIN_ROUND,
+    D2D1_RENDER_TARGET_PROPERTIES, D2D1_RENDER_TARGET_TYPE_DEFAULT, D2D1_RENDER_TARGET_USAGE_NONE,
+    D2D1_STROKE_STYLE_PROPERTIES, D2D1_SWEEP_DIRECTION_CLOCKWISE,
 };
 use windows::Win32::Graphics::DirectWrite::{
-    IDWriteFactory, IDWriteFontCollection, IDWriteFontFace, DWRITE_GLYPH_OFFSET,
+    IDWriteFactory, IDWriteFontCollection, IDWriteFontFace, DWRITE_FONT_STRETCH_NORMAL,
+    DWRITE_FONT_STYLE_NORMAL, DWRITE_FONT_WEIGHT,
 };
 use windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_B8G8R8A8_UNORM;
 use windows::Win32::Graphics::Gdi::{
-    CreateCompatibleDC, CreateDIBSection, DeleteDC, DeleteObject, GetDC, ReleaseDC, SelectObject,
-    BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, HBITMAP, HDC,
+    BitBlt, CreateCompatibleDC, CreateDIBSection, DeleteDC, DeleteObject, GetDC, ReleaseDC,
+    SelectObject, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, HBITMAP, HDC, SRCCOPY,
 };
 use windows::Win32::UI::WindowsAndMessaging::{
     GetCursorPos, SetWindowPos, UpdateLayeredWindow, HWND_TOPMOST, SWP_NOACTIVATE, SWP_NOMOVE,
@@ -34,8 +41,40 @@ use windows::Win32::UI::WindowsAndMessaging::{
 };
 use windows_numerics::{Matrix3x2, Vector2};
 
+use crate::i18n::{click_label, click_letter};
+use crate::model::animation::wall_clock_secs;
+use crate::model::click_burst::badge_label;
+use crate::model::click_hold::hold_progress;
+use crate::model::click_icon::click_icon;
+use crate::model::color_sampler::{sample_hex, to_unit, Rgb8};
 use crate::model::constants::*;
+use crate::model::contrast::halo_color;
+use crate::model::coordinates::coordinates_label;
+use crate::model::diagnostics::debug_label;
+use crate::model::glow::{glow_color, glow_offset};
+use crate::model::kinematics::{speed_fade_alpha, speed_size_scale};
+use crate::model::motion::reduce_motion;
+use crate::model::placement::{display_key, ScreenRect};
+use crate::model::pulse::pulse_phase;
+use crate::model::rainbow::rainbow_phase;
+use crate::model::ruler::{ruler_label, RulerLine};
+use crate::model::screen_frame::Rgba;
+use crate::model::trail::TrailSegment;
+use crate::model::zoom::{source_rect, to_zoomed};
 use crate::platform::windows::app::state::{WindowsRuntimeState, STATE};
+use crate::platform::windows::ffi::{monitor_rect_at, monitors, screen_pixel};
+use crate::render::display_list::{DisplayItem, DisplayList, Paint, Shape, Transform};
+use crate::render::effects;
+use crate::render::geometry::{
+    arc_point, badge_anchor, badge_size, center_offset, fill_alpha, glow_layers, halo_line_width,
+    laser_core_radius, laser_dot_radius, letter_height, letter_max_width, outline_halo_width,
+    readout_origin, ruler_label_origin, sample_origin,
+};
+use crate::render::lru::LruCache;
+
+/// Key of [`GLYPH_GEOMETRY`]: font face address, text, and the bits of
+/// the font size and widest width.
+type GlyphKey = (usize, String, u32, u32);
 
 /// Cached rendering resources to avoid per-frame allocations.
 struct RenderCache {
@@ -62,12 +101,32 @@ thread_local! {
     pub static D2D_FACTORY: RefCell<Option<ID2D1Factory>> = const { RefCell::new(None) };
     pub static DWRITE_FACTORY: RefCell<Option<IDWriteFactory>> = const { RefCell::new(None) };
     pub static FONT_FACE: RefCell<Option<IDWriteFontFace>> = const { RefCell::new(None) };
+    /// Font face of the click letters chosen in Settings, by family and
+    /// weight; `None` when it is not installed.
+    static LETTER_FONT_FACE: RefCell<Option<(String, i32, Option<IDWriteFontFace>)>> =
+        const { RefCell::new(None) };
+    /// Font face of the click icons, once looked up; `None` inside when no
+    /// icon font is installed.
+    static ICON_FONT_FACE: RefCell<Option<Option<IDWriteFontFace>>> = const { RefCell::new(None) };
+    /// Outlines of the click letters and badges drawn recently, by font
+    /// face, text, size and widest width, at the origin; `None` inside when
+    /// the face cannot draw the text.
+    static GLYPH_GEOMETRY: RefCell<LruCache<GlyphKey, Option<ID2D1PathGeometry>>> =
+        const { RefCell::new(LruCache::new(GLYPH_CACHE_SIZE)) };
     static RENDER_CACHE: RefCell<Option<RenderCache>> = const { RefCell::new(None) };
+    static CLOCK_START: Instant = Instant::now();
+}
+
+/// Seconds since the renderer clock started (used to age trail points).
+fn frame_time_secs() -> f64 {
+    CLOCK_START.with(|t| t.elapsed().as_secs_f64())
 }
 
 /// Create or retrieve cached rendering resources.
 ///
-/// Recreates resources only if screen dimensions changed.
+/// Recreates resources only if screen dimensions changed, or after
+/// [`invalidate_render_target`] (display change, lost target). The render
+/// target is bound to the memory DC once, here.
 unsafe fn get_or_create_cache(factory: &ID2D1Factory, width: i32, height: i32) -> Option<()> {
     RENDER_CACHE.with(|cache| {
         let mut cache = cache.borrow_mut();
@@ -137,7 +196,7 @@ unsafe fn get_or_create_cache(factory: &ID2D1Factory, width: i32, height: i32) -
         };
         let stroke_style = factory.CreateStrokeStyle(&stroke_props, None).ok()?;
 
-        *cache = Some(RenderCache {
+        let created = RenderCache {
             screen_dc,
             mem_dc,
             bitmap,
@@ -145,22 +204,65 @@ unsafe fn get_or_create_cache(factory: &ID2D1Factory, width: i32, height: i32) -
             stroke_style,
             width,
             height,
-        });
+        };
+        let rect = windows::Win32::Foundation::RECT {
+            left: 0,
+            top: 0,
+            right: width,
+            bottom: height,
+        };
+        // On failure the DCs and bitmap are released as `created` drops
+        created
+            .dc_render_target
+            .BindDC(created.mem_dc, &rect)
+            .ok()?;
+        *cache = Some(created);
 
         Some(())
     })
 }
 
-/// Release cached rendering resources (call on app exit).
-pub fn release_render_cache() {
+/// Drop the render target, bitmap and DCs; the next frame creates them
+/// again. Call when the displays change, whatever the overlay's size.
+pub fn invalidate_render_target() {
     RENDER_CACHE.with(|cache| {
         *cache.borrow_mut() = None;
     });
 }
 
+/// Release cached rendering resources (call on app exit).
+pub fn release_render_cache() {
+    invalidate_render_target();
+    LETTER_FONT_FACE.with(|face| {
+        *face.borrow_mut() = None;
+    });
+    ICON_FONT_FACE.with(|face| {
+        *face.borrow_mut() = None;
+    });
+    GLYPH_GEOMETRY.with(|cache| {
+        cache.borrow_mut().clear();
+    });
+}
+
 /// Create a font face for the Arial Bold font.
+///
+/// # Safety
+/// Must be called on the thread that owns `dwrite_factory`.
 pub unsafe fn create_arial_bold_font_face(
     dwrite_factory: &IDWriteFactory,
+) -> Option<IDWriteFontFace> {
+    create_font_face(dwrite_factory, "Arial", LETTER_WEIGHT_BOLD)
+}
+
+/// Create a font face for `family` at the closest `weight` (OpenType
+/// 100–900) it has. Returns `None` when the family is not installed.
+///
+/// # Safety
+/// Must be called on the thread that owns `dwrite_factory`.
+pub unsafe fn create_font_face(
+    dwrite_factory: &IDWriteFactory,
+    family: &str,
+    weight: i32,
 ) -> Option<IDWriteFontFace> {
     // Get system font collection (windows-rs 0.62 uses output parameter)
     let mut font_collection: Option<IDWriteFontCollection> = None;
@@ -169,11 +271,11 @@ pub unsafe fn create_arial_bold_font_face(
         .ok()?;
     let font_collection = font_collection?;
 
-    // Find Arial font family
+    // Find the font family
     let mut index: u32 = 0;
     let mut exists = BOOL::default();
     font_collection
-        .FindFamilyName(w!("Arial"), &mut index, &mut exists)
+        .FindFamilyName(&HSTRING::from(family), &mut index, &mut exists)
         .ok()?;
 
     if !exists.as_bool() {
@@ -183,12 +285,12 @@ pub unsafe fn create_arial_bold_font_face(
     // Get the font family
     let font_family = font_collection.GetFontFamily(index).ok()?;
 
-    // Get bold font from the family
+    // Get the closest weight from the family
     let font = font_family
         .GetFirstMatchingFont(
-            windows::Win32::Graphics::DirectWrite::DWRITE_FONT_WEIGHT_BOLD,
-            windows::Win32::Graphics::DirectWrite::DWRITE_FONT_STRETCH_NORMAL,
-            windows::Win32::Graphics::DirectWrite::DWRITE_FONT_STYLE_NORMAL,
+            DWRITE_FONT_WEIGHT(weight),
+            DWRITE_FONT_STRETCH_NORMAL,
+            DWRITE_FONT_STYLE_NORMAL,
         )
         .ok()?;
 
@@ -196,41 +298,95 @@ pub unsafe fn create_arial_bold_font_face(
     font.CreateFontFace().ok()
 }
 
-/// Create outlined letter geometry using DirectWrite glyph outlines.
-unsafe fn create_letter_geometry(
+/// Font face of the click letters: the family and weight chosen in
+/// Settings (Arial when no family is chosen), created once per choice.
+/// `None` means the shared Arial Bold face (the default, or a family that
+/// is not installed).
+unsafe fn letter_font_face(family: &str, weight: i32) -> Option<IDWriteFontFace> {
+    let family = if family.is_empty() { "Arial" } else { family };
+    if family == "Arial" && weight == LETTER_WEIGHT_BOLD {
+        return None;
+    }
+    LETTER_FONT_FACE.with(|cached| {
+        let mut cached = cached.borrow_mut();
+        if let Some((f, w, face)) = cached.as_ref() {
+            if f == family && *w == weight {
+                return face.clone();
+            }
+        }
+        let face = DWRITE_FACTORY.with(|factory| {
+            let factory = factory.borrow();
+            let factory = factory.as_ref()?;
+            create_font_face(factory, family, weight)
+                .or_else(|| create_font_face(factory, "Arial", weight))
+        });
+        *cached = Some((family.to_string(), weight, face.clone()));
+        // The old face goes away, and a new one may take its address
+        GLYPH_GEOMETRY.with(|cache| cache.borrow_mut().clear());
+        face
+    })
+}
+
+/// Font face of the click icons: Segoe Fluent Icons (Windows 11), else
+/// Segoe MDL2 Assets (Windows 10), which share their code points. `None`
+/// when neither is installed.
+unsafe fn icon_font_face() -> Option<IDWriteFontFace> {
+    ICON_FONT_FACE.with(|cached| {
+        cached
+            .borrow_mut()
+            .get_or_insert_with(|| {
+                DWRITE_FACTORY.with(|factory| {
+                    let factory = factory.borrow();
+                    let factory = factory.as_ref()?;
+                    ["Segoe Fluent Icons", "Segoe MDL2 Assets"]
+                        .into_iter()
+                        .find_map(|family| create_font_face(factory, family, LETTER_WEIGHT_REGULAR))
+                })
+            })
+            .clone()
+    })
+}
+
+/// Create outlined glyph geometry using DirectWrite glyph outlines, at
+/// the origin.
+///
+/// Multi-character text uses the font's own advances and is shrunk to
+/// `max_width` when wider. Returns `None` when the font has no glyph for
+/// some character.
+unsafe fn create_glyph_geometry(
     d2d_factory: &ID2D1Factory,
     font_face: &IDWriteFontFace,
-    letter: char,
+    text: &str,
     font_size: f32,
-    center_x: f32,
-    center_y: f32,
+    max_width: f32,
 ) -> Option<ID2D1PathGeometry> {
-    // Get glyph index for the letter
-    let code_point = letter as u32;
-    let mut glyph_index: u16 = 0;
+    // Get glyph indices for the text
+    let code_points: Vec<u32> = text.chars().map(|c| c as u32).collect();
+    let mut glyph_indices = vec![0u16; code_points.len()];
     font_face
-        .GetGlyphIndices(&code_point, 1, &mut glyph_index)
+        .GetGlyphIndices(
+            code_points.as_ptr(),
+            code_points.len() as u32,
+            glyph_indices.as_mut_ptr(),
+        )
         .ok()?;
 
-    if glyph_index == 0 {
+    if glyph_indices.is_empty() || glyph_indices.contains(&0) {
         return None;
     }
 
-    // Create path geometry for the glyph outline
+    // Create path geometry for the glyph outlines
     let path_geometry: ID2D1PathGeometry = d2d_factory.CreatePathGeometry().ok()?;
     let sink = path_geometry.Open().ok()?;
 
-    // Get glyph outline at the specified font size
-    let glyph_advance: f32 = 0.0;
-    let glyph_offset = DWRITE_GLYPH_OFFSET::default();
-
+    // Get glyph outlines at the specified font size
     font_face
         .GetGlyphRunOutline(
             font_size,
-            &glyph_index,
-            Some(&glyph_advance),
-            Some(&glyph_offset),
-            1,
+            glyph_indices.as_ptr(),
+            None,
+            None,
+            glyph_indices.len() as u32,
             false, // not sideways
             false, // not right-to-left
             &sink,
@@ -239,25 +395,110 @@ unsafe fn create_letter_geometry(
 
     sink.Close().ok()?;
 
-    // Get bounds to center the geometry
     let bounds = path_geometry.GetBounds(None).ok()?;
-    let glyph_width = bounds.right - bounds.left;
-    let glyph_height = bounds.bottom - bounds.top;
+    let width = bounds.right - bounds.left;
+    if width > max_width {
+        // Outlines scale with the font size, so one more pass fits
+        let size = font_size * max_width / width;
+        return create_glyph_geometry(d2d_factory, font_face, text, size, f32::INFINITY);
+    }
+    Some(path_geometry)
+}
+
+/// Glyph geometry of `text` (see [`create_glyph_geometry`]), kept in
+/// [`GLYPH_GEOMETRY`] so a held button or a click burst outlines the same
+/// text once rather than every frame.
+unsafe fn glyph_geometry(
+    d2d_factory: &ID2D1Factory,
+    font_face: &IDWriteFontFace,
+    text: &str,
+    font_size: f32,
+    max_width: f32,
+) -> Option<ID2D1PathGeometry> {
+    let key = (
+        font_face.as_raw() as usize,
+        text.to_string(),
+        font_size.to_bits(),
+        max_width.to_bits(),
+    );
+    GLYPH_GEOMETRY.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if let Some(geometry) = cache.get(&key) {
+            return geometry.clone();
+        }
+        let geometry = create_glyph_geometry(d2d_factory, font_face, text, font_size, max_width);
+        cache.insert(key, geometry.clone());
+        geometry
+    })
+}
 
-    // Create transform to center the glyph on the cursor
-    // Direct2D handles Y coordinates correctly, just need to center
+/// Offset that centers `geometry` on (`center_x`, `center_y`).
+unsafe fn centering_offset(
+    geometry: &ID2D1PathGeometry,
+    center_x: f32,
+    center_y: f32,
+) -> Option<(f32, f32)> {
+    let bounds = geometry.GetBounds(None).ok()?;
+    let bounds = ScreenRect::new(
+        bounds.left as f64,
+        bounds.top as f64,
+        (bounds.right - bounds.left) as f64,
+        (bounds.bottom - bounds.top) as f64,
+    );
+    let (dx, dy) = center_offset(&bounds, (center_x as f64, center_y as f64));
+    Some((dx as f32, dy as f32))
+}
+
+/// Create the multi-click counter badge geometry (e.g. "x3").
+///
+/// Glyphs use the font's own advances; the badge's bottom-left corner is
+/// placed at (`left`, `bottom`).
+unsafe fn create_badge_geometry(
+    d2d_factory: &ID2D1Factory,
+    font_face: &IDWriteFontFace,
+    text: &str,
+    font_size: f32,
+    left: f32,
+    bottom: f32,
+) -> Option<ID2D1PathGeometry> {
+    let path_geometry =
+        create_glyph_geometry(d2d_factory, font_face, text, font_size, f32::INFINITY)?;
+    let bounds = path_geometry.GetBounds(None).ok()?;
+    translate_geometry(
+        d2d_factory,
+        &path_geometry,
+        left - bounds.left,
+        bottom - bounds.bottom,
+    )
+}
+
+/// Run `draw` with the render target moved by (`dx`, `dy`), so a cached
+/// geometry is drawn in place without being copied.
+unsafe fn draw_translated(rt: &ID2D1RenderTarget, dx: f32, dy: f32, draw: impl FnOnce()) {
+    rt.SetTransform(&Matrix3x2::translation(dx, dy));
+    draw();
+    rt.SetTransform(&Matrix3x2::identity());
+}
+
+/// Bake a translation into a new path geometry.
+unsafe fn translate_geometry(
+    d2d_factory: &ID2D1Factory,
+    path_geometry: &ID2D1PathGeometry,
+    dx: f32,
+    dy: f32,
+) -> Option<ID2D1PathGeometry> {
     let transform = Matrix3x2 {
         M11: 1.0,
         M12: 0.0,
         M21: 0.0,
         M22: 1.0, // No flip needed
-        M31: center_x - bounds.left - glyph_width / 2.0,
-        M32: center_y - bounds.top - glyph_height / 2.0,
+        M31: dx,
+        M32: dy,
     };
 
     // Create transformed geometry
     let transformed_geometry = d2d_factory
-        .CreateTransformedGeometry(&path_geometry, &transform)
+        .CreateTransformedGeometry(path_geometry, &transform)
         .ok()?;
 
     // Create final path geometry from transformed geometry
@@ -271,6 +512,346 @@ unsafe fn create_letter_geometry(
     Some(final_geometry)
 }
 
+/// Draw the glow behind a shape as layered strokes, widest first.
+///
+/// Direct2D on a DC render target has no blur effect, so each of the
+/// [`glow_layers`] strokes the shape `width` plus twice its spread wide
+/// with `brush` at its opacity; stacked, they fade out from the outline
+/// like a blur. A drop shadow is moved down by [`glow_offset`].
+unsafe fn draw_glow(
+    rt: &ID2D1RenderTarget,
+    brush: &ID2D1SolidColorBrush,
+    glow: i32,
+    width: f32,
+    stroke: &dyn Fn(&ID2D1SolidColorBrush, f32),
+) {
+    let base_opacity = brush.GetOpacity();
+    let offset = glow_offset(glow) as f32;
+    let mut base = Matrix3x2::identity();
+    rt.GetTransform(&mut base);
+    rt.SetTransform(&(base * Matrix3x2::translation(0.0, offset)));
+    for (spread, alpha) in glow_layers() {
+        brush.SetOpacity(base_opacity * alpha as f32);
+        stroke(brush, width + 2.0 * spread as f32);
+    }
+    rt.SetTransform(&base);
+    brush.SetOpacity(base_opacity);
+}
+
+/// Direct2D color of `color`.
+fn d2d_color((r, g, b, a): Rgba) -> D2D1_COLOR_F {
+    D2D1_COLOR_F {
+        r: r as f32,
+        g: g as f32,
+        b: b as f32,
+        a: a as f32,
+    }
+}
+
+/// Path of the arc of `radius` around `center`, from 12 o'clock, `sweep`
+/// radians clockwise.
+unsafe fn arc_geometry(
+    factory: &ID2D1Factory,
+    center: (f64, f64),
+    radius: f64,
+    sweep: f64,
+) -> Option<ID2D1PathGeometry> {
+    let path = factory.CreatePathGeometry().ok()?;
+    let sink = path.Open().ok()?;
+    let (sx, sy) = arc_point(center, radius, 0.0, false);
+    let (ex, ey) = arc_point(center, radius, sweep, false);
+    sink.BeginFigure(Vector2::new(sx as f32, sy as f32), D2D1_FIGURE_BEGIN_HOLLOW);
+    sink.AddArc(&D2D1_ARC_SEGMENT {
+        point: Vector2::new(ex as f32, ey as f32),
+        size: D2D_SIZE_F {
+            width: radius as f32,
+            height: radius as f32,
+        },
+        rotationAngle: 0.0,
+        sweepDirection: D2D1_SWEEP_DIRECTION_CLOCKWISE,
+        arcSize: if sweep > std::f64::consts::PI {
+            D2D1_ARC_SIZE_LARGE
+        } else {
+            D2D1_ARC_SIZE_SMALL
+        },
+    });
+    sink.EndFigure(D2D1_FIGURE_END_OPEN);
+    sink.Close().ok()?;
+    Some(path)
+}
+
+/// Replay `list` on the render target, with `round_style` for strokes
+/// with round ends (butt ends otherwise, like NSBezierPath's default).
+///
+/// One brush is recolored per shape; pushed transforms go on top of the
+/// render target's own and are undone at the matching pop.
+unsafe fn draw_display_list(
+    rt: &ID2D1RenderTarget,
+    factory: &ID2D1Factory,
+    list: &DisplayList,
+    round_style: &ID2D1StrokeStyle,
+) {
+    if list.is_empty() {
+        return;
+    }
+    let Ok(brush) = rt.CreateSolidColorBrush(&d2d_color((0.0, 0.0, 0.0, 0.0)), None) else {
+        return;
+    };
+    let mut saved = Vec::new();
+    for item in list.items() {
+        match *item {
+            DisplayItem::Draw { shape, paint } => {
+                let (color, stroke) = match paint {
+                    Paint::Stroke {
+                        color,
+                        width,
+                        round,
+                    } => (color, Some((width as f32, round.then_some(round_style)))),
+                    Paint::Fill(color) => (color, None),
+                };
+                brush.SetColor(&d2d_color(color));
+                let point = |(x, y): (f64, f64)| Vector2::new(x as f32, y as f32);
+                match shape {
+                    Shape::Line(from, to) => {
+                        if let Some((width, style)) = stroke {
+                            rt.DrawLine(point(from), point(to), &brush, width, style);
+                        }
+                    }
+                    Shape::Circle { center, radius } => {
+                        let ellipse = D2D1_ELLIPSE {
+                            point: point(center),
+                            radiusX: radius as f32,
+                            radiusY: radius as f32,
+                        };
+                        match stroke {
+                            Some((width, style)) => rt.DrawEllipse(&ellipse, &brush, width, style),
+                            None => rt.FillEllipse(&ellipse, &brush),
+                        }
+                    }
+                    Shape::Arc {
+                        center,
+                        radius,
+                        sweep,
+                    } => {
+                        let Some(path) = arc_geometry(factory, center, radius, sweep) else {
+                            continue;
+                        };
+                        match stroke {
+                            Some((width, style)) => rt.DrawGeometry(&path, &brush, width, style),
+                            None => rt.FillGeometry(&path, &brush, None),
+                        }
+                    }
+                    Shape::Rect(r) => {
+                        let rect = D2D_RECT_F {
+                            left: r.x as f32,
+                            top: r.y as f32,
+                            right: (r.x + r.w) as f32,
+                            bottom: (r.y + r.h) as f32,
+                        };
+                        match stroke {
+                            Some((width, style)) => rt.DrawRectangle(&rect, &brush, width, style),
+                            None => rt.FillRectangle(&rect, &brush),
+                        }
+                    }
+                }
+            }
+            DisplayItem::Push(t) => {
+                let mut current = Matrix3x2::identity();
+                rt.GetTransform(&mut current);
+                saved.push(current);
+                let (s, (dx, dy)) = (t.scale as f32, t.offset);
+                let matrix = Matrix3x2 {
+                    M11: s,
+                    M12: 0.0,
+                    M21: 0.0,
+                    M22: s,
+                    M31: dx as f32,
+                    M32: dy as f32,
+                };
+                rt.SetTransform(&(matrix * current));
+            }
+            DisplayItem::Pop => {
+                if let Some(previous) = saved.pop() {
+                    rt.SetTransform(&previous);
+                }
+            }
+        }
+    }
+}
+
+/// Draw the laser dot and its fading trail.
+///
+/// The trail comes from [`effects::laser_trail`]; the dot goes on top
+/// with a light core, outlined by `halo` and over `glow` when set.
+/// `to_local` maps virtual-screen points to render-target ones.
+#[allow(clippy::too_many_arguments)]
+unsafe fn draw_laser(
+    rt: &ID2D1RenderTarget,
+    factory: &ID2D1Factory,
+    brush: &ID2D1SolidColorBrush,
+    halo: Option<&ID2D1SolidColorBrush>,
+    glow: Option<&ID2D1SolidColorBrush>,
+    state: &WindowsRuntimeState,
+    x: f32,
+    y: f32,
+    stroke_style: &ID2D1StrokeStyle,
+    to_local: &dyn Fn(f64, f64) -> Vector2,
+) {
+    let dot_radius = laser_dot_radius(state.radius()) as f32;
+    let base_opacity = brush.GetOpacity();
+
+    let local = |x: f64, y: f64| {
+        let p = to_local(x, y);
+        (p.X as f64, p.Y as f64)
+    };
+    let segments: Vec<TrailSegment> = state
+        .trail
+        .segments(frame_time_secs())
+        .into_iter()
+        .map(|seg| {
+            let ((x0, y0), (x1, y1)) = (local(seg.x0, seg.y0), local(seg.x1, seg.y1));
+            TrailSegment {
+                x0,
+                y0,
+                x1,
+                y1,
+                alpha: seg.alpha,
+            }
+        })
+        .collect();
+    let (r, g, b, a) = state.stroke_color();
+    let mut trail = DisplayList::new();
+    let stroke = (r, g, b, a * base_opacity as f64);
+    effects::laser_trail(&mut trail, &segments, dot_radius as f64, stroke);
+    draw_display_list(rt, factory, &trail, stroke_style);
+
+    let dot = D2D1_ELLIPSE {
+        point: Vector2::new(x, y),
+        radiusX: dot_radius,
+        radiusY: dot_radius,
+    };
+    if let Some(glow) = glow {
+        draw_glow(rt, glow, state.overlay.glow, 0.0, &|b, w| {
+            rt.DrawEllipse(&dot, b, w, None)
+        });
+    }
+    if let Some(halo) = halo {
+        rt.DrawEllipse(&dot, halo, outline_halo_width() as f32, None);
+    }
+    rt.FillEllipse(&dot, brush);
+
+    let core_color = D2D1_COLOR_F {
+        r: 1.0,
+        g: 1.0,
+        b: 1.0,
+        a: 0.85 * base_opacity * state.stroke_color().3 as f32,
+    };
+    if let Ok(core_brush) = rt.CreateSolidColorBrush(&core_color, None) {
+        let core_radius = laser_core_radius(dot_radius as f64) as f32;
+        let core = D2D1_ELLIPSE {
+            point: Vector2::new(x, y),
+            radiusX: core_radius,
+            radiusY: core_radius,
+        };
+        rt.FillEllipse(&core, &core_brush);
+    }
+}
+
+/// Draw the monitor under the cursor magnified around the zoom focus.
+///
+/// The source region is copied from the screen DC without `CAPTUREBLT`,
+/// which leaves layered windows (this overlay) out of the capture. Returns
+/// the monitor, level and focus used, so the pointer can be drawn where its
+/// content appears.
+unsafe fn draw_zoom(
+    rt: &ID2D1RenderTarget,
+    state: &WindowsRuntimeState,
+    screen_dc: HDC,
+    cursor: POINT,
+) -> Option<(ScreenRect, f64, (f64, f64))> {
+    if !state.zoom.is_active() {
+        return None;
+    }
+    let focus = state.zoom.focus()?;
+    let monitor = monitor_rect_at(cursor.x, cursor.y)?;
+    let level = state.zoom.level();
+    let src = source_rect(&monitor, level, focus);
+    let src_w = (src.w.round() as i32).max(1);
+    let src_h = (src.h.round() as i32).max(1);
+
+    let capture_dc = CreateCompatibleDC(Some(screen_dc));
+    let bmi = BITMAPINFO {
+        bmiHeader: BITMAPINFOHEADER {
+            biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+            biWidth: src_w,
+            biHeight: -src_h, // Top-down
+            biPlanes: 1,
+            biBitCount: 32,
+            biCompression: BI_RGB.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut bits: *mut std::ffi::c_void = std::ptr::null_mut();
+    let Ok(capture) = CreateDIBSection(Some(capture_dc), &bmi, DIB_RGB_COLORS, &mut bits, None, 0)
+    else {
+        let _ = DeleteDC(capture_dc);
+        return Some((monitor, level, focus));
+    };
+    let previous = SelectObject(capture_dc, capture.into());
+
+    let copied = BitBlt(
+        capture_dc,
+        0,
+        0,
+        src_w,
+        src_h,
+        Some(screen_dc),
+        src.x.round() as i32,
+        src.y.round() as i32,
+        SRCCOPY,
+    )
+    .is_ok();
+
+    if copied && !bits.is_null() {
+        // GDI leaves the alpha byte undefined, so draw the capture as opaque
+        let props = D2D1_BITMAP_PROPERTIES {
+            pixelFormat: D2D1_PIXEL_FORMAT {
+                format: DXGI_FORMAT_B8G8R8A8_UNORM,
+                alphaMode: D2D1_ALPHA_MODE_IGNORE,
+            },
+            dpiX: 96.0,
+            dpiY: 96.0,
+        };
+        let size = D2D_SIZE_U {
+            width: src_w as u32,
+            height: src_h as u32,
+        };
+        if let Ok(bitmap) = rt.CreateBitmap(size, Some(bits), (src_w * 4) as u32, &props) {
+            let left = (monitor.x - state.offset_x as f64) as f32;
+            let top = (monitor.y - state.offset_y as f64) as f32;
+            let dest = D2D_RECT_F {
+                left,
+                top,
+                right: left + monitor.w as f32,
+                bottom: top + monitor.h as f32,
+            };
+            rt.DrawBitmap(
+                &bitmap,
+                Some(&dest),
+                1.0,
+                D2D1_BITMAP_INTERPOLATION_MODE_LINEAR,
+                None,
+            );
+        }
+    }
+
+    SelectObject(capture_dc, previous);
+    let _ = DeleteObject(capture.into());
+    let _ = DeleteDC(capture_dc);
+    Some((monitor, level, focus))
+}
+
 /// Update the overlay using Direct2D rendering.
 ///
 /// Skips the expensive redraw if the cursor hasn't moved and
@@ -287,17 +868,171 @@ pub fn update_overlay() {
 
     let needs_redraw = STATE.with(|s| {
         let mut state = s.borrow_mut();
+
+        // Reduce Motion: no trail or speed ramp, instant idle fade and zoom pan
+        let reduced = reduce_motion(state.overlay.motion, state.system_reduce_motion);
+
+        // A toggle scales and fades the highlight in or out; it is drawn until fully hidden
+        let enabled = state.overlay.overlay_enabled;
+        let duration_ms = state.overlay.transition_ms;
+        let progress = state
+            .show_hide
+            .update(enabled, frame_time_secs(), duration_ms);
+        let transition = if reduced {
+            state.show_hide.snap()
+        } else {
+            progress
+        };
+        let transition_changed = transition != state.transition;
+        let drawn = transition > 0.0;
+
+        // A released button's letter stays up until its hold time runs out
+        let hold_ms = state.overlay.click_hold_ms;
+        state.overlay.display_mode = state.click_hold.mode(wall_clock_secs(), hold_ms);
+
+        // Laser trail keeps animating (fading) even while the cursor rests
+        let trail_changed =
+            if drawn && !reduced && state.overlay.highlight_style == HIGHLIGHT_STYLE_LASER {
+                let now = frame_time_secs();
+                let pushed = state.trail.push(cursor.x as f64, cursor.y as f64, now);
+                let pruned = state.trail.prune(now);
+                pushed || pruned
+            } else if !state.trail.is_empty() {
+                state.trail.clear();
+                true
+            } else {
+                false
+            };
+
+        // Opacity ramp and size scaling keep easing back after a fast sweep stops
+        let speed_fade = drawn && !reduced && state.overlay.speed_fade;
+        let speed_size = drawn && !reduced && state.overlay.speed_size;
+        let speed = if speed_fade || speed_size {
+            let now = frame_time_secs();
+            state
+                .kinematics
+                .update(cursor.x as f64, cursor.y as f64, now)
+        } else {
+            state.kinematics.reset();
+            0.0
+        };
+        let speed_alpha = if speed_fade {
+            speed_fade_alpha(speed)
+        } else {
+            1.0
+        };
+        let speed_scale = if speed_size {
+            speed_size_scale(speed)
+        } else {
+            1.0
+        };
+        let scale_changed = (speed_scale - state.speed_scale).abs() > SPEED_SIZE_EPSILON
+            || (speed_scale == 1.0) != (state.speed_scale == 1.0);
+
+        // Idle auto-hide fades out while the cursor rests (a held button counts as activity)
+        let idle_alpha = if drawn && state.overlay.idle_hide {
+            let now = frame_time_secs();
+            let active = state.overlay.display_mode != DISPLAY_MODE_CIRCLE;
+            let idle_secs = state.overlay.idle_hide_secs;
+            let alpha = state
+                .idle
+                .update(cursor.x as f64, cursor.y as f64, active, now, idle_secs);
+            if reduced {
+                state.idle.snap()
+            } else {
+                alpha
+            }
+        } else {
+            state.idle.reset();
+            1.0
+        };
+
+        let alpha = speed_alpha * idle_alpha;
+        let fade_changed = (alpha - state.fade_alpha).abs() > SPEED_FADE_EPSILON
+            || (alpha == 1.0) != (state.fade_alpha == 1.0)
+            || (alpha == 0.0) != (state.fade_alpha == 0.0);
+
+        // Rainbow mode turns the hue every frame, with the same phase as on macOS
+        let phase = (drawn && !reduced && state.overlay.rainbow)
+            .then(|| rainbow_phase(wall_clock_secs(), state.overlay.rainbow_speed));
+        let rainbow_changed = phase != state.rainbow_phase;
+
+        // The pulse grows and shrinks the radius on the same clock
+        let pulse = (drawn && !reduced && state.overlay.pulse)
+            .then(|| pulse_phase(wall_clock_secs(), state.overlay.pulse_period_ms));
+        let pulse_changed = pulse != state.pulse_phase;
+
+        // The hold ring fills while a button stays down
+        let hold = (drawn && state.overlay.hold_ring)
+            .then(|| state.click_hold.held_secs(wall_clock_secs()))
+            .flatten()
+            .map(|secs| hold_progress(secs, state.overlay.hold_ring_ms));
+        let hold_changed = hold != state.hold_progress;
+
+        // The color sampler reads the pixel under the pointer every frame
+        let sample = (drawn && state.color_sampler)
+            .then(|| screen_pixel(cursor.x, cursor.y))
+            .flatten();
+        let sample_changed = sample != state.color_sample;
+
+        // Touch rings follow their fingers and fade out once lifted
+        let touching = !state.touches.is_empty();
+        let rings = state.touches.rings(wall_clock_secs());
+
+        // While zoomed, the view pans after the cursor and shows live screen content
+        let zoom_active = state.zoom.is_active();
+        if zoom_active {
+            let now = frame_time_secs();
+            if reduced {
+                state.zoom.snap(cursor.x as f64, cursor.y as f64, now);
+            } else {
+                state.zoom.follow(cursor.x as f64, cursor.y as f64, now);
+            }
+        }
+
+        // The debug label measures every frame, so it never skips one
+        let debug = match state.diagnostics.as_mut() {
+            Some(timer) => {
+                timer.tick(frame_time_secs());
+                true
+            }
+            None => false,
+        };
+
+        // Pointer moves and clicks only show while something is drawn: a
+        // hidden or faded-out overlay is not redrawn for them
+        let shown = drawn && alpha > 0.0;
+        let moved = cursor.x != state.last_cursor_x || cursor.y != state.last_cursor_y;
+        let mode_changed = state.overlay.display_mode != state.last_display_mode;
+
         let changed = state.dirty
-            || cursor.x != state.last_cursor_x
-            || cursor.y != state.last_cursor_y
-            || state.display_mode != state.last_display_mode
-            || state.visible != state.last_visible;
+            || debug
+            || trail_changed
+            || fade_changed
+            || scale_changed
+            || rainbow_changed
+            || pulse_changed
+            || hold_changed
+            || sample_changed
+            || touching
+            || transition_changed
+            || zoom_active
+            || (shown && (moved || mode_changed))
+            || state.overlay.overlay_enabled != state.last_visible;
 
         if changed {
             state.last_cursor_x = cursor.x;
             state.last_cursor_y = cursor.y;
-            state.last_display_mode = state.display_mode;
-            state.last_visible = state.visible;
+            state.last_display_mode = state.overlay.display_mode;
+            state.last_visible = state.overlay.overlay_enabled;
+            state.fade_alpha = alpha;
+            state.speed_scale = speed_scale;
+            state.rainbow_phase = phase;
+            state.pulse_phase = pulse;
+            state.hold_progress = hold;
+            state.color_sample = sample;
+            state.touch_rings = rings;
+            state.transition = transition;
             state.dirty = false;
         }
 
@@ -323,6 +1058,251 @@ pub fn update_overlay() {
     });
 }
 
+/// Draw the diagnostics label (display number and average frame time) in
+/// the top-left corner of the monitor under the cursor, white on a dark
+/// plate so it reads on any background.
+unsafe fn draw_debug_label(
+    rt: &ID2D1RenderTarget,
+    factory: &ID2D1Factory,
+    font_face: &IDWriteFontFace,
+    state: &WindowsRuntimeState,
+    cursor: POINT,
+    frame_ms: f64,
+) {
+    let Some(monitor) = monitor_rect_at(cursor.x, cursor.y) else {
+        return;
+    };
+    let display = monitors()
+        .iter()
+        .find(|d| d.frame == monitor)
+        .map_or(0, |d| d.id);
+
+    let size = DEBUG_LABEL_SIZE as f32;
+    let margin = DEBUG_LABEL_MARGIN as f32;
+    let left = (monitor.x - state.offset_x as f64) as f32 + margin;
+    let top = (monitor.y - state.offset_y as f64) as f32 + margin;
+    let Some(geom) = create_badge_geometry(
+        factory,
+        font_face,
+        &debug_label(display, frame_ms),
+        size,
+        left,
+        top + size,
+    ) else {
+        return;
+    };
+    draw_label_plate(rt, &geom, margin / 2.0, None);
+}
+
+/// Draw the cursor coordinates readout beside the highlight at (`x`, `y`),
+/// on the same plate as the debug label.
+#[allow(clippy::too_many_arguments)]
+unsafe fn draw_coordinates_label(
+    rt: &ID2D1RenderTarget,
+    factory: &ID2D1Factory,
+    font_face: &IDWriteFontFace,
+    state: &WindowsRuntimeState,
+    cursor: POINT,
+    x: f32,
+    y: f32,
+    radius: f32,
+) {
+    let Some(monitor) = monitor_rect_at(cursor.x, cursor.y) else {
+        return;
+    };
+    let frames: Vec<ScreenRect> = monitors().iter().map(|d| d.frame).collect();
+    let text = coordinates_label((cursor.x as f64, cursor.y as f64), &frames, false);
+
+    // Measure at the origin, then lay out inside the local monitor
+    let size = DEBUG_LABEL_SIZE as f32;
+    let pad = DEBUG_LABEL_MARGIN / 2.0;
+    let Some(bounds) = create_badge_geometry(factory, font_face, &text, size, 0.0, size)
+        .and_then(|geom| geom.GetBounds(None).ok())
+    else {
+        return;
+    };
+    let plate = (
+        (bounds.right - bounds.left) as f64 + 2.0 * pad,
+        (bounds.bottom - bounds.top) as f64 + 2.0 * pad,
+    );
+    let local = ScreenRect::new(
+        monitor.x - state.offset_x as f64,
+        monitor.y - state.offset_y as f64,
+        monitor.w,
+        monitor.h,
+    );
+    let (left, top) = readout_origin((x as f64, y as f64), radius as f64, plate, &local, false);
+    let dx = (left + pad) as f32 - bounds.left;
+    let dy = (top + pad) as f32 - bounds.top;
+    if let Some(geom) = create_badge_geometry(factory, font_face, &text, size, dx, size + dy) {
+        draw_label_plate(rt, &geom, pad as f32, None);
+    }
+}
+
+/// Draw the hex of the sampled `color` on a swatch of it, beside the
+/// highlight at (`x`, `y`) opposite the coordinates readout.
+#[allow(clippy::too_many_arguments)]
+unsafe fn draw_color_sample(
+    rt: &ID2D1RenderTarget,
+    factory: &ID2D1Factory,
+    font_face: &IDWriteFontFace,
+    state: &WindowsRuntimeState,
+    cursor: POINT,
+    x: f32,
+    y: f32,
+    radius: f32,
+    color: Rgb8,
+) {
+    let Some(monitor) = monitor_rect_at(cursor.x, cursor.y) else {
+        return;
+    };
+    let text = sample_hex(color);
+
+    // Measure at the origin; the swatch is a square as tall as the text
+    let size = DEBUG_LABEL_SIZE as f32;
+    let pad = DEBUG_LABEL_MARGIN / 2.0;
+    let Some(bounds) = create_badge_geometry(factory, font_face, &text, size, 0.0, size)
+        .and_then(|geom| geom.GetBounds(None).ok())
+    else {
+        return;
+    };
+    let side = (bounds.bottom - bounds.top) as f64;
+    let plate = (
+        (bounds.right - bounds.left) as f64 + side + 3.0 * pad,
+        side + 2.0 * pad,
+    );
+    let local = ScreenRect::new(
+        monitor.x - state.offset_x as f64,
+        monitor.y - state.offset_y as f64,
+        monitor.w,
+        monitor.h,
+    );
+    let (left, top) = sample_origin((x as f64, y as f64), radius as f64, plate, &local, false);
+    let dx = (left + side + 2.0 * pad) as f32 - bounds.left;
+    let dy = (top + pad) as f32 - bounds.top;
+    let (r, g, b) = to_unit(color);
+    let swatch = D2D1_COLOR_F {
+        r: r as f32,
+        g: g as f32,
+        b: b as f32,
+        a: 1.0,
+    };
+    if let Some(geom) = create_badge_geometry(factory, font_face, &text, size, dx, size + dy) {
+        draw_label_plate(rt, &geom, pad as f32, Some(swatch));
+    }
+}
+
+/// Draw the ruler's `line` (virtual-screen coordinates, placed with
+/// `to_local`) white over a dark outline, with ticks across its ends and
+/// its length on the same plate as the other labels.
+#[allow(clippy::too_many_arguments)]
+unsafe fn draw_ruler(
+    rt: &ID2D1RenderTarget,
+    factory: &ID2D1Factory,
+    font_face: Option<&IDWriteFontFace>,
+    stroke_style: &ID2D1StrokeStyle,
+    state: &WindowsRuntimeState,
+    line: RulerLine,
+    to_local: impl Fn(f64, f64) -> Vector2,
+) {
+    let local = |(px, py): (f64, f64)| {
+        let p = to_local(px, py);
+        (p.X as f64, p.Y as f64)
+    };
+    let shown = (local(line.0), local(line.1));
+    let mut lines = DisplayList::new();
+    effects::ruler(&mut lines, shown);
+    draw_display_list(rt, factory, &lines, stroke_style);
+
+    // Length beside the middle, inside the monitor it lies on
+    let Some(ff) = font_face else {
+        return;
+    };
+    let middle = ((line.0 .0 + line.1 .0) / 2.0, (line.0 .1 + line.1 .1) / 2.0);
+    let Some(monitor) = monitor_rect_at(middle.0 as i32, middle.1 as i32) else {
+        return;
+    };
+    let text = ruler_label(line, 1.0);
+    let size = DEBUG_LABEL_SIZE as f32;
+    let pad = DEBUG_LABEL_MARGIN / 2.0;
+    let Some(bounds) = create_badge_geometry(factory, ff, &text, size, 0.0, size)
+        .and_then(|geom| geom.GetBounds(None).ok())
+    else {
+        return;
+    };
+    let plate = (
+        (bounds.right - bounds.left) as f64 + 2.0 * pad,
+        (bounds.bottom - bounds.top) as f64 + 2.0 * pad,
+    );
+    let local_monitor = ScreenRect::new(
+        monitor.x - state.offset_x as f64,
+        monitor.y - state.offset_y as f64,
+        monitor.w,
+        monitor.h,
+    );
+    let (left, top) = ruler_label_origin(shown, plate, &local_monitor);
+    let dx = (left + pad) as f32 - bounds.left;
+    let dy = (top + pad) as f32 - bounds.top;
+    if let Some(geom) = create_badge_geometry(factory, ff, &text, size, dx, size + dy) {
+        draw_label_plate(rt, &geom, pad as f32, None);
+    }
+}
+
+/// Fill `geom` white on a dark plate reaching `pad` past its bounds, with
+/// a square `swatch` of that color left of the text.
+unsafe fn draw_label_plate(
+    rt: &ID2D1RenderTarget,
+    geom: &ID2D1PathGeometry,
+    pad: f32,
+    swatch: Option<D2D1_COLOR_F>,
+) {
+    let Ok(bounds) = geom.GetBounds(None) else {
+        return;
+    };
+
+    let side = bounds.bottom - bounds.top;
+    let swatch_width = swatch.map_or(0.0, |_| side + pad);
+    let plate = D2D_RECT_F {
+        left: bounds.left - pad - swatch_width,
+        top: bounds.top - pad,
+        right: bounds.right + pad,
+        bottom: bounds.bottom + pad,
+    };
+    let dark = D2D1_COLOR_F {
+        r: 0.0,
+        g: 0.0,
+        b: 0.0,
+        a: 0.6,
+    };
+    let white = D2D1_COLOR_F {
+        r: 1.0,
+        g: 1.0,
+        b: 1.0,
+        a: 1.0,
+    };
+    if let Ok(brush) = rt.CreateSolidColorBrush(&dark, None) {
+        rt.FillRectangle(&plate, &brush);
+    }
+    if let Some(color) = swatch {
+        let square = D2D_RECT_F {
+            left: bounds.left - pad - side,
+            top: bounds.top,
+            right: bounds.left - pad,
+            bottom: bounds.bottom,
+        };
+        if let Ok(brush) = rt.CreateSolidColorBrush(&color, None) {
+            rt.FillRectangle(&square, &brush);
+        }
+        // White edge keeps dark samples visible on the plate
+        if let Ok(brush) = rt.CreateSolidColorBrush(&white, None) {
+            rt.DrawRectangle(&square, &brush, 1.0, None);
+        }
+    }
+    if let Ok(brush) = rt.CreateSolidColorBrush(&white, None) {
+        rt.FillGeometry(geom, &brush, None);
+    }
+}
+
 /// Draw using Direct2D and apply with UpdateLayeredWindow.
 ///
 /// Uses cached rendering resources (DC, bitmap, render target, stroke style)
@@ -341,21 +1321,10 @@ unsafe fn update_layered_window_d2d(
         return;
     }
 
-    RENDER_CACHE.with(|cache| {
+    let lost = RENDER_CACHE.with(|cache| {
         let cache = cache.borrow();
         let cache = cache.as_ref().unwrap();
 
-        let rect = windows::Win32::Foundation::RECT {
-            left: 0,
-            top: 0,
-            right: width,
-            bottom: height,
-        };
-
-        if cache.dc_render_target.BindDC(cache.mem_dc, &rect).is_err() {
-            return;
-        }
-
         let rt: ID2D1RenderTarget = cache.dc_render_target.clone().into();
 
         rt.BeginDraw();
@@ -368,52 +1337,178 @@ unsafe fn update_layered_window_d2d(
             a: 0.0,
         }));
 
-        if state.visible {
-            let mut cursor = POINT::default();
-            let _ = GetCursorPos(&mut cursor);
+        let mut cursor = POINT::default();
+        let _ = GetCursorPos(&mut cursor);
+
+        // Magnified screen content goes below everything else
+        let zoom = draw_zoom(&rt, state, cache.screen_dc, cursor);
+
+        if state.transition > 0.0 {
+            // Virtual screen -> (magnified) virtual screen -> render target
+            let to_local = |px: f64, py: f64| -> Vector2 {
+                let (px, py) = match zoom {
+                    Some((ref monitor, level, focus)) => to_zoomed(monitor, level, focus, px, py),
+                    None => (px, py),
+                };
+                Vector2::new(
+                    (px - state.offset_x as f64) as f32,
+                    (py - state.offset_y as f64) as f32,
+                )
+            };
+            let pointer = to_local(cursor.x as f64, cursor.y as f64);
+            let (x, y) = (pointer.X, pointer.Y);
+
+            // Grid and recording frame go behind the pointer, across the
+            // monitor containing the cursor
+            if let Some(monitor) = monitor_rect_at(cursor.x, cursor.y) {
+                let mut backdrop = DisplayList::new();
+                let to_window =
+                    Transform::translate(-state.offset_x as f64, -state.offset_y as f64);
+                backdrop.transformed(to_window, |list| {
+                    if state.show_grid {
+                        let (spacing, parts) =
+                            (state.overlay.grid_spacing, state.overlay.grid_subdivisions);
+                        let color = state.overlay.grid_color();
+                        effects::grid(list, &monitor, spacing, parts, color, false);
+                    }
+                    if state.overlay.screen_frame {
+                        let color = state.frame_colors.color_for(&display_key(&monitor));
+                        effects::screen_frame(list, &monitor, color);
+                    }
+                });
+                draw_display_list(&rt, factory, &backdrop, &cache.stroke_style);
+            }
 
-            let x = (cursor.x - state.offset_x) as f32;
-            let y = (cursor.y - state.offset_y) as f32;
+            // Measured line, under the highlight that places its end
+            let ruler_line = state
+                .ruler
+                .and_then(|r| r.line((cursor.x as f64, cursor.y as f64)));
+            if let Some(line) = ruler_line {
+                let style = &cache.stroke_style;
+                draw_ruler(&rt, factory, font_face, style, state, line, to_local);
+            }
 
-            let radius = state.radius as f32;
-            let border = state.border_width as f32;
+            // Breathing while the pulse animates
+            let radius = state.radius() as f32;
+            let border = state.overlay.border_width as f32;
 
             rt.SetAntialiasMode(D2D1_ANTIALIAS_MODE_PER_PRIMITIVE);
 
+            // Hue turned while the rainbow mode cycles
+            let (stroke_r, stroke_g, stroke_b, stroke_a) = state.stroke_color();
             let color = D2D1_COLOR_F {
-                r: state.stroke_r,
-                g: state.stroke_g,
-                b: state.stroke_b,
-                a: 1.0,
+                r: stroke_r as f32,
+                g: stroke_g as f32,
+                b: stroke_b as f32,
+                a: stroke_a as f32,
             };
 
+            // Speed ramp and idle auto-hide (1.0 when both are disabled)
+            let fade_alpha = state.fade_alpha as f32;
+
+            // A ring at each finger on the touchscreen, under the highlight
+            if !state.touch_rings.is_empty() {
+                let mut rings = DisplayList::new();
+                effects::touch_rings(
+                    &mut rings,
+                    &state.touch_rings,
+                    state.overlay.radius,
+                    state.overlay.border_width,
+                    state.stroke_color(),
+                    |px, py| {
+                        let p = to_local(px, py);
+                        (p.X as f64, p.Y as f64)
+                    },
+                );
+                draw_display_list(&rt, factory, &rings, &cache.stroke_style);
+            }
+
+            // Contrasting outline halo drawn under the stroke
+            let halo_brush = if state.overlay.halo {
+                let (r, g, b) = halo_color(stroke_r, stroke_g, stroke_b);
+                let halo_color = D2D1_COLOR_F {
+                    r: r as f32,
+                    g: g as f32,
+                    b: b as f32,
+                    a: stroke_a as f32,
+                };
+                rt.CreateSolidColorBrush(&halo_color, None).ok()
+            } else {
+                None
+            };
+            let halo_width = halo_line_width(state.overlay.border_width) as f32;
+
+            // Soft glow or drop shadow drawn under everything else
+            let glow = state.overlay.glow;
+            let stroke = (stroke_r, stroke_g, stroke_b, stroke_a);
+            let glow_brush = glow_color(glow, stroke).and_then(|(r, g, b, a)| {
+                let glow_color = D2D1_COLOR_F {
+                    r: r as f32,
+                    g: g as f32,
+                    b: b as f32,
+                    a: a as f32,
+                };
+                rt.CreateSolidColorBrush(&glow_color, None).ok()
+            });
+
             if let Ok(brush) = rt.CreateSolidColorBrush(&color, None) {
-                match state.display_mode {
+                brush.SetOpacity(fade_alpha);
+                if let Some(halo) = &halo_brush {
+                    halo.SetOpacity(fade_alpha);
+                }
+                if let Some(glow) = &glow_brush {
+                    glow.SetOpacity(fade_alpha);
+                }
+                match state.overlay.display_mode {
                     DISPLAY_MODE_LEFT | DISPLAY_MODE_RIGHT => {
-                        let letter = if state.display_mode == DISPLAY_MODE_LEFT {
-                            if state.lang == LANG_ES {
-                                'I'
-                            } else {
-                                'L'
-                            }
-                        } else if state.lang == LANG_ES {
-                            'D'
-                        } else {
-                            'R'
+                        let left = state.overlay.display_mode == DISPLAY_MODE_LEFT;
+                        let custom = state.overlay.custom_click_letter(left);
+                        let letter_face = letter_font_face(
+                            &state.overlay.letter_font,
+                            state.overlay.letter_weight,
+                        );
+                        let letter_face = letter_face.as_ref().or(font_face);
+
+                        let font_size = letter_height(radius as f64) as f32;
+                        let max_width = letter_max_width(radius as f64) as f32;
+                        let letter_geometry = |ff: &IDWriteFontFace, text: &str| {
+                            glyph_geometry(factory, ff, text, font_size, max_width)
                         };
 
-                        let font_size = 3.0 * radius;
+                        // The icon when one is chosen and the icon font is installed, else
+                        // the letter; custom text the font cannot draw falls back to the
+                        // language's letter
+                        let icon_geometry = click_icon(state.overlay.click_icon).and_then(|icon| {
+                            letter_geometry(&icon_font_face()?, &icon.glyph.to_string())
+                        });
+                        let geometry = icon_geometry.or_else(|| {
+                            let ff = letter_face?;
+                            letter_geometry(ff, &click_label(custom, left))
+                                .or_else(|| letter_geometry(ff, &click_letter(left).to_string()))
+                        });
+                        let placed = geometry.and_then(|g| Some((centering_offset(&g, x, y)?, g)));
+                        let drew_outline = if let Some(((dx, dy), letter_geom)) = placed {
+                            draw_translated(&rt, dx, dy, || {
+                                if let Some(glow_brush) = &glow_brush {
+                                    draw_glow(&rt, glow_brush, glow, border, &|b, w| {
+                                        rt.DrawGeometry(
+                                            &letter_geom,
+                                            b,
+                                            w,
+                                            Some(&cache.stroke_style),
+                                        )
+                                    });
+                                }
 
-                        let drew_outline = if let Some(ff) = font_face {
-                            if let Some(letter_geom) =
-                                create_letter_geometry(factory, ff, letter, font_size, x, y)
-                            {
-                                let fill_alpha = 1.0 - (state.fill_transparency_pct as f32 / 100.0);
+                                let fill_alpha = fill_alpha(
+                                    stroke_a * fade_alpha as f64,
+                                    state.overlay.fill_transparency_pct,
+                                ) as f32;
                                 if fill_alpha > 0.0 {
                                     let fill_color = D2D1_COLOR_F {
-                                        r: state.stroke_r,
-                                        g: state.stroke_g,
-                                        b: state.stroke_b,
+                                        r: stroke_r as f32,
+                                        g: stroke_g as f32,
+                                        b: stroke_b as f32,
                                         a: fill_alpha,
                                     };
                                     if let Ok(fill_brush) =
@@ -423,16 +1518,22 @@ unsafe fn update_layered_window_d2d(
                                     }
                                 }
 
+                                if let Some(halo) = &halo_brush {
+                                    rt.DrawGeometry(
+                                        &letter_geom,
+                                        halo,
+                                        halo_width,
+                                        Some(&cache.stroke_style),
+                                    );
+                                }
                                 rt.DrawGeometry(
                                     &letter_geom,
                                     &brush,
                                     border,
                                     Some(&cache.stroke_style),
                                 );
-                                true
-                            } else {
-                                false
-                            }
+                            });
+                            true
                         } else {
                             false
                         };
@@ -445,6 +1546,64 @@ unsafe fn update_layered_window_d2d(
                             };
                             rt.FillEllipse(&ellipse, &brush);
                         }
+
+                        // Ring filling up while the button stays down
+                        if let Some(progress) = state.hold_progress {
+                            let mut ring = DisplayList::new();
+                            effects::hold_ring(
+                                &mut ring,
+                                (x as f64, y as f64),
+                                radius as f64,
+                                border as f64,
+                                progress,
+                                (stroke_r, stroke_g, stroke_b, stroke_a * fade_alpha as f64),
+                                state.overlay.halo,
+                            );
+                            draw_display_list(&rt, factory, &ring, &cache.stroke_style);
+                        }
+
+                        // Multi-click counter next to the letter
+                        let badge = font_face
+                            .filter(|_| state.overlay.click_burst)
+                            .zip(badge_label(state.click_count));
+                        if let Some((ff, label)) = badge {
+                            let (left, bottom) =
+                                badge_anchor((x as f64, y as f64), radius as f64, false);
+                            let size = badge_size(radius as f64) as f32;
+                            let badge_geom =
+                                glyph_geometry(factory, ff, &label, size, f32::INFINITY);
+                            let placed =
+                                badge_geom.and_then(|g| Some((g.GetBounds(None).ok()?, g)));
+                            if let Some((bounds, badge_geom)) = placed {
+                                let dx = left as f32 - bounds.left;
+                                let dy = bottom as f32 - bounds.bottom;
+                                draw_translated(&rt, dx, dy, || {
+                                    if let Some(halo) = &halo_brush {
+                                        rt.DrawGeometry(
+                                            &badge_geom,
+                                            halo,
+                                            outline_halo_width() as f32,
+                                            Some(&cache.stroke_style),
+                                        );
+                                    }
+                                    rt.FillGeometry(&badge_geom, &brush, None);
+                                });
+                            }
+                        }
+                    }
+                    _ if state.overlay.highlight_style == HIGHLIGHT_STYLE_LASER => {
+                        draw_laser(
+                            &rt,
+                            factory,
+                            &brush,
+                            halo_brush.as_ref(),
+                            glow_brush.as_ref(),
+                            state,
+                            x,
+                            y,
+                            &cache.stroke_style,
+                            &to_local,
+                        );
                     }
                     _ => {
                         let ellipse = D2D1_ELLIPSE {
@@ -453,12 +1612,21 @@ unsafe fn update_layered_window_d2d(
                             radiusY: radius,
                         };
 
-                        let fill_alpha = 1.0 - (state.fill_transparency_pct as f32 / 100.0);
+                        if let Some(glow_brush) = &glow_brush {
+                            draw_glow(&rt, glow_brush, glow, border, &|b, w| {
+                                rt.DrawEllipse(&ellipse, b, w, Some(&cache.stroke_style))
+                            });
+                        }
+
+                        let fill_alpha = fill_alpha(
+                            stroke_a * fade_alpha as f64,
+                            state.overlay.fill_transparency_pct,
+                        ) as f32;
                         if fill_alpha > 0.0 {
                             let fill_color = D2D1_COLOR_F {
-                                r: state.stroke_r,
-                                g: state.stroke_g,
-                                b: state.stroke_b,
+                                r: stroke_r as f32,
+                                g: stroke_g as f32,
+                                b: stroke_b as f32,
                                 a: fill_alpha,
                             };
                             if let Ok(fill_brush) = rt.CreateSolidColorBrush(&fill_color, None) {
@@ -466,13 +1634,42 @@ unsafe fn update_layered_window_d2d(
                             }
                         }
 
+                        if let Some(halo) = &halo_brush {
+                            rt.DrawEllipse(&ellipse, halo, halo_width, Some(&cache.stroke_style));
+                        }
                         rt.DrawEllipse(&ellipse, &brush, border, Some(&cache.stroke_style));
                     }
                 }
             }
+
+            // Crosshair on the pen tip
+            if state.pen_pressure.is_some() {
+                let stroke = (stroke_r, stroke_g, stroke_b, stroke_a * fade_alpha as f64);
+                let mut tip = DisplayList::new();
+                effects::pen_tip(&mut tip, (x as f64, y as f64), stroke);
+                draw_display_list(&rt, factory, &tip, &cache.stroke_style);
+            }
+
+            // Coordinates readout beside the pointer
+            if let Some(ff) = font_face.filter(|_| state.show_coordinates) {
+                draw_coordinates_label(&rt, factory, ff, state, cursor, x, y, radius);
+            }
+
+            // Sampled color opposite the coordinates
+            if let Some((ff, color)) = font_face.zip(state.color_sample) {
+                draw_color_sample(&rt, factory, ff, state, cursor, x, y, radius, color);
+            }
+        }
+
+        if let Some((timer, ff)) = state.diagnostics.as_ref().zip(font_face) {
+            draw_debug_label(&rt, factory, ff, state, cursor, timer.average_ms());
         }
 
-        let _ = rt.EndDraw(None, None);
+        if let Err(e) = rt.EndDraw(None, None) {
+            if e.code() == D2DERR_RECREATE_TARGET {
+                return true;
+            }
+        }
 
         // Apply to window
         let pt_src = POINT { x: 0, y: 0 };
@@ -514,5 +1711,12 @@ unsafe fn update_layered_window_d2d(
             0,
             SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
         );
+        false
     });
+
+    // The device went away (driver update, remote session): start over on
+    // the next frame
+    if lost {
+        invalidate_render_target();
+    }
 }
@@ -0,0 +1,31 @@
+//! Session restore for auxiliary windows.
+//!
+//! Reopens the auxiliary windows that were open when the app last ran,
+//! using the `model::session` store persisted in config.toml.
+
+use windows::Win32::Foundation::{HWND, LPARAM, WPARAM};
+use windows::Win32::UI::WindowsAndMessaging::{PostMessageW, WM_HOTKEY};
+
+use crate::model::session::SESSION_HELP_OVERLAY;
+use crate::platform::windows::input::HOTKEY_HELP;
+use crate::platform::windows::storage::config;
+
+/// Reopen auxiliary windows saved in the previous session.
+///
+/// Windows are reopened by posting the same messages their hotkeys send,
+/// so they open once the main message loop is running.
+pub fn restore_session_windows(hwnd: HWND) {
+    let session = config::load_session();
+    for id in session.open_windows() {
+        if id == SESSION_HELP_OVERLAY {
+            unsafe {
+                let _ = PostMessageW(
+                    Some(hwnd),
+                    WM_HOTKEY,
+                    WPARAM(HOTKEY_HELP as usize),
+                    LPARAM(0),
+                );
+            }
+        }
+    }
+}
@@ -1,759 +1,228 @@
-//! Settings window for Windows.
+//! Settings window and first-run walkthrough for Windows.
 //!
-//! A modal dialog with controls for configuring the overlay appearance.
+//! Hosts the shared egui panels (`crate::settings`); this module only
+//! adapts `config.toml` and the overlay state to [`SettingsStore`].
 
-use crate::model::constants::*;
-use crate::platform::windows::storage::config;
-use crate::platform::windows::ui::tray;
-use std::cell::RefCell;
-use windows::core::{w, PCWSTR};
-use windows::Win32::Foundation::{COLORREF, HWND, LPARAM, LRESULT, WPARAM};
-use windows::Win32::Graphics::Gdi::{
-    CreateFontW, CreateSolidBrush, GetSysColorBrush, InvalidateRect, SetBkMode,
-    CLIP_DEFAULT_PRECIS, DEFAULT_CHARSET, DEFAULT_QUALITY, FW_NORMAL, HBRUSH, HDC, HFONT,
-    OUT_DEFAULT_PRECIS, TRANSPARENT,
+use std::cell::{Cell, RefCell};
+use std::path::Path;
+
+use tracing::warn;
+
+use crate::model::constants::{
+    PREF_CONFIRM_QUIT, PREF_EXCLUDE_FROM_CAPTURE, PREF_HOTKEY_OVERRIDES, PREF_ONBOARDING_DONE,
+    PREF_TOGGLE_NOTIFICATION,
 };
-use windows::Win32::System::LibraryLoader::GetModuleHandleW;
-use windows::Win32::UI::Controls::Dialogs::{ChooseColorW, CC_FULLOPEN, CC_RGBINIT, CHOOSECOLORW};
-use windows::Win32::UI::Input::KeyboardAndMouse::EnableWindow;
-use windows::Win32::UI::WindowsAndMessaging::{
-    CreateWindowExW, DefWindowProcW, DestroyWindow, DispatchMessageW, GetDlgCtrlID, GetDlgItem,
-    GetMessageW, GetSystemMetrics, GetWindowLongPtrW, LoadCursorW, PostMessageW, RegisterClassW,
-    SendMessageW, SetWindowLongPtrW, SetWindowTextW, ShowWindow, TranslateMessage, CS_HREDRAW,
-    CS_VREDRAW, GWLP_USERDATA, HMENU, IDC_ARROW, MSG, SM_CXSCREEN, SM_CYSCREEN, SW_SHOW,
-    WINDOW_EX_STYLE, WINDOW_STYLE, WM_CLOSE, WM_COMMAND, WM_CREATE, WM_DESTROY, WM_HSCROLL,
-    WM_SETFONT, WNDCLASSW, WS_CAPTION, WS_CHILD, WS_OVERLAPPED, WS_SYSMENU, WS_TABSTOP, WS_VISIBLE,
+use crate::model::placement::display_key;
+use crate::model::{encode_overrides, parse_overrides, Binding, ColorPalette, OverlayState};
+use crate::platform::windows::app::state::reload_settings_from_config;
+use crate::platform::windows::ffi::{
+    font_families, monitor_rect_at, run_at_startup, set_run_at_startup, system_locale,
 };
-
-// Control IDs
-const ID_RADIUS_SLIDER: i32 = 102;
-const ID_RADIUS_VALUE: i32 = 103;
-const ID_BORDER_SLIDER: i32 = 104;
-const ID_BORDER_VALUE: i32 = 105;
-const ID_COLOR_BUTTON: i32 = 106;
-const ID_COLOR_PREVIEW: i32 = 111;
-const ID_TRANSP_SLIDER: i32 = 107;
-const ID_TRANSP_VALUE: i32 = 108;
-const ID_LANG_COMBO: i32 = 109;
-const ID_CLOSE_BUTTON: i32 = 110;
-
-// Trackbar messages (from commctrl.h)
-const TBM_SETRANGE: u32 = 0x0406;
-const TBM_SETPOS: u32 = 0x0405;
-const TBM_GETPOS: u32 = 0x0400;
-
-// ComboBox messages
-const CB_ADDSTRING: u32 = 0x0143;
-const CB_SETCURSEL: u32 = 0x014E;
-const CB_GETCURSEL: u32 = 0x0147;
-const CBN_SELCHANGE: u32 = 1;
-
-// Window dimensions
-const WINDOW_WIDTH: i32 = 420;
-const WINDOW_HEIGHT: i32 = 340;
-
-// Layout constants
-const MARGIN: i32 = 24;
-const ROW_HEIGHT: i32 = 44;
-const LABEL_WIDTH: i32 = 150;
-const VALUE_WIDTH: i32 = 45;
-const SLIDER_WIDTH: i32 = 160;
-const COLOR_PREVIEW_SIZE: i32 = 24;
+use crate::platform::windows::input::{
+    current_hotkey_bindings, register_hotkeys, unregister_hotkeys,
+};
+use crate::platform::windows::storage::{config, is_setting_locked, watch_directory};
+use crate::platform::windows::ui::dialogs::choose_sync_folder;
+use crate::platform::windows::ui::tray;
+use crate::settings::{open_settings_window as open_hosted, run_onboarding_window};
+use crate::settings::{HostedWindow, SettingsStore};
+use windows::Win32::Foundation::HWND;
+use windows::Win32::UI::WindowsAndMessaging::{GetSystemMetrics, SM_CXSCREEN, SM_CYSCREEN};
 
 thread_local! {
-    static SETTINGS_HWND: RefCell<Option<HWND>> = const { RefCell::new(None) };
-    static PARENT_HWND: RefCell<Option<HWND>> = const { RefCell::new(None) };
-    static ON_SETTINGS_CHANGED: RefCell<Option<Box<dyn Fn()>>> = const { RefCell::new(None) };
-    static UI_FONT: RefCell<Option<HFONT>> = const { RefCell::new(None) };
+    static SETTINGS_OPEN: Cell<bool> = const { Cell::new(false) };
+    /// The open Settings window, pumped by the main message loop.
+    static SETTINGS_WINDOW: RefCell<Option<HostedWindow>> = const { RefCell::new(None) };
 }
 
-/// Set callback for when settings change.
-pub fn set_on_settings_changed<F: Fn() + 'static>(callback: F) {
-    ON_SETTINGS_CHANGED.with(|c| {
-        *c.borrow_mut() = Some(Box::new(callback));
-    });
+/// Settings backed by `config.toml`, applied live to the overlay.
+struct WindowsSettingsStore {
+    /// Display key of the primary monitor, where the window opens.
+    display: Option<String>,
+    /// Main window, told when the sync folder changes.
+    hwnd: HWND,
 }
 
-fn notify_settings_changed() {
-    ON_SETTINGS_CHANGED.with(|c| {
-        if let Some(ref callback) = *c.borrow() {
-            callback();
-        }
-    });
+impl WindowsSettingsStore {
+    fn new(hwnd: HWND) -> Self {
+        // The window is centered on the primary monitor
+        let (width, height) =
+            unsafe { (GetSystemMetrics(SM_CXSCREEN), GetSystemMetrics(SM_CYSCREEN)) };
+        let display = monitor_rect_at(width / 2, height / 2).map(|rect| display_key(&rect));
+        Self { display, hwnd }
+    }
 }
 
-/// Open the settings window.
-pub fn open_settings_window(parent_hwnd: HWND) {
-    // Check if already open
-    let already_open = SETTINGS_HWND.with(|h| h.borrow().is_some());
-    if already_open {
-        return;
+impl SettingsStore for WindowsSettingsStore {
+    fn load_state(&self) -> OverlayState {
+        config::load_state()
     }
 
-    PARENT_HWND.with(|h| *h.borrow_mut() = Some(parent_hwnd));
-
-    unsafe {
-        // Register window class
-        let class_name = w!("LumbusSettings");
-        let hinstance = GetModuleHandleW(None).unwrap_or_default();
-
-        let wc = WNDCLASSW {
-            style: CS_HREDRAW | CS_VREDRAW,
-            lpfnWndProc: Some(settings_wnd_proc),
-            hInstance: hinstance.into(),
-            hCursor: LoadCursorW(None, IDC_ARROW).unwrap_or_default(),
-            // Use system button face color for consistent look with controls
-            // COLOR_BTNFACE = 15, adding 1 converts it to a system color brush handle
-            hbrBackground: HBRUSH((15 + 1) as *mut _),
-            lpszClassName: class_name,
-            ..Default::default()
-        };
-        let _ = RegisterClassW(&wc);
-
-        // Create Segoe UI Semilight font (elegant, modern Windows font)
-        let font_name: Vec<u16> = "Segoe UI Semilight"
-            .encode_utf16()
-            .chain(std::iter::once(0))
-            .collect();
-        let font = CreateFontW(
-            -14, // height (slightly smaller for elegance)
-            0,
-            0,
-            0,
-            FW_NORMAL.0 as i32,
-            0,
-            0,
-            0,
-            DEFAULT_CHARSET,
-            OUT_DEFAULT_PRECIS,
-            CLIP_DEFAULT_PRECIS,
-            DEFAULT_QUALITY,
-            0, // DEFAULT_PITCH | FF_DONTCARE
-            PCWSTR(font_name.as_ptr()),
-        );
-        UI_FONT.with(|f| *f.borrow_mut() = Some(font));
-
-        // Center window on screen
-        let screen_width = GetSystemMetrics(SM_CXSCREEN);
-        let screen_height = GetSystemMetrics(SM_CYSCREEN);
-        let x = (screen_width - WINDOW_WIDTH) / 2;
-        let y = (screen_height - WINDOW_HEIGHT) / 2;
-
-        // Create window
-        let hwnd = CreateWindowExW(
-            WINDOW_EX_STYLE::default(),
-            class_name,
-            w!("Settings"),
-            WS_OVERLAPPED | WS_CAPTION | WS_SYSMENU,
-            x,
-            y,
-            WINDOW_WIDTH,
-            WINDOW_HEIGHT,
-            Some(parent_hwnd),
-            None,
-            Some(hinstance.into()),
-            None,
-        );
-
-        let hwnd = match hwnd {
-            Ok(h) => h,
-            Err(_) => {
-                eprintln!("Failed to create settings window");
-                return;
-            }
-        };
-
-        SETTINGS_HWND.with(|h| *h.borrow_mut() = Some(hwnd));
-
-        // Disable parent window (modal behavior)
-        let _ = EnableWindow(parent_hwnd, false);
-
-        let _ = ShowWindow(hwnd, SW_SHOW);
-
-        // Message loop for the settings window
-        let mut msg = MSG::default();
-        while GetMessageW(&mut msg, None, 0, 0).as_bool() {
-            let still_open = SETTINGS_HWND.with(|h| h.borrow().is_some());
-            if !still_open {
-                break;
-            }
-            let _ = TranslateMessage(&msg);
-            DispatchMessageW(&msg);
-        }
+    fn set_double(&mut self, key: &str, value: f64) {
+        config::prefs_set_double(key, value);
     }
-}
 
-/// Close the settings window.
-///
-/// Flushes config changes to disk before closing.
-pub fn close_settings_window() {
-    // Persist config changes to disk
-    config::flush_config();
+    fn set_int(&mut self, key: &str, value: i32) {
+        config::prefs_set_int(key, value);
+    }
 
-    // Take the HWND first, releasing the borrow before calling DestroyWindow
-    // (DestroyWindow sends WM_DESTROY synchronously which would cause a borrow conflict)
-    let hwnd_to_destroy = SETTINGS_HWND.with(|h| h.borrow_mut().take());
-
-    if let Some(hwnd) = hwnd_to_destroy {
-        unsafe {
-            PARENT_HWND.with(|p| {
-                if let Some(parent) = *p.borrow() {
-                    let _ = EnableWindow(parent, true);
-                }
-            });
-            let _ = DestroyWindow(hwnd);
-        }
+    fn set_string(&mut self, key: &str, value: &str) {
+        config::prefs_set_string(key, value);
     }
-}
 
-unsafe extern "system" fn settings_wnd_proc(
-    hwnd: HWND,
-    msg: u32,
-    wparam: WPARAM,
-    lparam: LPARAM,
-) -> LRESULT {
-    match msg {
-        WM_CREATE => {
-            create_controls(hwnd);
-            LRESULT(0)
-        }
+    fn is_locked(&self, key: &str) -> bool {
+        is_setting_locked(key)
+    }
 
-        WM_COMMAND => {
-            let control_id = (wparam.0 & 0xFFFF) as i32;
-            let notification = ((wparam.0 >> 16) & 0xFFFF) as u32;
-            handle_command(hwnd, control_id, notification, lparam);
-            LRESULT(0)
-        }
+    fn flush(&mut self) {
+        config::flush_config();
+    }
 
-        WM_HSCROLL => {
-            let slider_hwnd = HWND(lparam.0 as *mut _);
-            handle_slider_change(slider_hwnd);
-            LRESULT(0)
+    fn frame_color(&self) -> (f64, f64, f64, f64) {
+        let colors = config::load_frame_colors();
+        match &self.display {
+            Some(key) => colors.color_for(key),
+            None => crate::model::constants::DEFAULT_SCREEN_FRAME_COLOR,
         }
+    }
 
-        // WM_CTLCOLORSTATIC = 0x0138
-        0x0138 => {
-            let hdc = HDC(wparam.0 as *mut _);
-            let control_hwnd = HWND(lparam.0 as *mut _);
-            let control_id = GetDlgCtrlID(control_hwnd);
-
-            if control_id == ID_COLOR_PREVIEW {
-                // Color preview uses custom color from GWLP_USERDATA
-                let color = COLORREF(GetWindowLongPtrW(control_hwnd, GWLP_USERDATA) as u32);
-                let brush = CreateSolidBrush(color);
-                return LRESULT(brush.0 as isize);
-            }
-
-            // For all other STATIC controls (labels), use system button face color
-            // This ensures consistent background with the window and other controls
-            SetBkMode(hdc, TRANSPARENT);
-            // COLOR_BTNFACE = 15
-            let brush = GetSysColorBrush(windows::Win32::Graphics::Gdi::SYS_COLOR_INDEX(15));
-            LRESULT(brush.0 as isize)
+    fn set_frame_color(&mut self, color: (f64, f64, f64, f64)) {
+        if let Some(key) = &self.display {
+            let mut colors = config::load_frame_colors();
+            colors.set(key, color);
+            config::save_frame_colors(&colors);
         }
+    }
 
-        WM_CLOSE => {
-            close_settings_window();
-            LRESULT(0)
-        }
+    fn restore_defaults(&mut self) {
+        config::restore_defaults();
+    }
 
-        WM_DESTROY => {
-            // Cleanup already done by close_settings_window, just post quit
-            PostMessageW(None, 0x0012, WPARAM(0), LPARAM(0)).ok();
-            LRESULT(0)
-        }
+    fn font_families(&self) -> Vec<String> {
+        font_families()
+    }
 
-        _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+    fn color_palette(&self) -> ColorPalette {
+        config::load_color_palette()
     }
-}
 
-unsafe fn create_controls(hwnd: HWND) {
-    let hinstance = GetModuleHandleW(None).unwrap_or_default();
-    let state = config::load_state();
-    let is_spanish = state.lang == LANG_ES;
-
-    // Update window title based on language
-    let title = if is_spanish {
-        "Configuración"
-    } else {
-        "Settings"
-    };
-    let title_wide: Vec<u16> = title.encode_utf16().chain(std::iter::once(0)).collect();
-    let _ = SetWindowTextW(hwnd, PCWSTR(title_wide.as_ptr()));
-
-    let mut y = MARGIN;
-
-    // Radius row
-    let radius_label = if is_spanish {
-        "Radio (px)"
-    } else {
-        "Radius (px)"
-    };
-    create_label(hwnd, hinstance.into(), MARGIN, y, radius_label);
-    let radius_value = create_value_label(
-        hwnd,
-        hinstance.into(),
-        MARGIN + LABEL_WIDTH,
-        y,
-        ID_RADIUS_VALUE,
-    );
-    set_value_text(radius_value, state.radius as i32);
-    let radius_slider = create_slider(
-        hwnd,
-        hinstance.into(),
-        MARGIN + LABEL_WIDTH + VALUE_WIDTH + 10,
-        y,
-        ID_RADIUS_SLIDER,
-    );
-    init_slider(
-        radius_slider,
-        MIN_RADIUS as i32,
-        MAX_RADIUS as i32,
-        state.radius as i32,
-    );
-    SetWindowLongPtrW(radius_slider, GWLP_USERDATA, radius_value.0 as isize);
-
-    y += ROW_HEIGHT;
-
-    // Border row
-    let border_label = if is_spanish {
-        "Borde (px)"
-    } else {
-        "Border (px)"
-    };
-    create_label(hwnd, hinstance.into(), MARGIN, y, border_label);
-    let border_value = create_value_label(
-        hwnd,
-        hinstance.into(),
-        MARGIN + LABEL_WIDTH,
-        y,
-        ID_BORDER_VALUE,
-    );
-    set_value_text(border_value, state.border_width as i32);
-    let border_slider = create_slider(
-        hwnd,
-        hinstance.into(),
-        MARGIN + LABEL_WIDTH + VALUE_WIDTH + 10,
-        y,
-        ID_BORDER_SLIDER,
-    );
-    init_slider(
-        border_slider,
-        MIN_BORDER as i32,
-        MAX_BORDER as i32,
-        state.border_width as i32,
-    );
-    SetWindowLongPtrW(border_slider, GWLP_USERDATA, border_value.0 as isize);
-
-    y += ROW_HEIGHT;
-
-    // Color row
-    create_label(hwnd, hinstance.into(), MARGIN, y, "Color");
-
-    // Color preview square
-    let r = (state.stroke_r * 255.0) as u32;
-    let g = (state.stroke_g * 255.0) as u32;
-    let b = (state.stroke_b * 255.0) as u32;
-    let current_color = COLORREF(r | (g << 8) | (b << 16));
-    create_color_preview(
-        hwnd,
-        hinstance.into(),
-        MARGIN + LABEL_WIDTH,
-        y,
-        current_color,
-    );
-
-    // Choose button
-    let choose_label = if is_spanish { "Elegir..." } else { "Choose..." };
-    create_button(
-        hwnd,
-        hinstance.into(),
-        MARGIN + LABEL_WIDTH + COLOR_PREVIEW_SIZE + 10,
-        y,
-        choose_label,
-        ID_COLOR_BUTTON,
-        90,
-    );
-
-    y += ROW_HEIGHT;
-
-    // Transparency row
-    let transp_label = if is_spanish {
-        "Transparencia (%)"
-    } else {
-        "Fill Transparency (%)"
-    };
-    create_label(hwnd, hinstance.into(), MARGIN, y, transp_label);
-    let transp_value = create_value_label(
-        hwnd,
-        hinstance.into(),
-        MARGIN + LABEL_WIDTH,
-        y,
-        ID_TRANSP_VALUE,
-    );
-    set_value_text(transp_value, state.fill_transparency_pct as i32);
-    let transp_slider = create_slider(
-        hwnd,
-        hinstance.into(),
-        MARGIN + LABEL_WIDTH + VALUE_WIDTH + 10,
-        y,
-        ID_TRANSP_SLIDER,
-    );
-    init_slider(
-        transp_slider,
-        MIN_TRANSPARENCY as i32,
-        MAX_TRANSPARENCY as i32,
-        state.fill_transparency_pct as i32,
-    );
-    SetWindowLongPtrW(transp_slider, GWLP_USERDATA, transp_value.0 as isize);
-
-    y += ROW_HEIGHT;
-
-    // Language row
-    let lang_label = if is_spanish { "Idioma" } else { "Language" };
-    create_label(hwnd, hinstance.into(), MARGIN, y, lang_label);
-    let lang_combo = create_combobox(
-        hwnd,
-        hinstance.into(),
-        MARGIN + LABEL_WIDTH,
-        y,
-        ID_LANG_COMBO,
-    );
-    // Add language options
-    let en_text: Vec<u16> = "English".encode_utf16().chain(std::iter::once(0)).collect();
-    let es_text: Vec<u16> = "Español".encode_utf16().chain(std::iter::once(0)).collect();
-    SendMessageW(
-        lang_combo,
-        CB_ADDSTRING,
-        None,
-        Some(LPARAM(en_text.as_ptr() as isize)),
-    );
-    SendMessageW(
-        lang_combo,
-        CB_ADDSTRING,
-        None,
-        Some(LPARAM(es_text.as_ptr() as isize)),
-    );
-    // Set current selection (0 = English, 1 = Spanish)
-    let current_lang = if state.lang == LANG_ES { 1 } else { 0 };
-    SendMessageW(lang_combo, CB_SETCURSEL, Some(WPARAM(current_lang)), None);
-
-    y += ROW_HEIGHT + 10;
-
-    // Close button
-    let close_label = if is_spanish { "Cerrar" } else { "Close" };
-    create_button(
-        hwnd,
-        hinstance.into(),
-        WINDOW_WIDTH - 100 - MARGIN,
-        y,
-        close_label,
-        ID_CLOSE_BUTTON,
-        80,
-    );
-}
+    fn set_color_palette(&mut self, palette: &ColorPalette) {
+        config::save_color_palette(palette);
+    }
 
-unsafe fn apply_font(control: HWND) {
-    UI_FONT.with(|f| {
-        if let Some(font) = *f.borrow() {
-            SendMessageW(
-                control,
-                WM_SETFONT,
-                Some(WPARAM(font.0 as usize)),
-                Some(LPARAM(1)),
-            );
-        }
-    });
-}
+    fn system_locale(&self) -> Option<String> {
+        system_locale()
+    }
 
-unsafe fn create_label(
-    hwnd: HWND,
-    hinstance: windows::Win32::Foundation::HINSTANCE,
-    x: i32,
-    y: i32,
-    text: &str,
-) {
-    let text_wide: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
-    if let Ok(label) = CreateWindowExW(
-        WINDOW_EX_STYLE::default(),
-        w!("STATIC"),
-        PCWSTR(text_wide.as_ptr()),
-        WS_CHILD | WS_VISIBLE,
-        x,
-        y + 6,
-        LABEL_WIDTH,
-        22,
-        Some(hwnd),
-        None,
-        Some(hinstance),
-        None,
-    ) {
-        apply_font(label);
+    fn settings_changed(&mut self) {
+        reload_settings_from_config();
     }
-}
 
-unsafe fn create_value_label(
-    hwnd: HWND,
-    hinstance: windows::Win32::Foundation::HINSTANCE,
-    x: i32,
-    y: i32,
-    id: i32,
-) -> HWND {
-    let label = CreateWindowExW(
-        WINDOW_EX_STYLE::default(),
-        w!("STATIC"),
-        w!("0"),
-        WS_CHILD | WS_VISIBLE | WINDOW_STYLE(0x0001), // SS_CENTER
-        x,
-        y + 6,
-        VALUE_WIDTH,
-        22,
-        Some(hwnd),
-        Some(HMENU(id as *mut _)),
-        Some(hinstance),
-        None,
-    )
-    .unwrap_or_default();
-    apply_font(label);
-    label
-}
+    fn language_changed(&mut self) {
+        tray::update_tray_language();
+    }
 
-unsafe fn create_slider(
-    hwnd: HWND,
-    hinstance: windows::Win32::Foundation::HINSTANCE,
-    x: i32,
-    y: i32,
-    id: i32,
-) -> HWND {
-    CreateWindowExW(
-        WINDOW_EX_STYLE::default(),
-        w!("msctls_trackbar32"),
-        None,
-        WS_CHILD | WS_VISIBLE | WS_TABSTOP,
-        x,
-        y,
-        SLIDER_WIDTH,
-        28,
-        Some(hwnd),
-        Some(HMENU(id as *mut _)),
-        Some(hinstance),
-        None,
-    )
-    .unwrap_or_default()
-}
+    fn sync_folder(&self) -> Option<String> {
+        config::sync_folder().map(|f| f.display().to_string())
+    }
 
-unsafe fn create_button(
-    hwnd: HWND,
-    hinstance: windows::Win32::Foundation::HINSTANCE,
-    x: i32,
-    y: i32,
-    text: &str,
-    id: i32,
-    width: i32,
-) {
-    let text_wide: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
-    if let Ok(button) = CreateWindowExW(
-        WINDOW_EX_STYLE::default(),
-        w!("BUTTON"),
-        PCWSTR(text_wide.as_ptr()),
-        WS_CHILD | WS_VISIBLE | WS_TABSTOP,
-        x,
-        y + 2,
-        width,
-        28,
-        Some(hwnd),
-        Some(HMENU(id as *mut _)),
-        Some(hinstance),
-        None,
-    ) {
-        apply_font(button);
+    fn choose_sync_folder(&mut self) -> Option<String> {
+        choose_sync_folder()
     }
-}
 
-unsafe fn create_combobox(
-    hwnd: HWND,
-    hinstance: windows::Win32::Foundation::HINSTANCE,
-    x: i32,
-    y: i32,
-    id: i32,
-) -> HWND {
-    // CBS_DROPDOWNLIST = 0x0003
-    let combo = CreateWindowExW(
-        WINDOW_EX_STYLE::default(),
-        w!("COMBOBOX"),
-        None,
-        WS_CHILD | WS_VISIBLE | WS_TABSTOP | WINDOW_STYLE(0x0003),
-        x,
-        y + 2,
-        130,
-        120, // Height includes dropdown area
-        Some(hwnd),
-        Some(HMENU(id as *mut _)),
-        Some(hinstance),
-        None,
-    )
-    .unwrap_or_default();
-    apply_font(combo);
-    combo
-}
+    fn set_sync_folder(&mut self, folder: Option<&str>) {
+        config::set_sync_folder(folder.map(Path::new));
+        if let Some(folder) = config::sync_folder() {
+            watch_directory(self.hwnd, &folder);
+        }
+    }
 
-unsafe fn create_color_preview(
-    hwnd: HWND,
-    hinstance: windows::Win32::Foundation::HINSTANCE,
-    x: i32,
-    y: i32,
-    color: COLORREF,
-) -> HWND {
-    // SS_OWNERDRAW would be better, but for simplicity we create a static with a background brush
-    // We use SS_NOTIFY (0x0100) + SS_SUNKEN style (0x1000) for a bordered look
-    let preview = CreateWindowExW(
-        WINDOW_EX_STYLE::default(),
-        w!("STATIC"),
-        None,
-        WS_CHILD | WS_VISIBLE | WINDOW_STYLE(0x1000), // SS_SUNKEN
-        x,
-        y + 4,
-        COLOR_PREVIEW_SIZE,
-        COLOR_PREVIEW_SIZE,
-        Some(hwnd),
-        Some(HMENU(ID_COLOR_PREVIEW as *mut _)),
-        Some(hinstance),
-        None,
-    )
-    .unwrap_or_default();
-
-    // Set background color via subclassing would be complex; we'll update it in WM_CTLCOLORSTATIC
-    // For now, store color in GWLP_USERDATA
-    SetWindowLongPtrW(preview, GWLP_USERDATA, color.0 as isize);
-    preview
-}
+    fn hotkey_bindings(&self) -> Vec<Binding> {
+        current_hotkey_bindings()
+    }
 
-unsafe fn init_slider(slider: HWND, min: i32, max: i32, pos: i32) {
-    let range = ((max as isize) << 16) | (min as isize);
-    SendMessageW(slider, TBM_SETRANGE, Some(WPARAM(1)), Some(LPARAM(range)));
-    SendMessageW(
-        slider,
-        TBM_SETPOS,
-        Some(WPARAM(1)),
-        Some(LPARAM(pos as isize)),
-    );
-}
+    fn toggle_notification(&self) -> Option<bool> {
+        Some(config::prefs_get_int(PREF_TOGGLE_NOTIFICATION, 0) != 0)
+    }
 
-unsafe fn set_value_text(hwnd: HWND, value: i32) {
-    let text = format!("{}", value);
-    let text_wide: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
-    let _ = SetWindowTextW(hwnd, PCWSTR(text_wide.as_ptr()));
-}
+    fn confirm_quit(&self) -> Option<bool> {
+        Some(config::prefs_get_int(PREF_CONFIRM_QUIT, 1) != 0)
+    }
 
-unsafe fn handle_command(hwnd: HWND, control_id: i32, notification: u32, lparam: LPARAM) {
-    match control_id {
-        ID_CLOSE_BUTTON => {
-            close_settings_window();
-        }
-        ID_COLOR_BUTTON => {
-            show_color_picker(hwnd);
-        }
-        ID_LANG_COMBO => {
-            if notification == CBN_SELCHANGE {
-                let combo_hwnd = HWND(lparam.0 as *mut _);
-                let selection = SendMessageW(combo_hwnd, CB_GETCURSEL, None, None).0 as i32;
-                let new_lang = if selection == 1 { LANG_ES } else { LANG_EN };
-                config::prefs_set_int(PREF_LANG, new_lang);
-                // Update tray menu language
-                tray::update_tray_language(new_lang == LANG_ES);
-                notify_settings_changed();
-            }
+    fn exclude_from_capture(&self) -> Option<bool> {
+        Some(config::prefs_get_int(PREF_EXCLUDE_FROM_CAPTURE, 0) != 0)
+    }
+
+    fn run_at_startup(&self) -> Option<bool> {
+        Some(run_at_startup())
+    }
+
+    fn set_run_at_startup(&mut self, on: bool) {
+        if !set_run_at_startup(on) {
+            warn!("Could not change the startup registration");
         }
-        _ => {}
+    }
+
+    fn can_rebind_hotkeys(&self) -> bool {
+        true
+    }
+
+    fn hotkey_overrides(&self) -> Vec<Binding> {
+        parse_overrides(&config::prefs_get_string(PREF_HOTKEY_OVERRIDES, ""))
+    }
+
+    fn set_hotkey_overrides(&mut self, overrides: &[Binding]) {
+        config::prefs_set_string(PREF_HOTKEY_OVERRIDES, &encode_overrides(overrides));
+        unregister_hotkeys(self.hwnd);
+        register_hotkeys(self.hwnd);
+        tray::update_tray_language();
     }
 }
 
-unsafe fn handle_slider_change(slider_hwnd: HWND) {
-    use windows::Win32::UI::WindowsAndMessaging::GetDlgCtrlID;
-
-    let control_id = GetDlgCtrlID(slider_hwnd);
-    let pos = SendMessageW(slider_hwnd, TBM_GETPOS, None, None).0 as i32;
-    let value_hwnd = HWND(GetWindowLongPtrW(slider_hwnd, GWLP_USERDATA) as *mut _);
-
-    if !value_hwnd.is_invalid() {
-        set_value_text(value_hwnd, pos);
-    }
-
-    match control_id {
-        ID_RADIUS_SLIDER => {
-            // Snap to 5px increments
-            let snapped = ((pos + 2) / 5) * 5;
-            if snapped != pos {
-                SendMessageW(
-                    slider_hwnd,
-                    TBM_SETPOS,
-                    Some(WPARAM(1)),
-                    Some(LPARAM(snapped as isize)),
-                );
-                if !value_hwnd.is_invalid() {
-                    set_value_text(value_hwnd, snapped);
-                }
-            }
-            config::prefs_set_double(PREF_RADIUS, snapped as f64);
-        }
-        ID_BORDER_SLIDER => {
-            config::prefs_set_double(PREF_BORDER, pos as f64);
-        }
-        ID_TRANSP_SLIDER => {
-            // Snap to 5% increments
-            let snapped = ((pos + 2) / 5) * 5;
-            if snapped != pos {
-                SendMessageW(
-                    slider_hwnd,
-                    TBM_SETPOS,
-                    Some(WPARAM(1)),
-                    Some(LPARAM(snapped as isize)),
-                );
-                if !value_hwnd.is_invalid() {
-                    set_value_text(value_hwnd, snapped);
-                }
-            }
-            config::prefs_set_double(PREF_FILL_TRANSPARENCY, snapped as f64);
-        }
-        _ => return,
+/// Open the settings window and return at once; it stays open, next to
+/// the overlay, while the main message loop pumps it (see
+/// [`pump_settings_window`]). Changes apply live.
+pub fn open_settings_window(parent_hwnd: HWND) {
+    if SETTINGS_OPEN.with(|o| o.replace(true)) {
+        return;
     }
 
-    notify_settings_changed();
+    match open_hosted(Box::new(WindowsSettingsStore::new(parent_hwnd))) {
+        Some(window) => SETTINGS_WINDOW.with(|w| *w.borrow_mut() = Some(window)),
+        None => SETTINGS_OPEN.with(|o| o.set(false)),
+    }
 }
 
-unsafe fn show_color_picker(hwnd: HWND) {
-    let state = config::load_state();
-    let r = (state.stroke_r * 255.0) as u32;
-    let g = (state.stroke_g * 255.0) as u32;
-    let b = (state.stroke_b * 255.0) as u32;
-    let initial_color = COLORREF(r | (g << 8) | (b << 16));
-
-    let mut custom_colors = [COLORREF(0xFFFFFF); 16];
-
-    let mut cc = CHOOSECOLORW {
-        lStructSize: std::mem::size_of::<CHOOSECOLORW>() as u32,
-        hwndOwner: hwnd,
-        rgbResult: initial_color,
-        lpCustColors: custom_colors.as_mut_ptr(),
-        Flags: CC_FULLOPEN | CC_RGBINIT,
-        ..Default::default()
-    };
-
-    if ChooseColorW(&mut cc).as_bool() {
-        let new_r = (cc.rgbResult.0 & 0xFF) as f64 / 255.0;
-        let new_g = ((cc.rgbResult.0 >> 8) & 0xFF) as f64 / 255.0;
-        let new_b = ((cc.rgbResult.0 >> 16) & 0xFF) as f64 / 255.0;
-
-        config::prefs_set_double(PREF_STROKE_R, new_r);
-        config::prefs_set_double(PREF_STROKE_G, new_g);
-        config::prefs_set_double(PREF_STROKE_B, new_b);
-
-        // Update color preview
-        if let Ok(preview) = GetDlgItem(Some(hwnd), ID_COLOR_PREVIEW) {
-            SetWindowLongPtrW(preview, GWLP_USERDATA, cc.rgbResult.0 as isize);
-            let _ = InvalidateRect(Some(preview), None, true);
-        }
+/// While the settings window is open, wait for the next messages of the
+/// thread and handle them (the overlay's included), then return `true`.
+/// Returns `false` when it is not open, after closing it if it was
+/// closed meanwhile: config changes are flushed to disk on close.
+///
+/// Called by the main message loop in place of `GetMessageW`.
+pub fn pump_settings_window() -> bool {
+    let open = SETTINGS_WINDOW.with(|w| w.borrow_mut().as_mut().map(HostedWindow::pump));
+    match open {
+        Some(true) => true,
+        Some(false) => {
+            SETTINGS_WINDOW.with(|w| *w.borrow_mut() = None);
+            config::flush_config();
+            SETTINGS_OPEN.with(|o| o.set(false));
+            false
+        }
+        None => false,
+    }
+}
 
-        notify_settings_changed();
+/// Show the first-run walkthrough and return once it is finished, skipped
+/// or closed, recording that in `PREF_ONBOARDING_DONE`.
+pub fn open_onboarding_window(parent_hwnd: HWND) {
+    if SETTINGS_OPEN.with(|o| o.replace(true)) {
+        return;
     }
+
+    run_onboarding_window(Box::new(WindowsSettingsStore::new(parent_hwnd)));
+
+    config::prefs_set_int(PREF_ONBOARDING_DONE, 1);
+    config::flush_config();
+    SETTINGS_OPEN.with(|o| o.set(false));
 }
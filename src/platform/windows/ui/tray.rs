@@ -1,25 +1,45 @@
 //! System tray (notification area) icon for Windows.
 //!
-//! Provides a tray icon with context menu for controlling the overlay.
-//! Automatically selects light/dark icon based on system theme.
+//! Provides a tray icon with context menu for controlling the overlay,
+//! and notifications (a new version is available).
+//! Automatically selects light/dark icon based on system theme, and
+//! shows the state of the highlight on it (see `model::tray_icon`).
 
-use std::cell::RefCell;
-use windows::core::{w, PCWSTR};
+use std::cell::{Cell, RefCell};
+use windows::core::PCWSTR;
 use windows::Win32::Foundation::{HWND, POINT};
-use windows::Win32::System::LibraryLoader::GetModuleHandleW;
-use windows::Win32::System::Registry::{
-    RegCloseKey, RegOpenKeyExW, RegQueryValueExW, HKEY, HKEY_CURRENT_USER, KEY_READ, REG_VALUE_TYPE,
+use windows::Win32::Graphics::Gdi::{
+    CreateDIBSection, DeleteObject, GetDC, GetDIBits, GetObjectW, ReleaseDC, BITMAP, BITMAPINFO,
+    BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS,
 };
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
 use windows::Win32::UI::Shell::{
-    Shell_NotifyIconW, NIF_ICON, NIF_MESSAGE, NIF_TIP, NIM_ADD, NIM_DELETE, NIM_MODIFY,
-    NOTIFYICONDATAW,
+    Shell_NotifyIconW, NIF_ICON, NIF_INFO, NIF_MESSAGE, NIF_TIP, NIIF_INFO, NIIF_NOSOUND,
+    NIIF_RESPECT_QUIET_TIME, NIM_ADD, NIM_DELETE, NIM_MODIFY, NOTIFYICONDATAW,
+    NOTIFY_ICON_INFOTIP_FLAGS,
 };
 use windows::Win32::UI::WindowsAndMessaging::{
-    AppendMenuW, CreatePopupMenu, DestroyMenu, GetCursorPos, LoadImageW, SetForegroundWindow,
-    TrackPopupMenu, HMENU, IMAGE_ICON, LR_DEFAULTSIZE, LR_SHARED, MF_STRING, TPM_BOTTOMALIGN,
+    AppendMenuW, CreateIconIndirect, CreatePopupMenu, DestroyIcon, DestroyMenu, GetCursorPos,
+    GetIconInfo, LoadImageW, SetForegroundWindow, TrackPopupMenu, HICON, HMENU, ICONINFO,
+    IMAGE_ICON, LR_DEFAULTSIZE, LR_SHARED, MF_CHECKED, MF_POPUP, MF_STRING, TPM_BOTTOMALIGN,
     TPM_LEFTALIGN, TPM_RIGHTBUTTON, WM_USER,
 };
 
+use crate::i18n::{tr, tr_args};
+use crate::model::constants::{
+    PREF_HOTKEY_PROFILE, PREF_TOGGLE_NOTIFICATION, PREF_UPDATE_CHECK, QUICK_RADII,
+};
+use crate::model::{
+    current_look_profile, hotkey_for, is_current_radius, look_profile_label, profile_label,
+    radius_label, resolve_profile, tray_icon_pixels, HotkeyAction, TrayIconState, HOTKEY_PROFILES,
+    HOTKEY_PROFILE_STANDARD, LOOK_PROFILES,
+};
+use crate::platform::windows::app::state::STATE;
+use crate::platform::windows::app::update::available_update;
+use crate::platform::windows::ffi::is_light_theme;
+use crate::platform::windows::input::current_hotkey_bindings;
+use crate::platform::windows::storage::config;
+
 // Custom message for tray icon events
 pub const WM_TRAYICON: u32 = WM_USER + 1;
 
@@ -29,6 +49,20 @@ pub const MENU_SETTINGS: u32 = 1002;
 pub const MENU_HELP: u32 = 1003;
 pub const MENU_ABOUT: u32 = 1004;
 pub const MENU_QUIT: u32 = 1005;
+pub const MENU_LOCK: u32 = 1006;
+pub const MENU_RECORD_CLICKS: u32 = 1007;
+pub const MENU_EXPORT_CSV: u32 = 1008;
+pub const MENU_EXPORT_JSON: u32 = 1009;
+pub const MENU_UPDATE: u32 = 1010;
+pub const MENU_CHECK_UPDATES: u32 = 1011;
+pub const MENU_AUTO_UPDATE: u32 = 1012;
+pub const MENU_RESTART: u32 = 1013;
+/// First hotkey profile item; profile `i` of `HOTKEY_PROFILES` is `MENU_HOTKEY_PROFILE + i`.
+pub const MENU_HOTKEY_PROFILE: u32 = 1100;
+/// First radius item; radius `i` of `QUICK_RADII` is `MENU_RADIUS + i`.
+pub const MENU_RADIUS: u32 = 1200;
+/// First look item; look `i` of `LOOK_PROFILES` is `MENU_LOOK_PROFILE + i`.
+pub const MENU_LOOK_PROFILE: u32 = 1300;
 
 // Tray icon ID
 const TRAY_ICON_ID: u32 = 1;
@@ -37,58 +71,16 @@ const TRAY_ICON_ID: u32 = 1;
 const ICON_TRAY_DARK: u16 = 2; // Dark icon for light theme
 const ICON_TRAY_LIGHT: u16 = 3; // Light icon for dark theme
 
-/// Detect if Windows is using light theme.
-/// Returns true for light theme, false for dark theme.
-fn is_light_theme() -> bool {
-    unsafe {
-        let subkey: Vec<u16> = "Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize"
-            .encode_utf16()
-            .chain(std::iter::once(0))
-            .collect();
-        let value_name: Vec<u16> = "AppsUseLightTheme"
-            .encode_utf16()
-            .chain(std::iter::once(0))
-            .collect();
-
-        let mut hkey = HKEY::default();
-        let result = RegOpenKeyExW(
-            HKEY_CURRENT_USER,
-            PCWSTR(subkey.as_ptr()),
-            Some(0),
-            KEY_READ,
-            &mut hkey,
-        );
-
-        if result.is_err() {
-            return true; // Default to light theme if can't read registry
-        }
-
-        let mut data: u32 = 1;
-        let mut data_size: u32 = std::mem::size_of::<u32>() as u32;
-        let mut data_type = REG_VALUE_TYPE::default();
-
-        let query_result = RegQueryValueExW(
-            hkey,
-            PCWSTR(value_name.as_ptr()),
-            None,
-            Some(&mut data_type),
-            Some(&mut data as *mut u32 as *mut u8),
-            Some(&mut data_size),
-        );
-
-        let _ = RegCloseKey(hkey);
-
-        if query_result.is_err() {
-            return true; // Default to light theme
-        }
-
-        data == 1 // 1 = light theme, 0 = dark theme
-    }
-}
-
 thread_local! {
     static TRAY_HWND: RefCell<Option<HWND>> = const { RefCell::new(None) };
     static TRAY_MENU: RefCell<Option<HMENU>> = const { RefCell::new(None) };
+    /// Icon of the system theme, loaded from the resources.
+    static TRAY_ICON: RefCell<HICON> = const { RefCell::new(HICON(std::ptr::null_mut())) };
+    /// Icon made from `TRAY_ICON` for each state, by `TrayIconState::index`.
+    static TRAY_STATE_ICONS: RefCell<[Option<HICON>; TrayIconState::COUNT]> =
+        const { RefCell::new([None; TrayIconState::COUNT]) };
+    /// The last notification was a toggle notification, not an update.
+    static TOGGLE_NOTIFIED: Cell<bool> = const { Cell::new(false) };
 }
 
 /// Install the system tray icon with context menu.
@@ -115,9 +107,11 @@ pub fn install_tray_icon(hwnd: HWND) {
             LR_DEFAULTSIZE | LR_SHARED,
         );
         let hicon = match icon {
-            Ok(handle) => windows::Win32::UI::WindowsAndMessaging::HICON(handle.0),
-            Err(_) => windows::Win32::UI::WindowsAndMessaging::HICON::default(),
+            Ok(handle) => HICON(handle.0),
+            Err(_) => HICON::default(),
         };
+        TRAY_ICON.with(|i| *i.borrow_mut() = hicon);
+        let state = current_icon_state();
 
         // Create the notification icon
         let mut nid = NOTIFYICONDATAW {
@@ -126,49 +120,125 @@ pub fn install_tray_icon(hwnd: HWND) {
             uID: TRAY_ICON_ID,
             uFlags: NIF_ICON | NIF_MESSAGE | NIF_TIP,
             uCallbackMessage: WM_TRAYICON,
-            hIcon: hicon,
+            hIcon: state_icon(state),
             ..Default::default()
         };
 
-        // Set tooltip
-        let tip = "Lumbus - Mouse Highlighter";
-        let tip_wide: Vec<u16> = tip.encode_utf16().collect();
-        for (i, &c) in tip_wide.iter().enumerate().take(127) {
-            nid.szTip[i] = c;
-        }
+        set_tip(&mut nid, state);
 
         let _ = Shell_NotifyIconW(NIM_ADD, &nid);
+    }
 
-        // Create context menu
-        let menu = CreatePopupMenu().unwrap_or_default();
-        let _ = AppendMenuW(
-            menu,
-            MF_STRING,
-            MENU_TOGGLE as usize,
-            w!("Toggle (Ctrl+Shift+A)"),
-        );
-        let _ = AppendMenuW(
-            menu,
-            MF_STRING,
-            MENU_SETTINGS as usize,
-            w!("Settings (Ctrl+Shift+S)"),
-        );
-        let _ = AppendMenuW(
-            menu,
-            MF_STRING,
-            MENU_HELP as usize,
-            w!("Help (Ctrl+Shift+H)"),
-        );
-        let _ = AppendMenuW(menu, MF_STRING, MENU_ABOUT as usize, w!("About..."));
-        let _ = AppendMenuW(
-            menu,
-            MF_STRING,
-            MENU_QUIT as usize,
-            w!("Quit (Ctrl+Shift+Q)"),
+    // Context menu in the selected language, with the profile's hotkeys
+    update_tray_language();
+}
+
+/// State of the highlight and the presentation lock, as the icon shows it.
+fn current_icon_state() -> TrayIconState {
+    STATE.with(|s| {
+        let state = s.borrow();
+        TrayIconState {
+            visible: state.overlay.overlay_enabled,
+            locked: state.lock.is_locked(),
+        }
+    })
+}
+
+/// Put the tooltip for `state` in `nid`, in the selected language.
+fn set_tip(nid: &mut NOTIFYICONDATAW, state: TrayIconState) {
+    let mut tip = format!("Lumbus - {}", tr(state.label()));
+    if state.locked {
+        tip = format!("{tip} ({})", tr("Settings locked"));
+    }
+    for (i, c) in tip.encode_utf16().take(127).enumerate() {
+        nid.szTip[i] = c;
+    }
+}
+
+/// Icon for `state`, made from the theme's icon the first time it is
+/// needed. The theme's icon itself if it cannot be made.
+unsafe fn state_icon(state: TrayIconState) -> HICON {
+    if let Some(icon) = TRAY_STATE_ICONS.with(|i| i.borrow()[state.index()]) {
+        return icon;
+    }
+    let base = TRAY_ICON.with(|i| *i.borrow());
+    let icon = icon_variant(base, state).unwrap_or(base);
+    TRAY_STATE_ICONS.with(|i| i.borrow_mut()[state.index()] = Some(icon));
+    icon
+}
+
+/// Copy of `base` with its pixels changed for `state`.
+unsafe fn icon_variant(base: HICON, state: TrayIconState) -> Option<HICON> {
+    let mut info = ICONINFO::default();
+    GetIconInfo(base, &mut info).ok()?;
+    let variant = if info.hbmColor.is_invalid() {
+        None
+    } else {
+        let mut bitmap = BITMAP::default();
+        GetObjectW(
+            info.hbmColor.into(),
+            std::mem::size_of::<BITMAP>() as i32,
+            Some(&mut bitmap as *mut BITMAP as *mut _),
         );
+        recolor_icon(&info, bitmap.bmWidth.min(bitmap.bmHeight), state)
+    };
+    let _ = DeleteObject(info.hbmMask.into());
+    if !info.hbmColor.is_invalid() {
+        let _ = DeleteObject(info.hbmColor.into());
+    }
+    variant
+}
 
-        TRAY_MENU.with(|m| *m.borrow_mut() = Some(menu));
+/// New icon from the color bitmap of `info` (`size` pixels square) with
+/// the look of `state`, and the same mask.
+unsafe fn recolor_icon(info: &ICONINFO, size: i32, state: TrayIconState) -> Option<HICON> {
+    if size <= 0 {
+        return None;
     }
+    let mut bmi = BITMAPINFO {
+        bmiHeader: BITMAPINFOHEADER {
+            biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+            biWidth: size,
+            biHeight: -size,
+            biPlanes: 1,
+            biBitCount: 32,
+            biCompression: BI_RGB.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let screen_dc = GetDC(None);
+    let mut pixels = vec![0u8; size as usize * size as usize * 4];
+    let rows = GetDIBits(
+        screen_dc,
+        info.hbmColor,
+        0,
+        size as u32,
+        Some(pixels.as_mut_ptr() as *mut _),
+        &mut bmi,
+        DIB_RGB_COLORS,
+    );
+    let mut bits: *mut std::ffi::c_void = std::ptr::null_mut();
+    let color = if rows == size {
+        tray_icon_pixels(&mut pixels, size as usize, state);
+        CreateDIBSection(Some(screen_dc), &bmi, DIB_RGB_COLORS, &mut bits, None, 0).ok()
+    } else {
+        None
+    };
+    ReleaseDC(None, screen_dc);
+    let color = color?;
+    if !bits.is_null() {
+        std::ptr::copy_nonoverlapping(pixels.as_ptr(), bits as *mut u8, pixels.len());
+    }
+    let icon = CreateIconIndirect(&ICONINFO {
+        fIcon: true.into(),
+        hbmMask: info.hbmMask,
+        hbmColor: color,
+        ..Default::default()
+    })
+    .ok();
+    let _ = DeleteObject(color.into());
+    icon
 }
 
 /// Remove the tray icon.
@@ -194,6 +264,18 @@ pub fn remove_tray_icon() {
             }
         }
     });
+
+    // The theme's icon is shared and stays loaded
+    let base = TRAY_ICON.with(|i| *i.borrow());
+    TRAY_STATE_ICONS.with(|i| {
+        for icon in i.borrow_mut().iter_mut().filter_map(Option::take) {
+            if icon != base {
+                unsafe {
+                    let _ = DestroyIcon(icon);
+                }
+            }
+        }
+    });
 }
 
 /// Show the context menu at the cursor position.
@@ -221,8 +303,12 @@ pub fn show_tray_menu(hwnd: HWND) {
     });
 }
 
-/// Update tray tooltip to show current state.
-pub fn update_tray_tooltip(visible: bool) {
+/// Show the current state in the tray icon and its tooltip.
+///
+/// Called whenever the highlight is toggled (`AppEvent::ToggleOverlay`)
+/// and with the tray menu, which also follows the presentation lock.
+pub fn update_tray_state() {
+    let state = current_icon_state();
     TRAY_HWND.with(|h| {
         if let Some(hwnd) = *h.borrow() {
             unsafe {
@@ -230,18 +316,61 @@ pub fn update_tray_tooltip(visible: bool) {
                     cbSize: std::mem::size_of::<NOTIFYICONDATAW>() as u32,
                     hWnd: hwnd,
                     uID: TRAY_ICON_ID,
-                    uFlags: NIF_TIP,
+                    uFlags: NIF_ICON | NIF_TIP,
+                    hIcon: state_icon(state),
                     ..Default::default()
                 };
 
-                let tip = if visible {
-                    "Lumbus - Visible"
-                } else {
-                    "Lumbus - Hidden"
+                set_tip(&mut nid, state);
+
+                let _ = Shell_NotifyIconW(NIM_MODIFY, &nid);
+            }
+        }
+    });
+}
+
+/// Show a notification next to the tray icon.
+///
+/// Clicking it sends `NIN_BALLOONUSERCLICK` through `WM_TRAYICON`.
+pub fn show_tray_notification(title: &str, text: &str) {
+    TOGGLE_NOTIFIED.with(|n| n.set(false));
+    notify(title, text, NIIF_INFO);
+}
+
+/// Tell the user a hotkey toggled the highlight, if the toggle
+/// notification is on: a silent notification, left out in quiet hours.
+pub fn notify_toggle() {
+    if config::prefs_get_int(PREF_TOGGLE_NOTIFICATION, 0) == 0 {
+        return;
+    }
+    TOGGLE_NOTIFIED.with(|n| n.set(true));
+    let text = tr(current_icon_state().label());
+    notify("Lumbus", &text, NIIF_NOSOUND | NIIF_RESPECT_QUIET_TIME);
+}
+
+/// Was the last notification shown a toggle notification? Clicking one
+/// does nothing.
+pub fn toggle_notified() -> bool {
+    TOGGLE_NOTIFIED.with(|n| n.get())
+}
+
+fn notify(title: &str, text: &str, flags: NOTIFY_ICON_INFOTIP_FLAGS) {
+    TRAY_HWND.with(|h| {
+        if let Some(hwnd) = *h.borrow() {
+            unsafe {
+                let mut nid = NOTIFYICONDATAW {
+                    cbSize: std::mem::size_of::<NOTIFYICONDATAW>() as u32,
+                    hWnd: hwnd,
+                    uID: TRAY_ICON_ID,
+                    uFlags: NIF_INFO,
+                    dwInfoFlags: flags,
+                    ..Default::default()
                 };
-                let tip_wide: Vec<u16> = tip.encode_utf16().collect();
-                for (i, &c) in tip_wide.iter().enumerate().take(127) {
-                    nid.szTip[i] = c;
+                for (i, c) in title.encode_utf16().take(63).enumerate() {
+                    nid.szInfoTitle[i] = c;
+                }
+                for (i, c) in text.encode_utf16().take(255).enumerate() {
+                    nid.szInfo[i] = c;
                 }
 
                 let _ = Shell_NotifyIconW(NIM_MODIFY, &nid);
@@ -251,7 +380,26 @@ pub fn update_tray_tooltip(visible: bool) {
 }
 
 /// Update tray menu language.
-pub fn update_tray_language(is_spanish: bool) {
+///
+/// Rebuilds the menu in the selected language (see `i18n::set_language`).
+/// Also called when the presentation lock or click recording is toggled,
+/// or the hotkey profile, the daily update check or the available update
+/// changes: the Settings item is left out while the lock is on, the lock,
+/// recording and update check items are checked while active, hotkeys are
+/// shown for the selected profile and a found update is listed first.
+/// The Radius and Profile submenus are left out while locked.
+/// The icon and its tooltip are updated as well.
+pub fn update_tray_language() {
+    let (locked, recording, radius, look) = STATE.with(|s| {
+        let state = s.borrow();
+        (
+            state.lock.is_locked(),
+            state.click_log.is_recording(),
+            state.overlay.radius,
+            current_look_profile(&state.overlay),
+        )
+    });
+
     // Recreate menu with new language
     TRAY_MENU.with(|m| {
         if let Some(old_menu) = m.borrow_mut().take() {
@@ -261,63 +409,146 @@ pub fn update_tray_language(is_spanish: bool) {
         }
     });
 
+    let lock_flags = if locked {
+        MF_STRING | MF_CHECKED
+    } else {
+        MF_STRING
+    };
+    let record_flags = if recording {
+        MF_STRING | MF_CHECKED
+    } else {
+        MF_STRING
+    };
+    let auto_update_flags = if config::prefs_get_int(PREF_UPDATE_CHECK, 1) != 0 {
+        MF_STRING | MF_CHECKED
+    } else {
+        MF_STRING
+    };
+    let update_title = available_update().map(|release| {
+        tr_args("update-available", &[("version", &release.version)]).unwrap_or(release.version)
+    });
+
+    let bindings = current_hotkey_bindings();
+    let hotkey = |action| hotkey_for(&bindings, action).map(|h| h.label().replace(" + ", "+"));
+
+    // (flags, id, translation key, hotkey)
+    let mut items = vec![(
+        MF_STRING,
+        MENU_TOGGLE,
+        "Toggle",
+        hotkey(HotkeyAction::Toggle),
+    )];
+    if !locked {
+        items.push((
+            MF_STRING,
+            MENU_SETTINGS,
+            "Settings",
+            hotkey(HotkeyAction::Settings),
+        ));
+    }
+    items.extend([
+        (MF_STRING, MENU_HELP, "Help", hotkey(HotkeyAction::Help)),
+        (
+            lock_flags,
+            MENU_LOCK,
+            "Lock settings",
+            hotkey(HotkeyAction::Lock),
+        ),
+        (record_flags, MENU_RECORD_CLICKS, "Record clicks", None),
+        (MF_STRING, MENU_EXPORT_CSV, "Export clicks (CSV)...", None),
+        (MF_STRING, MENU_EXPORT_JSON, "Export clicks (JSON)...", None),
+        (MF_STRING, MENU_CHECK_UPDATES, "Check for updates", None),
+        (
+            auto_update_flags,
+            MENU_AUTO_UPDATE,
+            "Check for updates automatically",
+            None,
+        ),
+        (MF_STRING, MENU_ABOUT, "About...", None),
+        (MF_STRING, MENU_RESTART, "Restart", None),
+        (MF_STRING, MENU_QUIT, "Quit", hotkey(HotkeyAction::Quit)),
+    ]);
+
+    let selected = resolve_profile(&config::prefs_get_string(
+        PREF_HOTKEY_PROFILE,
+        HOTKEY_PROFILE_STANDARD,
+    ));
+
     unsafe {
         let menu = CreatePopupMenu().unwrap_or_default();
-
-        if is_spanish {
-            let _ = AppendMenuW(
-                menu,
-                MF_STRING,
-                MENU_TOGGLE as usize,
-                w!("Alternar (Ctrl+Shift+A)"),
-            );
-            let _ = AppendMenuW(
-                menu,
-                MF_STRING,
-                MENU_SETTINGS as usize,
-                w!("Configuración (Ctrl+Shift+S)"),
-            );
+        // Newer version found by the last update check: opens its download page
+        if let Some(title) = update_title {
+            let title_wide: Vec<u16> = title.encode_utf16().chain(std::iter::once(0)).collect();
             let _ = AppendMenuW(
                 menu,
                 MF_STRING,
-                MENU_HELP as usize,
-                w!("Ayuda (Ctrl+Shift+H)"),
-            );
-            let _ = AppendMenuW(menu, MF_STRING, MENU_ABOUT as usize, w!("Acerca de..."));
-            let _ = AppendMenuW(
-                menu,
-                MF_STRING,
-                MENU_QUIT as usize,
-                w!("Salir (Ctrl+Shift+Q)"),
-            );
-        } else {
-            let _ = AppendMenuW(
-                menu,
-                MF_STRING,
-                MENU_TOGGLE as usize,
-                w!("Toggle (Ctrl+Shift+A)"),
-            );
-            let _ = AppendMenuW(
-                menu,
-                MF_STRING,
-                MENU_SETTINGS as usize,
-                w!("Settings (Ctrl+Shift+S)"),
-            );
-            let _ = AppendMenuW(
-                menu,
-                MF_STRING,
-                MENU_HELP as usize,
-                w!("Help (Ctrl+Shift+H)"),
-            );
-            let _ = AppendMenuW(menu, MF_STRING, MENU_ABOUT as usize, w!("About..."));
-            let _ = AppendMenuW(
-                menu,
-                MF_STRING,
-                MENU_QUIT as usize,
-                w!("Quit (Ctrl+Shift+Q)"),
+                MENU_UPDATE as usize,
+                PCWSTR(title_wide.as_ptr()),
             );
         }
+        for (flags, id, key, hotkey) in items {
+            let title = match hotkey {
+                Some(keys) => format!("{} ({keys})", tr(key)),
+                None => tr(key),
+            };
+            let title_wide: Vec<u16> = title.encode_utf16().chain(std::iter::once(0)).collect();
+            let _ = AppendMenuW(menu, flags, id as usize, PCWSTR(title_wide.as_ptr()));
+
+            // Radius and Profile submenus after the settings item
+            if id == MENU_SETTINGS {
+                let radii = QUICK_RADII
+                    .iter()
+                    .map(|&r| (radius_label(r), is_current_radius(radius, r)));
+                append_submenu(menu, &tr("Radius"), MENU_RADIUS, radii);
+                let looks = LOOK_PROFILES
+                    .iter()
+                    .map(|name| (tr(look_profile_label(name)), look == Some(*name)));
+                append_submenu(menu, &tr("Profile"), MENU_LOOK_PROFILE, looks);
+            }
+
+            // Hotkeys submenu after the lock item: one entry per profile
+            if id == MENU_LOCK {
+                let profiles = HOTKEY_PROFILES
+                    .iter()
+                    .map(|profile| (tr(profile_label(profile)), *profile == selected));
+                append_submenu(menu, &tr("Hotkeys"), MENU_HOTKEY_PROFILE, profiles);
+            }
+        }
 
         TRAY_MENU.with(|m| *m.borrow_mut() = Some(menu));
     }
+
+    update_tray_state();
+}
+
+/// Append a submenu called `title` to `menu`, with one (title, checked)
+/// entry per item; entry `i` gets the id `first_id + i`.
+unsafe fn append_submenu(
+    menu: HMENU,
+    title: &str,
+    first_id: u32,
+    entries: impl Iterator<Item = (String, bool)>,
+) {
+    let submenu = CreatePopupMenu().unwrap_or_default();
+    for (i, (entry, checked)) in entries.enumerate() {
+        let flags = if checked {
+            MF_STRING | MF_CHECKED
+        } else {
+            MF_STRING
+        };
+        let entry_wide: Vec<u16> = entry.encode_utf16().chain(std::iter::once(0)).collect();
+        let _ = AppendMenuW(
+            submenu,
+            flags,
+            first_id as usize + i,
+            PCWSTR(entry_wide.as_ptr()),
+        );
+    }
+    let title_wide: Vec<u16> = title.encode_utf16().chain(std::iter::once(0)).collect();
+    let _ = AppendMenuW(
+        menu,
+        MF_POPUP,
+        submenu.0 as usize,
+        PCWSTR(title_wide.as_ptr()),
+    );
 }
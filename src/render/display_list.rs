@@ -0,0 +1,246 @@
+//! Display lists: shapes to draw, in order, with their colors.
+//!
+//! Platform-free code builds a [`DisplayList`] each frame (see
+//! [`effects`](super::effects)); the native renderers only know how to
+//! stroke and fill a [`Shape`] and how to apply a [`Transform`], so an
+//! effect built from shapes is drawn the same on both platforms.
+//!
+//! Text is not part of the list: glyph outlines come from the fonts of
+//! each platform and stay cached by its renderer.
+
+use crate::model::placement::ScreenRect;
+use crate::model::screen_frame::Rgba;
+
+/// A point in the renderer's coordinates.
+pub type Point = (f64, f64);
+
+/// Outline of something to draw.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shape {
+    /// Straight segment between two points.
+    Line(Point, Point),
+    /// Whole circle.
+    Circle { center: Point, radius: f64 },
+    /// Part of a circle, from 12 o'clock, `sweep` radians clockwise as seen
+    /// on screen (whichever way y grows).
+    Arc {
+        center: Point,
+        radius: f64,
+        sweep: f64,
+    },
+    /// Axis-aligned rectangle.
+    Rect(ScreenRect),
+}
+
+/// How a shape is drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Paint {
+    /// Outline `width` wide, with round ends if `round` (butt ends else).
+    Stroke {
+        color: Rgba,
+        width: f64,
+        round: bool,
+    },
+    /// Inside of the shape.
+    Fill(Rgba),
+}
+
+/// Scaling then translation: `p * scale + offset`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub scale: f64,
+    pub offset: (f64, f64),
+}
+
+impl Transform {
+    /// Leaves every point where it is.
+    pub const IDENTITY: Self = Self {
+        scale: 1.0,
+        offset: (0.0, 0.0),
+    };
+
+    /// Moves every point by (`dx`, `dy`).
+    pub fn translate(dx: f64, dy: f64) -> Self {
+        Self {
+            scale: 1.0,
+            offset: (dx, dy),
+        }
+    }
+
+    /// Scales about `origin` by `scale`.
+    pub fn scale_about(origin: Point, scale: f64) -> Self {
+        Self {
+            scale,
+            offset: (origin.0 * (1.0 - scale), origin.1 * (1.0 - scale)),
+        }
+    }
+
+    /// Where `point` ends up.
+    pub fn apply(&self, (x, y): Point) -> Point {
+        (
+            x * self.scale + self.offset.0,
+            y * self.scale + self.offset.1,
+        )
+    }
+
+    /// This transform applied inside `outer`, as nested lists draw.
+    pub fn within(&self, outer: &Transform) -> Transform {
+        Transform {
+            scale: self.scale * outer.scale,
+            offset: outer.apply(self.offset),
+        }
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// One entry of a display list.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DisplayItem {
+    /// Draw `shape` with `paint`.
+    Draw { shape: Shape, paint: Paint },
+    /// Draw the items up to the matching [`DisplayItem::Pop`] through the
+    /// transform, inside any transform already pushed.
+    Push(Transform),
+    /// End the last pushed transform.
+    Pop,
+}
+
+/// Shapes of a frame, bottom to top.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DisplayList {
+    items: Vec<DisplayItem>,
+}
+
+impl DisplayList {
+    /// Nothing to draw.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stroke `shape` `width` wide in `color`, with butt ends.
+    pub fn stroke(&mut self, shape: Shape, color: Rgba, width: f64) {
+        self.draw(
+            shape,
+            Paint::Stroke {
+                color,
+                width,
+                round: false,
+            },
+        );
+    }
+
+    /// Stroke `shape` `width` wide in `color`, with round ends.
+    pub fn stroke_round(&mut self, shape: Shape, color: Rgba, width: f64) {
+        self.draw(
+            shape,
+            Paint::Stroke {
+                color,
+                width,
+                round: true,
+            },
+        );
+    }
+
+    /// Fill `shape` with `color`.
+    pub fn fill(&mut self, shape: Shape, color: Rgba) {
+        self.draw(shape, Paint::Fill(color));
+    }
+
+    /// Draw `shape` with `paint`; fully transparent paint is left out.
+    pub fn draw(&mut self, shape: Shape, paint: Paint) {
+        let alpha = match paint {
+            Paint::Stroke { color, .. } | Paint::Fill(color) => color.3,
+        };
+        if alpha > 0.0 {
+            self.items.push(DisplayItem::Draw { shape, paint });
+        }
+    }
+
+    /// Add what `build` draws through `transform`.
+    pub fn transformed(&mut self, transform: Transform, build: impl FnOnce(&mut Self)) {
+        self.items.push(DisplayItem::Push(transform));
+        build(self);
+        self.items.push(DisplayItem::Pop);
+    }
+
+    /// Entries in drawing order.
+    pub fn items(&self) -> &[DisplayItem] {
+        &self.items
+    }
+
+    /// Is there nothing to draw?
+    pub fn is_empty(&self) -> bool {
+        !self
+            .items
+            .iter()
+            .any(|item| matches!(item, DisplayItem::Draw { .. }))
+    }
+
+    /// Shapes in drawing order with their transforms applied, stroke
+    /// widths scaled along.
+    pub fn flatten(&self) -> Vec<(Shape, Paint)> {
+        let mut stack = vec![Transform::IDENTITY];
+        let mut shapes = Vec::new();
+        for item in &self.items {
+            let current = *stack.last().unwrap_or(&Transform::IDENTITY);
+            match item {
+                DisplayItem::Draw { shape, paint } => {
+                    shapes.push((
+                        transform_shape(shape, &current),
+                        scale_paint(paint, &current),
+                    ));
+                }
+                DisplayItem::Push(transform) => stack.push(transform.within(&current)),
+                DisplayItem::Pop => {
+                    if stack.len() > 1 {
+                        stack.pop();
+                    }
+                }
+            }
+        }
+        shapes
+    }
+}
+
+fn transform_shape(shape: &Shape, t: &Transform) -> Shape {
+    match *shape {
+        Shape::Line(from, to) => Shape::Line(t.apply(from), t.apply(to)),
+        Shape::Circle { center, radius } => Shape::Circle {
+            center: t.apply(center),
+            radius: radius * t.scale,
+        },
+        Shape::Arc {
+            center,
+            radius,
+            sweep,
+        } => Shape::Arc {
+            center: t.apply(center),
+            radius: radius * t.scale,
+            sweep,
+        },
+        Shape::Rect(rect) => {
+            let (x, y) = t.apply((rect.x, rect.y));
+            Shape::Rect(ScreenRect::new(x, y, rect.w * t.scale, rect.h * t.scale))
+        }
+    }
+}
+
+fn scale_paint(paint: &Paint, t: &Transform) -> Paint {
+    match *paint {
+        Paint::Stroke {
+            color,
+            width,
+            round,
+        } => Paint::Stroke {
+            color,
+            width: width * t.scale,
+            round,
+        },
+        fill => fill,
+    }
+}
@@ -0,0 +1,147 @@
+//! Effects drawn from shapes alone, built once for both renderers.
+//!
+//! Each function adds the shapes of one effect to a [`DisplayList`], in
+//! the renderer's coordinates; the few that depend on which way y grows
+//! take a `y_up` flag (`true` on macOS), as in [`geometry`](super::geometry).
+
+use super::display_list::{DisplayList, Point, Shape};
+use super::geometry::{
+    grid_line_center, halo_line_width, hold_ring_radius, pen_tip_arms, ruler_ticks, trail_width,
+};
+use crate::model::constants::{
+    GRID_LINE_WIDTH, PEN_TIP_WIDTH, RULER_LINE_WIDTH, RULER_TICK_LENGTH, SCREEN_FRAME_WIDTH,
+};
+use crate::model::contrast::halo_color;
+use crate::model::grid::{grid_line_color, grid_lines};
+use crate::model::pen::pen_tip_color;
+use crate::model::placement::ScreenRect;
+use crate::model::ruler::RulerLine;
+use crate::model::screen_frame::{frame_rect, Rgba};
+use crate::model::touch::TouchRing;
+use crate::model::trail::TrailSegment;
+
+/// Color of the outline that sets `stroke` apart from any background.
+fn halo(stroke: Rgba) -> Rgba {
+    let (r, g, b) = halo_color(stroke.0, stroke.1, stroke.2);
+    (r, g, b, stroke.3)
+}
+
+/// Alignment grid across `bounds`, from its top-left corner.
+pub fn grid(
+    list: &mut DisplayList,
+    bounds: &ScreenRect,
+    spacing: f64,
+    subdivisions: i32,
+    color: Rgba,
+    y_up: bool,
+) {
+    let (left, right) = (bounds.x, bounds.x + bounds.w);
+    let (bottom, top) = (bounds.y, bounds.y + bounds.h);
+    for line in grid_lines(bounds.w, spacing, subdivisions) {
+        let x = left + grid_line_center(line.offset);
+        let color = grid_line_color(color, line.major);
+        list.stroke(Shape::Line((x, bottom), (x, top)), color, GRID_LINE_WIDTH);
+    }
+    for line in grid_lines(bounds.h, spacing, subdivisions) {
+        let y = if y_up {
+            top - grid_line_center(line.offset)
+        } else {
+            bottom + grid_line_center(line.offset)
+        };
+        let color = grid_line_color(color, line.major);
+        list.stroke(Shape::Line((left, y), (right, y)), color, GRID_LINE_WIDTH);
+    }
+}
+
+/// Recording frame along the edges of `bounds`.
+pub fn screen_frame(list: &mut DisplayList, bounds: &ScreenRect, color: Rgba) {
+    let rect = frame_rect(bounds, SCREEN_FRAME_WIDTH);
+    list.stroke(Shape::Rect(rect), color, SCREEN_FRAME_WIDTH);
+}
+
+/// The ruler's `line`, white over a dark outline, with ticks across its
+/// ends. Its label is drawn by the renderer, beside the middle.
+pub fn ruler(list: &mut DisplayList, line: RulerLine) {
+    let [start, end] = ruler_ticks(line, RULER_TICK_LENGTH);
+    let layers = [
+        ((0.0, 0.0, 0.0, 0.6), RULER_LINE_WIDTH + 2.0),
+        ((1.0, 1.0, 1.0, 1.0), RULER_LINE_WIDTH),
+    ];
+    for (color, width) in layers {
+        for (from, to) in [line, start, end] {
+            list.stroke_round(Shape::Line(from, to), color, width);
+        }
+    }
+}
+
+/// Laser trail behind a dot of `dot_radius`, oldest segment first so the
+/// head paints over the tail: each faded by its alpha and tapering
+/// towards the tail. `segments` are in the renderer's coordinates.
+pub fn laser_trail(
+    list: &mut DisplayList,
+    segments: &[TrailSegment],
+    dot_radius: f64,
+    stroke: Rgba,
+) {
+    for seg in segments {
+        let color = (stroke.0, stroke.1, stroke.2, stroke.3 * seg.alpha);
+        let line = Shape::Line((seg.x0, seg.y0), (seg.x1, seg.y1));
+        list.stroke_round(line, color, trail_width(dot_radius, seg.alpha));
+    }
+}
+
+/// Hold ring around the letter of a highlight of `radius`: an arc filling
+/// clockwise from 12 o'clock, `progress` [0.0, 1.0] of the way round.
+pub fn hold_ring(
+    list: &mut DisplayList,
+    center: Point,
+    radius: f64,
+    width: f64,
+    progress: f64,
+    stroke: Rgba,
+    outline: bool,
+) {
+    if progress <= 0.0 {
+        return;
+    }
+    let radius = hold_ring_radius(radius);
+    let shape = if progress >= 1.0 {
+        Shape::Circle { center, radius }
+    } else {
+        Shape::Arc {
+            center,
+            radius,
+            sweep: progress * std::f64::consts::TAU,
+        }
+    };
+    if outline {
+        list.stroke(shape, halo(stroke), halo_line_width(width));
+    }
+    list.stroke(shape, stroke, width);
+}
+
+/// Crosshair on the tip of the pen driving the pointer, against a
+/// highlight of `stroke`.
+pub fn pen_tip(list: &mut DisplayList, center: Point, stroke: Rgba) {
+    let color = pen_tip_color(stroke);
+    for (from, to) in pen_tip_arms(center) {
+        list.stroke(Shape::Line(from, to), color, PEN_TIP_WIDTH);
+    }
+}
+
+/// A ring of `radius` around each finger on the touchscreen, placed with
+/// `to_local` and faded by its alpha.
+pub fn touch_rings(
+    list: &mut DisplayList,
+    rings: &[TouchRing],
+    radius: f64,
+    width: f64,
+    color: Rgba,
+    to_local: impl Fn(f64, f64) -> Point,
+) {
+    for ring in rings {
+        let center = to_local(ring.x, ring.y);
+        let color = (color.0, color.1, color.2, color.3 * ring.alpha);
+        list.stroke(Shape::Circle { center, radius }, color, width);
+    }
+}
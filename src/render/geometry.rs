@@ -0,0 +1,225 @@
+//! Shape math shared by the overlay renderers (pure Rust, no FFI).
+//!
+//! Positions are in the renderer's own coordinates; the few helpers that
+//! depend on which way y grows take a `y_up` flag (`true` on macOS).
+
+use crate::model::constants::{
+    CLICK_BADGE_SCALE, DEBUG_LABEL_MARGIN, GRID_LINE_WIDTH, HALO_WIDTH, PEN_TIP_SIZE,
+};
+use crate::model::placement::{clamp_to_screen, ScreenRect};
+use crate::model::ruler::RulerLine;
+use crate::model::trail::TrailSegment;
+
+pub use crate::model::contrast::halo_line_width;
+pub use crate::model::glow::glow_layers;
+pub use crate::model::trail::laser_dot_radius;
+
+/// Height of a click letter: 1.5 × the circle's diameter.
+pub fn letter_height(radius: f64) -> f64 {
+    3.0 * radius
+}
+
+/// Space between the glyphs of a multi-character click letter.
+pub fn letter_gap(size: f64) -> f64 {
+    size * 0.08
+}
+
+/// Widest a click letter may be: 2.5 × the circle's diameter, about three
+/// capitals, so custom text such as "LMB" stays close to the pointer.
+pub fn letter_max_width(radius: f64) -> f64 {
+    5.0 * radius
+}
+
+/// Scale shrinking click text `width` wide to fit `letter_max_width`.
+pub fn letter_fit_scale(width: f64, radius: f64) -> f64 {
+    if width > 0.0 {
+        (letter_max_width(radius) / width).min(1.0)
+    } else {
+        1.0
+    }
+}
+
+/// Font size of the multi-click counter.
+pub fn badge_size(radius: f64) -> f64 {
+    CLICK_BADGE_SCALE * radius
+}
+
+/// Space between the glyphs of the counter.
+pub fn badge_gap(size: f64) -> f64 {
+    size * 0.08
+}
+
+/// Bottom-left corner of the counter: right of the letter, level with its
+/// baseline.
+pub fn badge_anchor(center: (f64, f64), radius: f64, y_up: bool) -> (f64, f64) {
+    let below = if y_up { -1.5 } else { 1.5 } * radius;
+    (center.0 + radius * 1.1, center.1 + below)
+}
+
+/// Radius of the hold ring, just outside the click letter.
+pub fn hold_ring_radius(radius: f64) -> f64 {
+    2.0 * radius
+}
+
+/// Point of the circle of `radius` around `center`, `angle` radians
+/// clockwise from 12 o'clock.
+pub fn arc_point(center: (f64, f64), radius: f64, angle: f64, y_up: bool) -> (f64, f64) {
+    let dy = if y_up { 1.0 } else { -1.0 } * radius * angle.cos();
+    (center.0 + radius * angle.sin(), center.1 + dy)
+}
+
+/// Origin (lowest x and y) of the coordinates readout of `size`: below and
+/// right of the pointer, clear of the hold ring, switching sides where it
+/// would leave `bounds`.
+pub fn readout_origin(
+    center: (f64, f64),
+    radius: f64,
+    size: (f64, f64),
+    bounds: &ScreenRect,
+    y_up: bool,
+) -> (f64, f64) {
+    beside_pointer(center, radius, size, bounds, !y_up)
+}
+
+/// Origin of the color sample label of `size`: above and right of the
+/// pointer, opposite the coordinates readout, switching sides where it
+/// would leave `bounds`.
+pub fn sample_origin(
+    center: (f64, f64),
+    radius: f64,
+    size: (f64, f64),
+    bounds: &ScreenRect,
+    y_up: bool,
+) -> (f64, f64) {
+    beside_pointer(center, radius, size, bounds, y_up)
+}
+
+/// Origin of a label of `size` right of the pointer and clear of the hold
+/// ring, at higher y than the pointer if `high`.
+fn beside_pointer(
+    center: (f64, f64),
+    radius: f64,
+    size: (f64, f64),
+    bounds: &ScreenRect,
+    high: bool,
+) -> (f64, f64) {
+    let offset = hold_ring_radius(radius) + DEBUG_LABEL_MARGIN;
+    let mut x = center.0 + offset;
+    if x + size.0 > bounds.x + bounds.w {
+        x = center.0 - offset - size.0;
+    }
+    let (higher, lower) = (center.1 + offset, center.1 - offset - size.1);
+    let fits_higher = higher + size.1 <= bounds.y + bounds.h;
+    let fits_lower = lower >= bounds.y;
+    let y = if (high && fits_higher) || (!high && !fits_lower) {
+        higher
+    } else {
+        lower
+    };
+    clamp_to_screen((x, y), size, bounds)
+}
+
+/// Unit vector across `line`, pointing to higher y (straight up or down
+/// for a line of no length).
+fn across(((x0, y0), (x1, y1)): RulerLine) -> (f64, f64) {
+    let (dx, dy) = (x1 - x0, y1 - y0);
+    let len = dx.hypot(dy);
+    if len == 0.0 {
+        return (0.0, 1.0);
+    }
+    let (nx, ny) = (-dy / len, dx / len);
+    if ny < 0.0 || (ny == 0.0 && nx < 0.0) {
+        (-nx, -ny)
+    } else {
+        (nx, ny)
+    }
+}
+
+/// Ticks of `length` across both ends of the ruler's `line`.
+pub fn ruler_ticks(line: RulerLine, length: f64) -> [RulerLine; 2] {
+    let (nx, ny) = across(line);
+    let (hx, hy) = (nx * length / 2.0, ny * length / 2.0);
+    let tick = |(x, y): (f64, f64)| ((x - hx, y - hy), (x + hx, y + hy));
+    [tick(line.0), tick(line.1)]
+}
+
+/// Origin of the ruler's label of `size`: beside the middle of `line`,
+/// on its higher-y side, kept inside `bounds`.
+pub fn ruler_label_origin(line: RulerLine, size: (f64, f64), bounds: &ScreenRect) -> (f64, f64) {
+    let (nx, ny) = across(line);
+    // Far enough along the normal that the label's nearest corner clears the line
+    let offset = DEBUG_LABEL_MARGIN + (nx.abs() * size.0 + ny.abs() * size.1) / 2.0;
+    let mid = ((line.0 .0 + line.1 .0) / 2.0, (line.0 .1 + line.1 .1) / 2.0);
+    let origin = (
+        mid.0 + nx * offset - size.0 / 2.0,
+        mid.1 + ny * offset - size.1 / 2.0,
+    );
+    clamp_to_screen(origin, size, bounds)
+}
+
+/// Distance from the display's edge to the middle of the grid line at
+/// `offset`, on whole pixels so the thin lines stay sharp and inside.
+pub fn grid_line_center(offset: f64) -> f64 {
+    offset.floor() + GRID_LINE_WIDTH / 2.0
+}
+
+/// Horizontal and vertical arms of the crosshair marking the pen tip at
+/// `center`.
+pub fn pen_tip_arms(center: (f64, f64)) -> [RulerLine; 2] {
+    let (x, y) = center;
+    [
+        ((x - PEN_TIP_SIZE, y), (x + PEN_TIP_SIZE, y)),
+        ((x, y - PEN_TIP_SIZE), (x, y + PEN_TIP_SIZE)),
+    ]
+}
+
+/// Bounding box of the circle of `radius` around `center`.
+pub fn circle_rect(center: (f64, f64), radius: f64) -> ScreenRect {
+    ScreenRect::new(
+        center.0 - radius,
+        center.1 - radius,
+        radius * 2.0,
+        radius * 2.0,
+    )
+}
+
+/// Translation that centers a shape with `bounds` on `center`.
+pub fn center_offset(bounds: &ScreenRect, center: (f64, f64)) -> (f64, f64) {
+    (
+        center.0 - bounds.x - bounds.w / 2.0,
+        center.1 - bounds.y - bounds.h / 2.0,
+    )
+}
+
+/// Opacity of the fill under a stroke of `stroke_alpha`.
+pub fn fill_alpha(stroke_alpha: f64, transparency_pct: f64) -> f64 {
+    stroke_alpha * (1.0 - transparency_pct.clamp(0.0, 100.0) / 100.0)
+}
+
+/// Width of the halo outlining a filled shape (laser dot, counter).
+pub fn outline_halo_width() -> f64 {
+    2.0 * HALO_WIDTH
+}
+
+/// Radius of the laser dot's bright core.
+pub fn laser_core_radius(dot_radius: f64) -> f64 {
+    dot_radius * 0.4
+}
+
+/// Width of a trail segment, tapering towards the tail.
+pub fn trail_width(dot_radius: f64, alpha: f64) -> f64 {
+    (dot_radius * 2.0 * alpha).max(1.0)
+}
+
+/// `segment` with both ends mapped through `to` (e.g. screen to view).
+pub fn map_segment(segment: TrailSegment, to: impl Fn(f64, f64) -> (f64, f64)) -> TrailSegment {
+    let (x0, y0) = to(segment.x0, segment.y0);
+    let (x1, y1) = to(segment.x1, segment.y1);
+    TrailSegment {
+        x0,
+        y0,
+        x1,
+        y1,
+        ..segment
+    }
+}
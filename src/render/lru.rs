@@ -0,0 +1,71 @@
+//! Small least-recently-used cache (pure Rust, no FFI).
+//!
+//! The renderers keep the outlines of the click letters and badges they
+//! drew recently, so holding a button does not lay the same glyphs out
+//! again on every frame. Only a handful of entries are ever live (the two
+//! letters, a few badge counts, the sizes of a radius animation), so the
+//! entries are a plain vector searched in order.
+
+/// Cache of up to `capacity` values, dropping the least recently used one
+/// to make room.
+#[derive(Debug, Clone)]
+pub struct LruCache<K, V> {
+    capacity: usize,
+    /// Least recently used first.
+    entries: Vec<(K, V)>,
+}
+
+impl<K: PartialEq, V> LruCache<K, V> {
+    /// Empty cache holding up to `capacity` values (at least one).
+    pub const fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Value for `key`, now the most recently used.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let index = self.entries.iter().position(|(k, _)| k == key)?;
+        let entry = self.entries.remove(index);
+        self.entries.push(entry);
+        self.entries.last().map(|(_, v)| v)
+    }
+
+    /// Store `value` for `key`, replacing any value it had and dropping the
+    /// least recently used value when full.
+    pub fn insert(&mut self, key: K, value: V) {
+        if let Some(index) = self.entries.iter().position(|(k, _)| *k == key) {
+            self.entries.remove(index);
+        }
+        while self.entries.len() >= self.capacity.max(1) {
+            self.entries.remove(0);
+        }
+        self.entries.push((key, value));
+    }
+
+    /// Value for `key`, made by `make` and stored when missing. Nothing is
+    /// stored when `make` returns `None`.
+    pub fn get_or_insert_with(&mut self, key: K, make: impl FnOnce() -> Option<V>) -> Option<&V> {
+        if self.get(&key).is_none() {
+            let value = make()?;
+            self.insert(key, value);
+        }
+        self.entries.last().map(|(_, v)| v)
+    }
+
+    /// Drop every value.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Number of values stored.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Is the cache empty?
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
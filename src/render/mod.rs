@@ -0,0 +1,13 @@
+//! Platform-free rendering helpers.
+//!
+//! The native renderers (AppKit on macOS, Direct2D on Windows) draw with
+//! their own APIs but take every size and position from [`geometry`], so
+//! both platforms draw the same shapes. Effects made of shapes alone are
+//! built once in [`effects`] as a [`display_list`] that each renderer
+//! replays. [`lru`] keeps what is costly to build each frame, such as
+//! glyph outlines.
+
+pub mod display_list;
+pub mod effects;
+pub mod geometry;
+pub mod lru;
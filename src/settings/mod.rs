@@ -0,0 +1,23 @@
+//! Settings window and first-run walkthrough, shared by all platforms.
+//!
+//! The panels are written once in egui against the [`SettingsStore`] trait.
+//! Each platform implements the store over its own preferences and overlay
+//! views, and `run_settings_window` (`open_settings_window` on Windows)
+//! or [`run_onboarding_window`] hosts a panel in a native window (eframe
+//! on macOS and Windows; a future Linux backend only needs a store).
+
+pub mod onboarding;
+pub mod panel;
+pub mod store;
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+pub mod window;
+
+pub use onboarding::OnboardingPanel;
+pub use panel::SettingsPanel;
+pub use store::SettingsStore;
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+pub use window::run_onboarding_window;
+#[cfg(target_os = "macos")]
+pub use window::run_settings_window;
+#[cfg(target_os = "windows")]
+pub use window::{open_settings_window, HostedWindow};
@@ -0,0 +1,148 @@
+//! First-run walkthrough drawn with egui.
+//!
+//! Steps through `model::Onboarding`: what Lumbus does, the hotkeys of the
+//! selected profile, the Accessibility permission (where the OS asks for
+//! it and it is not granted yet) and the size and color of the highlight,
+//! edited with the same rows as the Settings panel.
+
+use egui::{Align, Grid, Layout, RichText, Ui};
+
+use super::panel::{SettingsPanel, PERMISSION_POLL};
+use super::store::SettingsStore;
+use crate::i18n::{tr, tr_args};
+use crate::model::{Onboarding, OnboardingStep, Permission};
+
+/// State of the walkthrough between frames.
+pub struct OnboardingPanel {
+    walkthrough: Onboarding,
+    appearance: SettingsPanel,
+    finished: bool,
+}
+
+impl OnboardingPanel {
+    /// Walkthrough from the first step, over the values in `store`.
+    pub fn new(store: &dyn SettingsStore) -> Self {
+        Self {
+            walkthrough: Onboarding::new(needs_accessibility(store)),
+            appearance: SettingsPanel::new(store),
+            finished: false,
+        }
+    }
+
+    /// Step on screen.
+    pub fn step(&self) -> OnboardingStep {
+        self.walkthrough.step()
+    }
+
+    /// Was the walkthrough finished or skipped?
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Go to the next step, finishing after the last one.
+    pub fn next(&mut self) {
+        if !self.walkthrough.advance() {
+            self.finished = true;
+        }
+    }
+
+    /// Go back one step.
+    pub fn back(&mut self) {
+        self.walkthrough.back();
+    }
+
+    /// End the walkthrough without the remaining steps.
+    pub fn skip(&mut self) {
+        self.finished = true;
+    }
+
+    /// Draw the current step and the buttons moving between steps.
+    pub fn show(&mut self, ui: &mut Ui, store: &mut dyn SettingsStore) {
+        let step = self.step();
+        ui.heading(tr(step.title()));
+        ui.add_space(12.0);
+        match step {
+            OnboardingStep::Welcome => {
+                ui.label(paragraph("onboarding-welcome"));
+            }
+            OnboardingStep::Hotkeys => show_hotkeys(ui, store),
+            OnboardingStep::Permission => show_permission(ui, store),
+            OnboardingStep::Appearance => {
+                ui.label(paragraph("onboarding-appearance"));
+                ui.add_space(12.0);
+                self.appearance.show_appearance(ui, store);
+            }
+        }
+
+        ui.with_layout(Layout::bottom_up(Align::Min), |ui| {
+            ui.horizontal(|ui| {
+                let (number, total) = self.walkthrough.position();
+                let position = tr_args(
+                    "onboarding-step",
+                    &[("step", &number.to_string()), ("total", &total.to_string())],
+                );
+                if let Some(position) = position {
+                    ui.weak(position);
+                }
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    let last = self.walkthrough.is_last();
+                    if ui.button(tr(if last { "Done" } else { "Next" })).clicked() {
+                        self.next();
+                    }
+                    if !self.walkthrough.is_first() && ui.button(tr("Back")).clicked() {
+                        self.back();
+                    }
+                    if !last && ui.button(tr("Skip")).clicked() {
+                        self.skip();
+                    }
+                });
+            });
+        });
+    }
+}
+
+/// Does the OS ask for Accessibility, not granted yet?
+fn needs_accessibility(store: &dyn SettingsStore) -> bool {
+    store.permissions().contains(&Permission::Accessibility)
+        && !store.permission_granted(Permission::Accessibility)
+}
+
+/// Message `id` in the selected language (a paragraph with no variables).
+fn paragraph(id: &str) -> String {
+    tr_args(id, &[]).unwrap_or_default()
+}
+
+/// The hotkeys of the selected profile, one per row.
+fn show_hotkeys(ui: &mut Ui, store: &dyn SettingsStore) {
+    ui.label(paragraph("onboarding-hotkeys"));
+    ui.add_space(12.0);
+    Grid::new("hotkeys")
+        .num_columns(2)
+        .spacing([16.0, 8.0])
+        .show(ui, |ui| {
+            for binding in store.hotkey_bindings() {
+                ui.label(RichText::new(binding.hotkey.label()).strong());
+                ui.label(tr(binding.action.description()));
+                ui.end_row();
+            }
+        });
+    ui.add_space(12.0);
+    ui.weak(paragraph("onboarding-hotkey-profiles"));
+}
+
+/// Why the permission is needed, its live status and a button asking for it.
+fn show_permission(ui: &mut Ui, store: &mut dyn SettingsStore) {
+    ui.label(paragraph("onboarding-permission"));
+    ui.add_space(12.0);
+    if store.permission_granted(Permission::Accessibility) {
+        ui.strong(tr("Permission granted"));
+    } else {
+        if ui.button(tr("Grant access...")).clicked() {
+            store.request_permission(Permission::Accessibility);
+        }
+        ui.add_space(8.0);
+        ui.weak(paragraph("onboarding-permission-later"));
+    }
+    // Granted in System Settings, outside this window
+    ui.ctx().request_repaint_after(PERMISSION_POLL);
+}
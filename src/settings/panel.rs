@@ -0,0 +1,1420 @@
+//! Settings panel drawn with egui.
+//!
+//! Every control runs an [`AppCommand`] on the [`SettingsStore`], like the
+//! other frontends, and re-reads the stored state, so values clamped by the
+//! model or locked by the administrator show what is actually in effect.
+//! Controls of locked keys are hidden, with a note saying some settings are
+//! managed. Labels are translated on every frame, so a language change
+//! relabels the whole panel at once.
+//!
+//! Changes can be undone with Cmd+Z / Ctrl+Z (and redone with
+//! Cmd+Shift+Z / Ctrl+Y) while no text field has focus, "Revert to
+//! previous" goes back to the settings the window opened with and
+//! "Restore Defaults" to the built-in (or admin-managed) defaults.
+//!
+//! Where the store allows it, a collapsed "Hotkeys" section rebinds each
+//! command: its button takes the next key combination pressed (Esc
+//! cancels), refusing one already bound to another command.
+//!
+//! Where the OS asks for permissions, a last section shows whether each is
+//! granted, checked again every [`PERMISSION_POLL`] while the window is
+//! open, with the feature that stays off until it is.
+
+use std::time::{Duration, Instant};
+
+use egui::{
+    Color32, ComboBox, Grid, Key, KeyboardShortcut, Modifiers, Slider, TextEdit, Theme, Ui,
+    WidgetInfo, WidgetType,
+};
+use tracing::warn;
+
+use super::store::SettingsStore;
+use crate::backend::{run_command, AppCommand};
+use crate::i18n::{
+    click_letter, lang_code, lang_index, language_name, languages, resolve_language, tr, tr_args,
+};
+use crate::model::app_state::click_letter_text;
+use crate::model::click_icon::CLICK_ICONS;
+use crate::model::color::{hsb_to_rgb, rgb_to_hsb};
+use crate::model::constants::*;
+use crate::model::contrast::{high_contrast_preset, HIGH_CONTRAST_KEYS};
+use crate::model::font::LETTER_WEIGHTS;
+use crate::model::hotkeys::{MOD_ALT, MOD_CMD, MOD_CTRL, MOD_SHIFT};
+use crate::model::{
+    hotkey_conflict, hotkey_for, set_override, ColorPalette, Hotkey, HotkeyAction, OverlayState,
+    Permission, Preferences, SettingsHistory, SettingsPalette, SettingsSnapshot, BUILTIN_SWATCHES,
+    MANAGED_KEYS,
+};
+use crate::{color_to_hex, parse_color};
+
+/// Preference keys edited by the stroke color controls.
+const STROKE_KEYS: [&str; 4] = [PREF_STROKE_R, PREF_STROKE_G, PREF_STROKE_B, PREF_STROKE_A];
+
+/// Preference keys edited by the grid color button.
+const GRID_COLOR_KEYS: [&str; 4] = [PREF_GRID_R, PREF_GRID_G, PREF_GRID_B, PREF_GRID_A];
+
+/// History key of the stroke color controls (picker and Hex field).
+const HISTORY_COLOR: &str = "color";
+
+/// History key of the grid color button.
+const HISTORY_GRID_COLOR: &str = "gridColor";
+
+/// History key of the recording-frame color.
+const HISTORY_FRAME_COLOR: &str = "frameColor";
+
+/// History key of the presets: high contrast, "Revert to previous" and
+/// "Restore Defaults".
+const HISTORY_PRESET: &str = "preset";
+
+const UNDO_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Z);
+const REDO_SHORTCUTS: [KeyboardShortcut; 2] = [
+    KeyboardShortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::Z),
+    KeyboardShortcut::new(Modifiers::COMMAND, Key::Y),
+];
+
+/// How often permissions granted in the OS settings are looked for.
+pub const PERMISSION_POLL: Duration = Duration::from_secs(1);
+
+/// Idle delay slider step (whole seconds).
+const IDLE_HIDE_STEP_SECS: f64 = 1.0;
+
+/// A slider row: label, preference key, range and step.
+struct SliderRow {
+    label: &'static str,
+    key: &'static str,
+    min: f64,
+    max: f64,
+    step: f64,
+}
+
+const RADIUS_ROW: SliderRow = SliderRow {
+    label: "Radius (px)",
+    key: PREF_RADIUS,
+    min: MIN_RADIUS,
+    max: MAX_RADIUS,
+    step: RADIUS_STEP,
+};
+
+const BORDER_ROW: SliderRow = SliderRow {
+    label: "Border (px)",
+    key: PREF_BORDER,
+    min: MIN_BORDER,
+    max: MAX_BORDER,
+    step: BORDER_STEP,
+};
+
+const TRANSPARENCY_ROW: SliderRow = SliderRow {
+    label: "Fill Transparency (%)",
+    key: PREF_FILL_TRANSPARENCY,
+    min: MIN_TRANSPARENCY,
+    max: MAX_TRANSPARENCY,
+    step: TRANSPARENCY_STEP,
+};
+
+const CLICK_BURST_ROW: SliderRow = SliderRow {
+    label: "Click interval (ms)",
+    key: PREF_CLICK_BURST_MS,
+    min: MIN_CLICK_BURST_MS,
+    max: MAX_CLICK_BURST_MS,
+    step: CLICK_BURST_STEP_MS,
+};
+
+const CLICK_HOLD_ROW: SliderRow = SliderRow {
+    label: "Click hold (ms)",
+    key: PREF_CLICK_HOLD_MS,
+    min: 0.0,
+    max: MAX_CLICK_HOLD_MS,
+    step: CLICK_HOLD_STEP_MS,
+};
+
+const HOLD_RING_ROW: SliderRow = SliderRow {
+    label: "Hold ring (ms)",
+    key: PREF_HOLD_RING_MS,
+    min: MIN_HOLD_RING_MS,
+    max: MAX_HOLD_RING_MS,
+    step: HOLD_RING_STEP_MS,
+};
+
+const IDLE_HIDE_ROW: SliderRow = SliderRow {
+    label: "Idle delay (s)",
+    key: PREF_IDLE_HIDE_SECS,
+    min: MIN_IDLE_HIDE_SECS,
+    max: MAX_IDLE_HIDE_SECS,
+    step: IDLE_HIDE_STEP_SECS,
+};
+
+const RAINBOW_SPEED_ROW: SliderRow = SliderRow {
+    label: "Rainbow speed (cycles/min)",
+    key: PREF_RAINBOW_SPEED,
+    min: MIN_RAINBOW_SPEED,
+    max: MAX_RAINBOW_SPEED,
+    step: RAINBOW_SPEED_STEP,
+};
+
+const TRANSITION_ROW: SliderRow = SliderRow {
+    label: "Show/hide animation (ms)",
+    key: PREF_TRANSITION_MS,
+    min: MIN_TRANSITION_MS,
+    max: MAX_TRANSITION_MS,
+    step: TRANSITION_STEP_MS,
+};
+
+const PULSE_AMPLITUDE_ROW: SliderRow = SliderRow {
+    label: "Pulse amplitude (%)",
+    key: PREF_PULSE_AMPLITUDE,
+    min: MIN_PULSE_AMPLITUDE,
+    max: MAX_PULSE_AMPLITUDE,
+    step: PULSE_AMPLITUDE_STEP,
+};
+
+const PULSE_PERIOD_ROW: SliderRow = SliderRow {
+    label: "Pulse period (ms)",
+    key: PREF_PULSE_PERIOD_MS,
+    min: MIN_PULSE_PERIOD_MS,
+    max: MAX_PULSE_PERIOD_MS,
+    step: PULSE_PERIOD_STEP_MS,
+};
+
+const GRID_SPACING_ROW: SliderRow = SliderRow {
+    label: "Grid spacing (px)",
+    key: PREF_GRID_SPACING,
+    min: MIN_GRID_SPACING,
+    max: MAX_GRID_SPACING,
+    step: GRID_SPACING_STEP,
+};
+
+const GRID_SUBDIVISIONS_ROW: SliderRow = SliderRow {
+    label: "Grid subdivisions",
+    key: PREF_GRID_SUBDIVISIONS,
+    min: MIN_GRID_SUBDIVISIONS,
+    max: MAX_GRID_SUBDIVISIONS,
+    step: 1.0,
+};
+
+/// Each permission the OS asks for, and whether it is granted.
+fn permission_status(store: &dyn SettingsStore) -> Vec<(Permission, bool)> {
+    store
+        .permissions()
+        .iter()
+        .map(|&p| (p, store.permission_granted(p)))
+        .collect()
+}
+
+/// Round `value` to a multiple of `step` within `min..=max`.
+pub fn snap(value: f64, step: f64, min: f64, max: f64) -> f64 {
+    ((value / step).round() * step).clamp(min, max)
+}
+
+/// Language selector entry for a `PREF_LANG` value.
+///
+/// Entry 0 is "System default", followed by [`languages`] in order.
+pub fn lang_option(lang: i32) -> usize {
+    if lang == LANG_SYSTEM {
+        0
+    } else {
+        lang_index(&lang_code(lang)) as usize + 1
+    }
+}
+
+/// `PREF_LANG` value for a language selector entry.
+pub fn lang_from_option(option: usize) -> i32 {
+    option as i32 + LANG_SYSTEM
+}
+
+/// Use the Settings palettes for egui's light and dark styles.
+///
+/// egui follows the system theme; this only replaces its colors.
+pub fn apply_palette(ctx: &egui::Context) {
+    for theme in [Theme::Light, Theme::Dark] {
+        let palette = SettingsPalette::for_theme(theme == Theme::Dark);
+        let rgb = |(r, g, b): (u8, u8, u8)| Color32::from_rgb(r, g, b);
+        ctx.style_mut_of(theme, |style| {
+            style.visuals.panel_fill = rgb(palette.background);
+            style.visuals.window_fill = rgb(palette.background);
+            style.visuals.override_text_color = Some(rgb(palette.text));
+            style.visuals.weak_text_color = Some(rgb(palette.value_text));
+        });
+    }
+}
+
+/// State of the Settings panel between frames.
+pub struct SettingsPanel {
+    state: OverlayState,
+    frame_color: (f64, f64, f64, f64),
+    /// Text of the Hex field, committed when it loses focus.
+    hex: String,
+    /// Text of the left and right click letter fields, committed when
+    /// they lose focus.
+    letters: [String; 2],
+    /// Font families offered for the click letters.
+    font_families: Vec<String>,
+    /// Hue (degrees), saturation and brightness of the stroke color, kept
+    /// while they still match it so a gray keeps the hue it was dragged to.
+    hsb: (f64, f64, f64),
+    /// Colors saved to the palette.
+    palette: ColorPalette,
+    close_requested: bool,
+    /// Permissions the OS asks for, and whether each is granted.
+    permissions: Vec<(Permission, bool)>,
+    permissions_checked: Instant,
+    /// Changes made since the window opened, for undo and redo.
+    history: SettingsHistory,
+    /// Settings the window opened with ("Revert to previous").
+    opened: SettingsSnapshot,
+    created: Instant,
+    /// Command whose hotkey button waits for a key combination.
+    rebinding: Option<HotkeyAction>,
+    /// Why the last combination pressed was refused.
+    rebind_error: Option<String>,
+    /// Startup registration, read when the window opened.
+    run_at_startup: Option<bool>,
+}
+
+impl SettingsPanel {
+    /// Panel showing the values in `store`.
+    pub fn new(store: &dyn SettingsStore) -> Self {
+        let mut panel = Self {
+            state: OverlayState::default(),
+            frame_color: DEFAULT_SCREEN_FRAME_COLOR,
+            hex: String::new(),
+            letters: [String::new(), String::new()],
+            font_families: store.font_families(),
+            hsb: (0.0, 0.0, 0.0),
+            palette: ColorPalette::new(),
+            close_requested: false,
+            permissions: permission_status(store),
+            permissions_checked: Instant::now(),
+            history: SettingsHistory::new(),
+            opened: SettingsSnapshot {
+                state: OverlayState::default(),
+                frame_color: DEFAULT_SCREEN_FRAME_COLOR,
+            },
+            created: Instant::now(),
+            rebinding: None,
+            rebind_error: None,
+            run_at_startup: store.run_at_startup(),
+        };
+        panel.reload(store);
+        panel.opened = panel.snapshot();
+        panel
+    }
+
+    /// Settings as last read from the store.
+    pub fn state(&self) -> &OverlayState {
+        &self.state
+    }
+
+    /// Text of the Hex field.
+    pub fn hex(&self) -> &str {
+        &self.hex
+    }
+
+    /// Command whose hotkey button waits for a key combination.
+    pub fn rebinding(&self) -> Option<HotkeyAction> {
+        self.rebinding
+    }
+
+    /// Why the last combination pressed was refused.
+    pub fn rebind_error(&self) -> Option<&str> {
+        self.rebind_error.as_deref()
+    }
+
+    /// Wait for the key combination to bind `action` to.
+    pub fn start_rebinding(&mut self, action: HotkeyAction) {
+        self.rebinding = Some(action);
+        self.rebind_error = None;
+    }
+
+    /// Bind the command waiting for a combination to `hotkey`, unless
+    /// another command already has it. Returns whether it was bound.
+    pub fn rebind(&mut self, store: &mut dyn SettingsStore, hotkey: Hotkey) -> bool {
+        let Some(action) = self.rebinding else {
+            return false;
+        };
+        if let Some(other) = hotkey_conflict(&store.hotkey_bindings(), action, hotkey) {
+            let other = tr(other.description());
+            self.rebind_error = tr_args("hotkey-taken", &[("action", &other)]);
+            return false;
+        }
+        let overrides = set_override(&store.hotkey_overrides(), action, Some(hotkey));
+        store.set_hotkey_overrides(&overrides);
+        self.rebinding = None;
+        self.rebind_error = None;
+        true
+    }
+
+    /// Give `action` back the hotkey of the profile.
+    pub fn reset_hotkey(&mut self, store: &mut dyn SettingsStore, action: HotkeyAction) {
+        let overrides = set_override(&store.hotkey_overrides(), action, None);
+        store.set_hotkey_overrides(&overrides);
+        self.rebinding = None;
+        self.rebind_error = None;
+    }
+
+    /// Is there a change to undo?
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    /// Is there an undone change to redo?
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    /// Have the settings changed since the window opened?
+    pub fn can_revert(&self) -> bool {
+        self.snapshot() != self.opened
+    }
+
+    /// Hue (degrees), saturation and brightness ([0.0, 1.0]) shown by the
+    /// color sliders.
+    pub fn hsb(&self) -> (f64, f64, f64) {
+        self.hsb
+    }
+
+    /// Colors saved to the palette.
+    pub fn palette(&self) -> &ColorPalette {
+        &self.palette
+    }
+
+    /// Has the Close button been pressed?
+    pub fn close_requested(&self) -> bool {
+        self.close_requested
+    }
+
+    /// Permissions the OS asks for, and whether each was granted when
+    /// last checked.
+    pub fn permissions(&self) -> &[(Permission, bool)] {
+        &self.permissions
+    }
+
+    /// Check the permissions again, telling the store if any changed (or
+    /// in any case, with `force`: the "Re-check" button).
+    pub fn recheck_permissions(&mut self, store: &mut dyn SettingsStore, force: bool) {
+        let permissions = permission_status(store);
+        self.permissions_checked = Instant::now();
+        if force || permissions != self.permissions {
+            self.permissions = permissions;
+            store.permissions_changed();
+        }
+    }
+
+    /// Re-read every value from the store.
+    pub fn reload(&mut self, store: &dyn SettingsStore) {
+        self.state = store.load_state();
+        self.frame_color = store.frame_color();
+        self.palette = store.color_palette();
+        let (r, g, b, a) = self.state.stroke_color();
+        self.hex = color_to_hex(r, g, b, a);
+        self.letters = [
+            self.state.click_letter_left.clone(),
+            self.state.click_letter_right.clone(),
+        ];
+        let (hue, saturation, brightness) = self.hsb;
+        let shown = hsb_to_rgb(hue, saturation, brightness);
+        let matches = [(shown.0, r), (shown.1, g), (shown.2, b)]
+            .iter()
+            .all(|(x, y)| (x - y).abs() < 0.5 / 255.0);
+        if !matches {
+            self.hsb = rgb_to_hsb(r, g, b);
+        }
+    }
+
+    /// Startup registration as shown, `None` where it is not offered.
+    pub fn run_at_startup(&self) -> Option<bool> {
+        self.run_at_startup
+    }
+
+    /// Register the app to run at startup, or remove the registration,
+    /// then show the registration as it now is.
+    pub fn set_run_at_startup(&mut self, store: &mut dyn SettingsStore, on: bool) {
+        store.set_run_at_startup(on);
+        self.run_at_startup = store.run_at_startup();
+    }
+
+    /// Turn the notification shown when a hotkey toggles the highlight
+    /// on or off.
+    pub fn set_toggle_notification(&mut self, store: &mut dyn SettingsStore, on: bool) {
+        self.run(store, AppCommand::int(PREF_TOGGLE_NOTIFICATION, on as i32));
+    }
+
+    /// Keep the overlay out of screen captures (recordings, screen
+    /// sharing), or let them show it again.
+    pub fn set_exclude_from_capture(&mut self, store: &mut dyn SettingsStore, on: bool) {
+        self.run(store, AppCommand::int(PREF_EXCLUDE_FROM_CAPTURE, on as i32));
+    }
+
+    /// Ask before quitting from the quit hotkey or the menu, or quit at
+    /// once.
+    pub fn set_confirm_quit(&mut self, store: &mut dyn SettingsStore, on: bool) {
+        self.run(store, AppCommand::int(PREF_CONFIRM_QUIT, on as i32));
+    }
+
+    /// Store a numeric preference (a slider value).
+    pub fn set_number(&mut self, store: &mut dyn SettingsStore, key: &str, value: f64) {
+        self.record(key);
+        self.run(store, AppCommand::number(key, value));
+    }
+
+    /// Store an integer (or boolean) preference.
+    pub fn set_int(&mut self, store: &mut dyn SettingsStore, key: &str, value: i32) {
+        self.record(key);
+        self.run(store, AppCommand::int(key, value));
+    }
+
+    /// Store the recording-frame color of this display.
+    pub fn set_frame_color(&mut self, store: &mut dyn SettingsStore, rgba: (f64, f64, f64, f64)) {
+        self.record(HISTORY_FRAME_COLOR);
+        self.run(store, AppCommand::SetFrameColor(rgba));
+    }
+
+    /// Store the stroke color.
+    pub fn set_stroke_color(&mut self, store: &mut dyn SettingsStore, rgba: (f64, f64, f64, f64)) {
+        self.record(HISTORY_COLOR);
+        let (r, g, b, a) = rgba;
+        self.run(
+            store,
+            AppCommand::numbers(STROKE_KEYS.into_iter().zip([r, g, b, a])),
+        );
+    }
+
+    /// Store the color of the alignment grid.
+    pub fn set_grid_color(&mut self, store: &mut dyn SettingsStore, rgba: (f64, f64, f64, f64)) {
+        self.record(HISTORY_GRID_COLOR);
+        let (r, g, b, a) = rgba;
+        self.run(
+            store,
+            AppCommand::numbers(GRID_COLOR_KEYS.into_iter().zip([r, g, b, a])),
+        );
+    }
+
+    /// Store the stroke color with this hue (degrees), saturation and
+    /// brightness ([0.0, 1.0]), keeping its alpha.
+    pub fn set_hsb(
+        &mut self,
+        store: &mut dyn SettingsStore,
+        hue: f64,
+        saturation: f64,
+        brightness: f64,
+    ) {
+        self.hsb = (hue.rem_euclid(360.0), saturation, brightness);
+        let (r, g, b) = hsb_to_rgb(hue, saturation, brightness);
+        self.set_stroke_color(store, (r, g, b, self.state.stroke_a));
+    }
+
+    /// Save the stroke color to the palette. Returns false if the palette
+    /// already has it.
+    pub fn save_color(&mut self, store: &mut dyn SettingsStore) -> bool {
+        if !self.palette.add(self.state.stroke_color()) {
+            return false;
+        }
+        store.set_color_palette(&self.palette);
+        true
+    }
+
+    /// Remove the saved color at `index` from the palette.
+    pub fn remove_saved_color(&mut self, store: &mut dyn SettingsStore, index: usize) {
+        self.palette.remove(index);
+        store.set_color_palette(&self.palette);
+    }
+
+    /// Replace the text of the Hex field without committing it.
+    pub fn set_hex(&mut self, text: &str) {
+        self.hex = text.to_string();
+    }
+
+    /// Apply the Hex field (any format `parse_color` reads). Invalid text
+    /// reverts to the current color; valid text is normalized to hex.
+    /// Returns whether the color was stored.
+    pub fn commit_hex(&mut self, store: &mut dyn SettingsStore) -> bool {
+        match parse_color(&self.hex) {
+            Some(rgba) => {
+                self.set_stroke_color(store, rgba);
+                true
+            }
+            None => {
+                self.reload(store);
+                false
+            }
+        }
+    }
+
+    /// Store the custom text drawn for a left (`true`) or right click;
+    /// empty text goes back to the letter of the language.
+    pub fn set_click_letter(&mut self, store: &mut dyn SettingsStore, left: bool, text: &str) {
+        let text = click_letter_text(text);
+        if text == self.state.custom_click_letter(left) {
+            self.reload(store);
+            return;
+        }
+        let key = CLICK_LETTER_KEYS[usize::from(!left)];
+        self.record(key);
+        self.run(store, AppCommand::text(key, &text));
+    }
+
+    /// Store the font family of the click letters; empty text goes back
+    /// to the system font.
+    pub fn set_letter_font(&mut self, store: &mut dyn SettingsStore, family: &str) {
+        let family = family.trim();
+        if family == self.state.letter_font {
+            return;
+        }
+        self.record(PREF_LETTER_FONT);
+        self.run(store, AppCommand::text(PREF_LETTER_FONT, family));
+    }
+
+    /// Select a language (`PREF_LANG` value) and switch the UI to it.
+    pub fn set_language(&mut self, store: &mut dyn SettingsStore, lang: i32) {
+        self.record(PREF_LANG);
+        self.run(store, AppCommand::int(PREF_LANG, lang));
+        store.language_changed();
+    }
+
+    /// Re-read the store after a language change and relabel everything.
+    fn language_switched(&mut self, store: &mut dyn SettingsStore) {
+        self.reload(store);
+        crate::i18n::set_language(&resolve_language(
+            self.state.lang,
+            store.system_locale().as_deref(),
+        ));
+        store.settings_changed();
+        store.language_changed();
+    }
+
+    /// Sync the settings through `folder`, or stop syncing with `None`.
+    ///
+    /// Settings adopted from the folder may change any value, the
+    /// language included; they start a new history.
+    pub fn set_sync_folder(&mut self, store: &mut dyn SettingsStore, folder: Option<&str>) {
+        store.set_sync_folder(folder);
+        self.language_switched(store);
+        self.history.clear();
+        self.opened = self.snapshot();
+    }
+
+    /// Apply the high-contrast preset (locked keys keep their value).
+    pub fn apply_high_contrast(&mut self, store: &mut dyn SettingsStore) {
+        self.record(HISTORY_PRESET);
+        self.run(store, AppCommand::numbers(high_contrast_preset()));
+    }
+
+    /// Put every setting back to its default, as one change that can be
+    /// undone, and apply it everywhere.
+    pub fn restore_defaults(&mut self, store: &mut dyn SettingsStore) {
+        self.record(HISTORY_PRESET);
+        self.run(store, AppCommand::RestoreDefaults);
+        store.language_changed();
+    }
+
+    /// Undo the last change. Returns whether there was one.
+    pub fn undo(&mut self, store: &mut dyn SettingsStore) -> bool {
+        match self.history.undo(self.snapshot()) {
+            Some(previous) => {
+                self.restore(store, &previous);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Redo the last undone change. Returns whether there was one.
+    pub fn redo(&mut self, store: &mut dyn SettingsStore) -> bool {
+        match self.history.redo(self.snapshot()) {
+            Some(next) => {
+                self.restore(store, &next);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Go back to the settings the window opened with, as one change that
+    /// can itself be undone.
+    pub fn revert(&mut self, store: &mut dyn SettingsStore) {
+        if !self.can_revert() {
+            return;
+        }
+        self.record(HISTORY_PRESET);
+        let opened = self.opened.clone();
+        self.restore(store, &opened);
+    }
+
+    /// Settings shown right now.
+    fn snapshot(&self) -> SettingsSnapshot {
+        SettingsSnapshot {
+            state: self.state.clone(),
+            frame_color: self.frame_color,
+        }
+    }
+
+    /// Note that control `key` is about to change the settings.
+    fn record(&mut self, key: &str) {
+        let t = self.created.elapsed().as_secs_f64();
+        self.history.record(key, self.snapshot(), t);
+    }
+
+    /// Store the settings of `target` that differ from the current ones
+    /// and apply them (locked keys keep their value).
+    fn restore(&mut self, store: &mut dyn SettingsStore, target: &SettingsSnapshot) {
+        let mut current = Preferences::default();
+        current.set_state(&self.state);
+        let mut wanted = Preferences::default();
+        wanted.set_state(&target.state);
+        let mut changes = Vec::new();
+        // The managed keys are every setting of the overlay state
+        for key in MANAGED_KEYS {
+            if let Some(value) = wanted
+                .number(key)
+                .filter(|v| Some(*v) != current.number(key))
+            {
+                changes.push(AppCommand::number(key, value));
+            }
+        }
+        for key in SETTINGS_STRING_KEYS {
+            if let Some(text) = wanted
+                .string(key)
+                .filter(|t| Some(*t) != current.string(key))
+            {
+                changes.push(AppCommand::text(key, text));
+            }
+        }
+        if target.frame_color != self.frame_color {
+            changes.push(AppCommand::SetFrameColor(target.frame_color));
+        }
+        let lang_changed = target.state.lang != self.state.lang;
+        self.run(store, AppCommand::Batch(changes));
+        if lang_changed {
+            store.language_changed();
+        }
+    }
+
+    /// Run `command` on the store, then re-read it.
+    fn run(&mut self, store: &mut dyn SettingsStore, command: AppCommand) {
+        if let Err(e) = run_command(store, command) {
+            warn!("Ignoring setting change: {}", e);
+        }
+        self.reload(store);
+    }
+
+    /// Draw the panel and apply whatever the user changed.
+    pub fn show(&mut self, ui: &mut Ui, store: &mut dyn SettingsStore) {
+        Grid::new("settings")
+            .num_columns(2)
+            .spacing([16.0, 12.0])
+            .show(ui, |ui| self.show_rows(ui, store));
+
+        if store.can_rebind_hotkeys() {
+            ui.add_space(8.0);
+            self.hotkeys_section(ui, store);
+        }
+
+        if MANAGED_KEYS.iter().any(|k| store.is_locked(k)) {
+            ui.add_space(8.0);
+            ui.weak(tr("Some settings are managed by your administrator."));
+        }
+
+        if !self.permissions.is_empty() {
+            self.permissions_section(ui, store);
+        }
+
+        self.history_shortcuts(ui, store);
+        ui.add_space(12.0);
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Min), |ui| {
+            if ui.button(tr("Close")).clicked() {
+                self.close_requested = true;
+            }
+            if ui.button(tr("Restore Defaults")).clicked() {
+                self.restore_defaults(store);
+            }
+            let revert = egui::Button::new(tr("Revert to previous"));
+            if ui.add_enabled(self.can_revert(), revert).clicked() {
+                self.revert(store);
+            }
+            if ui
+                .add_enabled(self.can_redo(), egui::Button::new(tr("Redo")))
+                .clicked()
+            {
+                self.redo(store);
+            }
+            if ui
+                .add_enabled(self.can_undo(), egui::Button::new(tr("Undo")))
+                .clicked()
+            {
+                self.undo(store);
+            }
+        });
+    }
+
+    /// Each command with its hotkey, a button to rebind it and, once
+    /// rebound, one giving it back the hotkey of the profile.
+    fn hotkeys_section(&mut self, ui: &mut Ui, store: &mut dyn SettingsStore) {
+        if self.rebinding.is_some() {
+            self.take_hotkey(ui, store);
+        }
+        let bindings = store.hotkey_bindings();
+        let overrides = store.hotkey_overrides();
+        ui.collapsing(tr("Hotkeys"), |ui| {
+            Grid::new("hotkeys")
+                .num_columns(2)
+                .spacing([16.0, 8.0])
+                .show(ui, |ui| {
+                    for binding in &bindings {
+                        let action = binding.action;
+                        let label = ui.label(tr(action.description()));
+                        ui.horizontal(|ui| {
+                            let text = if self.rebinding == Some(action) {
+                                tr("Press keys...")
+                            } else {
+                                binding.hotkey.label()
+                            };
+                            if ui.button(text).labelled_by(label.id).clicked() {
+                                self.start_rebinding(action);
+                            }
+                            let rebound = hotkey_for(&overrides, action).is_some();
+                            if rebound && ui.button(tr("Reset")).clicked() {
+                                self.reset_hotkey(store, action);
+                            }
+                            if self.rebinding == Some(action) {
+                                if let Some(error) = &self.rebind_error {
+                                    ui.colored_label(ui.visuals().error_fg_color, error);
+                                }
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+
+    /// Bind the command waiting for a combination to the one just
+    /// pressed; Esc cancels. The keys are consumed, so they do not also
+    /// undo or close the window.
+    fn take_hotkey(&mut self, ui: &mut Ui, store: &mut dyn SettingsStore) {
+        let pressed = ui.input(|i| {
+            i.events.iter().find_map(|event| match event {
+                egui::Event::Key {
+                    key,
+                    pressed: true,
+                    modifiers,
+                    ..
+                } => Some((*key, *modifiers)),
+                _ => None,
+            })
+        });
+        let Some((key, modifiers)) = pressed else {
+            return;
+        };
+        ui.input_mut(|i| i.consume_key(modifiers, key));
+        if key == Key::Escape {
+            self.rebinding = None;
+            self.rebind_error = None;
+        } else if let Some(hotkey) = captured_hotkey(key, modifiers) {
+            self.rebind(store, hotkey);
+        }
+    }
+
+    /// Undo and redo from the keyboard, unless a text field (with its own
+    /// undo) has focus.
+    fn history_shortcuts(&mut self, ui: &mut Ui, store: &mut dyn SettingsStore) {
+        if ui.ctx().wants_keyboard_input() {
+            return;
+        }
+        // Cmd+Shift+Z first: Cmd+Z would also match it
+        let redo = ui.input_mut(|i| REDO_SHORTCUTS.iter().any(|s| i.consume_shortcut(s)));
+        if redo {
+            self.redo(store);
+        } else if ui.input_mut(|i| i.consume_shortcut(&UNDO_SHORTCUT)) {
+            self.undo(store);
+        }
+    }
+
+    /// Draw only the size and color rows (first-run walkthrough).
+    pub fn show_appearance(&mut self, ui: &mut Ui, store: &mut dyn SettingsStore) {
+        Grid::new("appearance")
+            .num_columns(2)
+            .spacing([16.0, 12.0])
+            .show(ui, |ui| {
+                self.slider_row(ui, store, &RADIUS_ROW);
+                self.slider_row(ui, store, &BORDER_ROW);
+                self.color_row(ui, store);
+            });
+    }
+
+    fn show_rows(&mut self, ui: &mut Ui, store: &mut dyn SettingsStore) {
+        // Language: "System default", then each language under its own name
+        let mut names = vec![tr("System default")];
+        names.extend(languages().iter().map(|code| language_name(code)));
+        let current = lang_option(self.state.lang);
+        if let Some(option) = combo_row(ui, store, "Language", PREF_LANG, current, &names) {
+            self.set_language(store, lang_from_option(option));
+        }
+
+        self.slider_row(ui, store, &RADIUS_ROW);
+        self.slider_row(ui, store, &BORDER_ROW);
+        self.color_row(ui, store);
+        self.hsb_rows(ui, store);
+        self.palette_row(ui, store);
+        self.check_row(ui, store, "Rainbow colors", PREF_RAINBOW);
+        self.slider_row(ui, store, &RAINBOW_SPEED_ROW);
+        self.slider_row(ui, store, &TRANSPARENCY_ROW);
+
+        let styles = [tr("Circle"), tr("Laser pointer")];
+        let current = usize::from(self.state.is_laser());
+        let style = combo_row(ui, store, "Style", PREF_HIGHLIGHT_STYLE, current, &styles);
+        if let Some(option) = style {
+            let style = if option == 1 {
+                HIGHLIGHT_STYLE_LASER
+            } else {
+                HIGHLIGHT_STYLE_CIRCLE
+            };
+            self.set_int(store, PREF_HIGHLIGHT_STYLE, style);
+        }
+
+        self.frame_row(ui, store);
+        self.check_row(ui, store, "Speed fade", PREF_SPEED_FADE);
+        self.check_row(ui, store, "Speed size", PREF_SPEED_SIZE);
+
+        self.check_row(ui, store, "Pulse", PREF_PULSE);
+        self.slider_row(ui, store, &PULSE_AMPLITUDE_ROW);
+        self.slider_row(ui, store, &PULSE_PERIOD_ROW);
+
+        self.letters_row(ui, store);
+        self.letter_font_rows(ui, store);
+
+        // "Letters", then each icon drawn instead of them
+        let mut icons = vec![tr("Letters")];
+        icons.extend(CLICK_ICONS.iter().map(|icon| tr(icon.label)));
+        let current = CLICK_ICONS
+            .iter()
+            .position(|icon| icon.id == self.state.click_icon)
+            .map_or(0, |i| i + 1);
+        if let Some(option) = combo_row(ui, store, "Click icon", PREF_CLICK_ICON, current, &icons) {
+            let icon = option
+                .checked_sub(1)
+                .map_or(CLICK_ICON_NONE, |i| CLICK_ICONS[i].id);
+            self.set_int(store, PREF_CLICK_ICON, icon);
+        }
+        self.slider_row(ui, store, &CLICK_HOLD_ROW);
+
+        self.check_row(ui, store, "Hold ring", PREF_HOLD_RING);
+        self.slider_row(ui, store, &HOLD_RING_ROW);
+
+        self.check_row(ui, store, "Click counter", PREF_CLICK_BURST);
+        self.slider_row(ui, store, &CLICK_BURST_ROW);
+
+        self.check_row(ui, store, "Idle auto-hide", PREF_IDLE_HIDE);
+        self.slider_row(ui, store, &IDLE_HIDE_ROW);
+
+        let modes = [tr("Follow system"), tr("Reduced"), tr("Full")];
+        let current = self.state.motion.clamp(MOTION_SYSTEM, MOTION_FULL) as usize;
+        if let Some(option) = combo_row(ui, store, "Motion", PREF_MOTION, current, &modes) {
+            self.set_int(store, PREF_MOTION, option as i32);
+        }
+        self.slider_row(ui, store, &TRANSITION_ROW);
+
+        self.halo_row(ui, store);
+
+        let glows = [tr("None"), tr("Soft glow"), tr("Drop shadow")];
+        let current = self.state.glow.clamp(GLOW_OFF, GLOW_SHADOW) as usize;
+        if let Some(option) = combo_row(ui, store, "Glow", PREF_GLOW, current, &glows) {
+            self.set_int(store, PREF_GLOW, option as i32);
+        }
+
+        self.grid_color_row(ui, store);
+        self.slider_row(ui, store, &GRID_SPACING_ROW);
+        self.slider_row(ui, store, &GRID_SUBDIVISIONS_ROW);
+
+        self.platform_rows(ui, store);
+        self.sync_row(ui, store);
+    }
+
+    /// Status of each permission, with a button opening the OS settings
+    /// for those missing, and "Re-check".
+    fn permissions_section(&mut self, ui: &mut Ui, store: &mut dyn SettingsStore) {
+        if self.permissions_checked.elapsed() >= PERMISSION_POLL {
+            self.recheck_permissions(store, false);
+        }
+        // Granted in the OS settings, outside this window
+        ui.ctx().request_repaint_after(PERMISSION_POLL);
+
+        ui.add_space(12.0);
+        ui.separator();
+        ui.strong(tr("Permissions"));
+        ui.add_space(4.0);
+        Grid::new("permissions")
+            .num_columns(3)
+            .spacing([16.0, 8.0])
+            .show(ui, |ui| {
+                for &(permission, granted) in &self.permissions {
+                    ui.label(tr(permission.label()));
+                    if granted {
+                        ui.label(tr("Granted"));
+                    } else {
+                        let feature = tr(permission.feature());
+                        let status = tr_args("permission-missing", &[("feature", &feature)])
+                            .unwrap_or_default();
+                        ui.colored_label(ui.visuals().warn_fg_color, status);
+                        if ui.button(tr("Open System Settings")).clicked() {
+                            store.open_permission_settings(permission);
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+        ui.add_space(4.0);
+        if ui.button(tr("Re-check")).clicked() {
+            self.recheck_permissions(store, true);
+        }
+    }
+
+    /// Current value of a numeric preference shown by a slider.
+    fn number(&self, key: &str) -> f64 {
+        let s = &self.state;
+        match key {
+            PREF_RADIUS => s.radius,
+            PREF_BORDER => s.border_width,
+            PREF_FILL_TRANSPARENCY => s.fill_transparency_pct,
+            PREF_CLICK_BURST_MS => s.click_burst_ms,
+            PREF_IDLE_HIDE_SECS => s.idle_hide_secs,
+            PREF_CLICK_HOLD_MS => s.click_hold_ms,
+            PREF_HOLD_RING_MS => s.hold_ring_ms,
+            PREF_RAINBOW_SPEED => s.rainbow_speed,
+            PREF_PULSE_AMPLITUDE => s.pulse_amplitude,
+            PREF_PULSE_PERIOD_MS => s.pulse_period_ms,
+            PREF_TRANSITION_MS => s.transition_ms,
+            PREF_GRID_SPACING => s.grid_spacing,
+            PREF_GRID_SUBDIVISIONS => s.grid_subdivisions as f64,
+            _ => 0.0,
+        }
+    }
+
+    /// Current value of a preference shown by a checkbox.
+    fn flag(&self, key: &str) -> bool {
+        let s = &self.state;
+        match key {
+            PREF_SCREEN_FRAME => s.screen_frame,
+            PREF_SPEED_FADE => s.speed_fade,
+            PREF_SPEED_SIZE => s.speed_size,
+            PREF_CLICK_BURST => s.click_burst,
+            PREF_IDLE_HIDE => s.idle_hide,
+            PREF_HOLD_RING => s.hold_ring,
+            PREF_HALO => s.halo,
+            PREF_RAINBOW => s.rainbow,
+            PREF_PULSE => s.pulse,
+            _ => false,
+        }
+    }
+
+    /// Labelled slider storing the snapped value (hidden when locked).
+    fn slider_row(&mut self, ui: &mut Ui, store: &mut dyn SettingsStore, row: &SliderRow) {
+        if store.is_locked(row.key) {
+            return;
+        }
+        let label = ui.label(tr(row.label));
+        let mut value = self.number(row.key);
+        let slider = Slider::new(&mut value, row.min..=row.max)
+            .step_by(row.step)
+            .fixed_decimals(0);
+        let response = ui.add(slider).labelled_by(label.id);
+        if response.changed() {
+            self.set_number(store, row.key, snap(value, row.step, row.min, row.max));
+        }
+        ui.end_row();
+    }
+
+    /// Labelled checkbox storing `key` (hidden when locked).
+    fn check_row(&mut self, ui: &mut Ui, store: &mut dyn SettingsStore, label: &str, key: &str) {
+        if store.is_locked(key) {
+            return;
+        }
+        let label = ui.label(tr(label));
+        self.checkbox(ui, store, key, label.id);
+        ui.end_row();
+    }
+
+    /// Checkbox storing `key`, named after the label with id `label`.
+    ///
+    /// Nothing is drawn when `key` is locked.
+    fn checkbox(&mut self, ui: &mut Ui, store: &mut dyn SettingsStore, key: &str, label: egui::Id) {
+        if store.is_locked(key) {
+            return;
+        }
+        let mut on = self.flag(key);
+        let response = ui
+            .add(egui::Checkbox::without_text(&mut on))
+            .labelled_by(label);
+        if response.changed() {
+            self.set_int(store, key, on as i32);
+        }
+    }
+
+    /// Color button and editable Hex field (hidden when any component is
+    /// locked).
+    fn color_row(&mut self, ui: &mut Ui, store: &mut dyn SettingsStore) {
+        if STROKE_KEYS.iter().any(|k| store.is_locked(k)) {
+            return;
+        }
+        let label = ui.label(tr("Color"));
+        ui.horizontal(|ui| {
+            let mut rgba = to_rgba32(self.state.stroke_color());
+            let button = ui
+                .color_edit_button_rgba_unmultiplied(&mut rgba)
+                .labelled_by(label.id);
+            if button.changed() {
+                self.set_stroke_color(store, from_rgba32(rgba));
+            }
+
+            let hex_label = ui.label(tr("Hex"));
+            let field = ui
+                .add(TextEdit::singleline(&mut self.hex).desired_width(90.0))
+                .labelled_by(hex_label.id);
+            field.widget_info(|| WidgetInfo::labeled(WidgetType::TextEdit, true, tr("Hex color")));
+            if field.lost_focus() {
+                self.commit_hex(store);
+            }
+        });
+        ui.end_row();
+    }
+
+    /// Color button of the alignment grid lines (hidden when any component
+    /// is locked).
+    fn grid_color_row(&mut self, ui: &mut Ui, store: &mut dyn SettingsStore) {
+        if GRID_COLOR_KEYS.iter().any(|k| store.is_locked(k)) {
+            return;
+        }
+        let label = ui.label(tr("Grid color"));
+        let mut rgba = to_rgba32(self.state.grid_color());
+        let button = ui
+            .color_edit_button_rgba_unmultiplied(&mut rgba)
+            .labelled_by(label.id);
+        if button.changed() {
+            self.set_grid_color(store, from_rgba32(rgba));
+        }
+        ui.end_row();
+    }
+
+    /// Hue, saturation and brightness sliders for the stroke color (hidden
+    /// when any component is locked).
+    fn hsb_rows(&mut self, ui: &mut Ui, store: &mut dyn SettingsStore) {
+        if STROKE_KEYS.iter().any(|k| store.is_locked(k)) {
+            return;
+        }
+        let (hue, saturation, brightness) = self.hsb;
+        let mut values = [hue, saturation * 100.0, brightness * 100.0];
+        let rows = [
+            ("Hue (°)", 360.0),
+            ("Saturation (%)", 100.0),
+            ("Brightness (%)", 100.0),
+        ];
+        let mut changed = false;
+        for ((label, max), value) in rows.into_iter().zip(values.iter_mut()) {
+            let label = ui.label(tr(label));
+            // Shown in whole units; only the slider moved takes the new value
+            let mut shown = value.round();
+            let slider = Slider::new(&mut shown, 0.0..=max).fixed_decimals(0);
+            if ui.add(slider).labelled_by(label.id).changed() {
+                *value = shown;
+                changed = true;
+            }
+            ui.end_row();
+        }
+        if changed {
+            let [hue, saturation, brightness] = values;
+            self.set_hsb(store, hue, saturation / 100.0, brightness / 100.0);
+        }
+    }
+
+    /// Palette swatches, applied on click, and a button saving the stroke
+    /// color; saved colors are removed from their context menu. Hidden
+    /// when any color component is locked.
+    fn palette_row(&mut self, ui: &mut Ui, store: &mut dyn SettingsStore) {
+        if STROKE_KEYS.iter().any(|k| store.is_locked(k)) {
+            return;
+        }
+        ui.label(tr("Palette"));
+        let builtin = BUILTIN_SWATCHES.len();
+        let swatches: Vec<_> = self.palette.swatches().collect();
+        ui.horizontal_wrapped(|ui| {
+            for (i, color) in swatches.into_iter().enumerate() {
+                let (r, g, b, a) = color;
+                let hex = color_to_hex(r, g, b, a);
+                let [r, g, b, a] = to_rgba32(color).map(|c| (c * 255.0).round() as u8);
+                let swatch = egui::Button::new("")
+                    .fill(Color32::from_rgba_unmultiplied(r, g, b, a))
+                    .min_size(egui::vec2(20.0, 20.0));
+                let response = ui.add(swatch).on_hover_text(&hex);
+                response.widget_info(|| WidgetInfo::labeled(WidgetType::Button, true, &hex));
+                if response.clicked() {
+                    self.set_stroke_color(store, color);
+                }
+                if i >= builtin {
+                    response.context_menu(|ui| {
+                        if ui.button(tr("Remove")).clicked() {
+                            self.remove_saved_color(store, i - builtin);
+                            ui.close();
+                        }
+                    });
+                }
+            }
+            let saved = self.palette.contains(self.state.stroke_color());
+            if ui
+                .add_enabled(!saved, egui::Button::new(tr("Save color")))
+                .clicked()
+            {
+                self.save_color(store);
+            }
+        });
+        ui.end_row();
+    }
+
+    /// Recording-frame toggle and the frame color of this display.
+    fn frame_row(&mut self, ui: &mut Ui, store: &mut dyn SettingsStore) {
+        let label = ui.label(tr("Recording frame"));
+        ui.horizontal(|ui| {
+            self.checkbox(ui, store, PREF_SCREEN_FRAME, label.id);
+
+            let mut rgba = to_rgba32(self.frame_color);
+            let button = ui.color_edit_button_rgba_unmultiplied(&mut rgba);
+            button.widget_info(|| {
+                WidgetInfo::labeled(WidgetType::ColorButton, true, tr("Frame color"))
+            });
+            if button.changed() {
+                self.set_frame_color(store, from_rgba32(rgba));
+            }
+        });
+        ui.end_row();
+    }
+
+    /// Custom text drawn on left and right clicks, with the letter of the
+    /// language as a hint while empty (fields hidden when locked).
+    fn letters_row(&mut self, ui: &mut Ui, store: &mut dyn SettingsStore) {
+        if CLICK_LETTER_KEYS.iter().all(|k| store.is_locked(k)) {
+            return;
+        }
+        ui.label(tr("Click letters"));
+        ui.horizontal(|ui| {
+            for (i, key) in CLICK_LETTER_KEYS.into_iter().enumerate() {
+                if store.is_locked(key) {
+                    continue;
+                }
+                let left = i == 0;
+                let name = if left {
+                    tr("Left click letter")
+                } else {
+                    tr("Right click letter")
+                };
+                let field = ui.add(
+                    TextEdit::singleline(&mut self.letters[i])
+                        .char_limit(MAX_CLICK_LETTER_CHARS)
+                        .hint_text(click_letter(left).to_string())
+                        .desired_width(60.0),
+                );
+                field.widget_info(|| WidgetInfo::labeled(WidgetType::TextEdit, true, &name));
+                if field.lost_focus() {
+                    let text = self.letters[i].clone();
+                    self.set_click_letter(store, left, &text);
+                }
+            }
+        });
+        ui.end_row();
+    }
+
+    /// Font family and weight of the click letters. The family list starts
+    /// with "System default" and keeps a stored family that is not
+    /// installed here (synced from another machine).
+    fn letter_font_rows(&mut self, ui: &mut Ui, store: &mut dyn SettingsStore) {
+        let mut families = vec![tr("System default")];
+        families.extend(self.font_families.iter().cloned());
+        let font = &self.state.letter_font;
+        let current = if font.is_empty() {
+            0
+        } else {
+            match self.font_families.iter().position(|f| f == font) {
+                Some(i) => i + 1,
+                None => {
+                    families.push(font.clone());
+                    families.len() - 1
+                }
+            }
+        };
+        if let Some(option) = combo_row(
+            ui,
+            store,
+            "Letter font",
+            PREF_LETTER_FONT,
+            current,
+            &families,
+        ) {
+            let family = if option == 0 {
+                String::new()
+            } else {
+                families[option].clone()
+            };
+            self.set_letter_font(store, &family);
+        }
+
+        let weights: Vec<String> = LETTER_WEIGHTS.iter().map(|(_, name)| tr(name)).collect();
+        let current = LETTER_WEIGHTS
+            .iter()
+            .position(|(w, _)| *w == self.state.letter_weight)
+            .unwrap_or_default();
+        let weight = combo_row(
+            ui,
+            store,
+            "Letter weight",
+            PREF_LETTER_WEIGHT,
+            current,
+            &weights,
+        );
+        if let Some(option) = weight {
+            self.set_int(store, PREF_LETTER_WEIGHT, LETTER_WEIGHTS[option].0);
+        }
+    }
+
+    /// Outline halo toggle and the one-click high-contrast preset.
+    ///
+    /// The preset is hidden when any setting it changes is locked.
+    fn halo_row(&mut self, ui: &mut Ui, store: &mut dyn SettingsStore) {
+        let preset = !HIGH_CONTRAST_KEYS.iter().any(|k| store.is_locked(k));
+        if store.is_locked(PREF_HALO) && !preset {
+            return;
+        }
+        let label = ui.label(tr("Outline halo"));
+        ui.horizontal(|ui| {
+            self.checkbox(ui, store, PREF_HALO, label.id);
+
+            if preset && ui.button(tr("High contrast")).clicked() {
+                self.apply_high_contrast(store);
+            }
+        });
+        ui.end_row();
+    }
+
+    /// Checkboxes of the app settings the platform offers, left out of
+    /// the undo history.
+    fn platform_rows(&mut self, ui: &mut Ui, store: &mut dyn SettingsStore) {
+        let confirm = store.confirm_quit();
+        if let Some(on) = optional_check_row(ui, "Confirm before quitting", confirm) {
+            self.set_confirm_quit(store, on);
+        }
+        let notify = store.toggle_notification();
+        if let Some(on) = optional_check_row(ui, "Notify on toggle", notify) {
+            self.set_toggle_notification(store, on);
+        }
+        let hidden = store.exclude_from_capture();
+        if let Some(on) = optional_check_row(ui, "Hide from screen capture", hidden) {
+            self.set_exclude_from_capture(store, on);
+        }
+        let startup = self.run_at_startup;
+        if let Some(on) = optional_check_row(ui, "Run at Windows startup", startup) {
+            self.set_run_at_startup(store, on);
+        }
+    }
+
+    /// Folder the settings are synced through, with buttons to choose it
+    /// and to stop syncing.
+    fn sync_row(&mut self, ui: &mut Ui, store: &mut dyn SettingsStore) {
+        ui.label(tr("Sync folder"));
+        let folder = store.sync_folder();
+        ui.horizontal(|ui| {
+            match &folder {
+                Some(path) => ui.weak(path.as_str()).on_hover_text(path.as_str()),
+                None => ui.weak(tr("Off")),
+            };
+            if ui.button(tr("Choose...")).clicked() {
+                if let Some(chosen) = store.choose_sync_folder() {
+                    self.set_sync_folder(store, Some(&chosen));
+                }
+            }
+            if folder.is_some() && ui.button(tr("Stop syncing")).clicked() {
+                self.set_sync_folder(store, None);
+            }
+        });
+        ui.end_row();
+    }
+}
+
+/// Hotkey for `key` pressed with `modifiers` in the Settings window, or
+/// `None` for a key hotkeys cannot use or a combination that would take
+/// over typing (see [`Hotkey::allowed_globally`]).
+pub fn captured_hotkey(key: Key, modifiers: Modifiers) -> Option<Hotkey> {
+    let mut mods = 0;
+    for (on, m) in [
+        (modifiers.ctrl, MOD_CTRL),
+        (modifiers.shift, MOD_SHIFT),
+        (modifiers.alt, MOD_ALT),
+        (modifiers.mac_cmd, MOD_CMD),
+    ] {
+        if on {
+            mods |= m;
+        }
+    }
+    let name = key.name();
+    let key = match key {
+        Key::Comma => crate::model::Key::Comma,
+        Key::Equals => crate::model::Key::Equal,
+        Key::Minus => crate::model::Key::Minus,
+        _ if name.len() == 1 && name.chars().all(|c| c.is_ascii_uppercase()) => {
+            crate::model::Key::Letter(name.chars().next()?)
+        }
+        _ => match name.strip_prefix('F')?.parse() {
+            Ok(n @ 1..=20) => crate::model::Key::F(n),
+            _ => return None,
+        },
+    };
+    let hotkey = Hotkey {
+        key,
+        modifiers: mods,
+    };
+    hotkey.allowed_globally().then_some(hotkey)
+}
+
+/// Labelled checkbox of a setting the platform may not offer (`value` is
+/// `None` then, and nothing is drawn); returns the new value when clicked.
+fn optional_check_row(ui: &mut Ui, label: &str, value: Option<bool>) -> Option<bool> {
+    let mut on = value?;
+    let label = ui.label(tr(label));
+    let response = ui
+        .add(egui::Checkbox::without_text(&mut on))
+        .labelled_by(label.id);
+    ui.end_row();
+    response.changed().then_some(on)
+}
+
+/// Labelled drop-down list; returns the newly selected entry, if any.
+///
+/// Nothing is drawn when `key` is locked.
+fn combo_row(
+    ui: &mut Ui,
+    store: &dyn SettingsStore,
+    label: &str,
+    key: &str,
+    selected: usize,
+    items: &[String],
+) -> Option<usize> {
+    if store.is_locked(key) {
+        return None;
+    }
+    let label = ui.label(tr(label));
+    let mut choice = selected;
+    let text = items.get(selected).cloned().unwrap_or_default();
+    ComboBox::from_id_salt(key)
+        .selected_text(text)
+        .show_ui(ui, |ui| {
+            for (i, item) in items.iter().enumerate() {
+                ui.selectable_value(&mut choice, i, item);
+            }
+        })
+        .response
+        .labelled_by(label.id);
+    ui.end_row();
+    (choice != selected).then_some(choice)
+}
+
+fn to_rgba32((r, g, b, a): (f64, f64, f64, f64)) -> [f32; 4] {
+    [r as f32, g as f32, b as f32, a as f32]
+}
+
+fn from_rgba32([r, g, b, a]: [f32; 4]) -> (f64, f64, f64, f64) {
+    (r as f64, g as f64, b as f64, a as f64)
+}
@@ -0,0 +1,199 @@
+//! Platform side of the Settings window.
+
+use crate::backend::CommandTarget;
+use crate::i18n::{resolve_language, set_language};
+use crate::model::constants::{DEFAULT_SCREEN_FRAME_COLOR, SETTINGS_STRING_KEYS};
+use crate::model::{Binding, ColorPalette, OverlayState, Permission, Preferences, MANAGED_KEYS};
+
+/// Preferences and overlay views behind the Settings window.
+///
+/// Each platform implements this over its own storage of `config.toml`
+/// (see `model::preferences`). Setters follow the storage rules:
+/// writes to keys locked by the administrator are ignored.
+pub trait SettingsStore {
+    /// Persisted settings, validated.
+    fn load_state(&self) -> OverlayState;
+
+    /// Store a numeric preference.
+    fn set_double(&mut self, key: &str, value: f64);
+
+    /// Store an integer (or boolean) preference.
+    fn set_int(&mut self, key: &str, value: i32);
+
+    /// Store a text preference (the custom click letters, their font).
+    fn set_string(&mut self, key: &str, value: &str);
+
+    /// Is `key` locked by the administrator?
+    fn is_locked(&self, key: &str) -> bool;
+
+    /// Write pending preference changes to disk.
+    fn flush(&mut self);
+
+    /// Recording-frame color of the display the window opened on.
+    fn frame_color(&self) -> (f64, f64, f64, f64);
+
+    /// Set the recording-frame color of the display the window opened on.
+    fn set_frame_color(&mut self, color: (f64, f64, f64, f64));
+
+    /// Put every setting shown in the window back to its default. The
+    /// default writes the built-in defaults key by key; platforms also
+    /// reset the frame color of every display and honor the admin-managed
+    /// defaults.
+    fn restore_defaults(&mut self) {
+        let defaults = Preferences::default();
+        for key in MANAGED_KEYS {
+            if let Some(value) = defaults.number(key) {
+                self.set_double(key, value);
+            }
+        }
+        for key in SETTINGS_STRING_KEYS {
+            self.set_string(key, "");
+        }
+        self.set_frame_color(DEFAULT_SCREEN_FRAME_COLOR);
+    }
+
+    /// Font families installed on the system, sorted, offered for the
+    /// click letters.
+    fn font_families(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Colors saved to the palette.
+    fn color_palette(&self) -> ColorPalette {
+        ColorPalette::new()
+    }
+
+    /// Save the colors of the palette.
+    fn set_color_palette(&mut self, _palette: &ColorPalette) {}
+
+    /// Locale tag of the OS (e.g. `es-ES`), for the "System default" language.
+    fn system_locale(&self) -> Option<String>;
+
+    /// Apply the stored settings to the overlay. Called after every change.
+    fn settings_changed(&mut self);
+
+    /// Relabel menus outside the Settings window after a language change.
+    fn language_changed(&mut self) {}
+
+    /// Folder the settings are synced through, if sync is on.
+    fn sync_folder(&self) -> Option<String> {
+        None
+    }
+
+    /// Ask the user for a folder to sync the settings through.
+    fn choose_sync_folder(&mut self) -> Option<String> {
+        None
+    }
+
+    /// Sync the settings through `folder` (see `model::sync`), or stop
+    /// syncing with `None`.
+    fn set_sync_folder(&mut self, _folder: Option<&str>) {}
+
+    /// Center of the display the window opens on, in points from the
+    /// top-left corner of the primary display. `None` centers the window
+    /// on the primary display.
+    fn window_center(&self) -> Option<(f32, f32)> {
+        None
+    }
+
+    /// Hotkeys of the selected profile with the rebinds in place, listed
+    /// by the first-run walkthrough and the Settings window.
+    fn hotkey_bindings(&self) -> Vec<Binding> {
+        Vec::new()
+    }
+
+    /// Is the notification shown when a hotkey toggles the highlight on
+    /// (`PREF_TOGGLE_NOTIFICATION`)? `None` where there is none.
+    fn toggle_notification(&self) -> Option<bool> {
+        None
+    }
+
+    /// Is the user asked before quitting (`PREF_CONFIRM_QUIT`)? `None`
+    /// where the platform has no quit confirmation.
+    fn confirm_quit(&self) -> Option<bool> {
+        None
+    }
+
+    /// Is the overlay kept out of screen captures
+    /// (`PREF_EXCLUDE_FROM_CAPTURE`)? `None` where it cannot be.
+    fn exclude_from_capture(&self) -> Option<bool> {
+        None
+    }
+
+    /// Is the app registered to run at startup? `None` where the window
+    /// does not offer it.
+    fn run_at_startup(&self) -> Option<bool> {
+        None
+    }
+
+    /// Register the app to run at startup, or remove the registration.
+    fn set_run_at_startup(&mut self, _on: bool) {}
+
+    /// Can single hotkeys be rebound from the Settings window?
+    fn can_rebind_hotkeys(&self) -> bool {
+        false
+    }
+
+    /// Hotkeys rebound on top of the profile (`PREF_HOTKEY_OVERRIDES`).
+    fn hotkey_overrides(&self) -> Vec<Binding> {
+        Vec::new()
+    }
+
+    /// Store the rebinds and register the hotkeys again.
+    fn set_hotkey_overrides(&mut self, _overrides: &[Binding]) {}
+
+    /// Permissions the OS asks for, in display order; empty where it asks
+    /// for none.
+    fn permissions(&self) -> &'static [Permission] {
+        &[]
+    }
+
+    /// Is `permission` granted right now? Checked again on every call.
+    fn permission_granted(&self, _permission: Permission) -> bool {
+        true
+    }
+
+    /// Ask the OS for `permission` (a system prompt, where there is one).
+    fn request_permission(&mut self, _permission: Permission) {}
+
+    /// Open the page of the OS settings granting `permission`.
+    fn open_permission_settings(&mut self, _permission: Permission) {}
+
+    /// The permissions were checked again: turn the features that need
+    /// them on or off to match.
+    fn permissions_changed(&mut self) {}
+}
+
+/// The Settings window changes settings through the same commands as the
+/// other frontends (see `backend::command`).
+impl CommandTarget for dyn SettingsStore + '_ {
+    fn write_number(&mut self, key: &str, value: f64) {
+        self.set_double(key, value);
+    }
+
+    fn write_int(&mut self, key: &str, value: i32) {
+        self.set_int(key, value);
+    }
+
+    fn write_text(&mut self, key: &str, text: &str) {
+        self.set_string(key, text);
+    }
+
+    fn write_frame_color(&mut self, color: (f64, f64, f64, f64)) {
+        self.set_frame_color(color);
+    }
+
+    fn write_defaults(&mut self) {
+        self.restore_defaults();
+    }
+
+    fn persist(&mut self) {
+        self.flush();
+    }
+
+    fn broadcast(&mut self) {
+        let lang = self.load_state().lang;
+        set_language(&resolve_language(lang, self.system_locale().as_deref()));
+        self.settings_changed();
+    }
+}
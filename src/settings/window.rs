@@ -0,0 +1,262 @@
+//! Native windows hosting the Settings panel and the first-run
+//! walkthrough (macOS and Windows).
+//!
+//! On macOS each window runs its own event loop until it closes, like a
+//! modal dialog; overlay timers and hooks keep running meanwhile. On
+//! Windows the Settings window is modeless: it shares one event loop with
+//! the walkthrough, which the overlay's message loop pumps while the
+//! window is open (see [`HostedWindow`]). In Settings, Esc, and Return
+//! outside the Hex field, close it; in the walkthrough, Return goes to the
+//! next step and Esc skips the rest.
+
+use eframe::egui::{self, Key, ViewportBuilder, ViewportCommand};
+use tracing::error;
+
+use super::onboarding::OnboardingPanel;
+use super::panel::{apply_palette, SettingsPanel};
+use super::store::SettingsStore;
+use crate::i18n::tr;
+
+/// Inner size of the Settings window, in points.
+const WINDOW_SIZE: [f32; 2] = [440.0, 620.0];
+
+/// Inner size of the walkthrough window, in points.
+const ONBOARDING_SIZE: [f32; 2] = [480.0, 400.0];
+
+/// Space between the panel and the window edges, in points.
+const MARGIN: f32 = 20.0;
+
+struct SettingsApp {
+    panel: SettingsPanel,
+    store: Box<dyn SettingsStore>,
+    /// Window title, in the language it was last set in.
+    title: String,
+}
+
+impl eframe::App for SettingsApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let frame = egui::Frame::central_panel(&ctx.style()).inner_margin(MARGIN);
+        egui::CentralPanel::default().frame(frame).show(ctx, |ui| {
+            // The permissions section may not fit the fixed window height
+            egui::ScrollArea::vertical().show(ui, |ui| self.panel.show(ui, self.store.as_mut()));
+        });
+
+        let title = tr("Settings");
+        if title != self.title {
+            ctx.send_viewport_cmd(ViewportCommand::Title(title.clone()));
+            self.title = title;
+        }
+
+        let (escape, enter) =
+            ctx.input(|i| (i.key_pressed(Key::Escape), i.key_pressed(Key::Enter)));
+        // Return in the Hex field commits it instead
+        let enter = enter && !ctx.wants_keyboard_input();
+        if self.panel.close_requested() || escape || enter {
+            ctx.send_viewport_cmd(ViewportCommand::Close);
+        }
+    }
+}
+
+struct OnboardingApp {
+    panel: OnboardingPanel,
+    store: Box<dyn SettingsStore>,
+    /// Window title, in the language it was last set in.
+    title: String,
+}
+
+impl eframe::App for OnboardingApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let (escape, enter) =
+            ctx.input(|i| (i.key_pressed(Key::Escape), i.key_pressed(Key::Enter)));
+        if escape {
+            self.panel.skip();
+        } else if enter && !ctx.wants_keyboard_input() {
+            self.panel.next();
+        }
+
+        let frame = egui::Frame::central_panel(&ctx.style()).inner_margin(MARGIN);
+        egui::CentralPanel::default()
+            .frame(frame)
+            .show(ctx, |ui| self.panel.show(ui, self.store.as_mut()));
+
+        let title = tr("Welcome to Lumbus");
+        if title != self.title {
+            ctx.send_viewport_cmd(ViewportCommand::Title(title.clone()));
+            self.title = title;
+        }
+
+        if self.panel.finished() {
+            ctx.send_viewport_cmd(ViewportCommand::Close);
+        }
+    }
+}
+
+/// Open the Settings window and return once it is closed.
+///
+/// Must be called from the main thread.
+#[cfg(target_os = "macos")]
+pub fn run_settings_window(store: Box<dyn SettingsStore>) {
+    let title = tr("Settings");
+    let options = native_options(&title, WINDOW_SIZE, store.as_ref());
+    let result = eframe::run_native(
+        "Lumbus Settings",
+        options,
+        Box::new(move |cc| {
+            apply_palette(&cc.egui_ctx);
+            let panel = SettingsPanel::new(store.as_ref());
+            Ok(Box::new(SettingsApp {
+                panel,
+                store,
+                title,
+            }))
+        }),
+    );
+    if let Err(e) = result {
+        error!("Failed to open settings window: {e}");
+    }
+}
+
+/// Open the Settings window and return at once; the caller pumps it from
+/// its message loop until [`HostedWindow::pump`] returns `false`.
+///
+/// Must be called from the main thread.
+#[cfg(target_os = "windows")]
+pub fn open_settings_window(store: Box<dyn SettingsStore>) -> Option<HostedWindow> {
+    let title = tr("Settings");
+    let options = native_options(&title, WINDOW_SIZE, store.as_ref());
+    HostedWindow::open(
+        "Lumbus Settings",
+        options,
+        Box::new(move |cc| {
+            apply_palette(&cc.egui_ctx);
+            let panel = SettingsPanel::new(store.as_ref());
+            Ok(Box::new(SettingsApp {
+                panel,
+                store,
+                title,
+            }))
+        }),
+    )
+}
+
+/// Open the first-run walkthrough and return once it is finished, skipped
+/// or closed.
+///
+/// Must be called from the main thread.
+pub fn run_onboarding_window(store: Box<dyn SettingsStore>) {
+    let title = tr("Welcome to Lumbus");
+    let options = native_options(&title, ONBOARDING_SIZE, store.as_ref());
+    let creator: eframe::AppCreator<'static> = Box::new(move |cc| {
+        apply_palette(&cc.egui_ctx);
+        let panel = OnboardingPanel::new(store.as_ref());
+        Ok(Box::new(OnboardingApp {
+            panel,
+            store,
+            title,
+        }))
+    });
+    #[cfg(target_os = "macos")]
+    if let Err(e) = eframe::run_native("Lumbus Welcome", options, creator) {
+        error!("Failed to open welcome window: {e}");
+    }
+    #[cfg(target_os = "windows")]
+    if let Some(mut window) = HostedWindow::open("Lumbus Welcome", options, creator) {
+        while window.pump() {}
+    }
+}
+
+#[cfg(target_os = "windows")]
+thread_local! {
+    /// winit allows one event loop per process: every window is made on
+    /// this one, created with the first of them.
+    static EVENT_LOOP: std::cell::RefCell<Option<winit::event_loop::EventLoop<eframe::UserEvent>>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// Window whose events are handled by pumping the shared event loop from
+/// the caller's message loop (Windows).
+///
+/// Pumping handles every message of the thread, so the overlay keeps
+/// tracking the cursor, and previews the settings, while it is open.
+#[cfg(target_os = "windows")]
+pub struct HostedWindow {
+    app: eframe::EframeWinitApplication<'static>,
+}
+
+#[cfg(target_os = "windows")]
+impl HostedWindow {
+    fn open(
+        app_name: &str,
+        options: eframe::NativeOptions,
+        creator: eframe::AppCreator<'static>,
+    ) -> Option<Self> {
+        EVENT_LOOP.with(|l| {
+            let mut event_loop = l.borrow_mut();
+            if event_loop.is_none() {
+                match winit::event_loop::EventLoop::with_user_event().build() {
+                    Ok(created) => *event_loop = Some(created),
+                    Err(e) => {
+                        error!("Failed to open {app_name}: {e}");
+                        return None;
+                    }
+                }
+            }
+            let app = eframe::create_native(app_name, options, creator, event_loop.as_ref()?);
+            Some(Self { app })
+        })
+    }
+
+    /// Wait for the next messages of the thread and handle them. Returns
+    /// `false` once the window is closed, or `WM_QUIT` was received.
+    ///
+    /// Must not be called again from a message it handles.
+    pub fn pump(&mut self) -> bool {
+        EVENT_LOOP.with(|l| {
+            let mut event_loop = l.borrow_mut();
+            let Some(event_loop) = event_loop.as_mut() else {
+                return false;
+            };
+            matches!(
+                self.app.pump_eframe_app(event_loop, None),
+                eframe::EframePumpStatus::Continue(_)
+            )
+        })
+    }
+}
+
+/// Options of a fixed-size, always-on-top window of `size` points, on the
+/// display chosen by `store`.
+fn native_options(title: &str, size: [f32; 2], store: &dyn SettingsStore) -> eframe::NativeOptions {
+    let mut viewport = ViewportBuilder::default()
+        .with_title(title)
+        .with_inner_size(size)
+        .with_resizable(false)
+        .with_minimize_button(false)
+        .with_maximize_button(false)
+        .with_always_on_top();
+    let centered = match store.window_center() {
+        Some((x, y)) => {
+            viewport = viewport.with_position([x - size[0] / 2.0, y - size[1] / 2.0]);
+            false
+        }
+        None => true,
+    };
+    #[allow(unused_mut)]
+    let mut options = eframe::NativeOptions {
+        viewport,
+        centered,
+        run_and_return: true,
+        ..Default::default()
+    };
+    #[cfg(target_os = "macos")]
+    {
+        // Stay a menu bar accessory: no Dock icon, no app menu
+        use winit::platform::macos::{ActivationPolicy, EventLoopBuilderExtMacOS};
+        options.event_loop_builder = Some(Box::new(|builder| {
+            builder
+                .with_activation_policy(ActivationPolicy::Accessory)
+                .with_default_menu(false);
+        }));
+    }
+    options
+}
@@ -0,0 +1,393 @@
+//! Cached preferences file with sync through a shared folder.
+//!
+//! Uses an in-memory cache to avoid disk I/O on every slider change.
+//! Call [`ConfigFile::flush`] to persist changes to disk.
+//!
+//! Settings missing from the file start from the admin-managed defaults;
+//! locked settings are applied on read and never written (see
+//! `model::managed`). Problems found in the file are logged and kept for
+//! a one-time dialog (see [`ConfigFile::take_issues`]).
+//!
+//! With sync on, every save also writes a copy to the sync folder, and
+//! copies written there by other machines are merged in (see
+//! `model::sync`).
+//!
+//! In safe mode (`--safe-mode`) the file is neither read nor written: the
+//! session starts from the defaults and its changes are dropped on exit.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use tracing::{error, warn};
+
+use crate::model::click_log::unix_time_now;
+use crate::model::{
+    merge_synced, safe_mode_preferences, synced_copy, ConfigIssue, ManagedConfig, OverlayState,
+    Preferences, SyncConflict, PREFERENCES_FILE, SYNC_CONFLICTS_LOG,
+};
+
+/// Settings older versions saved elsewhere, copied over the given
+/// defaults; `None` if there are none.
+pub type ImportFn = fn(&Preferences) -> Option<Preferences>;
+
+/// The preferences file at one path, loaded once and written on flush.
+#[derive(Debug)]
+pub struct ConfigFile {
+    path: PathBuf,
+    import: ImportFn,
+    cache: Option<Preferences>,
+    dirty: bool,
+    issues: Vec<ConfigIssue>,
+    /// Contents of the file as last read or written, to skip our own writes
+    on_disk: Option<String>,
+    /// When the cached settings last changed, in seconds since the Unix epoch
+    local_modified: f64,
+    /// Synced copy as last read or written: the base of the next merge
+    sync_base: Option<Preferences>,
+    /// Contents of the synced copy as last read or written
+    sync_on_disk: Option<String>,
+    /// Started with --safe-mode: the file is left alone
+    safe_mode: bool,
+}
+
+/// Built-in defaults overridden by the admin-managed defaults.
+pub fn managed_defaults(managed: &ManagedConfig) -> Preferences {
+    let mut base = Preferences::default();
+    for (key, val) in managed.defaults() {
+        base.set_number(key, val);
+    }
+    base
+}
+
+/// Seconds since the Unix epoch at which `path` was last written (0 if
+/// unknown).
+fn modified_time(path: &Path) -> f64 {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map_or(0.0, |d| d.as_secs_f64())
+}
+
+/// Path of the synced copy, if sync is on.
+fn sync_path(config: &Preferences) -> Option<PathBuf> {
+    (!config.sync_folder.is_empty())
+        .then(|| PathBuf::from(&config.sync_folder).join(PREFERENCES_FILE))
+}
+
+impl ConfigFile {
+    /// The file at `path`, not read yet. Without it, `import` supplies the
+    /// settings older versions saved, written on the next flush.
+    pub fn new(path: PathBuf, import: ImportFn) -> Self {
+        Self {
+            path,
+            import,
+            cache: None,
+            dirty: false,
+            issues: Vec::new(),
+            on_disk: None,
+            local_modified: 0.0,
+            sync_base: None,
+            sync_on_disk: None,
+            safe_mode: false,
+        }
+    }
+
+    /// Path of the file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Load the file, returning the defaults if not found or invalid.
+    fn load(&mut self, managed: &ManagedConfig) -> Preferences {
+        let base = managed_defaults(managed);
+        self.local_modified = modified_time(&self.path);
+        if let Ok(contents) = fs::read_to_string(&self.path) {
+            let (config, issues) = Preferences::load(&contents, base);
+            self.report_issues(issues);
+            self.on_disk = Some(contents);
+            return config;
+        }
+        match (self.import)(&base) {
+            Some(imported) => {
+                self.dirty = true;
+                imported
+            }
+            None => base,
+        }
+    }
+
+    /// The cached settings, loading the file if needed.
+    pub fn get(&mut self, managed: &ManagedConfig) -> Preferences {
+        if let Some(config) = &self.cache {
+            return config.clone();
+        }
+        let config = self.load(managed);
+        self.cache = Some(config.clone());
+        // Pick up what other machines synced while the app was not running
+        if self.merge_synced_copy(managed) {
+            return self.get(managed);
+        }
+        config
+    }
+
+    /// The cached settings with the admin-locked settings applied.
+    pub fn effective(&mut self, managed: &ManagedConfig) -> Preferences {
+        let mut config = self.get(managed);
+        for (key, val) in managed.locked() {
+            config.set_number(key, val);
+        }
+        config
+    }
+
+    /// Replace the cached settings and mark them dirty.
+    pub fn set(&mut self, config: Preferences) {
+        self.cache = Some(config);
+        self.dirty = true;
+        self.local_modified = unix_time_now();
+    }
+
+    /// Set a numeric setting (to cache, flush later).
+    ///
+    /// Ignored for settings locked by the administrator.
+    pub fn set_number(&mut self, key: &str, val: f64, managed: &ManagedConfig) {
+        if managed.is_locked(key) {
+            return;
+        }
+        let mut config = self.get(managed);
+        if config.set_number(key, val) {
+            self.set(config);
+        }
+    }
+
+    /// Set a string setting (to cache, flush later).
+    pub fn set_string(&mut self, key: &str, val: &str, managed: &ManagedConfig) {
+        let mut config = self.get(managed);
+        if config.set_string(key, val) {
+            self.set(config);
+        }
+    }
+
+    /// Store the overlay settings of `state` (to cache, flush later).
+    ///
+    /// Locked settings keep their stored value.
+    pub fn save_state(&mut self, state: &OverlayState, managed: &ManagedConfig) {
+        let previous = self.get(managed);
+        let mut config = previous.clone();
+        config.set_state(state);
+        for (key, _) in managed.locked() {
+            if let Some(val) = previous.number(key) {
+                config.set_number(key, val);
+            }
+        }
+        self.set(config);
+    }
+
+    /// Put the Settings window's settings back to their defaults (the
+    /// admin-managed ones where set) and save them at once.
+    pub fn restore_defaults(&mut self, managed: &ManagedConfig) {
+        let mut config = self.get(managed);
+        config.restore_defaults(&managed_defaults(managed));
+        self.set(config);
+        self.flush();
+    }
+
+    /// Re-read the file, and the synced copy, after either changed on
+    /// disk.
+    ///
+    /// Returns true if the settings may have changed.
+    pub fn reload(&mut self, managed: &ManagedConfig) -> bool {
+        if self.safe_mode {
+            return false;
+        }
+        let local = self.reload_local(managed);
+        let synced = self.merge_synced_copy(managed);
+        local || synced
+    }
+
+    /// Re-read the file after it changed on disk.
+    ///
+    /// Returns true if the file differs from what was last read or written;
+    /// its settings then replace the cached ones, unsaved changes included,
+    /// and its problems are reported as on startup.
+    fn reload_local(&mut self, managed: &ManagedConfig) -> bool {
+        let Ok(contents) = fs::read_to_string(&self.path) else {
+            return false;
+        };
+        if self.on_disk.as_deref() == Some(contents.as_str()) {
+            return false;
+        }
+        let (config, issues) = Preferences::load(&contents, managed_defaults(managed));
+        self.report_issues(issues);
+        self.on_disk = Some(contents);
+        self.cache = Some(config);
+        self.dirty = false;
+        self.local_modified = modified_time(&self.path);
+        true
+    }
+
+    /// Folder the settings are synced through, if sync is on.
+    pub fn sync_folder(&mut self, managed: &ManagedConfig) -> Option<PathBuf> {
+        sync_path(&self.get(managed)).and_then(|p| p.parent().map(Path::to_path_buf))
+    }
+
+    /// Sync the settings through `folder`, or stop syncing with `None`.
+    ///
+    /// Settings already synced to the folder by another machine replace this
+    /// machine's; otherwise this machine's settings are written there.
+    pub fn set_sync_folder(&mut self, folder: Option<&Path>, managed: &ManagedConfig) {
+        let mut config = self.get(managed);
+        config.sync_folder = folder
+            .map(|f| f.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.sync_base = None;
+        self.sync_on_disk = None;
+        self.set(config);
+        self.merge_synced_copy(managed);
+        self.flush();
+    }
+
+    /// Merge the synced copy into the cached settings after it changed.
+    ///
+    /// Returns true if the copy differs from what was last read or written.
+    /// The merged settings are saved at once, here and back to the sync
+    /// folder; conflicts are logged.
+    fn merge_synced_copy(&mut self, managed: &ManagedConfig) -> bool {
+        let local = self.get(managed);
+        let Some(path) = sync_path(&local) else {
+            return false;
+        };
+        let Ok(contents) = fs::read_to_string(&path) else {
+            return false;
+        };
+        if self.sync_on_disk.as_deref() == Some(contents.as_str()) {
+            return false;
+        }
+        let (remote, issues) = Preferences::load(&contents, managed_defaults(managed));
+        for issue in &issues {
+            warn!("{}: {}", path.display(), issue);
+        }
+        // Joining a sync folder adopts what is already there
+        let base = self
+            .sync_base
+            .clone()
+            .unwrap_or_else(|| synced_copy(&local));
+        let (merged, conflicts) = merge_synced(
+            &base,
+            &local,
+            self.local_modified,
+            &remote,
+            modified_time(&path),
+        );
+        self.log_sync_conflicts(&conflicts);
+        let changed = merged != local || synced_copy(&merged) != remote;
+        self.sync_base = Some(remote);
+        self.sync_on_disk = Some(contents);
+        if changed {
+            self.cache = Some(merged);
+            self.dirty = true;
+            self.flush();
+        }
+        true
+    }
+
+    /// Log sync conflicts, also appending them to the conflicts log next to
+    /// the file.
+    fn log_sync_conflicts(&self, conflicts: &[SyncConflict]) {
+        if conflicts.is_empty() {
+            return;
+        }
+        let now = unix_time_now() as u64;
+        let mut lines = String::new();
+        for conflict in conflicts {
+            warn!("Sync conflict: {}", conflict);
+            lines.push_str(&format!("{} {}\n", now, conflict));
+        }
+        let log = self.path.with_file_name(SYNC_CONFLICTS_LOG);
+        let written = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log)
+            .and_then(|mut f| f.write_all(lines.as_bytes()));
+        if let Err(e) = written {
+            error!("Failed to write {}: {}", log.display(), e);
+        }
+    }
+
+    /// Log the problems found in the file and keep them for the dialog.
+    fn report_issues(&mut self, issues: Vec<ConfigIssue>) {
+        for issue in &issues {
+            warn!("{}: {}", self.path.display(), issue);
+        }
+        self.issues = issues;
+    }
+
+    /// Problems found in the file when it was loaded.
+    ///
+    /// Returned once; later calls return nothing.
+    pub fn take_issues(&mut self, managed: &ManagedConfig) -> Vec<ConfigIssue> {
+        self.get(managed);
+        std::mem::take(&mut self.issues)
+    }
+
+    /// Start a safe-mode session: the defaults (the admin-managed ones where
+    /// set) replace the file, which is not read or written until the app
+    /// quits.
+    pub fn enter_safe_mode(&mut self, managed: &ManagedConfig) {
+        self.safe_mode = true;
+        self.cache = Some(safe_mode_preferences(managed_defaults(managed)));
+    }
+
+    /// Is this a safe-mode session?
+    pub fn safe_mode(&self) -> bool {
+        self.safe_mode
+    }
+
+    /// Write the cached settings to disk if dirty.
+    pub fn flush(&mut self) {
+        if !self.dirty || self.safe_mode {
+            return;
+        }
+        if let Some(config) = self.cache.clone() {
+            self.save(&config);
+        }
+        self.dirty = false;
+    }
+
+    /// Save `config` to the file, and its synced copy to the sync folder.
+    fn save(&mut self, config: &Preferences) {
+        if let Some(parent) = self.path.parent() {
+            if fs::create_dir_all(parent).is_err() {
+                error!("Failed to create config directory");
+                return;
+            }
+        }
+
+        let contents = config.encode();
+        match fs::write(&self.path, &contents) {
+            Ok(()) => self.on_disk = Some(contents),
+            Err(e) => error!("Failed to write config: {}", e),
+        }
+
+        let Some(path) = sync_path(config) else {
+            return;
+        };
+        // Changes synced from elsewhere are merged first, when the watcher
+        // reports them; writing now would overwrite them
+        let on_disk = fs::read_to_string(&path).ok();
+        if on_disk.is_some() && on_disk != self.sync_on_disk {
+            return;
+        }
+        let copy = synced_copy(config);
+        let contents = copy.encode();
+        match fs::write(&path, &contents) {
+            Ok(()) => {
+                self.sync_on_disk = Some(contents);
+                self.sync_base = Some(copy);
+            }
+            Err(e) => error!("Failed to write {}: {}", path.display(), e),
+        }
+    }
+}
@@ -0,0 +1,12 @@
+//! Preferences file storage, shared by all platforms (std only, no FFI).
+//!
+//! [`ConfigFile`] caches the settings of one `config.toml` (format in
+//! `model::preferences`), writes them back on flush, picks up edits made
+//! to the file while running, and syncs them through a user-chosen folder
+//! (see `model::sync`). Each platform only says where the file is, how to
+//! import what older versions saved, and which settings are managed; see
+//! `platform::{macos,windows}::storage`.
+
+pub mod config;
+
+pub use config::{managed_defaults, ConfigFile};
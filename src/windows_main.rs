@@ -3,52 +3,137 @@
 //! Uses Direct2D for GPU-accelerated, high-quality anti-aliased rendering
 //! with per-pixel alpha transparency via UpdateLayeredWindow.
 
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
+use tracing::{debug, error, info, info_span, warn};
 use windows::core::w;
 use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
 use windows::Win32::Graphics::Direct2D::{D2D1CreateFactory, D2D1_FACTORY_TYPE_SINGLE_THREADED};
 use windows::Win32::Graphics::DirectWrite::{DWriteCreateFactory, DWRITE_FACTORY_TYPE_SHARED};
 use windows::Win32::System::Com::{CoInitializeEx, CoUninitialize, COINIT_APARTMENTTHREADED};
 use windows::Win32::System::LibraryLoader::GetModuleHandleW;
-use windows::Win32::UI::Input::KeyboardAndMouse::{
-    RegisterHotKey, UnregisterHotKey, MOD_CONTROL, MOD_SHIFT,
-};
+use windows::Win32::UI::Shell::NIN_BALLOONUSERCLICK;
 use windows::Win32::UI::WindowsAndMessaging::{
-    CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, GetSystemMetrics, LoadCursorW,
-    PostQuitMessage, RegisterClassW, SetTimer, SetWindowsHookExW, ShowWindow, TranslateMessage,
-    UnhookWindowsHookEx, CS_HREDRAW, CS_VREDRAW, HHOOK, IDC_ARROW, MSG, SM_CXVIRTUALSCREEN,
-    SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN, SW_SHOW, WH_MOUSE_LL, WM_COMMAND,
-    WM_CREATE, WM_DESTROY, WM_HOTKEY, WM_TIMER, WNDCLASSW, WS_EX_LAYERED, WS_EX_NOACTIVATE,
-    WS_EX_TOOLWINDOW, WS_EX_TOPMOST, WS_EX_TRANSPARENT, WS_POPUP,
+    CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, LoadCursorW, PostQuitMessage,
+    RegisterClassW, SetTimer, ShowWindow, TranslateMessage, UnhookWindowsHookEx, CS_HREDRAW,
+    CS_VREDRAW, HHOOK, IDC_ARROW, MSG, SW_SHOW, WM_COMMAND, WM_COPYDATA, WM_CREATE, WM_DESTROY,
+    WM_DISPLAYCHANGE, WM_HOTKEY, WM_POINTERDOWN, WM_POINTERUP, WM_POINTERUPDATE, WM_SETTINGCHANGE,
+    WM_TIMER, WNDCLASSW, WS_EX_LAYERED, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW, WS_EX_TOPMOST,
+    WS_EX_TRANSPARENT, WS_POPUP,
 };
 
-use lumbus::model::constants::*;
-use lumbus::platform::windows::app::{reload_settings_from_config, STATE};
+use lumbus::backend::{
+    self, dispatch, dispatch_event, dispatch_pending, DispatchContext, HotkeyManager,
+    OverlayBackend,
+};
+use lumbus::events::{publish, take_event, AppEvent};
+use lumbus::model::click_log::unix_time_now;
+use lumbus::model::constants::{PREF_ONBOARDING_DONE, PREF_UPDATE_CHECK, QUICK_RADII};
+use lumbus::model::{
+    fresh_start_flag, handoff, restarted, ClickLogFormat, DiagnosticsConfig, HotkeyAction,
+    SoakConfig, UpdateStatus, HOTKEY_PROFILES, LOOK_PROFILES, RESTART_WAIT_SECS,
+};
+use lumbus::platform::windows::app::{
+    available_update, check_for_updates, check_if_due, finish_diagnostics, fit_overlay_to_displays,
+    install_crash_handler, open_release_page, reload_settings_from_config, run_diagnostics,
+    soak_tick, start_soak_test, start_update_checks, update_checked, virtual_screen_rect,
+    WindowsBackend, STATE,
+};
+use lumbus::platform::windows::ffi::system_reduce_motion;
 use lumbus::platform::windows::input::{
-    mouse_hook_proc, HOTKEY_HELP, HOTKEY_QUIT, HOTKEY_SETTINGS, HOTKEY_TOGGLE, MOUSE_HOOK,
-    TIMER_CURSOR, TIMER_INTERVAL_MS,
+    apply_control_server, check_mouse_hook, claim_instance, copied_args, forward_args,
+    hotkey_pressed, install_mouse_hook, observe_touch, publish_control_state, publish_url,
+    register_hotkeys, register_url_scheme, relaunch, track_pointer, unregister_hotkeys,
+    url_argument, WindowsHotkeys, HOTKEY_RULER_ESCAPE, MOUSE_HOOK, TIMER_CURSOR, TIMER_INTERVAL_MS,
+    TIMER_SOAK, TIMER_UPDATE,
+};
+use lumbus::platform::windows::storage::{config, watch_config, WM_CONFIG_CHANGED};
+use lumbus::platform::windows::ui::dialogs::{
+    confirm_quit, export_click_log, show_about_dialog, show_config_issues, show_help_overlay,
 };
-use lumbus::platform::windows::storage::config;
-use lumbus::platform::windows::ui::dialogs::{show_about_dialog, show_help_overlay};
 use lumbus::platform::windows::ui::overlay::{
-    create_arial_bold_font_face, release_render_cache, update_overlay, D2D_FACTORY, DWRITE_FACTORY,
-    FONT_FACE,
+    create_arial_bold_font_face, invalidate_render_target, release_render_cache, update_overlay,
+    D2D_FACTORY, DWRITE_FACTORY, FONT_FACE,
 };
+use lumbus::platform::windows::ui::session::restore_session_windows;
 use lumbus::platform::windows::ui::settings::window as settings_window;
 use lumbus::platform::windows::ui::tray::{
-    self, MENU_ABOUT, MENU_HELP, MENU_QUIT, MENU_SETTINGS, MENU_TOGGLE, WM_TRAYICON,
+    self, MENU_ABOUT, MENU_AUTO_UPDATE, MENU_CHECK_UPDATES, MENU_EXPORT_CSV, MENU_EXPORT_JSON,
+    MENU_HELP, MENU_HOTKEY_PROFILE, MENU_LOCK, MENU_LOOK_PROFILE, MENU_QUIT, MENU_RADIUS,
+    MENU_RECORD_CLICKS, MENU_RESTART, MENU_SETTINGS, MENU_TOGGLE, MENU_UPDATE, WM_TRAYICON,
 };
 
 /// Main entry point for Windows.
-pub fn run() {
-    if let Err(e) = run_app() {
-        eprintln!("Lumbus error: {}", e);
-        std::process::exit(1);
+///
+/// `soak` starts the hidden soak-test mode once the app is up,
+/// `diagnostics` saves the diagnostics report (`--diagnostics`) and
+/// `reset` restores the default settings first (`--reset`).
+pub fn run(
+    soak: Option<SoakConfig>,
+    diagnostics: Option<DiagnosticsConfig>,
+    reset: bool,
+) -> windows::core::Result<()> {
+    // Already running: hand the arguments (lumbus:// URLs) to that instance.
+    // After a restart, wait for the old instance to quit instead
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let wait = if restarted(&args) {
+        Duration::from_secs(RESTART_WAIT_SECS)
+    } else {
+        Duration::ZERO
+    };
+    if !claim_instance(wait) {
+        if let Some(flag) = fresh_start_flag(&args) {
+            warn!("Lumbus is already running; quit it before starting it with {flag}");
+        } else if !forward_args(&args) {
+            info!("Lumbus is already running");
+        }
+        return Ok(());
+    }
+    // Write a crash report instead of dying silently
+    install_crash_handler();
+    if reset {
+        info!("Restoring the default settings (--reset)");
+        config::restore_defaults();
     }
+    run_app(soak, diagnostics, url_argument())?;
+
+    // Started last, once this instance has cleaned up
+    if RESTART_REQUESTED.load(Ordering::SeqCst) {
+        info!("Restarting");
+        if let Err(e) = relaunch() {
+            error!("Could not restart: {e}");
+        }
+    }
+    Ok(())
+}
+
+/// Set by [`quit`]: the Settings window's event loop may take `WM_QUIT`
+/// off the queue before the main message loop sees it.
+static QUIT_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Set by [`restart`]: start a new instance once this one has quit.
+static RESTART_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// End the message loop, and the app.
+fn quit() {
+    QUIT_REQUESTED.store(true, Ordering::SeqCst);
+    unsafe { PostQuitMessage(0) };
+}
+
+/// Quit, then start again (see [`run`]).
+fn restart() {
+    config::flush_config();
+    RESTART_REQUESTED.store(true, Ordering::SeqCst);
+    quit();
 }
 
-fn run_app() -> windows::core::Result<()> {
+fn run_app(
+    soak: Option<SoakConfig>,
+    diagnostics: Option<DiagnosticsConfig>,
+    url: Option<String>,
+) -> windows::core::Result<()> {
+    let setup = info_span!("window_setup").entered();
     unsafe {
         // Initialize COM
         CoInitializeEx(None, COINIT_APARTMENTTHREADED).ok()?;
@@ -81,12 +166,7 @@ fn run_app() -> windows::core::Result<()> {
         RegisterClassW(&wc);
 
         // Get virtual screen dimensions (all monitors)
-        // Note: We use full dimensions but offset by 1 pixel to prevent Windows
-        // from detecting this as a "fullscreen app" and activating Focus Assist
-        let vx = GetSystemMetrics(SM_XVIRTUALSCREEN);
-        let vy = GetSystemMetrics(SM_YVIRTUALSCREEN);
-        let vw = GetSystemMetrics(SM_CXVIRTUALSCREEN) - 1;
-        let vh = GetSystemMetrics(SM_CYVIRTUALSCREEN) - 1;
+        let (vx, vy, vw, vh) = virtual_screen_rect();
 
         // Create layered, transparent, topmost window
         let ex_style =
@@ -106,6 +186,13 @@ fn run_app() -> windows::core::Result<()> {
             Some(instance.into()),
             None,
         )?;
+        info!(
+            x = vx,
+            y = vy,
+            width = vw,
+            height = vh,
+            "Overlay window created"
+        );
 
         // Store state
         STATE.with(|s| {
@@ -115,27 +202,37 @@ fn run_app() -> windows::core::Result<()> {
             state.height = vh;
             state.offset_x = vx;
             state.offset_y = vy;
+            state.system_reduce_motion = system_reduce_motion();
         });
 
         // Load settings from config file
-        reload_settings_from_config();
+        backend::start_overlay(&mut WindowsBackend::new(hwnd));
 
         // Install low-level mouse hook for click detection
-        let hook = SetWindowsHookExW(WH_MOUSE_LL, Some(mouse_hook_proc), None, 0)?;
-        MOUSE_HOOK.store(hook.0 as isize, Ordering::SeqCst);
+        install_mouse_hook()?;
+
+        // Rings at every touch point, or at the primary one through the hook
+        observe_touch(hwnd);
 
-        // Register global hotkeys
-        let _ = RegisterHotKey(Some(hwnd), HOTKEY_TOGGLE, MOD_CONTROL | MOD_SHIFT, 0x41); // Ctrl+Shift+A
-        let _ = RegisterHotKey(Some(hwnd), HOTKEY_SETTINGS, MOD_CONTROL | MOD_SHIFT, 0x53); // Ctrl+Shift+S
-        let _ = RegisterHotKey(Some(hwnd), HOTKEY_HELP, MOD_CONTROL | MOD_SHIFT, 0x48); // Ctrl+Shift+H
-        let _ = RegisterHotKey(Some(hwnd), HOTKEY_QUIT, MOD_CONTROL | MOD_SHIFT, 0x51); // Ctrl+Shift+Q
+        // Register the global hotkeys of the selected profile, carried
+        // out as soon as pressed rather than on the next tick
+        WindowsHotkeys::new(hwnd).on_trigger(hotkey_triggered);
+        register_hotkeys(hwnd);
 
         // Install system tray icon
         tray::install_tray_icon(hwnd);
 
         // Update tray menu language based on loaded settings
-        let is_spanish = STATE.with(|s| s.borrow().lang == LANG_ES);
-        tray::update_tray_language(is_spanish);
+        tray::update_tray_language();
+
+        // First launch: walkthrough before the overlay appears
+        if soak.is_none()
+            && diagnostics.is_none()
+            && config::prefs_get_int(PREF_ONBOARDING_DONE, 0) == 0
+        {
+            settings_window::open_onboarding_window(hwnd);
+            reload_settings_from_config();
+        }
 
         // Start timer for cursor tracking
         SetTimer(Some(hwnd), TIMER_CURSOR, TIMER_INTERVAL_MS, None);
@@ -144,23 +241,60 @@ fn run_app() -> windows::core::Result<()> {
         update_overlay();
         let _ = ShowWindow(hwnd, SW_SHOW);
 
-        // Message loop
+        // Reopen auxiliary windows left open in the previous session
+        restore_session_windows(hwnd);
+
+        // Report settings that could not be used as written
+        show_config_issues(hwnd);
+
+        // Apply edits made to the settings file while running
+        watch_config(hwnd);
+
+        // lumbus:// URLs, including the one this instance was started with
+        register_url_scheme();
+        if let Some(url) = url {
+            publish_url(&url);
+            dispatch_events(hwnd);
+        }
+
+        // Local control endpoint (off unless enabled in the settings file)
+        apply_control_server();
+
+        // Daily check for a new release (unless turned off)
+        start_update_checks(hwnd);
+
+        if let Some(config) = soak {
+            start_soak_test(hwnd, config);
+        }
+        if let Some(config) = diagnostics {
+            run_diagnostics(config);
+        }
+
+        drop(setup);
+
+        // Message loop; the Settings window's event loop takes over while
+        // it is open, handling the overlay's messages too
         let mut msg = MSG::default();
-        while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+        loop {
+            if settings_window::pump_settings_window() {
+                continue;
+            }
+            if QUIT_REQUESTED.load(Ordering::SeqCst) || !GetMessageW(&mut msg, None, 0, 0).as_bool()
+            {
+                break;
+            }
             let _ = TranslateMessage(&msg);
             DispatchMessageW(&msg);
         }
 
         // Cleanup
+        finish_diagnostics();
         let hook_handle = MOUSE_HOOK.load(Ordering::SeqCst);
         if hook_handle != 0 {
             let _ = UnhookWindowsHookEx(HHOOK(hook_handle as *mut _));
         }
 
-        let _ = UnregisterHotKey(Some(hwnd), HOTKEY_TOGGLE);
-        let _ = UnregisterHotKey(Some(hwnd), HOTKEY_SETTINGS);
-        let _ = UnregisterHotKey(Some(hwnd), HOTKEY_HELP);
-        let _ = UnregisterHotKey(Some(hwnd), HOTKEY_QUIT);
+        unregister_hotkeys(hwnd);
 
         // Remove system tray icon
         tray::remove_tray_icon();
@@ -183,70 +317,116 @@ extern "system" fn wndproc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM)
 
             WM_TIMER => {
                 if wparam.0 == TIMER_CURSOR {
+                    // Windows drops a hook that stalls; put it back
+                    check_mouse_hook();
+                    // Requests of the control endpoint
+                    dispatch_events(hwnd);
+                    publish_control_state();
                     update_overlay();
+                } else if wparam.0 == TIMER_SOAK {
+                    if soak_tick(hwnd) {
+                        quit();
+                    }
+                } else if wparam.0 == TIMER_UPDATE {
+                    check_if_due();
                 }
                 LRESULT(0)
             }
 
             WM_HOTKEY => {
-                let hotkey_id = wparam.0 as i32;
-                match hotkey_id {
-                    HOTKEY_TOGGLE => {
-                        let new_visible = STATE.with(|s| {
-                            let mut state = s.borrow_mut();
-                            state.visible = !state.visible;
-                            state.visible
-                        });
-                        eprintln!(
-                            "Toggle: overlay {}",
-                            if new_visible { "visible" } else { "hidden" }
-                        );
-                        update_overlay();
+                match wparam.0 as i32 {
+                    HOTKEY_RULER_ESCAPE => {
+                        dispatch(&mut WindowsBackend::new(hwnd), &AppEvent::ClearRuler);
                     }
-                    HOTKEY_SETTINGS => {
-                        eprintln!("Opening settings window");
-                        let hwnd = STATE.with(|s| s.borrow().hwnd);
-                        settings_window::open_settings_window(hwnd);
-                        reload_settings_from_config();
-                        update_overlay();
-                    }
-                    HOTKEY_HELP => {
-                        eprintln!("Showing help overlay");
-                        let (hwnd, is_spanish) = STATE.with(|s| {
-                            let state = s.borrow();
-                            (state.hwnd, state.lang == 1)
-                        });
-                        show_help_overlay(hwnd, is_spanish);
+                    // Goes to `hotkey_triggered`
+                    hotkey_id => {
+                        hotkey_pressed(hotkey_id);
                     }
-                    HOTKEY_QUIT => {
-                        PostQuitMessage(0);
-                    }
-                    _ => {}
                 }
                 LRESULT(0)
             }
 
+            // Follow the system "animation effects" setting, and display
+            // scaling changes that arrive without WM_DISPLAYCHANGE
+            WM_SETTINGCHANGE => {
+                fit_overlay_to_displays();
+                let reduced = system_reduce_motion();
+                STATE.with(|s| {
+                    let mut state = s.borrow_mut();
+                    if state.system_reduce_motion != reduced {
+                        state.system_reduce_motion = reduced;
+                        state.dirty = true;
+                    }
+                });
+                DefWindowProcW(hwnd, msg, wparam, lparam)
+            }
+
+            // Pen pressure, and touch points while touch input is observed
+            WM_POINTERDOWN | WM_POINTERUPDATE | WM_POINTERUP => {
+                track_pointer(msg, wparam);
+                DefWindowProcW(hwnd, msg, wparam, lparam)
+            }
+
+            // Monitors connected, disconnected, resized or rearranged
+            WM_DISPLAYCHANGE => {
+                fit_overlay_to_displays();
+                invalidate_render_target();
+                update_overlay();
+                LRESULT(0)
+            }
+
             WM_DESTROY => {
                 config::flush_config();
-                PostQuitMessage(0);
+                quit();
+                LRESULT(0)
+            }
+
+            // Settings file edited outside the app
+            msg if msg == WM_CONFIG_CHANGED => {
+                if config::reload_config() {
+                    let mut overlay = WindowsBackend::new(hwnd);
+                    backend::load_settings(&mut overlay);
+                    // The hotkey profile may have changed too
+                    overlay.reinstall_hotkeys();
+                    overlay.refresh_menus();
+                    overlay.redraw();
+                    apply_control_server();
+                    show_config_issues(hwnd);
+                }
                 LRESULT(0)
             }
 
+            // Arguments handed over by a second launch
+            WM_COPYDATA => match copied_args(lparam) {
+                Some(args) => {
+                    let request = handoff(&args);
+                    if request.opens_settings() {
+                        publish(AppEvent::OpenSettings);
+                    }
+                    request.urls.iter().for_each(|url| publish_url(url));
+                    if let Some(config) = request.diagnostics {
+                        run_diagnostics(config);
+                    }
+                    dispatch_events(hwnd);
+                    LRESULT(1)
+                }
+                None => DefWindowProcW(hwnd, msg, wparam, lparam),
+            },
+
             // System tray icon messages
             msg if msg == WM_TRAYICON => {
                 let event = lparam.0 as u32;
                 if event == 0x0205 {
                     // Right-click: show context menu
                     tray::show_tray_menu(hwnd);
-                } else if event == 0x0203 {
-                    // Double-click: toggle visibility
-                    let new_visible = STATE.with(|s| {
-                        let mut state = s.borrow_mut();
-                        state.visible = !state.visible;
-                        state.visible
-                    });
-                    tray::update_tray_tooltip(new_visible);
-                    update_overlay();
+                } else if event == NIN_BALLOONUSERCLICK && !tray::toggle_notified() {
+                    // Update notification clicked: open the download page
+                    if let Some(release) = available_update() {
+                        open_release_page(&release);
+                    }
+                } else if event == 0x0203 || event == 0x0208 {
+                    // Double-click or middle-click: toggle visibility
+                    dispatch(&mut WindowsBackend::new(hwnd), &AppEvent::ToggleOverlay);
                 }
                 LRESULT(0)
             }
@@ -256,28 +436,74 @@ extern "system" fn wndproc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM)
                 let cmd = (wparam.0 & 0xFFFF) as u32;
                 match cmd {
                     MENU_TOGGLE => {
-                        let new_visible = STATE.with(|s| {
-                            let mut state = s.borrow_mut();
-                            state.visible = !state.visible;
-                            state.visible
-                        });
-                        tray::update_tray_tooltip(new_visible);
-                        update_overlay();
+                        dispatch(&mut WindowsBackend::new(hwnd), &AppEvent::ToggleOverlay);
                     }
-                    MENU_SETTINGS => {
+                    MENU_SETTINGS if settings_allowed() => {
                         settings_window::open_settings_window(hwnd);
-                        reload_settings_from_config();
-                        update_overlay();
+                    }
+                    MENU_LOCK => {
+                        dispatch_event(
+                            &mut WindowsContext::new(hwnd),
+                            &AppEvent::TogglePresentationLock,
+                        );
+                    }
+                    MENU_RECORD_CLICKS => {
+                        dispatch_event(
+                            &mut WindowsContext::new(hwnd),
+                            &AppEvent::ToggleClickRecording,
+                        );
+                    }
+                    MENU_EXPORT_CSV => {
+                        export_click_log(hwnd, ClickLogFormat::Csv);
+                    }
+                    MENU_EXPORT_JSON => {
+                        export_click_log(hwnd, ClickLogFormat::Json);
+                    }
+                    MENU_UPDATE => {
+                        if let Some(release) = available_update() {
+                            open_release_page(&release);
+                        }
+                    }
+                    MENU_CHECK_UPDATES => {
+                        check_for_updates(true);
+                    }
+                    MENU_AUTO_UPDATE => {
+                        toggle_update_check();
                     }
                     MENU_ABOUT => {
                         show_about_dialog(hwnd);
                     }
                     MENU_HELP => {
-                        let is_spanish = STATE.with(|s| s.borrow().lang == 1);
-                        show_help_overlay(hwnd, is_spanish);
+                        show_help_overlay(hwnd);
+                    }
+                    MENU_RESTART => {
+                        restart();
                     }
-                    MENU_QUIT => {
-                        PostQuitMessage(0);
+                    MENU_QUIT if confirm_quit(hwnd) => {
+                        quit();
+                    }
+                    MENU_QUIT => {}
+                    cmd if (MENU_HOTKEY_PROFILE
+                        ..MENU_HOTKEY_PROFILE + HOTKEY_PROFILES.len() as u32)
+                        .contains(&cmd) =>
+                    {
+                        let profile = HOTKEY_PROFILES[(cmd - MENU_HOTKEY_PROFILE) as usize];
+                        select_hotkey_profile(hwnd, profile);
+                    }
+                    cmd if (MENU_RADIUS..MENU_RADIUS + QUICK_RADII.len() as u32).contains(&cmd)
+                        && settings_allowed() =>
+                    {
+                        let radius = QUICK_RADII[(cmd - MENU_RADIUS) as usize];
+                        let event = AppEvent::SetRadius(radius);
+                        dispatch(&mut WindowsBackend::new(hwnd), &event);
+                    }
+                    cmd if (MENU_LOOK_PROFILE..MENU_LOOK_PROFILE + LOOK_PROFILES.len() as u32)
+                        .contains(&cmd)
+                        && settings_allowed() =>
+                    {
+                        let name = LOOK_PROFILES[(cmd - MENU_LOOK_PROFILE) as usize];
+                        let event = AppEvent::SelectLookProfile(name.to_string());
+                        dispatch(&mut WindowsBackend::new(hwnd), &event);
                     }
                     _ => {}
                 }
@@ -288,3 +514,169 @@ extern "system" fn wndproc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM)
         }
     }
 }
+
+/// Handle the events published on the event bus (`lumbus://` URLs, the
+/// control endpoint, update checks).
+///
+/// Hotkeys and menu commands are handled directly in `wndproc`.
+fn dispatch_events(hwnd: HWND) {
+    dispatch_pending(&mut WindowsContext::new(hwnd), take_event);
+}
+
+/// The actions of the dispatcher, carried out through the main window.
+struct WindowsContext {
+    hwnd: HWND,
+    backend: WindowsBackend,
+}
+
+impl WindowsContext {
+    fn new(hwnd: HWND) -> Self {
+        Self {
+            hwnd,
+            backend: WindowsBackend::new(hwnd),
+        }
+    }
+
+    /// Zoom in or out one step and redraw if the level changed.
+    fn zoom(&mut self, zoom_in: bool) {
+        STATE.with(|s| {
+            let mut state = s.borrow_mut();
+            let changed = if zoom_in {
+                state.zoom.zoom_in()
+            } else {
+                state.zoom.zoom_out()
+            };
+            if changed {
+                debug!("Zoom: {}x", state.zoom.level());
+                state.dirty = true;
+            }
+        });
+        update_overlay();
+    }
+}
+
+impl DispatchContext for WindowsContext {
+    fn backend(&mut self) -> &mut dyn OverlayBackend {
+        &mut self.backend
+    }
+
+    fn allows(&self, event: &AppEvent) -> bool {
+        STATE.with(|s| s.borrow().lock.allows(event))
+    }
+
+    fn toggle_presentation_lock(&mut self) -> bool {
+        STATE.with(|s| s.borrow_mut().lock.toggle())
+    }
+
+    fn open_settings(&mut self) -> bool {
+        // Runs its own event loop, handling the overlay's messages too
+        settings_window::open_settings_window(self.hwnd);
+        false
+    }
+
+    fn confirm_quit(&mut self) -> bool {
+        if confirm_quit(self.hwnd) {
+            quit();
+        }
+        true
+    }
+
+    fn restart(&mut self) {
+        restart();
+    }
+
+    fn show_about(&mut self) {
+        show_about_dialog(self.hwnd);
+    }
+
+    fn show_help(&mut self) -> bool {
+        show_help_overlay(self.hwnd);
+        true
+    }
+
+    fn zoom_in(&mut self) {
+        self.zoom(true);
+    }
+
+    fn zoom_out(&mut self) {
+        self.zoom(false);
+    }
+
+    fn toggle_click_recording(&mut self) -> bool {
+        STATE.with(|s| s.borrow_mut().click_log.toggle(unix_time_now()))
+    }
+
+    fn export_click_log(&mut self, format: ClickLogFormat) -> bool {
+        export_click_log(self.hwnd, format);
+        true
+    }
+
+    fn check_for_updates(&mut self) {
+        check_for_updates(true);
+    }
+
+    fn update_checked(&mut self, status: &UpdateStatus, manual: bool) -> bool {
+        // A manual check answers with a message box
+        update_checked(self.hwnd, status, manual);
+        manual
+    }
+}
+
+/// Can the Settings window be opened right now (presentation lock off)?
+fn settings_allowed() -> bool {
+    STATE.with(|s| s.borrow().lock.allows(&AppEvent::OpenSettings))
+}
+
+/// Carry out the command of a global hotkey pressed.
+fn hotkey_triggered(action: HotkeyAction) {
+    let hwnd = STATE.with(|s| s.borrow().hwnd);
+    match action {
+        HotkeyAction::Toggle => {
+            dispatch(&mut WindowsBackend::new(hwnd), &AppEvent::ToggleOverlay);
+            tray::notify_toggle();
+        }
+        HotkeyAction::Settings if !settings_allowed() => {
+            info!("Settings locked for presentation");
+        }
+        HotkeyAction::Settings => {
+            info!("Opening settings window");
+            settings_window::open_settings_window(hwnd);
+        }
+        HotkeyAction::Help => {
+            info!("Showing help overlay");
+            show_help_overlay(hwnd);
+        }
+        HotkeyAction::Quit => {
+            if confirm_quit(hwnd) {
+                quit();
+            }
+        }
+        // Zoom and the lock need the platform context; the toggles only
+        // the backend
+        _ => {
+            dispatch_event(&mut WindowsContext::new(hwnd), &action.event());
+        }
+    }
+}
+
+/// Switch to another hotkey profile: save it, register its hotkeys and
+/// refresh the tray menu to match.
+fn select_hotkey_profile(hwnd: HWND, profile: &str) {
+    backend::select_hotkey_profile(&mut WindowsBackend::new(hwnd), profile);
+}
+
+/// Turn the daily update check on or off, then refresh the tray menu to
+/// match.
+fn toggle_update_check() {
+    let enabled = config::prefs_get_int(PREF_UPDATE_CHECK, 1) == 0;
+    config::prefs_set_int(PREF_UPDATE_CHECK, enabled as i32);
+    config::flush_config();
+    info!(
+        "Automatic update check {}",
+        if enabled { "on" } else { "off" }
+    );
+    tray::update_tray_language();
+    if enabled {
+        check_if_due();
+    }
+}
@@ -0,0 +1,27 @@
+//! Tests for the About window contents.
+
+use lumbus::i18n::{message_id, set_language, tr};
+use lumbus::model::about::{version_label, ABOUT_LINKS, BUILD_HASH, VERSION};
+
+#[test]
+fn version_label_includes_build_hash_when_known() {
+    assert_eq!(version_label("0.5.3", "a1b2c3d"), "0.5.3 (a1b2c3d)");
+    assert_eq!(version_label("0.5.3", ""), "0.5.3");
+}
+
+#[test]
+fn version_is_the_package_version() {
+    assert_eq!(VERSION, env!("CARGO_PKG_VERSION"));
+    assert!(!BUILD_HASH.contains(char::is_whitespace));
+}
+
+#[test]
+fn links_are_https_and_translated() {
+    for (key, url) in ABOUT_LINKS {
+        assert!(url.starts_with("https://"), "{url}");
+        assert!(!message_id(key).is_empty());
+    }
+    set_language("es");
+    assert_eq!(tr("License"), "Licencia");
+    set_language("en");
+}
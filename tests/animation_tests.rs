@@ -0,0 +1,66 @@
+//! Tests for the animation clock and the show/hide transition.
+
+use lumbus::model::constants::*;
+use lumbus::model::{transition_alpha, transition_scale, wall_clock_secs, ShowHide};
+
+fn approx_eq(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn wall_clock_is_past_2020() {
+    assert!(wall_clock_secs() > 1.6e9);
+}
+
+#[test]
+fn new_transition_is_shown() {
+    let mut show_hide = ShowHide::new();
+    assert_eq!(show_hide.progress(), 1.0);
+    assert_eq!(show_hide.update(true, 0.0, DEFAULT_TRANSITION_MS), 1.0);
+}
+
+#[test]
+fn hiding_runs_over_the_duration() {
+    let mut show_hide = ShowHide::new();
+    show_hide.update(true, 10.0, 200.0);
+    assert!(approx_eq(show_hide.update(false, 10.1, 200.0), 0.5));
+    assert!(approx_eq(show_hide.update(false, 10.2, 200.0), 0.0));
+    assert_eq!(show_hide.update(false, 11.0, 200.0), 0.0);
+}
+
+#[test]
+fn toggle_midway_reverses_from_where_it_is() {
+    let mut show_hide = ShowHide::new();
+    show_hide.update(false, 0.0, 400.0);
+    assert!(approx_eq(show_hide.update(false, 0.1, 400.0), 0.75));
+    assert!(approx_eq(show_hide.update(true, 0.2, 400.0), 1.0));
+    show_hide.update(false, 0.3, 400.0);
+    assert!(approx_eq(show_hide.update(true, 0.35, 400.0), 0.875));
+}
+
+#[test]
+fn zero_duration_switches_at_once() {
+    let mut show_hide = ShowHide::new();
+    assert_eq!(show_hide.update(false, 0.0, MIN_TRANSITION_MS), 0.0);
+    assert_eq!(show_hide.update(true, 0.0, MIN_TRANSITION_MS), 1.0);
+}
+
+#[test]
+fn snap_skips_the_running_transition() {
+    let mut show_hide = ShowHide::new();
+    show_hide.update(false, 0.0, 200.0);
+    show_hide.update(false, 0.05, 200.0);
+    assert_eq!(show_hide.snap(), 0.0);
+    show_hide.update(true, 0.06, 200.0);
+    assert_eq!(show_hide.snap(), 1.0);
+}
+
+#[test]
+fn transition_eases_alpha_and_scale() {
+    assert_eq!(transition_alpha(0.0), 0.0);
+    assert_eq!(transition_alpha(1.0), 1.0);
+    assert!(approx_eq(transition_alpha(0.5), 0.5));
+    assert!(transition_alpha(0.1) < 0.1);
+    assert!(approx_eq(transition_scale(0.0), TRANSITION_START_SCALE));
+    assert_eq!(transition_scale(1.0), 1.0);
+}
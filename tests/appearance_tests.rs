@@ -0,0 +1,36 @@
+//! Tests for the Settings window palettes.
+
+use lumbus::model::appearance::{DARK_PALETTE, LIGHT_PALETTE};
+use lumbus::model::contrast::relative_luminance;
+use lumbus::model::SettingsPalette;
+
+fn luminance((r, g, b): (u8, u8, u8)) -> f64 {
+    relative_luminance(r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0)
+}
+
+/// WCAG contrast ratio between two colors.
+fn contrast(a: (u8, u8, u8), b: (u8, u8, u8)) -> f64 {
+    let (la, lb) = (luminance(a), luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+#[test]
+fn palette_follows_the_system_theme() {
+    assert_eq!(SettingsPalette::for_theme(true), DARK_PALETTE);
+    assert_eq!(SettingsPalette::for_theme(false), LIGHT_PALETTE);
+}
+
+#[test]
+fn dark_palette_has_a_dark_background() {
+    assert!(luminance(DARK_PALETTE.background) < luminance(LIGHT_PALETTE.background));
+    assert!(luminance(DARK_PALETTE.text) > luminance(DARK_PALETTE.background));
+}
+
+#[test]
+fn text_is_readable_in_both_palettes() {
+    for palette in [LIGHT_PALETTE, DARK_PALETTE] {
+        // WCAG AA for normal text
+        assert!(contrast(palette.text, palette.background) >= 4.5);
+        assert!(contrast(palette.value_text, palette.background) >= 4.5);
+    }
+}
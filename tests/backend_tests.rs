@@ -0,0 +1,551 @@
+//! Tests for the shared event handling, run on the headless backend.
+
+use lumbus::backend::{compose_frame, DrawCommand, FrameInput, HeadlessBackend};
+use lumbus::events::AppEvent;
+use lumbus::i18n::{language, set_language};
+use lumbus::model::constants::*;
+use lumbus::model::trail::TrailSegment;
+use lumbus::model::{
+    profile_bindings, DisplayMode, HotkeyPlatform, OverlayState, Preferences,
+    HOTKEY_PROFILE_PRESENTATION, HOTKEY_PROFILE_STANDARD, LOOK_PROFILE_HIGH_CONTRAST,
+    LOOK_PROFILE_STANDARD,
+};
+
+#[test]
+fn toggle_hides_and_shows_the_highlight() {
+    let mut backend = HeadlessBackend::new(HotkeyPlatform::MacOs);
+    backend.input.pointer = (40.0, 60.0);
+    let left = backend.dispatch_all([AppEvent::ToggleOverlay, AppEvent::ToggleOverlay]);
+    assert!(left.is_empty());
+    assert!(backend.visible());
+
+    let frames = backend.frames();
+    assert_eq!(frames.len(), 2);
+    assert!(frames[0].is_empty());
+    assert!(matches!(
+        frames[1].as_slice(),
+        [DrawCommand::Circle {
+            center: (40.0, 60.0),
+            ..
+        }]
+    ));
+}
+
+#[test]
+fn coordinates_hotkey_toggles_the_readout() {
+    let mut backend = HeadlessBackend::new(HotkeyPlatform::Windows);
+    backend.input.pointer = (40.0, 60.0);
+    backend.dispatch_all([AppEvent::ToggleCoordinates]);
+    match backend.last_frame().unwrap() {
+        [DrawCommand::Circle { .. }, DrawCommand::Coordinates { label, .. }] => {
+            assert_eq!(label, "40, 60");
+        }
+        frame => panic!("unexpected frame: {frame:?}"),
+    }
+    backend.dispatch_all([AppEvent::ToggleCoordinates]);
+    assert_eq!(backend.last_frame().unwrap().len(), 1);
+}
+
+#[test]
+fn color_sampler_shows_the_screen_color() {
+    let mut backend = HeadlessBackend::new(HotkeyPlatform::MacOs);
+    backend.screen_color = (30, 144, 255);
+    backend.dispatch_all([AppEvent::ToggleColorSampler]);
+    match backend.last_frame().unwrap() {
+        [DrawCommand::Circle { .. }, DrawCommand::ColorSample { color, label, .. }] => {
+            assert_eq!(*color, (30, 144, 255));
+            assert_eq!(label, "#1E90FF");
+        }
+        frame => panic!("unexpected frame: {frame:?}"),
+    }
+    backend.dispatch_all([AppEvent::ToggleColorSampler]);
+    assert_eq!(backend.last_frame().unwrap().len(), 1);
+}
+
+#[test]
+fn grid_hotkey_draws_the_grid_under_the_highlight() {
+    let mut backend = HeadlessBackend::new(HotkeyPlatform::Windows);
+    backend.dispatch_all([
+        AppEvent::SetPreference(PREF_GRID_SPACING.to_string(), 42.0),
+        AppEvent::SetPreference(PREF_GRID_SUBDIVISIONS.to_string(), 0.0),
+        AppEvent::ToggleGrid,
+    ]);
+    match backend.last_frame().unwrap() {
+        [DrawCommand::Grid {
+            spacing,
+            subdivisions,
+            color,
+        }, DrawCommand::Circle { .. }] => {
+            assert_eq!(*spacing, 42.0);
+            assert_eq!(*subdivisions, MIN_GRID_SUBDIVISIONS as i32);
+            assert_eq!(*color, DEFAULT_GRID_COLOR);
+        }
+        frame => panic!("unexpected frame: {frame:?}"),
+    }
+    backend.dispatch_all([AppEvent::ToggleGrid]);
+    assert_eq!(backend.last_frame().unwrap().len(), 1);
+}
+
+#[test]
+fn ruler_measures_between_two_clicks_until_cleared() {
+    let mut backend = HeadlessBackend::new(HotkeyPlatform::Windows);
+    assert!(!backend.click((10.0, 10.0)));
+
+    backend.dispatch_all([AppEvent::ToggleRuler]);
+    backend.input.pointer = (10.0, 10.0);
+    assert!(backend.click((10.0, 10.0)));
+    assert!(matches!(
+        backend.last_frame().unwrap(),
+        [DrawCommand::Ruler { .. }, DrawCommand::Circle { .. }]
+    ));
+    // The pointer has moved on by the time the frame is drawn
+    backend.input.pointer = (90.0, 90.0);
+    assert!(backend.click((40.0, 50.0)));
+    match backend.last_frame().unwrap() {
+        [DrawCommand::Ruler { line, label }, DrawCommand::Circle { .. }] => {
+            assert_eq!(*line, ((10.0, 10.0), (40.0, 50.0)));
+            assert_eq!(label, "50 px  (30 × 40)");
+        }
+        frame => panic!("unexpected frame: {frame:?}"),
+    }
+
+    backend.dispatch_all([AppEvent::ClearRuler]);
+    assert_eq!(backend.last_frame().unwrap().len(), 1);
+    assert!(!backend.click((10.0, 10.0)));
+}
+
+#[test]
+fn ruler_does_not_start_while_the_overlay_is_hidden() {
+    let mut backend = HeadlessBackend::new(HotkeyPlatform::MacOs);
+    backend.dispatch_all([AppEvent::ToggleOverlay, AppEvent::ToggleRuler]);
+    assert!(!backend.click((10.0, 10.0)));
+}
+
+#[test]
+fn selecting_a_profile_registers_its_hotkeys() {
+    let mut backend = HeadlessBackend::new(HotkeyPlatform::Windows);
+    assert_eq!(
+        backend.hotkeys(),
+        profile_bindings(HOTKEY_PROFILE_STANDARD, HotkeyPlatform::Windows)
+    );
+
+    backend.dispatch_all([AppEvent::SelectHotkeyProfile(
+        HOTKEY_PROFILE_PRESENTATION.to_string(),
+    )]);
+    assert_eq!(backend.prefs.hotkey_profile, HOTKEY_PROFILE_PRESENTATION);
+    assert_eq!(
+        backend.hotkeys(),
+        profile_bindings(HOTKEY_PROFILE_PRESENTATION, HotkeyPlatform::Windows)
+    );
+    assert_eq!(backend.flushes(), 1);
+    assert_eq!(backend.menu_refreshes(), 1);
+
+    // Unknown names select the standard profile
+    backend.dispatch_all([AppEvent::SelectHotkeyProfile("nope".to_string())]);
+    assert_eq!(backend.prefs.hotkey_profile, HOTKEY_PROFILE_STANDARD);
+}
+
+#[test]
+fn preferences_are_clamped_and_applied_to_the_overlay() {
+    let mut backend = HeadlessBackend::new(HotkeyPlatform::MacOs);
+    backend.dispatch_all([
+        AppEvent::SetPreference(PREF_RADIUS.to_string(), 10_000.0),
+        AppEvent::SetPreference(PREF_SCREEN_FRAME.to_string(), 1.0),
+    ]);
+    assert_eq!(backend.prefs.radius, MAX_RADIUS);
+    assert_eq!(backend.state().radius, MAX_RADIUS);
+    assert_eq!(backend.flushes(), 2);
+
+    let frame = backend.last_frame().unwrap();
+    assert!(matches!(frame[0], DrawCommand::ScreenFrame { .. }));
+    assert!(matches!(
+        frame[1],
+        DrawCommand::Circle { radius, .. } if radius == MAX_RADIUS
+    ));
+}
+
+#[test]
+fn selecting_a_look_sets_its_values() {
+    let mut backend = HeadlessBackend::new(HotkeyPlatform::Windows);
+    backend.dispatch_all([AppEvent::SelectLookProfile(
+        LOOK_PROFILE_HIGH_CONTRAST.to_string(),
+    )]);
+    assert!(backend.prefs.halo);
+    assert_eq!(backend.prefs.border_width, HIGH_CONTRAST_BORDER_WIDTH);
+    assert_eq!(backend.flushes(), 1);
+    assert_eq!(backend.menu_refreshes(), 1);
+
+    backend.dispatch_all([AppEvent::SelectLookProfile(
+        LOOK_PROFILE_STANDARD.to_string(),
+    )]);
+    assert_eq!(backend.prefs, Preferences::default());
+
+    // Unknown looks change nothing
+    backend.dispatch_all([AppEvent::SelectLookProfile("neon".to_string())]);
+    assert_eq!(backend.flushes(), 2);
+}
+
+#[test]
+fn remote_control_events_set_radius_color_and_profile() {
+    let mut backend = HeadlessBackend::new(HotkeyPlatform::Windows);
+    backend.dispatch_all([
+        AppEvent::SetRadius(10_000.0),
+        AppEvent::SetColor {
+            r: 1.0,
+            g: 0.5,
+            b: -1.0,
+            a: 0.25,
+        },
+        AppEvent::LoadProfile("PRESENTATION".to_string()),
+    ]);
+    assert_eq!(backend.state().radius, MAX_RADIUS);
+    let prefs = &backend.prefs;
+    assert_eq!(
+        (
+            prefs.stroke_r,
+            prefs.stroke_g,
+            prefs.stroke_b,
+            prefs.stroke_a
+        ),
+        (1.0, 0.5, 0.0, 0.25)
+    );
+    assert_eq!(backend.prefs.hotkey_profile, HOTKEY_PROFILE_PRESENTATION);
+    assert_eq!(
+        backend.hotkeys(),
+        profile_bindings(HOTKEY_PROFILE_PRESENTATION, HotkeyPlatform::Windows)
+    );
+
+    // Unlike selecting from the menu, unknown profiles change nothing
+    backend.dispatch_all([AppEvent::LoadProfile("nope".to_string())]);
+    assert_eq!(backend.prefs.hotkey_profile, HOTKEY_PROFILE_PRESENTATION);
+}
+
+#[test]
+fn mode_event_draws_a_letter_until_the_circle_is_back() {
+    let mut backend = HeadlessBackend::new(HotkeyPlatform::MacOs);
+    backend.dispatch_all([AppEvent::SetMode(DisplayMode::Right)]);
+    assert!(matches!(
+        backend.last_frame().unwrap(),
+        [DrawCommand::Letter { .. }]
+    ));
+    backend.dispatch_all([AppEvent::SetMode(DisplayMode::Circle)]);
+    assert!(matches!(
+        backend.last_frame().unwrap(),
+        [DrawCommand::Circle { .. }]
+    ));
+}
+
+#[test]
+fn language_preference_switches_the_language() {
+    let mut backend = HeadlessBackend::new(HotkeyPlatform::MacOs);
+    backend.dispatch_all([AppEvent::SetPreference(
+        PREF_LANG.to_string(),
+        LANG_ES as f64,
+    )]);
+    assert_eq!(language(), "es");
+    assert_eq!(backend.menu_refreshes(), 1);
+
+    // "System default" follows the OS locale
+    backend.locale = Some("fr-FR".to_string());
+    backend.dispatch_all([AppEvent::SetPreference(
+        PREF_LANG.to_string(),
+        LANG_SYSTEM as f64,
+    )]);
+    assert_eq!(language(), "fr");
+    set_language("en");
+}
+
+#[test]
+fn platform_events_are_left_to_the_platform() {
+    let mut backend = HeadlessBackend::with_prefs(Preferences::default(), HotkeyPlatform::MacOs);
+    let left = backend.dispatch_all([
+        AppEvent::OpenSettings,
+        AppEvent::ToggleOverlay,
+        AppEvent::RequestQuit,
+        AppEvent::Restart,
+    ]);
+    assert_eq!(
+        left,
+        [
+            AppEvent::OpenSettings,
+            AppEvent::RequestQuit,
+            AppEvent::Restart
+        ]
+    );
+    assert_eq!(backend.frames().len(), 1);
+}
+
+#[test]
+fn custom_click_letters_are_drawn() {
+    let state = OverlayState {
+        click_letter_right: "RMB".to_string(),
+        ..OverlayState::default()
+    };
+    let input = FrameInput {
+        display_mode: DISPLAY_MODE_RIGHT,
+        ..FrameInput::default()
+    };
+    match compose_frame(&state, &input).as_slice() {
+        [DrawCommand::Letter { letter, .. }] => assert_eq!(letter, "RMB"),
+        frame => panic!("unexpected frame: {frame:?}"),
+    }
+}
+
+#[test]
+fn click_icon_replaces_the_letter_of_both_buttons() {
+    let state = OverlayState {
+        click_icon: CLICK_ICON_MOUSE,
+        click_burst: true,
+        ..OverlayState::default()
+    };
+    for display_mode in [DISPLAY_MODE_LEFT, DISPLAY_MODE_RIGHT] {
+        let input = FrameInput {
+            display_mode,
+            click_count: 2,
+            ..FrameInput::default()
+        };
+        match compose_frame(&state, &input).as_slice() {
+            [DrawCommand::Icon { icon, .. }, DrawCommand::Badge { label, .. }] => {
+                assert_eq!(icon.symbol, "computermouse.fill");
+                assert_eq!(label, "x2");
+            }
+            frame => panic!("unexpected frame: {frame:?}"),
+        }
+    }
+}
+
+#[test]
+fn frames_are_layered_like_the_renderers() {
+    let state = OverlayState {
+        screen_frame: true,
+        click_burst: true,
+        ..OverlayState::default()
+    };
+    let input = FrameInput {
+        display_mode: DISPLAY_MODE_RIGHT,
+        click_count: 3,
+        zoom: Some((2.0, (10.0, 10.0))),
+        debug: Some("16.7 ms".to_string()),
+        ..FrameInput::default()
+    };
+    let frame = compose_frame(&state, &input);
+    assert_eq!(frame.len(), 5);
+    assert!(matches!(frame[0], DrawCommand::Zoom { level, .. } if level == 2.0));
+    assert!(matches!(frame[1], DrawCommand::ScreenFrame { .. }));
+    assert!(matches!(frame[2], DrawCommand::Letter { .. }));
+    assert_eq!(
+        frame[3],
+        DrawCommand::Badge {
+            center: (0.0, 0.0),
+            radius: state.radius,
+            label: "x3".to_string(),
+            stroke: (
+                state.stroke_r,
+                state.stroke_g,
+                state.stroke_b,
+                state.stroke_a
+            ),
+            halo: false,
+        }
+    );
+    assert_eq!(frame[4], DrawCommand::DebugLabel("16.7 ms".to_string()));
+
+    // Hidden: only the magnified screen is drawn
+    let hidden = FrameInput {
+        visible: false,
+        ..input
+    };
+    assert_eq!(compose_frame(&state, &hidden).len(), 1);
+}
+
+#[test]
+fn frames_fill_and_outline_like_the_renderers() {
+    let state = OverlayState {
+        stroke_a: 0.5,
+        fill_transparency_pct: 50.0,
+        halo: true,
+        click_burst: true,
+        ..OverlayState::default()
+    };
+    // The fill is as see-through as the stroke, then less the transparency
+    match compose_frame(&state, &FrameInput::default()).as_slice() {
+        [DrawCommand::Circle {
+            fill_alpha, halo, ..
+        }] => {
+            assert_eq!(*fill_alpha, 0.25);
+            assert!(*halo);
+        }
+        frame => panic!("unexpected frame: {frame:?}"),
+    }
+
+    // Letters and badges get the outline halo too
+    let input = FrameInput {
+        display_mode: DISPLAY_MODE_LEFT,
+        click_count: 2,
+        ..FrameInput::default()
+    };
+    match compose_frame(&state, &input).as_slice() {
+        [DrawCommand::Letter { halo: letter, .. }, DrawCommand::Badge { halo: badge, .. }] => {
+            assert!(*letter && *badge);
+        }
+        frame => panic!("unexpected frame: {frame:?}"),
+    }
+}
+
+#[test]
+fn laser_frame_carries_the_trail_and_fade() {
+    let state = OverlayState {
+        highlight_style: HIGHLIGHT_STYLE_LASER,
+        ..OverlayState::default()
+    };
+    let segment = TrailSegment {
+        x0: 0.0,
+        y0: 0.0,
+        x1: 5.0,
+        y1: 5.0,
+        alpha: 0.5,
+    };
+    let input = FrameInput {
+        fade_alpha: 0.5,
+        trail: vec![segment],
+        ..FrameInput::default()
+    };
+    match compose_frame(&state, &input).as_slice() {
+        [DrawCommand::Laser { stroke, trail, .. }] => {
+            assert_eq!(stroke.3, state.stroke_a * 0.5);
+            assert_eq!(trail, &[segment]);
+        }
+        frame => panic!("unexpected frame: {frame:?}"),
+    }
+}
+
+#[test]
+fn rainbow_phase_turns_the_stroke_hue() {
+    let state = OverlayState {
+        stroke_r: 1.0,
+        stroke_g: 0.0,
+        stroke_b: 0.0,
+        stroke_a: 0.8,
+        ..OverlayState::default()
+    };
+    let input = FrameInput {
+        rainbow_phase: Some(1.0 / 3.0),
+        ..FrameInput::default()
+    };
+    match compose_frame(&state, &input).as_slice() {
+        [DrawCommand::Circle { stroke, .. }] => {
+            assert!(
+                stroke.0.abs() < 1e-9 && (stroke.1 - 1.0).abs() < 1e-9,
+                "{stroke:?}"
+            );
+            assert_eq!(stroke.3, 0.8);
+        }
+        frame => panic!("unexpected frame: {frame:?}"),
+    }
+}
+
+#[test]
+fn pulse_phase_breathes_the_radius() {
+    let state = OverlayState {
+        radius: 20.0,
+        click_burst: true,
+        pulse: true,
+        pulse_amplitude: 10.0,
+        ..OverlayState::default()
+    };
+    let input = FrameInput {
+        display_mode: DISPLAY_MODE_LEFT,
+        click_count: 2,
+        pulse_phase: Some(0.25),
+        ..FrameInput::default()
+    };
+    match compose_frame(&state, &input).as_slice() {
+        [DrawCommand::Letter { radius, .. }, DrawCommand::Badge { radius: badge, .. }] => {
+            assert!((radius - 22.0).abs() < 1e-9, "{radius}");
+            assert_eq!(radius, badge);
+        }
+        frame => panic!("unexpected frame: {frame:?}"),
+    }
+}
+
+#[test]
+fn speed_scale_grows_the_highlight() {
+    let state = OverlayState {
+        radius: 20.0,
+        speed_size: true,
+        ..OverlayState::default()
+    };
+    let input = FrameInput {
+        speed_scale: 1.5,
+        ..FrameInput::default()
+    };
+    match compose_frame(&state, &input).as_slice() {
+        [DrawCommand::Circle { radius, .. }] => assert!((radius - 30.0).abs() < 1e-9, "{radius}"),
+        frame => panic!("unexpected frame: {frame:?}"),
+    }
+}
+
+#[test]
+fn transition_scales_and_fades_the_highlight() {
+    let state = OverlayState {
+        radius: 20.0,
+        stroke_a: 1.0,
+        ..OverlayState::default()
+    };
+    let input = FrameInput {
+        transition: 0.0,
+        ..FrameInput::default()
+    };
+    match compose_frame(&state, &input).as_slice() {
+        [DrawCommand::Circle { radius, stroke, .. }] => {
+            assert_eq!(*radius, 20.0 * TRANSITION_START_SCALE);
+            assert_eq!(stroke.3, 0.0);
+        }
+        frame => panic!("unexpected frame: {frame:?}"),
+    }
+}
+
+#[test]
+fn hold_ring_is_drawn_only_while_a_button_is_held() {
+    let state = OverlayState {
+        hold_ring: true,
+        ..OverlayState::default()
+    };
+    let held = FrameInput {
+        display_mode: DISPLAY_MODE_LEFT,
+        hold_progress: Some(0.25),
+        ..FrameInput::default()
+    };
+    match compose_frame(&state, &held).as_slice() {
+        [DrawCommand::Letter { .. }, DrawCommand::HoldRing {
+            radius, progress, ..
+        }] => {
+            assert_eq!(*radius, 2.0 * state.radius);
+            assert_eq!(*progress, 0.25);
+        }
+        frame => panic!("unexpected frame: {frame:?}"),
+    }
+    // Letter kept up by the click hold after mouse-up
+    let released = FrameInput {
+        hold_progress: None,
+        ..held.clone()
+    };
+    assert_eq!(compose_frame(&state, &released).len(), 1);
+    let off = OverlayState {
+        hold_ring: false,
+        ..state
+    };
+    assert_eq!(compose_frame(&off, &held).len(), 1);
+}
+
+#[test]
+fn glow_is_passed_to_the_highlight() {
+    let state = OverlayState {
+        glow: GLOW_SHADOW,
+        ..OverlayState::default()
+    };
+    match compose_frame(&state, &FrameInput::default()).as_slice() {
+        [DrawCommand::Circle { glow, .. }] => assert_eq!(*glow, GLOW_SHADOW),
+        frame => panic!("unexpected frame: {frame:?}"),
+    }
+}
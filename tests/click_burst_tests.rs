@@ -0,0 +1,86 @@
+//! Tests for multi-click burst counting.
+
+use lumbus::model::click_burst::{badge_label, ClickBurst};
+use lumbus::model::constants::*;
+
+const INTERVAL: f64 = DEFAULT_CLICK_BURST_MS / 1000.0;
+
+// === Counting Tests ===
+
+#[test]
+fn new_counter_has_no_burst() {
+    assert_eq!(ClickBurst::new().count(), 0);
+}
+
+#[test]
+fn rapid_presses_of_same_button_accumulate() {
+    let mut b = ClickBurst::new();
+    assert_eq!(b.press(DISPLAY_MODE_LEFT, 10.0, 10.0, 0.0, INTERVAL), 1);
+    assert_eq!(b.press(DISPLAY_MODE_LEFT, 10.0, 10.0, 0.2, INTERVAL), 2);
+    assert_eq!(b.press(DISPLAY_MODE_LEFT, 11.0, 10.0, 0.4, INTERVAL), 3);
+    assert_eq!(b.count(), 3);
+}
+
+#[test]
+fn slow_press_starts_new_burst() {
+    let mut b = ClickBurst::new();
+    b.press(DISPLAY_MODE_LEFT, 0.0, 0.0, 0.0, INTERVAL);
+    assert_eq!(
+        b.press(DISPLAY_MODE_LEFT, 0.0, 0.0, INTERVAL + 0.01, INTERVAL),
+        1
+    );
+}
+
+#[test]
+fn other_button_starts_new_burst() {
+    let mut b = ClickBurst::new();
+    b.press(DISPLAY_MODE_LEFT, 0.0, 0.0, 0.0, INTERVAL);
+    assert_eq!(b.press(DISPLAY_MODE_RIGHT, 0.0, 0.0, 0.1, INTERVAL), 1);
+}
+
+#[test]
+fn distant_press_starts_new_burst() {
+    let mut b = ClickBurst::new();
+    b.press(DISPLAY_MODE_LEFT, 0.0, 0.0, 0.0, INTERVAL);
+    let far = CLICK_BURST_SLOP_PX + 1.0;
+    assert_eq!(b.press(DISPLAY_MODE_LEFT, far, 0.0, 0.1, INTERVAL), 1);
+}
+
+#[test]
+fn interval_is_respected() {
+    let mut b = ClickBurst::new();
+    b.press(DISPLAY_MODE_LEFT, 0.0, 0.0, 0.0, 1.0);
+    assert_eq!(b.press(DISPLAY_MODE_LEFT, 0.0, 0.0, 0.9, 1.0), 2);
+}
+
+#[test]
+fn count_is_capped() {
+    let mut b = ClickBurst::new();
+    for i in 0..(CLICK_BURST_MAX_COUNT + 10) {
+        b.press(DISPLAY_MODE_LEFT, 0.0, 0.0, i as f64 * 0.1, INTERVAL);
+    }
+    assert_eq!(b.count(), CLICK_BURST_MAX_COUNT);
+}
+
+#[test]
+fn reset_forgets_burst() {
+    let mut b = ClickBurst::new();
+    b.press(DISPLAY_MODE_LEFT, 0.0, 0.0, 0.0, INTERVAL);
+    b.reset();
+    assert_eq!(b.count(), 0);
+    assert_eq!(b.press(DISPLAY_MODE_LEFT, 0.0, 0.0, 0.1, INTERVAL), 1);
+}
+
+// === Badge Tests ===
+
+#[test]
+fn badge_hidden_for_single_click() {
+    assert_eq!(badge_label(0), None);
+    assert_eq!(badge_label(1), None);
+}
+
+#[test]
+fn badge_shows_burst_length() {
+    assert_eq!(badge_label(2).as_deref(), Some("x2"));
+    assert_eq!(badge_label(3).as_deref(), Some("x3"));
+}
@@ -0,0 +1,83 @@
+//! Tests for the click indicator hold after mouse-up.
+
+use lumbus::model::click_hold::{hold_progress, ClickHold};
+use lumbus::model::constants::*;
+
+const HOLD_MS: f64 = 300.0;
+
+#[test]
+fn new_tracker_shows_the_circle() {
+    assert_eq!(ClickHold::new().mode(0.0, HOLD_MS), DISPLAY_MODE_CIRCLE);
+}
+
+#[test]
+fn held_button_is_shown() {
+    let mut h = ClickHold::new();
+    h.press(DISPLAY_MODE_RIGHT, 9.0);
+    assert_eq!(h.mode(10.0, HOLD_MS), DISPLAY_MODE_RIGHT);
+}
+
+#[test]
+fn released_button_stays_up_for_the_hold_time() {
+    let mut h = ClickHold::new();
+    h.press(DISPLAY_MODE_LEFT, 0.9);
+    h.release(1.0);
+    assert_eq!(h.mode(1.0, HOLD_MS), DISPLAY_MODE_LEFT);
+    assert_eq!(h.mode(1.29, HOLD_MS), DISPLAY_MODE_LEFT);
+    assert_eq!(h.mode(1.31, HOLD_MS), DISPLAY_MODE_CIRCLE);
+}
+
+#[test]
+fn zero_hold_snaps_back_on_release() {
+    let mut h = ClickHold::new();
+    h.press(DISPLAY_MODE_LEFT, 0.9);
+    h.release(1.0);
+    assert_eq!(h.mode(1.0, 0.0), DISPLAY_MODE_CIRCLE);
+}
+
+#[test]
+fn new_press_replaces_the_hold() {
+    let mut h = ClickHold::new();
+    h.press(DISPLAY_MODE_LEFT, 0.9);
+    h.release(1.0);
+    h.press(DISPLAY_MODE_RIGHT, 1.05);
+    assert_eq!(h.mode(1.1, HOLD_MS), DISPLAY_MODE_RIGHT);
+    h.release(1.2);
+    assert_eq!(h.mode(1.3, HOLD_MS), DISPLAY_MODE_RIGHT);
+}
+
+#[test]
+fn release_without_press_keeps_the_circle() {
+    let mut h = ClickHold::new();
+    h.release(1.0);
+    assert_eq!(h.mode(1.1, HOLD_MS), DISPLAY_MODE_CIRCLE);
+}
+
+#[test]
+fn reset_forgets_the_buttons() {
+    let mut h = ClickHold::new();
+    h.press(DISPLAY_MODE_LEFT, 0.9);
+    h.release(1.0);
+    h.reset();
+    assert_eq!(h.mode(1.1, HOLD_MS), DISPLAY_MODE_CIRCLE);
+}
+
+// === Hold Ring Tests ===
+
+#[test]
+fn held_secs_counts_from_the_press() {
+    let mut h = ClickHold::new();
+    assert_eq!(h.held_secs(1.0), None);
+    h.press(DISPLAY_MODE_LEFT, 1.0);
+    assert_eq!(h.held_secs(1.5), Some(0.5));
+    h.release(1.6);
+    assert_eq!(h.held_secs(1.7), None);
+}
+
+#[test]
+fn hold_progress_fills_over_the_ring_time() {
+    assert_eq!(hold_progress(0.0, DEFAULT_HOLD_RING_MS), 0.0);
+    assert_eq!(hold_progress(0.5, DEFAULT_HOLD_RING_MS), 0.5);
+    assert_eq!(hold_progress(2.0, DEFAULT_HOLD_RING_MS), 1.0);
+    assert_eq!(hold_progress(0.1, 0.0), 1.0);
+}
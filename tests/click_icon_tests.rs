@@ -0,0 +1,29 @@
+//! Tests for the icons drawn on clicks instead of the letters.
+
+use std::collections::HashSet;
+
+use lumbus::model::click_icon::{click_icon, CLICK_ICONS};
+use lumbus::model::constants::*;
+
+#[test]
+fn letters_have_no_icon() {
+    assert_eq!(click_icon(CLICK_ICON_NONE), None);
+    assert_eq!(click_icon(-1), None);
+    assert_eq!(click_icon(99), None);
+}
+
+#[test]
+fn every_icon_is_found_by_its_id() {
+    for icon in &CLICK_ICONS {
+        assert_eq!(click_icon(icon.id), Some(icon));
+    }
+    assert_eq!(click_icon(CLICK_ICON_STAR).unwrap().symbol, "star.fill");
+}
+
+#[test]
+fn icons_are_distinct_private_use_glyphs() {
+    let glyphs: HashSet<char> = CLICK_ICONS.iter().map(|icon| icon.glyph).collect();
+    assert_eq!(glyphs.len(), CLICK_ICONS.len());
+    // Segoe Fluent Icons and MDL2 Assets keep their glyphs in the Private Use Area
+    assert!(glyphs.iter().all(|g| ('\u{E000}'..='\u{F8FF}').contains(g)));
+}
@@ -0,0 +1,133 @@
+//! Tests for the click log used in usability testing.
+
+use lumbus::model::click_log::{button_name, default_file_name};
+use lumbus::model::constants::*;
+use lumbus::model::{ClickLog, ClickLogFormat, ClickRecord};
+
+fn click(t: f64, button: i32) -> ClickRecord {
+    ClickRecord {
+        timestamp: t,
+        x: 100.0,
+        y: 200.5,
+        button,
+        display: "0_0_1920_1080".to_string(),
+    }
+}
+
+// === Recording Tests ===
+
+#[test]
+fn new_log_is_stopped_and_empty() {
+    let log = ClickLog::new();
+    assert!(!log.is_recording());
+    assert!(log.is_empty());
+}
+
+#[test]
+fn clicks_are_ignored_while_stopped() {
+    let mut log = ClickLog::new();
+    assert!(!log.record(click(1.0, DISPLAY_MODE_LEFT)));
+    assert!(log.is_empty());
+}
+
+#[test]
+fn clicks_are_stored_while_recording() {
+    let mut log = ClickLog::new();
+    log.start(0.0);
+    assert!(log.record(click(1.0, DISPLAY_MODE_LEFT)));
+    assert!(log.record(click(2.0, DISPLAY_MODE_RIGHT)));
+    assert_eq!(log.len(), 2);
+}
+
+#[test]
+fn stop_keeps_clicks_for_export() {
+    let mut log = ClickLog::new();
+    log.start(0.0);
+    log.record(click(1.0, DISPLAY_MODE_LEFT));
+    log.stop();
+    assert!(!log.is_recording());
+    assert_eq!(log.len(), 1);
+}
+
+#[test]
+fn start_discards_previous_recording() {
+    let mut log = ClickLog::new();
+    log.start(0.0);
+    log.record(click(1.0, DISPLAY_MODE_LEFT));
+    log.start(5.0);
+    assert!(log.is_empty());
+}
+
+#[test]
+fn log_drops_oldest_clicks_at_capacity() {
+    let mut log = ClickLog::with_capacity(3);
+    log.start(0.0);
+    for i in 0..5 {
+        log.record(click(i as f64, DISPLAY_MODE_LEFT));
+    }
+    assert_eq!(log.len(), 3);
+    let first = log.records().next().unwrap();
+    assert_eq!(first.timestamp, 2.0);
+}
+
+#[test]
+fn toggle_starts_and_stops_recording() {
+    let mut log = ClickLog::new();
+    assert!(log.toggle(0.0));
+    log.record(click(1.0, DISPLAY_MODE_LEFT));
+    assert!(!log.toggle(2.0));
+    assert_eq!(log.len(), 1);
+    assert!(log.toggle(3.0));
+    assert!(log.is_empty());
+}
+
+// === Export Tests ===
+
+#[test]
+fn csv_has_header_and_one_row_per_click() {
+    let mut log = ClickLog::new();
+    log.start(10.0);
+    log.record(click(12.5, DISPLAY_MODE_LEFT));
+    let csv = log.to_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "timestamp,elapsed,x,y,button,display");
+    assert_eq!(lines[1], "12.500,2.500,100.0,200.5,left,0_0_1920_1080");
+    assert_eq!(lines.len(), 2);
+}
+
+#[test]
+fn json_lists_click_objects() {
+    let mut log = ClickLog::new();
+    log.start(10.0);
+    log.record(click(12.5, DISPLAY_MODE_RIGHT));
+    let json = log.to_json();
+    assert!(json.starts_with('['));
+    assert!(json.contains("\"button\": \"right\""));
+    assert!(json.contains("\"elapsed\": 2.500"));
+    assert!(json.contains("\"display\": \"0_0_1920_1080\""));
+}
+
+#[test]
+fn json_of_empty_log_is_empty_array() {
+    assert_eq!(ClickLog::new().to_json().trim(), "[]");
+}
+
+#[test]
+fn export_dispatches_on_format() {
+    let mut log = ClickLog::new();
+    log.start(0.0);
+    log.record(click(1.0, DISPLAY_MODE_LEFT));
+    assert_eq!(log.export(ClickLogFormat::Csv), log.to_csv());
+    assert_eq!(log.export(ClickLogFormat::Json), log.to_json());
+}
+
+#[test]
+fn button_names_and_file_names() {
+    assert_eq!(button_name(DISPLAY_MODE_LEFT), "left");
+    assert_eq!(button_name(DISPLAY_MODE_RIGHT), "right");
+    assert_eq!(default_file_name(ClickLogFormat::Csv), "lumbus-clicks.csv");
+    assert_eq!(
+        default_file_name(ClickLogFormat::Json),
+        "lumbus-clicks.json"
+    );
+}
@@ -0,0 +1,31 @@
+//! Tests for the pixel color sampler.
+
+use lumbus::model::color_sampler::{
+    from_bgra, from_unit, pack_rgb, sample_hex, to_unit, unpack_rgb,
+};
+
+#[test]
+fn hex_is_uppercase_with_a_hash() {
+    assert_eq!(sample_hex((30, 144, 255)), "#1E90FF");
+    assert_eq!(sample_hex((0, 0, 0)), "#000000");
+}
+
+#[test]
+fn gdi_pixels_are_read_blue_first() {
+    assert_eq!(from_bgra(&[255, 144, 30, 0]), Some((30, 144, 255)));
+    assert_eq!(from_bgra(&[1, 2]), None);
+}
+
+#[test]
+fn cocoa_components_round_to_bytes() {
+    assert_eq!(from_unit(1.0, 0.5, 0.0), (255, 128, 0));
+    assert_eq!(from_unit(1.2, -0.1, 0.2), (255, 0, 51));
+    assert_eq!(to_unit((255, 0, 51)), (1.0, 0.0, 0.2));
+}
+
+#[test]
+fn packed_samples_round_trip() {
+    assert_eq!(pack_rgb((30, 144, 255)), 0x1E90FF);
+    assert_eq!(unpack_rgb(0x1E90FF), Some((30, 144, 255)));
+    assert_eq!(unpack_rgb(-1), None);
+}
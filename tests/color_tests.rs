@@ -0,0 +1,98 @@
+//! Tests for color input parsing.
+
+use lumbus::model::color::{hsb_to_rgb, rgb_to_hsb};
+use lumbus::{color_to_hex, parse_color};
+
+fn approx_eq(a: (f64, f64, f64), b: (f64, f64, f64)) -> bool {
+    (a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9 && (a.2 - b.2).abs() < 1e-9
+}
+
+/// Color as normalized hex, for readable comparisons.
+fn hex(s: &str) -> Option<String> {
+    parse_color(s).map(|(r, g, b, a)| color_to_hex(r, g, b, a))
+}
+
+#[test]
+fn long_hex_forms_are_accepted() {
+    assert_eq!(hex("#FF0080").as_deref(), Some("#FF0080"));
+    assert_eq!(hex("33669980").as_deref(), Some("#33669980"));
+}
+
+#[test]
+fn short_hex_forms_are_expanded() {
+    assert_eq!(hex("#F08").as_deref(), Some("#FF0088"));
+    assert_eq!(hex("#f088").as_deref(), Some("#FF008888"));
+    assert_eq!(hex("abc").as_deref(), Some("#AABBCC"));
+}
+
+#[test]
+fn rgb_functions_are_accepted() {
+    assert_eq!(hex("rgb(255, 0, 128)").as_deref(), Some("#FF0080"));
+    assert_eq!(hex("RGBA(255,0,128,0.5)").as_deref(), Some("#FF008080"));
+    assert_eq!(hex("rgb(100%, 0%, 50%)").as_deref(), Some("#FF0080"));
+    assert_eq!(hex("rgba(0, 0, 0, 25%)").as_deref(), Some("#00000040"));
+    // Space-separated CSS syntax with a slash before alpha
+    assert_eq!(hex("rgb(255 0 128 / 50%)").as_deref(), Some("#FF008080"));
+}
+
+#[test]
+fn out_of_range_components_are_clamped() {
+    assert_eq!(hex("rgb(300, -20, 128)").as_deref(), Some("#FF0080"));
+    assert_eq!(hex("rgba(0, 0, 0, 2)").as_deref(), Some("#000000"));
+}
+
+#[test]
+fn named_colors_are_accepted() {
+    assert_eq!(hex("red").as_deref(), Some("#FF0000"));
+    assert_eq!(hex("  RebeccaPurple ").as_deref(), Some("#663399"));
+    assert_eq!(hex("lightgoldenrodyellow").as_deref(), Some("#FAFAD2"));
+    assert_eq!(hex("transparent").as_deref(), Some("#00000000"));
+}
+
+#[test]
+fn invalid_colors_are_rejected() {
+    for text in [
+        "",
+        "#GG0000",
+        "#FF00F",
+        "#FF00FF0000",
+        "rgb(1, 2)",
+        "rgb(1, 2, 3, 4, 5)",
+        "rgb(a, b, c)",
+        "rgb(1, 2, 3",
+        "notacolor",
+    ] {
+        assert_eq!(parse_color(text), None, "{text:?}");
+    }
+}
+
+#[test]
+fn primary_colors_have_their_hue() {
+    assert!(approx_eq(rgb_to_hsb(1.0, 0.0, 0.0), (0.0, 1.0, 1.0)));
+    assert!(approx_eq(rgb_to_hsb(0.0, 1.0, 0.0), (120.0, 1.0, 1.0)));
+    assert!(approx_eq(rgb_to_hsb(0.0, 0.0, 1.0), (240.0, 1.0, 1.0)));
+    assert!(approx_eq(rgb_to_hsb(1.0, 0.0, 1.0), (300.0, 1.0, 1.0)));
+    assert!(approx_eq(rgb_to_hsb(0.5, 0.5, 0.5), (0.0, 0.0, 0.5)));
+    assert!(approx_eq(rgb_to_hsb(0.0, 0.0, 0.0), (0.0, 0.0, 0.0)));
+}
+
+#[test]
+fn hsb_converts_back_to_the_same_rgb() {
+    for rgb in [
+        (1.0, 0.5, 0.0),
+        (0.2, 0.4, 0.6),
+        (0.9, 0.1, 0.3),
+        (0.0, 0.75, 0.75),
+        (0.3, 0.3, 0.3),
+    ] {
+        let (h, s, b) = rgb_to_hsb(rgb.0, rgb.1, rgb.2);
+        assert!(approx_eq(hsb_to_rgb(h, s, b), rgb), "{rgb:?}");
+    }
+}
+
+#[test]
+fn hsb_input_is_wrapped_and_clamped() {
+    assert!(approx_eq(hsb_to_rgb(360.0, 1.0, 1.0), (1.0, 0.0, 0.0)));
+    assert!(approx_eq(hsb_to_rgb(-120.0, 1.0, 1.0), (0.0, 0.0, 1.0)));
+    assert!(approx_eq(hsb_to_rgb(60.0, 2.0, 1.5), (1.0, 1.0, 0.0)));
+}
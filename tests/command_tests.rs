@@ -0,0 +1,141 @@
+//! Tests for the settings commands shared by every frontend.
+
+use lumbus::backend::{
+    run_command, AppCommand, CommandError, CommandTarget, HeadlessBackend, OverlayBackend,
+};
+use lumbus::model::constants::*;
+use lumbus::model::{HotkeyPlatform, OverlayState};
+
+/// Target that records each step instead of storing anything.
+#[derive(Default)]
+struct Recorder {
+    steps: Vec<String>,
+}
+
+impl CommandTarget for Recorder {
+    fn write_number(&mut self, key: &str, value: f64) {
+        self.steps.push(format!("{key}={value}"));
+    }
+
+    fn write_int(&mut self, key: &str, value: i32) {
+        self.steps.push(format!("{key}:{value}"));
+    }
+
+    fn write_text(&mut self, key: &str, text: &str) {
+        self.steps.push(format!("{key}='{text}'"));
+    }
+
+    fn write_frame_color(&mut self, (r, g, b, a): (f64, f64, f64, f64)) {
+        self.steps.push(format!("frame={r},{g},{b},{a}"));
+    }
+
+    fn persist(&mut self) {
+        self.steps.push("persist".into());
+    }
+
+    fn broadcast(&mut self) {
+        self.steps.push("broadcast".into());
+    }
+}
+
+#[test]
+fn commands_are_applied_then_persisted_then_broadcast() {
+    let mut target = Recorder::default();
+    let command = AppCommand::Batch(vec![
+        AppCommand::number(PREF_RADIUS, 60.0),
+        AppCommand::int(PREF_HALO, 1),
+        AppCommand::text(PREF_LETTER_FONT, "Menlo"),
+    ]);
+    run_command(&mut target, command).unwrap();
+    assert_eq!(
+        target.steps,
+        [
+            "radius=60",
+            "halo:1",
+            "letterFont='Menlo'",
+            "persist",
+            "broadcast"
+        ]
+    );
+}
+
+#[test]
+fn numbers_are_clamped_to_their_limits() {
+    let command = AppCommand::number(PREF_RADIUS, 1e9).validate().unwrap();
+    assert_eq!(command, AppCommand::number(PREF_RADIUS, MAX_RADIUS));
+
+    let command = AppCommand::number(PREF_STROKE_R, f64::NAN)
+        .validate()
+        .unwrap();
+    assert!(matches!(command, AppCommand::SetNumber(_, v) if v.is_finite()));
+}
+
+#[test]
+fn unknown_keys_refuse_the_whole_batch() {
+    let mut target = Recorder::default();
+    let command = AppCommand::numbers([(PREF_RADIUS, 60.0), ("noSuchKey", 1.0)]);
+    assert_eq!(
+        run_command(&mut target, command),
+        Err(CommandError::UnknownNumber("noSuchKey".into()))
+    );
+    assert_eq!(
+        run_command(&mut target, AppCommand::text(PREF_RADIUS, "60")),
+        Err(CommandError::UnknownText(PREF_RADIUS.into()))
+    );
+    assert!(target.steps.is_empty());
+}
+
+#[test]
+fn frame_colors_are_clamped_to_unit_components() {
+    let mut target = Recorder::default();
+    let command = AppCommand::SetFrameColor((-1.0, 0.5, 3.0, f64::INFINITY));
+    run_command(&mut target, command).unwrap();
+    let (_, _, _, a) = DEFAULT_SCREEN_FRAME_COLOR;
+    assert_eq!(
+        target.steps,
+        [
+            format!("frame=0,0.5,1,{a}"),
+            "persist".into(),
+            "broadcast".into()
+        ]
+    );
+}
+
+#[test]
+fn restore_defaults_writes_the_defaults_once() {
+    let mut backend = HeadlessBackend::new(HotkeyPlatform::MacOs);
+    let target = &mut backend as &mut dyn OverlayBackend;
+    run_command(
+        target,
+        AppCommand::numbers([(PREF_RADIUS, 90.0), (PREF_BORDER, 9.0)]),
+    )
+    .unwrap();
+    let flushes = backend.flushes();
+
+    run_command(
+        &mut backend as &mut dyn OverlayBackend,
+        AppCommand::RestoreDefaults,
+    )
+    .unwrap();
+    assert_eq!(backend.flushes(), flushes + 1);
+    assert_eq!(backend.state(), &OverlayState::default());
+}
+
+#[test]
+fn a_batch_is_saved_and_applied_once() {
+    let mut backend = HeadlessBackend::new(HotkeyPlatform::MacOs);
+    let (flushes, refreshes) = (backend.flushes(), backend.menu_refreshes());
+    let command = AppCommand::numbers([
+        (PREF_STROKE_R, 0.1),
+        (PREF_STROKE_G, 0.2),
+        (PREF_STROKE_B, 2.0),
+    ]);
+    run_command(&mut backend as &mut dyn OverlayBackend, command).unwrap();
+    assert_eq!(backend.flushes(), flushes + 1);
+    assert_eq!(backend.menu_refreshes(), refreshes + 1);
+    let state = backend.state();
+    assert_eq!(
+        (state.stroke_r, state.stroke_g, state.stroke_b),
+        (0.1, 0.2, 1.0)
+    );
+}
@@ -0,0 +1,193 @@
+//! Tests for the companion protocol.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+use lumbus::control::ControlServer;
+use lumbus::events::{AppEvent, EventBus};
+use lumbus::model::constants::MAX_COMPANION_SESSIONS;
+use lumbus::model::{
+    is_http_request, state_changes, state_lines, CompanionCommand, CompanionSession, ControlState,
+};
+
+const TOKEN: &str = "secret";
+
+fn state() -> ControlState {
+    ControlState {
+        visible: true,
+        locked: false,
+        hotkey_profile: "standard".into(),
+        settings: Vec::new(),
+    }
+}
+
+fn authorized() -> CompanionSession {
+    let mut session = CompanionSession::new();
+    session.handle("AUTH secret", TOKEN, &state());
+    session
+}
+
+#[test]
+fn parse_commands() {
+    assert_eq!(
+        CompanionCommand::parse("auth abc"),
+        Ok(CompanionCommand::Auth("abc".into()))
+    );
+    assert_eq!(
+        CompanionCommand::parse("PROFILE Presentation"),
+        Ok(CompanionCommand::Profile("presentation".into()))
+    );
+    assert_eq!(
+        CompanionCommand::parse("SET radius 60"),
+        Ok(CompanionCommand::Set("radius".into(), 60.0))
+    );
+    assert!(CompanionCommand::parse("SET radius big").is_err());
+    assert!(CompanionCommand::parse("TOGGLE now").is_err());
+    assert!(CompanionCommand::parse("DANCE").is_err());
+}
+
+#[test]
+fn first_line_tells_http_from_companion() {
+    assert!(is_http_request("GET /state HTTP/1.1\r\n"));
+    assert!(!is_http_request("AUTH secret\n"));
+}
+
+#[test]
+fn commands_need_auth_first() {
+    let mut session = CompanionSession::new();
+    let reply = session.handle("TOGGLE", TOKEN, &state());
+    assert_eq!(reply.lines, vec!["ERR AUTH first"]);
+    assert!(reply.events.is_empty());
+    assert!(reply.close);
+
+    let mut session = CompanionSession::new();
+    let reply = session.handle("AUTH nope", TOKEN, &state());
+    assert!(reply.close);
+    assert_eq!(
+        session.handle("STATE", TOKEN, &state()).lines,
+        vec!["ERR AUTH first"]
+    );
+}
+
+#[test]
+fn watch_sends_the_state_and_subscribes() {
+    let mut session = authorized();
+    assert!(!session.is_watching());
+    let reply = session.handle("WATCH", TOKEN, &state());
+    assert_eq!(
+        reply.lines,
+        vec!["ENABLED on", "PROFILE standard", "LOCKED off", "OK"]
+    );
+    assert!(session.is_watching());
+}
+
+#[test]
+fn commands_publish_events() {
+    let mut session = authorized();
+    assert_eq!(
+        session.handle("TOGGLE", TOKEN, &state()).events,
+        vec![AppEvent::ToggleOverlay]
+    );
+    assert_eq!(
+        session
+            .handle("PROFILE presentation", TOKEN, &state())
+            .events,
+        vec![AppEvent::SelectHotkeyProfile("presentation".into())]
+    );
+    assert_eq!(
+        session.handle("PROFILE demo", TOKEN, &state()).lines,
+        vec!["ERR unknown profile: demo"]
+    );
+    assert_eq!(
+        session.handle("SET radius 60", TOKEN, &state()).events,
+        vec![AppEvent::SetPreference("radius".into(), 60.0)]
+    );
+    let locked = ControlState {
+        locked: true,
+        ..state()
+    };
+    assert!(session
+        .handle("SET radius 60", TOKEN, &locked)
+        .events
+        .is_empty());
+    assert!(session.handle("BYE", TOKEN, &state()).close);
+}
+
+#[test]
+fn state_changes_lists_only_what_changed() {
+    let hidden = ControlState {
+        visible: false,
+        ..state()
+    };
+    assert_eq!(state_changes(&state(), &hidden), vec!["ENABLED off"]);
+    assert!(state_changes(&state(), &state()).is_empty());
+    assert_eq!(state_lines(&hidden)[0], "ENABLED off");
+}
+
+#[test]
+fn session_over_the_socket_gets_pushes() {
+    let bus = EventBus::new();
+    let server = ControlServer::start(0, TOKEN, bus.publisher()).expect("bind loopback");
+    server.set_state(state());
+
+    let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut read_line = || {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        line.trim_end().to_string()
+    };
+
+    stream.write_all(b"AUTH secret\nWATCH\nTOGGLE\n").unwrap();
+    assert_eq!(read_line(), "OK");
+    assert_eq!(read_line(), "ENABLED on");
+    assert_eq!(read_line(), "PROFILE standard");
+    assert_eq!(read_line(), "LOCKED off");
+    assert_eq!(read_line(), "OK");
+    assert_eq!(read_line(), "OK");
+    assert_eq!(bus.drain(), vec![AppEvent::ToggleOverlay]);
+
+    // The app applies the toggle and reports its new state
+    server.set_state(ControlState {
+        visible: false,
+        ..state()
+    });
+    assert_eq!(read_line(), "ENABLED off");
+}
+
+#[test]
+fn sessions_past_the_limit_are_refused() {
+    let bus = EventBus::new();
+    let server = ControlServer::start(0, TOKEN, bus.publisher()).expect("bind loopback");
+    server.set_state(state());
+
+    let connect = || {
+        let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        stream.write_all(b"AUTH secret\n").unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        (stream, line.trim_end().to_string())
+    };
+
+    let open: Vec<_> = (0..MAX_COMPANION_SESSIONS).map(|_| connect()).collect();
+    assert!(open.iter().all(|(_, reply)| reply == "OK"));
+    assert_eq!(connect().1, "ERR too many sessions");
+
+    // Leaving makes room again
+    drop(open);
+    let reply = (0..50)
+        .map(|_| {
+            std::thread::sleep(Duration::from_millis(20));
+            connect().1
+        })
+        .find(|reply| reply == "OK");
+    assert_eq!(reply.as_deref(), Some("OK"));
+}
@@ -0,0 +1,66 @@
+//! Tests for the high-contrast preset and outline halo.
+
+use lumbus::model::constants::*;
+use lumbus::model::contrast::{halo_line_width, relative_luminance};
+use lumbus::model::{halo_color, high_contrast_preset, ManagedConfig, HIGH_CONTRAST_KEYS};
+
+fn approx_eq(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-6
+}
+
+// === Halo Color Tests ===
+
+#[test]
+fn luminance_spans_black_to_white() {
+    assert!(approx_eq(relative_luminance(0.0, 0.0, 0.0), 0.0));
+    assert!(approx_eq(relative_luminance(1.0, 1.0, 1.0), 1.0));
+}
+
+#[test]
+fn light_strokes_get_a_black_halo() {
+    let (r, g, b, _) = HIGH_CONTRAST_COLOR;
+    assert_eq!(halo_color(r, g, b), (0.0, 0.0, 0.0));
+    assert_eq!(halo_color(1.0, 1.0, 1.0), (0.0, 0.0, 0.0));
+    let (r, g, b, _) = DEFAULT_COLOR;
+    assert_eq!(halo_color(r, g, b), (0.0, 0.0, 0.0));
+}
+
+#[test]
+fn dark_strokes_get_a_white_halo() {
+    assert_eq!(halo_color(0.0, 0.0, 0.0), (1.0, 1.0, 1.0));
+    assert_eq!(halo_color(0.0, 0.0, 0.8), (1.0, 1.0, 1.0));
+}
+
+#[test]
+fn halo_extends_beyond_the_stroke_on_both_sides() {
+    assert!(approx_eq(halo_line_width(4.0), 4.0 + 2.0 * HALO_WIDTH));
+}
+
+// === Preset Tests ===
+
+#[test]
+fn preset_covers_its_keys_in_order() {
+    let keys: Vec<_> = high_contrast_preset().map(|(k, _)| k).collect();
+    assert_eq!(keys, HIGH_CONTRAST_KEYS);
+}
+
+#[test]
+fn preset_is_a_thick_unfilled_yellow_ring_with_halo() {
+    let preset: Vec<_> = high_contrast_preset().collect();
+    let value = |key: &str| preset.iter().find(|(k, _)| *k == key).unwrap().1;
+    assert!(approx_eq(value(PREF_STROKE_R), HIGH_CONTRAST_COLOR.0));
+    assert!(approx_eq(value(PREF_STROKE_G), HIGH_CONTRAST_COLOR.1));
+    assert!(approx_eq(value(PREF_STROKE_B), HIGH_CONTRAST_COLOR.2));
+    assert!(approx_eq(value(PREF_STROKE_A), 1.0));
+    assert!(value(PREF_BORDER) > DEFAULT_BORDER_WIDTH);
+    assert!(value(PREF_BORDER) <= MAX_BORDER);
+    assert!(approx_eq(value(PREF_FILL_TRANSPARENCY), MAX_TRANSPARENCY));
+    assert!(approx_eq(value(PREF_HALO), 1.0));
+}
+
+#[test]
+fn preset_keys_can_be_managed() {
+    for key in HIGH_CONTRAST_KEYS {
+        assert!(ManagedConfig::is_managed_key(key), "{key}");
+    }
+}
@@ -0,0 +1,201 @@
+//! Tests for the local control endpoint.
+
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::time::{Duration, Instant};
+
+use lumbus::control::{new_token, ControlServer};
+use lumbus::events::{AppEvent, EventBus};
+use lumbus::model::{body_length, handle_request, parse_request, ControlRequest, ControlState};
+
+const TOKEN: &str = "secret";
+
+fn request(method: &str, path: &str, body: &str) -> ControlRequest {
+    ControlRequest {
+        method: method.into(),
+        path: path.into(),
+        token: Some(TOKEN.into()),
+        body: body.into(),
+    }
+}
+
+fn state() -> ControlState {
+    ControlState {
+        visible: true,
+        locked: false,
+        hotkey_profile: "standard".into(),
+        settings: vec![("radius".into(), 40.0)],
+    }
+}
+
+#[test]
+fn parse_request_reads_method_path_token_and_body() {
+    let raw = "post /settings?x=1 HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer abc\r\nContent-Length: 2\r\n\r\n{}";
+    assert_eq!(body_length(raw), Some(2));
+    assert_eq!(
+        parse_request(raw),
+        Some(ControlRequest {
+            method: "POST".into(),
+            path: "/settings".into(),
+            token: Some("abc".into()),
+            body: "{}".into(),
+        })
+    );
+}
+
+#[test]
+fn headers_are_incomplete_until_the_blank_line() {
+    assert_eq!(body_length("GET /state HTTP/1.1\r\nHost: x\r\n"), None);
+    assert_eq!(body_length("GET /state HTTP/1.1\r\n\r\n"), Some(0));
+    assert_eq!(parse_request("GET /state HTTP/1.1\r\n"), None);
+}
+
+#[test]
+fn requests_need_the_token() {
+    let mut req = request("GET", "/state", "");
+    req.token = None;
+    assert_eq!(handle_request(&req, TOKEN, &state()).status, 401);
+    req.token = Some("wrong!".into());
+    assert_eq!(handle_request(&req, TOKEN, &state()).status, 401);
+    // No token configured: nothing is accepted
+    req.token = Some(String::new());
+    assert_eq!(handle_request(&req, "", &state()).status, 401);
+}
+
+#[test]
+fn state_reports_visibility_lock_and_profile() {
+    let response = handle_request(&request("GET", "/state", ""), TOKEN, &state());
+    assert_eq!(response.status, 200);
+    assert_eq!(
+        response.body,
+        r#"{"hotkeyProfile":"standard","locked":false,"visible":true}"#
+    );
+    assert!(response.events.is_empty());
+}
+
+#[test]
+fn toggle_publishes_toggle_overlay() {
+    let response = handle_request(&request("POST", "/toggle", ""), TOKEN, &state());
+    assert_eq!(response.status, 202);
+    assert_eq!(response.events, vec![AppEvent::ToggleOverlay]);
+}
+
+#[test]
+fn settings_lists_and_sets_overlay_settings() {
+    let response = handle_request(&request("GET", "/settings", ""), TOKEN, &state());
+    assert_eq!(response.body, r#"{"radius":40.0}"#);
+
+    let response = handle_request(
+        &request("PATCH", "/settings", r#"{"radius": 60, "halo": true}"#),
+        TOKEN,
+        &state(),
+    );
+    assert_eq!(response.status, 202);
+    assert_eq!(
+        response.events,
+        vec![
+            AppEvent::SetPreference("halo".into(), 1.0),
+            AppEvent::SetPreference("radius".into(), 60.0),
+        ]
+    );
+}
+
+#[test]
+fn settings_rejects_unknown_keys_bad_bodies_and_the_lock() {
+    for body in [
+        r#"{"controlPort": 80}"#,
+        r#"{"radius": "big"}"#,
+        "[1]",
+        "nope",
+    ] {
+        let response = handle_request(&request("POST", "/settings", body), TOKEN, &state());
+        assert_eq!(response.status, 400, "{body}");
+        assert!(response.events.is_empty());
+    }
+    let locked = ControlState {
+        locked: true,
+        ..state()
+    };
+    let response = handle_request(
+        &request("POST", "/settings", r#"{"radius": 60}"#),
+        TOKEN,
+        &locked,
+    );
+    assert_eq!(response.status, 400);
+    assert!(response.events.is_empty());
+}
+
+#[test]
+fn unknown_routes_and_methods() {
+    assert_eq!(
+        handle_request(&request("GET", "/quit", ""), TOKEN, &state()).status,
+        404
+    );
+    assert_eq!(
+        handle_request(&request("GET", "/toggle", ""), TOKEN, &state()).status,
+        405
+    );
+}
+
+#[test]
+fn new_tokens_are_long_and_distinct() {
+    let token = new_token().unwrap();
+    assert_eq!(token.len(), 32);
+    assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+    assert_ne!(token, new_token().unwrap());
+}
+
+#[test]
+fn server_answers_over_http_and_publishes_events() {
+    let bus = EventBus::new();
+    let server = ControlServer::start(0, TOKEN, bus.publisher()).expect("bind loopback");
+    server.set_state(state());
+
+    let send = |raw: &str| {
+        let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+        stream.write_all(raw.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    };
+
+    let response = send("GET /state HTTP/1.1\r\nAuthorization: Bearer secret\r\n\r\n");
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{response}");
+    assert!(response.ends_with(r#""visible":true}"#), "{response}");
+
+    let response =
+        send("POST /toggle HTTP/1.1\r\nAuthorization: Bearer secret\r\nContent-Length: 0\r\n\r\n");
+    assert!(
+        response.starts_with("HTTP/1.1 202 Accepted\r\n"),
+        "{response}"
+    );
+    assert_eq!(bus.drain(), vec![AppEvent::ToggleOverlay]);
+
+    let response = send("POST /toggle HTTP/1.1\r\n\r\n");
+    assert!(
+        response.starts_with("HTTP/1.1 401 Unauthorized\r\n"),
+        "{response}"
+    );
+    assert!(bus.drain().is_empty());
+}
+
+#[test]
+fn slow_client_does_not_hold_up_others() {
+    let bus = EventBus::new();
+    let server = ControlServer::start(0, TOKEN, bus.publisher()).expect("bind loopback");
+    server.set_state(state());
+
+    // Never finishes its first line
+    let mut slow = TcpStream::connect(server.local_addr()).unwrap();
+    slow.write_all(b"GET /sta").unwrap();
+
+    let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+    let started = Instant::now();
+    stream
+        .write_all(b"GET /state HTTP/1.1\r\nAuthorization: Bearer secret\r\n\r\n")
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{response}");
+    assert!(started.elapsed() < Duration::from_secs(1));
+}
@@ -0,0 +1,59 @@
+//! Tests for the cursor coordinates readout.
+
+use lumbus::model::coordinates::coordinates_label;
+use lumbus::model::placement::ScreenRect;
+
+#[test]
+fn single_display_shows_the_global_position() {
+    let screens = [ScreenRect::new(0.0, 0.0, 1920.0, 1080.0)];
+    assert_eq!(
+        coordinates_label((640.7, 360.2), &screens, false),
+        "640, 360"
+    );
+    assert_eq!(coordinates_label((640.0, 360.0), &[], false), "640, 360");
+}
+
+#[test]
+fn second_display_adds_the_local_position() {
+    let screens = [
+        ScreenRect::new(0.0, 0.0, 1920.0, 1080.0),
+        ScreenRect::new(1920.0, 0.0, 1280.0, 1024.0),
+    ];
+    assert_eq!(
+        coordinates_label((2000.0, 100.0), &screens, false),
+        "2000, 100  display 2: 80, 100"
+    );
+    assert_eq!(
+        coordinates_label((10.0, 20.0), &screens, false),
+        "10, 20  display 1: 10, 20"
+    );
+}
+
+#[test]
+fn cocoa_positions_count_from_the_top() {
+    // Secondary display left of the primary, bottoms aligned
+    let screens = [
+        ScreenRect::new(0.0, 0.0, 1440.0, 900.0),
+        ScreenRect::new(-1920.0, 0.0, 1920.0, 1080.0),
+    ];
+    assert_eq!(
+        coordinates_label((100.0, 800.0), &screens, true),
+        "100, 100  display 1: 100, 100"
+    );
+    assert_eq!(
+        coordinates_label((-1900.0, 1000.0), &screens, true),
+        "-1900, -100  display 2: 20, 80"
+    );
+}
+
+#[test]
+fn pointer_between_displays_keeps_the_global_position() {
+    let screens = [
+        ScreenRect::new(0.0, 0.0, 800.0, 600.0),
+        ScreenRect::new(800.0, 0.0, 800.0, 600.0),
+    ];
+    assert_eq!(
+        coordinates_label((900.0, 700.0), &screens, false),
+        "900, 700"
+    );
+}
@@ -0,0 +1,125 @@
+//! Tests for crash reports.
+
+use std::fs;
+
+use lumbus::logging::{append_to_log, write_crash_report};
+use lumbus::model::constants::*;
+use lumbus::model::crash::REDACTED;
+use lumbus::model::{config_snapshot, panic_message, CrashReport, Preferences};
+
+fn report() -> CrashReport {
+    CrashReport {
+        version: "1.2.3".into(),
+        os: "Windows 11 Pro 23H2 (build 22631)".into(),
+        time: 1_700_000_000,
+        thread: "main".into(),
+        message: "index out of bounds".into(),
+        location: "src/model/trail.rs:42:9".into(),
+        backtrace: "   0: lumbus::main\n".into(),
+        config: "radius = 40.0\n".into(),
+    }
+}
+
+// === Report Tests ===
+
+#[test]
+fn report_file_name_uses_the_time() {
+    assert_eq!(report().file_name(), "lumbus-crash-1700000000.txt");
+}
+
+#[test]
+fn report_text_has_panic_backtrace_and_settings() {
+    let text = report().to_string();
+    assert!(text.starts_with("Lumbus 1.2.3 crash report (Windows 11 Pro 23H2 (build 22631))\n"));
+    assert!(text.contains("Thread: main\n"));
+    assert!(text.contains("Panic: index out of bounds\n"));
+    assert!(text.contains("Location: src/model/trail.rs:42:9\n"));
+    assert!(text.contains("Backtrace:\n   0: lumbus::main\n\nSettings:\n"));
+    assert!(text.ends_with("radius = 40.0\n"));
+}
+
+#[test]
+fn report_is_written_to_the_folder() {
+    let dir = std::env::temp_dir()
+        .join(format!("lumbus-crash-test-{}", std::process::id()))
+        .join(CRASH_DIR);
+    let path = write_crash_report(&report(), &dir).unwrap();
+    assert_eq!(path, dir.join("lumbus-crash-1700000000.txt"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), report().to_string());
+    let _ = fs::remove_dir_all(dir.parent().unwrap());
+}
+
+#[test]
+fn report_names_the_days_log_file() {
+    assert_eq!(report().log_file_name(), "lumbus.2023-11-14.log");
+    let leap_day = CrashReport {
+        time: 1_709_164_800,
+        ..report()
+    };
+    assert_eq!(leap_day.log_file_name(), "lumbus.2024-02-29.log");
+}
+
+#[test]
+fn report_log_line_has_time_location_and_message() {
+    assert_eq!(
+        report().log_line(),
+        "2023-11-14T22:13:20Z ERROR lumbus::logging: \
+         Panic at src/model/trail.rs:42:9: index out of bounds thread=main\n"
+    );
+}
+
+#[test]
+fn panic_is_appended_to_the_log_file() {
+    let dir = std::env::temp_dir().join(format!("lumbus-crash-log-test-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let name = report().log_file_name();
+    fs::write(dir.join(&name), "earlier line\n").unwrap();
+    append_to_log(&dir, &name, &report().log_line()).unwrap();
+    assert_eq!(
+        fs::read_to_string(dir.join(&name)).unwrap(),
+        format!("earlier line\n{}", report().log_line())
+    );
+    let _ = fs::remove_dir_all(dir);
+}
+
+// === Panic Message Tests ===
+
+#[test]
+fn panic_message_reads_literals_and_formatted_messages() {
+    let literal = std::panic::catch_unwind(|| panic!("boom")).unwrap_err();
+    assert_eq!(panic_message(&*literal), "boom");
+    let value = 7;
+    let formatted = std::panic::catch_unwind(|| panic!("bad value {}", value)).unwrap_err();
+    assert_eq!(panic_message(&*formatted), "bad value 7");
+    let other = std::panic::catch_unwind(|| std::panic::panic_any(42)).unwrap_err();
+    assert_eq!(panic_message(&*other), "(no message)");
+}
+
+// === Config Snapshot Tests ===
+
+#[test]
+fn snapshot_redacts_colors_and_token() {
+    let prefs = Preferences {
+        stroke_r: 0.25,
+        screen_frame_colors: "0,0,1920,1080=#ff0000ff".into(),
+        control_token: "0123456789abcdef".into(),
+        radius: 55.0,
+        ..Preferences::default()
+    };
+    let snapshot = config_snapshot(&prefs.encode());
+    assert!(snapshot.contains(&format!("stroke_r = \"{}\"", REDACTED)));
+    assert!(snapshot.contains(&format!("stroke_a = \"{}\"", REDACTED)));
+    assert!(snapshot.contains(&format!("screen_frame_colors = \"{}\"", REDACTED)));
+    assert!(snapshot.contains(&format!("control_token = \"{}\"", REDACTED)));
+    assert!(!snapshot.contains("0.25"));
+    assert!(!snapshot.contains("#ff0000ff"));
+    assert!(!snapshot.contains("0123456789abcdef"));
+    assert!(snapshot.contains("radius = 55.0"));
+}
+
+#[test]
+fn snapshot_of_a_missing_or_broken_file_shows_defaults() {
+    let defaults = config_snapshot("");
+    assert!(defaults.contains("hotkey_profile = \"standard\""));
+    assert_eq!(config_snapshot("radius = ["), defaults);
+}
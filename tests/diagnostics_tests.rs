@@ -0,0 +1,125 @@
+//! Tests for the diagnostics flag, report and frame timer.
+
+use lumbus::model::constants::*;
+use lumbus::model::{
+    debug_label, DiagnosticsConfig, DiagnosticsReport, DisplayInfo, FrameTimer, ScreenRect,
+};
+
+fn report() -> DiagnosticsReport {
+    DiagnosticsReport {
+        version: "1.2.3".into(),
+        os: "macOS 14.5".into(),
+        displays: vec![
+            DisplayInfo {
+                id: 1,
+                frame: ScreenRect::new(0.0, 0.0, 1512.0, 982.0),
+                scale: 2.0,
+                primary: true,
+            },
+            DisplayInfo {
+                id: 7,
+                frame: ScreenRect::new(1512.0, 0.0, 2560.0, 1440.0),
+                scale: 1.0,
+                primary: false,
+            },
+        ],
+        permissions: vec![("Accessibility".into(), "granted".into())],
+        hotkey_profile: "standard".into(),
+        hotkeys: vec![
+            ("Ctrl+A".into(), "Toggle overlay".into(), true),
+            ("\u{2318}Q".into(), "Quit".into(), false),
+        ],
+        event_latency: vec![("open_settings".into(), 3, 1.2, 2.5)],
+        config_path: "/tmp/config.toml".into(),
+        log_dir: "/tmp/logs".into(),
+    }
+}
+
+// === Argument Tests ===
+
+#[test]
+fn diagnostics_is_off_without_the_flag() {
+    assert_eq!(DiagnosticsConfig::from_args(["--soak"]), None);
+    assert_eq!(DiagnosticsConfig::from_args(Vec::<String>::new()), None);
+}
+
+#[test]
+fn bare_flag_prints_the_report_only() {
+    let config = DiagnosticsConfig::from_args([DIAGNOSTICS_FLAG]).unwrap();
+    assert!(!config.overlay);
+}
+
+#[test]
+fn overlay_value_enables_the_debug_label() {
+    let config = DiagnosticsConfig::from_args(["--verbose", "--diagnostics=overlay"]).unwrap();
+    assert!(config.overlay);
+    let config = DiagnosticsConfig::from_args(["--diagnostics=other"]).unwrap();
+    assert!(!config.overlay);
+}
+
+#[test]
+fn similar_flags_are_ignored() {
+    assert_eq!(DiagnosticsConfig::from_args(["--diagnosticsoverlay"]), None);
+}
+
+// === Report Tests ===
+
+#[test]
+fn report_lists_displays_with_scale_and_primary() {
+    let text = report().to_string();
+    assert!(text.starts_with("Lumbus 1.2.3 diagnostics (macOS 14.5)\n"));
+    assert!(text.contains("Displays (2):"));
+    assert!(text.contains("#1 1512x982 at (0, 0) scale 2 (primary)"));
+    assert!(text.contains("#7 2560x1440 at (1512, 0) scale 1\n"));
+}
+
+#[test]
+fn report_lists_permissions_hotkeys_and_paths() {
+    let text = report().to_string();
+    assert!(text.contains("  Accessibility: granted"));
+    assert!(text.contains("Hotkeys (standard profile):"));
+    let quit = text.lines().find(|l| l.contains("Quit")).unwrap();
+    assert!(quit.ends_with("NOT registered"));
+    let toggle = text.lines().find(|l| l.contains("Toggle overlay")).unwrap();
+    assert!(toggle.ends_with(" registered") && !toggle.contains("NOT"));
+    assert!(text.contains("Config: /tmp/config.toml"));
+    assert!(text.ends_with("Logs: /tmp/logs\n"));
+}
+
+#[test]
+fn report_lists_event_latency() {
+    let text = report().to_string();
+    let settings = text.lines().find(|l| l.contains("open_settings")).unwrap();
+    assert!(settings.contains("3 taken"));
+    assert!(settings.ends_with("mean 1.2 ms  max 2.5 ms"));
+
+    let empty = DiagnosticsReport::default().to_string();
+    assert!(empty.contains("Event latency:\n  no events yet\n"));
+}
+
+// === Frame Timer Tests ===
+
+#[test]
+fn frame_timer_needs_two_frames() {
+    let mut timer = FrameTimer::new();
+    assert_eq!(timer.average_ms(), 0.0);
+    timer.tick(10.0);
+    assert_eq!(timer.average_ms(), 0.0);
+    timer.tick(10.016);
+    assert!((timer.average_ms() - 16.0).abs() < 1e-6);
+}
+
+#[test]
+fn frame_timer_smooths_spikes() {
+    let mut timer = FrameTimer::new();
+    timer.tick(0.0);
+    timer.tick(0.016);
+    timer.tick(0.116); // one 100 ms hitch
+    let expected = 16.0 + (100.0 - 16.0) * FRAME_TIME_SMOOTHING;
+    assert!((timer.average_ms() - expected).abs() < 1e-6);
+}
+
+#[test]
+fn debug_label_shows_display_and_frame_time() {
+    assert_eq!(debug_label(2, 16.666), "display 2  16.7 ms");
+}
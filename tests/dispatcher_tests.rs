@@ -0,0 +1,233 @@
+//! Tests for the event dispatcher, run on a fake platform context.
+
+use lumbus::backend::{
+    dispatch_event, dispatch_pending, DispatchContext, HeadlessBackend, HotkeyManager,
+    OverlayBackend,
+};
+use lumbus::events::{AppEvent, EventBus};
+use lumbus::model::{ClickLogFormat, HotkeyAction, HotkeyPlatform, PresentationLock, UpdateStatus};
+
+/// Context that records the platform actions instead of carrying them out.
+struct FakeContext {
+    backend: HeadlessBackend,
+    lock: PresentationLock,
+    recording: bool,
+    calls: Vec<String>,
+}
+
+impl FakeContext {
+    fn new() -> Self {
+        Self {
+            backend: HeadlessBackend::new(HotkeyPlatform::MacOs),
+            lock: PresentationLock::new(),
+            recording: false,
+            calls: Vec::new(),
+        }
+    }
+
+    fn call(&mut self, name: &str) {
+        self.calls.push(name.to_string());
+    }
+}
+
+impl DispatchContext for FakeContext {
+    fn backend(&mut self) -> &mut dyn OverlayBackend {
+        &mut self.backend
+    }
+
+    fn allows(&self, event: &AppEvent) -> bool {
+        self.lock.allows(event)
+    }
+
+    fn toggle_presentation_lock(&mut self) -> bool {
+        self.lock.toggle()
+    }
+
+    fn open_settings(&mut self) -> bool {
+        self.call("open_settings");
+        true
+    }
+
+    fn confirm_quit(&mut self) -> bool {
+        self.call("confirm_quit");
+        true
+    }
+
+    fn restart(&mut self) {
+        self.call("restart");
+    }
+
+    fn show_about(&mut self) {
+        self.call("show_about");
+    }
+
+    fn show_help(&mut self) -> bool {
+        self.call("show_help");
+        true
+    }
+
+    fn zoom_in(&mut self) {
+        self.call("zoom_in");
+    }
+
+    fn zoom_out(&mut self) {
+        self.call("zoom_out");
+    }
+
+    fn toggle_click_recording(&mut self) -> bool {
+        self.recording = !self.recording;
+        self.recording
+    }
+
+    fn export_click_log(&mut self, format: ClickLogFormat) -> bool {
+        self.call(&format!("export_click_log {:?}", format));
+        true
+    }
+
+    fn check_for_updates(&mut self) {
+        self.call("check_for_updates");
+    }
+
+    fn update_checked(&mut self, _status: &UpdateStatus, manual: bool) -> bool {
+        self.call(&format!("update_checked manual={}", manual));
+        manual
+    }
+}
+
+/// Dispatch `events` published on a bus, as the main loop does.
+fn dispatch_all(context: &mut FakeContext, events: impl IntoIterator<Item = AppEvent>) {
+    let bus = EventBus::new();
+    let publisher = bus.publisher();
+    events.into_iter().for_each(|e| publisher.publish(e));
+    dispatch_pending(context, || bus.try_recv());
+}
+
+#[test]
+fn shared_events_go_to_the_backend() {
+    let mut context = FakeContext::new();
+    assert!(context.backend.visible());
+    dispatch_all(&mut context, [AppEvent::ToggleOverlay]);
+    assert!(!context.backend.visible());
+    assert!(context.calls.is_empty());
+}
+
+#[test]
+fn platform_events_go_to_the_context() {
+    let mut context = FakeContext::new();
+    dispatch_all(
+        &mut context,
+        [
+            AppEvent::ZoomIn,
+            AppEvent::ZoomOut,
+            AppEvent::ShowAbout,
+            AppEvent::CheckForUpdates,
+            AppEvent::Restart,
+        ],
+    );
+    // Restart is high priority and goes first
+    assert_eq!(
+        context.calls,
+        [
+            "restart",
+            "zoom_in",
+            "zoom_out",
+            "show_about",
+            "check_for_updates"
+        ]
+    );
+}
+
+#[test]
+fn modal_events_report_blocking() {
+    let mut context = FakeContext::new();
+    assert!(dispatch_event(&mut context, &AppEvent::OpenSettings));
+    assert!(dispatch_event(&mut context, &AppEvent::ExportClickLogCsv));
+    assert!(!dispatch_event(&mut context, &AppEvent::ZoomIn));
+    let automatic = AppEvent::UpdateChecked {
+        status: UpdateStatus::UpToDate,
+        manual: false,
+    };
+    assert!(!dispatch_event(&mut context, &automatic));
+    assert_eq!(
+        context.calls,
+        [
+            "open_settings",
+            "export_click_log Csv",
+            "zoom_in",
+            "update_checked manual=false"
+        ]
+    );
+}
+
+#[test]
+fn requests_piled_up_behind_a_modal_are_dropped() {
+    let mut context = FakeContext::new();
+    dispatch_all(
+        &mut context,
+        [AppEvent::ShowHelp, AppEvent::ShowHelp, AppEvent::ShowHelp],
+    );
+    assert_eq!(context.calls, ["show_help"]);
+}
+
+#[test]
+fn quit_and_restart_behind_a_modal_still_run() {
+    let mut context = FakeContext::new();
+    dispatch_all(
+        &mut context,
+        [
+            AppEvent::OpenSettings,
+            AppEvent::RequestQuit,
+            AppEvent::Restart,
+        ],
+    );
+    assert_eq!(context.calls, ["open_settings", "confirm_quit", "restart"]);
+}
+
+#[test]
+fn events_queued_around_a_modal_still_run() {
+    let mut context = FakeContext::new();
+    // Settings unregisters the hotkeys while it is open
+    let installed = context.backend.hotkeys().len();
+    context.backend.keys.unregister(HotkeyAction::Toggle);
+    // OpenSettings is high priority and goes first; the toggle published
+    // before it and the SettingsClosed published after it must not be lost
+    dispatch_all(
+        &mut context,
+        [
+            AppEvent::ToggleOverlay,
+            AppEvent::OpenSettings,
+            AppEvent::SettingsClosed,
+            AppEvent::OpenSettings,
+        ],
+    );
+    assert_eq!(context.calls, ["open_settings"]);
+    assert!(!context.backend.visible());
+    assert_eq!(context.backend.hotkeys().len(), installed);
+}
+
+#[test]
+fn presentation_lock_holds_back_settings_changes() {
+    let mut context = FakeContext::new();
+    dispatch_all(
+        &mut context,
+        [
+            AppEvent::TogglePresentationLock,
+            AppEvent::SetPreference("radius".into(), 80.0),
+        ],
+    );
+    assert!(context.lock.is_locked());
+    assert_ne!(context.backend.state().radius, 80.0);
+
+    // The lock itself always goes through
+    dispatch_all(&mut context, [AppEvent::TogglePresentationLock]);
+    assert!(!context.lock.is_locked());
+}
+
+#[test]
+fn click_recording_is_toggled() {
+    let mut context = FakeContext::new();
+    dispatch_all(&mut context, [AppEvent::ToggleClickRecording]);
+    assert!(context.recording);
+    dispatch_all(&mut context, [AppEvent::ToggleClickRecording]);
+    assert!(!context.recording);
+}
@@ -0,0 +1,195 @@
+//! Tests for the display lists and the effects built into them for both
+//! renderers.
+
+use std::f64::consts::PI;
+
+use lumbus::model::constants::{GRID_LINE_WIDTH, RULER_LINE_WIDTH};
+use lumbus::model::placement::ScreenRect;
+use lumbus::model::screen_frame::Rgba;
+use lumbus::model::touch::TouchRing;
+use lumbus::model::trail::TrailSegment;
+use lumbus::render::display_list::*;
+use lumbus::render::effects;
+
+const RED: Rgba = (1.0, 0.0, 0.0, 1.0);
+
+fn strokes(list: &DisplayList) -> Vec<(Shape, Rgba, f64)> {
+    list.flatten()
+        .into_iter()
+        .map(|(shape, paint)| match paint {
+            Paint::Stroke { color, width, .. } => (shape, color, width),
+            Paint::Fill(_) => panic!("unexpected fill of {shape:?}"),
+        })
+        .collect()
+}
+
+#[test]
+fn transforms_nest_and_scale_stroke_widths() {
+    let mut list = DisplayList::new();
+    list.transformed(Transform::translate(-100.0, -50.0), |list| {
+        list.transformed(Transform::scale_about((110.0, 60.0), 2.0), |list| {
+            list.stroke(Shape::Line((110.0, 60.0), (120.0, 60.0)), RED, 1.5);
+        });
+        list.fill(
+            Shape::Circle {
+                center: (100.0, 50.0),
+                radius: 4.0,
+            },
+            RED,
+        );
+    });
+    assert_eq!(
+        list.flatten(),
+        [
+            (
+                Shape::Line((10.0, 10.0), (30.0, 10.0)),
+                Paint::Stroke {
+                    color: RED,
+                    width: 3.0,
+                    round: false
+                }
+            ),
+            (
+                Shape::Circle {
+                    center: (0.0, 0.0),
+                    radius: 4.0
+                },
+                Paint::Fill(RED)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn transparent_shapes_are_left_out() {
+    let mut list = DisplayList::new();
+    list.transformed(Transform::IDENTITY, |list| {
+        list.stroke(
+            Shape::Line((0.0, 0.0), (1.0, 1.0)),
+            (1.0, 1.0, 1.0, 0.0),
+            2.0,
+        );
+    });
+    assert!(list.is_empty());
+    assert_eq!(list.items().len(), 2);
+}
+
+#[test]
+fn the_hold_ring_is_an_arc_until_complete() {
+    let mut list = DisplayList::new();
+    effects::hold_ring(&mut list, (0.0, 0.0), 10.0, 3.0, 0.25, RED, false);
+    let [(shape, color, width)] = strokes(&list)[..] else {
+        panic!("expected a single stroke");
+    };
+    assert_eq!(
+        shape,
+        Shape::Arc {
+            center: (0.0, 0.0),
+            radius: 20.0,
+            sweep: PI / 2.0
+        }
+    );
+    assert_eq!((color, width), (RED, 3.0));
+
+    let mut list = DisplayList::new();
+    effects::hold_ring(&mut list, (0.0, 0.0), 10.0, 3.0, 1.0, RED, true);
+    let drawn = strokes(&list);
+    assert_eq!(drawn.len(), 2);
+    assert!(drawn
+        .iter()
+        .all(|(shape, ..)| matches!(shape, Shape::Circle { .. })));
+    // The halo goes under the stroke, wider and contrasting
+    assert!(drawn[0].2 > drawn[1].2);
+    assert_eq!(drawn[0].1, (0.0, 0.0, 0.0, 1.0));
+
+    let mut list = DisplayList::new();
+    effects::hold_ring(&mut list, (0.0, 0.0), 10.0, 3.0, 0.0, RED, true);
+    assert!(list.is_empty());
+}
+
+#[test]
+fn grid_rows_follow_the_direction_of_y() {
+    let bounds = ScreenRect::new(0.0, 0.0, 40.0, 40.0);
+    let rows = |y_up: bool| {
+        let mut list = DisplayList::new();
+        effects::grid(&mut list, &bounds, 20.0, 1, RED, y_up);
+        strokes(&list)
+            .into_iter()
+            .filter_map(|(shape, _, width)| {
+                assert_eq!(width, GRID_LINE_WIDTH);
+                match shape {
+                    Shape::Line((_, y0), (_, y1)) if y0 == y1 => Some(y0),
+                    _ => None,
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(rows(false), [0.5, 20.5]);
+    assert_eq!(rows(true), [39.5, 19.5]);
+}
+
+#[test]
+fn the_ruler_is_white_over_a_dark_outline() {
+    let mut list = DisplayList::new();
+    effects::ruler(&mut list, ((0.0, 0.0), (100.0, 0.0)));
+    let drawn = list.flatten();
+    assert_eq!(drawn.len(), 6);
+    let widths: Vec<f64> = drawn
+        .iter()
+        .map(|(_, paint)| match paint {
+            Paint::Stroke { width, round, .. } => {
+                assert!(round);
+                *width
+            }
+            Paint::Fill(_) => panic!("unexpected fill"),
+        })
+        .collect();
+    assert_eq!(widths[..3], [RULER_LINE_WIDTH + 2.0; 3]);
+    assert_eq!(widths[3..], [RULER_LINE_WIDTH; 3]);
+}
+
+#[test]
+fn lifted_fingers_and_old_trail_segments_fade() {
+    let mut list = DisplayList::new();
+    let rings = [
+        TouchRing {
+            x: 10.0,
+            y: 20.0,
+            alpha: 1.0,
+        },
+        TouchRing {
+            x: 30.0,
+            y: 40.0,
+            alpha: 0.5,
+        },
+        TouchRing {
+            x: 50.0,
+            y: 60.0,
+            alpha: 0.0,
+        },
+    ];
+    effects::touch_rings(&mut list, &rings, 15.0, 2.0, RED, |x, y| (x - 10.0, y));
+    let drawn = strokes(&list);
+    assert_eq!(drawn.len(), 2);
+    assert_eq!(
+        drawn[1].0,
+        Shape::Circle {
+            center: (20.0, 40.0),
+            radius: 15.0
+        }
+    );
+    assert_eq!(drawn[1].1, (1.0, 0.0, 0.0, 0.5));
+
+    let mut list = DisplayList::new();
+    let segment = |alpha| TrailSegment {
+        x0: 0.0,
+        y0: 0.0,
+        x1: 10.0,
+        y1: 0.0,
+        alpha,
+    };
+    effects::laser_trail(&mut list, &[segment(0.25), segment(1.0)], 6.0, RED);
+    let drawn = strokes(&list);
+    assert_eq!(drawn[0].1 .3, 0.25);
+    assert!(drawn[0].2 < drawn[1].2);
+}
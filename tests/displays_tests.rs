@@ -0,0 +1,74 @@
+//! Tests for matching overlay windows to connected displays.
+
+use lumbus::model::displays::{plan_overlays, OverlayPlan};
+
+#[test]
+fn unchanged_displays_need_nothing() {
+    let plan = plan_overlays(&[1, 2], &[2, 1], 0);
+    assert!(plan.is_empty());
+}
+
+#[test]
+fn connected_display_gets_a_new_overlay() {
+    let plan = plan_overlays(&[1], &[1, 7], 0);
+    assert_eq!(
+        plan,
+        OverlayPlan {
+            create: vec![1],
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn disconnected_display_closes_its_overlay() {
+    let plan = plan_overlays(&[1, 2], &[1], 0);
+    assert_eq!(
+        plan,
+        OverlayPlan {
+            remove: vec![1],
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn overlay_of_a_replaced_display_moves_to_the_new_one() {
+    let plan = plan_overlays(&[1, 2], &[1, 3], 0);
+    assert_eq!(
+        plan,
+        OverlayPlan {
+            moves: vec![(1, 1)],
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn host_is_never_closed() {
+    // The host's display is unplugged; the other overlay makes way
+    let plan = plan_overlays(&[1, 2], &[2], 0);
+    assert_eq!(plan.moves, vec![(0, 0)]);
+    assert_eq!(plan.remove, vec![1]);
+    assert!(plan.create.is_empty());
+}
+
+#[test]
+fn host_takes_a_free_display_first() {
+    let plan = plan_overlays(&[5, 6], &[9], 1);
+    assert_eq!(plan.moves, vec![(1, 0)]);
+    assert_eq!(plan.remove, vec![0]);
+}
+
+#[test]
+fn duplicate_overlays_of_one_display_are_closed() {
+    let plan = plan_overlays(&[1, 1], &[1], 0);
+    assert_eq!(plan.remove, vec![1]);
+    assert!(plan.moves.is_empty());
+}
+
+#[test]
+fn no_displays_keeps_every_overlay() {
+    let plan = plan_overlays(&[1, 2], &[], 0);
+    assert!(plan.is_empty());
+}
@@ -0,0 +1,130 @@
+//! JSON snapshots of the serialized events and overlay state.
+
+use lumbus::events::AppEvent;
+use lumbus::model::constants::*;
+use lumbus::model::{OverlayState, Release, UpdateStatus};
+use serde_json::{json, Value};
+
+fn to_json<T: serde::Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap()
+}
+
+#[test]
+fn events_without_arguments_are_just_a_name() {
+    assert_eq!(
+        to_json(&AppEvent::ToggleOverlay),
+        json!({"event": "toggle_overlay"})
+    );
+    assert_eq!(
+        to_json(&AppEvent::ExportClickLogCsv),
+        json!({"event": "export_click_log_csv"})
+    );
+}
+
+#[test]
+fn event_arguments_snapshot() {
+    assert_eq!(
+        to_json(&AppEvent::SetPreference(PREF_RADIUS.to_string(), 60.0)),
+        json!({"event": "set_preference", "args": ["radius", 60.0]})
+    );
+    assert_eq!(
+        to_json(&AppEvent::SelectHotkeyProfile("presentation".to_string())),
+        json!({"event": "select_hotkey_profile", "args": "presentation"})
+    );
+    assert_eq!(
+        to_json(&AppEvent::UpdateChecked {
+            status: UpdateStatus::Available(Release {
+                version: "0.6.0".to_string(),
+                url: "https://example.com/v0.6.0".to_string(),
+            }),
+            manual: true,
+        }),
+        json!({
+            "event": "update_checked",
+            "args": {
+                "status": {"available": {"version": "0.6.0", "url": "https://example.com/v0.6.0"}},
+                "manual": true
+            }
+        })
+    );
+}
+
+#[test]
+fn events_roundtrip_through_json() {
+    let events = [
+        AppEvent::ToggleOverlay,
+        AppEvent::ZoomIn,
+        AppEvent::SelectHotkeyProfile("standard".to_string()),
+        AppEvent::SetPreference(PREF_BORDER.to_string(), 4.5),
+        AppEvent::SelectLookProfile("high_contrast".to_string()),
+        AppEvent::UpdateChecked {
+            status: UpdateStatus::UpToDate,
+            manual: false,
+        },
+        AppEvent::UpdateChecked {
+            status: UpdateStatus::Failed("offline".to_string()),
+            manual: true,
+        },
+    ];
+    for event in events {
+        let json = serde_json::to_string(&event).unwrap();
+        let back: AppEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, event, "{json}");
+    }
+}
+
+#[test]
+fn unknown_events_are_rejected() {
+    assert!(serde_json::from_str::<AppEvent>(r#"{"event": "format_disk"}"#).is_err());
+    assert!(serde_json::from_str::<AppEvent>(r#"{"event": "set_preference"}"#).is_err());
+}
+
+#[test]
+fn overlay_state_snapshot() {
+    assert_eq!(
+        to_json(&OverlayState::default()),
+        json!({
+            "radius": DEFAULT_DIAMETER / 2.0,
+            "border_width": DEFAULT_BORDER_WIDTH,
+            "stroke_r": DEFAULT_COLOR.0,
+            "stroke_g": DEFAULT_COLOR.1,
+            "stroke_b": DEFAULT_COLOR.2,
+            "stroke_a": DEFAULT_COLOR.3,
+            "fill_transparency_pct": DEFAULT_FILL_TRANSPARENCY_PCT,
+            "lang": LANG_SYSTEM,
+            "overlay_enabled": true,
+            "display_mode": DISPLAY_MODE_CIRCLE,
+            "click_letter_left": "",
+            "click_letter_right": "",
+            "letter_font": "",
+            "letter_weight": LETTER_WEIGHT_BOLD,
+            "click_icon": CLICK_ICON_NONE,
+            "highlight_style": HIGHLIGHT_STYLE_CIRCLE,
+            "screen_frame": false,
+            "speed_fade": false,
+            "speed_size": false,
+            "click_burst": false,
+            "click_burst_ms": DEFAULT_CLICK_BURST_MS,
+            "idle_hide": false,
+            "idle_hide_secs": DEFAULT_IDLE_HIDE_SECS,
+            "click_hold_ms": DEFAULT_CLICK_HOLD_MS,
+            "hold_ring": false,
+            "hold_ring_ms": DEFAULT_HOLD_RING_MS,
+            "motion": MOTION_SYSTEM,
+            "halo": false,
+            "rainbow": false,
+            "rainbow_speed": DEFAULT_RAINBOW_SPEED,
+            "glow": GLOW_OFF,
+            "pulse": false,
+            "pulse_amplitude": DEFAULT_PULSE_AMPLITUDE,
+            "pulse_period_ms": DEFAULT_PULSE_PERIOD_MS,
+            "transition_ms": DEFAULT_TRANSITION_MS,
+            "grid_spacing": DEFAULT_GRID_SPACING,
+            "grid_subdivisions": DEFAULT_GRID_SUBDIVISIONS,
+            "grid_r": DEFAULT_GRID_COLOR.0,
+            "grid_g": DEFAULT_GRID_COLOR.1,
+            "grid_b": DEFAULT_GRID_COLOR.2,
+            "grid_a": DEFAULT_GRID_COLOR.3
+        })
+    );
+}
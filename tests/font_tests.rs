@@ -0,0 +1,38 @@
+//! Tests for the font of the click letters.
+
+use lumbus::model::constants::*;
+use lumbus::model::font::{appkit_weight, is_letter_weight, system_font_weight, LETTER_WEIGHTS};
+
+#[test]
+fn offered_weights_are_the_valid_ones() {
+    for (weight, _) in LETTER_WEIGHTS {
+        assert!(is_letter_weight(weight));
+    }
+    assert!(!is_letter_weight(0));
+    assert!(!is_letter_weight(600));
+    assert!(is_letter_weight(LETTER_WEIGHT_BOLD));
+}
+
+#[test]
+fn appkit_weights_match_the_named_ones() {
+    assert_eq!(appkit_weight(LETTER_WEIGHT_REGULAR), 5);
+    assert_eq!(appkit_weight(LETTER_WEIGHT_BOLD), 9);
+    assert_eq!(appkit_weight(LETTER_WEIGHT_LIGHT), 4);
+    assert_eq!(appkit_weight(LETTER_WEIGHT_BLACK), 11);
+}
+
+#[test]
+fn system_font_weight_is_zero_for_regular() {
+    assert_eq!(system_font_weight(LETTER_WEIGHT_REGULAR), 0.0);
+    assert!(system_font_weight(LETTER_WEIGHT_LIGHT) < 0.0);
+    assert!((system_font_weight(LETTER_WEIGHT_BOLD) - 0.4).abs() < 1e-9);
+}
+
+#[test]
+fn heavier_weights_never_map_lighter() {
+    let weights: Vec<i32> = (1..=9).map(|w| w * 100).collect();
+    for pair in weights.windows(2) {
+        assert!(appkit_weight(pair[0]) < appkit_weight(pair[1]));
+        assert!(system_font_weight(pair[0]) < system_font_weight(pair[1]));
+    }
+}
@@ -0,0 +1,199 @@
+//! Tests for the shape math shared by the overlay renderers.
+
+use lumbus::model::constants::{CLICK_BADGE_SCALE, HALO_WIDTH, PEN_TIP_SIZE};
+use lumbus::model::placement::ScreenRect;
+use lumbus::model::trail::TrailSegment;
+use lumbus::render::geometry::*;
+
+fn approx_eq(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn circle_rect_is_centered_on_the_pointer() {
+    assert_eq!(
+        circle_rect((100.0, 50.0), 15.0),
+        ScreenRect::new(85.0, 35.0, 30.0, 30.0)
+    );
+}
+
+#[test]
+fn letters_are_one_and_a_half_diameters_tall() {
+    assert!(approx_eq(letter_height(15.0), 45.0));
+    assert!(approx_eq(badge_size(10.0), CLICK_BADGE_SCALE * 10.0));
+    assert!(approx_eq(badge_gap(50.0), 4.0));
+    assert!(approx_eq(letter_gap(50.0), 4.0));
+}
+
+#[test]
+fn wide_click_letters_shrink_to_fit() {
+    assert!(approx_eq(letter_max_width(10.0), 50.0));
+    assert!(approx_eq(letter_fit_scale(30.0, 10.0), 1.0));
+    assert!(approx_eq(letter_fit_scale(100.0, 10.0), 0.5));
+    assert!(approx_eq(letter_fit_scale(0.0, 10.0), 1.0));
+}
+
+#[test]
+fn center_offset_moves_bounds_onto_the_point() {
+    let bounds = ScreenRect::new(2.0, -30.0, 20.0, 30.0);
+    let (dx, dy) = center_offset(&bounds, (100.0, 100.0));
+    assert!(approx_eq(bounds.x + dx + bounds.w / 2.0, 100.0));
+    assert!(approx_eq(bounds.y + dy + bounds.h / 2.0, 100.0));
+}
+
+#[test]
+fn badge_sits_below_right_of_the_letter_either_way_up() {
+    assert_eq!(badge_anchor((100.0, 100.0), 10.0, false), (111.0, 115.0));
+    assert_eq!(badge_anchor((100.0, 100.0), 10.0, true), (111.0, 85.0));
+}
+
+#[test]
+fn arc_points_run_clockwise_from_twelve_either_way_up() {
+    let quarter = std::f64::consts::FRAC_PI_2;
+    let (x, y) = arc_point((100.0, 100.0), 20.0, 0.0, false);
+    assert!(approx_eq(x, 100.0) && approx_eq(y, 80.0));
+    let (x, y) = arc_point((100.0, 100.0), 20.0, quarter, false);
+    assert!(approx_eq(x, 120.0) && approx_eq(y, 100.0));
+    let (x, y) = arc_point((100.0, 100.0), 20.0, 0.0, true);
+    assert!(approx_eq(x, 100.0) && approx_eq(y, 120.0));
+    assert!(approx_eq(hold_ring_radius(10.0), 20.0));
+}
+
+#[test]
+fn readout_sits_below_right_and_flips_at_the_screen_edges() {
+    let screen = ScreenRect::new(0.0, 0.0, 1000.0, 800.0);
+    let size = (120.0, 20.0);
+    assert_eq!(
+        readout_origin((100.0, 100.0), 10.0, size, &screen, false),
+        (128.0, 128.0)
+    );
+    assert_eq!(
+        readout_origin((950.0, 790.0), 10.0, size, &screen, false),
+        (802.0, 742.0)
+    );
+    assert_eq!(
+        readout_origin((100.0, 100.0), 10.0, size, &screen, true),
+        (128.0, 52.0)
+    );
+    assert_eq!(
+        readout_origin((100.0, 10.0), 10.0, size, &screen, true),
+        (128.0, 38.0)
+    );
+}
+
+#[test]
+fn color_sample_sits_opposite_the_readout() {
+    let screen = ScreenRect::new(0.0, 0.0, 1000.0, 800.0);
+    let size = (80.0, 20.0);
+    assert_eq!(
+        sample_origin((100.0, 100.0), 10.0, size, &screen, false),
+        (128.0, 52.0)
+    );
+    assert_eq!(
+        sample_origin((100.0, 100.0), 10.0, size, &screen, true),
+        (128.0, 128.0)
+    );
+    // Flipped below the pointer at the top edge
+    assert_eq!(
+        sample_origin((100.0, 10.0), 10.0, size, &screen, false),
+        (128.0, 38.0)
+    );
+}
+
+#[test]
+fn ruler_ticks_cross_both_ends() {
+    assert_eq!(
+        ruler_ticks(((0.0, 100.0), (200.0, 100.0)), 12.0),
+        [((0.0, 94.0), (0.0, 106.0)), ((200.0, 94.0), (200.0, 106.0))]
+    );
+    // A line of no length still gets upright ticks
+    assert_eq!(
+        ruler_ticks(((50.0, 50.0), (50.0, 50.0)), 12.0),
+        [((50.0, 44.0), (50.0, 56.0)), ((50.0, 44.0), (50.0, 56.0))]
+    );
+}
+
+#[test]
+fn ruler_label_clears_the_line() {
+    let screen = ScreenRect::new(0.0, 0.0, 1000.0, 800.0);
+    let size = (80.0, 20.0);
+    // Beside the middle, on the higher-y side, whichever way the line was drawn
+    assert_eq!(
+        ruler_label_origin(((0.0, 100.0), (200.0, 100.0)), size, &screen),
+        (60.0, 108.0)
+    );
+    assert_eq!(
+        ruler_label_origin(((200.0, 100.0), (0.0, 100.0)), size, &screen),
+        (60.0, 108.0)
+    );
+    assert_eq!(
+        ruler_label_origin(((100.0, 0.0), (100.0, 200.0)), size, &screen),
+        (108.0, 90.0)
+    );
+    // Kept on screen at the edge
+    assert_eq!(
+        ruler_label_origin(((0.0, 790.0), (200.0, 790.0)), size, &screen),
+        (60.0, 780.0)
+    );
+}
+
+#[test]
+fn fill_alpha_follows_stroke_alpha_and_transparency() {
+    assert!(approx_eq(fill_alpha(1.0, 0.0), 1.0));
+    assert!(approx_eq(fill_alpha(1.0, 100.0), 0.0));
+    assert!(approx_eq(fill_alpha(0.5, 50.0), 0.25));
+    // Out-of-range transparency is clamped
+    assert!(approx_eq(fill_alpha(1.0, 150.0), 0.0));
+    assert!(approx_eq(fill_alpha(1.0, -10.0), 1.0));
+}
+
+#[test]
+fn laser_dot_parts() {
+    assert!(approx_eq(laser_core_radius(10.0), 4.0));
+    assert!(approx_eq(outline_halo_width(), 2.0 * HALO_WIDTH));
+    assert!(approx_eq(trail_width(10.0, 0.5), 10.0));
+    // Never thinner than a pixel
+    assert!(approx_eq(trail_width(10.0, 0.01), 1.0));
+}
+
+#[test]
+fn map_segment_keeps_the_alpha() {
+    let segment = TrailSegment {
+        x0: 1.0,
+        y0: 2.0,
+        x1: 3.0,
+        y1: 4.0,
+        alpha: 0.25,
+    };
+    let mapped = map_segment(segment, |x, y| (x * 10.0, -y));
+    assert_eq!(
+        mapped,
+        TrailSegment {
+            x0: 10.0,
+            y0: -2.0,
+            x1: 30.0,
+            y1: -4.0,
+            alpha: 0.25,
+        }
+    );
+}
+
+#[test]
+fn grid_lines_sit_on_whole_pixels() {
+    assert_eq!(grid_line_center(0.0), 0.5);
+    assert_eq!(grid_line_center(33.3), 33.5);
+    assert_eq!(grid_line_center(100.0), 100.5);
+}
+
+#[test]
+fn pen_tip_crosses_at_the_pointer() {
+    let [across, down] = pen_tip_arms((50.0, 20.0));
+    assert_eq!(
+        across,
+        ((50.0 - PEN_TIP_SIZE, 20.0), (50.0 + PEN_TIP_SIZE, 20.0))
+    );
+    assert_eq!(
+        down,
+        ((50.0, 20.0 - PEN_TIP_SIZE), (50.0, 20.0 + PEN_TIP_SIZE))
+    );
+}
@@ -0,0 +1,37 @@
+//! Tests for the glow and drop shadow behind the highlight.
+
+use lumbus::model::constants::*;
+use lumbus::model::glow::{glow_color, glow_layers, glow_offset};
+
+#[test]
+fn no_glow_when_off() {
+    assert_eq!(glow_color(GLOW_OFF, (1.0, 0.5, 0.0, 1.0)), None);
+    assert_eq!(glow_color(9, (1.0, 0.5, 0.0, 1.0)), None);
+}
+
+#[test]
+fn soft_glow_takes_the_stroke_color() {
+    let (r, g, b, a) = glow_color(GLOW_SOFT, (1.0, 0.5, 0.0, 0.5)).unwrap();
+    assert_eq!((r, g, b), (1.0, 0.5, 0.0));
+    assert!((a - 0.5 * GLOW_ALPHA).abs() < 1e-9);
+    assert_eq!(glow_offset(GLOW_SOFT), 0.0);
+}
+
+#[test]
+fn drop_shadow_is_dark_and_moved_down() {
+    let (r, g, b, a) = glow_color(GLOW_SHADOW, (1.0, 1.0, 0.0, 1.0)).unwrap();
+    assert_eq!((r, g, b), (0.0, 0.0, 0.0));
+    assert!((a - GLOW_SHADOW_ALPHA).abs() < 1e-9);
+    assert_eq!(glow_offset(GLOW_SHADOW), GLOW_SHADOW_OFFSET);
+}
+
+#[test]
+fn layers_spread_to_the_glow_radius_widest_first() {
+    let layers = glow_layers();
+    assert_eq!(layers[0].0, GLOW_RADIUS);
+    assert!(layers.windows(2).all(|w| w[0].0 > w[1].0));
+    assert!(layers.last().unwrap().0 > 0.0);
+    // Stacked, the layers add up to at most the glow color's opacity
+    let total = layers.iter().fold(0.0, |acc, (_, a)| acc + a * (1.0 - acc));
+    assert!(total > 0.5 && total <= 1.0, "{total}");
+}
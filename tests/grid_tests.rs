@@ -0,0 +1,59 @@
+//! Tests for the alignment grid.
+
+use lumbus::model::constants::GRID_MINOR_ALPHA;
+use lumbus::model::grid::{grid_line_color, grid_lines, GridLine};
+
+fn offsets(lines: &[GridLine]) -> Vec<(f64, bool)> {
+    lines.iter().map(|l| (l.offset, l.major)).collect()
+}
+
+#[test]
+fn minor_lines_split_each_cell() {
+    let lines = grid_lines(100.0, 40.0, 4);
+    assert_eq!(
+        offsets(&lines),
+        [
+            (0.0, true),
+            (10.0, false),
+            (20.0, false),
+            (30.0, false),
+            (40.0, true),
+            (50.0, false),
+            (60.0, false),
+            (70.0, false),
+            (80.0, true),
+            (90.0, false),
+        ]
+    );
+}
+
+#[test]
+fn one_part_draws_only_major_lines() {
+    for subdivisions in [1, 0, -3] {
+        let lines = grid_lines(250.0, 100.0, subdivisions);
+        assert_eq!(offsets(&lines), [(0.0, true), (100.0, true), (200.0, true)]);
+    }
+}
+
+#[test]
+fn far_edge_gets_no_line() {
+    let lines = grid_lines(200.0, 100.0, 1);
+    assert_eq!(lines.last().map(|l| l.offset), Some(100.0));
+}
+
+#[test]
+fn degenerate_spacing_draws_nothing() {
+    assert!(grid_lines(100.0, 0.0, 4).is_empty());
+    assert!(grid_lines(100.0, f64::NAN, 4).is_empty());
+    assert!(grid_lines(f64::INFINITY, 50.0, 1).is_empty());
+}
+
+#[test]
+fn minor_lines_are_fainter() {
+    let color = (0.2, 0.4, 0.6, 0.5);
+    assert_eq!(grid_line_color(color, true), color);
+    assert_eq!(
+        grid_line_color(color, false),
+        (0.2, 0.4, 0.6, 0.5 * GRID_MINOR_ALPHA)
+    );
+}
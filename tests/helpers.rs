@@ -145,3 +145,12 @@ fn tr_key_help_overlay_localisation() {
         "Pulsa cualquier tecla para cerrar"
     );
 }
+
+#[test]
+fn tr_key_accessibility_labels_localisation() {
+    assert_eq!(tr_key("Hex color", false).as_ref(), "Hex color");
+    assert_eq!(tr_key("Hex color", true).as_ref(), "Color hexadecimal");
+
+    assert_eq!(tr_key("Frame color", false).as_ref(), "Frame color");
+    assert_eq!(tr_key("Frame color", true).as_ref(), "Color del marco");
+}
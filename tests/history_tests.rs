@@ -0,0 +1,98 @@
+//! Tests for the Settings undo/redo history.
+
+use lumbus::model::constants::*;
+use lumbus::model::{OverlayState, SettingsHistory, SettingsSnapshot};
+
+fn snapshot(radius: f64) -> SettingsSnapshot {
+    SettingsSnapshot {
+        state: OverlayState {
+            radius,
+            ..OverlayState::default()
+        },
+        frame_color: DEFAULT_SCREEN_FRAME_COLOR,
+    }
+}
+
+#[test]
+fn empty_history_has_nothing_to_undo() {
+    let mut history = SettingsHistory::new();
+    assert!(!history.can_undo());
+    assert!(!history.can_redo());
+    assert_eq!(history.undo(snapshot(10.0)), None);
+    assert_eq!(history.redo(snapshot(10.0)), None);
+}
+
+#[test]
+fn undo_and_redo_walk_the_changes() {
+    let mut history = SettingsHistory::new();
+    history.record(PREF_RADIUS, snapshot(10.0), 0.0);
+    history.record(PREF_BORDER, snapshot(20.0), 0.5);
+
+    assert_eq!(history.undo(snapshot(30.0)), Some(snapshot(20.0)));
+    assert_eq!(history.undo(snapshot(20.0)), Some(snapshot(10.0)));
+    assert!(!history.can_undo());
+
+    assert_eq!(history.redo(snapshot(10.0)), Some(snapshot(20.0)));
+    assert_eq!(history.redo(snapshot(20.0)), Some(snapshot(30.0)));
+    assert!(!history.can_redo());
+    assert!(history.can_undo());
+}
+
+#[test]
+fn quick_changes_of_one_control_are_one_step() {
+    let mut history = SettingsHistory::new();
+    // A slider drag: every step within the merge window of the last
+    for (i, radius) in [10.0, 15.0, 20.0, 25.0].into_iter().enumerate() {
+        history.record(PREF_RADIUS, snapshot(radius), i as f64 * 0.1);
+    }
+    assert_eq!(history.undo(snapshot(30.0)), Some(snapshot(10.0)));
+    assert!(!history.can_undo());
+}
+
+#[test]
+fn slow_or_different_changes_are_separate_steps() {
+    let mut history = SettingsHistory::new();
+    history.record(PREF_RADIUS, snapshot(10.0), 0.0);
+    history.record(PREF_RADIUS, snapshot(20.0), SETTINGS_UNDO_MERGE_SECS + 0.5);
+    history.record(PREF_BORDER, snapshot(30.0), SETTINGS_UNDO_MERGE_SECS + 0.6);
+    assert_eq!(history.undo(snapshot(40.0)), Some(snapshot(30.0)));
+    assert_eq!(history.undo(snapshot(30.0)), Some(snapshot(20.0)));
+    assert_eq!(history.undo(snapshot(20.0)), Some(snapshot(10.0)));
+}
+
+#[test]
+fn a_new_change_clears_the_redo_stack() {
+    let mut history = SettingsHistory::new();
+    history.record(PREF_RADIUS, snapshot(10.0), 0.0);
+    history.undo(snapshot(20.0));
+    assert!(history.can_redo());
+    history.record(PREF_BORDER, snapshot(10.0), 5.0);
+    assert!(!history.can_redo());
+}
+
+#[test]
+fn undo_ends_the_merged_step() {
+    let mut history = SettingsHistory::new();
+    history.record(PREF_RADIUS, snapshot(10.0), 0.0);
+    history.undo(snapshot(20.0));
+    // Same control right after the undo still starts a new step
+    history.record(PREF_RADIUS, snapshot(10.0), 0.1);
+    assert!(history.can_undo());
+    assert_eq!(history.undo(snapshot(15.0)), Some(snapshot(10.0)));
+}
+
+#[test]
+fn history_is_bounded() {
+    let mut history = SettingsHistory::new();
+    for i in 0..SETTINGS_UNDO_LIMIT + 10 {
+        history.record(PREF_RADIUS, snapshot(i as f64), i as f64 * 10.0);
+    }
+    let mut steps = 0;
+    let mut current = snapshot(1000.0);
+    while let Some(previous) = history.undo(current.clone()) {
+        current = previous;
+        steps += 1;
+    }
+    assert_eq!(steps, SETTINGS_UNDO_LIMIT);
+    assert_eq!(current, snapshot(10.0));
+}
@@ -0,0 +1,94 @@
+//! Tests for the mouse hook watchdog.
+
+use lumbus::model::constants::{HOOK_MISSED_EDGES, HOOK_WATCHDOG_SECS};
+use lumbus::model::hook_watchdog::{HookWatchdog, HOOK_BUTTON_LEFT, HOOK_BUTTON_RIGHT};
+
+#[test]
+fn agreeing_buttons_keep_the_hook() {
+    let mut watchdog = HookWatchdog::new();
+    assert!(!watchdog.check(0, 0.0));
+    watchdog.hook_saw(HOOK_BUTTON_LEFT, true);
+    assert!(!watchdog.check(HOOK_BUTTON_LEFT, 10.0));
+    watchdog.hook_saw(HOOK_BUTTON_LEFT, false);
+    assert!(!watchdog.check(0, 20.0));
+    assert_eq!(watchdog.reinstalls(), 0);
+}
+
+#[test]
+fn a_missed_press_removes_the_hook_after_the_grace_period() {
+    let mut watchdog = HookWatchdog::new();
+    assert!(!watchdog.check(HOOK_BUTTON_LEFT, 1.0));
+    assert!(!watchdog.check(HOOK_BUTTON_LEFT, 1.0 + HOOK_WATCHDOG_SECS / 2.0));
+    assert!(watchdog.check(HOOK_BUTTON_LEFT, 1.0 + HOOK_WATCHDOG_SECS));
+    assert_eq!(watchdog.reinstalls(), 1);
+    // Starts over from the polled buttons
+    assert!(!watchdog.check(HOOK_BUTTON_LEFT, 5.0));
+}
+
+#[test]
+fn a_missed_release_removes_the_hook() {
+    let mut watchdog = HookWatchdog::new();
+    watchdog.hook_saw(HOOK_BUTTON_RIGHT, true);
+    assert!(!watchdog.check(0, 0.0));
+    assert!(watchdog.check(0, HOOK_WATCHDOG_SECS));
+}
+
+#[test]
+fn a_brief_disagreement_is_forgiven() {
+    let mut watchdog = HookWatchdog::new();
+    assert!(!watchdog.check(HOOK_BUTTON_LEFT, 0.0));
+    watchdog.hook_saw(HOOK_BUTTON_LEFT, true);
+    assert!(!watchdog.check(HOOK_BUTTON_LEFT, 0.1));
+    watchdog.hook_saw(HOOK_BUTTON_LEFT, false);
+    assert!(!watchdog.check(HOOK_BUTTON_LEFT, 0.2));
+    assert!(!watchdog.check(0, HOOK_WATCHDOG_SECS + 0.1));
+    assert_eq!(watchdog.reinstalls(), 0);
+}
+
+#[test]
+fn swapped_buttons_still_agree() {
+    let mut watchdog = HookWatchdog::new();
+    watchdog.hook_saw(HOOK_BUTTON_LEFT, true);
+    assert!(!watchdog.check(HOOK_BUTTON_RIGHT, 0.0));
+    assert!(!watchdog.check(HOOK_BUTTON_RIGHT, HOOK_WATCHDOG_SECS * 2.0));
+}
+
+#[test]
+fn short_clicks_the_hook_misses_remove_it() {
+    let mut watchdog = HookWatchdog::new();
+    let mut t = 0.0;
+    let mut removed = false;
+    for _ in 0..HOOK_MISSED_EDGES / 2 {
+        assert!(!removed);
+        removed |= watchdog.check(HOOK_BUTTON_LEFT, t);
+        removed |= watchdog.check(0, t + 0.1);
+        t += 1.0;
+    }
+    assert!(removed);
+    assert_eq!(watchdog.reinstalls(), 1);
+}
+
+#[test]
+fn short_clicks_the_hook_reports_keep_it() {
+    let mut watchdog = HookWatchdog::new();
+    for i in 0..20 {
+        let t = i as f64;
+        if i % 2 == 0 {
+            // The hook's callback ran before the frame
+            watchdog.hook_saw(HOOK_BUTTON_LEFT, true);
+            assert!(!watchdog.check(HOOK_BUTTON_LEFT, t));
+            watchdog.hook_saw(HOOK_BUTTON_LEFT, false);
+            assert!(!watchdog.check(0, t + 0.1));
+        } else {
+            // The frame polled the press before the hook's callback ran
+            assert!(!watchdog.check(HOOK_BUTTON_LEFT, t));
+            watchdog.hook_saw(HOOK_BUTTON_LEFT, true);
+            watchdog.hook_saw(HOOK_BUTTON_LEFT, false);
+            assert!(!watchdog.check(0, t + 0.1));
+        }
+        // Too short for polling to see
+        watchdog.hook_saw(HOOK_BUTTON_LEFT, true);
+        watchdog.hook_saw(HOOK_BUTTON_LEFT, false);
+    }
+    assert_eq!(watchdog.reinstalls(), 0);
+}
@@ -0,0 +1,106 @@
+//! Tests for the hotkey registration written against `HotkeyManager`, run
+//! on the in-memory manager.
+
+use std::sync::Mutex;
+
+use lumbus::backend::{
+    conflict, is_registered, rebind, register_bindings, unregister_all, HeadlessHotkeys,
+    HotkeyError, HotkeyManager,
+};
+use lumbus::model::{
+    profile_bindings, Binding, Hotkey, HotkeyAction, HotkeyPlatform, HOTKEY_PROFILE_PRESENTATION,
+    HOTKEY_PROFILE_STANDARD,
+};
+
+fn hotkey(code: &str) -> Hotkey {
+    Hotkey::parse(code).unwrap()
+}
+
+fn standard() -> &'static [Binding] {
+    profile_bindings(HOTKEY_PROFILE_STANDARD, HotkeyPlatform::Windows)
+}
+
+#[test]
+fn registering_a_profile_replaces_the_previous_one() {
+    let mut manager = HeadlessHotkeys::new();
+    assert_eq!(
+        register_bindings(&mut manager, standard()),
+        standard().len()
+    );
+    assert_eq!(manager.list(), standard());
+
+    let presentation = profile_bindings(HOTKEY_PROFILE_PRESENTATION, HotkeyPlatform::Windows);
+    register_bindings(&mut manager, presentation);
+    assert_eq!(manager.list(), presentation);
+
+    unregister_all(&mut manager);
+    assert!(manager.list().is_empty());
+}
+
+#[test]
+fn hotkeys_taken_by_other_apps_are_skipped() {
+    let mut manager = HeadlessHotkeys::new();
+    manager.take(hotkey("Ctrl+Shift+H"));
+    assert_eq!(
+        register_bindings(&mut manager, standard()),
+        standard().len() - 1
+    );
+    assert!(!is_registered(&manager, HotkeyAction::Help));
+    assert!(is_registered(&manager, HotkeyAction::Toggle));
+}
+
+#[test]
+fn rebinding_refuses_a_hotkey_of_another_command() {
+    let mut manager = HeadlessHotkeys::new();
+    register_bindings(&mut manager, standard());
+    assert_eq!(
+        conflict(&manager, HotkeyAction::Help, hotkey("Ctrl+Shift+A")),
+        Some(HotkeyAction::Toggle)
+    );
+    assert_eq!(
+        rebind(&mut manager, HotkeyAction::Help, hotkey("Ctrl+Shift+A")),
+        Err(HotkeyError::Taken {
+            hotkey: hotkey("Ctrl+Shift+A"),
+            by: HotkeyAction::Toggle
+        })
+    );
+
+    rebind(&mut manager, HotkeyAction::Help, hotkey("Ctrl+Alt+H")).unwrap();
+    let help = manager
+        .list()
+        .into_iter()
+        .find(|b| b.action == HotkeyAction::Help);
+    assert_eq!(help.map(|b| b.hotkey), Some(hotkey("Ctrl+Alt+H")));
+}
+
+#[test]
+fn a_refused_rebind_keeps_the_old_hotkey() {
+    let mut manager = HeadlessHotkeys::new();
+    register_bindings(&mut manager, standard());
+    manager.take(hotkey("Ctrl+Alt+H"));
+    assert!(matches!(
+        rebind(&mut manager, HotkeyAction::Help, hotkey("Ctrl+Alt+H")),
+        Err(HotkeyError::Refused { .. })
+    ));
+    let help = manager
+        .list()
+        .into_iter()
+        .find(|b| b.action == HotkeyAction::Help);
+    assert_eq!(help.map(|b| b.hotkey), Some(hotkey("Ctrl+Shift+H")));
+}
+
+static TRIGGERED: Mutex<Vec<HotkeyAction>> = Mutex::new(Vec::new());
+
+fn record_trigger(action: HotkeyAction) {
+    TRIGGERED.lock().unwrap().push(action);
+}
+
+#[test]
+fn pressed_hotkeys_go_to_the_trigger_handler() {
+    let mut manager = HeadlessHotkeys::new();
+    manager.on_trigger(record_trigger);
+    register_bindings(&mut manager, standard());
+    assert!(manager.press(hotkey("Ctrl+Shift+G")));
+    assert!(!manager.press(hotkey("Ctrl+Alt+Z")));
+    assert_eq!(*TRIGGERED.lock().unwrap(), [HotkeyAction::Grid]);
+}
@@ -0,0 +1,278 @@
+//! Tests for the hotkey profiles.
+
+use lumbus::events::AppEvent;
+use lumbus::model::hotkeys::{MOD_ALT, MOD_CMD, MOD_CTRL, MOD_SHIFT};
+use lumbus::model::{
+    apply_overrides, encode_overrides, help_entries, hotkey_conflict, hotkey_for, parse_overrides,
+    profile_bindings, profile_label, resolve_profile, safe_mode_bindings, set_override, Binding,
+    Hotkey, HotkeyAction, HotkeyPlatform, Key, HOTKEY_PROFILES, HOTKEY_PROFILE_PRESENTATION,
+    HOTKEY_PROFILE_STANDARD,
+};
+
+#[test]
+fn every_profile_binds_every_action_once() {
+    for profile in HOTKEY_PROFILES {
+        for platform in [HotkeyPlatform::MacOs, HotkeyPlatform::Windows] {
+            let bindings = profile_bindings(profile, platform);
+            for action in HotkeyAction::ALL {
+                let count = bindings.iter().filter(|b| b.action == action).count();
+                assert_eq!(count, 1, "{profile} {platform:?} {action:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn standard_profile_keeps_the_platform_shortcuts() {
+    let mac = profile_bindings(HOTKEY_PROFILE_STANDARD, HotkeyPlatform::MacOs);
+    assert_eq!(
+        hotkey_for(mac, HotkeyAction::Toggle),
+        Some(Hotkey {
+            key: Key::Letter('A'),
+            modifiers: MOD_CTRL
+        })
+    );
+    assert_eq!(
+        hotkey_for(mac, HotkeyAction::Settings).unwrap().modifiers,
+        MOD_CMD | MOD_SHIFT
+    );
+
+    let win = profile_bindings(HOTKEY_PROFILE_STANDARD, HotkeyPlatform::Windows);
+    assert_eq!(
+        hotkey_for(win, HotkeyAction::Quit),
+        Some(Hotkey {
+            key: Key::Letter('Q'),
+            modifiers: MOD_CTRL | MOD_SHIFT
+        })
+    );
+}
+
+#[test]
+fn presentation_profile_uses_bare_function_keys() {
+    let bindings = profile_bindings(HOTKEY_PROFILE_PRESENTATION, HotkeyPlatform::Windows);
+    for binding in bindings {
+        assert!(matches!(binding.hotkey.key, Key::F(13..=20)));
+        if !matches!(
+            binding.action,
+            HotkeyAction::ColorSampler | HotkeyAction::Ruler | HotkeyAction::Grid
+        ) {
+            assert_eq!(binding.hotkey.modifiers, 0);
+        }
+    }
+    assert_eq!(
+        hotkey_for(bindings, HotkeyAction::ColorSampler),
+        Some(Hotkey {
+            key: Key::F(13),
+            modifiers: MOD_SHIFT
+        })
+    );
+    assert_eq!(
+        hotkey_for(bindings, HotkeyAction::Ruler),
+        Some(Hotkey {
+            key: Key::F(14),
+            modifiers: MOD_SHIFT
+        })
+    );
+    assert_eq!(
+        hotkey_for(bindings, HotkeyAction::Grid),
+        Some(Hotkey {
+            key: Key::F(15),
+            modifiers: MOD_SHIFT
+        })
+    );
+    assert_eq!(
+        hotkey_for(bindings, HotkeyAction::Toggle).unwrap().key,
+        Key::F(13)
+    );
+}
+
+#[test]
+fn unknown_profile_falls_back_to_standard() {
+    assert_eq!(resolve_profile("gaming"), HOTKEY_PROFILE_STANDARD);
+    assert_eq!(
+        profile_bindings("gaming", HotkeyPlatform::MacOs),
+        profile_bindings(HOTKEY_PROFILE_STANDARD, HotkeyPlatform::MacOs)
+    );
+    assert_eq!(profile_label("gaming"), "Standard");
+}
+
+#[test]
+fn labels_list_modifiers_before_the_key() {
+    let hotkey = Hotkey {
+        key: Key::Comma,
+        modifiers: MOD_CMD | MOD_SHIFT,
+    };
+    assert_eq!(hotkey.label(), "\u{2318} + Shift + ,");
+    let hotkey = Hotkey {
+        key: Key::Equal,
+        modifiers: MOD_CTRL | MOD_SHIFT,
+    };
+    assert_eq!(hotkey.label(), "Ctrl + Shift + =");
+    let hotkey = Hotkey {
+        key: Key::F(15),
+        modifiers: 0,
+    };
+    assert_eq!(hotkey.label(), "F15");
+}
+
+#[test]
+fn actions_publish_their_events() {
+    assert_eq!(HotkeyAction::Toggle.event(), AppEvent::ToggleOverlay);
+    assert_eq!(HotkeyAction::Lock.event(), AppEvent::TogglePresentationLock);
+    assert_eq!(HotkeyAction::Quit.event(), AppEvent::RequestQuit);
+    assert_eq!(
+        HotkeyAction::Coordinates.event(),
+        AppEvent::ToggleCoordinates
+    );
+}
+
+#[test]
+fn hotkey_codes_round_trip() {
+    for profile in HOTKEY_PROFILES {
+        for platform in [HotkeyPlatform::MacOs, HotkeyPlatform::Windows] {
+            for binding in profile_bindings(profile, platform) {
+                let code = binding.hotkey.code();
+                assert_eq!(Hotkey::parse(&code), Some(binding.hotkey), "{code}");
+            }
+        }
+    }
+    let hotkey = Hotkey {
+        key: Key::Equal,
+        modifiers: MOD_CTRL | MOD_ALT,
+    };
+    assert_eq!(hotkey.code(), "Ctrl+Alt+Equal");
+}
+
+#[test]
+fn malformed_hotkey_codes_are_rejected() {
+    for code in ["", "Ctrl+", "Hyper+A", "Ctrl+a", "F0", "F21", "Ctrl+AB"] {
+        assert_eq!(Hotkey::parse(code), None, "{code}");
+    }
+}
+
+#[test]
+fn action_ids_round_trip() {
+    for action in HotkeyAction::ALL {
+        assert_eq!(HotkeyAction::from_id(action.id()), Some(action));
+    }
+    assert_eq!(HotkeyAction::from_id("dance"), None);
+}
+
+#[test]
+fn overrides_round_trip_and_skip_malformed_entries() {
+    let overrides = parse_overrides("toggle=Ctrl+Alt+H;dance=F1;quit=Nope;help=F12");
+    assert_eq!(
+        overrides,
+        vec![
+            Binding {
+                action: HotkeyAction::Toggle,
+                hotkey: Hotkey {
+                    key: Key::Letter('H'),
+                    modifiers: MOD_CTRL | MOD_ALT
+                }
+            },
+            Binding {
+                action: HotkeyAction::Help,
+                hotkey: Hotkey {
+                    key: Key::F(12),
+                    modifiers: 0
+                }
+            },
+        ]
+    );
+    assert_eq!(encode_overrides(&overrides), "toggle=Ctrl+Alt+H;help=F12");
+    assert!(parse_overrides("").is_empty());
+}
+
+#[test]
+fn overrides_that_would_take_over_typing_are_skipped() {
+    let overrides = parse_overrides("toggle=A;help=Shift+H;quit=Shift+Minus;zoom_in=Shift+F5");
+    assert_eq!(
+        overrides,
+        vec![Binding {
+            action: HotkeyAction::ZoomIn,
+            hotkey: Hotkey {
+                key: Key::F(5),
+                modifiers: MOD_SHIFT
+            }
+        }]
+    );
+}
+
+#[test]
+fn overrides_replace_the_profile_hotkeys() {
+    let profile = profile_bindings(HOTKEY_PROFILE_STANDARD, HotkeyPlatform::Windows);
+    let f9 = Hotkey {
+        key: Key::F(9),
+        modifiers: 0,
+    };
+    let overrides = set_override(&[], HotkeyAction::Toggle, Some(f9));
+    let bindings = apply_overrides(profile, &overrides);
+    assert_eq!(bindings.len(), profile.len());
+    assert_eq!(hotkey_for(&bindings, HotkeyAction::Toggle), Some(f9));
+    assert_eq!(
+        hotkey_for(&bindings, HotkeyAction::Quit),
+        hotkey_for(profile, HotkeyAction::Quit)
+    );
+
+    // Rebinding again replaces the override, resetting drops it
+    let f10 = Hotkey {
+        key: Key::F(10),
+        modifiers: 0,
+    };
+    let overrides = set_override(&overrides, HotkeyAction::Toggle, Some(f10));
+    assert_eq!(overrides.len(), 1);
+    assert!(set_override(&overrides, HotkeyAction::Toggle, None).is_empty());
+}
+
+#[test]
+fn conflicts_name_the_other_action() {
+    let profile = profile_bindings(HOTKEY_PROFILE_STANDARD, HotkeyPlatform::Windows);
+    let quit = hotkey_for(profile, HotkeyAction::Quit).unwrap();
+    assert_eq!(
+        hotkey_conflict(profile, HotkeyAction::Toggle, quit),
+        Some(HotkeyAction::Quit)
+    );
+    // Its own hotkey is no conflict
+    assert_eq!(hotkey_conflict(profile, HotkeyAction::Quit, quit), None);
+}
+
+#[test]
+fn help_lists_the_hotkeys_in_use() {
+    let profile = profile_bindings(HOTKEY_PROFILE_STANDARD, HotkeyPlatform::Windows);
+    let f9 = Hotkey {
+        key: Key::F(9),
+        modifiers: 0,
+    };
+    let overrides = set_override(&[], HotkeyAction::Toggle, Some(f9));
+    let bindings = apply_overrides(profile, &overrides);
+    let entries = help_entries(&bindings, |action| action != HotkeyAction::Quit);
+
+    assert_eq!(entries.len(), profile.len());
+    // Rebinds show their new keys
+    let toggle = &entries[0];
+    assert_eq!(toggle.keys, "F9");
+    assert_eq!(toggle.description, HotkeyAction::Toggle.description());
+    // A hotkey another app holds is marked
+    let quit = entries
+        .iter()
+        .find(|e| e.description == HotkeyAction::Quit.description())
+        .unwrap();
+    assert!(!quit.registered);
+    assert_eq!(entries.iter().filter(|e| !e.registered).count(), 1);
+}
+
+#[test]
+fn safe_mode_keeps_the_standard_toggle_only() {
+    for platform in [HotkeyPlatform::MacOs, HotkeyPlatform::Windows] {
+        let bindings = safe_mode_bindings(platform);
+        assert_eq!(bindings.len(), 1);
+        assert_eq!(
+            Some(bindings[0].hotkey),
+            hotkey_for(
+                profile_bindings(HOTKEY_PROFILE_STANDARD, platform),
+                HotkeyAction::Toggle
+            )
+        );
+    }
+}
@@ -0,0 +1,258 @@
+//! Tests for the Fluent localization backend.
+
+use lumbus::i18n::ftl::{self, Element};
+use lumbus::i18n::localizer::{BUILTIN_RESOURCES, EN_FTL};
+use lumbus::i18n::{self, message_id, normalize_locale, Localizer};
+use lumbus::model::constants::*;
+use lumbus::tr_key;
+
+// === Parser Tests ===
+
+#[test]
+fn parses_messages_and_skips_comments() {
+    let res = ftl::parse("# comment\n## Section\nhello = Hello\nbye = Bye\n");
+    assert!(res.errors.is_empty());
+    let ids: Vec<_> = res.messages.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(ids, ["hello", "bye"]);
+}
+
+#[test]
+fn parses_multiline_values_and_attributes() {
+    let res = ftl::parse("msg = First\n    second\n    .title = Tip\n");
+    assert!(res.errors.is_empty());
+    assert_eq!(
+        res.messages[0],
+        ("msg".into(), vec![Element::Text("First\nsecond".into())])
+    );
+    assert_eq!(
+        res.messages[1],
+        ("msg.title".into(), vec![Element::Text("Tip".into())])
+    );
+}
+
+#[test]
+fn parses_literals_and_variables() {
+    let res = ftl::parse("dots = Export{ \"...\" }\ngreet = Hi { $name }!\n");
+    assert!(res.errors.is_empty());
+    assert_eq!(ftl::format_pattern(&res.messages[0].1, &[]), "Export...");
+    assert_eq!(
+        ftl::format_pattern(&res.messages[1].1, &[("name", "Ana")]),
+        "Hi Ana!"
+    );
+    assert_eq!(ftl::format_pattern(&res.messages[1].1, &[]), "Hi {$name}!");
+}
+
+#[test]
+fn reports_unsupported_entries_and_keeps_the_rest() {
+    let res = ftl::parse("-brand = Lumbus\nbad = { -brand }\nnot an entry\nok = Fine\n");
+    assert_eq!(res.errors.len(), 3);
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].0, "ok");
+}
+
+#[test]
+fn message_ids_are_slugs_of_english_text() {
+    assert_eq!(message_id("Radius (px)"), "radius-px");
+    assert_eq!(message_id("Fill Transparency (%)"), "fill-transparency");
+    assert_eq!(message_id("Export clicks (CSV)..."), "export-clicks-csv");
+    assert_eq!(message_id("radius-px"), "radius-px");
+}
+
+// === Localizer Tests ===
+
+#[test]
+fn fallback_chain_ends_with_default_locale() {
+    let l = Localizer::new("en");
+    assert_eq!(l.fallback_chain("es_MX.UTF-8"), ["es-mx", "es", "en"]);
+    assert_eq!(l.fallback_chain("en-GB"), ["en-gb", "en"]);
+    assert_eq!(normalize_locale("pt_BR"), "pt-br");
+}
+
+#[test]
+fn missing_messages_fall_back_to_base_and_default() {
+    let mut l = Localizer::new("en");
+    l.add_resource("en", "a = A\nb = B\nc = C\n");
+    l.add_resource("es", "a = A-es\nb = B-es\n");
+    l.add_resource("es-MX", "a = A-mx\n");
+    assert_eq!(l.translate("es-MX", "a"), "A-mx");
+    assert_eq!(l.translate("es-MX", "b"), "B-es");
+    assert_eq!(l.translate("es-MX", "c"), "C");
+    assert_eq!(l.translate("es-MX", "Unknown key"), "Unknown key");
+}
+
+#[test]
+fn language_can_be_switched_at_runtime() {
+    let mut l = Localizer::with_builtin("en");
+    assert_eq!(l.tr("Settings"), "Settings");
+    l.set_locale("es");
+    assert_eq!(l.locale(), "es");
+    assert_eq!(l.tr("Settings"), "Configuración");
+}
+
+#[test]
+fn later_resources_override_and_add_languages() {
+    let dir = std::env::temp_dir().join(format!("lumbus-i18n-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("es.ftl"), "settings = Ajustes\n").unwrap();
+    std::fs::write(dir.join("it.ftl"), "settings = Impostazioni\n").unwrap();
+    std::fs::write(dir.join("notes.txt"), "ignored = yes\n").unwrap();
+
+    let mut l = Localizer::with_builtin("en");
+    let errors = l.load_dir(&dir).unwrap();
+    std::fs::remove_dir_all(&dir).ok();
+
+    assert!(errors.is_empty());
+    assert_eq!(l.locales(), ["de", "en", "es", "fr", "it", "ja", "pt"]);
+    assert_eq!(l.translate("es", "Settings"), "Ajustes");
+    assert_eq!(l.translate("es", "Language"), "Idioma");
+    assert_eq!(l.translate("it", "Settings"), "Impostazioni");
+    assert_eq!(l.translate("it", "Language"), "Language");
+}
+
+// === Built-in Resource Tests ===
+
+#[test]
+fn builtin_resources_parse_cleanly() {
+    for (locale, source) in BUILTIN_RESOURCES {
+        let errors = ftl::parse(source).errors;
+        assert!(errors.is_empty(), "{locale}.ftl: {errors:?}");
+    }
+}
+
+#[test]
+fn builtin_resources_match_builtin_languages() {
+    let locales: Vec<_> = BUILTIN_RESOURCES.iter().map(|(l, _)| *l).collect();
+    assert_eq!(locales, BUILTIN_LANGUAGES);
+}
+
+#[test]
+fn every_language_translates_every_english_message() {
+    let en = ftl::parse(EN_FTL).messages;
+    for (locale, source) in BUILTIN_RESOURCES {
+        let ids: Vec<_> = ftl::parse(source)
+            .messages
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        for (id, _) in &en {
+            assert!(ids.contains(id), "missing message `{id}` in {locale}.ftl");
+        }
+    }
+}
+
+/// English keys of the `tr("...")` calls under `dir`.
+fn tr_keys(dir: &std::path::Path, keys: &mut Vec<(String, String)>) {
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            tr_keys(&path, keys);
+        } else if path.extension().is_some_and(|e| e == "rs") {
+            let source = std::fs::read_to_string(&path).unwrap();
+            for (at, call) in source.match_indices("tr(\"") {
+                let before = source[..at].chars().next_back();
+                if before.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                    continue;
+                }
+                let rest = &source[at + call.len()..];
+                if let Some(key) = rest.split('"').next().filter(|k| !k.contains('\\')) {
+                    keys.push((path.display().to_string(), key.to_string()));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn every_translated_key_has_an_english_message() {
+    let en: Vec<_> = ftl::parse(EN_FTL)
+        .messages
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+    let mut keys = Vec::new();
+    tr_keys(std::path::Path::new("src"), &mut keys);
+    assert!(!keys.is_empty());
+    for (file, key) in keys {
+        let id = message_id(&key);
+        assert!(en.contains(&id), "{file}: no message `{id}` for {key:?}");
+    }
+}
+
+// === Language Preference Tests ===
+
+#[test]
+fn stored_language_indexes_keep_their_meaning() {
+    assert_eq!(i18n::lang_code(LANG_EN), "en");
+    assert_eq!(i18n::lang_code(LANG_ES), "es");
+    assert_eq!(i18n::lang_code(LANG_FR), "fr");
+    assert_eq!(i18n::lang_code(LANG_DE), "de");
+    assert_eq!(i18n::lang_code(LANG_PT), "pt");
+    assert_eq!(i18n::lang_code(LANG_JA), "ja");
+}
+
+#[test]
+fn out_of_range_languages_fall_back_to_english() {
+    assert_eq!(i18n::lang_code(-1), "en");
+    assert_eq!(i18n::lang_code(1000), "en");
+    assert_eq!(i18n::lang_index("xx"), LANG_EN);
+}
+
+#[test]
+fn language_indexes_round_trip() {
+    for (i, code) in i18n::languages().iter().enumerate() {
+        assert_eq!(i18n::lang_index(code), i as i32);
+        assert_eq!(i18n::lang_code(i as i32), *code);
+    }
+    assert_eq!(i18n::lang_index("pt_BR.UTF-8"), LANG_EN);
+    assert_eq!(i18n::lang_index("PT"), LANG_PT);
+}
+
+#[test]
+fn system_locale_picks_the_closest_offered_language() {
+    assert_eq!(i18n::system_language("es-ES").as_deref(), Some("es"));
+    assert_eq!(i18n::system_language("pt_BR.UTF-8").as_deref(), Some("pt"));
+    assert_eq!(i18n::system_language("ja").as_deref(), Some("ja"));
+    assert_eq!(i18n::system_language("zh-Hans-CN"), None);
+}
+
+#[test]
+fn system_default_follows_the_os_and_falls_back_to_english() {
+    assert_eq!(i18n::resolve_language(LANG_SYSTEM, Some("de-AT")), "de");
+    assert_eq!(i18n::resolve_language(LANG_SYSTEM, Some("zh-CN")), "en");
+    assert_eq!(i18n::resolve_language(LANG_SYSTEM, None), "en");
+}
+
+#[test]
+fn explicit_language_ignores_the_os() {
+    assert_eq!(i18n::resolve_language(LANG_FR, Some("de-AT")), "fr");
+    assert_eq!(i18n::resolve_language(LANG_EN, Some("es-ES")), "en");
+}
+
+#[test]
+fn languages_are_listed_under_their_own_names() {
+    assert_eq!(i18n::language_name("es"), "Español");
+    assert_eq!(i18n::language_name("de"), "Deutsch");
+    assert_eq!(i18n::language_name("ja"), "日本語");
+}
+
+#[test]
+fn click_letters_follow_the_language() {
+    assert_eq!(i18n::click_letter_in("en", true), 'L');
+    assert_eq!(i18n::click_letter_in("es", false), 'D');
+    assert_eq!(i18n::click_letter_in("fr", true), 'G');
+    assert_eq!(i18n::click_letter_in("pt", true), 'E');
+}
+
+#[test]
+fn custom_click_letters_replace_the_language_letter() {
+    assert_eq!(i18n::click_label("LMB", true), "LMB");
+    assert_eq!(i18n::click_label("→", false), "→");
+}
+
+#[test]
+fn tr_key_reads_builtin_resources() {
+    assert_eq!(tr_key("Radius (px)", true), "Radio (px)");
+    assert_eq!(tr_key("Radius (px)", false), "Radius (px)");
+    assert_eq!(i18n::tr_in("es", "Quit app"), tr_key("Quit app", true));
+    assert_eq!(i18n::lang_code(LANG_ES), "es");
+}
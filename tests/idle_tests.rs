@@ -0,0 +1,92 @@
+//! Tests for the cursor idle auto-hide.
+
+use lumbus::model::constants::*;
+use lumbus::model::IdleFade;
+
+fn approx_eq(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-6
+}
+
+/// Feeds a resting cursor at 60 fps from `from` to `to` seconds.
+fn rest(idle: &mut IdleFade, from: f64, to: f64, active: bool) -> f64 {
+    let mut t = from;
+    let mut alpha = idle.alpha();
+    while t <= to {
+        alpha = idle.update(10.0, 10.0, active, t, DEFAULT_IDLE_HIDE_SECS);
+        t += 1.0 / 60.0;
+    }
+    alpha
+}
+
+// === Fade-Out Tests ===
+
+#[test]
+fn new_tracker_is_fully_visible() {
+    let mut idle = IdleFade::new();
+    assert!(approx_eq(idle.alpha(), 1.0));
+    assert!(approx_eq(
+        idle.update(0.0, 0.0, false, 0.0, DEFAULT_IDLE_HIDE_SECS),
+        1.0
+    ));
+}
+
+#[test]
+fn stays_visible_before_the_delay() {
+    let mut idle = IdleFade::new();
+    let alpha = rest(&mut idle, 0.0, DEFAULT_IDLE_HIDE_SECS - 0.1, false);
+    assert!(approx_eq(alpha, 1.0));
+}
+
+#[test]
+fn fades_out_after_the_delay() {
+    let mut idle = IdleFade::new();
+    let mid = rest(
+        &mut idle,
+        0.0,
+        DEFAULT_IDLE_HIDE_SECS + IDLE_FADE_OUT_SECS / 2.0,
+        false,
+    );
+    assert!(mid > 0.0 && mid < 1.0);
+    let end = rest(
+        &mut idle,
+        DEFAULT_IDLE_HIDE_SECS + IDLE_FADE_OUT_SECS / 2.0,
+        DEFAULT_IDLE_HIDE_SECS + IDLE_FADE_OUT_SECS + 0.1,
+        false,
+    );
+    assert!(approx_eq(end, 0.0));
+}
+
+#[test]
+fn held_button_keeps_it_visible() {
+    let mut idle = IdleFade::new();
+    let alpha = rest(&mut idle, 0.0, DEFAULT_IDLE_HIDE_SECS * 2.0, true);
+    assert!(approx_eq(alpha, 1.0));
+}
+
+// === Fade-In Tests ===
+
+#[test]
+fn movement_fades_back_in() {
+    let mut idle = IdleFade::new();
+    let hidden_at = DEFAULT_IDLE_HIDE_SECS + IDLE_FADE_OUT_SECS + 0.1;
+    assert!(approx_eq(rest(&mut idle, 0.0, hidden_at, false), 0.0));
+
+    let first = idle.update(20.0, 20.0, false, hidden_at + 0.05, DEFAULT_IDLE_HIDE_SECS);
+    assert!(first > 0.0 && first < 1.0);
+    let back = idle.update(
+        20.0,
+        20.0,
+        false,
+        hidden_at + 0.05 + IDLE_FADE_IN_SECS,
+        DEFAULT_IDLE_HIDE_SECS,
+    );
+    assert!(approx_eq(back, 1.0));
+}
+
+#[test]
+fn reset_shows_the_highlight_again() {
+    let mut idle = IdleFade::new();
+    rest(&mut idle, 0.0, DEFAULT_IDLE_HIDE_SECS + 1.0, false);
+    idle.reset();
+    assert!(approx_eq(idle.alpha(), 1.0));
+}
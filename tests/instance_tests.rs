@@ -0,0 +1,70 @@
+//! Tests for the single-instance handoff.
+
+use lumbus::model::constants::{RESET_FLAG, SOAK_FLAG};
+use lumbus::model::{
+    decode_args, encode_args, fresh_start_flag, handoff, restarted, DiagnosticsConfig, Handoff,
+    RESTART_FLAG,
+};
+
+#[test]
+fn launch_without_requests_opens_settings() {
+    assert!(handoff::<&str>(&[]).opens_settings());
+    assert!(handoff(&["--safe-mode"]).opens_settings());
+}
+
+#[test]
+fn urls_are_handed_over_in_order() {
+    let args = ["--reset", "lumbus://toggle", "LUMBUS://set?radius=60"];
+    assert_eq!(
+        handoff(&args),
+        Handoff {
+            urls: vec![
+                "lumbus://toggle".to_string(),
+                "LUMBUS://set?radius=60".to_string()
+            ],
+            ..Handoff::default()
+        }
+    );
+}
+
+#[test]
+fn diagnostics_are_of_the_running_instance() {
+    let request = handoff(&["--diagnostics=overlay"]);
+    assert!(!request.opens_settings());
+    assert_eq!(
+        request.diagnostics,
+        Some(DiagnosticsConfig { overlay: true })
+    );
+}
+
+#[test]
+fn reset_and_soak_are_not_handed_off() {
+    assert_eq!(fresh_start_flag(&["--reset"]), Some(RESET_FLAG));
+    assert_eq!(fresh_start_flag(&["--soak=5"]), Some(SOAK_FLAG));
+    assert_eq!(
+        fresh_start_flag(&["--diagnostics", "lumbus://toggle"]),
+        None
+    );
+}
+
+#[test]
+fn other_schemes_are_not_urls() {
+    assert!(handoff(&["https://example.com"]).opens_settings());
+}
+
+#[test]
+fn args_round_trip() {
+    let args = vec![
+        "lumbus://toggle".to_string(),
+        "lumbus://set?radius=60&borderWidth=4".to_string(),
+    ];
+    assert_eq!(decode_args(&encode_args(&args)), args);
+    assert!(decode_args(&encode_args::<&str>(&[])).is_empty());
+}
+
+#[test]
+fn restart_flag_is_detected() {
+    assert!(restarted([RESTART_FLAG]));
+    assert!(!restarted(["--reset"]));
+    assert!(handoff(&[RESTART_FLAG]).opens_settings());
+}
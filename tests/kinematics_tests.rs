@@ -0,0 +1,122 @@
+//! Tests for cursor kinematics and the speed-based opacity ramp.
+
+use lumbus::model::constants::*;
+use lumbus::model::kinematics::{speed_fade_alpha, speed_size_scale, Kinematics};
+
+fn approx_eq(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-6
+}
+
+// === Speed Tracking Tests ===
+
+#[test]
+fn new_tracker_is_at_rest() {
+    let mut k = Kinematics::new();
+    assert!(approx_eq(k.speed(), 0.0));
+    assert!(approx_eq(k.update(100.0, 100.0, 0.0), 0.0));
+}
+
+#[test]
+fn steady_motion_converges_to_true_speed() {
+    let mut k = Kinematics::new();
+    let mut speed = 0.0;
+    for i in 0..120 {
+        let t = i as f64 / 60.0;
+        speed = k.update(t * 1000.0, 0.0, t);
+    }
+    assert!((speed - 1000.0).abs() < 1.0);
+}
+
+#[test]
+fn resting_cursor_decays_to_zero() {
+    let mut k = Kinematics::new();
+    for i in 0..30 {
+        let t = i as f64 / 60.0;
+        k.update(t * 3000.0, 0.0, t);
+    }
+    let moving = k.speed();
+    for i in 30..90 {
+        k.update(29.0 / 60.0 * 3000.0, 0.0, i as f64 / 60.0);
+    }
+    assert!(moving > 1000.0);
+    assert!(k.speed() < 1.0);
+}
+
+#[test]
+fn non_increasing_time_is_ignored() {
+    let mut k = Kinematics::new();
+    k.update(0.0, 0.0, 1.0);
+    let s = k.update(500.0, 0.0, 1.0);
+    assert!(approx_eq(s, 0.0));
+}
+
+#[test]
+fn reset_forgets_history() {
+    let mut k = Kinematics::new();
+    k.update(0.0, 0.0, 0.0);
+    k.update(1000.0, 0.0, 0.1);
+    k.reset();
+    assert!(approx_eq(k.speed(), 0.0));
+    assert!(approx_eq(k.update(5000.0, 0.0, 0.2), 0.0));
+}
+
+// === Opacity Ramp Tests ===
+
+#[test]
+fn slow_movement_is_fully_opaque() {
+    assert!(approx_eq(speed_fade_alpha(0.0), 1.0));
+    assert!(approx_eq(speed_fade_alpha(SPEED_FADE_SLOW_PX_S), 1.0));
+}
+
+#[test]
+fn fast_movement_reaches_minimum_alpha() {
+    assert!(approx_eq(
+        speed_fade_alpha(SPEED_FADE_FAST_PX_S),
+        SPEED_FADE_MIN_ALPHA
+    ));
+    assert!(approx_eq(speed_fade_alpha(1e9), SPEED_FADE_MIN_ALPHA));
+}
+
+#[test]
+fn ramp_is_monotonic() {
+    let mut prev = speed_fade_alpha(0.0);
+    for i in 1..=100 {
+        let a = speed_fade_alpha(i as f64 * 50.0);
+        assert!(a <= prev + 1e-12);
+        prev = a;
+    }
+}
+
+#[test]
+fn ramp_midpoint_is_halfway() {
+    let mid = (SPEED_FADE_SLOW_PX_S + SPEED_FADE_FAST_PX_S) / 2.0;
+    let expected = (1.0 + SPEED_FADE_MIN_ALPHA) / 2.0;
+    assert!(approx_eq(speed_fade_alpha(mid), expected));
+}
+
+// === Size Scaling Tests ===
+
+#[test]
+fn slow_movement_keeps_the_size() {
+    assert!(approx_eq(speed_size_scale(0.0), 1.0));
+    assert!(approx_eq(speed_size_scale(SPEED_FADE_SLOW_PX_S), 1.0));
+}
+
+#[test]
+fn fast_movement_reaches_maximum_scale() {
+    assert!(approx_eq(
+        speed_size_scale(SPEED_FADE_FAST_PX_S),
+        SPEED_SIZE_MAX_SCALE
+    ));
+    assert!(approx_eq(speed_size_scale(1e9), SPEED_SIZE_MAX_SCALE));
+}
+
+#[test]
+fn scale_grows_monotonically() {
+    let mut prev = speed_size_scale(0.0);
+    for i in 1..=100 {
+        let s = speed_size_scale(i as f64 * 50.0);
+        assert!(s >= prev - 1e-12);
+        prev = s;
+    }
+}
@@ -0,0 +1,59 @@
+//! Tests for bringing the overlay up and reloading its settings, run on the
+//! headless backend.
+
+use lumbus::backend::{load_settings, start_overlay, HeadlessBackend, OverlayBackend};
+use lumbus::events::AppEvent;
+use lumbus::model::constants::*;
+use lumbus::model::{HotkeyPlatform, Preferences};
+
+#[test]
+fn startup_covers_every_screen_with_the_saved_settings() {
+    let prefs = Preferences {
+        radius: 80.0,
+        exclude_from_capture: true,
+        ..Preferences::default()
+    };
+    let mut backend = HeadlessBackend::with_prefs(prefs, HotkeyPlatform::Windows);
+    backend.screens = 3;
+    assert_eq!(start_overlay(&mut backend), 3);
+    assert_eq!(backend.windows(), 3);
+    assert_eq!(backend.state().radius, 80.0);
+    assert!(backend.excluded_from_capture());
+    // Nothing is drawn before the windows are shown
+    assert!(backend.frames().is_empty());
+}
+
+#[test]
+fn reloaded_settings_are_validated() {
+    let mut backend = HeadlessBackend::new(HotkeyPlatform::MacOs);
+    backend.prefs.radius = 1e9;
+    backend.prefs.highlight_style = 42;
+    let state = load_settings(&mut backend);
+    assert_eq!(state.radius, MAX_RADIUS);
+    assert_eq!(state.highlight_style, HIGHLIGHT_STYLE_CIRCLE);
+    assert_eq!(backend.state(), &state);
+}
+
+#[test]
+fn capture_exclusion_follows_the_preference() {
+    let mut backend = HeadlessBackend::new(HotkeyPlatform::MacOs);
+    backend.dispatch_all([AppEvent::SetPreference(
+        PREF_EXCLUDE_FROM_CAPTURE.to_string(),
+        1.0,
+    )]);
+    assert!(backend.excluded_from_capture());
+    backend.dispatch_all([AppEvent::SetPreference(
+        PREF_EXCLUDE_FROM_CAPTURE.to_string(),
+        0.0,
+    )]);
+    assert!(!backend.excluded_from_capture());
+}
+
+#[test]
+fn the_coordinates_readout_shows_the_cursor() {
+    let mut backend = HeadlessBackend::new(HotkeyPlatform::MacOs);
+    backend.input.pointer = (120.0, 45.0);
+    assert_eq!(backend.cursor_position(), (120.0, 45.0));
+    backend.dispatch_all([AppEvent::ToggleCoordinates]);
+    assert!(backend.input.coordinates.is_some());
+}
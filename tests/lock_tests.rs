@@ -0,0 +1,63 @@
+//! Tests for the presentation lock.
+
+use lumbus::events::AppEvent;
+use lumbus::model::PresentationLock;
+
+// === State Tests ===
+
+#[test]
+fn new_lock_is_unlocked() {
+    let lock = PresentationLock::new();
+    assert!(!lock.is_locked());
+    assert!(lock.settings_available());
+}
+
+#[test]
+fn toggle_flips_and_returns_new_state() {
+    let mut lock = PresentationLock::new();
+    assert!(lock.toggle());
+    assert!(lock.is_locked());
+    assert!(!lock.settings_available());
+    assert!(!lock.toggle());
+    assert!(!lock.is_locked());
+}
+
+// === Event Filter Tests ===
+
+#[test]
+fn unlocked_allows_every_event() {
+    let lock = PresentationLock::new();
+    assert!(lock.allows(&AppEvent::OpenSettings));
+    assert!(lock.allows(&AppEvent::ToggleOverlay));
+    assert!(lock.allows(&AppEvent::TogglePresentationLock));
+}
+
+#[test]
+fn locked_ignores_settings_events() {
+    let mut lock = PresentationLock::new();
+    lock.toggle();
+    assert!(!lock.allows(&AppEvent::OpenSettings));
+}
+
+#[test]
+fn locked_keeps_presentation_controls() {
+    let mut lock = PresentationLock::new();
+    lock.toggle();
+    for event in [
+        AppEvent::ToggleOverlay,
+        AppEvent::ShowHelp,
+        AppEvent::ZoomIn,
+        AppEvent::ZoomOut,
+        AppEvent::RequestQuit,
+        AppEvent::SettingsClosed,
+    ] {
+        assert!(lock.allows(&event), "{:?} should pass", event);
+    }
+}
+
+#[test]
+fn locked_can_always_be_unlocked() {
+    let mut lock = PresentationLock::new();
+    lock.toggle();
+    assert!(lock.allows(&AppEvent::TogglePresentationLock));
+}
@@ -0,0 +1,17 @@
+//! Tests for the log level setting.
+
+use lumbus::logging::log_level;
+use tracing_subscriber::filter::LevelFilter;
+
+#[test]
+fn log_level_defaults_to_info() {
+    assert_eq!(log_level(None), LevelFilter::INFO);
+    assert_eq!(log_level(Some("chatty")), LevelFilter::INFO);
+}
+
+#[test]
+fn log_level_reads_level_names() {
+    assert_eq!(log_level(Some("debug")), LevelFilter::DEBUG);
+    assert_eq!(log_level(Some(" WARN ")), LevelFilter::WARN);
+    assert_eq!(log_level(Some("off")), LevelFilter::OFF);
+}
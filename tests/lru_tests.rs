@@ -0,0 +1,75 @@
+//! Tests for the least-recently-used cache of the overlay renderers.
+
+use lumbus::render::lru::LruCache;
+
+#[test]
+fn get_returns_inserted_value() {
+    let mut cache = LruCache::new(2);
+    cache.insert("L", 1);
+    assert_eq!(cache.get(&"L"), Some(&1));
+    assert_eq!(cache.get(&"R"), None);
+}
+
+#[test]
+fn insert_replaces_value_of_same_key() {
+    let mut cache = LruCache::new(2);
+    cache.insert("L", 1);
+    cache.insert("L", 2);
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.get(&"L"), Some(&2));
+}
+
+#[test]
+fn full_cache_drops_least_recently_used() {
+    let mut cache = LruCache::new(2);
+    cache.insert("L", 1);
+    cache.insert("R", 2);
+    // Using L makes R the least recently used
+    assert!(cache.get(&"L").is_some());
+    cache.insert("x2", 3);
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get(&"R"), None);
+    assert_eq!(cache.get(&"L"), Some(&1));
+    assert_eq!(cache.get(&"x2"), Some(&3));
+}
+
+#[test]
+fn zero_capacity_still_keeps_one_value() {
+    let mut cache = LruCache::new(0);
+    cache.insert("L", 1);
+    assert_eq!(cache.get(&"L"), Some(&1));
+    cache.insert("R", 2);
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.get(&"R"), Some(&2));
+}
+
+#[test]
+fn get_or_insert_with_makes_value_once() {
+    let mut cache = LruCache::new(2);
+    let mut made = 0;
+    for _ in 0..3 {
+        let value = cache.get_or_insert_with("L", || {
+            made += 1;
+            Some(7)
+        });
+        assert_eq!(value, Some(&7));
+    }
+    assert_eq!(made, 1);
+}
+
+#[test]
+fn get_or_insert_with_stores_nothing_on_none() {
+    let mut cache: LruCache<&str, i32> = LruCache::new(2);
+    assert_eq!(cache.get_or_insert_with("🙂", || None), None);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn clear_drops_every_value() {
+    let mut cache = LruCache::new(2);
+    cache.insert("L", 1);
+    cache.insert("R", 2);
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(cache.get(&"L"), None);
+}
@@ -0,0 +1,91 @@
+//! Tests for the admin-managed configuration layer.
+
+use lumbus::model::constants::*;
+use lumbus::model::{ManagedConfig, MANAGED_KEYS};
+
+// === Precedence Tests ===
+
+#[test]
+fn empty_layer_uses_user_then_builtin() {
+    let managed = ManagedConfig::new();
+    assert!(managed.is_empty());
+    assert_eq!(managed.resolve(PREF_RADIUS, Some(40.0), 50.0), 40.0);
+    assert_eq!(managed.resolve(PREF_RADIUS, None, 50.0), 50.0);
+}
+
+#[test]
+fn managed_default_sits_below_user_value() {
+    let mut managed = ManagedConfig::new();
+    managed.set_default(PREF_RADIUS, 30.0);
+    assert_eq!(managed.resolve(PREF_RADIUS, None, 50.0), 30.0);
+    assert_eq!(managed.resolve(PREF_RADIUS, Some(40.0), 50.0), 40.0);
+}
+
+#[test]
+fn locked_value_overrides_user_and_default() {
+    let mut managed = ManagedConfig::new();
+    managed.set_default(PREF_HIGHLIGHT_STYLE, 0.0);
+    managed.lock(PREF_HIGHLIGHT_STYLE, 1.0);
+    assert!(managed.is_locked(PREF_HIGHLIGHT_STYLE));
+    assert_eq!(managed.resolve(PREF_HIGHLIGHT_STYLE, Some(0.0), 0.0), 1.0);
+    assert_eq!(managed.resolve(PREF_HIGHLIGHT_STYLE, None, 0.0), 1.0);
+}
+
+// === Validation Tests ===
+
+#[test]
+fn rejects_unknown_keys_and_non_finite_values() {
+    let mut managed = ManagedConfig::new();
+    assert!(!managed.set_default(PREF_SESSION, 1.0));
+    assert!(!managed.lock("bogus", 1.0));
+    assert!(!managed.set_default(PREF_RADIUS, f64::NAN));
+    assert!(!managed.lock(PREF_BORDER, f64::INFINITY));
+    assert!(managed.is_empty());
+}
+
+#[test]
+fn every_managed_key_is_accepted() {
+    let mut managed = ManagedConfig::new();
+    for key in MANAGED_KEYS {
+        assert!(managed.lock(key, 1.0));
+        assert!(managed.is_locked(key));
+    }
+    assert_eq!(managed.locked().count(), MANAGED_KEYS.len());
+}
+
+#[test]
+fn the_language_is_a_locale_tag_or_a_builtin_code() {
+    let mut managed = ManagedConfig::new();
+    assert!(managed.lock_text(PREF_LANG, "fr"));
+    assert_eq!(managed.locked_value(PREF_LANG), Some(LANG_FR as f64));
+    assert!(managed.set_default_text(PREF_LANG, "-1"));
+    assert_eq!(managed.default_value(PREF_LANG), Some(LANG_SYSTEM as f64));
+    assert!(managed.set_default_text(PREF_RADIUS, " 40 "));
+    assert_eq!(managed.default_value(PREF_RADIUS), Some(40.0));
+    assert!(!managed.lock_text(PREF_RADIUS, "big"));
+
+    // Languages past the built-in ones have a different index on each
+    // machine
+    let runtime = BUILTIN_LANGUAGES.len() as f64;
+    assert!(!managed.lock(PREF_LANG, runtime));
+    assert!(!managed.set_default_text(PREF_LANG, &runtime.to_string()));
+}
+
+// === Merge Tests ===
+
+#[test]
+fn merge_lets_higher_source_win_per_key() {
+    let mut file = ManagedConfig::new();
+    file.set_default(PREF_RADIUS, 30.0);
+    file.set_default(PREF_BORDER, 2.0);
+    file.lock(PREF_LANG, 0.0);
+
+    let mut policy = ManagedConfig::new();
+    policy.set_default(PREF_RADIUS, 60.0);
+    policy.lock(PREF_LANG, 1.0);
+
+    file.merge(&policy);
+    assert_eq!(file.default_value(PREF_RADIUS), Some(60.0));
+    assert_eq!(file.default_value(PREF_BORDER), Some(2.0));
+    assert_eq!(file.locked_value(PREF_LANG), Some(1.0));
+}
@@ -44,9 +44,9 @@ fn overlay_state_default_fill_transparency() {
 }
 
 #[test]
-fn overlay_state_default_lang_is_english() {
+fn overlay_state_default_lang_follows_system() {
     let state = OverlayState::default();
-    assert_eq!(state.lang, LANG_EN);
+    assert_eq!(state.lang, LANG_SYSTEM);
 }
 
 #[test]
@@ -61,6 +61,57 @@ fn overlay_state_default_display_mode_is_circle() {
     assert_eq!(state.display_mode, DISPLAY_MODE_CIRCLE);
 }
 
+#[test]
+fn overlay_state_default_highlight_style_is_circle() {
+    let state = OverlayState::default();
+    assert_eq!(state.highlight_style, HIGHLIGHT_STYLE_CIRCLE);
+    assert!(!state.is_laser());
+}
+
+#[test]
+fn overlay_state_default_screen_frame_is_off() {
+    let state = OverlayState::default();
+    assert!(!state.screen_frame);
+}
+
+#[test]
+fn overlay_state_default_speed_fade_is_off() {
+    let state = OverlayState::default();
+    assert!(!state.speed_fade);
+}
+
+#[test]
+fn overlay_state_default_speed_size_is_off() {
+    let state = OverlayState::default();
+    assert!(!state.speed_size);
+}
+
+#[test]
+fn overlay_state_default_click_burst_is_off() {
+    let state = OverlayState::default();
+    assert!(!state.click_burst);
+    assert!(approx_eq(state.click_burst_ms, DEFAULT_CLICK_BURST_MS));
+}
+
+#[test]
+fn overlay_state_default_idle_hide_is_off() {
+    let state = OverlayState::default();
+    assert!(!state.idle_hide);
+    assert!(approx_eq(state.idle_hide_secs, DEFAULT_IDLE_HIDE_SECS));
+}
+
+#[test]
+fn overlay_state_default_motion_follows_system() {
+    let state = OverlayState::default();
+    assert_eq!(state.motion, MOTION_SYSTEM);
+}
+
+#[test]
+fn overlay_state_default_halo_is_off() {
+    let state = OverlayState::default();
+    assert!(!state.halo);
+}
+
 // === Validation Tests ===
 
 #[test]
@@ -135,6 +186,41 @@ fn validate_clamps_stroke_r_above_one() {
     assert!(approx_eq(state.stroke_r, 1.0));
 }
 
+#[test]
+fn validate_trims_custom_click_letters() {
+    let mut state = OverlayState {
+        click_letter_left: "  LMB  ".to_string(),
+        click_letter_right: "Derecho".to_string(),
+        ..OverlayState::default()
+    };
+    state.validate();
+    assert_eq!(state.click_letter_left, "LMB");
+    assert_eq!(state.click_letter_right, "Dere");
+    assert_eq!(state.custom_click_letter(false), "Dere");
+}
+
+#[test]
+fn validate_resets_unknown_letter_weight() {
+    let mut state = OverlayState {
+        letter_font: " Menlo ".to_string(),
+        letter_weight: 650,
+        ..OverlayState::default()
+    };
+    state.validate();
+    assert_eq!(state.letter_font, "Menlo");
+    assert_eq!(state.letter_weight, LETTER_WEIGHT_BOLD);
+}
+
+#[test]
+fn validate_resets_unknown_click_icon() {
+    let mut state = OverlayState {
+        click_icon: 42,
+        ..OverlayState::default()
+    };
+    state.validate();
+    assert_eq!(state.click_icon, CLICK_ICON_NONE);
+}
+
 #[test]
 fn validate_clamps_stroke_g() {
     let mut state = OverlayState::default();
@@ -159,6 +245,146 @@ fn validate_clamps_stroke_a() {
     assert!(approx_eq(state.stroke_a, 0.0));
 }
 
+#[test]
+fn validate_resets_unknown_highlight_style() {
+    let mut state = OverlayState::default();
+    state.highlight_style = 42;
+    state.validate();
+    assert_eq!(state.highlight_style, HIGHLIGHT_STYLE_CIRCLE);
+}
+
+#[test]
+fn validate_clamps_click_burst_interval() {
+    let mut state = OverlayState::default();
+    state.click_burst_ms = 10.0;
+    state.validate();
+    assert!(approx_eq(state.click_burst_ms, MIN_CLICK_BURST_MS));
+    state.click_burst_ms = 5000.0;
+    state.validate();
+    assert!(approx_eq(state.click_burst_ms, MAX_CLICK_BURST_MS));
+}
+
+#[test]
+fn validate_clamps_idle_hide_delay() {
+    let mut state = OverlayState::default();
+    state.idle_hide_secs = 0.0;
+    state.validate();
+    assert!(approx_eq(state.idle_hide_secs, MIN_IDLE_HIDE_SECS));
+    state.idle_hide_secs = 600.0;
+    state.validate();
+    assert!(approx_eq(state.idle_hide_secs, MAX_IDLE_HIDE_SECS));
+}
+
+#[test]
+fn validate_clamps_click_hold() {
+    let mut state = OverlayState::default();
+    assert!(approx_eq(state.click_hold_ms, DEFAULT_CLICK_HOLD_MS));
+    state.click_hold_ms = -50.0;
+    state.validate();
+    assert!(approx_eq(state.click_hold_ms, 0.0));
+    state.click_hold_ms = 5000.0;
+    state.validate();
+    assert!(approx_eq(state.click_hold_ms, MAX_CLICK_HOLD_MS));
+}
+
+#[test]
+fn validate_clamps_hold_ring_time() {
+    let mut state = OverlayState::default();
+    assert!(!state.hold_ring);
+    state.hold_ring_ms = 0.0;
+    state.validate();
+    assert!(approx_eq(state.hold_ring_ms, MIN_HOLD_RING_MS));
+    state.hold_ring_ms = 60_000.0;
+    state.validate();
+    assert!(approx_eq(state.hold_ring_ms, MAX_HOLD_RING_MS));
+}
+
+#[test]
+fn validate_clamps_rainbow_speed() {
+    let mut state = OverlayState::default();
+    assert!(approx_eq(state.rainbow_speed, DEFAULT_RAINBOW_SPEED));
+    state.rainbow_speed = 0.0;
+    state.validate();
+    assert!(approx_eq(state.rainbow_speed, MIN_RAINBOW_SPEED));
+    state.rainbow_speed = 600.0;
+    state.validate();
+    assert!(approx_eq(state.rainbow_speed, MAX_RAINBOW_SPEED));
+}
+
+#[test]
+fn validate_clamps_pulse() {
+    let mut state = OverlayState::default();
+    assert!(approx_eq(state.pulse_amplitude, DEFAULT_PULSE_AMPLITUDE));
+    assert!(approx_eq(state.pulse_period_ms, DEFAULT_PULSE_PERIOD_MS));
+    state.pulse_amplitude = 90.0;
+    state.pulse_period_ms = 10.0;
+    state.validate();
+    assert!(approx_eq(state.pulse_amplitude, MAX_PULSE_AMPLITUDE));
+    assert!(approx_eq(state.pulse_period_ms, MIN_PULSE_PERIOD_MS));
+}
+
+#[test]
+fn validate_clamps_transition_duration() {
+    let mut state = OverlayState::default();
+    assert!(approx_eq(state.transition_ms, DEFAULT_TRANSITION_MS));
+    state.transition_ms = -50.0;
+    state.validate();
+    assert!(approx_eq(state.transition_ms, MIN_TRANSITION_MS));
+    state.transition_ms = 5000.0;
+    state.validate();
+    assert!(approx_eq(state.transition_ms, MAX_TRANSITION_MS));
+}
+
+#[test]
+fn validate_clamps_grid_settings() {
+    let mut state = OverlayState::default();
+    assert!(approx_eq(state.grid_spacing, DEFAULT_GRID_SPACING));
+    assert_eq!(state.grid_subdivisions, DEFAULT_GRID_SUBDIVISIONS);
+    state.grid_spacing = 1.0;
+    state.grid_subdivisions = 0;
+    state.grid_a = 2.0;
+    state.validate();
+    assert!(approx_eq(state.grid_spacing, MIN_GRID_SPACING));
+    assert_eq!(state.grid_subdivisions, 1);
+    assert!(approx_eq(state.grid_a, 1.0));
+    state.grid_spacing = 5000.0;
+    state.grid_subdivisions = 50;
+    state.validate();
+    assert!(approx_eq(state.grid_spacing, MAX_GRID_SPACING));
+    assert_eq!(state.grid_subdivisions, MAX_GRID_SUBDIVISIONS as i32);
+}
+
+#[test]
+fn validate_resets_unknown_motion_mode() {
+    let mut state = OverlayState::default();
+    state.motion = MOTION_FULL;
+    state.validate();
+    assert_eq!(state.motion, MOTION_FULL);
+    state.motion = 7;
+    state.validate();
+    assert_eq!(state.motion, MOTION_SYSTEM);
+}
+
+#[test]
+fn validate_resets_unknown_glow() {
+    let mut state = OverlayState::default();
+    assert_eq!(state.glow, GLOW_OFF);
+    state.glow = GLOW_SHADOW;
+    state.validate();
+    assert_eq!(state.glow, GLOW_SHADOW);
+    state.glow = -1;
+    state.validate();
+    assert_eq!(state.glow, GLOW_OFF);
+}
+
+#[test]
+fn validate_keeps_laser_highlight_style() {
+    let mut state = OverlayState::default();
+    state.highlight_style = HIGHLIGHT_STYLE_LASER;
+    state.validate();
+    assert!(state.is_laser());
+}
+
 // === Helper Method Tests ===
 
 #[test]
@@ -223,3 +449,38 @@ fn overlay_state_equality() {
     state2.radius = 100.0;
     assert_ne!(state1, state2);
 }
+
+// === Serialization Tests ===
+
+#[test]
+fn overlay_state_json_roundtrip() {
+    let mut state = OverlayState::default();
+    state.stroke_a = 0.5;
+    state.fill_transparency_pct = 40.0;
+    let json = serde_json::to_string(&state).unwrap();
+    let back: OverlayState = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, state);
+}
+
+#[test]
+fn overlay_state_missing_fields_use_defaults() {
+    let state: OverlayState = serde_json::from_str(r#"{"radius": 80.0}"#).unwrap();
+    assert!(approx_eq(state.radius, 80.0));
+    assert!(approx_eq(state.stroke_a, DEFAULT_COLOR.3));
+    assert!(state.overlay_enabled);
+}
+
+// === Display Mode Tests ===
+
+#[test]
+fn display_modes_round_trip_through_codes_and_names() {
+    use lumbus::model::DisplayMode;
+    for mode in DisplayMode::ALL {
+        assert_eq!(DisplayMode::from_code(mode.code()), Some(mode));
+        assert_eq!(DisplayMode::from_name(mode.name()), Some(mode));
+    }
+    assert_eq!(DisplayMode::Left.code(), DISPLAY_MODE_LEFT);
+    assert_eq!(DisplayMode::from_name(" RIGHT "), Some(DisplayMode::Right));
+    assert_eq!(DisplayMode::from_code(7), None);
+    assert_eq!(DisplayMode::default(), DisplayMode::Circle);
+}
@@ -0,0 +1,68 @@
+//! Tests for Reduce Motion support.
+
+use lumbus::model::constants::*;
+use lumbus::model::{reduce_motion, IdleFade, Zoom};
+
+fn approx_eq(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-6
+}
+
+// === Mode Tests ===
+
+#[test]
+fn system_mode_follows_the_os_setting() {
+    assert!(reduce_motion(MOTION_SYSTEM, true));
+    assert!(!reduce_motion(MOTION_SYSTEM, false));
+}
+
+#[test]
+fn manual_modes_override_the_os_setting() {
+    assert!(reduce_motion(MOTION_REDUCED, false));
+    assert!(!reduce_motion(MOTION_FULL, true));
+}
+
+#[test]
+fn unknown_mode_follows_the_os_setting() {
+    assert!(reduce_motion(42, true));
+    assert!(!reduce_motion(-1, false));
+}
+
+// === Instant State Changes ===
+
+#[test]
+fn idle_snap_hides_without_fading() {
+    let mut idle = IdleFade::new();
+    idle.update(10.0, 10.0, false, 0.0, DEFAULT_IDLE_HIDE_SECS);
+    idle.update(
+        10.0,
+        10.0,
+        false,
+        DEFAULT_IDLE_HIDE_SECS - 0.01,
+        DEFAULT_IDLE_HIDE_SECS,
+    );
+    let t = DEFAULT_IDLE_HIDE_SECS + 0.01;
+    let faded = idle.update(10.0, 10.0, false, t, DEFAULT_IDLE_HIDE_SECS);
+    assert!(faded > 0.0);
+    assert!(approx_eq(idle.snap(), 0.0));
+}
+
+#[test]
+fn idle_snap_shows_without_fading() {
+    let mut idle = IdleFade::new();
+    idle.update(10.0, 10.0, false, 0.0, DEFAULT_IDLE_HIDE_SECS);
+    idle.update(10.0, 10.0, false, 10.0, DEFAULT_IDLE_HIDE_SECS);
+    assert!(approx_eq(idle.snap(), 0.0));
+
+    let shown = idle.update(20.0, 20.0, false, 10.01, DEFAULT_IDLE_HIDE_SECS);
+    assert!(shown < 1.0);
+    assert!(approx_eq(idle.snap(), 1.0));
+}
+
+#[test]
+fn zoom_snap_jumps_to_the_cursor() {
+    let mut zoom = Zoom::new();
+    zoom.zoom_in();
+    zoom.follow(0.0, 0.0, 0.0);
+    assert_eq!(zoom.snap(500.0, 300.0, 0.016), (500.0, 300.0));
+    assert_eq!(zoom.focus(), Some((500.0, 300.0)));
+}
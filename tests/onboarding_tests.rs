@@ -0,0 +1,136 @@
+//! Tests for the first-run walkthrough.
+
+use lumbus::i18n::{set_language, tr, tr_args};
+use lumbus::model::constants::PREF_ONBOARDING_DONE;
+use lumbus::model::{Onboarding, OnboardingStep, OverlayState, Permission, Preferences};
+use lumbus::settings::{OnboardingPanel, SettingsStore};
+
+/// Store with nothing behind it but the permission status (`None`: the
+/// OS asks for no permission).
+struct PermissionStore {
+    granted: Option<bool>,
+}
+
+impl SettingsStore for PermissionStore {
+    fn load_state(&self) -> OverlayState {
+        OverlayState::default()
+    }
+
+    fn set_double(&mut self, _key: &str, _value: f64) {}
+
+    fn set_int(&mut self, _key: &str, _value: i32) {}
+
+    fn set_string(&mut self, _key: &str, _value: &str) {}
+
+    fn is_locked(&self, _key: &str) -> bool {
+        false
+    }
+
+    fn flush(&mut self) {}
+
+    fn frame_color(&self) -> (f64, f64, f64, f64) {
+        (0.0, 0.0, 0.0, 0.0)
+    }
+
+    fn set_frame_color(&mut self, _color: (f64, f64, f64, f64)) {}
+
+    fn system_locale(&self) -> Option<String> {
+        None
+    }
+
+    fn settings_changed(&mut self) {}
+
+    fn permissions(&self) -> &'static [Permission] {
+        match self.granted {
+            Some(_) => &Permission::ALL,
+            None => &[],
+        }
+    }
+
+    fn permission_granted(&self, _permission: Permission) -> bool {
+        self.granted.unwrap_or(true)
+    }
+}
+
+fn steps(mut walkthrough: Onboarding) -> Vec<OnboardingStep> {
+    let mut steps = vec![walkthrough.step()];
+    while walkthrough.advance() {
+        steps.push(walkthrough.step());
+    }
+    steps
+}
+
+#[test]
+fn permission_step_only_when_asked_for() {
+    use OnboardingStep::*;
+    assert_eq!(
+        steps(Onboarding::new(true)),
+        [Welcome, Hotkeys, Permission, Appearance]
+    );
+    assert_eq!(
+        steps(Onboarding::new(false)),
+        [Welcome, Hotkeys, Appearance]
+    );
+}
+
+#[test]
+fn back_and_advance_stay_within_the_steps() {
+    let mut walkthrough = Onboarding::new(false);
+    assert!(walkthrough.is_first());
+    walkthrough.back();
+    assert_eq!(walkthrough.step(), OnboardingStep::Welcome);
+    assert_eq!(walkthrough.position(), (1, 3));
+
+    assert!(walkthrough.advance());
+    assert!(walkthrough.advance());
+    assert!(walkthrough.is_last());
+    assert!(!walkthrough.advance());
+    assert_eq!(walkthrough.position(), (3, 3));
+
+    walkthrough.back();
+    assert_eq!(walkthrough.step(), OnboardingStep::Hotkeys);
+}
+
+#[test]
+fn panel_asks_for_permission_only_when_missing() {
+    for (granted, shown) in [(Some(false), true), (Some(true), false), (None, false)] {
+        let mut panel = OnboardingPanel::new(&PermissionStore { granted });
+        let mut seen = false;
+        while !panel.finished() {
+            seen |= panel.step() == OnboardingStep::Permission;
+            panel.next();
+        }
+        assert_eq!(seen, shown, "{granted:?}");
+    }
+}
+
+#[test]
+fn skip_finishes_the_walkthrough() {
+    let mut panel = OnboardingPanel::new(&PermissionStore { granted: None });
+    panel.next();
+    assert!(!panel.finished());
+    panel.skip();
+    assert!(panel.finished());
+}
+
+#[test]
+fn completion_is_stored_in_the_preferences() {
+    let mut prefs = Preferences::default();
+    assert!(!prefs.onboarding_done);
+    prefs.set_number(PREF_ONBOARDING_DONE, 1.0);
+    assert!(prefs.onboarding_done);
+    let loaded = Preferences::decode(&prefs.encode(), Preferences::default());
+    assert_eq!(loaded.number(PREF_ONBOARDING_DONE), Some(1.0));
+}
+
+#[test]
+fn step_titles_and_paragraphs_are_translated() {
+    set_language("es");
+    assert_eq!(tr(OnboardingStep::Welcome.title()), "Bienvenido a Lumbus");
+    assert_eq!(
+        tr_args("onboarding-step", &[("step", "2"), ("total", "4")]).as_deref(),
+        Some("Paso 2 de 4")
+    );
+    assert!(tr_args("onboarding-permission", &[]).is_some());
+    set_language("en");
+}
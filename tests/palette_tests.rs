@@ -0,0 +1,57 @@
+//! Tests for the saved color palette.
+
+use lumbus::model::constants::MAX_SAVED_COLORS;
+use lumbus::model::{ColorPalette, BUILTIN_SWATCHES};
+
+#[test]
+fn swatches_start_with_the_builtins() {
+    let mut palette = ColorPalette::new();
+    assert!(palette.add((0.0, 0.0, 0.0, 1.0)));
+    let swatches: Vec<_> = palette.swatches().collect();
+    assert_eq!(&swatches[..BUILTIN_SWATCHES.len()], &BUILTIN_SWATCHES);
+    assert_eq!(swatches.last(), Some(&(0.0, 0.0, 0.0, 1.0)));
+}
+
+#[test]
+fn colors_already_offered_are_not_saved_again() {
+    let mut palette = ColorPalette::new();
+    assert!(!palette.add(BUILTIN_SWATCHES[2]));
+    assert!(palette.add((0.5, 0.5, 0.5, 1.0)));
+    // Same hex once rounded to bytes
+    assert!(!palette.add((0.501, 0.5, 0.5, 1.0)));
+    assert_eq!(palette.colors().len(), 1);
+}
+
+#[test]
+fn saving_past_the_limit_drops_the_oldest() {
+    let mut palette = ColorPalette::new();
+    for i in 0..=MAX_SAVED_COLORS {
+        palette.add((0.0, 0.0, i as f64 / 255.0, 1.0));
+    }
+    assert_eq!(palette.colors().len(), MAX_SAVED_COLORS);
+    assert_eq!(palette.colors()[0], (0.0, 0.0, 1.0 / 255.0, 1.0));
+}
+
+#[test]
+fn palette_roundtrips_through_its_string_form() {
+    let mut palette = ColorPalette::new();
+    palette.add((0.2, 0.4, 0.6, 1.0));
+    palette.add((1.0, 1.0, 1.0, 0.5));
+    let encoded = palette.encode();
+    assert_eq!(encoded, "#336699;#FFFFFF80");
+    assert_eq!(ColorPalette::decode(&encoded).encode(), encoded);
+
+    // Malformed entries are skipped
+    assert_eq!(ColorPalette::decode("nope;#336699;;").encode(), "#336699");
+    assert!(ColorPalette::decode("").colors().is_empty());
+}
+
+#[test]
+fn remove_ignores_out_of_range_indices() {
+    let mut palette = ColorPalette::new();
+    palette.add((0.2, 0.4, 0.6, 1.0));
+    palette.remove(3);
+    assert_eq!(palette.colors().len(), 1);
+    palette.remove(0);
+    assert!(palette.colors().is_empty());
+}
@@ -0,0 +1,34 @@
+//! Tests for pen and tablet input.
+
+use lumbus::model::constants::PEN_MAX_SCALE;
+use lumbus::model::pen::{is_pen_extra_info, pen_scale, pen_tip_color};
+
+#[test]
+fn pressure_grows_the_highlight() {
+    assert_eq!(pen_scale(0.0), 1.0);
+    assert_eq!(pen_scale(0.5), (1.0 + PEN_MAX_SCALE) / 2.0);
+    assert_eq!(pen_scale(1.0), PEN_MAX_SCALE);
+}
+
+#[test]
+fn pressure_out_of_range_is_clamped() {
+    assert_eq!(pen_scale(-0.3), 1.0);
+    assert_eq!(pen_scale(1.7), PEN_MAX_SCALE);
+    assert_eq!(pen_scale(f64::NAN), 1.0);
+}
+
+#[test]
+fn tip_contrasts_with_the_highlight() {
+    assert_eq!(pen_tip_color((1.0, 0.85, 0.0, 0.8)), (0.0, 0.0, 0.0, 0.8));
+    assert_eq!(pen_tip_color((0.1, 0.1, 0.4, 1.0)), (1.0, 1.0, 1.0, 1.0));
+}
+
+#[test]
+fn pen_messages_are_told_from_touch_and_mouse() {
+    assert!(is_pen_extra_info(0xFF51_5700));
+    assert!(is_pen_extra_info(0xFF51_5701));
+    // Touch sets the 0x80 bit
+    assert!(!is_pen_extra_info(0xFF51_5780));
+    assert!(!is_pen_extra_info(0));
+    assert!(!is_pen_extra_info(0x1234_5678));
+}
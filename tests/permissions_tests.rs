@@ -0,0 +1,128 @@
+//! Tests for the permissions and the features that depend on them.
+
+use lumbus::i18n::{set_language, tr, tr_args};
+use lumbus::model::{OverlayState, Permission, PermissionStatus};
+use lumbus::settings::{SettingsPanel, SettingsStore};
+
+/// Store whose permissions can be granted while the panel is open.
+#[derive(Default)]
+struct PermissionStore {
+    screen_recording: bool,
+    changes: usize,
+}
+
+impl SettingsStore for PermissionStore {
+    fn load_state(&self) -> OverlayState {
+        OverlayState::default()
+    }
+
+    fn set_double(&mut self, _key: &str, _value: f64) {}
+
+    fn set_int(&mut self, _key: &str, _value: i32) {}
+
+    fn set_string(&mut self, _key: &str, _value: &str) {}
+
+    fn is_locked(&self, _key: &str) -> bool {
+        false
+    }
+
+    fn flush(&mut self) {}
+
+    fn frame_color(&self) -> (f64, f64, f64, f64) {
+        (0.0, 0.0, 0.0, 0.0)
+    }
+
+    fn set_frame_color(&mut self, _color: (f64, f64, f64, f64)) {}
+
+    fn system_locale(&self) -> Option<String> {
+        None
+    }
+
+    fn settings_changed(&mut self) {}
+
+    fn permissions(&self) -> &'static [Permission] {
+        &Permission::ALL
+    }
+
+    fn permission_granted(&self, permission: Permission) -> bool {
+        permission == Permission::Accessibility || self.screen_recording
+    }
+
+    fn permissions_changed(&mut self) {
+        self.changes += 1;
+    }
+}
+
+#[test]
+fn everything_is_on_until_checked() {
+    let status = PermissionStatus::default();
+    assert!(status.click_letters());
+    assert!(status.zoom());
+    assert!(status.missing().is_empty());
+}
+
+#[test]
+fn missing_permissions_turn_their_feature_off() {
+    let status = PermissionStatus {
+        accessibility: false,
+        screen_recording: true,
+    };
+    assert!(!status.click_letters());
+    assert!(status.zoom());
+    assert_eq!(status.missing(), [Permission::Accessibility]);
+
+    let status = PermissionStatus {
+        accessibility: true,
+        screen_recording: false,
+    };
+    assert!(status.click_letters());
+    assert!(!status.zoom());
+    assert!(!status.color_sampler());
+    assert!(!status.granted(Permission::ScreenRecording));
+}
+
+#[test]
+fn panel_shows_the_status_of_each_permission() {
+    let store = PermissionStore::default();
+    let panel = SettingsPanel::new(&store);
+    assert_eq!(
+        panel.permissions(),
+        [
+            (Permission::Accessibility, true),
+            (Permission::ScreenRecording, false)
+        ]
+    );
+}
+
+#[test]
+fn recheck_tells_the_store_about_changes() {
+    let mut store = PermissionStore::default();
+    let mut panel = SettingsPanel::new(&store);
+
+    panel.recheck_permissions(&mut store, false);
+    assert_eq!(store.changes, 0);
+
+    store.screen_recording = true;
+    panel.recheck_permissions(&mut store, false);
+    assert_eq!(store.changes, 1);
+    assert!(panel.permissions().iter().all(|(_, granted)| *granted));
+
+    // The Re-check button applies the status even if nothing changed
+    panel.recheck_permissions(&mut store, true);
+    assert_eq!(store.changes, 2);
+}
+
+#[test]
+fn permissions_and_features_are_translated() {
+    set_language("es");
+    assert_eq!(
+        tr(Permission::ScreenRecording.label()),
+        "Grabación de pantalla"
+    );
+    let feature = tr(Permission::Accessibility.feature());
+    assert_eq!(
+        tr_args("permission-missing", &[("feature", &feature)]).as_deref(),
+        Some("No concedido: Letras de clic desactivado")
+    );
+    set_language("en");
+}
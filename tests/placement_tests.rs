@@ -0,0 +1,73 @@
+//! Tests for auxiliary overlay placement helpers.
+
+use lumbus::model::constants::OVERLAY_SNAP_DISTANCE;
+use lumbus::model::placement::{clamp_to_screen, display_key, snap_to_edges, ScreenRect};
+
+fn screen() -> ScreenRect {
+    ScreenRect::new(0.0, 0.0, 1920.0, 1080.0)
+}
+
+// === Clamp Tests ===
+
+#[test]
+fn clamp_keeps_window_inside_screen() {
+    let pos = clamp_to_screen((1900.0, -50.0), (200.0, 100.0), &screen());
+    assert_eq!(pos, (1720.0, 0.0));
+}
+
+#[test]
+fn clamp_leaves_inside_position_untouched() {
+    let pos = clamp_to_screen((300.0, 400.0), (200.0, 100.0), &screen());
+    assert_eq!(pos, (300.0, 400.0));
+}
+
+#[test]
+fn clamp_oversized_window_aligns_to_origin() {
+    let pos = clamp_to_screen((500.0, 500.0), (3000.0, 2000.0), &screen());
+    assert_eq!(pos, (0.0, 0.0));
+}
+
+#[test]
+fn clamp_respects_offset_screens() {
+    let left = ScreenRect::new(-1280.0, 0.0, 1280.0, 800.0);
+    let pos = clamp_to_screen((50.0, 100.0), (200.0, 100.0), &left);
+    assert_eq!(pos, (-200.0, 100.0));
+}
+
+// === Snap Tests ===
+
+#[test]
+fn snap_to_near_edges() {
+    let near = OVERLAY_SNAP_DISTANCE - 1.0;
+    let pos = snap_to_edges((near, 1080.0 - 100.0 - near), (200.0, 100.0), &screen());
+    assert_eq!(pos, (0.0, 980.0));
+}
+
+#[test]
+fn no_snap_away_from_edges() {
+    let far = OVERLAY_SNAP_DISTANCE + 1.0;
+    let pos = snap_to_edges((far, far), (200.0, 100.0), &screen());
+    assert_eq!(pos, (far, far));
+}
+
+#[test]
+fn snap_clamps_offscreen_positions() {
+    let pos = snap_to_edges((-500.0, 2000.0), (200.0, 100.0), &screen());
+    assert_eq!(pos, (0.0, 980.0));
+}
+
+// === Display Key Tests ===
+
+#[test]
+fn display_key_is_rounded_frame() {
+    let key = display_key(&ScreenRect::new(-1920.0, 0.4, 1920.0, 1080.0));
+    assert_eq!(key, "-1920_0_1920_1080");
+}
+
+#[test]
+fn screen_rect_contains_is_half_open() {
+    let s = screen();
+    assert!(s.contains(0.0, 0.0));
+    assert!(!s.contains(1920.0, 10.0));
+    assert!(!s.contains(-1.0, 10.0));
+}
@@ -0,0 +1,338 @@
+//! Tests for the user preferences file.
+
+use lumbus::model::constants::*;
+use lumbus::model::{
+    clamp_number, describe_issues, reset_requested, safe_mode_preferences, safe_mode_requested,
+    ConfigIssue, OverlayState, Preferences,
+};
+
+#[test]
+fn default_values() {
+    let prefs = Preferences::default();
+    assert!((prefs.radius - DEFAULT_DIAMETER / 2.0).abs() < f64::EPSILON);
+    assert_eq!(prefs.lang, "");
+    assert_eq!(prefs.to_state().lang, LANG_SYSTEM);
+}
+
+#[test]
+fn encode_decode_roundtrip() {
+    let prefs = Preferences {
+        radius: 42.0,
+        border_width: 3.0,
+        stroke_r: 0.5,
+        stroke_g: 0.6,
+        stroke_b: 0.7,
+        stroke_a: 1.0,
+        fill_transparency_pct: 50.0,
+        lang: "es".to_string(),
+        highlight_style: HIGHLIGHT_STYLE_LASER,
+        screen_frame: true,
+        speed_fade: true,
+        speed_size: true,
+        click_burst: true,
+        click_burst_ms: 600.0,
+        idle_hide: true,
+        idle_hide_secs: 5.0,
+        click_hold_ms: 300.0,
+        hold_ring: true,
+        hold_ring_ms: 1500.0,
+        motion: MOTION_REDUCED,
+        halo: true,
+        rainbow: true,
+        rainbow_speed: 12.0,
+        glow: GLOW_SHADOW,
+        pulse: true,
+        pulse_amplitude: 25.0,
+        pulse_period_ms: 1500.0,
+        transition_ms: 350.0,
+        grid_spacing: 40.0,
+        grid_subdivisions: 2,
+        grid_r: 1.0,
+        grid_g: 0.0,
+        grid_b: 1.0,
+        grid_a: 0.25,
+        letter_weight: LETTER_WEIGHT_LIGHT,
+        click_icon: CLICK_ICON_STAR,
+        screen_frame_colors: "0_0_1920_1080=#00FF00".to_string(),
+        color_palette: "#336699;#FFFFFF80".to_string(),
+        click_letter_left: "LMB".to_string(),
+        click_letter_right: "→".to_string(),
+        letter_font: "Helvetica Neue".to_string(),
+        session: "help=1".to_string(),
+        hotkey_profile: "presentation".to_string(),
+        hotkey_overrides: "toggle=Ctrl+Alt+H".to_string(),
+        control_server: true,
+        control_port: 8080,
+        control_token: "secret".to_string(),
+        sync_folder: "/Users/me/Dropbox/Lumbus".to_string(),
+        update_check: false,
+        last_update_check: 1_700_000_000.0,
+        onboarding_done: true,
+        toggle_notification: true,
+        exclude_from_capture: true,
+        confirm_quit: false,
+    };
+    let loaded = Preferences::decode(&prefs.encode(), Preferences::default());
+    assert_eq!(loaded, prefs);
+}
+
+#[test]
+fn missing_fields_keep_the_base() {
+    let mut base = Preferences::default();
+    base.set_number(PREF_RADIUS, 30.0);
+    base.set_number(PREF_SPEED_FADE, 1.0);
+    let loaded = Preferences::decode("radius = 45.0\nlang = 1\n", base);
+    assert!((loaded.radius - 45.0).abs() < f64::EPSILON);
+    // Older files store the index of the language
+    assert_eq!(loaded.lang, "es");
+    assert!(loaded.speed_fade);
+    assert_eq!(loaded.highlight_style, HIGHLIGHT_STYLE_CIRCLE);
+    assert!((loaded.click_burst_ms - DEFAULT_CLICK_BURST_MS).abs() < f64::EPSILON);
+    assert_eq!(loaded.motion, MOTION_SYSTEM);
+}
+
+#[test]
+fn the_language_is_stored_as_its_locale_tag() {
+    let mut prefs = Preferences::decode("lang = \"fr\"\n", Preferences::default());
+    assert_eq!(prefs.number(PREF_LANG), Some(LANG_FR as f64));
+    prefs.set_number(PREF_LANG, LANG_JA as f64);
+    assert_eq!(prefs.lang, "ja");
+    assert!(prefs.encode().contains("lang = \"ja\""));
+    prefs.set_number(PREF_LANG, LANG_SYSTEM as f64);
+    assert_eq!(prefs.lang, "");
+    assert_eq!(
+        Preferences::decode("lang = -1\n", Preferences::default()).lang,
+        ""
+    );
+
+    // A language this machine has no resources for follows the system
+    let (prefs, issues) = Preferences::load("lang = \"xx\"\n", Preferences::default());
+    assert_eq!(prefs.lang, "");
+    assert_eq!(prefs.to_state().lang, LANG_SYSTEM);
+    assert_eq!(
+        issues,
+        vec![ConfigIssue::OutOfRange {
+            key: "lang".to_string(),
+            value: "\"xx\"".to_string(),
+            used: "\"\"".to_string(),
+        }]
+    );
+    assert_eq!(
+        Preferences::decode("lang = \"DE\"\n", Preferences::default()).lang,
+        "DE"
+    );
+}
+
+#[test]
+fn whole_numbers_are_read_as_decimals() {
+    let loaded = Preferences::decode("radius = 45\n", Preferences::default());
+    assert!((loaded.radius - 45.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn invalid_file_keeps_the_base() {
+    let mut base = Preferences::default();
+    base.set_number(PREF_RADIUS, 30.0);
+    assert_eq!(Preferences::decode("radius = ", base.clone()), base);
+    assert_eq!(Preferences::decode("radius = \"big\"", base.clone()), base);
+}
+
+#[test]
+fn number_roundtrips_through_set_number() {
+    let mut prefs = Preferences::default();
+    assert!(prefs.set_number(PREF_HIGHLIGHT_STYLE, 1.0));
+    assert!(prefs.set_number(PREF_CLICK_BURST, 1.0));
+    assert_eq!(prefs.number(PREF_HIGHLIGHT_STYLE), Some(1.0));
+    assert_eq!(prefs.number(PREF_CLICK_BURST), Some(1.0));
+    assert!(!prefs.set_number(PREF_SESSION, 1.0));
+    assert_eq!(prefs.number(PREF_SESSION), None);
+}
+
+#[test]
+fn strings_are_separate_from_numbers() {
+    let mut prefs = Preferences::default();
+    assert!(prefs.set_string(PREF_SESSION, "help=1"));
+    assert_eq!(prefs.string(PREF_SESSION), Some("help=1"));
+    assert!(!prefs.set_string(PREF_RADIUS, "40"));
+    assert_eq!(prefs.string(PREF_RADIUS), None);
+}
+
+#[test]
+fn state_roundtrip_keeps_strings() {
+    let mut prefs = Preferences {
+        session: "help=1".to_string(),
+        ..Default::default()
+    };
+    let state = OverlayState {
+        radius: 60.0,
+        halo: true,
+        ..Default::default()
+    };
+    prefs.set_state(&state);
+    assert_eq!(prefs.session, "help=1");
+    assert_eq!(prefs.to_state(), state);
+}
+
+#[test]
+fn loaded_state_is_validated() {
+    let prefs = Preferences {
+        radius: 1e6,
+        ..Default::default()
+    };
+    assert_eq!(prefs.to_state().radius, MAX_RADIUS);
+}
+
+#[test]
+fn valid_file_has_no_issues() {
+    let (_, issues) = Preferences::load(&Preferences::default().encode(), Preferences::default());
+    assert!(issues.is_empty());
+}
+
+#[test]
+fn out_of_range_values_are_clamped() {
+    let (prefs, issues) =
+        Preferences::load("radius = 5000\nstroke_a = -1.0\n", Preferences::default());
+    assert_eq!(prefs.radius, MAX_RADIUS);
+    assert_eq!(prefs.stroke_a, 0.0);
+    assert_eq!(
+        issues,
+        vec![
+            ConfigIssue::OutOfRange {
+                key: "radius".to_string(),
+                value: "5000".to_string(),
+                used: "200.0".to_string(),
+            },
+            ConfigIssue::OutOfRange {
+                key: "stroke_a".to_string(),
+                value: "-1.0".to_string(),
+                used: "0.0".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn invalid_choices_and_non_numbers_use_the_default() {
+    let (prefs, issues) = Preferences::load(
+        "highlight_style = 7\nborder_width = nan\n",
+        Preferences::default(),
+    );
+    assert_eq!(prefs.highlight_style, HIGHLIGHT_STYLE_CIRCLE);
+    assert_eq!(prefs.border_width, DEFAULT_BORDER_WIDTH);
+    assert_eq!(issues.len(), 2);
+}
+
+#[test]
+fn wrong_types_and_unknown_keys_are_ignored() {
+    let mut base = Preferences::default();
+    base.set_number(PREF_RADIUS, 30.0);
+    let (prefs, issues) = Preferences::load(
+        "radius = \"big\"\nhalo = 1\ncolour = \"red\"\nspeed_fade = true\n",
+        base,
+    );
+    assert_eq!(prefs.radius, 30.0);
+    assert!(!prefs.halo);
+    assert!(prefs.speed_fade);
+    assert!(issues.contains(&ConfigIssue::WrongType {
+        key: "radius".to_string(),
+        expected: "a number",
+    }));
+    assert!(issues.contains(&ConfigIssue::WrongType {
+        key: "halo".to_string(),
+        expected: "true or false",
+    }));
+    assert!(issues.contains(&ConfigIssue::UnknownKey {
+        key: "colour".to_string(),
+    }));
+    assert_eq!(issues.len(), 3);
+}
+
+#[test]
+fn syntax_errors_report_the_line() {
+    let (prefs, issues) = Preferences::load("radius = 40\nhalo = \n", Preferences::default());
+    assert_eq!(prefs, Preferences::default());
+    assert!(matches!(issues[..], [ConfigIssue::Syntax { line: 2, .. }]));
+}
+
+#[test]
+fn issues_are_listed_one_per_line() {
+    let issues = [
+        ConfigIssue::UnknownKey {
+            key: "colour".to_string(),
+        },
+        ConfigIssue::OutOfRange {
+            key: "radius".to_string(),
+            value: "5000".to_string(),
+            used: "200.0".to_string(),
+        },
+    ];
+    assert_eq!(
+        describe_issues(&issues),
+        "• `colour`: unknown setting, ignored\n\
+         • `radius` = 5000 is out of range, using 200.0"
+    );
+}
+
+#[test]
+fn clamp_number_applies_the_file_limits() {
+    assert_eq!(clamp_number(PREF_RADIUS, MAX_RADIUS + 100.0), MAX_RADIUS);
+    assert_eq!(clamp_number(PREF_BORDER, MIN_BORDER - 1.0), MIN_BORDER);
+    assert_eq!(clamp_number(PREF_RADIUS, 60.0), 60.0);
+    // Invalid choices fall back to the default
+    assert_eq!(clamp_number(PREF_MOTION, 7.0), MOTION_SYSTEM as f64);
+    assert_eq!(clamp_number(PREF_RADIUS, f64::NAN), DEFAULT_DIAMETER / 2.0);
+}
+
+#[test]
+fn restore_defaults_resets_the_settings_window() {
+    let mut prefs = Preferences {
+        radius: 80.0,
+        halo: true,
+        lang: "es".to_string(),
+        screen_frame_colors: "main=#FF0000FF".to_string(),
+        hotkey_profile: "presentation".to_string(),
+        hotkey_overrides: "quit=F12".to_string(),
+        sync_folder: "/sync".to_string(),
+        onboarding_done: true,
+        toggle_notification: true,
+        confirm_quit: false,
+        ..Preferences::default()
+    };
+    let mut defaults = Preferences::default();
+    defaults.set_number(PREF_BORDER, 6.0);
+    prefs.restore_defaults(&defaults);
+
+    assert_eq!(prefs.to_state(), defaults.to_state());
+    assert_eq!(prefs.border_width, 6.0);
+    assert!(prefs.screen_frame_colors.is_empty());
+    // Not shown in the Settings window
+    assert_eq!(prefs.hotkey_profile, "presentation");
+    assert_eq!(prefs.hotkey_overrides, "quit=F12");
+    assert_eq!(prefs.sync_folder, "/sync");
+    assert!(prefs.onboarding_done);
+    assert!(prefs.toggle_notification);
+    assert!(!prefs.confirm_quit);
+}
+
+#[test]
+fn reset_flag_is_recognized() {
+    assert!(reset_requested(["--reset"]));
+    assert!(reset_requested(["--diagnostics", "--reset"]));
+    assert!(!reset_requested(["--resets", "reset"]));
+    assert!(!reset_requested(Vec::<String>::new()));
+}
+
+#[test]
+fn safe_mode_flag_is_recognized() {
+    assert!(safe_mode_requested(["--safe-mode"]));
+    assert!(safe_mode_requested(["--reset", "--safe-mode"]));
+    assert!(!safe_mode_requested(["--safe", "safe-mode"]));
+}
+
+#[test]
+fn safe_mode_starts_from_defaults_without_walkthrough() {
+    let defaults = Preferences::default();
+    let prefs = safe_mode_preferences(defaults.clone());
+    assert!(prefs.onboarding_done);
+    assert_eq!(prefs.to_state(), defaults.to_state());
+}
@@ -0,0 +1,39 @@
+//! Tests for the pulsing "breathing" animation.
+
+use lumbus::model::constants::*;
+use lumbus::model::pulse::{pulse_phase, pulse_radius};
+
+fn approx_eq(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn phase_cycles_once_per_period() {
+    assert_eq!(pulse_phase(0.0, DEFAULT_PULSE_PERIOD_MS), 0.0);
+    assert!(approx_eq(pulse_phase(0.5, 2000.0), 0.25));
+    assert!(approx_eq(pulse_phase(3.0, 2000.0), 0.5));
+    assert!(approx_eq(pulse_phase(0.25, 500.0), 0.5));
+}
+
+#[test]
+fn phase_stays_in_range() {
+    for i in 0..1000 {
+        let t = 1.7e9 + i as f64 * 0.037;
+        let phase = pulse_phase(t, MIN_PULSE_PERIOD_MS);
+        assert!((0.0..1.0).contains(&phase), "{phase}");
+    }
+}
+
+#[test]
+fn radius_breathes_by_the_amplitude() {
+    assert!(approx_eq(pulse_radius(20.0, 15.0, 0.0), 20.0));
+    assert!(approx_eq(pulse_radius(20.0, 15.0, 0.25), 23.0));
+    assert!(approx_eq(pulse_radius(20.0, 15.0, 0.5), 20.0));
+    assert!(approx_eq(pulse_radius(20.0, 15.0, 0.75), 17.0));
+}
+
+#[test]
+fn radius_stays_positive_at_the_largest_amplitude() {
+    let smallest = pulse_radius(MIN_RADIUS, MAX_PULSE_AMPLITUDE, 0.75);
+    assert!(smallest > 0.0, "{smallest}");
+}
@@ -0,0 +1,72 @@
+//! Tests for the quick-adjust menu entries.
+
+use lumbus::model::constants::*;
+use lumbus::model::{
+    current_look_profile, high_contrast_preset, is_current_radius, look_profile_values,
+    radius_label, resolve_look_profile, OverlayState, Preferences, LOOK_PROFILE_HIGH_CONTRAST,
+    LOOK_PROFILE_STANDARD,
+};
+
+#[test]
+fn quick_radii_are_valid_settings() {
+    let default = Preferences::default().radius;
+    assert_eq!(QUICK_RADII[0], default);
+    for radius in QUICK_RADII {
+        assert!((MIN_RADIUS..=MAX_RADIUS).contains(&radius));
+    }
+}
+
+#[test]
+fn current_radius_is_matched_to_the_nearest_pixel() {
+    assert!(is_current_radius(40.2, 40.0));
+    assert!(!is_current_radius(42.0, 40.0));
+    assert_eq!(radius_label(40.0), "40 px");
+}
+
+#[test]
+fn default_settings_have_the_standard_look() {
+    assert_eq!(
+        current_look_profile(&OverlayState::default()),
+        Some(LOOK_PROFILE_STANDARD)
+    );
+}
+
+#[test]
+fn high_contrast_look_applies_the_preset() {
+    let values = look_profile_values(LOOK_PROFILE_HIGH_CONTRAST);
+    assert_eq!(values, high_contrast_preset().collect::<Vec<_>>());
+
+    let mut prefs = Preferences::default();
+    for (key, value) in values {
+        prefs.set_number(key, value);
+    }
+    assert_eq!(
+        current_look_profile(&prefs.to_state()),
+        Some(LOOK_PROFILE_HIGH_CONTRAST)
+    );
+
+    // Changed by other means: no look is checked
+    prefs.set_number(PREF_BORDER, 2.5);
+    assert_eq!(current_look_profile(&prefs.to_state()), None);
+}
+
+#[test]
+fn standard_look_restores_the_defaults() {
+    let mut prefs = Preferences::default();
+    for (key, value) in high_contrast_preset() {
+        prefs.set_number(key, value);
+    }
+    for (key, value) in look_profile_values(LOOK_PROFILE_STANDARD) {
+        prefs.set_number(key, value);
+    }
+    assert_eq!(prefs, Preferences::default());
+}
+
+#[test]
+fn unknown_looks_are_not_resolved() {
+    assert_eq!(resolve_look_profile("neon"), None);
+    assert_eq!(
+        resolve_look_profile("high_contrast"),
+        Some(LOOK_PROFILE_HIGH_CONTRAST)
+    );
+}
@@ -0,0 +1,45 @@
+//! Tests for the rainbow color mode.
+
+use lumbus::model::constants::*;
+use lumbus::model::rainbow::{rainbow_color, rainbow_phase};
+
+fn close(a: (f64, f64, f64, f64), b: (f64, f64, f64, f64)) -> bool {
+    [a.0 - b.0, a.1 - b.1, a.2 - b.2, a.3 - b.3]
+        .iter()
+        .all(|d| d.abs() < 1e-9)
+}
+
+#[test]
+fn phase_cycles_speed_times_per_minute() {
+    assert_eq!(rainbow_phase(0.0, DEFAULT_RAINBOW_SPEED), 0.0);
+    assert!((rainbow_phase(5.0, 6.0) - 0.5).abs() < 1e-9);
+    assert!((rainbow_phase(12.5, 6.0) - 0.25).abs() < 1e-9);
+    assert!((rainbow_phase(15.0, 2.0) - 0.5).abs() < 1e-9);
+}
+
+#[test]
+fn phase_stays_in_range_at_wall_clock_times() {
+    for t in [1.7e9, 1.7e9 + 0.016, -3.0] {
+        let phase = rainbow_phase(t, MAX_RAINBOW_SPEED);
+        assert!((0.0..1.0).contains(&phase), "{t}: {phase}");
+    }
+}
+
+#[test]
+fn color_turns_the_hue_and_keeps_the_rest() {
+    let red = (1.0, 0.0, 0.0, 0.5);
+    assert!(close(rainbow_color(red, 0.0), red));
+    assert!(close(rainbow_color(red, 1.0 / 3.0), (0.0, 1.0, 0.0, 0.5)));
+    assert!(close(rainbow_color(red, 2.0 / 3.0), (0.0, 0.0, 1.0, 0.5)));
+
+    // Saturation and brightness are kept
+    let pale = (0.5, 0.25, 0.25, 1.0);
+    assert!(close(rainbow_color(pale, 0.5), (0.25, 0.5, 0.5, 1.0)));
+}
+
+#[test]
+fn gray_strokes_cycle_saturated_hues() {
+    let gray = (0.6, 0.6, 0.6, 1.0);
+    assert!(close(rainbow_color(gray, 0.0), (0.6, 0.0, 0.0, 1.0)));
+    assert!(close(rainbow_color(gray, 0.5), (0.0, 0.6, 0.6, 1.0)));
+}
@@ -0,0 +1,551 @@
+//! Test-only renderer: rasterizes `backend::DrawCommand`s to RGBA pixels
+//! and compares them with golden PNGs in `tests/golden/`.
+//!
+//! Shapes are antialiased from their signed distance at each pixel center,
+//! with the same geometry as the native renderers (ring centered on the
+//! circle's edge, letters 1.5 × the diameter tall, counter below right of
+//! the letter, hold ring with butt ends, glow as the Windows layered
+//! strokes). Text uses a small built-in stroke font, so the goldens catch
+//! size and placement changes, not glyph shapes; click icons, drawn from
+//! the platform icon fonts, stand as a disc as tall as a letter. Grid
+//! lines start at the canvas's top-left corner, and the pen tip is a
+//! crosshair with butt ends. The
+//! magnified screen, the ruler, the coordinates readout, the color sample
+//! and the debug label need the screen or a system font and are skipped.
+//!
+//! Set `LUMBUS_UPDATE_GOLDEN=1` to rewrite the goldens after an intended
+//! change, then review the new PNGs before committing them.
+
+use std::f64::consts::TAU;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use lumbus::backend::DrawCommand;
+use lumbus::model::constants::{GRID_LINE_WIDTH, PEN_TIP_WIDTH, SCREEN_FRAME_WIDTH};
+use lumbus::model::contrast::halo_color;
+use lumbus::model::glow::{glow_color, glow_offset};
+use lumbus::model::grid::{grid_line_color, grid_lines};
+use lumbus::model::placement::ScreenRect;
+use lumbus::model::screen_frame::{frame_rect, Rgba};
+use lumbus::render::geometry::{
+    badge_anchor, badge_size, glow_layers, grid_line_center, halo_line_width, laser_core_radius,
+    laser_dot_radius, letter_fit_scale, letter_height, outline_halo_width, pen_tip_arms,
+    trail_width,
+};
+
+/// Largest per-channel difference still counted as equal.
+const TOLERANCE: u8 = 2;
+
+/// Premultiplied RGBA pixels, row by row from the top left.
+pub struct Canvas {
+    pub width: u32,
+    pub height: u32,
+    pixels: Vec<[f64; 4]>,
+}
+
+impl Canvas {
+    /// Fully transparent canvas.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![[0.0; 4]; (width * height) as usize],
+        }
+    }
+
+    /// Straight-alpha RGBA bytes of pixel (`x`, `y`).
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let [r, g, b, a] = self.pixels[(y * self.width + x) as usize];
+        let channel = |c: f64| (c * 255.0).round().clamp(0.0, 255.0) as u8;
+        if a <= 0.0 {
+            return [0; 4];
+        }
+        [channel(r / a), channel(g / a), channel(b / a), channel(a)]
+    }
+
+    /// Straight-alpha RGBA bytes of the whole canvas.
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .flat_map(|(x, y)| self.pixel(x, y))
+            .collect()
+    }
+
+    /// Paint `color` over every pixel, weighted by the coverage of the
+    /// shape whose signed distance is `sd` (negative inside).
+    fn fill(&mut self, color: Rgba, sd: impl Fn(f64, f64) -> f64) {
+        let (r, g, b, a) = color;
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let coverage = (0.5 - sd(x as f64 + 0.5, y as f64 + 0.5)).clamp(0.0, 1.0);
+                let alpha = a * coverage;
+                if alpha <= 0.0 {
+                    continue;
+                }
+                let dst = &mut self.pixels[(y * self.width + x) as usize];
+                for (d, s) in dst.iter_mut().zip([r * alpha, g * alpha, b * alpha, alpha]) {
+                    *d = s + *d * (1.0 - alpha);
+                }
+            }
+        }
+    }
+}
+
+/// Rasterize `commands` (bottom to top) on a `width` × `height` screen.
+pub fn render(commands: &[DrawCommand], width: u32, height: u32) -> Canvas {
+    let mut canvas = Canvas::new(width, height);
+    for command in commands {
+        match command {
+            DrawCommand::Zoom { .. }
+            | DrawCommand::Ruler { .. }
+            | DrawCommand::Coordinates { .. }
+            | DrawCommand::ColorSample { .. }
+            | DrawCommand::DebugLabel(_) => {}
+            DrawCommand::Grid {
+                spacing,
+                subdivisions,
+                color,
+            } => {
+                let half = GRID_LINE_WIDTH / 2.0;
+                for line in grid_lines(width as f64, *spacing, *subdivisions) {
+                    let x0 = grid_line_center(line.offset);
+                    let color = grid_line_color(*color, line.major);
+                    canvas.fill(color, |x, _| (x - x0).abs() - half);
+                }
+                for line in grid_lines(height as f64, *spacing, *subdivisions) {
+                    let y0 = grid_line_center(line.offset);
+                    let color = grid_line_color(*color, line.major);
+                    canvas.fill(color, |_, y| (y - y0).abs() - half);
+                }
+            }
+            DrawCommand::ScreenFrame { color } => {
+                let screen = ScreenRect::new(0.0, 0.0, width as f64, height as f64);
+                let rect = frame_rect(&screen, SCREEN_FRAME_WIDTH);
+                // Mitered corners: between the rect grown and shrunk by half the width
+                let half = SCREEN_FRAME_WIDTH / 2.0;
+                canvas.fill(*color, |x, y| {
+                    rect_distance(&rect, half, x, y).max(-rect_distance(&rect, -half, x, y))
+                });
+            }
+            DrawCommand::TouchRing {
+                center,
+                radius,
+                border_width,
+                stroke,
+            } => {
+                let (cx, cy, radius) = (center.0, center.1, *radius);
+                canvas.fill(*stroke, |x, y| {
+                    ((x - cx).hypot(y - cy) - radius).abs() - border_width / 2.0
+                });
+            }
+            DrawCommand::Circle {
+                center,
+                radius,
+                border_width,
+                stroke,
+                fill_alpha,
+                halo,
+                glow,
+            } => {
+                let (r, g, b, _) = *stroke;
+                let (cx, cy, radius) = (center.0, center.1, *radius);
+                draw_glow(&mut canvas, *glow, *stroke, *border_width, |x, y| {
+                    ((x - cx).hypot(y - cy) - radius).abs()
+                });
+                if *fill_alpha > 0.0 {
+                    canvas.fill((r, g, b, *fill_alpha), |x, y| {
+                        (x - cx).hypot(y - cy) - radius
+                    });
+                }
+                if *halo {
+                    let width = halo_line_width(*border_width);
+                    canvas.fill(halo_rgba(*stroke), |x, y| {
+                        ((x - cx).hypot(y - cy) - radius).abs() - width / 2.0
+                    });
+                }
+                canvas.fill(*stroke, |x, y| {
+                    ((x - cx).hypot(y - cy) - radius).abs() - border_width / 2.0
+                });
+            }
+            DrawCommand::Laser {
+                center,
+                radius,
+                stroke,
+                trail,
+                halo,
+                glow,
+            } => {
+                let (r, g, b, a) = *stroke;
+                let (cx, cy) = *center;
+                let dot = laser_dot_radius(*radius);
+                // Oldest first so the head paints over the tail
+                for seg in trail {
+                    let width = trail_width(dot, seg.alpha);
+                    canvas.fill((r, g, b, a * seg.alpha), |x, y| {
+                        segment_distance((seg.x0, seg.y0), (seg.x1, seg.y1), x, y) - width / 2.0
+                    });
+                }
+                draw_glow(&mut canvas, *glow, *stroke, 0.0, |x, y| {
+                    ((x - cx).hypot(y - cy) - dot).abs()
+                });
+                if *halo {
+                    canvas.fill(halo_rgba(*stroke), |x, y| {
+                        ((x - cx).hypot(y - cy) - dot).abs() - outline_halo_width() / 2.0
+                    });
+                }
+                canvas.fill(*stroke, |x, y| (x - cx).hypot(y - cy) - dot);
+                let core = laser_core_radius(dot);
+                canvas.fill((1.0, 1.0, 1.0, 0.85 * a), |x, y| {
+                    (x - cx).hypot(y - cy) - core
+                });
+            }
+            DrawCommand::Letter {
+                center,
+                letter,
+                radius,
+                stroke,
+                halo,
+                glow,
+            } => {
+                let height = letter_height(*radius);
+                let height = height * letter_fit_scale(text_width(letter, height), *radius);
+                let width = text_width(letter, height);
+                let origin = (center.0 - width / 2.0, center.1 - height / 2.0);
+                draw_text(&mut canvas, letter, origin, height, *stroke, *halo, *glow);
+            }
+            DrawCommand::Icon {
+                center,
+                radius,
+                stroke,
+                halo,
+                glow,
+                ..
+            } => {
+                let (cx, cy) = *center;
+                let r = letter_height(*radius) / 2.0;
+                draw_glow(&mut canvas, *glow, *stroke, 0.0, |x, y| {
+                    ((x - cx).hypot(y - cy) - r).abs()
+                });
+                if *halo {
+                    canvas.fill(halo_rgba(*stroke), |x, y| {
+                        ((x - cx).hypot(y - cy) - r).abs() - outline_halo_width() / 2.0
+                    });
+                }
+                canvas.fill(*stroke, |x, y| (x - cx).hypot(y - cy) - r);
+            }
+            DrawCommand::PenTip { center, color } => {
+                // Butt ends: each arm is a rectangle exactly as long as it
+                let w = PEN_TIP_WIDTH;
+                canvas.fill(*color, |x, y| {
+                    pen_tip_arms(*center)
+                        .iter()
+                        .map(|&((x0, y0), (x1, y1))| {
+                            let (hw, hh) = if y0 == y1 {
+                                (0.0, w / 2.0)
+                            } else {
+                                (w / 2.0, 0.0)
+                            };
+                            let rect = ScreenRect::new(
+                                x0.min(x1) - hw,
+                                y0.min(y1) - hh,
+                                (x1 - x0).abs() + 2.0 * hw,
+                                (y1 - y0).abs() + 2.0 * hh,
+                            );
+                            rect_distance(&rect, 0.0, x, y)
+                        })
+                        .fold(f64::INFINITY, f64::min)
+                });
+            }
+            DrawCommand::HoldRing {
+                center,
+                radius,
+                width,
+                progress,
+                stroke,
+                halo,
+            } => {
+                let (cx, cy, radius) = (center.0, center.1, *radius);
+                let sweep = TAU * progress;
+                // Butt ends: nothing past the sweep, measured clockwise from 12 o'clock
+                let ring = move |x: f64, y: f64, w: f64| {
+                    let angle = (x - cx).atan2(cy - y).rem_euclid(TAU);
+                    if angle <= sweep {
+                        ((x - cx).hypot(y - cy) - radius).abs() - w / 2.0
+                    } else {
+                        f64::INFINITY
+                    }
+                };
+                if *halo {
+                    let w = halo_line_width(*width);
+                    canvas.fill(halo_rgba(*stroke), |x, y| ring(x, y, w));
+                }
+                canvas.fill(*stroke, |x, y| ring(x, y, *width));
+            }
+            DrawCommand::Badge {
+                center,
+                radius,
+                label,
+                stroke,
+                halo,
+            } => {
+                // Glyphs about as tall as the font's capitals
+                let height = 0.7 * badge_size(*radius);
+                let (left, bottom) = badge_anchor(*center, *radius, false);
+                let origin = (left, bottom - height);
+                draw_text(&mut canvas, label, origin, height, *stroke, *halo, 0);
+            }
+        }
+    }
+    canvas
+}
+
+/// Contrasting halo color under `stroke`.
+fn halo_rgba(stroke: Rgba) -> Rgba {
+    let (r, g, b) = halo_color(stroke.0, stroke.1, stroke.2);
+    (r, g, b, stroke.3)
+}
+
+/// Glow behind the outline at distance `outline` (unsigned) from each
+/// point, stroked `width` wide: the layered strokes of the Windows renderer.
+fn draw_glow(
+    canvas: &mut Canvas,
+    glow: i32,
+    stroke: Rgba,
+    width: f64,
+    outline: impl Fn(f64, f64) -> f64,
+) {
+    let Some((r, g, b, a)) = glow_color(glow, stroke) else {
+        return;
+    };
+    let offset = glow_offset(glow);
+    for (spread, alpha) in glow_layers() {
+        canvas.fill((r, g, b, a * alpha), |x, y| {
+            outline(x, y - offset) - width / 2.0 - spread
+        });
+    }
+}
+
+/// Signed distance from (`x`, `y`) to `rect` grown by `grow` on each side.
+fn rect_distance(rect: &ScreenRect, grow: f64, x: f64, y: f64) -> f64 {
+    let dx = (x - (rect.x + rect.w / 2.0)).abs() - rect.w / 2.0 - grow;
+    let dy = (y - (rect.y + rect.h / 2.0)).abs() - rect.h / 2.0 - grow;
+    dx.max(0.0).hypot(dy.max(0.0)) + dx.max(dy).min(0.0)
+}
+
+/// Distance from (`x`, `y`) to the segment `a`-`b`.
+fn segment_distance(a: (f64, f64), b: (f64, f64), x: f64, y: f64) -> f64 {
+    let (vx, vy) = (b.0 - a.0, b.1 - a.1);
+    let len2 = vx * vx + vy * vy;
+    let t = if len2 > 0.0 {
+        (((x - a.0) * vx + (y - a.1) * vy) / len2).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (x - a.0 - t * vx).hypot(y - a.1 - t * vy)
+}
+
+/// Glyph box of the stroke font, in font units.
+const GLYPH_W: f64 = 4.0;
+const GLYPH_H: f64 = 6.0;
+
+/// Polylines of `c` in a `GLYPH_W` × `GLYPH_H` box, y down. Digits are
+/// seven-segment; unknown characters are a box.
+fn glyph(c: char) -> Vec<Vec<(f64, f64)>> {
+    const SEGMENTS: [[(f64, f64); 2]; 7] = [
+        [(0.0, 0.0), (4.0, 0.0)], // a: top
+        [(4.0, 0.0), (4.0, 3.0)], // b: top right
+        [(4.0, 3.0), (4.0, 6.0)], // c: bottom right
+        [(0.0, 6.0), (4.0, 6.0)], // d: bottom
+        [(0.0, 3.0), (0.0, 6.0)], // e: bottom left
+        [(0.0, 0.0), (0.0, 3.0)], // f: top left
+        [(0.0, 3.0), (4.0, 3.0)], // g: middle
+    ];
+    let lines: &[&[(f64, f64)]] = match c {
+        'L' => &[&[(0.0, 0.0), (0.0, 6.0), (4.0, 6.0)]],
+        'I' => &[
+            &[(2.0, 0.0), (2.0, 6.0)],
+            &[(1.0, 0.0), (3.0, 0.0)],
+            &[(1.0, 6.0), (3.0, 6.0)],
+        ],
+        'E' => &[
+            &[(4.0, 0.0), (0.0, 0.0), (0.0, 6.0), (4.0, 6.0)],
+            &[(0.0, 3.0), (3.0, 3.0)],
+        ],
+        'D' => &[&[
+            (0.0, 0.0),
+            (0.0, 6.0),
+            (2.0, 6.0),
+            (4.0, 4.0),
+            (4.0, 2.0),
+            (2.0, 0.0),
+            (0.0, 0.0),
+        ]],
+        'R' => &[
+            &[
+                (0.0, 6.0),
+                (0.0, 0.0),
+                (3.0, 0.0),
+                (4.0, 1.0),
+                (4.0, 2.0),
+                (3.0, 3.0),
+                (0.0, 3.0),
+            ],
+            &[(2.0, 3.0), (4.0, 6.0)],
+        ],
+        'G' => &[&[
+            (4.0, 1.0),
+            (3.0, 0.0),
+            (1.0, 0.0),
+            (0.0, 1.0),
+            (0.0, 5.0),
+            (1.0, 6.0),
+            (3.0, 6.0),
+            (4.0, 5.0),
+            (4.0, 3.0),
+            (2.0, 3.0),
+        ]],
+        'x' => &[&[(0.0, 2.0), (4.0, 6.0)], &[(4.0, 2.0), (0.0, 6.0)]],
+        '0'..='9' => {
+            let lit: &[usize] = match c {
+                '0' => &[0, 1, 2, 3, 4, 5],
+                '1' => &[1, 2],
+                '2' => &[0, 1, 6, 4, 3],
+                '3' => &[0, 1, 6, 2, 3],
+                '4' => &[5, 6, 1, 2],
+                '5' => &[0, 5, 6, 2, 3],
+                '6' => &[0, 5, 6, 4, 3, 2],
+                '7' => &[0, 1, 2],
+                '8' => &[0, 1, 2, 3, 4, 5, 6],
+                _ => &[0, 1, 5, 6, 2, 3],
+            };
+            return lit.iter().map(|i| SEGMENTS[*i].to_vec()).collect();
+        }
+        _ => &[&[(0.0, 0.0), (4.0, 0.0), (4.0, 6.0), (0.0, 6.0), (0.0, 0.0)]],
+    };
+    lines.iter().map(|line| line.to_vec()).collect()
+}
+
+/// Width of `text` drawn `height` tall by `draw_text`.
+fn text_width(text: &str, height: f64) -> f64 {
+    let count = text.chars().count() as f64;
+    let gap = height * 0.08;
+    count * height * GLYPH_W / GLYPH_H + (count - 1.0).max(0.0) * gap
+}
+
+/// Draw `text` left to right from the top-left `origin`, `height` tall.
+fn draw_text(
+    canvas: &mut Canvas,
+    text: &str,
+    origin: (f64, f64),
+    height: f64,
+    color: Rgba,
+    halo: bool,
+    glow: i32,
+) {
+    let scale = height / GLYPH_H;
+    let weight = (height * 0.12).max(1.0);
+    let gap = height * 0.08;
+    let mut segments = Vec::new();
+    for (i, c) in text.chars().enumerate() {
+        let left = origin.0 + i as f64 * (GLYPH_W * scale + gap);
+        for line in glyph(c) {
+            let points: Vec<_> = line
+                .iter()
+                .map(|(x, y)| (left + x * scale, origin.1 + y * scale))
+                .collect();
+            segments.extend(points.windows(2).map(|w| (w[0], w[1])));
+        }
+    }
+    let distance = |x: f64, y: f64| {
+        segments
+            .iter()
+            .map(|(a, b)| segment_distance(*a, *b, x, y))
+            .fold(f64::INFINITY, f64::min)
+    };
+    draw_glow(canvas, glow, color, weight, distance);
+    if halo {
+        canvas.fill(halo_rgba(color), |x, y| {
+            distance(x, y) - weight / 2.0 - outline_halo_width() / 2.0
+        });
+    }
+    canvas.fill(color, |x, y| distance(x, y) - weight / 2.0);
+}
+
+/// Golden PNG called `name`.
+fn golden_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join(format!("{name}.png"))
+}
+
+/// Write `canvas` as an RGBA PNG to `path`.
+pub fn save_png(canvas: &Canvas, path: &Path) {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).expect("create PNG directory");
+    }
+    let file = File::create(path).expect("create PNG");
+    let mut encoder = png::Encoder::new(BufWriter::new(file), canvas.width, canvas.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().expect("write PNG header");
+    writer
+        .write_image_data(&canvas.to_rgba8())
+        .expect("write PNG data");
+}
+
+/// RGBA bytes and size of the PNG at `path`.
+fn load_png(path: &Path) -> (Vec<u8>, u32, u32) {
+    let decoder = png::Decoder::new(BufReader::new(File::open(path).expect("open golden")));
+    let mut reader = decoder.read_info().expect("read golden header");
+    let mut pixels = vec![0; reader.output_buffer_size().expect("golden size")];
+    let info = reader.next_frame(&mut pixels).expect("read golden");
+    assert_eq!(info.color_type, png::ColorType::Rgba, "golden must be RGBA");
+    pixels.truncate(info.buffer_size());
+    (pixels, info.width, info.height)
+}
+
+/// Compare `canvas` with the golden called `name`.
+///
+/// On a mismatch the rendered image is written next to the test binaries
+/// (`target/tmp/golden/`) for inspection.
+pub fn assert_golden(name: &str, canvas: &Canvas) {
+    let path = golden_path(name);
+    if std::env::var_os("LUMBUS_UPDATE_GOLDEN").is_some() {
+        save_png(canvas, &path);
+        return;
+    }
+    assert!(
+        path.exists(),
+        "missing golden {}: run with LUMBUS_UPDATE_GOLDEN=1 to create it",
+        path.display()
+    );
+
+    let (expected, width, height) = load_png(&path);
+    assert_eq!(
+        (width, height),
+        (canvas.width, canvas.height),
+        "{name}: size differs from the golden"
+    );
+    let actual = canvas.to_rgba8();
+    let differing = actual
+        .chunks(4)
+        .zip(expected.chunks(4))
+        .filter(|(a, e)| {
+            a.iter()
+                .zip(e.iter())
+                .any(|(a, e)| a.abs_diff(*e) > TOLERANCE)
+        })
+        .count();
+    if differing > 0 {
+        let out = Path::new(env!("CARGO_TARGET_TMPDIR"))
+            .join("golden")
+            .join(format!("{name}.png"));
+        save_png(canvas, &out);
+        panic!(
+            "{name}: {differing} pixels differ from the golden; rendered image written to {}",
+            out.display()
+        );
+    }
+}
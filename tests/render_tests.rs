@@ -0,0 +1,282 @@
+//! Golden-image tests of the overlay shapes, rasterized by `raster`.
+//!
+//! After an intended visual change, rerun with `LUMBUS_UPDATE_GOLDEN=1`
+//! and review the PNGs in `tests/golden/`.
+
+mod raster;
+
+use lumbus::backend::{compose_frame, FrameInput};
+use lumbus::i18n::set_language;
+use lumbus::model::constants::*;
+use lumbus::model::touch::TouchRing;
+use lumbus::model::trail::TrailSegment;
+use lumbus::model::OverlayState;
+use raster::{assert_golden, render};
+
+/// Size of the rendered screen.
+const SIZE: u32 = 96;
+
+/// Frame of `state` with the pointer in the middle of the screen.
+fn render_frame(state: &OverlayState, input: FrameInput) -> raster::Canvas {
+    let input = FrameInput {
+        pointer: (SIZE as f64 / 2.0, SIZE as f64 / 2.0),
+        ..input
+    };
+    render(&compose_frame(state, &input), SIZE, SIZE)
+}
+
+#[test]
+fn default_circle() {
+    let canvas = render_frame(&OverlayState::default(), FrameInput::default());
+    assert_golden("circle", &canvas);
+}
+
+#[test]
+fn circle_ring_is_centered_on_the_radius() {
+    let state = OverlayState::default();
+    let canvas = render_frame(&state, FrameInput::default());
+    let center = SIZE / 2;
+    let edge = center + state.radius as u32;
+    let (r, g, b, _) = state.stroke_color();
+    let stroke = [r, g, b].map(|c| (c * 255.0).round() as u8);
+    assert_eq!(canvas.pixel(edge, center)[..3], stroke);
+    assert_eq!(canvas.pixel(edge, center)[3], 255);
+    // Inside: the fill, 10% transparent
+    assert_eq!(canvas.pixel(center, center)[3], 230);
+    // Outside: nothing
+    assert_eq!(canvas.pixel(edge + 4, center), [0; 4]);
+}
+
+#[test]
+fn large_circle_with_halo() {
+    let state = OverlayState {
+        radius: 36.0,
+        border_width: 6.0,
+        fill_transparency_pct: 100.0,
+        halo: true,
+        ..OverlayState::default()
+    };
+    let canvas = render_frame(&state, FrameInput::default());
+    assert_golden("circle_halo", &canvas);
+}
+
+#[test]
+fn circle_with_soft_glow() {
+    let state = OverlayState {
+        fill_transparency_pct: 100.0,
+        glow: GLOW_SOFT,
+        ..OverlayState::default()
+    };
+    let canvas = render_frame(&state, FrameInput::default());
+    assert_golden("circle_glow", &canvas);
+}
+
+#[test]
+fn circle_with_drop_shadow() {
+    let state = OverlayState {
+        fill_transparency_pct: 100.0,
+        glow: GLOW_SHADOW,
+        ..OverlayState::default()
+    };
+    let canvas = render_frame(&state, FrameInput::default());
+    let center = SIZE / 2;
+    let edge = state.radius as u32 + GLOW_RADIUS as u32 / 2;
+    // Dark, and lower than it is high
+    assert_eq!(canvas.pixel(center, center + edge)[..3], [0, 0, 0]);
+    assert!(canvas.pixel(center, center + edge)[3] > canvas.pixel(center, center - edge)[3]);
+    assert_golden("circle_shadow", &canvas);
+}
+
+#[test]
+fn faded_circle() {
+    let input = FrameInput {
+        fade_alpha: 0.5,
+        ..FrameInput::default()
+    };
+    let canvas = render_frame(&OverlayState::default(), input);
+    assert_golden("circle_faded", &canvas);
+}
+
+#[test]
+fn click_letters() {
+    set_language("en");
+    for (name, mode) in [
+        ("letter_left", DISPLAY_MODE_LEFT),
+        ("letter_right", DISPLAY_MODE_RIGHT),
+    ] {
+        let input = FrameInput {
+            display_mode: mode,
+            ..FrameInput::default()
+        };
+        assert_golden(name, &render_frame(&OverlayState::default(), input));
+    }
+}
+
+#[test]
+fn custom_click_letters_fit_beside_the_pointer() {
+    let state = OverlayState {
+        click_letter_left: "LLLL".to_string(),
+        ..OverlayState::default()
+    };
+    let input = FrameInput {
+        display_mode: DISPLAY_MODE_LEFT,
+        ..FrameInput::default()
+    };
+    assert_golden("letter_custom", &render_frame(&state, input));
+}
+
+#[test]
+fn click_icon_is_centered_on_the_pointer() {
+    let state = OverlayState {
+        click_icon: CLICK_ICON_TAP,
+        halo: true,
+        ..OverlayState::default()
+    };
+    let input = FrameInput {
+        display_mode: DISPLAY_MODE_RIGHT,
+        ..FrameInput::default()
+    };
+    assert_golden("click_icon", &render_frame(&state, input));
+}
+
+#[test]
+fn hold_ring_fills_clockwise_around_the_letter() {
+    set_language("en");
+    let state = OverlayState {
+        radius: 15.0,
+        border_width: 4.0,
+        hold_ring: true,
+        ..OverlayState::default()
+    };
+    let input = FrameInput {
+        display_mode: DISPLAY_MODE_LEFT,
+        hold_progress: Some(0.6),
+        ..FrameInput::default()
+    };
+    assert_golden("hold_ring", &render_frame(&state, input));
+}
+
+#[test]
+fn letter_with_click_counter() {
+    set_language("en");
+    let state = OverlayState {
+        click_burst: true,
+        ..OverlayState::default()
+    };
+    let input = FrameInput {
+        display_mode: DISPLAY_MODE_LEFT,
+        click_count: 3,
+        ..FrameInput::default()
+    };
+    assert_golden("letter_badge", &render_frame(&state, input));
+}
+
+#[test]
+fn laser_with_trail() {
+    let state = OverlayState {
+        highlight_style: HIGHLIGHT_STYLE_LASER,
+        ..OverlayState::default()
+    };
+    let trail = (0..4)
+        .map(|i| {
+            let x = 12.0 + i as f64 * 9.0;
+            TrailSegment {
+                x0: x,
+                y0: 20.0 + i as f64 * 7.0,
+                x1: x + 9.0,
+                y1: 27.0 + i as f64 * 7.0,
+                alpha: (i + 1) as f64 / 4.0,
+            }
+        })
+        .collect();
+    let input = FrameInput {
+        trail,
+        ..FrameInput::default()
+    };
+    assert_golden("laser", &render_frame(&state, input));
+}
+
+#[test]
+fn pen_pressure_grows_the_circle_and_marks_the_tip() {
+    let state = OverlayState::default();
+    let input = FrameInput {
+        pen_pressure: Some(0.5),
+        ..FrameInput::default()
+    };
+    let canvas = render_frame(&state, input);
+    let center = SIZE / 2;
+    // Half pressure: halfway to twice the radius
+    let edge = center + (state.radius * 1.5) as u32;
+    assert_eq!(canvas.pixel(edge, center)[3], 255);
+    assert_golden("pen_tip", &canvas);
+}
+
+#[test]
+fn touch_points_get_rings_of_the_highlight() {
+    let state = OverlayState::default();
+    let input = FrameInput {
+        pointer: (-100.0, -100.0),
+        touches: vec![
+            TouchRing {
+                x: 30.0,
+                y: 30.0,
+                alpha: 1.0,
+            },
+            TouchRing {
+                x: 66.0,
+                y: 66.0,
+                alpha: 0.5,
+            },
+        ],
+        ..FrameInput::default()
+    };
+    let canvas = render(&compose_frame(&state, &input), SIZE, SIZE);
+    let (_, _, _, a) = state.stroke_color();
+    let alpha = |a: f64| (a * 255.0).round() as u8;
+    // Rings only: the centers stay clear
+    assert_eq!(canvas.pixel(30, 30)[3], 0);
+    assert_eq!(canvas.pixel(30 + state.radius as u32, 30)[3], alpha(a));
+    assert_eq!(
+        canvas.pixel(66 + state.radius as u32, 66)[3],
+        alpha(a * 0.5)
+    );
+}
+
+#[test]
+fn grid_lines_fall_on_whole_pixels() {
+    let state = OverlayState {
+        grid_spacing: 40.0,
+        grid_subdivisions: 2,
+        ..OverlayState::default()
+    };
+    let input = FrameInput {
+        grid: true,
+        ..FrameInput::default()
+    };
+    let canvas = render_frame(&state, input);
+    let alpha = |a: f64| (a * 255.0).round() as u8;
+    let (_, _, _, a) = state.grid_color();
+    // Major lines every 40 px from the top-left corner, minor ones between
+    assert_eq!(canvas.pixel(40, 5)[3], alpha(a));
+    assert_eq!(canvas.pixel(5, 40)[3], alpha(a));
+    assert_eq!(canvas.pixel(20, 5)[3], alpha(a * GRID_MINOR_ALPHA));
+    assert_eq!(canvas.pixel(10, 5), [0; 4]);
+    assert_eq!(canvas.pixel(41, 5), [0; 4]);
+}
+
+#[test]
+fn recording_frame_behind_the_circle() {
+    let state = OverlayState {
+        screen_frame: true,
+        ..OverlayState::default()
+    };
+    let input = FrameInput {
+        frame_color: DEFAULT_SCREEN_FRAME_COLOR,
+        ..FrameInput::default()
+    };
+    let canvas = render_frame(&state, input);
+    // The border lies fully inside the screen
+    assert_eq!(canvas.pixel(0, 0)[3], 230);
+    assert_eq!(canvas.pixel(SCREEN_FRAME_WIDTH as u32, SIZE / 4), [0; 4]);
+    assert_golden("screen_frame", &canvas);
+}
@@ -0,0 +1,51 @@
+//! Tests for the on-screen ruler.
+
+use lumbus::model::ruler::{ruler_label, Ruler};
+
+#[test]
+fn line_follows_the_pointer_until_the_second_click() {
+    let mut ruler = Ruler::new();
+    assert_eq!(ruler.line((5.0, 5.0)), None);
+
+    ruler.click((10.0, 20.0));
+    assert_eq!(ruler.line((30.0, 40.0)), Some(((10.0, 20.0), (30.0, 40.0))));
+
+    ruler.click((50.0, 60.0));
+    assert_eq!(ruler.line((0.0, 0.0)), Some(((10.0, 20.0), (50.0, 60.0))));
+}
+
+#[test]
+fn third_click_starts_a_new_line() {
+    let mut ruler = Ruler::new();
+    ruler.click((10.0, 20.0));
+    ruler.click((50.0, 60.0));
+    ruler.click((70.0, 80.0));
+    assert_eq!(ruler.line((90.0, 80.0)), Some(((70.0, 80.0), (90.0, 80.0))));
+}
+
+#[test]
+fn label_shows_length_and_extent() {
+    assert_eq!(
+        ruler_label(((100.0, 100.0), (250.0, 300.0)), 1.0),
+        "250 px  (150 × 200)"
+    );
+    // Drawn backwards, it measures the same
+    assert_eq!(
+        ruler_label(((250.0, 300.0), (100.0, 100.0)), 1.0),
+        "250 px  (150 × 200)"
+    );
+    assert_eq!(ruler_label(((0.0, 0.0), (0.0, 0.0)), 1.0), "0 px  (0 × 0)");
+}
+
+#[test]
+fn label_counts_pixels_of_high_density_displays() {
+    // Points on a 2x display
+    assert_eq!(
+        ruler_label(((0.0, 0.0), (30.0, 40.0)), 2.0),
+        "100 px  (60 × 80)"
+    );
+    assert_eq!(
+        ruler_label(((0.0, 0.0), (10.4, 0.0)), 1.0),
+        "10 px  (10 × 0)"
+    );
+}
@@ -0,0 +1,99 @@
+//! Tests for the recording-frame color table and geometry.
+
+use lumbus::model::constants::{DEFAULT_SCREEN_FRAME_COLOR, SCREEN_FRAME_WIDTH};
+use lumbus::model::placement::ScreenRect;
+use lumbus::model::screen_frame::{frame_rect, FrameColors};
+
+fn approx_eq(a: f64, b: f64) -> bool {
+    (a - b).abs() < 0.01
+}
+
+// === Color Table Tests ===
+
+#[test]
+fn unknown_display_uses_default_color() {
+    let colors = FrameColors::new();
+    assert_eq!(colors.get("0_0_1920_1080"), None);
+    assert_eq!(
+        colors.color_for("0_0_1920_1080"),
+        DEFAULT_SCREEN_FRAME_COLOR
+    );
+}
+
+#[test]
+fn colors_are_per_display() {
+    let mut colors = FrameColors::new();
+    colors.set("0_0_1920_1080", (0.0, 1.0, 0.0, 1.0));
+    colors.set("1920_0_1280_800", (0.0, 0.0, 1.0, 1.0));
+    assert_eq!(colors.color_for("0_0_1920_1080"), (0.0, 1.0, 0.0, 1.0));
+    assert_eq!(colors.color_for("1920_0_1280_800"), (0.0, 0.0, 1.0, 1.0));
+}
+
+#[test]
+fn set_clamps_components() {
+    let mut colors = FrameColors::new();
+    colors.set("d", (2.0, -1.0, 0.5, 1.5));
+    assert_eq!(colors.get("d"), Some((1.0, 0.0, 0.5, 1.0)));
+}
+
+#[test]
+fn set_ignores_invalid_display_keys() {
+    let mut colors = FrameColors::new();
+    colors.set("", (1.0, 1.0, 1.0, 1.0));
+    colors.set("a=b", (1.0, 1.0, 1.0, 1.0));
+    colors.set("a;b", (1.0, 1.0, 1.0, 1.0));
+    assert_eq!(colors, FrameColors::new());
+}
+
+// === Encoding Tests ===
+
+#[test]
+fn encode_uses_hex_colors() {
+    let mut colors = FrameColors::new();
+    colors.set("-1920_0_1920_1080", (1.0, 0.0, 0.0, 1.0));
+    assert_eq!(colors.encode(), "-1920_0_1920_1080=#FF0000");
+}
+
+#[test]
+fn roundtrip_preserves_colors() {
+    let mut colors = FrameColors::new();
+    colors.set("0_0_1920_1080", (0.2, 0.4, 0.6, 0.8));
+    colors.set("1920_0_1280_800", (1.0, 1.0, 0.0, 1.0));
+
+    let decoded = FrameColors::decode(&colors.encode());
+    let (r, g, b, a) = decoded.color_for("0_0_1920_1080");
+    assert!(approx_eq(r, 0.2) && approx_eq(g, 0.4) && approx_eq(b, 0.6) && approx_eq(a, 0.8));
+    assert_eq!(decoded.color_for("1920_0_1280_800"), (1.0, 1.0, 0.0, 1.0));
+}
+
+#[test]
+fn decode_skips_malformed_entries() {
+    let colors = FrameColors::decode("a=#00FF00;b=nothex;garbage;=#FFFFFF");
+    assert_eq!(colors.get("a"), Some((0.0, 1.0, 0.0, 1.0)));
+    assert_eq!(colors.get("b"), None);
+    assert_eq!(colors.encode(), "a=#00FF00");
+}
+
+// === Geometry Tests ===
+
+#[test]
+fn frame_rect_insets_by_half_width() {
+    let screen = ScreenRect::new(100.0, 0.0, 1920.0, 1080.0);
+    let inner = frame_rect(&screen, SCREEN_FRAME_WIDTH);
+    let half = SCREEN_FRAME_WIDTH / 2.0;
+    assert_eq!(
+        inner,
+        ScreenRect::new(
+            100.0 + half,
+            half,
+            1920.0 - SCREEN_FRAME_WIDTH,
+            1080.0 - SCREEN_FRAME_WIDTH
+        )
+    );
+}
+
+#[test]
+fn frame_rect_never_inverts_tiny_screens() {
+    let inner = frame_rect(&ScreenRect::new(0.0, 0.0, 2.0, 10.0), 8.0);
+    assert!(inner.w >= 0.0 && inner.h >= 0.0);
+}
@@ -0,0 +1,52 @@
+//! Tests for the AppleScript commands.
+
+use lumbus::events::AppEvent;
+use lumbus::model::script::{SCRIPT_LOAD_PROFILE, SCRIPT_SET_RADIUS, SCRIPT_TOGGLE};
+use lumbus::model::{four_cc, script_event, ScriptArgument, SCRIPT_SUITE};
+
+#[test]
+fn four_char_codes_are_big_endian() {
+    assert_eq!(SCRIPT_SUITE, 0x4C6D_6273);
+    assert_eq!(four_cc(b"----"), 0x2D2D_2D2D);
+}
+
+#[test]
+fn toggle_needs_no_argument() {
+    assert_eq!(
+        script_event(SCRIPT_TOGGLE, &ScriptArgument::None),
+        Some(AppEvent::ToggleOverlay)
+    );
+}
+
+#[test]
+fn set_radius_takes_a_number() {
+    assert_eq!(
+        script_event(SCRIPT_SET_RADIUS, &ScriptArgument::Number(60.0)),
+        Some(AppEvent::SetRadius(60.0))
+    );
+    assert_eq!(script_event(SCRIPT_SET_RADIUS, &ScriptArgument::None), None);
+    assert_eq!(
+        script_event(SCRIPT_SET_RADIUS, &ScriptArgument::Text("big".into())),
+        None
+    );
+}
+
+#[test]
+fn load_profile_accepts_known_profiles_only() {
+    assert_eq!(
+        script_event(
+            SCRIPT_LOAD_PROFILE,
+            &ScriptArgument::Text(" Presentation ".into())
+        ),
+        Some(AppEvent::LoadProfile("presentation".into()))
+    );
+    assert_eq!(
+        script_event(SCRIPT_LOAD_PROFILE, &ScriptArgument::Text("demo".into())),
+        None
+    );
+}
+
+#[test]
+fn unknown_commands_are_rejected() {
+    assert_eq!(script_event(four_cc(b"zzzz"), &ScriptArgument::None), None);
+}
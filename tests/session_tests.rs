@@ -0,0 +1,111 @@
+//! Tests for the auxiliary-window session store.
+
+use lumbus::model::session::{SessionStore, SESSION_HELP_OVERLAY};
+
+// === Open/Close Tests ===
+
+#[test]
+fn new_session_has_no_open_windows() {
+    let session = SessionStore::new();
+    assert!(session.open_windows().is_empty());
+    assert!(!session.is_open(SESSION_HELP_OVERLAY));
+}
+
+#[test]
+fn mark_open_then_closed() {
+    let mut session = SessionStore::new();
+    session.mark_open(SESSION_HELP_OVERLAY, Some((10.0, 20.0)));
+    assert!(session.is_open(SESSION_HELP_OVERLAY));
+
+    session.mark_closed(SESSION_HELP_OVERLAY);
+    assert!(!session.is_open(SESSION_HELP_OVERLAY));
+    assert_eq!(session.position(SESSION_HELP_OVERLAY), Some((10.0, 20.0)));
+}
+
+#[test]
+fn mark_open_without_position_keeps_previous() {
+    let mut session = SessionStore::new();
+    session.set_position("countdown", 5.0, 6.0);
+    session.mark_open("countdown", None);
+    assert_eq!(session.position("countdown"), Some((5.0, 6.0)));
+}
+
+#[test]
+fn invalid_ids_are_ignored() {
+    let mut session = SessionStore::new();
+    session.mark_open("", None);
+    session.mark_open("a;b", None);
+    session.mark_open("a b", None);
+    assert!(session.open_windows().is_empty());
+}
+
+// === Encoding Tests ===
+
+#[test]
+fn encode_decode_roundtrip() {
+    let mut session = SessionStore::new();
+    session.mark_open(SESSION_HELP_OVERLAY, Some((120.5, -340.0)));
+    session.mark_open("keystrokes", None);
+    session.mark_closed("keystrokes");
+
+    let decoded = SessionStore::decode(&session.encode());
+    assert_eq!(decoded, session);
+}
+
+#[test]
+fn encode_format_is_compact() {
+    let mut session = SessionStore::new();
+    session.mark_open(SESSION_HELP_OVERLAY, Some((120.0, 340.0)));
+    assert_eq!(session.encode(), "help=1@120,340");
+}
+
+#[test]
+fn decode_skips_malformed_entries() {
+    let session = SessionStore::decode("help=1@1,2;bad;x=7;y=1@nan,3;z=0@a,b");
+    assert!(session.is_open(SESSION_HELP_OVERLAY));
+    assert!(session.get("bad").is_none());
+    assert!(session.get("x").is_none());
+    assert_eq!(session.position("y"), None);
+    assert!(session.get("z").is_some());
+}
+
+#[test]
+fn decode_empty_string() {
+    assert_eq!(SessionStore::decode(""), SessionStore::new());
+}
+
+// === Per-Display Position Tests ===
+
+#[test]
+fn display_positions_are_independent() {
+    let mut session = SessionStore::new();
+    session.set_display_position(SESSION_HELP_OVERLAY, "0_0_1920_1080", 10.0, 20.0);
+    session.set_display_position(SESSION_HELP_OVERLAY, "1920_0_1280_800", 2000.0, 30.0);
+
+    assert_eq!(
+        session.display_position(SESSION_HELP_OVERLAY, "0_0_1920_1080"),
+        Some((10.0, 20.0))
+    );
+    assert_eq!(
+        session.display_position(SESSION_HELP_OVERLAY, "1920_0_1280_800"),
+        Some((2000.0, 30.0))
+    );
+    assert_eq!(
+        session.display_position(SESSION_HELP_OVERLAY, "other"),
+        None
+    );
+    assert_eq!(session.position(SESSION_HELP_OVERLAY), None);
+}
+
+#[test]
+fn display_positions_survive_roundtrip_and_are_never_open() {
+    let mut session = SessionStore::new();
+    session.set_display_position(SESSION_HELP_OVERLAY, "-1920_0_1920_1080", -100.0, 50.0);
+
+    let decoded = SessionStore::decode(&session.encode());
+    assert_eq!(
+        decoded.display_position(SESSION_HELP_OVERLAY, "-1920_0_1920_1080"),
+        Some((-100.0, 50.0))
+    );
+    assert!(decoded.open_windows().is_empty());
+}
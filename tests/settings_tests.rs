@@ -0,0 +1,664 @@
+//! Tests for the shared Settings panel.
+
+use std::collections::HashSet;
+
+use lumbus::model::constants::*;
+use lumbus::model::hotkeys::{MOD_CTRL, MOD_SHIFT};
+use lumbus::model::{
+    apply_overrides, hotkey_for, profile_bindings, Binding, ColorPalette, Hotkey, HotkeyAction,
+    HotkeyPlatform, Key, OverlayState, BUILTIN_SWATCHES, HOTKEY_PROFILE_STANDARD,
+};
+use lumbus::settings::panel::{captured_hotkey, lang_from_option, lang_option, snap};
+use lumbus::settings::{SettingsPanel, SettingsStore};
+
+/// In-memory store recording what the panel does with it.
+#[derive(Default)]
+struct FakeStore {
+    state: OverlayState,
+    frame_color: (f64, f64, f64, f64),
+    locked: HashSet<&'static str>,
+    sync_folder: Option<String>,
+    /// Settings found in the sync folder, adopted when it is chosen.
+    synced: Option<OverlayState>,
+    palette: ColorPalette,
+    hotkey_overrides: Vec<Binding>,
+    /// The toggle notification, `None` where there is none.
+    toggle_notification: Option<bool>,
+    /// Quit confirmation, `None` where there is none.
+    confirm_quit: Option<bool>,
+    /// Screen capture exclusion, `None` where it is not offered.
+    exclude_from_capture: Option<bool>,
+    /// Startup registration, `None` where it is not offered.
+    run_at_startup: Option<bool>,
+    writes: usize,
+    changes: usize,
+    language_changes: usize,
+    flushes: usize,
+}
+
+impl SettingsStore for FakeStore {
+    fn load_state(&self) -> OverlayState {
+        self.state.clone()
+    }
+
+    fn set_double(&mut self, key: &str, value: f64) {
+        if self.locked.contains(key) {
+            return;
+        }
+        self.writes += 1;
+        let s = &mut self.state;
+        match key {
+            PREF_RADIUS => s.radius = value,
+            PREF_BORDER => s.border_width = value,
+            PREF_STROKE_R => s.stroke_r = value,
+            PREF_STROKE_G => s.stroke_g = value,
+            PREF_STROKE_B => s.stroke_b = value,
+            PREF_STROKE_A => s.stroke_a = value,
+            PREF_FILL_TRANSPARENCY => s.fill_transparency_pct = value,
+            PREF_GRID_SPACING => s.grid_spacing = value,
+            PREF_GRID_R => s.grid_r = value,
+            PREF_GRID_G => s.grid_g = value,
+            PREF_GRID_B => s.grid_b = value,
+            PREF_GRID_A => s.grid_a = value,
+            PREF_HALO => s.halo = value != 0.0,
+            PREF_LETTER_WEIGHT => s.letter_weight = value as i32,
+            _ => {}
+        }
+    }
+
+    fn set_int(&mut self, key: &str, value: i32) {
+        if self.locked.contains(key) {
+            return;
+        }
+        self.writes += 1;
+        match key {
+            PREF_LANG => self.state.lang = value,
+            PREF_HALO => self.state.halo = value != 0,
+            PREF_LETTER_WEIGHT => self.state.letter_weight = value,
+            PREF_TOGGLE_NOTIFICATION => self.toggle_notification = Some(value != 0),
+            PREF_EXCLUDE_FROM_CAPTURE => self.exclude_from_capture = Some(value != 0),
+            PREF_CONFIRM_QUIT => self.confirm_quit = Some(value != 0),
+            _ => {}
+        }
+    }
+
+    fn set_string(&mut self, key: &str, value: &str) {
+        if self.locked.contains(key) {
+            return;
+        }
+        self.writes += 1;
+        match key {
+            PREF_CLICK_LETTER_LEFT => self.state.click_letter_left = value.to_string(),
+            PREF_CLICK_LETTER_RIGHT => self.state.click_letter_right = value.to_string(),
+            PREF_LETTER_FONT => self.state.letter_font = value.to_string(),
+            _ => {}
+        }
+    }
+
+    fn is_locked(&self, key: &str) -> bool {
+        self.locked.contains(key)
+    }
+
+    fn flush(&mut self) {
+        self.flushes += 1;
+    }
+
+    fn frame_color(&self) -> (f64, f64, f64, f64) {
+        self.frame_color
+    }
+
+    fn set_frame_color(&mut self, color: (f64, f64, f64, f64)) {
+        self.frame_color = color;
+    }
+
+    fn system_locale(&self) -> Option<String> {
+        None
+    }
+
+    fn settings_changed(&mut self) {
+        self.changes += 1;
+    }
+
+    fn language_changed(&mut self) {
+        self.language_changes += 1;
+    }
+
+    fn toggle_notification(&self) -> Option<bool> {
+        self.toggle_notification
+    }
+
+    fn confirm_quit(&self) -> Option<bool> {
+        self.confirm_quit
+    }
+
+    fn exclude_from_capture(&self) -> Option<bool> {
+        self.exclude_from_capture
+    }
+
+    fn run_at_startup(&self) -> Option<bool> {
+        self.run_at_startup
+    }
+
+    fn set_run_at_startup(&mut self, on: bool) {
+        if self.run_at_startup.is_some() {
+            self.run_at_startup = Some(on);
+        }
+    }
+
+    fn sync_folder(&self) -> Option<String> {
+        self.sync_folder.clone()
+    }
+
+    fn set_sync_folder(&mut self, folder: Option<&str>) {
+        self.sync_folder = folder.map(str::to_string);
+        if let (Some(_), Some(synced)) = (folder, self.synced.take()) {
+            self.state = synced;
+        }
+    }
+
+    fn color_palette(&self) -> ColorPalette {
+        self.palette.clone()
+    }
+
+    fn set_color_palette(&mut self, palette: &ColorPalette) {
+        self.palette = palette.clone();
+    }
+
+    fn hotkey_bindings(&self) -> Vec<Binding> {
+        let profile = profile_bindings(HOTKEY_PROFILE_STANDARD, HotkeyPlatform::Windows);
+        apply_overrides(profile, &self.hotkey_overrides)
+    }
+
+    fn can_rebind_hotkeys(&self) -> bool {
+        true
+    }
+
+    fn hotkey_overrides(&self) -> Vec<Binding> {
+        self.hotkey_overrides.clone()
+    }
+
+    fn set_hotkey_overrides(&mut self, overrides: &[Binding]) {
+        self.hotkey_overrides = overrides.to_vec();
+    }
+}
+
+#[test]
+fn snap_rounds_to_the_step_within_range() {
+    assert_eq!(snap(37.0, 5.0, 5.0, 200.0), 35.0);
+    assert_eq!(snap(38.0, 5.0, 5.0, 200.0), 40.0);
+    assert_eq!(snap(1.0, 5.0, 5.0, 200.0), 5.0);
+    assert_eq!(snap(500.0, 5.0, 5.0, 200.0), 200.0);
+}
+
+#[test]
+fn language_options_start_with_system_default() {
+    assert_eq!(lang_option(LANG_SYSTEM), 0);
+    assert_eq!(lang_from_option(0), LANG_SYSTEM);
+    for lang in 0..3 {
+        assert_eq!(lang_from_option(lang_option(lang)), lang);
+    }
+}
+
+#[test]
+fn panel_shows_the_stored_color_as_hex() {
+    let mut store = FakeStore::default();
+    store.state.stroke_r = 1.0;
+    store.state.stroke_g = 0.0;
+    store.state.stroke_b = 0.5;
+    store.state.stroke_a = 1.0;
+    let panel = SettingsPanel::new(&store);
+    assert_eq!(panel.hex(), "#FF0080");
+}
+
+#[test]
+fn valid_hex_is_stored_and_normalized() {
+    let mut store = FakeStore::default();
+    let mut panel = SettingsPanel::new(&store);
+    panel.set_hex("#00ff00");
+    assert!(panel.commit_hex(&mut store));
+    assert_eq!(panel.hex(), "#00FF00");
+    assert_eq!(store.state.stroke_color(), (0.0, 1.0, 0.0, 1.0));
+    assert_eq!(store.changes, 1);
+}
+
+#[test]
+fn invalid_hex_reverts_to_the_current_color() {
+    let mut store = FakeStore::default();
+    let mut panel = SettingsPanel::new(&store);
+    let before = panel.hex().to_string();
+    panel.set_hex("not a color");
+    assert!(!panel.commit_hex(&mut store));
+    assert_eq!(panel.hex(), before);
+    assert_eq!(store.writes, 0);
+}
+
+#[test]
+fn high_contrast_keeps_locked_settings() {
+    let mut store = FakeStore::default();
+    store.locked.insert(PREF_BORDER);
+    let border = store.state.border_width;
+    let mut panel = SettingsPanel::new(&store);
+    panel.apply_high_contrast(&mut store);
+    assert_eq!(panel.state().border_width, border);
+    assert_eq!(panel.state().stroke_color(), HIGH_CONTRAST_COLOR);
+    assert!(panel.state().halo);
+}
+
+#[test]
+fn language_change_relabels_outside_the_window() {
+    let mut store = FakeStore::default();
+    let mut panel = SettingsPanel::new(&store);
+    panel.set_language(&mut store, LANG_SYSTEM);
+    assert_eq!(store.state.lang, LANG_SYSTEM);
+    assert_eq!(store.language_changes, 1);
+    assert_eq!(store.changes, 1);
+}
+
+#[test]
+fn drawing_the_panel_changes_nothing() {
+    let mut store = FakeStore::default();
+    let mut panel = SettingsPanel::new(&store);
+    let ctx = egui::Context::default();
+    for _ in 0..2 {
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| panel.show(ui, &mut store));
+        });
+    }
+    assert_eq!(store.writes, 0);
+    assert_eq!(store.changes, 0);
+    assert!(!panel.close_requested());
+}
+
+#[test]
+fn joining_a_sync_folder_shows_its_settings() {
+    let mut store = FakeStore {
+        synced: Some(OverlayState {
+            radius: 80.0,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let mut panel = SettingsPanel::new(&store);
+    panel.set_sync_folder(&mut store, Some("/sync"));
+    assert_eq!(store.sync_folder.as_deref(), Some("/sync"));
+    assert_eq!(panel.state().radius, 80.0);
+    assert_eq!(store.changes, 1);
+    assert_eq!(store.language_changes, 1);
+
+    panel.set_sync_folder(&mut store, None);
+    assert_eq!(store.sync_folder, None);
+    assert_eq!(panel.state().radius, 80.0);
+}
+
+#[test]
+fn toggle_notification_is_stored_outside_the_undo_history() {
+    let mut store = FakeStore {
+        toggle_notification: Some(false),
+        ..Default::default()
+    };
+    let mut panel = SettingsPanel::new(&store);
+    panel.set_toggle_notification(&mut store, true);
+    assert_eq!(store.toggle_notification, Some(true));
+    assert_eq!(store.changes, 1);
+    assert!(!panel.can_undo());
+}
+
+#[test]
+fn quit_confirmation_is_stored_outside_the_undo_history() {
+    let mut store = FakeStore {
+        confirm_quit: Some(true),
+        ..Default::default()
+    };
+    let mut panel = SettingsPanel::new(&store);
+    panel.set_confirm_quit(&mut store, false);
+    assert_eq!(store.confirm_quit, Some(false));
+    assert_eq!(store.changes, 1);
+    assert!(!panel.can_undo());
+}
+
+#[test]
+fn capture_exclusion_is_stored_outside_the_undo_history() {
+    let mut store = FakeStore {
+        exclude_from_capture: Some(false),
+        ..Default::default()
+    };
+    let mut panel = SettingsPanel::new(&store);
+    panel.set_exclude_from_capture(&mut store, true);
+    assert_eq!(store.exclude_from_capture, Some(true));
+    assert_eq!(store.changes, 1);
+    assert!(!panel.can_undo());
+}
+
+#[test]
+fn startup_registration_is_read_on_open_and_written_through() {
+    let mut store = FakeStore {
+        run_at_startup: Some(true),
+        ..Default::default()
+    };
+    let mut panel = SettingsPanel::new(&store);
+    assert_eq!(panel.run_at_startup(), Some(true));
+
+    panel.set_run_at_startup(&mut store, false);
+    assert_eq!(store.run_at_startup, Some(false));
+    assert_eq!(panel.run_at_startup(), Some(false));
+    assert!(!panel.can_undo());
+
+    assert_eq!(
+        SettingsPanel::new(&FakeStore::default()).run_at_startup(),
+        None
+    );
+}
+
+#[test]
+fn undo_and_redo_restore_slider_and_color_changes() {
+    let mut store = FakeStore::default();
+    let mut panel = SettingsPanel::new(&store);
+    let before = store.state.clone();
+    assert!(!panel.can_undo());
+
+    panel.set_number(&mut store, PREF_RADIUS, 80.0);
+    panel.set_stroke_color(&mut store, (0.0, 1.0, 0.0, 1.0));
+    assert_eq!(store.state.radius, 80.0);
+
+    assert!(panel.undo(&mut store));
+    assert_eq!(store.state.stroke_g, before.stroke_g);
+    assert_eq!(store.state.radius, 80.0);
+    assert!(panel.undo(&mut store));
+    assert_eq!(store.state, before);
+    assert_eq!(panel.state(), &before);
+    assert!(!panel.undo(&mut store));
+
+    assert!(panel.redo(&mut store));
+    assert!(panel.redo(&mut store));
+    assert_eq!(store.state.radius, 80.0);
+    assert_eq!(store.state.stroke_g, 1.0);
+    assert_eq!(panel.hex(), "#00FF00");
+}
+
+#[test]
+fn grid_color_is_one_undo_step() {
+    let mut store = FakeStore::default();
+    let before = store.state.clone();
+    let mut panel = SettingsPanel::new(&store);
+
+    panel.set_number(&mut store, PREF_GRID_SPACING, 40.0);
+    panel.set_grid_color(&mut store, (1.0, 0.0, 1.0, 0.25));
+    assert_eq!(store.state.grid_color(), (1.0, 0.0, 1.0, 0.25));
+    assert_eq!(store.state.stroke_color(), before.stroke_color());
+
+    assert!(panel.undo(&mut store));
+    assert_eq!(store.state.grid_color(), DEFAULT_GRID_COLOR);
+    assert_eq!(store.state.grid_spacing, 40.0);
+    assert!(panel.undo(&mut store));
+    assert_eq!(store.state, before);
+}
+
+#[test]
+fn revert_goes_back_to_the_settings_the_window_opened_with() {
+    let mut store = FakeStore::default();
+    let mut panel = SettingsPanel::new(&store);
+    let opened = store.state.clone();
+    assert!(!panel.can_revert());
+
+    panel.set_number(&mut store, PREF_RADIUS, 80.0);
+    panel.set_number(&mut store, PREF_BORDER, 9.0);
+    panel.revert(&mut store);
+    assert_eq!(store.state, opened);
+    assert!(!panel.can_revert());
+
+    // The revert is itself a change
+    assert!(panel.undo(&mut store));
+    assert_eq!(store.state.radius, 80.0);
+    assert_eq!(store.state.border_width, 9.0);
+}
+
+#[test]
+fn click_letters_are_trimmed_and_can_be_undone() {
+    let mut store = FakeStore::default();
+    let mut panel = SettingsPanel::new(&store);
+
+    panel.set_click_letter(&mut store, true, "  LMB ");
+    panel.set_click_letter(&mut store, false, "RMB-long");
+    assert_eq!(store.state.click_letter_left, "LMB");
+    assert_eq!(store.state.click_letter_right, "RMB-");
+
+    // Committing unchanged text records nothing
+    let writes = store.writes;
+    panel.set_click_letter(&mut store, true, "LMB");
+    assert_eq!(store.writes, writes);
+
+    assert!(panel.undo(&mut store));
+    assert_eq!(store.state.click_letter_right, "");
+    assert_eq!(store.state.click_letter_left, "LMB");
+}
+
+#[test]
+fn letter_font_and_weight_are_restored_together() {
+    let mut store = FakeStore::default();
+    let mut panel = SettingsPanel::new(&store);
+
+    panel.set_letter_font(&mut store, " Menlo ");
+    panel.set_int(&mut store, PREF_LETTER_WEIGHT, LETTER_WEIGHT_LIGHT);
+    assert_eq!(store.state.letter_font, "Menlo");
+    assert_eq!(store.state.letter_weight, LETTER_WEIGHT_LIGHT);
+
+    // Choosing the same family again records nothing
+    let writes = store.writes;
+    panel.set_letter_font(&mut store, "Menlo");
+    assert_eq!(store.writes, writes);
+
+    panel.revert(&mut store);
+    assert_eq!(store.state.letter_font, "");
+    assert_eq!(store.state.letter_weight, LETTER_WEIGHT_BOLD);
+}
+
+#[test]
+fn command_z_undoes_the_last_change() {
+    let mut store = FakeStore::default();
+    let mut panel = SettingsPanel::new(&store);
+    let before = store.state.radius;
+    panel.set_number(&mut store, PREF_RADIUS, 80.0);
+
+    let ctx = egui::Context::default();
+    let mut input = egui::RawInput::default();
+    input.events.push(egui::Event::Key {
+        key: egui::Key::Z,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers: egui::Modifiers::COMMAND,
+    });
+    input.modifiers = egui::Modifiers::COMMAND;
+    let _ = ctx.run(input, |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| panel.show(ui, &mut store));
+    });
+    assert_eq!(store.state.radius, before);
+    assert!(panel.can_redo());
+}
+
+#[test]
+fn restore_defaults_can_be_undone() {
+    let mut store = FakeStore::default();
+    let mut panel = SettingsPanel::new(&store);
+    panel.set_number(&mut store, PREF_RADIUS, 80.0);
+    panel.set_stroke_color(&mut store, (0.0, 1.0, 0.0, 1.0));
+    panel.set_frame_color(&mut store, (0.0, 0.0, 1.0, 1.0));
+    let changes = store.changes;
+
+    panel.restore_defaults(&mut store);
+    assert_eq!(store.state, OverlayState::default());
+    assert_eq!(store.frame_color, DEFAULT_SCREEN_FRAME_COLOR);
+    assert_eq!(panel.state(), &OverlayState::default());
+    assert_eq!(store.changes, changes + 1);
+
+    assert!(panel.undo(&mut store));
+    assert_eq!(store.state.radius, 80.0);
+    assert_eq!(store.state.stroke_g, 1.0);
+    assert_eq!(store.frame_color, (0.0, 0.0, 1.0, 1.0));
+}
+
+#[test]
+fn frame_color_and_restore_defaults_are_saved_as_they_happen() {
+    let mut store = FakeStore::default();
+    let mut panel = SettingsPanel::new(&store);
+
+    panel.set_frame_color(&mut store, (0.0, 2.0, f64::NAN, 1.0));
+    assert_eq!(
+        store.frame_color,
+        (0.0, 1.0, DEFAULT_SCREEN_FRAME_COLOR.2, 1.0)
+    );
+    assert_eq!((store.flushes, store.changes), (1, 1));
+
+    panel.restore_defaults(&mut store);
+    assert_eq!(store.frame_color, DEFAULT_SCREEN_FRAME_COLOR);
+    assert_eq!((store.flushes, store.changes), (2, 2));
+}
+
+#[test]
+fn pasted_css_colors_are_normalized_to_hex() {
+    let mut store = FakeStore::default();
+    let mut panel = SettingsPanel::new(&store);
+    panel.set_hex("rgb(0, 128, 255)");
+    assert!(panel.commit_hex(&mut store));
+    assert_eq!(panel.hex(), "#0080FF");
+
+    panel.set_hex("gold");
+    assert!(panel.commit_hex(&mut store));
+    assert_eq!(panel.hex(), "#FFD700");
+}
+
+#[test]
+fn hsb_sliders_store_the_color_and_keep_the_hue() {
+    let mut store = FakeStore::default();
+    store.state.stroke_a = 0.5;
+    let mut panel = SettingsPanel::new(&store);
+
+    panel.set_hsb(&mut store, 120.0, 1.0, 1.0);
+    assert_eq!(
+        (
+            store.state.stroke_r,
+            store.state.stroke_g,
+            store.state.stroke_b
+        ),
+        (0.0, 1.0, 0.0)
+    );
+    assert_eq!(store.state.stroke_a, 0.5);
+    assert_eq!(panel.hex(), "#00FF0080");
+
+    // Fully desaturated is gray, but the hue slider stays where it was
+    panel.set_hsb(&mut store, 120.0, 0.0, 1.0);
+    assert_eq!(store.state.stroke_r, 1.0);
+    assert_eq!(panel.hsb(), (120.0, 0.0, 1.0));
+
+    // A color set another way moves the sliders
+    panel.set_stroke_color(&mut store, (0.0, 0.0, 1.0, 1.0));
+    assert_eq!(panel.hsb(), (240.0, 1.0, 1.0));
+}
+
+#[test]
+fn picked_color_stores_its_opacity() {
+    let mut store = FakeStore::default();
+    let mut panel = SettingsPanel::new(&store);
+    panel.set_stroke_color(&mut store, (1.0, 0.0, 0.0, 0.5));
+    assert_eq!(store.state.stroke_a, 0.5);
+    assert_eq!(panel.hex(), "#FF000080");
+}
+
+#[test]
+fn saved_colors_are_stored_and_applied() {
+    let mut store = FakeStore::default();
+    store.state.stroke_r = 0.2;
+    store.state.stroke_g = 0.4;
+    store.state.stroke_b = 0.6;
+    let mut panel = SettingsPanel::new(&store);
+    assert!(panel.save_color(&mut store));
+    assert!(!panel.save_color(&mut store));
+    assert_eq!(store.palette.encode(), "#336699");
+
+    // Applying a swatch is one undoable change
+    panel.set_stroke_color(&mut store, BUILTIN_SWATCHES[0]);
+    assert_eq!(store.state.stroke_color(), BUILTIN_SWATCHES[0]);
+    panel.undo(&mut store);
+    assert_eq!(store.state.stroke_r, 0.2);
+
+    panel.remove_saved_color(&mut store, 0);
+    assert!(store.palette.colors().is_empty());
+    assert!(panel.palette().colors().is_empty());
+}
+
+#[test]
+fn hotkeys_are_rebound_and_reset() {
+    let mut store = FakeStore::default();
+    let mut panel = SettingsPanel::new(&store);
+    let combo = Hotkey {
+        key: Key::F(9),
+        modifiers: MOD_CTRL,
+    };
+    // Nothing is waiting for a combination yet
+    assert!(!panel.rebind(&mut store, combo));
+
+    panel.start_rebinding(HotkeyAction::Toggle);
+    assert!(panel.rebind(&mut store, combo));
+    assert_eq!(panel.rebinding(), None);
+    let bindings = store.hotkey_bindings();
+    assert_eq!(hotkey_for(&bindings, HotkeyAction::Toggle), Some(combo));
+
+    panel.reset_hotkey(&mut store, HotkeyAction::Toggle);
+    assert!(store.hotkey_overrides.is_empty());
+}
+
+#[test]
+fn a_hotkey_of_another_command_is_refused() {
+    let mut store = FakeStore::default();
+    let mut panel = SettingsPanel::new(&store);
+    let quit = hotkey_for(&store.hotkey_bindings(), HotkeyAction::Quit).unwrap();
+    panel.start_rebinding(HotkeyAction::Toggle);
+    assert!(!panel.rebind(&mut store, quit));
+    assert!(panel.rebind_error().is_some());
+    assert_eq!(panel.rebinding(), Some(HotkeyAction::Toggle));
+    assert!(store.hotkey_overrides.is_empty());
+}
+
+#[test]
+fn captured_keys_need_a_modifier_unless_function_keys() {
+    let ctrl_shift = egui::Modifiers::CTRL | egui::Modifiers::SHIFT;
+    assert_eq!(
+        captured_hotkey(egui::Key::K, ctrl_shift),
+        Some(Hotkey {
+            key: Key::Letter('K'),
+            modifiers: MOD_CTRL | MOD_SHIFT
+        })
+    );
+    assert_eq!(
+        captured_hotkey(egui::Key::Equals, egui::Modifiers::CTRL),
+        Some(Hotkey {
+            key: Key::Equal,
+            modifiers: MOD_CTRL
+        })
+    );
+    assert_eq!(
+        captured_hotkey(egui::Key::F13, egui::Modifiers::NONE),
+        Some(Hotkey {
+            key: Key::F(13),
+            modifiers: 0
+        })
+    );
+    assert_eq!(captured_hotkey(egui::Key::K, egui::Modifiers::NONE), None);
+    assert_eq!(captured_hotkey(egui::Key::A, egui::Modifiers::SHIFT), None);
+    assert_eq!(
+        captured_hotkey(egui::Key::Minus, egui::Modifiers::SHIFT),
+        None
+    );
+    assert_eq!(
+        captured_hotkey(egui::Key::F5, egui::Modifiers::SHIFT),
+        Some(Hotkey {
+            key: Key::F(5),
+            modifiers: MOD_SHIFT
+        })
+    );
+    assert_eq!(
+        captured_hotkey(egui::Key::Num1, egui::Modifiers::CTRL),
+        None
+    );
+    assert_eq!(captured_hotkey(egui::Key::F25, egui::Modifiers::NONE), None);
+}
@@ -0,0 +1,157 @@
+//! Tests for the soak-test script and report.
+
+use lumbus::model::constants::*;
+use lumbus::model::{ScreenRect, SoakAction, SoakConfig, SoakReport, SoakSample, SoakScript};
+
+const MB: u64 = 1024 * 1024;
+
+fn screen() -> ScreenRect {
+    ScreenRect::new(0.0, 0.0, 1920.0, 1080.0)
+}
+
+fn sample(elapsed_secs: f64, memory_mb: u64, handles: u64, hotkeys: u32) -> SoakSample {
+    SoakSample {
+        elapsed_secs,
+        memory_bytes: memory_mb * MB,
+        handles,
+        gui_objects: None,
+        queue_depth: Some(0),
+        hotkeys: Some(hotkeys),
+    }
+}
+
+// === Argument Tests ===
+
+#[test]
+fn soak_is_off_without_the_flag() {
+    assert_eq!(SoakConfig::from_args(["--verbose"]), None);
+    assert_eq!(SoakConfig::from_args(Vec::<String>::new()), None);
+}
+
+#[test]
+fn bare_flag_uses_default_duration() {
+    let config = SoakConfig::from_args([SOAK_FLAG]).unwrap();
+    assert_eq!(config.duration_secs, DEFAULT_SOAK_MINUTES * 60.0);
+}
+
+#[test]
+fn flag_accepts_minutes() {
+    let config = SoakConfig::from_args(["--soak=90"]).unwrap();
+    assert_eq!(config.duration_secs, 90.0 * 60.0);
+}
+
+#[test]
+fn invalid_minutes_fall_back_to_default() {
+    for arg in ["--soak=abc", "--soak=-5", "--soak="] {
+        let config = SoakConfig::from_args([arg]).unwrap();
+        assert_eq!(config.duration_secs, DEFAULT_SOAK_MINUTES * 60.0);
+    }
+    assert_eq!(SoakConfig::from_args(["--soaked"]), None);
+}
+
+// === Script Tests ===
+
+#[test]
+fn every_step_moves_the_cursor_on_screen() {
+    let mut script = SoakScript::new();
+    let area = screen();
+    for _ in 0..1000 {
+        let actions = script.next_actions(&area);
+        match actions[0] {
+            SoakAction::MoveCursor { x, y } => {
+                assert!(x >= area.x && x <= area.x + area.w);
+                assert!(y >= area.y && y <= area.y + area.h);
+            }
+            other => panic!("expected a cursor move, got {:?}", other),
+        }
+    }
+    assert_eq!(script.ticks(), 1000);
+}
+
+#[test]
+fn script_schedules_toggles_churn_and_space_changes() {
+    let mut script = SoakScript::new();
+    let mut report = SoakReport::new();
+    let (mut toggles, mut churns, mut spaces) = (0, 0, 0);
+    for _ in 0..=SOAK_SPACE_TICKS {
+        for action in script.next_actions(&screen()) {
+            report.count(&action);
+            match action {
+                SoakAction::ToggleOverlay => toggles += 1,
+                SoakAction::ChurnSettings {
+                    radius,
+                    highlight_style,
+                } => {
+                    churns += 1;
+                    assert!((MIN_RADIUS..=MAX_RADIUS).contains(&radius));
+                    assert!(
+                        highlight_style == HIGHLIGHT_STYLE_CIRCLE
+                            || highlight_style == HIGHLIGHT_STYLE_LASER
+                    );
+                }
+                SoakAction::SpaceChange => spaces += 1,
+                SoakAction::MoveCursor { .. } => {}
+            }
+        }
+    }
+    assert_eq!(toggles, SOAK_SPACE_TICKS / SOAK_TOGGLE_TICKS);
+    assert_eq!(churns, SOAK_SPACE_TICKS / SOAK_CHURN_TICKS);
+    assert_eq!(spaces, 1);
+}
+
+// === Report Tests ===
+
+#[test]
+fn report_tracks_growth_since_baseline() {
+    let mut report = SoakReport::new();
+    report.record(sample(0.0, 40, 100, 7));
+    report.record(sample(60.0, 55, 130, 7));
+    report.record(sample(120.0, 48, 120, 7));
+    assert_eq!(report.samples(), 3);
+    assert_eq!(report.memory_growth(), 8 * MB as i64);
+    assert_eq!(report.handle_growth(), 20);
+    assert!(!report.hotkeys_lost());
+    assert!(report.summary().contains("peak 55.0MB"));
+}
+
+#[test]
+fn report_flags_lost_hotkeys() {
+    let mut report = SoakReport::new();
+    report.record(sample(0.0, 40, 100, 7));
+    report.record(sample(60.0, 40, 100, 0));
+    report.record(sample(120.0, 40, 100, 7));
+    assert!(report.hotkeys_lost());
+    assert!(report.summary().contains("hotkeys were lost"));
+}
+
+#[test]
+fn gui_object_growth_needs_windows_samples() {
+    let mut report = SoakReport::new();
+    report.record(sample(0.0, 40, 100, 7));
+    assert_eq!(report.gui_object_growth(), None);
+
+    let mut report = SoakReport::new();
+    for (t, gui) in [(0.0, 50), (60.0, 65)] {
+        report.record(SoakSample {
+            elapsed_secs: t,
+            gui_objects: Some(gui),
+            ..Default::default()
+        });
+    }
+    assert_eq!(report.gui_object_growth(), Some(15));
+}
+
+#[test]
+fn log_line_marks_missing_metrics() {
+    let line = SoakSample {
+        elapsed_secs: 60.0,
+        memory_bytes: 32 * MB,
+        handles: 12,
+        ..Default::default()
+    }
+    .log_line();
+    assert_eq!(
+        line,
+        "[soak] t=60s mem=32.0MB handles=12 gui=n/a queue=n/a hotkeys=n/a"
+    );
+}
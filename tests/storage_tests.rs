@@ -0,0 +1,166 @@
+//! Tests for the shared preferences file storage.
+
+use std::fs;
+use std::path::PathBuf;
+
+use lumbus::model::constants::*;
+use lumbus::model::{ConfigIssue, ManagedConfig, Preferences, PREFERENCES_FILE};
+use lumbus::storage::{managed_defaults, ConfigFile};
+
+/// Empty folder for one test.
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("lumbus-storage-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn no_import(_: &Preferences) -> Option<Preferences> {
+    None
+}
+
+// === Cache Tests ===
+
+#[test]
+fn missing_file_starts_from_managed_defaults_and_is_written_on_flush() {
+    let dir = temp_dir("defaults");
+    let path = dir.join("Lumbus").join(PREFERENCES_FILE);
+    let mut managed = ManagedConfig::new();
+    managed.set_default(PREF_RADIUS, 30.0);
+    let mut config = ConfigFile::new(path.clone(), no_import);
+
+    assert_eq!(config.get(&managed).radius, 30.0);
+    config.set_number(PREF_BORDER, 5.0, &managed);
+    assert!(!path.exists());
+    config.flush();
+
+    let saved = Preferences::decode(&fs::read_to_string(&path).unwrap(), Preferences::default());
+    assert_eq!(saved.radius, 30.0);
+    assert_eq!(saved.border_width, 5.0);
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn imported_settings_are_saved_on_the_next_flush() {
+    let dir = temp_dir("import");
+    let path = dir.join(PREFERENCES_FILE);
+    let mut config = ConfigFile::new(path.clone(), |base| {
+        let mut imported = base.clone();
+        imported.set_number(PREF_RADIUS, 42.0);
+        Some(imported)
+    });
+
+    assert_eq!(config.get(&ManagedConfig::new()).radius, 42.0);
+    config.flush();
+    assert!(fs::read_to_string(&path).unwrap().contains("radius = 42.0"));
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn locked_settings_are_applied_on_read_and_never_written() {
+    let dir = temp_dir("locked");
+    let mut managed = ManagedConfig::new();
+    managed.lock(PREF_RADIUS, 80.0);
+    let mut config = ConfigFile::new(dir.join(PREFERENCES_FILE), no_import);
+
+    config.set_number(PREF_RADIUS, 60.0, &managed);
+    assert_eq!(config.effective(&managed).radius, 80.0);
+    assert_eq!(config.get(&managed).radius, Preferences::default().radius);
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn edits_on_disk_are_reloaded_with_their_issues() {
+    let dir = temp_dir("reload");
+    let path = dir.join(PREFERENCES_FILE);
+    let managed = ManagedConfig::new();
+    let mut config = ConfigFile::new(path.clone(), no_import);
+    config.set_number(PREF_RADIUS, 60.0, &managed);
+    config.flush();
+
+    // Our own write is not a change
+    assert!(!config.reload(&managed));
+    fs::write(&path, "radius = 70.0\ncolour = 1\n").unwrap();
+    assert!(config.reload(&managed));
+    assert_eq!(config.get(&managed).radius, 70.0);
+    assert_eq!(
+        config.take_issues(&managed),
+        vec![ConfigIssue::UnknownKey {
+            key: "colour".to_string()
+        }]
+    );
+    assert!(config.take_issues(&managed).is_empty());
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn safe_mode_leaves_the_file_alone() {
+    let dir = temp_dir("safe");
+    let path = dir.join(PREFERENCES_FILE);
+    fs::write(&path, "radius = 70.0\n").unwrap();
+    let managed = ManagedConfig::new();
+    let mut config = ConfigFile::new(path.clone(), no_import);
+
+    config.enter_safe_mode(&managed);
+    assert!(config.safe_mode());
+    assert_eq!(config.get(&managed).radius, Preferences::default().radius);
+    config.set_number(PREF_RADIUS, 60.0, &managed);
+    config.flush();
+    fs::write(&path, "radius = 75.0\n").unwrap();
+    assert!(!config.reload(&managed));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "radius = 75.0\n");
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn restore_defaults_uses_the_managed_defaults() {
+    let dir = temp_dir("restore");
+    let mut managed = ManagedConfig::new();
+    managed.set_default(PREF_RADIUS, 30.0);
+    let mut config = ConfigFile::new(dir.join(PREFERENCES_FILE), no_import);
+    config.set_number(PREF_RADIUS, 90.0, &managed);
+
+    config.restore_defaults(&managed);
+    assert_eq!(config.get(&managed).radius, 30.0);
+    assert_eq!(managed_defaults(&managed).radius, 30.0);
+    let _ = fs::remove_dir_all(dir);
+}
+
+// === Sync Tests ===
+
+#[test]
+fn joining_a_sync_folder_adopts_what_is_there() {
+    let dir = temp_dir("sync");
+    let folder = dir.join("Sync");
+    fs::create_dir_all(&folder).unwrap();
+    fs::write(folder.join(PREFERENCES_FILE), "radius = 65.0\n").unwrap();
+    let managed = ManagedConfig::new();
+    let mut config = ConfigFile::new(dir.join(PREFERENCES_FILE), no_import);
+    config.set_number(PREF_RADIUS, 45.0, &managed);
+
+    config.set_sync_folder(Some(&folder), &managed);
+    assert_eq!(config.sync_folder(&managed), Some(folder.clone()));
+    assert_eq!(config.get(&managed).radius, 65.0);
+    let saved = fs::read_to_string(dir.join(PREFERENCES_FILE)).unwrap();
+    assert!(saved.contains("radius = 65.0"));
+
+    config.set_sync_folder(None, &managed);
+    assert_eq!(config.sync_folder(&managed), None);
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn saves_are_copied_to_the_sync_folder() {
+    let dir = temp_dir("sync-save");
+    let folder = dir.join("Sync");
+    fs::create_dir_all(&folder).unwrap();
+    let managed = ManagedConfig::new();
+    let mut config = ConfigFile::new(dir.join(PREFERENCES_FILE), no_import);
+    config.set_sync_folder(Some(&folder), &managed);
+
+    config.set_number(PREF_RADIUS, 55.0, &managed);
+    config.flush();
+    let copy = fs::read_to_string(folder.join(PREFERENCES_FILE)).unwrap();
+    assert!(copy.contains("radius = 55.0"));
+    let _ = fs::remove_dir_all(dir);
+}
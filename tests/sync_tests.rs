@@ -0,0 +1,110 @@
+//! Tests for merging the synced copy of the settings.
+
+use lumbus::model::constants::*;
+use lumbus::model::{merge_synced, synced_copy, Preferences, SyncConflict, SyncSide};
+
+fn prefs(radius: f64, halo: bool) -> Preferences {
+    Preferences {
+        radius,
+        halo,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn unchanged_copy_keeps_local_settings() {
+    let base = prefs(30.0, false);
+    let local = prefs(40.0, false);
+    let (merged, conflicts) = merge_synced(&base, &local, 10.0, &base, 20.0);
+    assert_eq!(merged, local);
+    assert!(conflicts.is_empty());
+}
+
+#[test]
+fn changes_on_different_keys_are_combined() {
+    let base = prefs(30.0, false);
+    let local = prefs(40.0, false);
+    let remote = prefs(30.0, true);
+    let (merged, conflicts) = merge_synced(&base, &local, 20.0, &remote, 10.0);
+    assert_eq!(merged, prefs(40.0, true));
+    assert!(conflicts.is_empty());
+}
+
+#[test]
+fn same_change_on_both_sides_is_not_a_conflict() {
+    let base = prefs(30.0, false);
+    let both = prefs(60.0, false);
+    let (merged, conflicts) = merge_synced(&base, &both, 10.0, &both, 20.0);
+    assert_eq!(merged, both);
+    assert!(conflicts.is_empty());
+}
+
+#[test]
+fn the_language_is_synced_by_its_locale_tag() {
+    let base = Preferences::default();
+    // A language the other machine has resources for, and this one not
+    let remote = Preferences {
+        lang: "xx".to_string(),
+        ..Default::default()
+    };
+    let (merged, conflicts) = merge_synced(&base, &base, 10.0, &remote, 20.0);
+    assert_eq!(merged.lang, "xx");
+    assert!(conflicts.is_empty());
+}
+
+#[test]
+fn conflicts_go_to_the_side_written_last() {
+    let base = prefs(30.0, false);
+    let local = prefs(40.0, false);
+    let remote = prefs(60.0, false);
+
+    let (merged, conflicts) = merge_synced(&base, &local, 10.0, &remote, 20.0);
+    assert_eq!(merged.radius, 60.0);
+    assert_eq!(
+        conflicts,
+        vec![SyncConflict {
+            key: PREF_RADIUS.to_string(),
+            local: "40".to_string(),
+            remote: "60".to_string(),
+            kept: SyncSide::Remote,
+        }]
+    );
+
+    let (merged, conflicts) = merge_synced(&base, &local, 20.0, &remote, 20.0);
+    assert_eq!(merged.radius, 40.0);
+    assert_eq!(conflicts[0].kept, SyncSide::Local);
+}
+
+#[test]
+fn machine_settings_are_not_synced() {
+    let local = Preferences {
+        session: "help=1".to_string(),
+        sync_folder: "/sync".to_string(),
+        ..Default::default()
+    };
+    let copy = synced_copy(&local);
+    assert!(copy.session.is_empty());
+    assert!(copy.sync_folder.is_empty());
+
+    let remote = Preferences {
+        session: "help=0".to_string(),
+        ..Default::default()
+    };
+    let (merged, _) = merge_synced(&copy, &local, 10.0, &remote, 20.0);
+    assert_eq!(merged.session, "help=1");
+    assert_eq!(merged.sync_folder, "/sync");
+}
+
+#[test]
+fn conflicts_describe_both_values() {
+    let conflict = SyncConflict {
+        key: PREF_RADIUS.to_string(),
+        local: "40".to_string(),
+        remote: "60".to_string(),
+        kept: SyncSide::Remote,
+    };
+    assert_eq!(
+        conflict.to_string(),
+        "`radius`: 40 here, 60 in the synced copy; kept 60 (written last)"
+    );
+}
@@ -0,0 +1,51 @@
+//! Tests for the refresh timer watchdog.
+
+use lumbus::model::constants::{TIMER_WATCHDOG_CHECK_SECS, TIMER_WATCHDOG_SECS};
+use lumbus::model::TimerWatchdog;
+
+#[test]
+fn a_running_timer_is_left_alone() {
+    let mut watchdog = TimerWatchdog::new();
+    let mut now = 0.0;
+    for _ in 0..10 {
+        watchdog.tick(now);
+        now += TIMER_WATCHDOG_CHECK_SECS;
+        assert!(!watchdog.check(now));
+    }
+    assert_eq!(watchdog.restarts(), 0);
+}
+
+#[test]
+fn a_stalled_timer_is_restarted_once_per_grace_period() {
+    let mut watchdog = TimerWatchdog::new();
+    watchdog.tick(0.0);
+    let mut now = 0.0;
+    let mut restarts = 0;
+    while now < TIMER_WATCHDOG_SECS * 2.0 {
+        now += TIMER_WATCHDOG_CHECK_SECS;
+        if watchdog.check(now) {
+            restarts += 1;
+        }
+    }
+    assert_eq!(restarts, 2);
+    assert_eq!(watchdog.restarts(), 2);
+}
+
+#[test]
+fn a_late_check_starts_over() {
+    // Both timers were held up, e.g. while the Mac slept
+    let mut watchdog = TimerWatchdog::new();
+    watchdog.tick(0.0);
+    assert!(!watchdog.check(TIMER_WATCHDOG_CHECK_SECS));
+    assert!(!watchdog.check(100.0));
+    watchdog.tick(100.0 + TIMER_WATCHDOG_CHECK_SECS);
+    assert!(!watchdog.check(100.0 + TIMER_WATCHDOG_CHECK_SECS));
+    assert_eq!(watchdog.restarts(), 0);
+}
+
+#[test]
+fn no_tick_yet_counts_from_the_first_check() {
+    let mut watchdog = TimerWatchdog::new();
+    assert!(!watchdog.check(5.0));
+    assert!(!watchdog.check(5.0 + TIMER_WATCHDOG_CHECK_SECS));
+}
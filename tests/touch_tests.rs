@@ -0,0 +1,83 @@
+//! Tests for the touch point rings.
+
+use lumbus::model::constants::TOUCH_FADE_SECS;
+use lumbus::model::pen::is_touch_extra_info;
+use lumbus::model::touch::{TouchRing, Touches};
+
+#[test]
+fn each_finger_gets_a_ring() {
+    let mut touches = Touches::new();
+    touches.down(1, 10.0, 20.0);
+    touches.down(2, 30.0, 40.0);
+    assert_eq!(
+        touches.rings(0.0),
+        vec![
+            TouchRing {
+                x: 10.0,
+                y: 20.0,
+                alpha: 1.0
+            },
+            TouchRing {
+                x: 30.0,
+                y: 40.0,
+                alpha: 1.0
+            },
+        ]
+    );
+}
+
+#[test]
+fn rings_follow_their_finger() {
+    let mut touches = Touches::new();
+    touches.down(1, 10.0, 20.0);
+    touches.down(2, 30.0, 40.0);
+    touches.moved(2, 35.0, 45.0);
+    let rings = touches.rings(0.0);
+    assert_eq!((rings[0].x, rings[0].y), (10.0, 20.0));
+    assert_eq!((rings[1].x, rings[1].y), (35.0, 45.0));
+}
+
+#[test]
+fn lifted_rings_fade_out() {
+    let mut touches = Touches::new();
+    touches.down(1, 10.0, 20.0);
+    touches.up(1, 0.0);
+    assert_eq!(touches.rings(0.0)[0].alpha, 1.0);
+    assert_eq!(touches.rings(TOUCH_FADE_SECS / 2.0)[0].alpha, 0.5);
+    assert!(touches.rings(TOUCH_FADE_SECS).is_empty());
+    assert!(touches.is_empty());
+}
+
+#[test]
+fn a_lifted_finger_does_not_move() {
+    let mut touches = Touches::new();
+    touches.down(1, 10.0, 20.0);
+    touches.up(1, 0.0);
+    touches.moved(1, 50.0, 50.0);
+    let ring = touches.rings(0.0)[0];
+    assert_eq!((ring.x, ring.y), (10.0, 20.0));
+}
+
+#[test]
+fn a_finger_down_again_replaces_its_fading_ring() {
+    let mut touches = Touches::new();
+    touches.down(1, 10.0, 20.0);
+    touches.up(1, 0.0);
+    touches.down(1, 50.0, 60.0);
+    assert_eq!(
+        touches.rings(0.1),
+        vec![TouchRing {
+            x: 50.0,
+            y: 60.0,
+            alpha: 1.0
+        }]
+    );
+}
+
+#[test]
+fn touch_messages_are_told_from_pen_and_mouse() {
+    assert!(is_touch_extra_info(0xFF51_5780));
+    assert!(is_touch_extra_info(0xFF51_5781));
+    assert!(!is_touch_extra_info(0xFF51_5700));
+    assert!(!is_touch_extra_info(0));
+}
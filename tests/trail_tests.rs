@@ -0,0 +1,102 @@
+//! Tests for the laser-pointer trail buffer.
+
+use lumbus::model::constants::*;
+use lumbus::model::trail::{laser_dot_radius, Trail};
+
+fn approx_eq(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-6
+}
+
+// === Buffer Tests ===
+
+#[test]
+fn new_trail_is_empty() {
+    let trail = Trail::default();
+    assert!(trail.is_empty());
+    assert!(trail.segments(0.0).is_empty());
+}
+
+#[test]
+fn push_ignores_repeated_position() {
+    let mut trail = Trail::new(1.0, 8);
+    assert!(trail.push(10.0, 10.0, 0.0));
+    assert!(!trail.push(10.0, 10.0, 0.1));
+    assert_eq!(trail.len(), 1);
+}
+
+#[test]
+fn push_respects_capacity() {
+    let mut trail = Trail::new(10.0, 4);
+    for i in 0..10 {
+        trail.push(i as f64, 0.0, i as f64 * 0.01);
+    }
+    assert_eq!(trail.len(), 4);
+}
+
+#[test]
+fn prune_drops_expired_points() {
+    let mut trail = Trail::new(0.5, 8);
+    trail.push(0.0, 0.0, 0.0);
+    trail.push(1.0, 0.0, 0.4);
+    assert!(trail.prune(0.6));
+    assert_eq!(trail.len(), 1);
+    assert!(!trail.prune(0.6));
+}
+
+#[test]
+fn clear_empties_buffer() {
+    let mut trail = Trail::default();
+    trail.push(0.0, 0.0, 0.0);
+    trail.push(1.0, 1.0, 0.01);
+    trail.clear();
+    assert!(trail.is_empty());
+}
+
+// === Segment Tests ===
+
+#[test]
+fn segments_connect_consecutive_points() {
+    let mut trail = Trail::new(1.0, 8);
+    trail.push(0.0, 0.0, 0.0);
+    trail.push(5.0, 0.0, 0.0);
+    trail.push(5.0, 5.0, 0.0);
+    let segs = trail.segments(0.0);
+    assert_eq!(segs.len(), 2);
+    assert!(approx_eq(segs[0].x1, segs[1].x0));
+    assert!(approx_eq(segs[0].y1, segs[1].y0));
+}
+
+#[test]
+fn segment_alpha_fades_towards_tail() {
+    let mut trail = Trail::new(1.0, 8);
+    trail.push(0.0, 0.0, 0.0);
+    trail.push(1.0, 0.0, 0.2);
+    trail.push(2.0, 0.0, 0.4);
+    let segs = trail.segments(0.4);
+    assert!(segs[0].alpha < segs[1].alpha);
+    assert!(segs.iter().all(|s| s.alpha > 0.0 && s.alpha <= 1.0));
+}
+
+#[test]
+fn fresh_segment_is_fully_opaque() {
+    let mut trail = Trail::new(1.0, 8);
+    trail.push(0.0, 0.0, 1.0);
+    trail.push(1.0, 0.0, 1.0);
+    let segs = trail.segments(1.0);
+    assert!(approx_eq(segs[0].alpha, 1.0));
+}
+
+// === Dot Size Tests ===
+
+#[test]
+fn laser_dot_scales_with_radius() {
+    assert!(approx_eq(laser_dot_radius(100.0), 100.0 * LASER_DOT_SCALE));
+}
+
+#[test]
+fn laser_dot_has_minimum_size() {
+    assert!(approx_eq(
+        laser_dot_radius(MIN_RADIUS),
+        MIN_LASER_DOT_RADIUS
+    ));
+}
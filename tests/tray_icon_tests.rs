@@ -0,0 +1,79 @@
+//! Tests for the tray icon states.
+
+use lumbus::model::constants::TRAY_ICON_HIDDEN_ALPHA;
+use lumbus::model::{tray_icon_pixels, TrayIconState};
+
+const SIZE: usize = 16;
+
+/// Opaque white icon, 32-bit BGRA.
+fn white_icon() -> Vec<u8> {
+    vec![255; SIZE * SIZE * 4]
+}
+
+fn pixel(pixels: &[u8], x: usize, y: usize) -> &[u8] {
+    let i = (y * SIZE + x) * 4;
+    &pixels[i..i + 4]
+}
+
+fn state(visible: bool, locked: bool) -> TrayIconState {
+    TrayIconState { visible, locked }
+}
+
+#[test]
+fn shown_icon_is_unchanged() {
+    let mut pixels = white_icon();
+    tray_icon_pixels(&mut pixels, SIZE, state(true, false));
+    assert_eq!(pixels, white_icon());
+}
+
+#[test]
+fn hidden_icon_is_faded() {
+    let mut pixels = white_icon();
+    tray_icon_pixels(&mut pixels, SIZE, state(false, false));
+    let alpha = (255.0 * TRAY_ICON_HIDDEN_ALPHA).round() as u8;
+    assert!(pixels.chunks(4).all(|p| p == [255, 255, 255, alpha]));
+}
+
+#[test]
+fn lock_badge_covers_the_bottom_right_corner_only() {
+    let mut pixels = white_icon();
+    tray_icon_pixels(&mut pixels, SIZE, state(true, true));
+    // Badge color (amber) in the corner, icon untouched elsewhere
+    let corner = pixel(&pixels, SIZE - 3, SIZE - 3);
+    assert_eq!(corner[3], 255);
+    assert!(corner[2] > corner[0], "red over blue: {corner:?}");
+    assert_eq!(pixel(&pixels, 0, 0), [255, 255, 255, 255]);
+    assert_eq!(pixel(&pixels, SIZE - 3, 0), [255, 255, 255, 255]);
+}
+
+#[test]
+fn lock_badge_stays_opaque_on_the_hidden_icon() {
+    let mut pixels = white_icon();
+    tray_icon_pixels(&mut pixels, SIZE, state(false, true));
+    assert_eq!(pixel(&pixels, SIZE - 3, SIZE - 3)[3], 255);
+    assert!(pixel(&pixels, 0, 0)[3] < 255);
+}
+
+#[test]
+fn lock_badge_shows_on_transparent_pixels() {
+    let mut pixels = vec![0; SIZE * SIZE * 4];
+    tray_icon_pixels(&mut pixels, SIZE, state(true, true));
+    assert_eq!(pixel(&pixels, SIZE - 3, SIZE - 3)[3], 255);
+    assert_eq!(pixel(&pixels, 0, 0), [0, 0, 0, 0]);
+}
+
+#[test]
+fn every_state_has_its_own_index() {
+    let mut indices: Vec<usize> = [(false, false), (true, false), (false, true), (true, true)]
+        .into_iter()
+        .map(|(visible, locked)| state(visible, locked).index())
+        .collect();
+    indices.sort();
+    assert_eq!(indices, (0..TrayIconState::COUNT).collect::<Vec<_>>());
+}
+
+#[test]
+fn label_follows_visibility() {
+    assert_eq!(state(true, true).label(), "Highlight shown");
+    assert_eq!(state(false, false).label(), "Highlight hidden");
+}
@@ -0,0 +1,99 @@
+//! Tests for the update check.
+
+use lumbus::model::constants::*;
+use lumbus::model::{
+    check_due, parse_release, parse_version, synced_copy, update_status, Preferences, Release,
+    UpdateStatus,
+};
+
+fn feed(tag: &str) -> String {
+    format!(
+        r#"{{"tag_name":"{tag}","html_url":"https://github.com/restevean/lumbus/releases/tag/{tag}","draft":false,"prerelease":false,"assets":[]}}"#
+    )
+}
+
+#[test]
+fn versions_parse_with_or_without_prefix() {
+    assert_eq!(parse_version("v1.2.3"), Some((1, 2, 3)));
+    assert_eq!(parse_version("0.6.0"), Some((0, 6, 0)));
+    assert_eq!(parse_version("2.1"), Some((2, 1, 0)));
+    assert_eq!(parse_version("1.0.0-beta.1"), Some((1, 0, 0)));
+    assert_eq!(parse_version("latest"), None);
+    assert_eq!(parse_version("1.2.3.4"), None);
+}
+
+#[test]
+fn release_is_read_from_the_feed() {
+    let release = parse_release(&feed("v0.7.0")).unwrap();
+    assert_eq!(
+        release,
+        Release {
+            version: "0.7.0".into(),
+            url: "https://github.com/restevean/lumbus/releases/tag/v0.7.0".into(),
+        }
+    );
+    assert!(parse_release("<html>rate limited</html>").is_err());
+    assert!(parse_release(r#"{"message":"Not Found"}"#).is_err());
+}
+
+#[test]
+fn prereleases_are_not_offered() {
+    let json = feed("v9.0.0").replace(r#""prerelease":false"#, r#""prerelease":true"#);
+    assert!(parse_release(&json).is_err());
+}
+
+#[test]
+fn newer_release_is_available() {
+    match update_status("0.5.0", Ok(feed("v0.6.0"))) {
+        UpdateStatus::Available(release) => assert_eq!(release.version, "0.6.0"),
+        other => panic!("unexpected {other:?}"),
+    }
+    assert_eq!(
+        update_status("0.6.0", Ok(feed("v0.6.0"))),
+        UpdateStatus::UpToDate
+    );
+    // A development build ahead of the latest release
+    assert_eq!(
+        update_status("0.7.0", Ok(feed("v0.6.0"))),
+        UpdateStatus::UpToDate
+    );
+}
+
+#[test]
+fn failed_fetch_is_reported() {
+    assert_eq!(
+        update_status("0.5.0", Err("HTTP 403".into())),
+        UpdateStatus::Failed("HTTP 403".into())
+    );
+    assert!(matches!(
+        update_status("0.5.0", Ok(feed("nightly"))),
+        UpdateStatus::Failed(_)
+    ));
+}
+
+#[test]
+fn check_is_due_once_a_day() {
+    let last = 1_700_000_000.0;
+    assert!(check_due(true, 0.0, last));
+    assert!(!check_due(true, last, last + 60.0));
+    assert!(check_due(true, last, last + UPDATE_CHECK_INTERVAL_SECS));
+    // Clock set back: check again rather than wait
+    assert!(check_due(true, last, last - 60.0));
+    assert!(!check_due(false, 0.0, last));
+}
+
+#[test]
+fn update_check_is_on_by_default_and_not_synced() {
+    let prefs = Preferences::default();
+    assert!(prefs.update_check);
+    assert_eq!(prefs.number(PREF_UPDATE_CHECK), Some(1.0));
+
+    let mut prefs = Preferences {
+        last_update_check: 1_700_000_000.0,
+        ..Default::default()
+    };
+    assert!(prefs.set_number(PREF_UPDATE_CHECK, 0.0));
+    assert!(!prefs.update_check);
+    assert_eq!(synced_copy(&prefs).last_update_check, 0.0);
+    assert!(!synced_copy(&prefs).update_check);
+}
@@ -0,0 +1,144 @@
+//! Tests for `lumbus://` URLs.
+
+use lumbus::events::AppEvent;
+use lumbus::model::{url_events, DisplayMode, UrlError};
+
+#[test]
+fn toggle_url() {
+    assert_eq!(
+        url_events("lumbus://toggle"),
+        Ok(vec![AppEvent::ToggleOverlay])
+    );
+    assert_eq!(
+        url_events("LUMBUS://Toggle/"),
+        Ok(vec![AppEvent::ToggleOverlay])
+    );
+}
+
+#[test]
+fn restart_url() {
+    assert_eq!(url_events("lumbus://restart"), Ok(vec![AppEvent::Restart]));
+}
+
+#[test]
+fn profile_url_selects_a_known_profile() {
+    assert_eq!(
+        url_events("lumbus://profile/presentation"),
+        Ok(vec![AppEvent::LoadProfile("presentation".into())])
+    );
+    assert_eq!(
+        url_events("lumbus://profile/demo"),
+        Err(UrlError::UnknownProfile("demo".into()))
+    );
+}
+
+#[test]
+fn color_url_takes_any_color_format() {
+    let orange = AppEvent::SetColor {
+        r: 1.0,
+        g: 136.0 / 255.0,
+        b: 0.0,
+        a: 1.0,
+    };
+    assert_eq!(
+        url_events("lumbus://color/ff8800"),
+        Ok(vec![orange.clone()])
+    );
+    assert_eq!(url_events("lumbus://color/%23FF8800"), Ok(vec![orange]));
+    assert_eq!(
+        url_events("lumbus://color/rgba(0,%200,%20255,%200.5)"),
+        Ok(vec![AppEvent::SetColor {
+            r: 0.0,
+            g: 0.0,
+            b: 1.0,
+            a: 0.5
+        }])
+    );
+    assert_eq!(
+        url_events("lumbus://color/blurple"),
+        Err(UrlError::BadColor("blurple".into()))
+    );
+}
+
+#[test]
+fn color_url_refuses_what_is_not_hex() {
+    assert_eq!(
+        url_events("lumbus://color/a%C3%A9bcd"),
+        Err(UrlError::BadColor("aébcd".into()))
+    );
+    assert_eq!(
+        url_events("lumbus://color/%2Bf0000"),
+        Err(UrlError::BadColor("+f0000".into()))
+    );
+}
+
+#[test]
+fn mode_url_shows_a_letter_or_the_circle() {
+    assert_eq!(
+        url_events("lumbus://mode/Left"),
+        Ok(vec![AppEvent::SetMode(DisplayMode::Left)])
+    );
+    assert_eq!(
+        url_events("lumbus://mode/circle"),
+        Ok(vec![AppEvent::SetMode(DisplayMode::Circle)])
+    );
+    assert_eq!(
+        url_events("lumbus://mode/middle"),
+        Err(UrlError::UnknownMode("middle".into()))
+    );
+}
+
+#[test]
+fn malformed_escapes_are_kept() {
+    assert_eq!(
+        url_events("lumbus://color/%+1"),
+        Err(UrlError::BadColor("%+1".into()))
+    );
+    assert_eq!(
+        url_events("lumbus://color/%1"),
+        Err(UrlError::BadColor("%1".into()))
+    );
+}
+
+#[test]
+fn set_url_sets_each_numeric_setting() {
+    assert_eq!(
+        url_events("lumbus://set?radius=60&borderWidth=4"),
+        Ok(vec![
+            AppEvent::SetPreference("radius".into(), 60.0),
+            AppEvent::SetPreference("borderWidth".into(), 4.0),
+        ])
+    );
+}
+
+#[test]
+fn set_url_rejects_unknown_keys_and_bad_values() {
+    assert_eq!(
+        url_events("lumbus://set?radius=60&colour=3"),
+        Err(UrlError::BadSetting("colour=3".into()))
+    );
+    assert_eq!(
+        url_events("lumbus://set?radius=big"),
+        Err(UrlError::BadSetting("radius=big".into()))
+    );
+    // String settings cannot be set through URLs
+    assert!(url_events("lumbus://set?session=x").is_err());
+    assert!(url_events("lumbus://set").is_err());
+}
+
+#[test]
+fn other_urls_are_rejected() {
+    assert_eq!(url_events("https://toggle"), Err(UrlError::WrongScheme));
+    assert_eq!(
+        url_events("lumbus://explode"),
+        Err(UrlError::UnknownCommand("explode".into()))
+    );
+}
+
+#[test]
+fn errors_describe_the_problem() {
+    assert_eq!(
+        UrlError::UnknownProfile("demo".into()).to_string(),
+        "unknown hotkey profile `demo`"
+    );
+}
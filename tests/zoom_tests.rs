@@ -0,0 +1,105 @@
+//! Tests for the zoom-follow magnification model.
+
+use lumbus::model::constants::*;
+use lumbus::model::placement::ScreenRect;
+use lumbus::model::zoom::{source_rect, to_zoomed, Zoom};
+
+fn approx_eq(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-6
+}
+
+// === Level Tests ===
+
+#[test]
+fn new_zoom_is_inactive() {
+    let z = Zoom::new();
+    assert!(!z.is_active());
+    assert!(approx_eq(z.level(), 1.0));
+    assert!(z.focus().is_none());
+}
+
+#[test]
+fn zoom_in_steps_up_to_maximum() {
+    let mut z = Zoom::new();
+    for _ in 1..ZOOM_LEVELS.len() {
+        assert!(z.zoom_in());
+    }
+    assert!(!z.zoom_in());
+    assert!(approx_eq(z.level(), ZOOM_LEVELS[ZOOM_LEVELS.len() - 1]));
+}
+
+#[test]
+fn zoom_out_to_off_forgets_focus() {
+    let mut z = Zoom::new();
+    z.zoom_in();
+    z.follow(10.0, 20.0, 0.0);
+    assert!(z.zoom_out());
+    assert!(!z.is_active());
+    assert!(z.focus().is_none());
+    assert!(!z.zoom_out());
+}
+
+// === Follow Tests ===
+
+#[test]
+fn first_follow_snaps_to_cursor() {
+    let mut z = Zoom::new();
+    z.zoom_in();
+    let (fx, fy) = z.follow(300.0, 200.0, 1.0);
+    assert!(approx_eq(fx, 300.0) && approx_eq(fy, 200.0));
+}
+
+#[test]
+fn follow_eases_towards_cursor() {
+    let mut z = Zoom::new();
+    z.zoom_in();
+    z.follow(0.0, 0.0, 0.0);
+    let (fx, _) = z.follow(100.0, 0.0, 1.0 / 60.0);
+    assert!(fx > 0.0 && fx < 100.0);
+
+    let mut last = fx;
+    for i in 2..120 {
+        last = z.follow(100.0, 0.0, i as f64 / 60.0).0;
+    }
+    assert!((last - 100.0).abs() < 0.01);
+}
+
+#[test]
+fn follow_ignores_non_increasing_time() {
+    let mut z = Zoom::new();
+    z.follow(50.0, 50.0, 1.0);
+    let (fx, fy) = z.follow(500.0, 500.0, 1.0);
+    assert!(approx_eq(fx, 50.0) && approx_eq(fy, 50.0));
+}
+
+// === Mapping Tests ===
+
+#[test]
+fn source_rect_shrinks_by_level_around_focus() {
+    let screen = ScreenRect::new(0.0, 0.0, 1920.0, 1080.0);
+    let src = source_rect(&screen, 2.0, (960.0, 540.0));
+    assert!(approx_eq(src.x, 480.0) && approx_eq(src.y, 270.0));
+    assert!(approx_eq(src.w, 960.0) && approx_eq(src.h, 540.0));
+}
+
+#[test]
+fn source_rect_stays_within_screen() {
+    let screen = ScreenRect::new(1920.0, 0.0, 1280.0, 800.0);
+    for &focus in &[(1920.0, 0.0), (3200.0, 800.0), (5000.0, -100.0)] {
+        let src = source_rect(&screen, 4.0, focus);
+        assert!(src.x >= screen.x - 1e-9 && src.y >= screen.y - 1e-9);
+        assert!(src.x + src.w <= screen.x + screen.w + 1e-9);
+        assert!(src.y + src.h <= screen.y + screen.h + 1e-9);
+    }
+}
+
+#[test]
+fn focus_is_a_fixed_point_of_the_mapping() {
+    let screen = ScreenRect::new(0.0, 0.0, 1920.0, 1080.0);
+    let (x, y) = to_zoomed(&screen, 3.0, (700.0, 300.0), 700.0, 300.0);
+    assert!(approx_eq(x, 700.0) && approx_eq(y, 300.0));
+
+    let src = source_rect(&screen, 3.0, (700.0, 300.0));
+    let (x, y) = to_zoomed(&screen, 3.0, (700.0, 300.0), src.x, src.y);
+    assert!(approx_eq(x, 0.0) && approx_eq(y, 0.0));
+}